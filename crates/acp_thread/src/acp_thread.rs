@@ -26,6 +26,17 @@ pub fn subagent_session_id_from_meta(meta: &Option<acp::Meta>) -> Option<acp::Se
         .map(|s| acp::SessionId::from(s.to_string()))
 }
 
+/// Key used in the meta of a native agent session to store the session it was forked from.
+pub const FORKED_FROM_SESSION_ID_META_KEY: &str = "forked_from_session_id";
+
+/// Helper to extract the session a session was forked from, from ACP meta
+pub fn forked_from_session_id_from_meta(meta: &Option<acp::Meta>) -> Option<acp::SessionId> {
+    meta.as_ref()
+        .and_then(|m| m.get(FORKED_FROM_SESSION_ID_META_KEY))
+        .and_then(|v| v.as_str())
+        .map(|s| acp::SessionId::from(s.to_string()))
+}
+
 /// Helper to create meta with tool name
 pub fn meta_with_tool_name(tool_name: &str) -> acp::Meta {
     acp::Meta::from_iter([(TOOL_NAME_META_KEY.into(), tool_name.into())])
@@ -2114,6 +2125,18 @@ impl AcpThread {
         })
     }
 
+    /// Returns the git checkpoint taken before the given user message was sent.
+    pub fn checkpoint(&self, id: &UserMessageId) -> Option<GitStoreCheckpoint> {
+        self.entries.iter().find_map(|entry| {
+            let message = entry.user_message()?;
+            if message.id.as_ref() == Some(id) {
+                Some(message.checkpoint.as_ref()?.git_checkpoint.clone())
+            } else {
+                None
+            }
+        })
+    }
+
     /// Rewinds this thread to before the entry at `index`, removing it and all
     /// subsequent entries while rejecting any action_log changes made from that point.
     /// Unlike `restore_checkpoint`, this method does not restore from git.
@@ -2829,6 +2852,7 @@ mod tests {
                     cx,
                     vec![],
                     PathStyle::local(),
+                    None,
                 )
             })
             .await
@@ -60,6 +60,11 @@ pub enum MentionUri {
     GitDiff {
         base_ref: String,
     },
+    McpResource {
+        server_id: String,
+        uri: String,
+        name: String,
+    },
 }
 
 impl MentionUri {
@@ -215,6 +220,21 @@ impl MentionUri {
                     let base_ref =
                         single_query_param(&url, "base")?.unwrap_or_else(|| "main".to_string());
                     Ok(Self::GitDiff { base_ref })
+                } else if let Some(server_id) = path.strip_prefix("/agent/mcp-resource/") {
+                    let mut uri = None;
+                    let mut name = None;
+                    for (key, value) in url.query_pairs() {
+                        match key.as_ref() {
+                            "uri" => uri = Some(value.into_owned()),
+                            "name" => name = Some(value.into_owned()),
+                            _ => bail!("invalid query parameter"),
+                        }
+                    }
+                    Ok(Self::McpResource {
+                        server_id: decode(server_id)?.into_owned(),
+                        uri: uri.context("Missing uri for MCP resource")?,
+                        name: name.context("Missing name for MCP resource")?,
+                    })
                 } else {
                     bail!("invalid zed url: {:?}", input);
                 }
@@ -245,6 +265,7 @@ impl MentionUri {
                 }
             }
             MentionUri::GitDiff { base_ref } => format!("Branch Diff ({})", base_ref),
+            MentionUri::McpResource { name, .. } => name.clone(),
             MentionUri::Selection {
                 abs_path: path,
                 line_range,
@@ -271,6 +292,7 @@ impl MentionUri {
             MentionUri::Selection { .. } => IconName::Reader.path().into(),
             MentionUri::Fetch { .. } => IconName::ToolWeb.path().into(),
             MentionUri::GitDiff { .. } => IconName::GitBranch.path().into(),
+            MentionUri::McpResource { .. } => IconName::ToolHammer.path().into(),
         }
     }
 
@@ -374,6 +396,18 @@ impl MentionUri {
                 url.query_pairs_mut().append_pair("base", base_ref);
                 url
             }
+            MentionUri::McpResource {
+                server_id,
+                uri,
+                name,
+            } => {
+                let mut url = Url::parse("zed:///").unwrap();
+                url.set_path(&format!("/agent/mcp-resource/{server_id}"));
+                url.query_pairs_mut()
+                    .append_pair("uri", uri)
+                    .append_pair("name", name);
+                url
+            }
         }
     }
 }
@@ -697,4 +731,29 @@ mod tests {
         let parsed_single = MentionUri::parse(single_line_uri, PathStyle::local()).unwrap();
         assert_eq!(parsed_single.name(), "Terminal (1 line)");
     }
+
+    #[test]
+    fn test_parse_mcp_resource_uri() {
+        let resource_uri =
+            "zed:///agent/mcp-resource/tickets?uri=ticket%3A%2F%2F123&name=Login+is+slow";
+        let parsed = MentionUri::parse(resource_uri, PathStyle::local()).unwrap();
+        match &parsed {
+            MentionUri::McpResource {
+                server_id,
+                uri,
+                name,
+            } => {
+                assert_eq!(server_id, "tickets");
+                assert_eq!(uri, "ticket://123");
+                assert_eq!(name, "Login is slow");
+            }
+            _ => panic!("Expected McpResource variant"),
+        }
+        assert_eq!(parsed.to_uri().to_string(), resource_uri);
+        assert_eq!(parsed.name(), "Login is slow");
+
+        assert!(
+            MentionUri::parse("zed:///agent/mcp-resource/tickets", PathStyle::local()).is_err()
+        );
+    }
 }
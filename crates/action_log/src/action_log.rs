@@ -7,7 +7,7 @@ use gpui::{
     App, AppContext, AsyncApp, Context, Entity, SharedString, Subscription, Task, WeakEntity,
 };
 use language::{Anchor, Buffer, BufferEvent, Point, ToOffset, ToPoint};
-use project::{Project, ProjectItem, lsp_store::OpenLspBufferHandle};
+use project::{Project, ProjectItem, ProjectTransaction, lsp_store::OpenLspBufferHandle};
 use std::{cmp, ops::Range, sync::Arc};
 use text::{Edit, Patch, Rope};
 use util::{RangeExt, ResultExt as _};
@@ -301,13 +301,37 @@ impl ActionLog {
                 let unreviewed_edits = tracked_buffer.unreviewed_edits.clone();
                 let edits = diff_snapshots(&old_snapshot, &new_snapshot);
                 async move {
-                    if let ChangeAuthor::User = author {
-                        apply_non_conflicting_edits(
-                            &unreviewed_edits,
-                            edits,
-                            &mut base_text,
-                            new_snapshot.as_rope(),
-                        );
+                    match author {
+                        ChangeAuthor::User => {
+                            apply_non_conflicting_edits(
+                                &unreviewed_edits,
+                                edits,
+                                &mut base_text,
+                                new_snapshot.as_rope(),
+                            );
+                        }
+                        ChangeAuthor::Agent => {}
+                        ChangeAuthor::AgentTransaction { start, end } => {
+                            // The transaction's edits were taken for the user's and kept when
+                            // they were made, so revert them in the diff base. This is only
+                            // exact while nothing else edited the buffer since.
+                            if old_snapshot.version() == &end {
+                                let text_before = old_snapshot.rope_for_version(&start);
+                                let reverted_edits = language::line_diff(
+                                    &old_snapshot.text(),
+                                    &text_before.to_string(),
+                                )
+                                .into_iter()
+                                .map(|(old, new)| Edit { old, new })
+                                .collect();
+                                apply_non_conflicting_edits(
+                                    &unreviewed_edits,
+                                    reverted_edits,
+                                    &mut base_text,
+                                    &text_before,
+                                );
+                            }
+                        }
                     }
 
                     (Arc::from(base_text.to_string().as_str()), base_text)
@@ -516,6 +540,61 @@ impl ActionLog {
         tracked_buffer.schedule_diff_update(ChangeAuthor::Agent, cx);
     }
 
+    /// Tracks the edits of a project transaction the agent applies, like a language server's
+    /// rename or code action. Its edits are made before the buffers they touch are known, so
+    /// they are attributed to the agent once the transaction completes: buffers that weren't
+    /// tracked yet are diffed against their text before the transaction, and the transaction's
+    /// edits to tracked buffers are taken back from the user.
+    pub fn track_project_transaction(
+        &mut self,
+        transaction: Task<Result<ProjectTransaction>>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<ProjectTransaction>> {
+        cx.spawn(async move |this, cx| {
+            let transaction = transaction.await?;
+            this.update(cx, |this, cx| {
+                for (buffer, buffer_transaction) in &transaction.0 {
+                    this.project_transaction_applied(buffer, buffer_transaction, cx);
+                }
+            })?;
+            Ok(transaction)
+        })
+    }
+
+    fn project_transaction_applied(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        transaction: &language::Transaction,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.tracked_buffers.contains_key(buffer) {
+            let base_text = buffer
+                .read(cx)
+                .text_snapshot()
+                .rope_for_version(&transaction.start);
+            self.track_buffer_internal(buffer.clone(), false, cx)
+                .diff_base = base_text;
+            self.buffer_edited(buffer.clone(), cx);
+            return;
+        }
+
+        let mut end = transaction.start.clone();
+        for edit_id in &transaction.edit_ids {
+            end.observe(*edit_id);
+        }
+        let tracked_buffer = self.track_buffer_internal(buffer.clone(), false, cx);
+        if let TrackedBufferStatus::Deleted = tracked_buffer.status {
+            tracked_buffer.status = TrackedBufferStatus::Modified;
+        }
+        tracked_buffer.schedule_diff_update(
+            ChangeAuthor::AgentTransaction {
+                start: transaction.start.clone(),
+                end,
+            },
+            cx,
+        );
+    }
+
     pub fn will_delete_buffer(&mut self, buffer: Entity<Buffer>, cx: &mut Context<Self>) {
         let tracked_buffer = self.track_buffer_internal(buffer.clone(), false, cx);
         match tracked_buffer.status {
@@ -1109,10 +1188,17 @@ fn point_to_row_edit(edit: Edit<Point>, old_text: &Rope, new_text: &Rope) -> Edi
     }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 enum ChangeAuthor {
     User,
     Agent,
+    /// The agent made the edits of a project transaction between these versions, which were
+    /// first attributed to the user, since the transaction's buffers are only known once it
+    /// completes.
+    AgentTransaction {
+        start: clock::Global,
+        end: clock::Global,
+    },
 }
 
 #[derive(Debug)]
@@ -1270,6 +1356,126 @@ mod tests {
         assert_eq!(unreviewed_hunks(&action_log, cx), vec![]);
     }
 
+    #[gpui::test(iterations = 10)]
+    async fn test_project_transaction(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({"file1": "abc\ndef\nghi", "file2": "jkl\nmno", "file3": "pqr\nstu"}),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let action_log = cx.new(|_| ActionLog::new(project.clone()));
+        let file1_path = project
+            .read_with(cx, |project, cx| project.find_project_path("dir/file1", cx))
+            .unwrap();
+        let buffer1 = project
+            .update(cx, |project, cx| project.open_buffer(file1_path, cx))
+            .await
+            .unwrap();
+        let file2_path = project
+            .read_with(cx, |project, cx| project.find_project_path("dir/file2", cx))
+            .unwrap();
+        let buffer2 = project
+            .update(cx, |project, cx| project.open_buffer(file2_path, cx))
+            .await
+            .unwrap();
+
+        let file3_path = project
+            .read_with(cx, |project, cx| project.find_project_path("dir/file3", cx))
+            .unwrap();
+        let buffer3 = project
+            .update(cx, |project, cx| project.open_buffer(file3_path, cx))
+            .await
+            .unwrap();
+        action_log.update(cx, |log, cx| log.buffer_read(buffer3.clone(), cx));
+
+        // The agent has already edited the first file, but not the second one.
+        cx.update(|cx| {
+            action_log.update(cx, |log, cx| log.buffer_read(buffer1.clone(), cx));
+            buffer1.update(cx, |buffer, cx| {
+                buffer
+                    .edit([(Point::new(0, 0)..Point::new(0, 3), "ABC")], None, cx)
+                    .unwrap()
+            });
+            action_log.update(cx, |log, cx| log.buffer_edited(buffer1.clone(), cx));
+        });
+        cx.run_until_parked();
+
+        let (transaction_tx, transaction_rx) = futures::channel::oneshot::channel();
+        let transaction_task = action_log.update(cx, |log, cx| {
+            let transaction = cx.background_spawn(async move { anyhow::Ok(transaction_rx.await?) });
+            log.track_project_transaction(transaction, cx)
+        });
+        let mut transaction = ProjectTransaction::default();
+        for (buffer, row) in [(&buffer1, 2), (&buffer2, 1)] {
+            let buffer_transaction = buffer.update(cx, |buffer, cx| {
+                buffer.start_transaction();
+                buffer.edit([(Point::new(row, 0)..Point::new(row, 3), "XYZ")], None, cx);
+                buffer.end_transaction(cx);
+                buffer.finalize_last_transaction().cloned().unwrap()
+            });
+            transaction.0.insert(buffer.clone(), buffer_transaction);
+        }
+        // The user edits a file the transaction doesn't touch while it is pending.
+        buffer3.update(cx, |buffer, cx| {
+            buffer.edit([(Point::new(0, 0)..Point::new(0, 3), "PQR")], None, cx)
+        });
+        cx.run_until_parked();
+        transaction_tx.send(transaction).unwrap();
+        transaction_task.await.unwrap();
+        cx.run_until_parked();
+
+        assert_eq!(
+            unreviewed_hunks(&action_log, cx),
+            vec![
+                (
+                    buffer1.clone(),
+                    vec![
+                        HunkStatus {
+                            range: Point::new(0, 0)..Point::new(1, 0),
+                            diff_status: DiffHunkStatusKind::Modified,
+                            old_text: "abc\n".into(),
+                        },
+                        HunkStatus {
+                            range: Point::new(2, 0)..Point::new(2, 3),
+                            diff_status: DiffHunkStatusKind::Modified,
+                            old_text: "ghi".into(),
+                        }
+                    ],
+                ),
+                (
+                    buffer2.clone(),
+                    vec![HunkStatus {
+                        range: Point::new(1, 0)..Point::new(1, 3),
+                        diff_status: DiffHunkStatusKind::Modified,
+                        old_text: "mno".into(),
+                    }],
+                ),
+            ]
+        );
+
+        // A transaction dropped before completing, e.g. because the tool was cancelled, leaves
+        // later edits to the user.
+        drop(action_log.update(cx, |log, cx| {
+            let transaction = cx.background_spawn(futures::future::pending());
+            log.track_project_transaction(transaction, cx)
+        }));
+        buffer3.update(cx, |buffer, cx| {
+            buffer.edit([(Point::new(1, 0)..Point::new(1, 3), "STU")], None, cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            unreviewed_hunks(&action_log, cx)
+                .into_iter()
+                .map(|(buffer, _)| buffer)
+                .collect::<Vec<_>>(),
+            vec![buffer1.clone(), buffer2.clone()]
+        );
+    }
+
     #[gpui::test(iterations = 10)]
     async fn test_deletions(cx: &mut TestAppContext) {
         init_test(cx);
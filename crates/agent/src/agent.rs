@@ -35,17 +35,19 @@ use fs::Fs;
 use futures::channel::{mpsc, oneshot};
 use futures::future::Shared;
 use futures::{FutureExt as _, StreamExt as _, future};
+use git::repository::validate_worktree_directory;
 use gpui::{
     App, AppContext, AsyncApp, Context, Entity, SharedString, Subscription, Task, WeakEntity,
 };
+use itertools::Itertools as _;
 use language_model::{IconOrSvg, LanguageModel, LanguageModelProvider, LanguageModelRegistry};
-use project::{Project, ProjectItem, ProjectPath, Worktree};
+use project::{Project, ProjectItem, ProjectPath, Worktree, project_settings::ProjectSettings};
 use prompt_store::{
     ProjectContext, PromptStore, RULES_FILE_NAMES, RulesFileContext, UserRulesContext,
     WorktreeContext,
 };
 use serde::{Deserialize, Serialize};
-use settings::{LanguageModelSelection, update_settings_file};
+use settings::{LanguageModelSelection, Settings as _, update_settings_file};
 use std::any::Any;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
@@ -686,6 +688,8 @@ impl NativeAgent {
             ContextServerRegistryEvent::PromptsChanged => {
                 self.update_available_commands(cx);
             }
+            ContextServerRegistryEvent::ResourcesChanged
+            | ContextServerRegistryEvent::ResourceUpdated { .. } => {}
         }
     }
 
@@ -717,7 +721,7 @@ impl NativeAgent {
 
         registry
             .prompts()
-            .flat_map(|context_server_prompt| {
+            .map(|context_server_prompt| {
                 let prompt = &context_server_prompt.prompt;
 
                 let should_prefix = prompt_name_counts
@@ -737,22 +741,20 @@ impl NativeAgent {
                     prompt.description.clone().unwrap_or_default(),
                 );
 
-                match prompt.arguments.as_deref() {
-                    Some([arg]) => {
-                        let hint = format!("<{}>", arg.name);
+                if let Some(arguments) = prompt.arguments.as_deref()
+                    && !arguments.is_empty()
+                {
+                    let hint = arguments
+                        .iter()
+                        .map(|arg| format!("<{}>", arg.name))
+                        .join(" ");
 
-                        command = command.input(acp::AvailableCommandInput::Unstructured(
-                            acp::UnstructuredCommandInput::new(hint),
-                        ));
-                    }
-                    Some([]) | None => {}
-                    Some(_) => {
-                        // skip >1 argument commands since we don't support them yet
-                        return None;
-                    }
+                    command = command.input(acp::AvailableCommandInput::Unstructured(
+                        acp::UnstructuredCommandInput::new(hint),
+                    ));
                 }
 
-                Some(command)
+                command
             })
             .collect()
     }
@@ -839,6 +841,92 @@ impl NativeAgent {
         })
     }
 
+    /// Saves a new thread that keeps the messages that came before the given user message.
+    ///
+    /// With `in_worktree`, the project's files as they were before that message are restored in a
+    /// new git worktree, which the forked thread is associated with.
+    pub fn fork_thread(
+        &mut self,
+        id: acp::SessionId,
+        message_id: UserMessageId,
+        in_worktree: bool,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<acp::SessionId>> {
+        let Some(session) = self.sessions.get(&id) else {
+            return Task::ready(Err(anyhow!("Session not found")));
+        };
+        let fork_id = acp::SessionId::new(uuid::Uuid::new_v4().to_string());
+        let db_thread = session.thread.read(cx).to_db(cx);
+        let worktree = if in_worktree {
+            match self.create_fork_worktree(&session.acp_thread, &message_id, &fork_id, cx) {
+                Ok(worktree) => Some(worktree),
+                Err(error) => return Task::ready(Err(error)),
+            }
+        } else {
+            None
+        };
+
+        let thread_store = self.thread_store.clone();
+        cx.spawn(async move |_, cx| {
+            let mut db_thread = db_thread.await.fork(id, &message_id)?;
+            if let Some((worktree_created, worktree_info)) = worktree {
+                worktree_created
+                    .await
+                    .context("Failed to create the git worktree")??;
+                db_thread.git_worktree_info = Some(worktree_info);
+            }
+            thread_store
+                .update(cx, |store, cx| {
+                    store.save_thread(fork_id.clone(), db_thread, cx)
+                })
+                .await?;
+            Ok(fork_id)
+        })
+    }
+
+    /// Starts creating a git worktree of the project's active repository, at the checkpoint
+    /// that was taken before the given user message.
+    fn create_fork_worktree(
+        &self,
+        acp_thread: &Entity<AcpThread>,
+        message_id: &UserMessageId,
+        fork_id: &acp::SessionId,
+        cx: &mut App,
+    ) -> Result<(oneshot::Receiver<Result<()>>, AgentGitWorktreeInfo)> {
+        let checkpoint = acp_thread
+            .read(cx)
+            .checkpoint(message_id)
+            .context("No checkpoint was taken before this message")?;
+        let repository = self
+            .project
+            .read(cx)
+            .active_repository(cx)
+            .context("The project has no git repository")?;
+        let worktree_directory_setting = ProjectSettings::get_global(cx)
+            .git
+            .worktree_directory
+            .clone();
+        repository.update(cx, |repository, _| {
+            let work_dir = repository.work_directory_abs_path.clone();
+            let commit_sha = checkpoint
+                .commit_sha(&work_dir)
+                .context("No checkpoint was taken of the project's git repository")?
+                .to_string();
+            let directory = validate_worktree_directory(&work_dir, &worktree_directory_setting)?;
+            let branch = format!(
+                "zed/agent/{}",
+                fork_id.0.chars().take(8).collect::<String>()
+            );
+            let info = AgentGitWorktreeInfo {
+                branch: branch.clone(),
+                worktree_path: directory.join(&branch),
+                base_ref: commit_sha.clone(),
+            };
+            let worktree_created = repository.create_worktree(branch, directory, Some(commit_sha));
+            Ok((worktree_created, info))
+        })
+    }
+
     fn save_thread(&mut self, thread: Entity<Thread>, cx: &mut Context<Self>) {
         if thread.read(cx).is_empty() {
             return;
@@ -975,6 +1063,18 @@ impl NativeAgentConnection {
         self.0.update(cx, |this, cx| this.load_thread(id, cx))
     }
 
+    pub fn fork_thread(
+        &self,
+        id: acp::SessionId,
+        message_id: UserMessageId,
+        in_worktree: bool,
+        cx: &mut App,
+    ) -> Task<Result<acp::SessionId>> {
+        self.0.update(cx, |this, cx| {
+            this.fork_thread(id, message_id, in_worktree, cx)
+        })
+    }
+
     fn run_turn(
         &self,
         session_id: acp::SessionId,
@@ -1127,6 +1227,30 @@ impl<'a> Command<'a> {
     }
 }
 
+/// Assigns the words of a slash command's input to the prompt's arguments, in order. The last
+/// argument takes the rest of the input, so that it can contain spaces.
+fn prompt_arguments(
+    arguments: &[context_server::types::PromptArgument],
+    mut input: &str,
+) -> HashMap<String, String> {
+    let mut values = HashMap::default();
+    for (ix, argument) in arguments.iter().enumerate() {
+        input = input.trim_start();
+        if input.is_empty() {
+            break;
+        }
+        let value = if ix + 1 == arguments.len() {
+            std::mem::take(&mut input)
+        } else {
+            let (value, rest) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+            input = rest;
+            value
+        };
+        values.insert(argument.name.clone(), value.trim_end().to_string());
+    }
+    values
+}
+
 struct NativeAgentModelSelector {
     session_id: acp::SessionId,
     connection: NativeAgentConnection,
@@ -1309,18 +1433,10 @@ impl acp_thread::AgentConnection for NativeAgentConnection {
             if let Some(prompt) =
                 registry.find_prompt(explicit_server_id.as_ref(), parsed_command.prompt_name)
             {
-                let arguments = if !parsed_command.arg_value.is_empty()
-                    && let Some(arg_name) = prompt
-                        .prompt
-                        .arguments
-                        .as_ref()
-                        .and_then(|args| args.first())
-                        .map(|arg| arg.name.clone())
-                {
-                    HashMap::from_iter([(arg_name, parsed_command.arg_value.to_string())])
-                } else {
-                    Default::default()
-                };
+                let arguments = prompt_arguments(
+                    prompt.prompt.arguments.as_deref().unwrap_or_default(),
+                    parsed_command.arg_value,
+                );
 
                 let prompt_name = prompt.prompt.name.clone();
                 let server_id = prompt.server_id.clone();
@@ -1472,7 +1588,12 @@ impl NativeAgentSessionList {
             cwd: None,
             title: Some(entry.title),
             updated_at: Some(entry.updated_at),
-            meta: None,
+            meta: entry.forked_from_session_id.map(|forked_from| {
+                acp::Meta::from_iter([(
+                    acp_thread::FORKED_FROM_SESSION_ID_META_KEY.into(),
+                    forked_from.0.to_string().into(),
+                )])
+            }),
         }
     }
 
@@ -1866,6 +1987,35 @@ mod internal_tests {
     use settings::SettingsStore;
     use util::{path, rel_path::rel_path};
 
+    #[test]
+    fn test_prompt_arguments() {
+        let arguments = ["ticket", "summary"]
+            .into_iter()
+            .map(|name| context_server::types::PromptArgument {
+                name: name.to_string(),
+                description: None,
+                required: None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            prompt_arguments(&arguments, "  ENG-12   login is  slow "),
+            HashMap::from_iter([
+                ("ticket".to_string(), "ENG-12".to_string()),
+                ("summary".to_string(), "login is  slow".to_string()),
+            ])
+        );
+        assert_eq!(
+            prompt_arguments(&arguments, "ENG-12"),
+            HashMap::from_iter([("ticket".to_string(), "ENG-12".to_string())])
+        );
+        assert_eq!(prompt_arguments(&arguments, ""), HashMap::default());
+        assert_eq!(
+            prompt_arguments(&arguments[..1], "login is slow"),
+            HashMap::from_iter([("ticket".to_string(), "login is slow".to_string())])
+        );
+    }
+
     #[gpui::test]
     async fn test_maintaining_project_context(cx: &mut TestAppContext) {
         init_test(cx);
@@ -45,6 +45,9 @@ pub struct DbThreadMetadata {
     /// listing without decompressing thread data. The blob is the source of
     /// truth; this column is populated on save for query convenience.
     pub worktree_branch: Option<String>,
+    /// Denormalized from `DbThread::forked_from`, so the history can show
+    /// which thread a thread was forked from.
+    pub forked_from_session_id: Option<acp::SessionId>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -70,6 +73,9 @@ pub struct DbThread {
     pub subagent_context: Option<crate::SubagentContext>,
     #[serde(default)]
     pub git_worktree_info: Option<AgentGitWorktreeInfo>,
+    /// The thread this thread was forked from, if any.
+    #[serde(default)]
+    pub forked_from: Option<acp::SessionId>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -109,6 +115,7 @@ impl SharedThread {
             imported: true,
             subagent_context: None,
             git_worktree_info: None,
+            forked_from: None,
         }
     }
 
@@ -143,6 +150,31 @@ impl DbThread {
         }
     }
 
+    /// Forks this thread into a new one that only keeps the messages that came before the
+    /// given user message, so a different direction can be explored from that point.
+    pub fn fork(mut self, forked_from: acp::SessionId, message_id: &UserMessageId) -> Result<Self> {
+        let position = self
+            .messages
+            .iter()
+            .position(|message| {
+                matches!(message, DbMessage::User(UserMessage { id, .. }) if id == message_id)
+            })
+            .ok_or_else(|| anyhow!("Message not found"))?;
+
+        for message in self.messages.drain(position..) {
+            if let DbMessage::User(message) = message {
+                self.request_token_usage.remove(&message.id);
+            }
+        }
+        self.updated_at = Utc::now();
+        self.detailed_summary = None;
+        self.imported = false;
+        self.subagent_context = None;
+        self.git_worktree_info = None;
+        self.forked_from = Some(forked_from);
+        Ok(self)
+    }
+
     fn upgrade_from_agent_1(thread: crate::legacy_thread::SerializedThread) -> Result<Self> {
         let mut messages = Vec::new();
         let mut request_token_usage = HashMap::default();
@@ -284,6 +316,7 @@ impl DbThread {
             imported: false,
             subagent_context: None,
             git_worktree_info: None,
+            forked_from: None,
         })
     }
 }
@@ -395,6 +428,13 @@ impl ThreadsDatabase {
             s().ok();
         }
 
+        if let Ok(mut s) = connection.exec(indoc! {"
+            ALTER TABLE threads ADD COLUMN forked_from_id TEXT
+        "})
+        {
+            s().ok();
+        }
+
         let db = Self {
             executor,
             connection: Arc::new(Mutex::new(connection)),
@@ -427,6 +467,7 @@ impl ThreadsDatabase {
             .git_worktree_info
             .as_ref()
             .map(|info| info.branch.clone());
+        let forked_from_id = thread.forked_from.as_ref().map(|id| id.0.clone());
         let json_data = serde_json::to_string(&SerializedThread {
             thread,
             version: DbThread::VERSION,
@@ -438,14 +479,15 @@ impl ThreadsDatabase {
         let data_type = DataType::Zstd;
         let data = compressed;
 
-        let mut insert = connection.exec_bound::<(Arc<str>, Option<Arc<str>>, Option<String>, String, String, DataType, Vec<u8>)>(indoc! {"
-            INSERT OR REPLACE INTO threads (id, parent_id, worktree_branch, summary, updated_at, data_type, data) VALUES (?, ?, ?, ?, ?, ?, ?)
+        let mut insert = connection.exec_bound::<(Arc<str>, Option<Arc<str>>, Option<String>, Option<Arc<str>>, String, String, DataType, Vec<u8>)>(indoc! {"
+            INSERT OR REPLACE INTO threads (id, parent_id, worktree_branch, forked_from_id, summary, updated_at, data_type, data) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
         "})?;
 
         insert((
             id.0,
             parent_id,
             worktree_branch,
+            forked_from_id,
             title,
             updated_at,
             data_type,
@@ -462,20 +504,21 @@ impl ThreadsDatabase {
             let connection = connection.lock();
 
             let mut select = connection
-                .select_bound::<(), (Arc<str>, Option<Arc<str>>, Option<String>, String, String)>(indoc! {"
-                SELECT id, parent_id, worktree_branch, summary, updated_at FROM threads ORDER BY updated_at DESC
+                .select_bound::<(), (Arc<str>, Option<Arc<str>>, Option<String>, Option<Arc<str>>, String, String)>(indoc! {"
+                SELECT id, parent_id, worktree_branch, forked_from_id, summary, updated_at FROM threads ORDER BY updated_at DESC
             "})?;
 
             let rows = select(())?;
             let mut threads = Vec::new();
 
-            for (id, parent_id, worktree_branch, summary, updated_at) in rows {
+            for (id, parent_id, worktree_branch, forked_from_id, summary, updated_at) in rows {
                 threads.push(DbThreadMetadata {
                     id: acp::SessionId::new(id),
                     parent_session_id: parent_id.map(acp::SessionId::new),
                     title: summary.into(),
                     updated_at: DateTime::parse_from_rfc3339(&updated_at)?.with_timezone(&Utc),
                     worktree_branch,
+                    forked_from_session_id: forked_from_id.map(acp::SessionId::new),
                 });
             }
 
@@ -610,6 +653,7 @@ mod tests {
             imported: false,
             subagent_context: None,
             git_worktree_info: None,
+            forked_from: None,
         }
     }
 
@@ -843,4 +887,82 @@ mod tests {
             "plain thread should have no worktree_branch"
         );
     }
+
+    #[gpui::test]
+    async fn test_fork_thread(cx: &mut TestAppContext) {
+        let database = ThreadsDatabase::new(cx.executor()).unwrap();
+
+        let first_message_id = UserMessageId::new();
+        let second_message_id = UserMessageId::new();
+        let user_message = |id: &UserMessageId, text: &str| {
+            DbMessage::User(UserMessage {
+                id: id.clone(),
+                content: vec![UserMessageContent::Text(text.to_string())],
+            })
+        };
+        let agent_message = |text: &str| {
+            DbMessage::Agent(AgentMessage {
+                content: vec![AgentMessageContent::Text(text.to_string())],
+                ..Default::default()
+            })
+        };
+
+        let original_id = session_id("original-thread");
+        let mut original_thread = make_thread(
+            "Original",
+            Utc.with_ymd_and_hms(2024, 6, 15, 12, 0, 0).unwrap(),
+        );
+        original_thread.messages = vec![
+            user_message(&first_message_id, "Add a parser"),
+            agent_message("Added a recursive descent parser"),
+            user_message(&second_message_id, "Make it faster"),
+            agent_message("Switched to a table-driven parser"),
+        ];
+        original_thread
+            .request_token_usage
+            .insert(first_message_id.clone(), Default::default());
+        original_thread
+            .request_token_usage
+            .insert(second_message_id.clone(), Default::default());
+        original_thread.detailed_summary = Some("A parser was added".into());
+
+        let forked_thread = original_thread
+            .fork(original_id.clone(), &second_message_id)
+            .unwrap();
+        assert_eq!(
+            forked_thread.messages,
+            vec![
+                user_message(&first_message_id, "Add a parser"),
+                agent_message("Added a recursive descent parser"),
+            ]
+        );
+        assert_eq!(
+            forked_thread
+                .request_token_usage
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>(),
+            vec![first_message_id.clone()]
+        );
+        assert_eq!(forked_thread.title.as_ref(), "Original");
+        assert!(forked_thread.detailed_summary.is_none());
+        assert_eq!(forked_thread.forked_from, Some(original_id.clone()));
+
+        let forked_id = session_id("forked-thread");
+        database
+            .save_thread(forked_id.clone(), forked_thread)
+            .await
+            .unwrap();
+        let threads = database.list_threads().await.unwrap();
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].id, forked_id);
+        assert_eq!(threads[0].forked_from_session_id, Some(original_id));
+
+        assert!(
+            make_thread("Empty", Utc::now())
+                .fork(session_id("empty-thread"), &first_message_id)
+                .is_err(),
+            "forking from a message that isn't in the thread should fail"
+        );
+    }
 }
@@ -1,10 +1,12 @@
 use crate::{
-    AgentGitWorktreeInfo, ContextServerRegistry, CopyPathTool, CreateDirectoryTool,
-    DbLanguageModel, DbThread, DeletePathTool, DiagnosticsTool, EditFileTool, FetchTool,
-    FindPathTool, GrepTool, ListDirectoryTool, MovePathTool, NowTool, OpenTool, ProjectSnapshot,
-    ReadFileTool, RestoreFileFromDiskTool, SaveFileTool, StreamingEditFileTool, SubagentTool,
+    AgentGitWorktreeInfo, ApplyCodeActionTool, CodeActionsTool, ContextServerRegistry,
+    CopyPathTool, CreateDirectoryTool, DbLanguageModel, DbThread, DeletePathTool, DiagnosticsTool,
+    EditFileTool, FetchTool, FindPathTool, FindReferencesTool, GitBlameTool, GitDiffTool,
+    GitLogTool, GitShowTool, GoToDefinitionTool, GrepTool, HoverTool, ListDirectoryTool,
+    MovePathTool, NowTool, OpenTool, ProjectSnapshot, ReadFileTool, RenameSymbolTool,
+    RestoreFileFromDiskTool, SaveFileTool, StreamingEditFileTool, SubagentTool,
     SystemPromptTemplate, Template, Templates, TerminalTool, ToolPermissionDecision, WebSearchTool,
-    decide_permission_from_settings,
+    WorkspaceSymbolsTool, decide_permission_from_settings,
 };
 use acp_thread::{MentionUri, UserMessageId};
 use action_log::ActionLog;
@@ -217,6 +219,7 @@ impl UserMessage {
             "<rules>\nThe user has specified the following rules that should be applied:\n";
         const OPEN_DIAGNOSTICS_TAG: &str = "<diagnostics>";
         const OPEN_DIFFS_TAG: &str = "<diffs>";
+        const OPEN_RESOURCES_TAG: &str = "<mcp_resources>";
 
         let mut file_context = OPEN_FILES_TAG.to_string();
         let mut directory_context = OPEN_DIRECTORIES_TAG.to_string();
@@ -227,6 +230,7 @@ impl UserMessage {
         let mut rules_context = OPEN_RULES_TAG.to_string();
         let mut diagnostics_context = OPEN_DIAGNOSTICS_TAG.to_string();
         let mut diffs_context = OPEN_DIFFS_TAG.to_string();
+        let mut resources_context = OPEN_RESOURCES_TAG.to_string();
 
         for chunk in &self.content {
             let chunk = match chunk {
@@ -334,6 +338,10 @@ impl UserMessage {
                             )
                             .ok();
                         }
+                        MentionUri::McpResource { uri, .. } => {
+                            write!(&mut resources_context, "\nResource: {}\n\n{}", uri, content)
+                                .ok();
+                        }
                     }
 
                     language_model::MessageContent::Text(uri.as_link().to_string())
@@ -394,6 +402,13 @@ impl UserMessage {
                 .push(language_model::MessageContent::Text(fetch_context));
         }
 
+        if resources_context.len() > OPEN_RESOURCES_TAG.len() {
+            resources_context.push_str("</mcp_resources>\n");
+            message
+                .content
+                .push(language_model::MessageContent::Text(resources_context));
+        }
+
         if rules_context.len() > OPEN_RULES_TAG.len() {
             rules_context.push_str("</user_rules>\n");
             message
@@ -741,6 +756,8 @@ impl ToolPermissionContext {
                 || tool_name == DeletePathTool::NAME
                 || tool_name == CreateDirectoryTool::NAME
                 || tool_name == SaveFileTool::NAME
+                || tool_name == RenameSymbolTool::NAME
+                || tool_name == ApplyCodeActionTool::NAME
             {
                 (
                     extract_path_pattern(value),
@@ -893,6 +910,8 @@ pub struct Thread {
     running_subagents: Vec<WeakEntity<Thread>>,
     /// Git worktree info if this thread is running in an agent worktree.
     git_worktree_info: Option<AgentGitWorktreeInfo>,
+    /// The thread this thread was forked from, if any.
+    forked_from: Option<acp::SessionId>,
 }
 
 impl Thread {
@@ -984,6 +1003,7 @@ impl Thread {
             subagent_context: None,
             running_subagents: Vec::new(),
             git_worktree_info: None,
+            forked_from: None,
         }
     }
 
@@ -1209,6 +1229,7 @@ impl Thread {
             subagent_context: db_thread.subagent_context,
             running_subagents: Vec::new(),
             git_worktree_info: db_thread.git_worktree_info,
+            forked_from: db_thread.forked_from,
         }
     }
 
@@ -1230,6 +1251,7 @@ impl Thread {
             imported: self.imported,
             subagent_context: self.subagent_context.clone(),
             git_worktree_info: self.git_worktree_info.clone(),
+            forked_from: self.forked_from.clone(),
         };
 
         cx.background_spawn(async move {
@@ -1333,6 +1355,14 @@ impl Thread {
         cx: &mut Context<Self>,
     ) {
         let language_registry = self.project.read(cx).languages().clone();
+        self.add_tool(
+            ApplyCodeActionTool::new(self.project.clone(), self.action_log.clone()),
+            allowed_tool_names.as_ref(),
+        );
+        self.add_tool(
+            CodeActionsTool::new(self.project.clone()),
+            allowed_tool_names.as_ref(),
+        );
         self.add_tool(
             CopyPathTool::new(self.project.clone()),
             allowed_tool_names.as_ref(),
@@ -1375,10 +1405,38 @@ impl Thread {
             FindPathTool::new(self.project.clone()),
             allowed_tool_names.as_ref(),
         );
+        self.add_tool(
+            FindReferencesTool::new(self.project.clone()),
+            allowed_tool_names.as_ref(),
+        );
+        self.add_tool(
+            GitBlameTool::new(self.project.clone()),
+            allowed_tool_names.as_ref(),
+        );
+        self.add_tool(
+            GitDiffTool::new(self.project.clone()),
+            allowed_tool_names.as_ref(),
+        );
+        self.add_tool(
+            GitLogTool::new(self.project.clone()),
+            allowed_tool_names.as_ref(),
+        );
+        self.add_tool(
+            GitShowTool::new(self.project.clone()),
+            allowed_tool_names.as_ref(),
+        );
+        self.add_tool(
+            GoToDefinitionTool::new(self.project.clone()),
+            allowed_tool_names.as_ref(),
+        );
         self.add_tool(
             GrepTool::new(self.project.clone()),
             allowed_tool_names.as_ref(),
         );
+        self.add_tool(
+            HoverTool::new(self.project.clone()),
+            allowed_tool_names.as_ref(),
+        );
         self.add_tool(
             ListDirectoryTool::new(self.project.clone()),
             allowed_tool_names.as_ref(),
@@ -1400,6 +1458,10 @@ impl Thread {
             ),
             allowed_tool_names.as_ref(),
         );
+        self.add_tool(
+            RenameSymbolTool::new(self.project.clone(), self.action_log.clone()),
+            allowed_tool_names.as_ref(),
+        );
         self.add_tool(
             SaveFileTool::new(self.project.clone()),
             allowed_tool_names.as_ref(),
@@ -1413,6 +1475,10 @@ impl Thread {
             allowed_tool_names.as_ref(),
         );
         self.add_tool(WebSearchTool, allowed_tool_names.as_ref());
+        self.add_tool(
+            WorkspaceSymbolsTool::new(self.project.clone()),
+            allowed_tool_names.as_ref(),
+        );
 
         if cx.has_flag::<SubagentsFeatureFlag>() && self.depth() < MAX_SUBAGENT_DEPTH {
             self.add_tool(
@@ -2481,6 +2547,7 @@ impl Thread {
             tool_choice: None,
             stop: Vec::new(),
             temperature: AgentSettings::temperature_for_model(model, cx),
+            max_output_tokens: None,
             thinking_allowed: self.thinking_enabled,
             thinking_effort: self.thinking_effort.clone(),
         };
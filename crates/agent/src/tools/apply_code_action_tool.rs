@@ -0,0 +1,198 @@
+use super::code_actions_tool::request_code_actions;
+use super::code_navigation::{LspEdits, authorize_lsp_edits, finish_lsp_edits};
+use crate::{AgentTool, ToolCallEventStream, ToolPermissionDecision, decide_permission_for_path};
+use action_log::ActionLog;
+use agent_client_protocol as acp;
+use agent_settings::AgentSettings;
+use anyhow::{Result, anyhow};
+use collections::HashSet;
+use futures::FutureExt as _;
+use gpui::{App, Entity, SharedString, Task};
+use itertools::Itertools as _;
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use std::{fmt::Write, sync::Arc};
+use util::markdown::MarkdownInlineCode;
+
+/// Applies one of the code actions the language server offers for some lines of a file, as listed by the `code_actions` tool.
+///
+/// The edited files are saved, and the user can review the edits like any other edit.
+///
+/// <example>
+/// To apply the "Import `HashMap`" code action offered for line 18 of `src/main.rs`:
+/// {
+///     "path": "project/src/main.rs",
+///     "start_line": 18,
+///     "title": "Import `HashMap`"
+/// }
+/// </example>
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ApplyCodeActionToolInput {
+    /// The path of the file.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    pub path: String,
+    /// The 1-based line number the code action was listed for.
+    pub start_line: u32,
+    /// The 1-based, inclusive line number where the lines the code action was listed for end. Defaults to `start_line`.
+    pub end_line: Option<u32>,
+    /// The title of the code action, exactly as the `code_actions` tool listed it.
+    pub title: String,
+}
+
+pub struct ApplyCodeActionTool {
+    project: Entity<Project>,
+    action_log: Entity<ActionLog>,
+}
+
+impl ApplyCodeActionTool {
+    pub fn new(project: Entity<Project>, action_log: Entity<ActionLog>) -> Self {
+        Self {
+            project,
+            action_log,
+        }
+    }
+}
+
+impl AgentTool for ApplyCodeActionTool {
+    type Input = ApplyCodeActionToolInput;
+    type Output = String;
+
+    const NAME: &'static str = "apply_code_action";
+
+    fn kind() -> acp::ToolKind {
+        acp::ToolKind::Edit
+    }
+
+    fn initial_title(
+        &self,
+        input: Result<Self::Input, serde_json::Value>,
+        _cx: &mut App,
+    ) -> SharedString {
+        match input {
+            Ok(input) => format!("Apply {}", MarkdownInlineCode(&input.title)).into(),
+            Err(_) => "Apply code action".into(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: Self::Input,
+        event_stream: ToolCallEventStream,
+        cx: &mut App,
+    ) -> Task<Result<Self::Output>> {
+        let settings = AgentSettings::get_global(cx);
+        if let ToolPermissionDecision::Deny(reason) =
+            decide_permission_for_path(Self::NAME, &input.path, settings)
+        {
+            return Task::ready(Err(anyhow!("{reason}")));
+        }
+
+        let code_actions = request_code_actions(
+            &self.project,
+            &input.path,
+            input.start_line,
+            input.end_line,
+            cx,
+        );
+        let project = self.project.clone();
+        let action_log = self.action_log.clone();
+        cx.spawn(async move |cx| {
+            let (lsp_buffer, code_actions) = futures::select! {
+                code_actions = code_actions.fuse() => code_actions?,
+                _ = event_stream.cancelled_by_user().fuse() => {
+                    anyhow::bail!("Code action cancelled by user");
+                }
+            };
+            let Some(code_action) = code_actions
+                .iter()
+                .find(|code_action| code_action.lsp_action.title() == input.title)
+            else {
+                if code_actions.is_empty() {
+                    anyhow::bail!("No code actions are available for these lines.");
+                }
+                anyhow::bail!(
+                    "No code action is titled {:?}. The available code actions are: {}",
+                    input.title,
+                    code_actions
+                        .iter()
+                        .map(|code_action| format!("{:?}", code_action.lsp_action.title()))
+                        .join(", ")
+                );
+            };
+
+            // Code actions that run a command, and the code actions of remote language servers,
+            // only make their edits once applied, so they can only be authorized up front.
+            let edits = if project.read_with(cx, |project, _| project.is_local()) {
+                let proposed_edit = project.update(cx, |project, cx| {
+                    project.propose_code_action(lsp_buffer.buffer.clone(), code_action.clone(), cx)
+                });
+                let proposed_edit = futures::select! {
+                    proposed_edit = proposed_edit.fuse() => proposed_edit?,
+                    _ = event_stream.cancelled_by_user().fuse() => {
+                        anyhow::bail!("Code action cancelled by user");
+                    }
+                };
+                proposed_edit.map_or(LspEdits::Unknown, LspEdits::Proposed)
+            } else {
+                LspEdits::Unknown
+            };
+
+            let title = format!("Apply {}", MarkdownInlineCode(&input.title));
+            authorize_lsp_edits(
+                Self::NAME,
+                title,
+                &edits,
+                &input.path,
+                &project,
+                &event_stream,
+                cx,
+            )
+            .await?;
+
+            let apply = project.update(cx, |project, cx| match edits {
+                LspEdits::Proposed(proposed_edit) => {
+                    project.apply_workspace_edit(proposed_edit, true, cx)
+                }
+                LspEdits::Unknown => project.apply_code_action(
+                    lsp_buffer.buffer.clone(),
+                    code_action.clone(),
+                    true,
+                    cx,
+                ),
+            });
+            let apply = action_log.update(cx, |action_log, cx| {
+                action_log.track_project_transaction(apply, cx)
+            });
+            let transaction =
+                finish_lsp_edits(apply, "Code action cancelled by user", &event_stream, cx).await?;
+            if transaction.0.is_empty() {
+                return Ok(format!(
+                    "Applied `{}`, which didn't edit any files.",
+                    input.title
+                ));
+            }
+
+            let buffers = transaction.0.into_keys().collect::<HashSet<_>>();
+            let mut paths = cx.update(|cx| {
+                buffers
+                    .iter()
+                    .filter_map(|buffer| Some(buffer.read(cx).file()?.full_path(cx)))
+                    .collect::<Vec<_>>()
+            });
+            paths.sort();
+            project
+                .update(cx, |project, cx| project.save_buffers(buffers, cx))
+                .await?;
+
+            let mut output = format!("Applied `{}`, editing:\n", input.title);
+            for path in paths {
+                writeln!(output, "- {}", path.display())?;
+            }
+            Ok(output)
+        })
+    }
+}
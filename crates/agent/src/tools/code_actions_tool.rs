@@ -0,0 +1,130 @@
+use super::code_navigation::{LspBuffer, line_range, open_lsp_buffer};
+use crate::{AgentTool, ToolCallEventStream};
+use agent_client_protocol as acp;
+use anyhow::Result;
+use futures::FutureExt as _;
+use gpui::{App, Entity, SharedString, Task};
+use project::{CodeAction, Project};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Write, sync::Arc};
+use util::markdown::MarkdownInlineCode;
+
+/// Lists the code actions the language server offers for some lines of a file, like quick fixes for diagnostics, refactorings and imports of missing symbols.
+///
+/// Use the `apply_code_action` tool to apply one of them.
+///
+/// <example>
+/// To list the code actions for an error on line 18 of `src/main.rs`:
+/// {
+///     "path": "project/src/main.rs",
+///     "start_line": 18
+/// }
+/// </example>
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CodeActionsToolInput {
+    /// The path of the file.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    pub path: String,
+    /// The 1-based line number to list code actions for.
+    pub start_line: u32,
+    /// The 1-based, inclusive line number where the lines end. Defaults to `start_line`.
+    pub end_line: Option<u32>,
+}
+
+pub struct CodeActionsTool {
+    project: Entity<Project>,
+}
+
+impl CodeActionsTool {
+    pub fn new(project: Entity<Project>) -> Self {
+        Self { project }
+    }
+}
+
+/// Requests the code actions for the given 1-based lines of the file at `path`.
+pub(crate) fn request_code_actions(
+    project: &Entity<Project>,
+    path: &str,
+    start_line: u32,
+    end_line: Option<u32>,
+    cx: &mut App,
+) -> Task<Result<(LspBuffer, Vec<CodeAction>)>> {
+    let buffer = open_lsp_buffer(project, path, cx);
+    let project = project.clone();
+    let path = path.to_string();
+    cx.spawn(async move |cx| {
+        let lsp_buffer = buffer.await?;
+        let code_actions = project.update(cx, |project, cx| {
+            let range = line_range(
+                lsp_buffer.buffer.read(cx),
+                start_line,
+                end_line.unwrap_or(start_line),
+                &path,
+            )?;
+            anyhow::Ok(project.code_actions(&lsp_buffer.buffer, range, None, cx))
+        })?;
+        let code_actions = code_actions.await?.unwrap_or_default();
+        Ok((lsp_buffer, code_actions))
+    })
+}
+
+impl AgentTool for CodeActionsTool {
+    type Input = CodeActionsToolInput;
+    type Output = String;
+
+    const NAME: &'static str = "code_actions";
+
+    fn kind() -> acp::ToolKind {
+        acp::ToolKind::Read
+    }
+
+    fn initial_title(
+        &self,
+        input: Result<Self::Input, serde_json::Value>,
+        _cx: &mut App,
+    ) -> SharedString {
+        match input {
+            Ok(input) => format!("List code actions in {}", MarkdownInlineCode(&input.path)).into(),
+            Err(_) => "List code actions".into(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: Self::Input,
+        event_stream: ToolCallEventStream,
+        cx: &mut App,
+    ) -> Task<Result<Self::Output>> {
+        let code_actions = request_code_actions(
+            &self.project,
+            &input.path,
+            input.start_line,
+            input.end_line,
+            cx,
+        );
+        cx.spawn(async move |_cx| {
+            let (_, code_actions) = futures::select! {
+                code_actions = code_actions.fuse() => code_actions?,
+                _ = event_stream.cancelled_by_user().fuse() => {
+                    anyhow::bail!("Listing code actions cancelled by user");
+                }
+            };
+            if code_actions.is_empty() {
+                return Ok("No code actions available for these lines.".to_string());
+            }
+
+            let mut output = String::new();
+            for code_action in &code_actions {
+                let title = code_action.lsp_action.title();
+                match code_action.lsp_action.action_kind() {
+                    Some(kind) => writeln!(output, "- {title} ({})", kind.as_str())?,
+                    None => writeln!(output, "- {title}")?,
+                }
+            }
+            Ok(output)
+        })
+    }
+}
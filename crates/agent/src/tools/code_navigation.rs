@@ -0,0 +1,311 @@
+//! Helpers shared by the tools that ask language servers about the code.
+
+use crate::{
+    ToolCallEventStream, ToolPermissionContext, ToolPermissionDecision, decide_permission_for_path,
+};
+use agent_settings::AgentSettings;
+use anyhow::{Context as _, Result, anyhow};
+use gpui::{App, AsyncApp, Entity, Task};
+use itertools::Itertools as _;
+use language::{Buffer, Location, Point, ToPoint as _};
+use project::{
+    Project, ProjectPath, ProjectTransaction, ProposedWorkspaceEdit, WorktreeSettings,
+    lsp_store::OpenLspBufferHandle,
+};
+use settings::Settings as _;
+use std::{fmt::Write as _, ops::Range};
+use util::markdown::MarkdownInlineCode;
+
+/// The most locations a tool lists, so that widely used symbols don't fill the context window.
+pub(crate) const MAX_LOCATIONS: usize = 100;
+
+/// A buffer that is registered with its language servers, so that they can be queried about it.
+pub(crate) struct LspBuffer {
+    pub buffer: Entity<Buffer>,
+    _lsp_handle: OpenLspBufferHandle,
+}
+
+pub(crate) fn open_lsp_buffer(
+    project: &Entity<Project>,
+    path: &str,
+    cx: &mut App,
+) -> Task<Result<LspBuffer>> {
+    let Some(project_path) = project.read(cx).find_project_path(path, cx) else {
+        return Task::ready(Err(anyhow!("Could not find path {path} in project")));
+    };
+    if let Some(setting) = hiding_setting(&project_path, cx) {
+        return Task::ready(Err(anyhow!(
+            "Cannot query file because its path matches the {setting} setting: {path}"
+        )));
+    }
+    let open_buffer = project.update(cx, |project, cx| project.open_buffer(project_path, cx));
+
+    let project = project.clone();
+    cx.spawn(async move |cx| {
+        let buffer = open_buffer.await?;
+        let lsp_handle = project.update(cx, |project, cx| {
+            project.register_buffer_with_language_servers(&buffer, cx)
+        });
+        Ok(LspBuffer {
+            buffer,
+            _lsp_handle: lsp_handle,
+        })
+    })
+}
+
+/// Returns the setting that hides the file from the agent, since tools must not reveal the
+/// files that `read_file` refuses to read.
+fn hiding_setting(project_path: &ProjectPath, cx: &App) -> Option<&'static str> {
+    let global_settings = WorktreeSettings::get_global(cx);
+    let worktree_settings = WorktreeSettings::get(Some(project_path.into()), cx);
+    if global_settings.is_path_excluded(&project_path.path) {
+        Some("global `file_scan_exclusions`")
+    } else if global_settings.is_path_private(&project_path.path) {
+        Some("global `private_files`")
+    } else if worktree_settings.is_path_excluded(&project_path.path) {
+        Some("worktree `file_scan_exclusions`")
+    } else if worktree_settings.is_path_private(&project_path.path) {
+        Some("worktree `private_files`")
+    } else {
+        None
+    }
+}
+
+/// Whether the file is hidden from the agent by the `file_scan_exclusions` or `private_files`
+/// settings.
+pub(crate) fn is_path_hidden(project_path: &ProjectPath, cx: &App) -> bool {
+    hiding_setting(project_path, cx).is_some()
+}
+
+/// Drops the locations in files that are hidden from the agent.
+pub(crate) fn visible_locations(locations: Vec<Location>, cx: &App) -> Vec<Location> {
+    locations
+        .into_iter()
+        .filter(|location| {
+            location
+                .buffer
+                .read(cx)
+                .file()
+                .is_some_and(|file| !is_path_hidden(&ProjectPath::from_file(file.as_ref(), cx), cx))
+        })
+        .collect()
+}
+
+/// Returns the range covering the given 1-based lines of the buffer.
+pub(crate) fn line_range(
+    buffer: &Buffer,
+    start_line: u32,
+    end_line: u32,
+    path: &str,
+) -> Result<Range<Point>> {
+    let max_row = buffer.max_point().row;
+    let row = |line: u32| {
+        line.checked_sub(1)
+            .filter(|row| *row <= max_row)
+            .with_context(|| format!("{path} doesn't have a line {line}"))
+    };
+    let start_row = row(start_line)?;
+    let end_row = row(end_line)?.max(start_row);
+    Ok(Point::new(start_row, 0)..Point::new(end_row, buffer.line_len(end_row)))
+}
+
+/// A symbol in a buffer that is registered with its language servers.
+pub(crate) struct SymbolPosition {
+    pub buffer: Entity<Buffer>,
+    pub position: Point,
+    _lsp_handle: OpenLspBufferHandle,
+}
+
+/// Finds `symbol` on the 1-based `line` of the file at `path`. Models refer to a symbol by its
+/// line and name much more reliably than by its column.
+pub(crate) fn resolve_symbol_position(
+    project: &Entity<Project>,
+    path: &str,
+    line: u32,
+    symbol: &str,
+    cx: &mut App,
+) -> Task<Result<SymbolPosition>> {
+    let buffer = open_lsp_buffer(project, path, cx);
+    let path = path.to_string();
+    let symbol = symbol.to_string();
+    cx.spawn(async move |cx| {
+        let LspBuffer {
+            buffer,
+            _lsp_handle,
+        } = buffer.await?;
+        let position = buffer.read_with(cx, |buffer, _| {
+            let line_range = line_range(buffer, line, line, &path)?;
+            let line_text = buffer
+                .text_for_range(line_range.clone())
+                .collect::<String>();
+            let column = find_symbol(&line_text, &symbol)
+                .with_context(|| format!("Could not find `{symbol}` on line {line} of {path}"))?;
+            anyhow::Ok(Point::new(line_range.start.row, column as u32))
+        })?;
+        Ok(SymbolPosition {
+            buffer,
+            position,
+            _lsp_handle,
+        })
+    })
+}
+
+/// Returns the offset of `symbol` in `line`, preferring an occurrence that isn't part of a
+/// longer identifier.
+fn find_symbol(line: &str, symbol: &str) -> Option<usize> {
+    let is_identifier_char = |c: char| c.is_alphanumeric() || c == '_';
+    line.match_indices(symbol)
+        .map(|(offset, _)| offset)
+        .find(|&offset| {
+            let before = line[..offset].chars().next_back();
+            let after = line[offset + symbol.len()..].chars().next();
+            !before.is_some_and(is_identifier_char) && !after.is_some_and(is_identifier_char)
+        })
+        .or_else(|| line.find(symbol))
+}
+
+/// The edits a language server makes for a tool.
+pub(crate) enum LspEdits {
+    /// Edits that the language server proposed, which are only made once applied.
+    Proposed(ProposedWorkspaceEdit),
+    /// Edits that are only known once the language server makes them, because it runs on a
+    /// remote host, or makes them by running a command.
+    Unknown,
+}
+
+/// Checks the permission to make a language server's edits before they're made, since they may
+/// reach into any file of the project, and not only the one at `path` that the tool was given.
+/// Edits that can't be known in advance always need to be confirmed.
+pub(crate) async fn authorize_lsp_edits(
+    tool_name: &str,
+    title: String,
+    edits: &LspEdits,
+    path: &str,
+    project: &Entity<Project>,
+    event_stream: &ToolCallEventStream,
+    cx: &mut AsyncApp,
+) -> Result<()> {
+    let authorize = cx.update(|cx| {
+        let mut paths = vec![path.to_string()];
+        if let LspEdits::Proposed(edit) = edits {
+            let project = project.read(cx);
+            paths.extend(edit.abs_paths().into_iter().map(|abs_path| {
+                project
+                    .find_project_path(&abs_path, cx)
+                    .and_then(|project_path| {
+                        let worktree = project.worktree_for_id(project_path.worktree_id, cx)?;
+                        Some(worktree.read(cx).full_path(&project_path.path))
+                    })
+                    .unwrap_or(abs_path)
+                    .to_string_lossy()
+                    .into_owned()
+            }));
+        }
+
+        let settings = AgentSettings::get_global(cx);
+        let mut confirmation_paths = Vec::new();
+        for path in paths.into_iter().sorted().dedup() {
+            match decide_permission_for_path(tool_name, &path, settings) {
+                ToolPermissionDecision::Allow => {}
+                ToolPermissionDecision::Deny(reason) => return Err(anyhow!("{reason}")),
+                ToolPermissionDecision::Confirm => confirmation_paths.push(path),
+            }
+        }
+        let title = match edits {
+            LspEdits::Proposed(_) if confirmation_paths.is_empty() => return Ok(None),
+            LspEdits::Proposed(_) => format!(
+                "{title}, editing {}",
+                confirmation_paths
+                    .iter()
+                    .map(|path| MarkdownInlineCode(path).to_string())
+                    .join(", ")
+            ),
+            LspEdits::Unknown => {
+                if confirmation_paths.is_empty() {
+                    confirmation_paths.push(path.to_string());
+                }
+                format!(
+                    "{title} in {}, which may edit any file",
+                    MarkdownInlineCode(path)
+                )
+            }
+        };
+        let context = ToolPermissionContext::new(tool_name, confirmation_paths);
+        Ok(Some(event_stream.authorize(title, context, cx)))
+    })?;
+    if let Some(authorize) = authorize {
+        authorize.await?;
+    }
+    Ok(())
+}
+
+/// Waits for a language server's edits to be made, undoing them if the user cancelled the tool
+/// call in the meantime. Making the edits isn't interrupted, so that it doesn't stop halfway.
+pub(crate) async fn finish_lsp_edits(
+    edits: Task<Result<ProjectTransaction>>,
+    cancelled_message: &str,
+    event_stream: &ToolCallEventStream,
+    cx: &mut AsyncApp,
+) -> Result<ProjectTransaction> {
+    let transaction = edits.await?;
+    if event_stream.was_cancelled_by_user() {
+        cx.update(|cx| {
+            for (buffer, buffer_transaction) in &transaction.0 {
+                buffer.update(cx, |buffer, cx| {
+                    buffer.undo_transaction(buffer_transaction.id, cx);
+                });
+            }
+        });
+        anyhow::bail!("{cancelled_message}");
+    }
+    Ok(transaction)
+}
+
+/// Lists locations as `path:line: text`, one per line, skipping those in hidden files.
+pub(crate) fn format_locations(locations: &[Location], cx: &App) -> String {
+    let mut output = String::new();
+    for location in locations.iter().take(MAX_LOCATIONS) {
+        let buffer = location.buffer.read(cx);
+        let Some(file) = buffer.file() else {
+            continue;
+        };
+        if is_path_hidden(&ProjectPath::from_file(file.as_ref(), cx), cx) {
+            continue;
+        }
+        let row = location.range.start.to_point(buffer).row;
+        let line_text = buffer
+            .text_for_range(Point::new(row, 0)..Point::new(row, buffer.line_len(row)))
+            .collect::<String>();
+        writeln!(
+            output,
+            "{}:{}: {}",
+            file.full_path(cx).display(),
+            row + 1,
+            line_text.trim()
+        )
+        .ok();
+    }
+    if locations.len() > MAX_LOCATIONS {
+        writeln!(
+            output,
+            "...and {} more locations",
+            locations.len() - MAX_LOCATIONS
+        )
+        .ok();
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_find_symbol() {
+        assert_eq!(find_symbol("foo_bar(foo)", "foo"), Some(8));
+        assert_eq!(find_symbol("let foo_bar = 1;", "foo"), Some(4));
+        assert_eq!(find_symbol("fn run(self_: Self)", "Self"), Some(14));
+        assert_eq!(find_symbol("let x = 1;", "y"), None);
+    }
+}
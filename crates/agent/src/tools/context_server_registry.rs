@@ -2,11 +2,16 @@ use crate::{AgentToolOutput, AnyAgentTool, ToolCallEventStream};
 use agent_client_protocol::ToolKind;
 use anyhow::{Result, anyhow};
 use collections::{BTreeMap, HashMap};
-use context_server::{ContextServerId, client::NotificationSubscription};
+use context_server::{
+    ContextServerId, client::NotificationSubscription, protocol::InitializedContextServerProtocol,
+};
 use futures::FutureExt as _;
-use gpui::{App, AppContext, AsyncApp, Context, Entity, EventEmitter, SharedString, Task};
+use gpui::{
+    App, AppContext, AsyncApp, BackgroundExecutor, Context, Entity, EventEmitter, SharedString,
+    Task,
+};
 use project::context_server_store::{ContextServerStatus, ContextServerStore};
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 use util::ResultExt;
 
 /// Generates a tool ID for an MCP tool that can be used in settings.
@@ -21,9 +26,48 @@ pub struct ContextServerPrompt {
     pub prompt: context_server::types::Prompt,
 }
 
+pub struct ContextServerResource {
+    pub server_id: ContextServerId,
+    pub resource: context_server::types::Resource,
+}
+
+/// A subscription to the changes to a resource. The server is asked to stop reporting them once
+/// every subscription to the resource is dropped.
+pub struct ResourceSubscription {
+    client: Arc<InitializedContextServerProtocol>,
+    uri: String,
+    executor: BackgroundExecutor,
+}
+
+impl Drop for ResourceSubscription {
+    fn drop(&mut self) {
+        let client = self.client.clone();
+        let uri = std::mem::take(&mut self.uri);
+        self.executor
+            .spawn(async move {
+                client
+                    .request::<context_server::types::requests::ResourcesUnsubscribe>(
+                        context_server::types::ResourcesUnsubscribeParams {
+                            uri: uri.parse()?,
+                            meta: None,
+                        },
+                    )
+                    .await
+            })
+            // The server may have stopped since, taking its subscriptions with it.
+            .detach();
+    }
+}
+
 pub enum ContextServerRegistryEvent {
     ToolsChanged,
     PromptsChanged,
+    ResourcesChanged,
+    /// A resource the registry subscribed to was changed by its server.
+    ResourceUpdated {
+        server_id: ContextServerId,
+        uri: String,
+    },
 }
 
 impl EventEmitter<ContextServerRegistryEvent> for ContextServerRegistry {}
@@ -37,9 +81,12 @@ pub struct ContextServerRegistry {
 struct RegisteredContextServer {
     tools: BTreeMap<SharedString, Arc<dyn AnyAgentTool>>,
     prompts: BTreeMap<SharedString, ContextServerPrompt>,
+    resources: BTreeMap<SharedString, ContextServerResource>,
+    subscribed_resources: HashMap<String, Weak<ResourceSubscription>>,
     load_tools: Task<Result<()>>,
     load_prompts: Task<Result<()>>,
-    _tools_updated_subscription: Option<NotificationSubscription>,
+    load_resources: Task<Result<()>>,
+    _notification_subscriptions: Vec<NotificationSubscription>,
 }
 
 impl ContextServerRegistry {
@@ -52,6 +99,7 @@ impl ContextServerRegistry {
         for server in server_store.read(cx).running_servers() {
             this.reload_tools_for_server(server.id(), cx);
             this.reload_prompts_for_server(server.id(), cx);
+            this.reload_resources_for_server(server.id(), cx);
         }
         this
     }
@@ -102,6 +150,68 @@ impl ContextServerRegistry {
         }
     }
 
+    pub fn resources(&self) -> impl Iterator<Item = &ContextServerResource> {
+        self.registered_servers
+            .values()
+            .flat_map(|server| server.resources.values())
+    }
+
+    /// Asks the server to notify us when the resource changes, which is reported as a
+    /// [`ContextServerRegistryEvent::ResourceUpdated`] event until every subscription to the
+    /// resource is dropped. Returns `None` if the server does not support subscriptions.
+    pub fn subscribe_to_resource(
+        &mut self,
+        server_id: &ContextServerId,
+        uri: &str,
+        cx: &mut Context<Self>,
+    ) -> Option<Arc<ResourceSubscription>> {
+        let client = self
+            .server_store
+            .read(cx)
+            .get_running_server(server_id)
+            .and_then(|server| server.client())?;
+        let supports_subscriptions = client
+            .initialize
+            .capabilities
+            .resources
+            .as_ref()
+            .is_some_and(|resources| resources.subscribe == Some(true));
+        if !supports_subscriptions {
+            return None;
+        }
+        let registered_server = self.registered_servers.get_mut(server_id)?;
+        if let Some(subscription) = registered_server
+            .subscribed_resources
+            .get(uri)
+            .and_then(Weak::upgrade)
+        {
+            return Some(subscription);
+        }
+
+        let subscription = Arc::new(ResourceSubscription {
+            client: client.clone(),
+            uri: uri.to_string(),
+            executor: cx.background_executor().clone(),
+        });
+        registered_server
+            .subscribed_resources
+            .insert(uri.to_string(), Arc::downgrade(&subscription));
+
+        let uri = uri.to_string();
+        cx.background_spawn(async move {
+            client
+                .request::<context_server::types::requests::ResourcesSubscribe>(
+                    context_server::types::ResourcesSubscribeParams {
+                        uri: uri.parse()?,
+                        meta: None,
+                    },
+                )
+                .await
+        })
+        .detach_and_log_err(cx);
+        Some(subscription)
+    }
+
     pub fn server_store(&self) -> &Entity<ContextServerStore> {
         &self.server_store
     }
@@ -121,47 +231,96 @@ impl ContextServerRegistry {
         server_store: &Entity<ContextServerStore>,
         cx: &mut Context<Self>,
     ) -> RegisteredContextServer {
-        let tools_updated_subscription = server_store
+        let mut notification_subscriptions = Vec::new();
+        if let Some(client) = server_store
             .read(cx)
             .get_running_server(server_id)
-            .and_then(|server| {
-                let client = server.client()?;
-
-                if !client.capable(context_server::protocol::ServerCapability::Tools) {
-                    return None;
-                }
-
-                let server_id = server.id();
-                let this = cx.entity().downgrade();
-
-                Some(client.on_notification(
+            .and_then(|server| server.client())
+        {
+            if client.capable(context_server::protocol::ServerCapability::Tools) {
+                notification_subscriptions.push(Self::on_notification(
+                    &client,
                     "notifications/tools/list_changed",
-                    Box::new(move |_params, cx: AsyncApp| {
-                        let server_id = server_id.clone();
-                        let this = this.clone();
-                        cx.spawn(async move |cx| {
-                            this.update(cx, |this, cx| {
-                                log::info!(
-                                    "Received tools/list_changed notification for server {}",
-                                    server_id
-                                );
-                                this.reload_tools_for_server(server_id, cx);
-                            })
-                        })
-                        .detach();
-                    }),
-                ))
-            });
+                    server_id,
+                    |this, server_id, _params, cx| this.reload_tools_for_server(server_id, cx),
+                    cx,
+                ));
+            }
+            if client.capable(context_server::protocol::ServerCapability::Prompts) {
+                notification_subscriptions.push(Self::on_notification(
+                    &client,
+                    "notifications/prompts/list_changed",
+                    server_id,
+                    |this, server_id, _params, cx| this.reload_prompts_for_server(server_id, cx),
+                    cx,
+                ));
+            }
+            if client.capable(context_server::protocol::ServerCapability::Resources) {
+                notification_subscriptions.push(Self::on_notification(
+                    &client,
+                    "notifications/resources/list_changed",
+                    server_id,
+                    |this, server_id, _params, cx| this.reload_resources_for_server(server_id, cx),
+                    cx,
+                ));
+                notification_subscriptions.push(Self::on_notification(
+                    &client,
+                    "notifications/resources/updated",
+                    server_id,
+                    |_this, server_id, params, cx| {
+                        let Some(params) = serde_json::from_value::<
+                            context_server::types::ResourcesUpdatedParams,
+                        >(params)
+                        .log_err() else {
+                            return;
+                        };
+                        cx.emit(ContextServerRegistryEvent::ResourceUpdated {
+                            server_id,
+                            uri: params.uri,
+                        });
+                    },
+                    cx,
+                ));
+            }
+        }
 
         RegisteredContextServer {
             tools: BTreeMap::default(),
             prompts: BTreeMap::default(),
+            resources: BTreeMap::default(),
+            subscribed_resources: HashMap::default(),
             load_tools: Task::ready(Ok(())),
             load_prompts: Task::ready(Ok(())),
-            _tools_updated_subscription: tools_updated_subscription,
+            load_resources: Task::ready(Ok(())),
+            _notification_subscriptions: notification_subscriptions,
         }
     }
 
+    fn on_notification(
+        client: &InitializedContextServerProtocol,
+        method: &'static str,
+        server_id: &ContextServerId,
+        handler: fn(&mut Self, ContextServerId, serde_json::Value, &mut Context<Self>),
+        cx: &mut Context<Self>,
+    ) -> NotificationSubscription {
+        let server_id = server_id.clone();
+        let this = cx.entity().downgrade();
+        client.on_notification(
+            method,
+            Box::new(move |params, cx: AsyncApp| {
+                let server_id = server_id.clone();
+                let this = this.clone();
+                cx.spawn(async move |cx| {
+                    this.update(cx, |this, cx| {
+                        log::info!("Received {} notification for server {}", method, server_id);
+                        handler(this, server_id, params, cx);
+                    })
+                })
+                .detach();
+            }),
+        )
+    }
+
     fn reload_tools_for_server(&mut self, server_id: ContextServerId, cx: &mut Context<Self>) {
         let Some(server) = self.server_store.read(cx).get_running_server(&server_id) else {
             return;
@@ -244,6 +403,48 @@ impl ContextServerRegistry {
         });
     }
 
+    fn reload_resources_for_server(&mut self, server_id: ContextServerId, cx: &mut Context<Self>) {
+        let Some(server) = self.server_store.read(cx).get_running_server(&server_id) else {
+            return;
+        };
+        let Some(client) = server.client() else {
+            return;
+        };
+        if !client.capable(context_server::protocol::ServerCapability::Resources) {
+            return;
+        }
+
+        let registered_server = self.get_or_register_server(&server_id, cx);
+
+        registered_server.load_resources = cx.spawn(async move |this, cx| {
+            let response = client
+                .request::<context_server::types::requests::ResourcesList>(())
+                .await;
+
+            this.update(cx, |this, cx| {
+                let Some(registered_server) = this.registered_servers.get_mut(&server_id) else {
+                    return;
+                };
+
+                registered_server.resources.clear();
+                if let Some(response) = response.log_err() {
+                    for resource in response.resources {
+                        let uri: SharedString = resource.uri.to_string().into();
+                        registered_server.resources.insert(
+                            uri,
+                            ContextServerResource {
+                                server_id: server_id.clone(),
+                                resource,
+                            },
+                        );
+                    }
+                    cx.emit(ContextServerRegistryEvent::ResourcesChanged);
+                    cx.notify();
+                }
+            })
+        });
+    }
+
     fn handle_context_server_store_event(
         &mut self,
         _: Entity<ContextServerStore>,
@@ -257,6 +458,7 @@ impl ContextServerRegistry {
             ContextServerStatus::Running => {
                 self.reload_tools_for_server(server_id.clone(), cx);
                 self.reload_prompts_for_server(server_id.clone(), cx);
+                self.reload_resources_for_server(server_id.clone(), cx);
             }
             ContextServerStatus::Stopped | ContextServerStatus::Error(_) => {
                 if let Some(registered_server) = self.registered_servers.remove(server_id) {
@@ -266,6 +468,9 @@ impl ContextServerRegistry {
                     if !registered_server.prompts.is_empty() {
                         cx.emit(ContextServerRegistryEvent::PromptsChanged);
                     }
+                    if !registered_server.resources.is_empty() {
+                        cx.emit(ContextServerRegistryEvent::ResourcesChanged);
+                    }
                 }
                 cx.notify();
             }
@@ -454,6 +659,49 @@ pub fn get_prompt(
     })
 }
 
+/// Reads the text contents of a resource. Binary contents are left out.
+pub fn read_resource(
+    server_store: &Entity<ContextServerStore>,
+    server_id: &ContextServerId,
+    uri: &str,
+    cx: &App,
+) -> Task<Result<String>> {
+    let Some(server) = server_store.read(cx).get_running_server(server_id) else {
+        return Task::ready(Err(anyhow!("Context server not found")));
+    };
+    let Some(protocol) = server.client() else {
+        return Task::ready(Err(anyhow!("Context server not initialized")));
+    };
+
+    let uri = uri.to_string();
+    cx.background_spawn(async move {
+        let response = protocol
+            .request::<context_server::types::requests::ResourcesRead>(
+                context_server::types::ResourcesReadParams {
+                    uri: uri.parse()?,
+                    meta: None,
+                },
+            )
+            .await?;
+
+        let mut text = String::new();
+        for contents in response.contents {
+            match contents {
+                context_server::types::ResourceContentsType::Text(contents) => {
+                    if !text.is_empty() {
+                        text.push('\n');
+                    }
+                    text.push_str(&contents.text);
+                }
+                context_server::types::ResourceContentsType::Blob(_) => {
+                    log::warn!("Ignoring blob contents of resource {uri}");
+                }
+            }
+        }
+        Ok(text)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,271 @@
+use super::code_navigation::{format_locations, resolve_symbol_position, visible_locations};
+use crate::{AgentTool, ToolCallEventStream};
+use agent_client_protocol as acp;
+use anyhow::Result;
+use futures::FutureExt as _;
+use gpui::{App, Entity, SharedString, Task};
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use util::markdown::MarkdownInlineCode;
+
+/// Finds every reference to a symbol across the project, using the language server of its file.
+///
+/// Prefer this tool to searching with grep when changing how a symbol is used, since it only lists real references to that symbol, and not unrelated symbols with the same name.
+///
+/// <example>
+/// To find the references to the `Config` struct, defined on line 10 of `src/config.rs`:
+/// {
+///     "path": "project/src/config.rs",
+///     "line": 10,
+///     "symbol": "Config"
+/// }
+/// </example>
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct FindReferencesToolInput {
+    /// The path of a file the symbol is defined or used in.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    pub path: String,
+    /// The 1-based line number the symbol is on.
+    pub line: u32,
+    /// The name of the symbol, exactly as it appears on the line.
+    pub symbol: String,
+}
+
+pub struct FindReferencesTool {
+    project: Entity<Project>,
+}
+
+impl FindReferencesTool {
+    pub fn new(project: Entity<Project>) -> Self {
+        Self { project }
+    }
+}
+
+impl AgentTool for FindReferencesTool {
+    type Input = FindReferencesToolInput;
+    type Output = String;
+
+    const NAME: &'static str = "find_references";
+
+    fn kind() -> acp::ToolKind {
+        acp::ToolKind::Search
+    }
+
+    fn initial_title(
+        &self,
+        input: Result<Self::Input, serde_json::Value>,
+        _cx: &mut App,
+    ) -> SharedString {
+        match input {
+            Ok(input) => format!("Find references to {}", MarkdownInlineCode(&input.symbol)).into(),
+            Err(_) => "Find references".into(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: Self::Input,
+        event_stream: ToolCallEventStream,
+        cx: &mut App,
+    ) -> Task<Result<Self::Output>> {
+        let symbol_position =
+            resolve_symbol_position(&self.project, &input.path, input.line, &input.symbol, cx);
+        let project = self.project.clone();
+        cx.spawn(async move |cx| {
+            let symbol_position = symbol_position.await?;
+            let references = project.update(cx, |project, cx| {
+                project.references(&symbol_position.buffer, symbol_position.position, cx)
+            });
+            let references = futures::select! {
+                references = references.fuse() => references?,
+                _ = event_stream.cancelled_by_user().fuse() => {
+                    anyhow::bail!("Find references cancelled by user");
+                }
+            };
+
+            let references =
+                cx.update(|cx| visible_locations(references.unwrap_or_default(), cx));
+            if references.is_empty() {
+                return Ok(format!(
+                    "No references found for `{}`. The file's language server may not support this, or may still be starting.",
+                    input.symbol
+                ));
+            }
+            Ok(cx.update(|cx| {
+                format!(
+                    "Found {} references:\n{}",
+                    references.len(),
+                    format_locations(&references, cx)
+                )
+            }))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt as _;
+    use gpui::{TestAppContext, UpdateGlobal as _};
+    use language::{Language, LanguageConfig, LanguageMatcher};
+    use project::{FakeFs, lsp_store::OpenLspBufferHandle};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    /// Creates a project whose language server finds the references to `helper` in both files.
+    async fn init_test(cx: &mut TestAppContext) -> (Entity<Project>, OpenLspBufferHandle) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                "src": {
+                    "lib.rs": "pub fn helper() {}\n",
+                    "main.rs": "fn main() {\n    helper();\n    helper();\n}\n",
+                }
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        )));
+        let mut fake_language_servers = language_registry.register_fake_lsp(
+            "Rust",
+            language::FakeLspAdapter {
+                capabilities: lsp::ServerCapabilities {
+                    references_provider: Some(lsp::OneOf::Left(true)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        // Start the language server before running the tool, so that it can answer the request.
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/root/src/main.rs"), cx)
+            })
+            .await
+            .unwrap();
+        let handle = project.update(cx, |project, cx| {
+            project.register_buffer_with_language_servers(&buffer, cx)
+        });
+        let fake_language_server = fake_language_servers.next().await.unwrap();
+        fake_language_server.set_request_handler::<lsp::request::References, _, _>(
+            |params, _| async move {
+                assert_eq!(
+                    params.text_document_position.position,
+                    lsp::Position::new(1, 4)
+                );
+                let main_uri = params.text_document_position.text_document.uri;
+                let lib_uri = lsp::Uri::from_file_path(path!("/root/src/lib.rs")).unwrap();
+                Ok(Some(vec![
+                    lsp::Location::new(
+                        lib_uri,
+                        lsp::Range::new(lsp::Position::new(0, 7), lsp::Position::new(0, 13)),
+                    ),
+                    lsp::Location::new(
+                        main_uri.clone(),
+                        lsp::Range::new(lsp::Position::new(1, 4), lsp::Position::new(1, 10)),
+                    ),
+                    lsp::Location::new(
+                        main_uri,
+                        lsp::Range::new(lsp::Position::new(2, 4), lsp::Position::new(2, 10)),
+                    ),
+                ]))
+            },
+        );
+        (project, handle)
+    }
+
+    async fn find_references(
+        project: &Entity<Project>,
+        path: &str,
+        line: u32,
+        cx: &mut TestAppContext,
+    ) -> Result<String> {
+        let tool = Arc::new(FindReferencesTool::new(project.clone()));
+        cx.update(|cx| {
+            tool.run(
+                FindReferencesToolInput {
+                    path: path.into(),
+                    line,
+                    symbol: "helper".into(),
+                },
+                ToolCallEventStream::test().0,
+                cx,
+            )
+        })
+        .await
+    }
+
+    #[gpui::test]
+    async fn test_find_references(cx: &mut TestAppContext) {
+        let (project, _handle) = init_test(cx).await;
+        let output = find_references(&project, "root/src/main.rs", 2, cx)
+            .await
+            .unwrap();
+        assert_eq!(
+            output,
+            format!(
+                "Found 3 references:\n{}:1: pub fn helper() {{}}\n{}:2: helper();\n{}:3: helper();\n",
+                path!("root/src/lib.rs"),
+                path!("root/src/main.rs"),
+                path!("root/src/main.rs"),
+            )
+        );
+    }
+
+    #[gpui::test]
+    async fn test_find_references_in_private_files(cx: &mut TestAppContext) {
+        let (project, _handle) = init_test(cx).await;
+        cx.update(|cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings.project.worktree.private_files =
+                        Some(vec!["**/lib.rs".to_string()].into());
+                });
+            });
+        });
+
+        let output = find_references(&project, "root/src/main.rs", 2, cx)
+            .await
+            .unwrap();
+        assert_eq!(
+            output,
+            format!(
+                "Found 2 references:\n{}:2: helper();\n{}:3: helper();\n",
+                path!("root/src/main.rs"),
+                path!("root/src/main.rs"),
+            )
+        );
+
+        let error = find_references(&project, "root/src/lib.rs", 1, cx)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Cannot query file because its path matches the global `private_files` setting: root/src/lib.rs"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use super::git_history::{format_date, truncate_output};
+use crate::{AgentTool, ToolCallEventStream};
+use agent_client_protocol as acp;
+use anyhow::{Context as _, Result, anyhow};
+use collections::HashSet;
+use futures::FutureExt as _;
+use git::{SHORT_SHA_LENGTH, blame::BlameEntry};
+use gpui::{App, Entity, SharedString, Task};
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Write, sync::Arc};
+use util::markdown::MarkdownInlineCode;
+
+/// Shows which commit last changed each line of a file, along with the date, author and subject of those commits.
+///
+/// - Use this tool rather than running `git blame` in the terminal.
+/// - Use it to find out why some code was written the way it is, then use the `git_show` tool to see the commits it lists.
+/// - Lines that were changed since the last commit, including unsaved changes, are attributed to `0000000`.
+///
+/// <example>
+/// To find out which commits wrote lines 40 to 60 of `src/config.rs`:
+/// {
+///     "path": "project/src/config.rs",
+///     "start_line": 40,
+///     "end_line": 60
+/// }
+/// </example>
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GitBlameToolInput {
+    /// The path of the file to blame.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    pub path: String,
+    /// The 1-based line number of the first line to blame. Defaults to the first line of the file.
+    #[serde(default)]
+    pub start_line: Option<u32>,
+    /// The 1-based, inclusive line number of the last line to blame. Defaults to the last line of the file.
+    #[serde(default)]
+    pub end_line: Option<u32>,
+}
+
+pub struct GitBlameTool {
+    project: Entity<Project>,
+}
+
+impl GitBlameTool {
+    pub fn new(project: Entity<Project>) -> Self {
+        Self { project }
+    }
+}
+
+impl AgentTool for GitBlameTool {
+    type Input = GitBlameToolInput;
+    type Output = String;
+
+    const NAME: &'static str = "git_blame";
+
+    fn kind() -> acp::ToolKind {
+        acp::ToolKind::Read
+    }
+
+    fn initial_title(
+        &self,
+        input: Result<Self::Input, serde_json::Value>,
+        _cx: &mut App,
+    ) -> SharedString {
+        match input {
+            Ok(input) => format!("Git blame {}", MarkdownInlineCode(&input.path)).into(),
+            Err(_) => "Git blame".into(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: Self::Input,
+        event_stream: ToolCallEventStream,
+        cx: &mut App,
+    ) -> Task<Result<Self::Output>> {
+        let Some(project_path) = self.project.read(cx).find_project_path(&input.path, cx) else {
+            return Task::ready(Err(anyhow!(
+                "Could not find path {} in project",
+                input.path
+            )));
+        };
+        let open_buffer = self
+            .project
+            .update(cx, |project, cx| project.open_buffer(project_path, cx));
+
+        let project = self.project.clone();
+        cx.spawn(async move |cx| {
+            let buffer = open_buffer.await?;
+            let blame = project.update(cx, |project, cx| project.blame_buffer(&buffer, None, cx));
+            let blame = futures::select! {
+                blame = blame.fuse() => blame?,
+                _ = event_stream.cancelled_by_user().fuse() => {
+                    anyhow::bail!("Git blame cancelled by user");
+                }
+            };
+            let blame = blame.with_context(|| format!("Could not blame {}", input.path))?;
+
+            let text = buffer.read_with(cx, |buffer, _| buffer.text());
+            let output = format_blame(&text, &blame.entries, input.start_line, input.end_line)?;
+            Ok(truncate_output(
+                output,
+                "Pass `start_line` and `end_line` to blame fewer lines.",
+            ))
+        })
+    }
+}
+
+/// Lists the commits that changed the given 1-based lines, followed by the lines annotated with
+/// the abbreviated hashes of those commits.
+fn format_blame(
+    text: &str,
+    entries: &[BlameEntry],
+    start_line: Option<u32>,
+    end_line: Option<u32>,
+) -> Result<String> {
+    let lines = text.lines().collect::<Vec<_>>();
+    let line_count = lines.len() as u32;
+    let start_line = start_line.unwrap_or(1).max(1);
+    let end_line = end_line.unwrap_or(line_count).min(line_count);
+    anyhow::ensure!(
+        start_line <= end_line,
+        "The file has {line_count} lines, so it has no lines {start_line} to {end_line} to blame"
+    );
+
+    let mut commits = String::new();
+    let mut blamed_lines = String::new();
+    let mut listed_commits = HashSet::default();
+    for row in start_line - 1..end_line {
+        // Git leaves out the lines that were changed since the last commit.
+        let ix = entries.partition_point(|entry| entry.range.end <= row);
+        let entry = entries.get(ix).filter(|entry| entry.range.contains(&row));
+        let sha = match entry {
+            Some(entry) => {
+                let sha = entry.sha.display_short();
+                if listed_commits.insert(Some(entry.sha)) {
+                    writeln!(
+                        commits,
+                        "{sha} {} {}: {}",
+                        entry.author_time.map(format_date).unwrap_or_default(),
+                        entry.author.as_deref().unwrap_or_default(),
+                        entry.summary.as_deref().unwrap_or_default()
+                    )?;
+                }
+                sha
+            }
+            None => {
+                let sha = "0".repeat(SHORT_SHA_LENGTH);
+                if listed_commits.insert(None) {
+                    writeln!(commits, "{sha} Not committed yet")?;
+                }
+                sha
+            }
+        };
+        writeln!(blamed_lines, "{} {sha} | {}", row + 1, lines[row as usize])?;
+    }
+    Ok(format!("{commits}\n{blamed_lines}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::str::FromStr as _;
+
+    #[test]
+    fn test_format_blame() {
+        let text = "fn main() {\n    load();\n    run();\n}\n";
+        let entries = [
+            BlameEntry {
+                sha: git::Oid::from_str("3f2a9c1e5d7b8a6f4c2e0d9b7a5f3e1c8d6b4a2f").unwrap(),
+                range: 0..2,
+                author: Some("Ada".into()),
+                author_time: Some(1_700_000_000),
+                summary: Some("Load the config".into()),
+                ..Default::default()
+            },
+            BlameEntry {
+                sha: git::Oid::from_str("9b1d4e7a2c5f8e3b6d9a1c4f7e2b5d8a3c6f9e1b").unwrap(),
+                range: 3..4,
+                author: Some("Grace".into()),
+                author_time: Some(1_600_000_000),
+                summary: Some("Add main".into()),
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(
+            format_blame(text, &entries, None, None).unwrap(),
+            concat!(
+                "3f2a9c1 2023-11-14 Ada: Load the config\n",
+                "0000000 Not committed yet\n",
+                "9b1d4e7 2020-09-13 Grace: Add main\n",
+                "\n",
+                "1 3f2a9c1 | fn main() {\n",
+                "2 3f2a9c1 |     load();\n",
+                "3 0000000 |     run();\n",
+                "4 9b1d4e7 | }\n",
+            )
+        );
+        assert_eq!(
+            format_blame(text, &entries, Some(2), Some(3)).unwrap(),
+            concat!(
+                "3f2a9c1 2023-11-14 Ada: Load the config\n",
+                "0000000 Not committed yet\n",
+                "\n",
+                "2 3f2a9c1 |     load();\n",
+                "3 0000000 |     run();\n",
+            )
+        );
+        assert!(format_blame(text, &entries, Some(5), None).is_err());
+    }
+}
@@ -0,0 +1,208 @@
+use super::git_history::{repository_for_path, truncate_output};
+use crate::{AgentTool, ToolCallEventStream};
+use agent_client_protocol as acp;
+use anyhow::Result;
+use futures::FutureExt as _;
+use git::repository::DiffType;
+use gpui::{App, Entity, SharedString, Task};
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use util::markdown::MarkdownInlineCode;
+
+/// Shows the changes between two revisions of the project's git repository, or between a revision and the files on disk, as a unified diff.
+///
+/// - Use this tool rather than running `git diff` in the terminal.
+/// - Without a `base` or `head`, shows the changes that haven't been committed yet.
+/// - Pass a `base` without a `head` to see what changed since then, including the changes that haven't been committed yet, e.g. `v1.2.0` for the changes since a release.
+/// - Pass a `path` to only see the changes to a file or directory.
+///
+/// <example>
+/// To see what changed in `src/config.rs` between the `v1.2.0` tag and `main`:
+/// {
+///     "base": "v1.2.0",
+///     "head": "main",
+///     "path": "project/src/config.rs"
+/// }
+/// </example>
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GitDiffToolInput {
+    /// The revision to compare from, like a commit hash, branch or tag. Defaults to `HEAD`.
+    #[serde(default)]
+    pub base: Option<String>,
+    /// The revision to compare to. Defaults to the files on disk.
+    #[serde(default)]
+    pub head: Option<String>,
+    /// The path of a file or directory to show the changes of. Without a path, the changes to the whole repository are shown.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+pub struct GitDiffTool {
+    project: Entity<Project>,
+}
+
+impl GitDiffTool {
+    pub fn new(project: Entity<Project>) -> Self {
+        Self { project }
+    }
+}
+
+impl AgentTool for GitDiffTool {
+    type Input = GitDiffToolInput;
+    type Output = String;
+
+    const NAME: &'static str = "git_diff";
+
+    fn kind() -> acp::ToolKind {
+        acp::ToolKind::Read
+    }
+
+    fn initial_title(
+        &self,
+        input: Result<Self::Input, serde_json::Value>,
+        _cx: &mut App,
+    ) -> SharedString {
+        let Ok(input) = input else {
+            return "Git diff".into();
+        };
+        let base = input.base.as_deref().unwrap_or("HEAD");
+        match &input.head {
+            Some(head) => format!(
+                "Git diff {}",
+                MarkdownInlineCode(&format!("{base}..{head}"))
+            )
+            .into(),
+            None => format!("Git diff since {}", MarkdownInlineCode(base)).into(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: Self::Input,
+        event_stream: ToolCallEventStream,
+        cx: &mut App,
+    ) -> Task<Result<Self::Output>> {
+        let (repository, repo_path) =
+            match repository_for_path(&self.project, input.path.as_deref(), cx) {
+                Ok(repository) => repository,
+                Err(error) => return Task::ready(Err(error)),
+            };
+        let diff_type = DiffType::Refs {
+            base_ref: input.base.unwrap_or_else(|| "HEAD".into()).into(),
+            head_ref: input.head.map(Into::into),
+            path: repo_path,
+        };
+        let diff = repository.update(cx, |repository, cx| repository.diff(diff_type, cx));
+
+        cx.spawn(async move |_cx| {
+            let diff = futures::select! {
+                diff = diff.fuse() => diff??,
+                _ = event_stream.cancelled_by_user().fuse() => {
+                    anyhow::bail!("Git diff cancelled by user");
+                }
+            };
+            if diff.is_empty() {
+                return Ok("No changes.".to_string());
+            }
+            Ok(truncate_output(
+                diff,
+                "Pass a `path` to see the changes of some files only.",
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_git_diff(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+        });
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                ".git": {},
+                "src": {
+                    "config.rs": "fn load() {}\n",
+                    "main.rs": "fn main() {}\n",
+                },
+            }),
+        )
+        .await;
+        fs.set_commits_for_repo(
+            Path::new(path!("/root/.git")),
+            &[
+                (
+                    "Add main",
+                    &[
+                        ("src/config.rs", Some("fn parse() {}\n")),
+                        ("src/main.rs", Some("fn main() {}\n")),
+                    ],
+                ),
+                (
+                    "Read the config",
+                    &[("src/config.rs", Some("fn read() {}\n"))],
+                ),
+            ],
+        );
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+        cx.run_until_parked();
+        let tool = Arc::new(GitDiffTool::new(project));
+        let mut diff = |base: Option<&str>, head: Option<&str>, path: Option<&str>| {
+            let input = GitDiffToolInput {
+                base: base.map(Into::into),
+                head: head.map(Into::into),
+                path: path.map(Into::into),
+            };
+            cx.update(|cx| tool.clone().run(input, ToolCallEventStream::test().0, cx))
+        };
+
+        // Without revisions, the changes that haven't been committed yet are shown.
+        assert_eq!(
+            diff(None, None, None).await.unwrap(),
+            concat!(
+                "diff --git a/src/config.rs b/src/config.rs\n",
+                "--- a/src/config.rs\n",
+                "+++ b/src/config.rs\n",
+                "@@ -1,1 +1,1 @@\n",
+                "-fn read() {}\n",
+                "+fn load() {}\n",
+            )
+        );
+        assert_eq!(
+            diff(Some("HEAD~1"), Some("HEAD"), None).await.unwrap(),
+            concat!(
+                "diff --git a/src/config.rs b/src/config.rs\n",
+                "--- a/src/config.rs\n",
+                "+++ b/src/config.rs\n",
+                "@@ -1,1 +1,1 @@\n",
+                "-fn parse() {}\n",
+                "+fn read() {}\n",
+            )
+        );
+        assert_eq!(
+            diff(Some("HEAD~1"), None, Some("root/src/main.rs"))
+                .await
+                .unwrap(),
+            "No changes."
+        );
+        assert!(diff(Some("v1.0"), None, None).await.is_err());
+    }
+}
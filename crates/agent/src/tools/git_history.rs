@@ -0,0 +1,87 @@
+//! Helpers shared by the tools that read the history of the project's git repositories.
+
+use anyhow::{Context as _, Result};
+use chrono::DateTime;
+use git::{SHORT_SHA_LENGTH, repository::RepoPath};
+use gpui::{App, Entity};
+use project::{Project, git_store::Repository};
+use util::truncate_lines_to_byte_limit;
+
+/// The most bytes of git output a tool returns, so that large diffs don't fill the context window.
+pub(crate) const MAX_OUTPUT_BYTES: usize = 24 * 1024;
+
+/// Returns the repository containing the project path, and the path within that repository.
+///
+/// Without a path, returns the active repository. The repository path is `None` when it would
+/// cover the whole repository.
+pub(crate) fn repository_for_path(
+    project: &Entity<Project>,
+    path: Option<&str>,
+    cx: &App,
+) -> Result<(Entity<Repository>, Option<RepoPath>)> {
+    let project = project.read(cx);
+    let git_store = project.git_store().read(cx);
+    let Some(path) = path else {
+        let repository = git_store
+            .active_repository()
+            .context("The project isn't in a git repository")?;
+        return Ok((repository, None));
+    };
+
+    let project_path = project
+        .find_project_path(path, cx)
+        .with_context(|| format!("Could not find path {path} in project"))?;
+    let (repository, repo_path) = git_store
+        .repository_and_path_for_project_path(&project_path, cx)
+        .with_context(|| format!("{path} isn't in a git repository"))?;
+    Ok((repository, (!repo_path.is_empty()).then_some(repo_path)))
+}
+
+/// Abbreviates a commit hash the way `git log --oneline` does.
+pub(crate) fn short_sha(sha: &str) -> &str {
+    &sha[..sha.len().min(SHORT_SHA_LENGTH)]
+}
+
+/// Formats a commit timestamp as a date, which is precise enough to place a commit in history.
+pub(crate) fn format_date(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Truncates the output to whole lines that fit in [`MAX_OUTPUT_BYTES`], explaining how to see the rest.
+pub(crate) fn truncate_output(output: String, hint: &str) -> String {
+    if output.len() <= MAX_OUTPUT_BYTES {
+        return output;
+    }
+    let truncated = truncate_lines_to_byte_limit(&output, MAX_OUTPUT_BYTES);
+    format!(
+        "{truncated}\n[Output truncated after {} of {} bytes. {hint}]",
+        truncated.len(),
+        output.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_truncate_output() {
+        assert_eq!(truncate_output("short\n".into(), "hint"), "short\n");
+
+        let line = "x".repeat(99) + "\n";
+        let output = line.repeat(MAX_OUTPUT_BYTES / 100 + 10);
+        let truncated = truncate_output(output.clone(), "Pass a path.");
+        let kept = MAX_OUTPUT_BYTES / 100 * 100;
+        assert_eq!(&truncated[..kept], &output[..kept]);
+        assert_eq!(
+            &truncated[kept..],
+            format!(
+                "\n[Output truncated after {kept} of {} bytes. Pass a path.]",
+                output.len()
+            )
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use super::git_history::{format_date, repository_for_path, short_sha};
+use crate::{AgentTool, ToolCallEventStream};
+use agent_client_protocol as acp;
+use anyhow::Result;
+use futures::FutureExt as _;
+use gpui::{App, Entity, SharedString, Task};
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Write, sync::Arc};
+use util::markdown::MarkdownInlineCode;
+
+/// The number of commits listed when the input doesn't give a limit.
+const DEFAULT_LIMIT: usize = 20;
+/// The most commits listed, whatever the input's limit.
+const MAX_LIMIT: usize = 100;
+
+/// Lists the commits of the project's git repository, newest first, with their hashes, dates, authors and subjects.
+///
+/// - Use this tool rather than running `git log` in the terminal.
+/// - Pass a `path` to only list the commits that changed a file or directory, e.g. to find out why some code was written the way it is.
+/// - Pass a `range` to only list some commits, e.g. `v1.2.0..HEAD` for the commits since a release.
+/// - Use the `git_show` tool to see the full message and changes of a commit.
+///
+/// <example>
+/// To list the commits that changed `src/config.rs` since the `v1.2.0` tag:
+/// {
+///     "path": "project/src/config.rs",
+///     "range": "v1.2.0..HEAD"
+/// }
+/// </example>
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GitLogToolInput {
+    /// The path of a file or directory to list the commits of. Without a path, the commits of the whole repository are listed.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    #[serde(default)]
+    pub path: Option<String>,
+    /// A revision or revision range to list the commits of, like `main`, `v1.2.0..HEAD` or `origin/main..`. Defaults to `HEAD`.
+    #[serde(default)]
+    pub range: Option<String>,
+    /// The most commits to list. Defaults to 20, and can't be more than 100.
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+pub struct GitLogTool {
+    project: Entity<Project>,
+}
+
+impl GitLogTool {
+    pub fn new(project: Entity<Project>) -> Self {
+        Self { project }
+    }
+}
+
+impl AgentTool for GitLogTool {
+    type Input = GitLogToolInput;
+    type Output = String;
+
+    const NAME: &'static str = "git_log";
+
+    fn kind() -> acp::ToolKind {
+        acp::ToolKind::Read
+    }
+
+    fn initial_title(
+        &self,
+        input: Result<Self::Input, serde_json::Value>,
+        _cx: &mut App,
+    ) -> SharedString {
+        match input {
+            Ok(GitLogToolInput {
+                path: Some(path), ..
+            }) => format!("Git log of {}", MarkdownInlineCode(&path)).into(),
+            Ok(GitLogToolInput {
+                range: Some(range), ..
+            }) => format!("Git log of {}", MarkdownInlineCode(&range)).into(),
+            _ => "Git log".into(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: Self::Input,
+        event_stream: ToolCallEventStream,
+        cx: &mut App,
+    ) -> Task<Result<Self::Output>> {
+        let (repository, repo_path) =
+            match repository_for_path(&self.project, input.path.as_deref(), cx) {
+                Ok(repository) => repository,
+                Err(error) => return Task::ready(Err(error)),
+            };
+        let limit = input.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        let log = repository.update(cx, |repository, _| {
+            repository.log(input.range.clone(), repo_path, limit)
+        });
+
+        cx.spawn(async move |_cx| {
+            let entries = futures::select! {
+                entries = log.fuse() => entries??,
+                _ = event_stream.cancelled_by_user().fuse() => {
+                    anyhow::bail!("Git log cancelled by user");
+                }
+            };
+            if entries.is_empty() {
+                return Ok("No commits found.".to_string());
+            }
+
+            let mut output = String::new();
+            for entry in &entries {
+                writeln!(
+                    output,
+                    "{} {} {}: {}",
+                    short_sha(&entry.sha),
+                    format_date(entry.commit_timestamp),
+                    entry.author_name,
+                    entry.subject
+                )?;
+            }
+            if entries.len() == limit {
+                writeln!(
+                    output,
+                    "[Listed the first {limit} commits. Pass a narrower `range` or a larger `limit` to see the others.]"
+                )?;
+            }
+            Ok(output)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_git_log(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+        });
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                ".git": {},
+                "README.md": "# Root\n",
+                "src": { "main.rs": "fn run() {}\n" },
+            }),
+        )
+        .await;
+        let shas = fs.set_commits_for_repo(
+            Path::new(path!("/root/.git")),
+            &[
+                ("Add a readme", &[("README.md", Some("# Root\n"))]),
+                (
+                    "Add main\n\nIt does nothing yet.",
+                    &[("src/main.rs", Some("fn main() {}\n"))],
+                ),
+                ("Rename main", &[("src/main.rs", Some("fn run() {}\n"))]),
+            ],
+        );
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+        cx.run_until_parked();
+        let tool = Arc::new(GitLogTool::new(project));
+        let line = |ix: usize, subject: &str| {
+            format!(
+                "{} 1970-01-01 Test Author: {subject}\n",
+                short_sha(&shas[ix].to_string())
+            )
+        };
+        let mut log = |path: Option<&str>, range: Option<&str>, limit: Option<usize>| {
+            let input = GitLogToolInput {
+                path: path.map(Into::into),
+                range: range.map(Into::into),
+                limit,
+            };
+            cx.update(|cx| tool.clone().run(input, ToolCallEventStream::test().0, cx))
+        };
+
+        assert_eq!(
+            log(None, None, None).await.unwrap(),
+            line(2, "Rename main") + &line(1, "Add main") + &line(0, "Add a readme")
+        );
+        assert_eq!(
+            log(Some("root/src"), None, None).await.unwrap(),
+            line(2, "Rename main") + &line(1, "Add main")
+        );
+        assert_eq!(
+            log(None, Some("HEAD~2..HEAD~1"), None).await.unwrap(),
+            line(1, "Add main")
+        );
+        assert_eq!(
+            log(Some("root/README.md"), Some("HEAD~1.."), None)
+                .await
+                .unwrap(),
+            "No commits found."
+        );
+        assert_eq!(
+            log(None, None, Some(1)).await.unwrap(),
+            line(2, "Rename main")
+                + "[Listed the first 1 commits. Pass a narrower `range` or a larger `limit` to see the others.]\n"
+        );
+        assert!(log(None, Some("v1.0..HEAD"), None).await.is_err());
+    }
+}
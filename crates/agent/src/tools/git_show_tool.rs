@@ -0,0 +1,211 @@
+use super::git_history::{format_date, repository_for_path, truncate_output};
+use crate::{AgentTool, ToolCallEventStream};
+use agent_client_protocol as acp;
+use anyhow::Result;
+use futures::FutureExt as _;
+use git::repository::{CommitDetails, CommitFile, RepoPath};
+use gpui::{App, Entity, SharedString, Task};
+use language::unified_diff;
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Write, sync::Arc};
+use util::markdown::MarkdownInlineCode;
+
+/// Shows a commit of the project's git repository: its author, date and full message, and the changes it made.
+///
+/// - Use this tool rather than running `git show` in the terminal.
+/// - Pass a `path` to only see the changes the commit made to a file or directory.
+///
+/// <example>
+/// To see what commit `3f2a9c1` changed in `src/config.rs`:
+/// {
+///     "commit": "3f2a9c1",
+///     "path": "project/src/config.rs"
+/// }
+/// </example>
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GitShowToolInput {
+    /// The commit to show, as a hash or any other revision, like `HEAD~2` or a tag.
+    pub commit: String,
+    /// The path of a file or directory to show the changes of. Without a path, all the changes of the commit are shown.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+pub struct GitShowTool {
+    project: Entity<Project>,
+}
+
+impl GitShowTool {
+    pub fn new(project: Entity<Project>) -> Self {
+        Self { project }
+    }
+}
+
+impl AgentTool for GitShowTool {
+    type Input = GitShowToolInput;
+    type Output = String;
+
+    const NAME: &'static str = "git_show";
+
+    fn kind() -> acp::ToolKind {
+        acp::ToolKind::Read
+    }
+
+    fn initial_title(
+        &self,
+        input: Result<Self::Input, serde_json::Value>,
+        _cx: &mut App,
+    ) -> SharedString {
+        match input {
+            Ok(input) => format!("Show commit {}", MarkdownInlineCode(&input.commit)).into(),
+            Err(_) => "Show commit".into(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: Self::Input,
+        event_stream: ToolCallEventStream,
+        cx: &mut App,
+    ) -> Task<Result<Self::Output>> {
+        let (repository, repo_path) =
+            match repository_for_path(&self.project, input.path.as_deref(), cx) {
+                Ok(repository) => repository,
+                Err(error) => return Task::ready(Err(error)),
+            };
+        let (details, diff) = repository.update(cx, |repository, _| {
+            (
+                repository.show(input.commit.clone()),
+                repository.load_commit_diff(input.commit.clone()),
+            )
+        });
+
+        cx.spawn(async move |_cx| {
+            let (details, diff) = futures::select! {
+                result = futures::future::join(details, diff).fuse() => result,
+                _ = event_stream.cancelled_by_user().fuse() => {
+                    anyhow::bail!("Showing commit cancelled by user");
+                }
+            };
+            let details = details??;
+            let diff = diff??;
+
+            let files = diff
+                .files
+                .iter()
+                .filter(|file| {
+                    repo_path
+                        .as_ref()
+                        .is_none_or(|repo_path| file.path.starts_with(repo_path))
+                })
+                .collect::<Vec<_>>();
+            let output = format_commit(&details, &files)?;
+            Ok(truncate_output(
+                output,
+                "Pass a `path` to see the changes of some files only.",
+            ))
+        })
+    }
+}
+
+fn format_commit(details: &CommitDetails, files: &[&CommitFile]) -> Result<String> {
+    let mut output = String::new();
+    writeln!(output, "commit {}", details.sha)?;
+    writeln!(
+        output,
+        "Author: {} <{}>",
+        details.author_name, details.author_email
+    )?;
+    writeln!(output, "Date: {}", format_date(details.commit_timestamp))?;
+    writeln!(output)?;
+    writeln!(output, "{}", details.message.trim_end())?;
+
+    if files.is_empty() {
+        writeln!(output, "\nNo changes.")?;
+    }
+    for file in files {
+        let path = file.path.as_unix_str();
+        writeln!(output)?;
+        if file.is_binary {
+            writeln!(output, "Binary file {path} changed")?;
+            continue;
+        }
+        let old_path = old_or_new_path(&file.path, file.old_text.is_some(), "a");
+        let new_path = old_or_new_path(&file.path, file.new_text.is_some(), "b");
+        writeln!(output, "--- {old_path}")?;
+        writeln!(output, "+++ {new_path}")?;
+        output.push_str(&unified_diff(
+            file.old_text.as_deref().unwrap_or_default(),
+            file.new_text.as_deref().unwrap_or_default(),
+        ));
+    }
+    Ok(output)
+}
+
+fn old_or_new_path(path: &RepoPath, exists: bool, prefix: &str) -> String {
+    if exists {
+        format!("{prefix}/{}", path.as_unix_str())
+    } else {
+        "/dev/null".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git::repository::repo_path;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_format_commit() {
+        let details = CommitDetails {
+            sha: "3f2a9c1e5d7b8a6f4c2e0d9b7a5f3e1c8d6b4a2f".into(),
+            message: "Load the config lazily\n\nIt's slow to parse.\n".into(),
+            commit_timestamp: 1_700_000_000,
+            author_email: "ada@example.com".into(),
+            author_name: "Ada".into(),
+        };
+        let edited = CommitFile {
+            path: repo_path("src/config.rs"),
+            old_text: Some("fn load() {}\nfn parse() {}\n".into()),
+            new_text: Some("fn load_lazily() {}\nfn parse() {}\n".into()),
+            is_binary: false,
+        };
+        let deleted = CommitFile {
+            path: repo_path("src/old.rs"),
+            old_text: Some("fn old() {}\n".into()),
+            new_text: None,
+            is_binary: false,
+        };
+
+        assert_eq!(
+            format_commit(&details, &[&edited, &deleted]).unwrap(),
+            concat!(
+                "commit 3f2a9c1e5d7b8a6f4c2e0d9b7a5f3e1c8d6b4a2f\n",
+                "Author: Ada <ada@example.com>\n",
+                "Date: 2023-11-14\n",
+                "\n",
+                "Load the config lazily\n",
+                "\n",
+                "It's slow to parse.\n",
+                "\n",
+                "--- a/src/config.rs\n",
+                "+++ b/src/config.rs\n",
+                "@@ -1,2 +1,2 @@\n",
+                "-fn load() {}\n",
+                "+fn load_lazily() {}\n",
+                " fn parse() {}\n",
+                "\n",
+                "--- a/src/old.rs\n",
+                "+++ /dev/null\n",
+                "@@ -1,1 +1,0 @@\n",
+                "-fn old() {}\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use super::code_navigation::{format_locations, resolve_symbol_position, visible_locations};
+use crate::{AgentTool, ToolCallEventStream};
+use agent_client_protocol as acp;
+use anyhow::Result;
+use futures::FutureExt as _;
+use gpui::{App, Entity, SharedString, Task};
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use util::markdown::MarkdownInlineCode;
+
+/// Finds where a symbol is defined, using the language server of its file.
+///
+/// Prefer this tool to searching with grep when you know where a symbol is used, since it resolves the exact definition, even through imports, re-exports and methods with common names.
+///
+/// <example>
+/// To find the definition of `parse_config`, called on line 42 of `src/main.rs`:
+/// {
+///     "path": "project/src/main.rs",
+///     "line": 42,
+///     "symbol": "parse_config"
+/// }
+/// </example>
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GoToDefinitionToolInput {
+    /// The path of the file the symbol is used in.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    pub path: String,
+    /// The 1-based line number the symbol is on.
+    pub line: u32,
+    /// The name of the symbol, exactly as it appears on the line.
+    pub symbol: String,
+}
+
+pub struct GoToDefinitionTool {
+    project: Entity<Project>,
+}
+
+impl GoToDefinitionTool {
+    pub fn new(project: Entity<Project>) -> Self {
+        Self { project }
+    }
+}
+
+impl AgentTool for GoToDefinitionTool {
+    type Input = GoToDefinitionToolInput;
+    type Output = String;
+
+    const NAME: &'static str = "go_to_definition";
+
+    fn kind() -> acp::ToolKind {
+        acp::ToolKind::Search
+    }
+
+    fn initial_title(
+        &self,
+        input: Result<Self::Input, serde_json::Value>,
+        _cx: &mut App,
+    ) -> SharedString {
+        match input {
+            Ok(input) => {
+                format!("Go to definition of {}", MarkdownInlineCode(&input.symbol)).into()
+            }
+            Err(_) => "Go to definition".into(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: Self::Input,
+        event_stream: ToolCallEventStream,
+        cx: &mut App,
+    ) -> Task<Result<Self::Output>> {
+        let symbol_position =
+            resolve_symbol_position(&self.project, &input.path, input.line, &input.symbol, cx);
+        let project = self.project.clone();
+        cx.spawn(async move |cx| {
+            let symbol_position = symbol_position.await?;
+            let definitions = project.update(cx, |project, cx| {
+                project.definitions(&symbol_position.buffer, symbol_position.position, cx)
+            });
+            let definitions = futures::select! {
+                definitions = definitions.fuse() => definitions?,
+                _ = event_stream.cancelled_by_user().fuse() => {
+                    anyhow::bail!("Go to definition cancelled by user");
+                }
+            };
+
+            let locations = definitions
+                .unwrap_or_default()
+                .into_iter()
+                .map(|definition| definition.target)
+                .collect::<Vec<_>>();
+            let locations = cx.update(|cx| visible_locations(locations, cx));
+            if locations.is_empty() {
+                return Ok(format!(
+                    "No definition found for `{}`. The file's language server may not support this, or may still be starting.",
+                    input.symbol
+                ));
+            }
+            Ok(cx.update(|cx| format_locations(&locations, cx)))
+        })
+    }
+}
@@ -0,0 +1,113 @@
+use super::code_navigation::resolve_symbol_position;
+use crate::{AgentTool, ToolCallEventStream};
+use agent_client_protocol as acp;
+use anyhow::Result;
+use futures::FutureExt as _;
+use gpui::{App, Entity, SharedString, Task};
+use project::{HoverBlockKind, Project};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use util::markdown::{MarkdownCodeBlock, MarkdownInlineCode};
+
+/// Shows what the language server knows about a symbol: its type or signature, and its documentation.
+///
+/// Use this tool to learn the inferred type of a variable, the signature of a function or the documentation of a symbol without reading the files it's defined in.
+///
+/// <example>
+/// To see the type of the `settings` variable on line 25 of `src/app.rs`:
+/// {
+///     "path": "project/src/app.rs",
+///     "line": 25,
+///     "symbol": "settings"
+/// }
+/// </example>
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct HoverToolInput {
+    /// The path of the file the symbol is in.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    pub path: String,
+    /// The 1-based line number the symbol is on.
+    pub line: u32,
+    /// The name of the symbol, exactly as it appears on the line.
+    pub symbol: String,
+}
+
+pub struct HoverTool {
+    project: Entity<Project>,
+}
+
+impl HoverTool {
+    pub fn new(project: Entity<Project>) -> Self {
+        Self { project }
+    }
+}
+
+impl AgentTool for HoverTool {
+    type Input = HoverToolInput;
+    type Output = String;
+
+    const NAME: &'static str = "hover";
+
+    fn kind() -> acp::ToolKind {
+        acp::ToolKind::Read
+    }
+
+    fn initial_title(
+        &self,
+        input: Result<Self::Input, serde_json::Value>,
+        _cx: &mut App,
+    ) -> SharedString {
+        match input {
+            Ok(input) => format!("Get info about {}", MarkdownInlineCode(&input.symbol)).into(),
+            Err(_) => "Get symbol info".into(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: Self::Input,
+        event_stream: ToolCallEventStream,
+        cx: &mut App,
+    ) -> Task<Result<Self::Output>> {
+        let symbol_position =
+            resolve_symbol_position(&self.project, &input.path, input.line, &input.symbol, cx);
+        let project = self.project.clone();
+        cx.spawn(async move |cx| {
+            let symbol_position = symbol_position.await?;
+            let hovers = project.update(cx, |project, cx| {
+                project.hover(&symbol_position.buffer, symbol_position.position, cx)
+            });
+            let hovers = futures::select! {
+                hovers = hovers.fuse() => hovers,
+                _ = event_stream.cancelled_by_user().fuse() => {
+                    anyhow::bail!("Hover cancelled by user");
+                }
+            };
+
+            let blocks = hovers
+                .unwrap_or_default()
+                .into_iter()
+                .flat_map(|hover| hover.contents)
+                .filter(|block| !block.text.trim().is_empty())
+                .map(|block| match block.kind {
+                    HoverBlockKind::Code { language } => MarkdownCodeBlock {
+                        tag: &language,
+                        text: &block.text,
+                    }
+                    .to_string(),
+                    HoverBlockKind::PlainText | HoverBlockKind::Markdown => block.text,
+                })
+                .collect::<Vec<_>>();
+            if blocks.is_empty() {
+                return Ok(format!(
+                    "No information found for `{}`. The file's language server may not support this, or may still be starting.",
+                    input.symbol
+                ));
+            }
+            Ok(blocks.join("\n\n"))
+        })
+    }
+}
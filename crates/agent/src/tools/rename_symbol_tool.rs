@@ -0,0 +1,418 @@
+use super::code_navigation::{
+    LspEdits, authorize_lsp_edits, finish_lsp_edits, resolve_symbol_position,
+};
+use crate::{AgentTool, ToolCallEventStream, ToolPermissionDecision, decide_permission_for_path};
+use action_log::ActionLog;
+use agent_client_protocol as acp;
+use agent_settings::AgentSettings;
+use anyhow::{Result, anyhow};
+use collections::HashSet;
+use futures::FutureExt as _;
+use gpui::{App, Entity, SharedString, Task};
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use std::{fmt::Write, sync::Arc};
+use util::markdown::MarkdownInlineCode;
+
+/// Renames a symbol and every reference to it across the project, using the language server of its file.
+///
+/// - Prefer this tool to editing files one by one when renaming a variable, function, type or module, since the language server finds every reference, including the ones grep would miss.
+/// - The edited files are saved, and the user can review the edits like any other edit.
+///
+/// <example>
+/// To rename the `load` function, defined on line 12 of `src/config.rs`, to `load_config`:
+/// {
+///     "path": "project/src/config.rs",
+///     "line": 12,
+///     "symbol": "load",
+///     "new_name": "load_config"
+/// }
+/// </example>
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RenameSymbolToolInput {
+    /// The path of a file the symbol is defined or used in.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    pub path: String,
+    /// The 1-based line number the symbol is on.
+    pub line: u32,
+    /// The current name of the symbol, exactly as it appears on the line.
+    pub symbol: String,
+    /// The new name of the symbol.
+    pub new_name: String,
+}
+
+pub struct RenameSymbolTool {
+    project: Entity<Project>,
+    action_log: Entity<ActionLog>,
+}
+
+impl RenameSymbolTool {
+    pub fn new(project: Entity<Project>, action_log: Entity<ActionLog>) -> Self {
+        Self {
+            project,
+            action_log,
+        }
+    }
+}
+
+impl AgentTool for RenameSymbolTool {
+    type Input = RenameSymbolToolInput;
+    type Output = String;
+
+    const NAME: &'static str = "rename_symbol";
+
+    fn kind() -> acp::ToolKind {
+        acp::ToolKind::Edit
+    }
+
+    fn initial_title(
+        &self,
+        input: Result<Self::Input, serde_json::Value>,
+        _cx: &mut App,
+    ) -> SharedString {
+        match input {
+            Ok(input) => format!(
+                "Rename {} to {}",
+                MarkdownInlineCode(&input.symbol),
+                MarkdownInlineCode(&input.new_name)
+            )
+            .into(),
+            Err(_) => "Rename symbol".into(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: Self::Input,
+        event_stream: ToolCallEventStream,
+        cx: &mut App,
+    ) -> Task<Result<Self::Output>> {
+        let settings = AgentSettings::get_global(cx);
+        if let ToolPermissionDecision::Deny(reason) =
+            decide_permission_for_path(Self::NAME, &input.path, settings)
+        {
+            return Task::ready(Err(anyhow!("{reason}")));
+        }
+
+        let symbol_position =
+            resolve_symbol_position(&self.project, &input.path, input.line, &input.symbol, cx);
+        let project = self.project.clone();
+        let action_log = self.action_log.clone();
+        cx.spawn(async move |cx| {
+            let symbol_position = symbol_position.await?;
+
+            // Remote language servers apply the edits of a rename themselves, so they can only
+            // be authorized up front.
+            let edits = if project.read_with(cx, |project, _| project.is_local()) {
+                let proposed_edit = project.update(cx, |project, cx| {
+                    project.propose_rename(
+                        symbol_position.buffer.clone(),
+                        symbol_position.position,
+                        input.new_name.clone(),
+                        cx,
+                    )
+                });
+                let proposed_edit = futures::select! {
+                    proposed_edit = proposed_edit.fuse() => proposed_edit?,
+                    _ = event_stream.cancelled_by_user().fuse() => {
+                        anyhow::bail!("Rename cancelled by user");
+                    }
+                };
+                let Some(proposed_edit) = proposed_edit else {
+                    anyhow::bail!(
+                        "The language server didn't rename `{}`. It may not support renaming, or may still be starting.",
+                        input.symbol
+                    );
+                };
+                LspEdits::Proposed(proposed_edit)
+            } else {
+                LspEdits::Unknown
+            };
+
+            let title = format!(
+                "Rename {} to {}",
+                MarkdownInlineCode(&input.symbol),
+                MarkdownInlineCode(&input.new_name)
+            );
+            authorize_lsp_edits(
+                Self::NAME,
+                title,
+                &edits,
+                &input.path,
+                &project,
+                &event_stream,
+                cx,
+            )
+            .await?;
+
+            let rename = project.update(cx, |project, cx| match edits {
+                LspEdits::Proposed(proposed_edit) => {
+                    project.apply_workspace_edit(proposed_edit, true, cx)
+                }
+                LspEdits::Unknown => project.perform_rename(
+                    symbol_position.buffer.clone(),
+                    symbol_position.position,
+                    input.new_name.clone(),
+                    cx,
+                ),
+            });
+            let rename = action_log.update(cx, |action_log, cx| {
+                action_log.track_project_transaction(rename, cx)
+            });
+            let transaction =
+                finish_lsp_edits(rename, "Rename cancelled by user", &event_stream, cx).await?;
+            if transaction.0.is_empty() {
+                anyhow::bail!(
+                    "The language server didn't rename `{}`. It may not support renaming, or may still be starting.",
+                    input.symbol
+                );
+            }
+
+            let buffers = transaction.0.into_keys().collect::<HashSet<_>>();
+            let mut paths = cx.update(|cx| {
+                buffers
+                    .iter()
+                    .filter_map(|buffer| Some(buffer.read(cx).file()?.full_path(cx)))
+                    .collect::<Vec<_>>()
+            });
+            paths.sort();
+            project
+                .update(cx, |project, cx| project.save_buffers(buffers, cx))
+                .await?;
+
+            let mut output = format!(
+                "Renamed `{}` to `{}` in {} files:\n",
+                input.symbol,
+                input.new_name,
+                paths.len()
+            );
+            for path in paths {
+                writeln!(output, "- {}", path.display())?;
+            }
+            Ok(output)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::Fs as _;
+    use futures::StreamExt as _;
+    use gpui::{AppContext as _, TestAppContext};
+    use language::{Language, LanguageConfig, LanguageMatcher};
+    use project::{FakeFs, lsp_store::OpenLspBufferHandle};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    struct RenameTest {
+        fs: Arc<FakeFs>,
+        action_log: Entity<ActionLog>,
+        tool: Arc<RenameSymbolTool>,
+        _lsp_handle: OpenLspBufferHandle,
+    }
+
+    /// Sets up a project whose language server renames `helper` in `src/lib.rs` and
+    /// `src/main.rs`.
+    async fn init_test(
+        cx: &mut TestAppContext,
+        update_settings: impl FnOnce(&mut AgentSettings),
+    ) -> RenameTest {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            let mut settings = AgentSettings::get_global(cx).clone();
+            settings.tool_permissions.default = settings::ToolPermissionMode::Allow;
+            update_settings(&mut settings);
+            AgentSettings::override_global(settings, cx);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                "src": {
+                    "lib.rs": "pub fn helper() {}\n",
+                    "main.rs": "fn main() {\n    helper();\n}\n",
+                }
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+        let action_log = cx.new(|_| ActionLog::new(project.clone()));
+
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        )));
+        let mut fake_language_servers = language_registry.register_fake_lsp(
+            "Rust",
+            language::FakeLspAdapter {
+                capabilities: lsp::ServerCapabilities {
+                    rename_provider: Some(lsp::OneOf::Left(true)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/root/src/main.rs"), cx)
+            })
+            .await
+            .unwrap();
+        let lsp_handle = project.update(cx, |project, cx| {
+            project.register_buffer_with_language_servers(&buffer, cx)
+        });
+        let fake_language_server = fake_language_servers.next().await.unwrap();
+        fake_language_server.set_request_handler::<lsp::request::Rename, _, _>(
+            |params, _| async move {
+                assert_eq!(params.new_name, "assist");
+                let main_uri = params.text_document_position.text_document.uri;
+                let lib_uri = lsp::Uri::from_file_path(path!("/root/src/lib.rs")).unwrap();
+                Ok(Some(lsp::WorkspaceEdit {
+                    changes: Some(
+                        [
+                            (
+                                lib_uri,
+                                vec![lsp::TextEdit::new(
+                                    lsp::Range::new(
+                                        lsp::Position::new(0, 7),
+                                        lsp::Position::new(0, 13),
+                                    ),
+                                    "assist".into(),
+                                )],
+                            ),
+                            (
+                                main_uri,
+                                vec![lsp::TextEdit::new(
+                                    lsp::Range::new(
+                                        lsp::Position::new(1, 4),
+                                        lsp::Position::new(1, 10),
+                                    ),
+                                    "assist".into(),
+                                )],
+                            ),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    ),
+                    ..Default::default()
+                }))
+            },
+        );
+
+        RenameTest {
+            fs,
+            action_log: action_log.clone(),
+            tool: Arc::new(RenameSymbolTool::new(project, action_log)),
+            _lsp_handle: lsp_handle,
+        }
+    }
+
+    fn rename_input() -> RenameSymbolToolInput {
+        RenameSymbolToolInput {
+            path: "root/src/main.rs".into(),
+            line: 2,
+            symbol: "helper".into(),
+            new_name: "assist".into(),
+        }
+    }
+
+    #[gpui::test]
+    async fn test_rename_symbol(cx: &mut TestAppContext) {
+        let RenameTest {
+            fs,
+            action_log,
+            tool,
+            _lsp_handle,
+        } = init_test(cx, |_| {}).await;
+
+        let output = cx
+            .update(|cx| tool.run(rename_input(), ToolCallEventStream::test().0, cx))
+            .await
+            .unwrap();
+        assert_eq!(
+            output,
+            format!(
+                "Renamed `helper` to `assist` in 2 files:\n- {}\n- {}\n",
+                path!("root/src/lib.rs"),
+                path!("root/src/main.rs"),
+            )
+        );
+        cx.run_until_parked();
+
+        assert_eq!(
+            fs.load(path!("/root/src/main.rs").as_ref()).await.unwrap(),
+            "fn main() {\n    assist();\n}\n"
+        );
+        let changed_files = action_log.read_with(cx, |action_log, cx| {
+            let mut changed_files = action_log
+                .changed_buffers(cx)
+                .into_keys()
+                .map(|buffer| buffer.read(cx).text())
+                .collect::<Vec<_>>();
+            changed_files.sort();
+            changed_files
+        });
+        assert_eq!(
+            changed_files,
+            ["fn main() {\n    assist();\n}\n", "pub fn assist() {}\n"]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_rename_symbol_in_denied_file(cx: &mut TestAppContext) {
+        let RenameTest {
+            fs,
+            action_log,
+            tool,
+            _lsp_handle,
+        } = init_test(cx, |settings| {
+            settings.tool_permissions.tools.insert(
+                RenameSymbolTool::NAME.into(),
+                agent_settings::ToolRules {
+                    always_deny: vec![
+                        agent_settings::CompiledRegex::new(r"lib\.rs$", false).unwrap(),
+                    ],
+                    ..Default::default()
+                },
+            );
+        })
+        .await;
+
+        // Only `main.rs` is given to the tool, but the rename reaches into `lib.rs`, so nothing
+        // is renamed.
+        let result = cx
+            .update(|cx| tool.run(rename_input(), ToolCallEventStream::test().0, cx))
+            .await;
+        assert!(result.is_err(), "{result:?}");
+        cx.run_until_parked();
+
+        let changed_buffers =
+            action_log.read_with(cx, |action_log, cx| action_log.changed_buffers(cx).len());
+        assert_eq!(changed_buffers, 0);
+        assert_eq!(
+            fs.load(path!("/root/src/lib.rs").as_ref()).await.unwrap(),
+            "pub fn helper() {}\n"
+        );
+        assert_eq!(
+            fs.load(path!("/root/src/main.rs").as_ref()).await.unwrap(),
+            "fn main() {\n    helper();\n}\n"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use super::code_navigation::is_path_hidden;
+use crate::{AgentTool, ToolCallEventStream};
+use agent_client_protocol as acp;
+use anyhow::Result;
+use futures::FutureExt as _;
+use gpui::{App, Entity, SharedString, Task};
+use project::{Project, lsp_store::SymbolLocation};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Write, sync::Arc};
+use util::markdown::MarkdownInlineCode;
+
+/// The most symbols to list, since language servers match queries loosely.
+const MAX_SYMBOLS: usize = 50;
+
+/// Searches the symbols of the whole project, such as types, functions and constants, by name, using the project's language servers.
+///
+/// - Prefer this tool to grep to find where a symbol is defined when you only know its name.
+/// - Language servers match the query fuzzily, so the results may include symbols with similar names.
+/// - Only the files of languages with a running language server are searched.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WorkspaceSymbolsToolInput {
+    /// The name, or part of the name, of the symbols to find.
+    pub query: String,
+}
+
+pub struct WorkspaceSymbolsTool {
+    project: Entity<Project>,
+}
+
+impl WorkspaceSymbolsTool {
+    pub fn new(project: Entity<Project>) -> Self {
+        Self { project }
+    }
+}
+
+impl AgentTool for WorkspaceSymbolsTool {
+    type Input = WorkspaceSymbolsToolInput;
+    type Output = String;
+
+    const NAME: &'static str = "workspace_symbols";
+
+    fn kind() -> acp::ToolKind {
+        acp::ToolKind::Search
+    }
+
+    fn initial_title(
+        &self,
+        input: Result<Self::Input, serde_json::Value>,
+        _cx: &mut App,
+    ) -> SharedString {
+        match input {
+            Ok(input) => format!("Search symbols for {}", MarkdownInlineCode(&input.query)).into(),
+            Err(_) => "Search symbols".into(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: Self::Input,
+        event_stream: ToolCallEventStream,
+        cx: &mut App,
+    ) -> Task<Result<Self::Output>> {
+        let symbols = self
+            .project
+            .update(cx, |project, cx| project.symbols(&input.query, cx));
+        let project = self.project.clone();
+        cx.spawn(async move |cx| {
+            let symbols = futures::select! {
+                symbols = symbols.fuse() => symbols?,
+                _ = event_stream.cancelled_by_user().fuse() => {
+                    anyhow::bail!("Symbol search cancelled by user");
+                }
+            };
+            let symbols = cx.update(|cx| {
+                symbols
+                    .into_iter()
+                    .filter(|symbol| match &symbol.path {
+                        SymbolLocation::InProject(project_path) => {
+                            !is_path_hidden(project_path, cx)
+                        }
+                        SymbolLocation::OutsideProject { .. } => true,
+                    })
+                    .collect::<Vec<_>>()
+            });
+            if symbols.is_empty() {
+                return Ok(format!(
+                    "No symbols found for `{}`. The project's language servers may not support this, or may still be starting.",
+                    input.query
+                ));
+            }
+
+            project.read_with(cx, |project, cx| {
+                let path_style = project.path_style(cx);
+                let mut output = String::new();
+                for symbol in symbols.iter().take(MAX_SYMBOLS) {
+                    let path = match &symbol.path {
+                        SymbolLocation::InProject(project_path) => {
+                            let Some(worktree) =
+                                project.worktree_for_id(project_path.worktree_id, cx)
+                            else {
+                                continue;
+                            };
+                            worktree
+                                .read(cx)
+                                .root_name()
+                                .join(&project_path.path)
+                                .display(path_style)
+                                .to_string()
+                        }
+                        SymbolLocation::OutsideProject { abs_path, .. } => {
+                            abs_path.display().to_string()
+                        }
+                    };
+                    writeln!(
+                        output,
+                        "{} ({}:{})",
+                        symbol.label.text(),
+                        path,
+                        symbol.range.start.0.row + 1
+                    )?;
+                }
+                if symbols.len() > MAX_SYMBOLS {
+                    writeln!(
+                        output,
+                        "...and {} more symbols. Use a more specific query to narrow them down.",
+                        symbols.len() - MAX_SYMBOLS
+                    )?;
+                }
+                anyhow::Ok(output)
+            })
+        })
+    }
+}
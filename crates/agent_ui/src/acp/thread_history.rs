@@ -1,6 +1,9 @@
 use crate::acp::AcpServerView;
 use crate::{AgentPanel, RemoveHistory, RemoveSelectedThread};
-use acp_thread::{AgentSessionInfo, AgentSessionList, AgentSessionListRequest, SessionListUpdate};
+use acp_thread::{
+    AgentSessionInfo, AgentSessionList, AgentSessionListRequest, SessionListUpdate,
+    forked_from_session_id_from_meta,
+};
 use agent_client_protocol as acp;
 use chrono::{Datelike as _, Local, NaiveDate, TimeDelta, Utc};
 use editor::{Editor, EditorEvent};
@@ -660,6 +663,19 @@ impl AcpThreadHistory {
                 EntryTimeFormat::DateAndTime.format_timestamp(time.timestamp(), self.local_timezone)
             })
             .unwrap_or_else(|| "Unknown".to_string());
+        let forked_from = forked_from_session_id_from_meta(&entry.meta).map(|forked_from_id| {
+            self.sessions
+                .iter()
+                .find(|session| session.session_id == forked_from_id)
+                .map_or_else(
+                    || "a deleted thread".to_string(),
+                    |session| format!("\"{}\"", thread_title(session)),
+                )
+        });
+        let tooltip_meta = match &forked_from {
+            Some(forked_from) => format!("{full_date} · Forked from {forked_from}"),
+            None => full_date,
+        };
 
         h_flex()
             .w_full()
@@ -675,9 +691,24 @@ impl AcpThreadHistory {
                             .gap_2()
                             .justify_between()
                             .child(
-                                HighlightedLabel::new(thread_title(entry), highlight_positions)
-                                    .size(LabelSize::Small)
-                                    .truncate(),
+                                h_flex()
+                                    .min_w_0()
+                                    .gap_1()
+                                    .when(forked_from.is_some(), |this| {
+                                        this.child(
+                                            Icon::new(IconName::GitBranch)
+                                                .size(IconSize::XSmall)
+                                                .color(Color::Muted),
+                                        )
+                                    })
+                                    .child(
+                                        HighlightedLabel::new(
+                                            thread_title(entry),
+                                            highlight_positions,
+                                        )
+                                        .size(LabelSize::Small)
+                                        .truncate(),
+                                    ),
                             )
                             .child(
                                 Label::new(display_text)
@@ -686,7 +717,7 @@ impl AcpThreadHistory {
                             ),
                     )
                     .tooltip(move |_, cx| {
-                        Tooltip::with_meta(title.clone(), None, full_date.clone(), cx)
+                        Tooltip::with_meta(title.clone(), None, tooltip_meta.clone(), cx)
                     })
                     .on_hover(cx.listener(move |this, is_hovered, _window, cx| {
                         if *is_hovered {
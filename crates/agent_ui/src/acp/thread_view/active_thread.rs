@@ -1594,6 +1594,127 @@ impl AcpThreadView {
             .detach_and_log_err(cx);
     }
 
+    /// Forks the thread into a new one that keeps the messages that came before the given message,
+    /// then opens it.
+    pub fn fork_thread(
+        &mut self,
+        message_id: &UserMessageId,
+        in_worktree: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(connection) = self.as_native_connection(cx) else {
+            return;
+        };
+        let session_id = self.thread.read(cx).session_id().clone();
+        let title = self.thread.read(cx).title();
+        let fork = connection.fork_thread(session_id, message_id.clone(), in_worktree, cx);
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let fork_id = match fork.await {
+                Ok(fork_id) => fork_id,
+                Err(error) => {
+                    this.update(cx, |this, cx| this.handle_any_thread_error(error, cx))?;
+                    return anyhow::Ok(());
+                }
+            };
+            workspace.update_in(cx, |workspace, window, cx| {
+                if in_worktree {
+                    struct ThreadForkedToast;
+                    workspace.show_toast(
+                        Toast::new(
+                            NotificationId::unique::<ThreadForkedToast>(),
+                            "Forked thread into a new git worktree",
+                        )
+                        .autohide(),
+                        cx,
+                    );
+                }
+                if let Some(panel) = workspace.panel::<AgentPanel>(cx) {
+                    panel.update(cx, |panel, cx| {
+                        panel.open_thread(
+                            AgentSessionInfo {
+                                session_id: fork_id,
+                                cwd: None,
+                                title: Some(title),
+                                updated_at: None,
+                                meta: None,
+                            },
+                            window,
+                            cx,
+                        )
+                    });
+                }
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Forks the thread from the message being edited, if any.
+    fn fork_edited_message(
+        &mut self,
+        in_worktree: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry_ix) = self.editing_message else {
+            return;
+        };
+        let Some(AgentThreadEntry::UserMessage(message)) =
+            self.thread.read(cx).entries().get(entry_ix)
+        else {
+            return;
+        };
+        let Some(message_id) = message.id.clone() else {
+            return;
+        };
+        if in_worktree && message.checkpoint.is_none() {
+            return;
+        }
+        self.fork_thread(&message_id, in_worktree, window, cx);
+    }
+
+    fn render_fork_buttons(
+        &self,
+        message_id: &UserMessageId,
+        has_checkpoint: bool,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        h_flex()
+            .gap_1()
+            .child(
+                IconButton::new("fork", IconName::GitBranch)
+                    .icon_color(Color::Muted)
+                    .icon_size(IconSize::XSmall)
+                    .tooltip(Tooltip::text(
+                        "Fork a new thread from the conversation before this message.",
+                    ))
+                    .on_click(cx.listener({
+                        let message_id = message_id.clone();
+                        move |this, _, window, cx| {
+                            this.fork_thread(&message_id, false, window, cx);
+                        }
+                    })),
+            )
+            .when(has_checkpoint, |this| {
+                this.child(
+                    IconButton::new("fork-in-worktree", IconName::GitBranchPlus)
+                        .icon_color(Color::Muted)
+                        .icon_size(IconSize::XSmall)
+                        .tooltip(Tooltip::text(
+                            "Fork a new thread from the conversation before this message, in a new git worktree with the project's files as they were at this point.",
+                        ))
+                        .on_click(cx.listener({
+                            let message_id = message_id.clone();
+                            move |this, _, window, cx| {
+                                this.fork_thread(&message_id, true, window, cx);
+                            }
+                        })),
+                )
+            })
+    }
+
     pub fn clear_thread_error(&mut self, cx: &mut Context<Self>) {
         self.thread_error = None;
         self.thread_error_markdown = None;
@@ -3452,6 +3573,10 @@ impl AcpThreadView {
 
                 let agent_name = self.agent_name.clone();
                 let is_subagent = self.is_subagent();
+                let can_fork = self.as_native_connection(cx).is_some();
+                let has_checkpoint = message.checkpoint.is_some();
+
+                let message_group = SharedString::from(format!("user-message-{entry_ix}"));
 
                 let non_editable_icon = || {
                     IconButton::new("non_editable", IconName::PencilUnavailable)
@@ -3503,6 +3628,7 @@ impl AcpThreadView {
                     .child(
                         div()
                             .relative()
+                            .group(message_group.clone())
                             .child(
                                 div()
                                     .py_3()
@@ -3534,6 +3660,27 @@ impl AcpThreadView {
                                     .text_xs()
                                     .child(editor.clone().into_any_element())
                             )
+                            .when_some(
+                                message
+                                    .id
+                                    .clone()
+                                    .filter(|_| can_fork && !editor_focus && !is_subagent),
+                                |this, message_id| {
+                                    this.child(
+                                        h_flex()
+                                            .absolute()
+                                            .top_neg_3p5()
+                                            .right_3()
+                                            .rounded_sm()
+                                            .border_1()
+                                            .border_color(cx.theme().colors().border)
+                                            .bg(cx.theme().colors().editor_background)
+                                            .overflow_hidden()
+                                            .visible_on_hover(message_group)
+                                            .child(self.render_fork_buttons(&message_id, has_checkpoint, cx)),
+                                    )
+                                },
+                            )
                             .when(editor_focus, |this| {
                                 let base_container = h_flex()
                                     .absolute()
@@ -3560,9 +3707,12 @@ impl AcpThreadView {
                                             }),
                                         ),
                                     )
-                                } else if message.id.is_some() {
+                                } else if let Some(message_id) = message.id.clone() {
                                     this.child(
                                         base_container
+                                            .when(can_fork, |this| {
+                                                this.child(self.render_fork_buttons(&message_id, has_checkpoint, cx))
+                                            })
                                             .child(
                                                 IconButton::new("cancel", IconName::Close)
                                                     .disabled(is_loading_contents)
@@ -7367,6 +7517,12 @@ impl Render for AcpThreadView {
                     });
                 }
             }))
+            .on_action(cx.listener(|this, _: &ForkThread, window, cx| {
+                this.fork_edited_message(false, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &ForkThreadInWorktree, window, cx| {
+                this.fork_edited_message(true, window, cx);
+            }))
             .on_action(cx.listener(|this, _: &CycleThinkingEffort, _window, cx| {
                 this.cycle_thinking_effort(cx);
             }))
@@ -7614,6 +7770,7 @@ pub(crate) fn open_link(
             MentionUri::Diagnostics { .. } => {}
             MentionUri::TerminalSelection { .. } => {}
             MentionUri::GitDiff { .. } => {}
+            MentionUri::McpResource { .. } => {}
         })
     } else {
         cx.open_url(&url);
@@ -22,6 +22,7 @@ use crate::{
     ToggleOptionsMenu,
     acp::AcpServerView,
     agent_configuration::{AgentConfiguration, AssistantConfigurationEvent},
+    context_server_requests::ContextServerRequestsView,
     slash_command::SlashCommandCompletionProvider,
     text_thread_editor::{AgentPanelDelegate, TextThreadEditor, make_lsp_adapter_delegate},
     ui::EndTrialUpsell,
@@ -464,6 +465,7 @@ pub struct AgentPanel {
     text_thread_store: Entity<assistant_text_thread::TextThreadStore>,
     prompt_store: Option<Entity<PromptStore>>,
     context_server_registry: Entity<ContextServerRegistry>,
+    context_server_requests: Entity<ContextServerRequestsView>,
     configuration: Option<Entity<AgentConfiguration>>,
     configuration_subscription: Option<Subscription>,
     focus_handle: FocusHandle,
@@ -630,6 +632,14 @@ impl AgentPanel {
 
         let context_server_registry =
             cx.new(|cx| ContextServerRegistry::new(project.read(cx).context_server_store(), cx));
+        let context_server_requests = cx.new(|cx| {
+            ContextServerRequestsView::new(
+                project.read(cx).context_server_store(),
+                fs.clone(),
+                window,
+                cx,
+            )
+        });
 
         let thread_store = ThreadStore::global(cx);
         let acp_history = cx.new(|cx| AcpThreadHistory::new(None, window, cx));
@@ -744,6 +754,7 @@ impl AgentPanel {
             configuration_subscription: None,
             focus_handle: cx.focus_handle(),
             context_server_registry,
+            context_server_requests,
             previous_view: None,
             _active_view_observation: None,
             new_thread_menu_handle: PopoverMenuHandle::default(),
@@ -3162,6 +3173,7 @@ impl Render for AgentPanel {
             }))
             .child(self.render_toolbar(window, cx))
             .children(self.render_workspace_trust_message(cx))
+            .child(self.context_server_requests.clone())
             .children(self.render_onboarding(window, cx))
             .map(|parent| {
                 // Emit configuration error telemetry before entering the match to avoid borrow conflicts
@@ -8,6 +8,7 @@ mod buffer_codegen;
 mod completion_provider;
 mod context;
 mod context_server_configuration;
+mod context_server_requests;
 mod favorite_models;
 mod inline_assistant;
 mod inline_prompt_editor;
@@ -149,6 +150,11 @@ actions!(
         CycleThinkingEffort,
         /// Toggles the thinking effort selector menu open or closed.
         ToggleThinkingEffortMenu,
+        /// Forks a new thread from the conversation before the message being edited.
+        ForkThread,
+        /// Forks a new thread from the conversation before the message being edited, in a new git
+        /// worktree with the project's files as they were at that point.
+        ForkThreadInWorktree,
     ]
 );
 
@@ -543,6 +543,7 @@ impl CodegenAlternative {
                 stop: Vec::new(),
                 temperature,
                 messages,
+                max_output_tokens: None,
                 thinking_allowed: false,
                 thinking_effort: None,
             }
@@ -622,6 +623,7 @@ impl CodegenAlternative {
                 stop: Vec::new(),
                 temperature,
                 messages: vec![request_message],
+                max_output_tokens: None,
                 thinking_allowed: false,
                 thinking_effort: None,
             }
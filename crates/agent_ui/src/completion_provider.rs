@@ -63,6 +63,7 @@ pub(crate) enum PromptContextType {
     Thread,
     Rules,
     Diagnostics,
+    Resource,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -101,6 +102,7 @@ impl TryFrom<&str> for PromptContextType {
             "thread" => Ok(Self::Thread),
             "rule" => Ok(Self::Rules),
             "diagnostics" => Ok(Self::Diagnostics),
+            "resource" => Ok(Self::Resource),
             _ => Err(format!("Invalid context picker mode: {}", value)),
         }
     }
@@ -115,6 +117,7 @@ impl PromptContextType {
             Self::Thread => "thread",
             Self::Rules => "rule",
             Self::Diagnostics => "diagnostics",
+            Self::Resource => "resource",
         }
     }
 
@@ -126,6 +129,7 @@ impl PromptContextType {
             Self::Thread => "Threads",
             Self::Rules => "Rules",
             Self::Diagnostics => "Diagnostics",
+            Self::Resource => "MCP Resources",
         }
     }
 
@@ -137,6 +141,7 @@ impl PromptContextType {
             Self::Thread => IconName::Thread,
             Self::Rules => IconName::Reader,
             Self::Diagnostics => IconName::Warning,
+            Self::Resource => IconName::ToolHammer,
         }
     }
 }
@@ -148,6 +153,7 @@ pub(crate) enum Match {
     RecentThread(AgentSessionInfo),
     Fetch(SharedString),
     Rules(RulesContextEntry),
+    Resource(ResourceContextEntry),
     Entry(EntryMatch),
 }
 
@@ -160,6 +166,7 @@ impl Match {
             Match::RecentThread(_) => 1.,
             Match::Symbol(_) => 1.,
             Match::Rules(_) => 1.,
+            Match::Resource(_) => 1.,
             Match::Fetch(_) => 1.,
         }
     }
@@ -184,6 +191,14 @@ pub struct RulesContextEntry {
     pub title: SharedString,
 }
 
+#[derive(Debug, Clone)]
+pub struct ResourceContextEntry {
+    pub server_id: SharedString,
+    pub uri: SharedString,
+    pub name: SharedString,
+    pub description: Option<SharedString>,
+}
+
 #[derive(Debug, Clone)]
 pub struct AvailableCommand {
     pub name: Arc<str>,
@@ -352,6 +367,57 @@ impl<T: PromptCompletionProviderDelegate> PromptCompletionProvider<T> {
         }
     }
 
+    fn completion_for_resource(
+        resource: ResourceContextEntry,
+        source_range: Range<Anchor>,
+        source: Arc<T>,
+        editor: WeakEntity<Editor>,
+        mention_set: WeakEntity<MentionSet>,
+        workspace: Entity<Workspace>,
+        cx: &mut App,
+    ) -> Completion {
+        let uri = MentionUri::McpResource {
+            server_id: resource.server_id.to_string(),
+            uri: resource.uri.to_string(),
+            name: resource.name.to_string(),
+        };
+        let new_text = format!("{} ", uri.as_link());
+        let new_text_len = new_text.len();
+        let icon_path = uri.icon_path(cx);
+        let server_highlight_id = cx
+            .theme()
+            .syntax()
+            .highlight_id("variable")
+            .map(HighlightId);
+        let mut label = CodeLabelBuilder::default();
+        label.push_str(&resource.name, None);
+        label.push_str(" ", None);
+        label.push_str(&resource.server_id, server_highlight_id);
+        Completion {
+            replace_range: source_range.clone(),
+            new_text,
+            label: label.build(),
+            documentation: resource
+                .description
+                .map(CompletionDocumentation::SingleLine),
+            insert_text_mode: None,
+            source: project::CompletionSource::Custom,
+            match_start: None,
+            snippet_deduplication_key: None,
+            icon_path: Some(icon_path),
+            confirm: Some(confirm_completion_callback(
+                resource.name,
+                source_range.start,
+                new_text_len - 1,
+                uri,
+                source,
+                editor,
+                mention_set,
+                workspace,
+            )),
+        }
+    }
+
     pub(crate) fn completion_for_path(
         project_path: ProjectPath,
         path_prefix: &RelPath,
@@ -877,6 +943,14 @@ impl<T: PromptCompletionProviderDelegate> PromptCompletionProvider<T> {
 
             Some(PromptContextType::Diagnostics) => Task::ready(Vec::new()),
 
+            Some(PromptContextType::Resource) => {
+                let resources = context_server_resources(&workspace, cx);
+                let search_task = search_resources(query, cancellation_flag, resources, cx);
+                cx.background_spawn(async move {
+                    search_task.await.into_iter().map(Match::Resource).collect()
+                })
+            }
+
             None if query.is_empty() => {
                 let recent_task = self.recent_context_picker_entries(&workspace, cx);
                 let entries = self
@@ -1075,6 +1149,14 @@ impl<T: PromptCompletionProviderDelegate> PromptCompletionProvider<T> {
             entries.push(PromptContextEntry::Mode(PromptContextType::Fetch));
         }
 
+        if self
+            .source
+            .supports_context(PromptContextType::Resource, cx)
+            && !context_server_resources(workspace, cx).is_empty()
+        {
+            entries.push(PromptContextEntry::Mode(PromptContextType::Resource));
+        }
+
         if self
             .source
             .supports_context(PromptContextType::Diagnostics, cx)
@@ -1325,6 +1407,15 @@ impl<T: PromptCompletionProviderDelegate> CompletionProvider for PromptCompletio
                                     workspace.clone(),
                                     cx,
                                 )),
+                                Match::Resource(resource) => Some(Self::completion_for_resource(
+                                    resource,
+                                    source_range.clone(),
+                                    source.clone(),
+                                    editor.clone(),
+                                    mention_set.clone(),
+                                    workspace.clone(),
+                                    cx,
+                                )),
                                 Match::Fetch(url) => Self::completion_for_fetch(
                                     source_range.clone(),
                                     url,
@@ -1944,6 +2035,59 @@ pub(crate) fn search_rules(
     })
 }
 
+/// Returns the resources of the running context servers, as listed by the agent panel.
+fn context_server_resources(workspace: &Entity<Workspace>, cx: &App) -> Vec<ResourceContextEntry> {
+    let Some(agent_panel) = workspace.read(cx).panel::<AgentPanel>(cx) else {
+        return Vec::new();
+    };
+    agent_panel
+        .read(cx)
+        .context_server_registry()
+        .read(cx)
+        .resources()
+        .map(|resource| ResourceContextEntry {
+            server_id: resource.server_id.0.clone().into(),
+            uri: resource.resource.uri.to_string().into(),
+            name: resource.resource.name.clone().into(),
+            description: resource.resource.description.clone().map(Into::into),
+        })
+        .collect()
+}
+
+fn search_resources(
+    query: String,
+    cancellation_flag: Arc<AtomicBool>,
+    resources: Vec<ResourceContextEntry>,
+    cx: &mut App,
+) -> Task<Vec<ResourceContextEntry>> {
+    if query.is_empty() {
+        return Task::ready(resources);
+    }
+    let executor = cx.background_executor().clone();
+    cx.background_spawn(async move {
+        let candidates = resources
+            .iter()
+            .enumerate()
+            .map(|(id, resource)| StringMatchCandidate::new(id, &resource.name))
+            .collect::<Vec<_>>();
+        let matches = fuzzy::match_strings(
+            &candidates,
+            &query,
+            false,
+            true,
+            100,
+            &cancellation_flag,
+            executor,
+        )
+        .await;
+
+        matches
+            .into_iter()
+            .map(|mat| resources[mat.candidate_id].clone())
+            .collect()
+    })
+}
+
 pub struct SymbolMatch {
     pub symbol: Symbol,
 }
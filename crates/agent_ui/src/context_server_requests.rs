@@ -0,0 +1,792 @@
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use collections::{HashMap, HashSet};
+use context_server::{
+    ContextServerId,
+    types::{
+        CreateMessageRequest, CreateMessageResult, ElicitationAction, ElicitationCreateParams,
+        ElicitationCreateResult, ElicitationProperty, MessageContent, Role as SamplingRole,
+    },
+};
+use fs::Fs;
+use futures::StreamExt as _;
+use gpui::{Entity, Subscription, Task};
+use language_model::{
+    LanguageModelRegistry, LanguageModelRequest, LanguageModelRequestMessage, Role,
+};
+use project::{
+    context_server_store::{
+        ContextServerStore,
+        server_requests::{ServerRequestId, ServerRequestKind},
+    },
+    project_settings::ContextServerSamplingPolicy,
+};
+use serde_json::Value;
+use settings::update_settings_file;
+use ui::{Callout, Checkbox, ToggleState, prelude::*};
+use ui_input::InputField;
+
+/// The number of characters of a sampling request shown when asking the user to approve it.
+const SAMPLING_PREVIEW_LENGTH: usize = 300;
+
+/// Shows the requests that context servers make to the user at the top of the agent panel:
+/// approving the messages they ask to generate with the user's language model, and filling
+/// in the forms they ask for.
+pub struct ContextServerRequestsView {
+    context_server_store: Entity<ContextServerStore>,
+    fs: Arc<dyn Fs>,
+    forms: HashMap<ServerRequestId, ElicitationForm>,
+    sampling_tasks: HashMap<ServerRequestId, Task<()>>,
+    _store_subscription: Subscription,
+}
+
+struct ElicitationForm {
+    fields: Vec<ElicitationField>,
+    error: Option<SharedString>,
+}
+
+struct ElicitationField {
+    name: String,
+    property: ElicitationProperty,
+    required: bool,
+    input: ElicitationInput,
+}
+
+enum ElicitationInput {
+    Text(Entity<InputField>),
+    Boolean(bool),
+}
+
+impl ContextServerRequestsView {
+    pub fn new(
+        context_server_store: Entity<ContextServerStore>,
+        fs: Arc<dyn Fs>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let subscription = cx.observe_in(&context_server_store, window, |this, _, window, cx| {
+            this.sync_requests(window, cx)
+        });
+        let this = cx.entity();
+        context_server_store.update(cx, |store, cx| {
+            store.register_request_handler(&this, cx);
+        });
+        let mut this = Self {
+            context_server_store,
+            fs,
+            forms: HashMap::default(),
+            sampling_tasks: HashMap::default(),
+            _store_subscription: subscription,
+        };
+        this.sync_requests(window, cx);
+        this
+    }
+
+    /// Creates forms for new elicitation requests and generates the messages of approved
+    /// sampling requests, forgetting about the requests that were answered or cancelled.
+    fn sync_requests(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let store = self.context_server_store.read(cx);
+        let mut pending_ids = HashSet::default();
+        let mut new_forms = Vec::new();
+        let mut approved_sampling = Vec::new();
+        for request in store.pending_requests() {
+            pending_ids.insert(request.id);
+            match &request.kind {
+                ServerRequestKind::Sampling { params, approved } => {
+                    if *approved && !self.sampling_tasks.contains_key(&request.id) {
+                        approved_sampling.push((request.id, sampling_request(params)));
+                    }
+                }
+                ServerRequestKind::Elicitation { params } => {
+                    if !self.forms.contains_key(&request.id) {
+                        new_forms.push((request.id, elicitation_fields(params)));
+                    }
+                }
+            }
+        }
+
+        self.forms.retain(|id, _| pending_ids.contains(id));
+        self.sampling_tasks.retain(|id, _| pending_ids.contains(id));
+        for (id, fields) in new_forms {
+            let form = ElicitationForm::new(fields, window, cx);
+            self.forms.insert(id, form);
+        }
+        for (id, request) in approved_sampling {
+            self.generate_sampling_message(id, request, cx);
+        }
+        cx.notify();
+    }
+
+    fn generate_sampling_message(
+        &mut self,
+        id: ServerRequestId,
+        request: Result<LanguageModelRequest>,
+        cx: &mut Context<Self>,
+    ) {
+        let model = LanguageModelRegistry::read_global(cx).default_model();
+        let store = self.context_server_store.clone();
+        let task = cx.spawn(async move |_, cx| {
+            let result = async {
+                let request = request?;
+                let model = model.ok_or_else(|| anyhow!("No language model is configured"))?;
+                let mut stream = model.model.stream_completion_text(request, cx).await?;
+                let mut text = String::new();
+                while let Some(chunk) = stream.stream.next().await {
+                    text.push_str(&chunk?);
+                }
+                anyhow::Ok(CreateMessageResult {
+                    role: SamplingRole::Assistant,
+                    content: MessageContent::Text {
+                        text,
+                        annotations: None,
+                    },
+                    model: model.model.id().0.to_string(),
+                    stop_reason: Some("endTurn".into()),
+                })
+            }
+            .await;
+            store.update(cx, |store, cx| {
+                store.respond_to_sampling_request(id, result, cx)
+            });
+        });
+        self.sampling_tasks.insert(id, task);
+    }
+
+    fn allow_sampling_always(
+        &mut self,
+        id: ServerRequestId,
+        server_id: ContextServerId,
+        cx: &mut Context<Self>,
+    ) {
+        update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            settings
+                .project
+                .context_server_sampling
+                .insert(server_id.0, ContextServerSamplingPolicy::Allow);
+        });
+        self.context_server_store.update(cx, |store, cx| {
+            store.approve_sampling_request(id, cx);
+        });
+    }
+
+    fn submit_form(&mut self, id: ServerRequestId, cx: &mut Context<Self>) {
+        let Some(form) = self.forms.get_mut(&id) else {
+            return;
+        };
+        match form.content(cx) {
+            Ok(content) => self.respond_to_elicitation(
+                id,
+                ElicitationCreateResult {
+                    action: ElicitationAction::Accept,
+                    content: Some(content),
+                },
+                cx,
+            ),
+            Err(error) => {
+                form.error = Some(error.into());
+                cx.notify();
+            }
+        }
+    }
+
+    fn respond_to_elicitation(
+        &mut self,
+        id: ServerRequestId,
+        result: ElicitationCreateResult,
+        cx: &mut Context<Self>,
+    ) {
+        self.context_server_store.update(cx, |store, cx| {
+            store.respond_to_elicitation_request(id, result, cx);
+        });
+    }
+
+    fn render_sampling_request(
+        &self,
+        id: ServerRequestId,
+        server_id: &ContextServerId,
+        params: &CreateMessageRequest,
+        approved: bool,
+        cx: &Context<Self>,
+    ) -> AnyElement {
+        let id_ix = id.0;
+        let callout = Callout::new()
+            .icon(IconName::ToolHammer)
+            .border_position(ui::BorderPosition::Bottom);
+
+        if approved {
+            return callout
+                .severity(Severity::Info)
+                .title(format!("Generating a message for {server_id}…"))
+                .actions_slot(
+                    Button::new(("cancel-sampling", id_ix), "Cancel")
+                        .label_size(LabelSize::Small)
+                        .on_click(cx.listener(move |this, _, _window, cx| {
+                            this.context_server_store.update(cx, |store, cx| {
+                                store.respond_to_sampling_request(
+                                    id,
+                                    Err(anyhow!("The user cancelled the request")),
+                                    cx,
+                                )
+                            });
+                        })),
+                )
+                .into_any_element();
+        }
+
+        callout
+            .severity(Severity::Warning)
+            .title(format!(
+                "{server_id} wants to generate a message with your language model"
+            ))
+            .description(sampling_preview(params))
+            .actions_slot(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new(("deny-sampling", id_ix), "Deny")
+                            .label_size(LabelSize::Small)
+                            .on_click(cx.listener(move |this, _, _window, cx| {
+                                this.context_server_store.update(cx, |store, cx| {
+                                    store.respond_to_sampling_request(
+                                        id,
+                                        Err(anyhow!("The user denied the request")),
+                                        cx,
+                                    )
+                                });
+                            })),
+                    )
+                    .child(
+                        Button::new(("always-allow-sampling", id_ix), "Always Allow")
+                            .label_size(LabelSize::Small)
+                            .on_click(cx.listener({
+                                let server_id = server_id.clone();
+                                move |this, _, _window, cx| {
+                                    this.allow_sampling_always(id, server_id.clone(), cx)
+                                }
+                            })),
+                    )
+                    .child(
+                        Button::new(("allow-sampling", id_ix), "Allow")
+                            .label_size(LabelSize::Small)
+                            .style(ButtonStyle::Outlined)
+                            .on_click(cx.listener(move |this, _, _window, cx| {
+                                this.context_server_store
+                                    .update(cx, |store, cx| store.approve_sampling_request(id, cx));
+                            })),
+                    ),
+            )
+            .into_any_element()
+    }
+
+    fn render_elicitation_request(
+        &self,
+        id: ServerRequestId,
+        server_id: &ContextServerId,
+        params: &ElicitationCreateParams,
+        cx: &Context<Self>,
+    ) -> Option<AnyElement> {
+        let form = self.forms.get(&id)?;
+        let id_ix = id.0;
+
+        let fields = form.fields.iter().enumerate().map(|(field_ix, field)| {
+            let label = field.property.title().unwrap_or(&field.name).to_string();
+            let label = if field.required {
+                format!("{label} *")
+            } else {
+                label
+            };
+            let input = match &field.input {
+                ElicitationInput::Text(input) => v_flex()
+                    .gap_0p5()
+                    .child(Label::new(label).size(LabelSize::Small))
+                    .child(input.clone())
+                    .into_any_element(),
+                ElicitationInput::Boolean(checked) => {
+                    Checkbox::new(("elicitation-checkbox", field_ix), (*checked).into())
+                        .label(label)
+                        .on_click(cx.listener(move |this, state: &ToggleState, _window, cx| {
+                            if let Some(ElicitationField {
+                                input: ElicitationInput::Boolean(checked),
+                                ..
+                            }) = this
+                                .forms
+                                .get_mut(&id)
+                                .and_then(|form| form.fields.get_mut(field_ix))
+                            {
+                                *checked = *state == ToggleState::Selected;
+                                cx.notify();
+                            }
+                        }))
+                        .into_any_element()
+                }
+            };
+            v_flex()
+                .gap_0p5()
+                .child(input)
+                .children(field.property.description().map(|description| {
+                    Label::new(description.to_string())
+                        .size(LabelSize::XSmall)
+                        .color(Color::Muted)
+                }))
+        });
+
+        let buttons = h_flex()
+            .gap_1()
+            .justify_end()
+            .child(
+                Button::new(("cancel-elicitation", id_ix), "Cancel")
+                    .label_size(LabelSize::Small)
+                    .on_click(cx.listener(move |this, _, _window, cx| {
+                        this.respond_to_elicitation(
+                            id,
+                            ElicitationCreateResult {
+                                action: ElicitationAction::Cancel,
+                                content: None,
+                            },
+                            cx,
+                        )
+                    })),
+            )
+            .child(
+                Button::new(("decline-elicitation", id_ix), "Decline")
+                    .label_size(LabelSize::Small)
+                    .on_click(cx.listener(move |this, _, _window, cx| {
+                        this.respond_to_elicitation(
+                            id,
+                            ElicitationCreateResult {
+                                action: ElicitationAction::Decline,
+                                content: None,
+                            },
+                            cx,
+                        )
+                    })),
+            )
+            .child(
+                Button::new(("submit-elicitation", id_ix), "Submit")
+                    .label_size(LabelSize::Small)
+                    .style(ButtonStyle::Outlined)
+                    .on_click(cx.listener(move |this, _, _window, cx| this.submit_form(id, cx))),
+            );
+
+        Some(
+            v_flex()
+                .p_2()
+                .gap_2()
+                .border_b_1()
+                .border_color(cx.theme().colors().border)
+                .bg(cx.theme().colors().editor_background)
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(Icon::new(IconName::ToolHammer).size(IconSize::Small))
+                        .child(Label::new(format!("{server_id} needs more information"))),
+                )
+                .child(Label::new(params.message.clone()).size(LabelSize::Small))
+                .children(fields)
+                .children(
+                    form.error
+                        .clone()
+                        .map(|error| Label::new(error).size(LabelSize::Small).color(Color::Error)),
+                )
+                .child(buttons)
+                .into_any_element(),
+        )
+    }
+}
+
+impl Render for ContextServerRequestsView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let store = self.context_server_store.read(cx);
+        v_flex().children(
+            store
+                .pending_requests()
+                .filter_map(|request| match &request.kind {
+                    ServerRequestKind::Sampling { params, approved } => {
+                        Some(self.render_sampling_request(
+                            request.id,
+                            &request.server_id,
+                            params,
+                            *approved,
+                            cx,
+                        ))
+                    }
+                    ServerRequestKind::Elicitation { params } => {
+                        self.render_elicitation_request(request.id, &request.server_id, params, cx)
+                    }
+                }),
+        )
+    }
+}
+
+impl ElicitationForm {
+    fn new(
+        fields: Vec<(String, ElicitationProperty, bool)>,
+        window: &mut Window,
+        cx: &mut Context<ContextServerRequestsView>,
+    ) -> Self {
+        let fields = fields
+            .into_iter()
+            .map(|(name, property, required)| {
+                let input = match &property {
+                    ElicitationProperty::Boolean { default, .. } => {
+                        ElicitationInput::Boolean(default.unwrap_or(false))
+                    }
+                    _ => {
+                        let placeholder = field_placeholder(&property);
+                        ElicitationInput::Text(
+                            cx.new(|cx| InputField::new(window, cx, &placeholder)),
+                        )
+                    }
+                };
+                ElicitationField {
+                    name,
+                    property,
+                    required,
+                    input,
+                }
+            })
+            .collect();
+        Self {
+            fields,
+            error: None,
+        }
+    }
+
+    /// Validates the fields of the form, returning the content to send back to the server.
+    fn content(&self, cx: &App) -> Result<HashMap<String, Value>, String> {
+        let mut content = HashMap::default();
+        for field in &self.fields {
+            let value = match &field.input {
+                ElicitationInput::Boolean(checked) => Some(Value::Bool(*checked)),
+                ElicitationInput::Text(input) => {
+                    let label = field.property.title().unwrap_or(&field.name);
+                    parse_field(
+                        label,
+                        &field.property,
+                        field.required,
+                        &input.read(cx).text(cx),
+                    )?
+                }
+            };
+            if let Some(value) = value {
+                content.insert(field.name.clone(), value);
+            }
+        }
+        Ok(content)
+    }
+}
+
+fn elicitation_fields(
+    params: &ElicitationCreateParams,
+) -> Vec<(String, ElicitationProperty, bool)> {
+    params
+        .requested_schema
+        .properties
+        .iter()
+        .map(|(name, property)| {
+            let required = params.requested_schema.required.contains(name);
+            (name.clone(), property.clone(), required)
+        })
+        .collect()
+}
+
+fn field_placeholder(property: &ElicitationProperty) -> String {
+    match property {
+        ElicitationProperty::String {
+            enum_values: Some(values),
+            enum_names,
+            ..
+        } => {
+            let options = enum_names.as_ref().unwrap_or(values);
+            format!("One of: {}", options.join(", "))
+        }
+        ElicitationProperty::String { format, .. } => format.clone().unwrap_or_default(),
+        ElicitationProperty::Number { .. } => "A number".into(),
+        ElicitationProperty::Integer { .. } => "A whole number".into(),
+        ElicitationProperty::Boolean { .. } => String::new(),
+    }
+}
+
+/// Parses the text the user entered in a form field into the value the server asked for,
+/// or explains why it isn't valid. Empty fields that aren't required have no value.
+fn parse_field(
+    label: &str,
+    property: &ElicitationProperty,
+    required: bool,
+    text: &str,
+) -> Result<Option<Value>, String> {
+    if text.trim().is_empty() {
+        return if required {
+            Err(format!("{label} is required"))
+        } else {
+            Ok(None)
+        };
+    }
+
+    let value = match property {
+        ElicitationProperty::String {
+            enum_values: Some(values),
+            enum_names,
+            ..
+        } => {
+            let text = text.trim();
+            let ix = values
+                .iter()
+                .position(|value| value == text)
+                .or_else(|| enum_names.as_ref()?.iter().position(|name| name == text))
+                .ok_or_else(|| {
+                    let options = enum_names.as_ref().unwrap_or(values);
+                    format!("{label} must be one of: {}", options.join(", "))
+                })?;
+            Value::String(values[ix].clone())
+        }
+        ElicitationProperty::String {
+            min_length,
+            max_length,
+            ..
+        } => {
+            let length = text.chars().count();
+            if let Some(min_length) = min_length
+                && length < *min_length
+            {
+                return Err(format!(
+                    "{label} must be at least {min_length} characters long"
+                ));
+            }
+            if let Some(max_length) = max_length
+                && length > *max_length
+            {
+                return Err(format!(
+                    "{label} must be at most {max_length} characters long"
+                ));
+            }
+            Value::String(text.to_string())
+        }
+        ElicitationProperty::Number {
+            minimum, maximum, ..
+        } => {
+            let number = text
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| format!("{label} must be a number"))?;
+            check_range(label, number, *minimum, *maximum)?;
+            serde_json::Number::from_f64(number)
+                .map(Value::Number)
+                .ok_or_else(|| format!("{label} must be a number"))?
+        }
+        ElicitationProperty::Integer {
+            minimum, maximum, ..
+        } => {
+            let number = text
+                .trim()
+                .parse::<i64>()
+                .map_err(|_| format!("{label} must be a whole number"))?;
+            check_range(label, number as f64, *minimum, *maximum)?;
+            Value::from(number)
+        }
+        ElicitationProperty::Boolean { .. } => match text.trim() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ => return Err(format!("{label} must be true or false")),
+        },
+    };
+    Ok(Some(value))
+}
+
+fn check_range(
+    label: &str,
+    number: f64,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+) -> Result<(), String> {
+    if let Some(minimum) = minimum
+        && number < minimum
+    {
+        return Err(format!("{label} must be at least {minimum}"));
+    }
+    if let Some(maximum) = maximum
+        && number > maximum
+    {
+        return Err(format!("{label} must be at most {maximum}"));
+    }
+    Ok(())
+}
+
+/// Converts the messages a context server asked to sample into a request for the user's language model.
+fn sampling_request(params: &CreateMessageRequest) -> Result<LanguageModelRequest> {
+    let mut messages = Vec::new();
+    if let Some(system_prompt) = &params.system_prompt {
+        messages.push(LanguageModelRequestMessage {
+            role: Role::System,
+            content: vec![system_prompt.clone().into()],
+            cache: false,
+            reasoning_details: None,
+        });
+    }
+    for message in &params.messages {
+        let text = match &message.content {
+            MessageContent::Text { text, .. } => text.clone(),
+            MessageContent::Resource { resource, .. } => format!("Resource: {}", resource.uri),
+            MessageContent::Image { .. } | MessageContent::Audio { .. } => {
+                anyhow::bail!("Sampling messages with images or audio aren't supported")
+            }
+        };
+        messages.push(LanguageModelRequestMessage {
+            role: match message.role {
+                SamplingRole::User => Role::User,
+                SamplingRole::Assistant => Role::Assistant,
+            },
+            content: vec![text.into()],
+            cache: false,
+            reasoning_details: None,
+        });
+    }
+
+    Ok(LanguageModelRequest {
+        messages,
+        stop: params.stop_sequences.clone().unwrap_or_default(),
+        temperature: params.temperature.map(|temperature| temperature as f32),
+        max_output_tokens: Some(params.max_tokens.into()),
+        ..Default::default()
+    })
+}
+
+/// Describes the message a context server asks to generate, so that the user can decide whether to allow it.
+fn sampling_preview(params: &CreateMessageRequest) -> String {
+    let prompt = params
+        .messages
+        .iter()
+        .rev()
+        .find_map(|message| match &message.content {
+            MessageContent::Text { text, .. } if message.role == SamplingRole::User => {
+                Some(text.as_str())
+            }
+            _ => None,
+        })
+        .unwrap_or_default();
+    let mut preview = prompt
+        .chars()
+        .take(SAMPLING_PREVIEW_LENGTH)
+        .collect::<String>();
+    if preview.len() < prompt.len() {
+        preview.push('…');
+    }
+    format!("{preview}\n\nUp to {} tokens", params.max_tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use context_server::types::{ElicitationSchema, SamplingMessage};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_field() {
+        let schema: ElicitationSchema = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "minLength": 2 },
+                "color": { "type": "string", "enum": ["red", "blue"], "enumNames": ["Red", "Blue"] },
+                "age": { "type": "integer", "minimum": 0 },
+                "ratio": { "type": "number", "maximum": 1 }
+            },
+            "required": ["name"]
+        }))
+        .unwrap();
+        let property = |name: &str| &schema.properties[name];
+
+        assert_eq!(
+            parse_field("Name", property("name"), true, "Ada"),
+            Ok(Some(json!("Ada")))
+        );
+        assert_eq!(
+            parse_field("Name", property("name"), true, " "),
+            Err("Name is required".into())
+        );
+        assert_eq!(
+            parse_field("Name", property("name"), true, "A"),
+            Err("Name must be at least 2 characters long".into())
+        );
+        assert_eq!(parse_field("Color", property("color"), false, ""), Ok(None));
+        assert_eq!(
+            parse_field("Color", property("color"), false, "Blue"),
+            Ok(Some(json!("blue")))
+        );
+        assert_eq!(
+            parse_field("Color", property("color"), false, "green"),
+            Err("Color must be one of: Red, Blue".into())
+        );
+        assert_eq!(
+            parse_field("Age", property("age"), false, "42"),
+            Ok(Some(json!(42)))
+        );
+        assert_eq!(
+            parse_field("Age", property("age"), false, "4.2"),
+            Err("Age must be a whole number".into())
+        );
+        assert_eq!(
+            parse_field("Age", property("age"), false, "-1"),
+            Err("Age must be at least 0".into())
+        );
+        assert_eq!(
+            parse_field("Ratio", property("ratio"), false, "0.5"),
+            Ok(Some(json!(0.5)))
+        );
+        assert_eq!(
+            parse_field("Ratio", property("ratio"), false, "2"),
+            Err("Ratio must be at most 1".into())
+        );
+    }
+
+    #[test]
+    fn test_sampling_request() {
+        let params = CreateMessageRequest {
+            messages: vec![
+                SamplingMessage {
+                    role: SamplingRole::User,
+                    content: MessageContent::Text {
+                        text: "What's the capital of France?".into(),
+                        annotations: None,
+                    },
+                },
+                SamplingMessage {
+                    role: SamplingRole::Assistant,
+                    content: MessageContent::Text {
+                        text: "Paris.".into(),
+                        annotations: None,
+                    },
+                },
+            ],
+            model_preferences: None,
+            system_prompt: Some("Be brief.".into()),
+            include_context: None,
+            temperature: Some(0.5),
+            max_tokens: 100,
+            stop_sequences: Some(vec!["\n\n".into()]),
+            metadata: None,
+        };
+
+        let request = sampling_request(&params).unwrap();
+        assert_eq!(
+            request
+                .messages
+                .iter()
+                .map(|message| (message.role, message.string_contents()))
+                .collect::<Vec<_>>(),
+            [
+                (Role::System, "Be brief.".to_string()),
+                (Role::User, "What's the capital of France?".to_string()),
+                (Role::Assistant, "Paris.".to_string()),
+            ]
+        );
+        assert_eq!(request.stop, ["\n\n"]);
+        assert_eq!(request.temperature, Some(0.5));
+        assert_eq!(request.max_output_tokens, Some(100));
+        assert_eq!(
+            sampling_preview(&params),
+            "What's the capital of France?\n\nUp to 100 tokens"
+        );
+    }
+}
@@ -1,10 +1,13 @@
 use acp_thread::{MentionUri, selection_name};
-use agent::{ThreadStore, outline};
+use agent::{
+    ContextServerRegistry, ContextServerRegistryEvent, ResourceSubscription, ThreadStore, outline,
+};
 use agent_client_protocol as acp;
 use agent_servers::{AgentServer, AgentServerDelegate};
 use anyhow::{Context as _, Result, anyhow};
 use assistant_slash_commands::{codeblock_fence_for_path, collect_diagnostics_output};
 use collections::{HashMap, HashSet};
+use context_server::ContextServerId;
 use editor::{
     Anchor, Editor, EditorSnapshot, ExcerptId, FoldPlaceholder, ToOffset,
     display_map::{Crease, CreaseId, CreaseMetadata, FoldId},
@@ -13,7 +16,7 @@ use editor::{
 use futures::{AsyncReadExt as _, FutureExt as _, future::Shared};
 use gpui::{
     AppContext, ClipboardEntry, Context, Empty, Entity, EntityId, Image, ImageFormat, Img,
-    SharedString, Task, WeakEntity,
+    SharedString, Subscription, Task, WeakEntity,
 };
 use http_client::{AsyncBody, HttpClientWithUrl};
 use itertools::Either;
@@ -38,10 +41,14 @@ use ui::{Disclosure, Toggleable, prelude::*};
 use util::{ResultExt, debug_panic, rel_path::RelPath};
 use workspace::{Workspace, notifications::NotifyResultExt as _};
 
+use crate::AgentPanel;
 use crate::ui::MentionCrease;
 
 pub type MentionTask = Shared<Task<Result<Mention, String>>>;
 
+/// A mention, along with the subscription that reloads the MCP resource it mentions.
+pub type MentionEntry = (MentionUri, MentionTask, Option<Arc<ResourceSubscription>>);
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Mention {
     Text {
@@ -62,7 +69,8 @@ pub struct MentionSet {
     project: WeakEntity<Project>,
     thread_store: Option<Entity<ThreadStore>>,
     prompt_store: Option<Entity<PromptStore>>,
-    mentions: HashMap<CreaseId, (MentionUri, MentionTask)>,
+    mentions: HashMap<CreaseId, MentionEntry>,
+    context_server_registry_subscription: Option<Subscription>,
 }
 
 impl MentionSet {
@@ -76,6 +84,7 @@ impl MentionSet {
             thread_store,
             prompt_store,
             mentions: HashMap::default(),
+            context_server_registry_subscription: None,
         }
     }
 
@@ -90,7 +99,7 @@ impl MentionSet {
         let mentions = self.mentions.clone();
         cx.spawn(async move |cx| {
             let mut contents = HashMap::default();
-            for (crease_id, (mention_uri, task)) in mentions {
+            for (crease_id, (mention_uri, task, _)) in mentions {
                 let content = if full_mention_content
                     && let MentionUri::Directory { abs_path } = &mention_uri
                 {
@@ -115,7 +124,7 @@ impl MentionSet {
     }
 
     pub fn insert_mention(&mut self, crease_id: CreaseId, uri: MentionUri, task: MentionTask) {
-        self.mentions.insert(crease_id, (uri, task));
+        self.mentions.insert(crease_id, (uri, task, None));
     }
 
     /// Creates the appropriate confirmation task for a mention based on its URI type.
@@ -147,6 +156,9 @@ impl MentionSet {
                 include_errors,
                 include_warnings,
             } => self.confirm_mention_for_diagnostics(include_errors, include_warnings, cx),
+            MentionUri::McpResource { server_id, uri, .. } => {
+                self.confirm_mention_for_resource(server_id, uri, cx)
+            }
             MentionUri::PastedImage
             | MentionUri::Selection { .. }
             | MentionUri::TerminalSelection { .. }
@@ -165,14 +177,17 @@ impl MentionSet {
     }
 
     pub fn mentions(&self) -> HashSet<MentionUri> {
-        self.mentions.values().map(|(uri, _)| uri.clone()).collect()
+        self.mentions
+            .values()
+            .map(|(uri, _, _)| uri.clone())
+            .collect()
     }
 
-    pub fn set_mentions(&mut self, mentions: HashMap<CreaseId, (MentionUri, MentionTask)>) {
+    pub fn set_mentions(&mut self, mentions: HashMap<CreaseId, MentionEntry>) {
         self.mentions = mentions;
     }
 
-    pub fn clear(&mut self) -> impl Iterator<Item = (CreaseId, (MentionUri, MentionTask))> {
+    pub fn clear(&mut self) -> impl Iterator<Item = (CreaseId, MentionEntry)> {
         self.mentions.drain()
     }
 
@@ -255,6 +270,7 @@ impl MentionSet {
             return Task::ready(());
         };
 
+        let mut resource_subscription = None;
         let task = match mention_uri.clone() {
             MentionUri::Fetch { url } => {
                 self.confirm_mention_for_fetch(url, workspace.read(cx).client().http_client(), cx)
@@ -277,6 +293,10 @@ impl MentionSet {
                 include_errors,
                 include_warnings,
             } => self.confirm_mention_for_diagnostics(include_errors, include_warnings, cx),
+            MentionUri::McpResource { server_id, uri, .. } => {
+                resource_subscription = self.watch_resource(&server_id, &uri, workspace, cx);
+                self.confirm_mention_for_resource(server_id, uri, cx)
+            }
             MentionUri::PastedImage => {
                 debug_panic!("pasted image URI should not be included in completions");
                 Task::ready(Err(anyhow!(
@@ -299,7 +319,10 @@ impl MentionSet {
         let task = cx
             .spawn(async move |_, _| task.await.map_err(|e| e.to_string()))
             .shared();
-        self.mentions.insert(crease_id, (mention_uri, task.clone()));
+        self.mentions.insert(
+            crease_id,
+            (mention_uri, task.clone(), resource_subscription),
+        );
 
         // Notify the user if we failed to load the mentioned context
         let workspace = workspace.downgrade();
@@ -443,6 +466,86 @@ impl MentionSet {
         })
     }
 
+    fn confirm_mention_for_resource(
+        &self,
+        server_id: String,
+        uri: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Mention>> {
+        let Some(project) = self.project.upgrade() else {
+            return Task::ready(Err(anyhow!("project not found")));
+        };
+        let server_store = project.read(cx).context_server_store();
+        let contents =
+            agent::read_resource(&server_store, &ContextServerId(server_id.into()), &uri, cx);
+        cx.spawn(async move |_, _| {
+            let content = contents.await?;
+            Ok(Mention::Text {
+                content,
+                tracked_buffers: Vec::new(),
+            })
+        })
+    }
+
+    /// Subscribes to changes to the resource, so that its mentions are reloaded when it changes.
+    /// The subscription is kept along with the mention: once the mention is removed, or sent
+    /// with its message, the resource is not reloaded anymore.
+    fn watch_resource(
+        &mut self,
+        server_id: &str,
+        uri: &str,
+        workspace: &Entity<Workspace>,
+        cx: &mut Context<Self>,
+    ) -> Option<Arc<ResourceSubscription>> {
+        let agent_panel = workspace.read(cx).panel::<AgentPanel>(cx)?;
+        let registry = agent_panel.read(cx).context_server_registry().clone();
+        let subscription = registry.update(cx, |registry, cx| {
+            registry.subscribe_to_resource(&ContextServerId(server_id.into()), uri, cx)
+        })?;
+        if self.context_server_registry_subscription.is_none() {
+            self.context_server_registry_subscription =
+                Some(cx.subscribe(&registry, Self::handle_context_server_registry_event));
+        }
+        Some(subscription)
+    }
+
+    fn handle_context_server_registry_event(
+        &mut self,
+        _registry: Entity<ContextServerRegistry>,
+        event: &ContextServerRegistryEvent,
+        cx: &mut Context<Self>,
+    ) {
+        let ContextServerRegistryEvent::ResourceUpdated { server_id, uri } = event else {
+            return;
+        };
+        let updated_crease_ids = self
+            .mentions
+            .iter()
+            .filter(|(_, (mention_uri, _, _))| {
+                matches!(
+                    mention_uri,
+                    MentionUri::McpResource {
+                        server_id: mention_server_id,
+                        uri: mention_resource_uri,
+                        ..
+                    } if mention_server_id.as_str() == server_id.0.as_ref()
+                        && mention_resource_uri == uri
+                )
+            })
+            .map(|(crease_id, _)| *crease_id)
+            .collect::<Vec<_>>();
+
+        for crease_id in updated_crease_ids {
+            let task = self.confirm_mention_for_resource(server_id.0.to_string(), uri.clone(), cx);
+            let task = cx
+                .spawn(async move |_, _| task.await.map_err(|e| e.to_string()))
+                .shared();
+            if let Some((_, mention_task, _)) = self.mentions.get_mut(&crease_id) {
+                *mention_task = task;
+            }
+        }
+    }
+
     pub fn confirm_mention_for_selection(
         &mut self,
         source_range: Range<text::Anchor>,
@@ -504,6 +607,7 @@ impl MentionSet {
                         tracked_buffers: vec![buffer],
                     }))
                     .shared(),
+                    None,
                 ),
             );
         }
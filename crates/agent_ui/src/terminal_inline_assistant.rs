@@ -274,6 +274,7 @@ impl TerminalInlineAssistant {
                 tool_choice: None,
                 stop: Vec::new(),
                 temperature,
+                max_output_tokens: None,
                 thinking_allowed: false,
                 thinking_effort: None,
             }
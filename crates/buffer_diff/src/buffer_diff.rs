@@ -1457,6 +1457,137 @@ fn process_patch_hunk(
     }
 }
 
+/// A change between two texts, as the rows it removes and the rows that replace them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LineChange {
+    old_rows: Range<u32>,
+    new_rows: Range<u32>,
+}
+
+fn line_changes(old_text: &str, new_text: &str) -> Vec<LineChange> {
+    let mut options = GitOptions::default();
+    options.context_lines(0);
+    let Some(patch) = GitPatch::from_buffers(
+        old_text.as_bytes(),
+        None,
+        new_text.as_bytes(),
+        None,
+        Some(&mut options),
+    )
+    .log_err() else {
+        return Vec::new();
+    };
+
+    // Git numbers rows from 1, except in empty ranges, which start after the given row.
+    let rows = |start: u32, len: u32| {
+        let start = if len == 0 { start } else { start - 1 };
+        start..start + len
+    };
+    (0..patch.num_hunks())
+        .filter_map(|hunk_index| patch.hunk(hunk_index).log_err())
+        .map(|(hunk, _)| LineChange {
+            old_rows: rows(hunk.old_start(), hunk.old_lines()),
+            new_rows: rows(hunk.new_start(), hunk.new_lines()),
+        })
+        .collect()
+}
+
+fn merge_row_ranges(ranges: impl IntoIterator<Item = Range<u32>>) -> Vec<Range<u32>> {
+    let mut ranges = ranges.into_iter().collect::<Vec<_>>();
+    ranges.sort_unstable_by_key(|range| range.start);
+    let mut merged: Vec<Range<u32>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Returns the parts of `changes` that touch the given sorted `rows` of the new text.
+///
+/// The rows removed by a change that doesn't add as many rows as it removes can't be matched
+/// to the added ones, so they all go along with its first selected part.
+fn selected_line_changes(changes: &[LineChange], rows: &[Range<u32>]) -> Vec<LineChange> {
+    let mut selected = Vec::new();
+    for change in changes {
+        if change.new_rows.is_empty() {
+            let row = change.new_rows.start;
+            if rows
+                .iter()
+                .any(|range| range.start <= row && row <= range.end)
+            {
+                selected.push(change.clone());
+            }
+            continue;
+        }
+
+        let mut removed_rows_taken = false;
+        for range in rows {
+            let start = range.start.max(change.new_rows.start);
+            let end = range.end.min(change.new_rows.end);
+            if start >= end {
+                continue;
+            }
+            let old_rows = if change.old_rows.len() == change.new_rows.len() {
+                let old_start = change.old_rows.start + (start - change.new_rows.start);
+                old_start..old_start + (end - start)
+            } else if removed_rows_taken {
+                change.old_rows.end..change.old_rows.end
+            } else {
+                removed_rows_taken = true;
+                change.old_rows.clone()
+            };
+            selected.push(LineChange {
+                old_rows,
+                new_rows: start..end,
+            });
+        }
+    }
+    selected
+}
+
+/// Maps the given sorted `rows` of the new text to the old text, leaving out changed rows.
+fn unchanged_old_rows(changes: &[LineChange], rows: &[Range<u32>]) -> Vec<Range<u32>> {
+    let mut old_rows = Vec::new();
+    let mut old_start = 0;
+    let mut new_start = 0;
+    let trailing_rows = LineChange {
+        old_rows: u32::MAX..u32::MAX,
+        new_rows: u32::MAX..u32::MAX,
+    };
+    for change in changes.iter().chain([&trailing_rows]) {
+        for range in rows {
+            let start = range.start.max(new_start);
+            let end = range.end.min(change.new_rows.start);
+            if start < end {
+                old_rows.push(old_start + (start - new_start)..old_start + (end - new_start));
+            }
+        }
+        old_start = change.old_rows.end;
+        new_start = change.new_rows.end;
+    }
+    old_rows
+}
+
+/// Replaces rows of `target` with rows of `source`, given edits sorted by target rows.
+fn splice_rows(
+    target: &Rope,
+    source: &Rope,
+    edits: impl IntoIterator<Item = (Range<u32>, Range<u32>)>,
+) -> Rope {
+    let mut new_text = Rope::new();
+    let mut cursor = target.cursor(0);
+    for (target_rows, source_rows) in edits {
+        new_text.append(cursor.slice(target.point_to_offset(Point::new(target_rows.start, 0))));
+        cursor.seek_forward(target.point_to_offset(Point::new(target_rows.end, 0)));
+        new_text.append(source.slice_rows(source_rows));
+    }
+    new_text.append(cursor.suffix());
+    new_text
+}
+
 impl std::fmt::Debug for BufferDiff {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("BufferChangeSet")
@@ -1634,6 +1765,77 @@ impl BufferDiff {
         }
     }
 
+    /// Stages or unstages the given lines of the buffer rather than the whole hunks they
+    /// belong to, returning the new index text.
+    ///
+    /// Rows a hunk removes are staged along with the rows that replace them, unless the hunk
+    /// replaces as many rows as it removes, in which case each row is staged on its own.
+    pub fn stage_or_unstage_lines(
+        &mut self,
+        stage: bool,
+        ranges: &[Range<Point>],
+        buffer: &text::BufferSnapshot,
+        cx: &mut Context<Self>,
+    ) -> Option<Rope> {
+        let secondary_diff = self.secondary_diff.clone()?;
+        let index_text = secondary_diff.update(cx, |secondary_diff, cx| {
+            if !secondary_diff.inner.base_text_exists {
+                None
+            } else if self.inner.base_text_exists {
+                // Build on top of the hunks that are still being staged or unstaged.
+                self.inner.stage_or_unstage_hunks_impl(
+                    &secondary_diff.inner,
+                    true,
+                    &[],
+                    buffer,
+                    true,
+                    cx,
+                )
+            } else {
+                Some(secondary_diff.inner.base_text.read(cx).as_rope().clone())
+            }
+        });
+        if index_text.is_none() && !stage {
+            return None;
+        }
+        let index_text = index_text.unwrap_or_default();
+
+        let rows = merge_row_ranges(ranges.iter().map(|range| {
+            let end = if range.end.column == 0 && range.end.row > range.start.row {
+                range.end.row
+            } else {
+                range.end.row + 1
+            };
+            range.start.row..end
+        }));
+        let buffer_text = buffer.as_rope();
+        let unstaged_changes = line_changes(&index_text.to_string(), &buffer_text.to_string());
+        let new_index_text = if stage {
+            let edits = selected_line_changes(&unstaged_changes, &rows)
+                .into_iter()
+                .map(|change| (change.old_rows, change.new_rows));
+            splice_rows(&index_text, buffer_text, edits)
+        } else {
+            let head_text = if self.inner.base_text_exists {
+                self.inner.base_text.read(cx).as_rope().clone()
+            } else {
+                Rope::new()
+            };
+            // Only the rows that match the index can have staged changes to undo.
+            let index_rows = merge_row_ranges(unchanged_old_rows(&unstaged_changes, &rows));
+            let staged_changes = line_changes(&head_text.to_string(), &index_text.to_string());
+            let edits = selected_line_changes(&staged_changes, &index_rows)
+                .into_iter()
+                .map(|change| (change.new_rows, change.old_rows));
+            splice_rows(&index_text, &head_text, edits)
+        };
+
+        cx.emit(BufferDiffEvent::HunksStagedOrUnstaged(Some(
+            new_index_text.clone(),
+        )));
+        Some(new_index_text)
+    }
+
     pub fn update_diff(
         &self,
         buffer: text::BufferSnapshot,
@@ -2733,6 +2935,128 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_stage_and_unstage_lines(cx: &mut TestAppContext) {
+        let head_text = "
+            one
+            two
+            three
+            four
+            five
+        "
+        .unindent();
+        let buffer_text = "
+            one
+            TWO
+            three
+            four
+            FIVE
+            six
+        "
+        .unindent();
+        let buffer = Buffer::new(
+            ReplicaId::LOCAL,
+            BufferId::new(1).unwrap(),
+            buffer_text.clone(),
+        );
+        let diffs_with_index_text = |index_text: &str, cx: &mut TestAppContext| {
+            let unstaged_diff =
+                cx.new(|cx| BufferDiff::new_with_base_text(index_text, &buffer, cx));
+            cx.new(|cx| {
+                let mut diff = BufferDiff::new_with_base_text(&head_text, &buffer, cx);
+                diff.set_secondary_diff(unstaged_diff);
+                diff
+            })
+        };
+
+        let uncommitted_diff = diffs_with_index_text(&head_text, cx);
+        uncommitted_diff.update(cx, |diff, cx| {
+            // A row replacing a single row is staged on its own.
+            let index_text = diff
+                .stage_or_unstage_lines(true, &[Point::new(1, 0)..Point::new(1, 0)], &buffer, cx)
+                .unwrap();
+            assert_eq!(
+                index_text.to_string(),
+                "
+                one
+                TWO
+                three
+                four
+                five
+                "
+                .unindent()
+            );
+
+            // The removed row goes along with the rows added in its place.
+            let index_text = diff
+                .stage_or_unstage_lines(true, &[Point::new(5, 0)..Point::new(5, 3)], &buffer, cx)
+                .unwrap();
+            assert_eq!(
+                index_text.to_string(),
+                "
+                one
+                two
+                three
+                four
+                six
+                "
+                .unindent()
+            );
+        });
+
+        let uncommitted_diff = diffs_with_index_text(&buffer_text, cx);
+        uncommitted_diff.update(cx, |diff, cx| {
+            let index_text = diff
+                .stage_or_unstage_lines(false, &[Point::new(1, 0)..Point::new(2, 0)], &buffer, cx)
+                .unwrap();
+            assert_eq!(
+                index_text.to_string(),
+                "
+                one
+                two
+                three
+                four
+                FIVE
+                six
+                "
+                .unindent()
+            );
+
+            let index_text = diff
+                .stage_or_unstage_lines(false, &[Point::new(4, 2)..Point::new(4, 2)], &buffer, cx)
+                .unwrap();
+            assert_eq!(
+                index_text.to_string(),
+                "
+                one
+                TWO
+                three
+                four
+                five
+                six
+                "
+                .unindent()
+            );
+        });
+
+        // Lines with unstaged changes have nothing staged to unstage.
+        let index_text = "
+            one
+            TWO
+            three
+            four
+            five
+        "
+        .unindent();
+        let uncommitted_diff = diffs_with_index_text(&index_text, cx);
+        uncommitted_diff.update(cx, |diff, cx| {
+            let new_index_text = diff
+                .stage_or_unstage_lines(false, &[Point::new(4, 0)..Point::new(5, 0)], &buffer, cx)
+                .unwrap();
+            assert_eq!(new_index_text.to_string(), index_text);
+        });
+    }
+
     #[gpui::test]
     async fn test_buffer_diff_compare(cx: &mut TestAppContext) {
         let base_text = "
@@ -53,6 +53,7 @@ pub(crate) struct Client {
     name: Arc<str>,
     subscription_set: Arc<Mutex<NotificationSubscriptionSet>>,
     response_handlers: Arc<Mutex<Option<HashMap<RequestId, ResponseHandler>>>>,
+    request_handlers: Arc<Mutex<HashMap<&'static str, RequestHandler>>>,
     #[allow(clippy::type_complexity)]
     #[allow(dead_code)]
     io_tasks: Mutex<Option<(Task<Option<()>>, Task<Option<()>>)>>,
@@ -101,7 +102,6 @@ struct AnyResponse<'a> {
 }
 
 #[derive(Serialize, Deserialize)]
-#[allow(dead_code)]
 pub(crate) struct Response<T> {
     pub jsonrpc: &'static str,
     pub id: RequestId,
@@ -114,7 +114,6 @@ pub(crate) struct Response<T> {
 pub(crate) enum CspResult<T> {
     #[serde(rename = "result")]
     Ok(Option<T>),
-    #[allow(dead_code)]
     Error(Option<Error>),
 }
 
@@ -201,6 +200,7 @@ impl Client {
             let subscription_set = subscription_set.clone();
             let response_handlers = response_handlers.clone();
             let request_handlers = request_handlers.clone();
+            let outbound_tx = outbound_tx.clone();
             let transport = transport.clone();
             async move |cx| {
                 Self::handle_input(
@@ -208,6 +208,7 @@ impl Client {
                     subscription_set,
                     request_handlers,
                     response_handlers,
+                    outbound_tx,
                     cx,
                 )
                 .log_err()
@@ -238,6 +239,7 @@ impl Client {
             server_id,
             subscription_set,
             response_handlers,
+            request_handlers,
             name: server_name,
             next_id: Default::default(),
             outbound_tx,
@@ -255,11 +257,14 @@ impl Client {
     /// parses them as JSON-RPC responses or notifications, and dispatches them
     /// to the appropriate handlers. It processes both responses (which are matched
     /// to pending requests) and notifications (which trigger registered handlers).
+    /// Requests from the server are dispatched to their registered handlers, and
+    /// answered with an error when no handler is registered for their method.
     async fn handle_input(
         transport: Arc<dyn Transport>,
         subscription_set: Arc<Mutex<NotificationSubscriptionSet>>,
         request_handlers: Arc<Mutex<HashMap<&'static str, RequestHandler>>>,
         response_handlers: Arc<Mutex<Option<HashMap<RequestId, ResponseHandler>>>>,
+        outbound_tx: channel::Sender<String>,
         cx: &mut AsyncApp,
     ) -> anyhow::Result<()> {
         let mut receiver = transport.receive();
@@ -274,6 +279,16 @@ impl Client {
                         request.params.unwrap_or(RawValue::NULL),
                         cx.clone(),
                     );
+                } else {
+                    log::debug!("unhandled context server request: {}", request.method);
+                    let response = serialize_response::<()>(
+                        request.id,
+                        Err(Error {
+                            message: format!("Method not found: {}", request.method),
+                            code: METHOD_NOT_FOUND,
+                        }),
+                    );
+                    outbound_tx.try_send(response).log_err();
                 }
             } else if let Ok(response) = serde_json::from_str::<AnyResponse>(&message) {
                 if let Some(handlers) = response_handlers.lock().as_mut()
@@ -465,6 +480,64 @@ impl Client {
             set: self.subscription_set.clone(),
         }
     }
+
+    /// Registers a handler for the requests the server sends for the given method, replacing
+    /// any previous handler. The result of the task returned by the handler is sent back to
+    /// the server as the response.
+    pub fn on_request<P, R>(
+        &self,
+        method: &'static str,
+        mut f: impl 'static + Send + FnMut(P, AsyncApp) -> Task<Result<R>>,
+    ) where
+        P: DeserializeOwned,
+        R: 'static + Serialize,
+    {
+        let outbound_tx = self.outbound_tx.clone();
+        self.request_handlers.lock().insert(
+            method,
+            Box::new(move |id, params, cx| {
+                let params = match serde_json::from_str::<P>(params.get()) {
+                    Ok(params) => params,
+                    Err(error) => {
+                        let response = serialize_response::<R>(
+                            id,
+                            Err(Error {
+                                message: error.to_string(),
+                                code: INVALID_PARAMS,
+                            }),
+                        );
+                        outbound_tx.try_send(response).log_err();
+                        return;
+                    }
+                };
+                let result = f(params, cx.clone());
+                let outbound_tx = outbound_tx.clone();
+                cx.spawn(async move |_| {
+                    let result = result.await.map_err(|error| Error {
+                        message: error.to_string(),
+                        code: INTERNAL_ERROR,
+                    });
+                    outbound_tx
+                        .try_send(serialize_response(id, result))
+                        .log_err();
+                })
+                .detach();
+            }),
+        );
+    }
+}
+
+fn serialize_response<T: Serialize>(id: RequestId, result: Result<T, Error>) -> String {
+    let value = match result {
+        Ok(result) => CspResult::Ok(Some(result)),
+        Err(error) => CspResult::Error(Some(error)),
+    };
+    serde_json::to_string(&Response {
+        jsonrpc: JSON_RPC_VERSION,
+        id,
+        value,
+    })
+    .unwrap()
 }
 
 #[derive(Debug)]
@@ -105,8 +105,13 @@ impl ContextServer {
         self.client.read().clone()
     }
 
-    pub async fn start(&self, cx: &AsyncApp) -> Result<()> {
-        self.initialize(self.new_client(cx)?).await
+    /// Starts the server, telling it which of the requests it may send to Zed are handled.
+    pub async fn start(
+        &self,
+        capabilities: types::ClientCapabilities,
+        cx: &AsyncApp,
+    ) -> Result<()> {
+        self.initialize(self.new_client(cx)?, capabilities).await
     }
 
     fn new_client(&self, cx: &AsyncApp) -> Result<Client> {
@@ -132,14 +137,18 @@ impl ContextServer {
         })
     }
 
-    async fn initialize(&self, client: Client) -> Result<()> {
+    async fn initialize(
+        &self,
+        client: Client,
+        capabilities: types::ClientCapabilities,
+    ) -> Result<()> {
         log::debug!("starting context server {}", self.id);
         let protocol = crate::protocol::ModelContextProtocol::new(client);
         let client_info = types::Implementation {
             name: "Zed".to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
         };
-        let initialized_protocol = protocol.initialize(client_info).await?;
+        let initialized_protocol = protocol.initialize(client_info, capabilities).await?;
 
         log::debug!(
             "context server {} initialized: {:?}",
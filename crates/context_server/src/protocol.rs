@@ -9,7 +9,7 @@ use std::time::Duration;
 
 use anyhow::Result;
 use futures::channel::oneshot;
-use gpui::AsyncApp;
+use gpui::{AsyncApp, Task};
 use serde_json::Value;
 
 use crate::client::{Client, NotificationSubscription};
@@ -34,14 +34,11 @@ impl ModelContextProtocol {
     pub async fn initialize(
         self,
         client_info: types::Implementation,
+        capabilities: types::ClientCapabilities,
     ) -> Result<InitializedContextServerProtocol> {
         let params = types::InitializeParams {
             protocol_version: types::ProtocolVersion(types::LATEST_PROTOCOL_VERSION.to_string()),
-            capabilities: types::ClientCapabilities {
-                experimental: None,
-                sampling: None,
-                roots: None,
-            },
+            capabilities,
             meta: None,
             client_info,
         };
@@ -122,4 +119,12 @@ impl InitializedContextServerProtocol {
     ) -> NotificationSubscription {
         self.inner.on_notification(method, f)
     }
+
+    /// Handles the requests of type `T` that the server sends, like sampling or elicitation requests.
+    pub fn on_request<T: Request>(
+        &self,
+        handler: impl 'static + Send + FnMut(T::Params, AsyncApp) -> Task<Result<T::Response>>,
+    ) {
+        self.inner.on_request(T::METHOD, handler)
+    }
 }
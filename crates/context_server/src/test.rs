@@ -1,8 +1,16 @@
 use anyhow::Context as _;
 use collections::HashMap;
-use futures::{FutureExt, Stream, StreamExt as _, future::BoxFuture, lock::Mutex};
+use futures::{
+    FutureExt, Stream, StreamExt as _, channel::oneshot, future::BoxFuture, lock::Mutex,
+};
 use gpui::BackgroundExecutor;
-use std::{pin::Pin, sync::Arc};
+use std::{
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering::SeqCst},
+    },
+};
 
 use crate::{
     transport::Transport,
@@ -39,6 +47,8 @@ pub struct FakeTransport {
         &'static str,
         Arc<dyn Send + Sync + Fn(serde_json::Value) -> BoxFuture<'static, serde_json::Value>>,
     >,
+    client_response_handlers: parking_lot::Mutex<HashMap<u64, oneshot::Sender<serde_json::Value>>>,
+    next_client_request_id: AtomicU64,
     tx: futures::channel::mpsc::UnboundedSender<String>,
     rx: Arc<Mutex<futures::channel::mpsc::UnboundedReceiver<String>>>,
     executor: BackgroundExecutor,
@@ -49,6 +59,8 @@ impl FakeTransport {
         let (tx, rx) = futures::channel::mpsc::unbounded();
         Self {
             request_handlers: Default::default(),
+            client_response_handlers: Default::default(),
+            next_client_request_id: AtomicU64::new(0),
             tx,
             rx: Arc::new(Mutex::new(rx)),
             executor,
@@ -78,6 +90,31 @@ impl FakeTransport {
         );
         self
     }
+
+    /// Sends a request to the client, as if the server sent it, and waits for the client's response.
+    pub async fn request_client<T: crate::types::Request>(
+        &self,
+        params: T::Params,
+    ) -> anyhow::Result<T::Response> {
+        let id = self.next_client_request_id.fetch_add(1, SeqCst);
+        let (tx, rx) = oneshot::channel();
+        self.client_response_handlers.lock().insert(id, tx);
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": T::METHOD,
+            "params": params,
+        });
+        self.tx
+            .unbounded_send(request.to_string())
+            .context("sending a request")?;
+
+        let response = rx.await.context("client never responded")?;
+        if let Some(error) = response.get("error") {
+            anyhow::bail!("{}", error["message"]);
+        }
+        Ok(serde_json::from_value(response["result"].clone())?)
+    }
 }
 
 #[async_trait::async_trait]
@@ -101,6 +138,8 @@ impl Transport for FakeTransport {
                 } else {
                     log::debug!("No handler registered for MCP request '{method}'");
                 }
+            } else if let Some(tx) = self.client_response_handlers.lock().remove(&id) {
+                tx.send(msg).ok();
             }
         }
         Ok(())
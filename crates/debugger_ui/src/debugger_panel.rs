@@ -4,10 +4,11 @@ use crate::session::running::RunningState;
 use crate::session::running::breakpoint_list::BreakpointList;
 
 use crate::{
-    ClearAllBreakpoints, Continue, CopyDebugAdapterArguments, Detach, FocusBreakpointList,
-    FocusConsole, FocusFrames, FocusLoadedSources, FocusModules, FocusTerminal, FocusVariables,
-    NewProcessModal, NewProcessMode, Pause, RerunSession, StepInto, StepOut, StepOver, Stop,
-    ToggleExpandItem, ToggleSessionPicker, ToggleThreadPicker, persistence, spawn_task_or_modal,
+    ClearAllBreakpoints, Continue, CopyDebugAdapterArguments, Detach, ExportBreakpoints,
+    FocusBreakpointList, FocusConsole, FocusFrames, FocusLoadedSources, FocusModules,
+    FocusTerminal, FocusVariables, ImportBreakpoints, NewProcessModal, NewProcessMode, Pause,
+    RerunSession, StepInto, StepOut, StepOver, Stop, ToggleExpandItem, ToggleSessionPicker,
+    ToggleThreadPicker, persistence, spawn_task_or_modal,
 };
 use anyhow::{Context as _, Result, anyhow};
 use collections::IndexMap;
@@ -165,6 +166,12 @@ impl DebugPanel {
                         },
                     )
                 });
+                workspace.register_action(|workspace, _: &ExportBreakpoints, window, cx| {
+                    BreakpointList::export_breakpoints(workspace, window, cx);
+                });
+                workspace.register_action(|workspace, _: &ImportBreakpoints, window, cx| {
+                    BreakpointList::import_breakpoints(workspace, window, cx);
+                });
 
                 workspace.set_debugger_provider(DebuggerProvider(debug_panel.clone()));
 
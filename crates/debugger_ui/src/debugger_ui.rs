@@ -50,12 +50,22 @@ actions!(
         StepOut,
         /// Steps back to the previous statement.
         StepBack,
+        /// Steps into the next machine instruction.
+        StepIntoInstruction,
+        /// Steps over the next machine instruction.
+        StepOverInstruction,
         /// Stops the debugging session.
         Stop,
         /// Toggles whether to ignore all breakpoints.
         ToggleIgnoreBreakpoints,
         /// Clears all breakpoints in the project.
         ClearAllBreakpoints,
+        /// Adds a breakpoint on a function by name.
+        AddFunctionBreakpoint,
+        /// Exports all breakpoints in the project to a JSON file.
+        ExportBreakpoints,
+        /// Imports breakpoints from a JSON file into the project.
+        ImportBreakpoints,
         /// Focuses on the debugger console panel.
         FocusConsole,
         /// Focuses on the variables panel.
@@ -193,6 +203,22 @@ pub fn init(cx: &mut App) {
                             active_item.update(cx, |item, cx| item.step_out(cx)).ok();
                         }
                     })
+                    .on_action({
+                        let active_item = active_item.clone();
+                        move |_: &StepIntoInstruction, _, cx| {
+                            active_item
+                                .update(cx, |item, cx| item.step_in_instruction(cx))
+                                .ok();
+                        }
+                    })
+                    .on_action({
+                        let active_item = active_item.clone();
+                        move |_: &StepOverInstruction, _, cx| {
+                            active_item
+                                .update(cx, |item, cx| item.step_over_instruction(cx))
+                                .ok();
+                        }
+                    })
                     .when(supports_step_back, |div| {
                         let active_item = active_item.clone();
                         div.on_action(move |_: &StepBack, _, cx| {
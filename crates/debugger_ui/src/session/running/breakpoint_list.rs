@@ -5,19 +5,23 @@ use std::{
     time::Duration,
 };
 
+use anyhow::Context as _;
 use dap::{Capabilities, ExceptionBreakpointsFilter, adapters::DebugAdapterName};
 use db::kvp::KEY_VALUE_STORE;
 use editor::Editor;
 use gpui::{
-    Action, AppContext, ClickEvent, Entity, FocusHandle, Focusable, MouseButton, ScrollStrategy,
-    Task, UniformListScrollHandle, WeakEntity, actions, uniform_list,
+    Action, AppContext, ClickEvent, Entity, FocusHandle, Focusable, MouseButton, PathPromptOptions,
+    ScrollStrategy, Task, UniformListScrollHandle, WeakEntity, actions, uniform_list,
 };
 use itertools::Itertools;
 use language::Point;
 use project::{
-    Project,
+    DirectoryLister, Fs as _, Project,
     debugger::{
-        breakpoint_store::{BreakpointEditAction, BreakpointStore, SourceBreakpoint},
+        breakpoint_store::{
+            BreakpointEditAction, BreakpointSet, BreakpointStore, FunctionBreakpoint,
+            SourceBreakpoint,
+        },
         dap_store::{DapStore, PersistedAdapterOptions},
         session::Session,
     },
@@ -28,9 +32,11 @@ use ui::{
     ScrollAxes, StatefulInteractiveElement, Tooltip, WithScrollbar, prelude::*,
 };
 use util::rel_path::RelPath;
-use workspace::Workspace;
+use workspace::{Workspace, notifications::DetachAndPromptErr};
 use zed_actions::{ToggleEnableBreakpoint, UnsetBreakpoint};
 
+use crate::AddFunctionBreakpoint;
+
 actions!(
     debugger,
     [
@@ -43,6 +49,7 @@ actions!(
 #[derive(Clone, Copy, PartialEq)]
 pub(crate) enum SelectedBreakpointKind {
     Source,
+    Function,
     Exception,
     Data,
 }
@@ -58,6 +65,7 @@ pub(crate) struct BreakpointList {
     selected_ix: Option<usize>,
     max_width_index: Option<usize>,
     input: Entity<Editor>,
+    function_breakpoint_input: Option<Entity<Editor>>,
     strip_mode: Option<ActiveBreakpointStripMode>,
     serialize_exception_breakpoints_task: Option<Task<anyhow::Result<()>>>,
 }
@@ -104,6 +112,7 @@ impl BreakpointList {
                 scroll_handle,
                 selected_ix: None,
                 input: cx.new(|cx| Editor::single_line(window, cx)),
+                function_breakpoint_input: None,
                 strip_mode: None,
                 serialize_exception_breakpoints_task: None,
             };
@@ -139,6 +148,111 @@ impl BreakpointList {
         })
     }
 
+    pub(crate) fn export_breakpoints(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let project = workspace.project().clone();
+        let breakpoint_set = project
+            .read(cx)
+            .breakpoint_store()
+            .read(cx)
+            .export_breakpoints(cx);
+        let fs = workspace.app_state().fs.clone();
+        let path = workspace.prompt_for_new_path(
+            DirectoryLister::Local(project, fs.clone()),
+            Some("breakpoints.json".into()),
+            window,
+            cx,
+        );
+        cx.spawn(async move |_, _| {
+            let Some(path) = path.await?.and_then(|paths| paths.into_iter().next()) else {
+                return Ok(());
+            };
+            let contents = serde_json::to_string_pretty(&breakpoint_set)?;
+            fs.atomic_write(path, contents).await
+        })
+        .detach_and_prompt_err("Failed to export breakpoints", window, cx, |_, _, _| None);
+    }
+
+    pub(crate) fn import_breakpoints(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let project = workspace.project().clone();
+        let fs = workspace.app_state().fs.clone();
+        let paths = workspace.prompt_for_open_path(
+            PathPromptOptions {
+                files: true,
+                directories: false,
+                multiple: false,
+                prompt: None,
+            },
+            DirectoryLister::Local(project.clone(), fs.clone()),
+            window,
+            cx,
+        );
+        cx.spawn(async move |_, cx| {
+            let Some(path) = paths.await?.and_then(|paths| paths.into_iter().next()) else {
+                return Ok(());
+            };
+            let contents = fs.load(&path).await?;
+            let breakpoint_set: BreakpointSet = serde_json::from_str(&contents)
+                .with_context(|| format!("parsing breakpoints from {path:?}"))?;
+            project
+                .update(cx, |project, cx| {
+                    project
+                        .breakpoint_store()
+                        .update(cx, |store, cx| store.import_breakpoints(breakpoint_set, cx))
+                })
+                .await
+        })
+        .detach_and_prompt_err("Failed to import breakpoints", window, cx, |_, _, _| None);
+    }
+
+    fn edit_function_breakpoint(&self, name: &str, action: BreakpointEditAction, cx: &mut App) {
+        self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+            breakpoint_store.edit_function_breakpoint(name, action, cx);
+        })
+    }
+
+    fn add_function_breakpoint(
+        &mut self,
+        _: &AddFunctionBreakpoint,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let input = self
+            .function_breakpoint_input
+            .get_or_insert_with(|| {
+                cx.new(|cx| {
+                    let mut editor = Editor::single_line(window, cx);
+                    editor.set_placeholder_text("Function name, e.g. rust_panic", window, cx);
+                    editor
+                })
+            })
+            .clone();
+        input.focus_handle(cx).focus(window, cx);
+        cx.notify();
+    }
+
+    fn confirm_function_breakpoint(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(input) = self.function_breakpoint_input.take() else {
+            return;
+        };
+        let name = input.read(cx).text(cx);
+        let name = name.trim();
+        if !name.is_empty() {
+            self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+                breakpoint_store.add_function_breakpoint(FunctionBreakpoint::new(name), cx);
+            });
+        }
+        self.focus_handle.focus(window, cx);
+        cx.notify();
+    }
+
     fn go_to_line_breakpoint(
         &mut self,
         path: Arc<Path>,
@@ -179,6 +293,10 @@ impl BreakpointList {
                     bp.breakpoint.state
                         == project::debugger::breakpoint_store::BreakpointState::Enabled,
                 ),
+                BreakpointEntryKind::FunctionBreakpoint(bp) => (
+                    SelectedBreakpointKind::Function,
+                    bp.breakpoint.state.is_enabled(),
+                ),
                 BreakpointEntryKind::ExceptionBreakpoint(bp) => {
                     (SelectedBreakpointKind::Exception, bp.is_enabled)
                 }
@@ -201,11 +319,11 @@ impl BreakpointList {
             ActiveBreakpointStripMode::Condition => "Set Condition",
             ActiveBreakpointStripMode::HitCondition => "Set Hit Condition",
         };
-        let mut is_exception_breakpoint = true;
+        let mut is_read_only = true;
         let active_value = self.selected_ix.and_then(|ix| {
-            self.breakpoints.get(ix).and_then(|bp| {
-                if let BreakpointEntryKind::LineBreakpoint(bp) = &bp.kind {
-                    is_exception_breakpoint = false;
+            self.breakpoints.get(ix).and_then(|bp| match &bp.kind {
+                BreakpointEntryKind::LineBreakpoint(bp) => {
+                    is_read_only = false;
                     match prop {
                         ActiveBreakpointStripMode::Log => bp.breakpoint.message.clone(),
                         ActiveBreakpointStripMode::Condition => bp.breakpoint.condition.clone(),
@@ -213,15 +331,24 @@ impl BreakpointList {
                             bp.breakpoint.hit_condition.clone()
                         }
                     }
-                } else {
-                    None
                 }
+                BreakpointEntryKind::FunctionBreakpoint(bp) => {
+                    is_read_only = prop == ActiveBreakpointStripMode::Log;
+                    match prop {
+                        ActiveBreakpointStripMode::Log => None,
+                        ActiveBreakpointStripMode::Condition => bp.breakpoint.condition.clone(),
+                        ActiveBreakpointStripMode::HitCondition => {
+                            bp.breakpoint.hit_condition.clone()
+                        }
+                    }
+                }
+                _ => None,
             })
         });
 
         self.input.update(cx, |this, cx| {
             this.set_placeholder_text(placeholder, window, cx);
-            this.set_read_only(is_exception_breakpoint);
+            this.set_read_only(is_read_only);
             this.set_text(active_value.as_deref().unwrap_or(""), window, cx);
         });
     }
@@ -309,7 +436,15 @@ impl BreakpointList {
     }
 
     fn dismiss(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
-        if self.input.focus_handle(cx).contains_focused(window, cx) {
+        if self
+            .function_breakpoint_input
+            .as_ref()
+            .is_some_and(|input| input.focus_handle(cx).contains_focused(window, cx))
+        {
+            self.function_breakpoint_input.take();
+            self.focus_handle.focus(window, cx);
+            cx.notify();
+        } else if self.input.focus_handle(cx).contains_focused(window, cx) {
             self.focus_handle.focus(window, cx);
         } else if self.strip_mode.is_some() {
             self.strip_mode.take();
@@ -319,6 +454,14 @@ impl BreakpointList {
         }
     }
     fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if self
+            .function_breakpoint_input
+            .as_ref()
+            .is_some_and(|input| input.focus_handle(cx).contains_focused(window, cx))
+        {
+            self.confirm_function_breakpoint(window, cx);
+            return;
+        }
         let Some(entry) = self.selected_ix.and_then(|ix| self.breakpoints.get_mut(ix)) else {
             return;
         };
@@ -341,8 +484,8 @@ impl BreakpointList {
                             );
                         }
                     }
-                    ActiveBreakpointStripMode::Condition => {
-                        if let BreakpointEntryKind::LineBreakpoint(line_breakpoint) = &entry.kind {
+                    ActiveBreakpointStripMode::Condition => match &entry.kind {
+                        BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                             Self::edit_line_breakpoint_inner(
                                 &self.breakpoint_store,
                                 line_breakpoint.breakpoint.path.clone(),
@@ -351,9 +494,18 @@ impl BreakpointList {
                                 cx,
                             );
                         }
-                    }
-                    ActiveBreakpointStripMode::HitCondition => {
-                        if let BreakpointEntryKind::LineBreakpoint(line_breakpoint) = &entry.kind {
+                        BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                            let name = function_breakpoint.breakpoint.name.clone();
+                            self.edit_function_breakpoint(
+                                &name,
+                                BreakpointEditAction::EditCondition(Arc::from(text)),
+                                cx,
+                            );
+                        }
+                        _ => {}
+                    },
+                    ActiveBreakpointStripMode::HitCondition => match &entry.kind {
+                        BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                             Self::edit_line_breakpoint_inner(
                                 &self.breakpoint_store,
                                 line_breakpoint.breakpoint.path.clone(),
@@ -362,7 +514,16 @@ impl BreakpointList {
                                 cx,
                             );
                         }
-                    }
+                        BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                            let name = function_breakpoint.breakpoint.name.clone();
+                            self.edit_function_breakpoint(
+                                &name,
+                                BreakpointEditAction::EditHitCondition(Arc::from(text)),
+                                cx,
+                            );
+                        }
+                        _ => {}
+                    },
                 }
                 self.focus_handle.focus(window, cx);
             } else {
@@ -377,7 +538,8 @@ impl BreakpointList {
                 let row = line_breakpoint.breakpoint.row;
                 self.go_to_line_breakpoint(path, row, window, cx);
             }
-            BreakpointEntryKind::DataBreakpoint(_)
+            BreakpointEntryKind::FunctionBreakpoint(_)
+            | BreakpointEntryKind::DataBreakpoint(_)
             | BreakpointEntryKind::ExceptionBreakpoint(_) => {}
         }
     }
@@ -402,6 +564,10 @@ impl BreakpointList {
                 let row = line_breakpoint.breakpoint.row;
                 self.edit_line_breakpoint(path, row, BreakpointEditAction::InvertState, cx);
             }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                let name = function_breakpoint.breakpoint.name.clone();
+                self.edit_function_breakpoint(&name, BreakpointEditAction::InvertState, cx);
+            }
             BreakpointEntryKind::ExceptionBreakpoint(exception_breakpoint) => {
                 let id = exception_breakpoint.id.clone();
                 self.toggle_exception_breakpoint(&id, cx);
@@ -424,10 +590,18 @@ impl BreakpointList {
             return;
         };
 
-        if let BreakpointEntryKind::LineBreakpoint(line_breakpoint) = &mut entry.kind {
-            let path = line_breakpoint.breakpoint.path.clone();
-            let row = line_breakpoint.breakpoint.row;
-            self.edit_line_breakpoint(path, row, BreakpointEditAction::Toggle, cx);
+        match &mut entry.kind {
+            BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
+                let path = line_breakpoint.breakpoint.path.clone();
+                let row = line_breakpoint.breakpoint.row;
+                self.edit_line_breakpoint(path, row, BreakpointEditAction::Toggle, cx);
+            }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                let name = function_breakpoint.breakpoint.name.clone();
+                self.edit_function_breakpoint(&name, BreakpointEditAction::Toggle, cx);
+            }
+            BreakpointEntryKind::ExceptionBreakpoint(_)
+            | BreakpointEntryKind::DataBreakpoint(_) => {}
         }
         cx.notify();
     }
@@ -585,6 +759,7 @@ impl BreakpointList {
 
         let remove_breakpoint_tooltip = selection_kind.map(|(kind, _)| match kind {
             SelectedBreakpointKind::Source => "Remove breakpoint from a breakpoint list",
+            SelectedBreakpointKind::Function => "Remove function breakpoint from a breakpoint list",
             SelectedBreakpointKind::Exception => {
                 "Exception Breakpoints cannot be removed from the breakpoint list"
             }
@@ -649,16 +824,38 @@ impl BreakpointList {
                             }
                         })
                     })
-                    .disabled(
-                        selection_kind.map(|kind| kind.0) != Some(SelectedBreakpointKind::Source),
-                    )
+                    .disabled(!matches!(
+                        selection_kind.map(|kind| kind.0),
+                        Some(SelectedBreakpointKind::Source | SelectedBreakpointKind::Function)
+                    ))
                     .on_click({
+                        let focus_handle = focus_handle.clone();
                         move |_, window, cx| {
                             focus_handle.focus(window, cx);
                             window.dispatch_action(UnsetBreakpoint.boxed_clone(), cx)
                         }
                     }),
             )
+            .child(
+                IconButton::new("add-function-breakpoint-breakpoint-list", IconName::Plus)
+                    .icon_size(IconSize::Small)
+                    .tooltip({
+                        let focus_handle = focus_handle.clone();
+                        move |_window, cx| {
+                            Tooltip::with_meta_in(
+                                "Add Function Breakpoint",
+                                Some(&AddFunctionBreakpoint),
+                                "Stop whenever a function with the given name is entered",
+                                &focus_handle,
+                                cx,
+                            )
+                        }
+                    })
+                    .on_click(move |_, window, cx| {
+                        focus_handle.focus(window, cx);
+                        window.dispatch_action(AddFunctionBreakpoint.boxed_clone(), cx)
+                    }),
+            )
             .into_any_element()
     }
 }
@@ -709,6 +906,18 @@ impl Render for BreakpointList {
                 })
             })
         });
+        let function_breakpoints = self
+            .breakpoint_store
+            .read(cx)
+            .function_breakpoints()
+            .iter()
+            .map(|breakpoint| BreakpointEntry {
+                kind: BreakpointEntryKind::FunctionBreakpoint(FunctionBreakpointEntry {
+                    breakpoint: breakpoint.clone(),
+                }),
+                weak: weak.clone(),
+            })
+            .collect::<Vec<_>>();
         let exception_breakpoints = self.session.as_ref().into_iter().flat_map(|session| {
             session
                 .read(cx)
@@ -733,6 +942,7 @@ impl Render for BreakpointList {
         });
         self.breakpoints.extend(
             breakpoints
+                .chain(function_breakpoints)
                 .chain(data_breakpoints)
                 .chain(exception_breakpoints),
         );
@@ -748,6 +958,9 @@ impl Render for BreakpointList {
                     let dir_len = line_bp.dir.as_ref().map(|d| d.len()).unwrap_or(0);
                     (name_and_line.len() + dir_len) as f32 * text_pixels
                 }
+                BreakpointEntryKind::FunctionBreakpoint(function_bp) => {
+                    function_bp.breakpoint.name.len() as f32 * text_pixels
+                }
                 BreakpointEntryKind::ExceptionBreakpoint(exc_bp) => {
                     exc_bp.data.label.len() as f32 * text_pixels
                 }
@@ -769,6 +982,7 @@ impl Render for BreakpointList {
             .on_action(cx.listener(Self::confirm))
             .on_action(cx.listener(Self::toggle_enable_breakpoint))
             .on_action(cx.listener(Self::unset_breakpoint))
+            .on_action(cx.listener(Self::add_function_breakpoint))
             .on_action(cx.listener(Self::next_breakpoint_property))
             .on_action(cx.listener(Self::previous_breakpoint_property))
             .size_full()
@@ -807,6 +1021,22 @@ impl Render for BreakpointList {
                             .child(self.input.clone()),
                     )
             })
+            .when_some(self.function_breakpoint_input.clone(), |this, input| {
+                this.child(Divider::horizontal().color(DividerColor::Border))
+                    .child(
+                        h_flex()
+                            .p_1()
+                            .gap_1()
+                            .rounded_sm()
+                            .bg(cx.theme().colors().editor_background)
+                            .child(
+                                Icon::new(IconName::DebugBreakpoint)
+                                    .color(Color::Debugger)
+                                    .size(IconSize::XSmall),
+                            )
+                            .child(input),
+                    )
+            })
     }
 }
 
@@ -1170,9 +1400,127 @@ impl ExceptionBreakpoint {
         )
     }
 }
+#[derive(Clone, Debug)]
+struct FunctionBreakpointEntry {
+    breakpoint: FunctionBreakpoint,
+}
+
+impl FunctionBreakpointEntry {
+    fn render(
+        &self,
+        props: SupportedBreakpointProperties,
+        strip_mode: Option<ActiveBreakpointStripMode>,
+        ix: usize,
+        is_selected: bool,
+        focus_handle: FocusHandle,
+        list: WeakEntity<BreakpointList>,
+    ) -> ListItem {
+        let is_enabled = self.breakpoint.state.is_enabled();
+        let icon_name = if is_enabled {
+            IconName::DebugBreakpoint
+        } else {
+            IconName::DebugDisabledBreakpoint
+        };
+        let name = self.breakpoint.name.clone();
+
+        ListItem::new(SharedString::from(format!(
+            "function-breakpoint-ui-item-{}",
+            self.breakpoint.name
+        )))
+        .toggle_state(is_selected)
+        .inset(true)
+        .on_click({
+            let list = list.clone();
+            move |_, window, cx| {
+                list.update(cx, |list, cx| list.select_ix(Some(ix), window, cx))
+                    .ok();
+            }
+        })
+        .on_secondary_mouse_down(|_, _, cx| {
+            cx.stop_propagation();
+        })
+        .start_slot(
+            div()
+                .id(SharedString::from(format!(
+                    "function-breakpoint-ui-item-{}-click-handler",
+                    self.breakpoint.name
+                )))
+                .child(
+                    Icon::new(icon_name)
+                        .color(Color::Debugger)
+                        .size(IconSize::XSmall),
+                )
+                .tooltip({
+                    let focus_handle = focus_handle.clone();
+                    move |_window, cx| {
+                        Tooltip::for_action_in(
+                            if is_enabled {
+                                "Disable Function Breakpoint"
+                            } else {
+                                "Enable Function Breakpoint"
+                            },
+                            &ToggleEnableBreakpoint,
+                            &focus_handle,
+                            cx,
+                        )
+                    }
+                })
+                .on_click({
+                    let list = list.clone();
+                    move |_, _, cx| {
+                        list.update(cx, |this, cx| {
+                            this.edit_function_breakpoint(
+                                &name,
+                                BreakpointEditAction::InvertState,
+                                cx,
+                            );
+                        })
+                        .ok();
+                    }
+                })
+                .on_mouse_down(MouseButton::Left, move |_, _, _| {}),
+        )
+        .child(
+            h_flex()
+                .w_full()
+                .gap_1()
+                .min_h(rems_from_px(26.))
+                .justify_between()
+                .child(
+                    h_flex()
+                        .gap_0p5()
+                        .id(("function-breakpoint-label", ix))
+                        .child(
+                            Label::new(self.breakpoint.name.to_string())
+                                .size(LabelSize::Small)
+                                .line_height_style(ui::LineHeightStyle::UiLabel),
+                        )
+                        .child(
+                            Label::new("function")
+                                .color(Color::Muted)
+                                .size(LabelSize::Small)
+                                .line_height_style(ui::LineHeightStyle::UiLabel),
+                        ),
+                )
+                .child(BreakpointOptionsStrip {
+                    props,
+                    breakpoint: BreakpointEntry {
+                        kind: BreakpointEntryKind::FunctionBreakpoint(self.clone()),
+                        weak: list,
+                    },
+                    is_selected,
+                    focus_handle,
+                    strip_mode,
+                    index: ix,
+                }),
+        )
+    }
+}
+
 #[derive(Clone, Debug)]
 enum BreakpointEntryKind {
     LineBreakpoint(LineBreakpoint),
+    FunctionBreakpoint(FunctionBreakpointEntry),
     ExceptionBreakpoint(ExceptionBreakpoint),
     DataBreakpoint(DataBreakpoint),
 }
@@ -1201,6 +1549,15 @@ impl BreakpointEntry {
                 focus_handle,
                 self.weak.clone(),
             ),
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => function_breakpoint
+                .render(
+                    props.for_function_breakpoints(),
+                    strip_mode,
+                    ix,
+                    is_selected,
+                    focus_handle,
+                    self.weak.clone(),
+                ),
             BreakpointEntryKind::ExceptionBreakpoint(exception_breakpoint) => exception_breakpoint
                 .render(
                     props.for_exception_breakpoints(),
@@ -1228,6 +1585,11 @@ impl BreakpointEntry {
                 line_breakpoint.breakpoint.path, line_breakpoint.breakpoint.row
             )
             .into(),
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => format!(
+                "function-breakpoint-control-strip--{}",
+                function_breakpoint.breakpoint.name
+            )
+            .into(),
             BreakpointEntryKind::ExceptionBreakpoint(exception_breakpoint) => format!(
                 "exception-breakpoint-control-strip--{}",
                 exception_breakpoint.id
@@ -1255,6 +1617,9 @@ impl BreakpointEntry {
             BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                 line_breakpoint.breakpoint.condition.is_some()
             }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                function_breakpoint.breakpoint.condition.is_some()
+            }
             // We don't support conditions on exception/data breakpoints
             _ => false,
         }
@@ -1265,6 +1630,9 @@ impl BreakpointEntry {
             BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                 line_breakpoint.breakpoint.hit_condition.is_some()
             }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                function_breakpoint.breakpoint.hit_condition.is_some()
+            }
             _ => false,
         }
     }
@@ -1305,6 +1673,10 @@ impl From<&Capabilities> for SupportedBreakpointProperties {
 }
 
 impl SupportedBreakpointProperties {
+    fn for_function_breakpoints(self) -> Self {
+        // Function breakpoints have no log messages in DAP.
+        self.difference(Self::LOG)
+    }
     fn for_exception_breakpoints(self) -> Self {
         // TODO: we don't yet support conditions for exception breakpoints at the data layer, hence all props are disabled here.
         Self::empty()
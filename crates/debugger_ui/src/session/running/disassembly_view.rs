@@ -0,0 +1,364 @@
+use dap::{DisassembledInstruction, StackFrameId};
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, ScrollStrategy, Subscription, Task,
+    UniformListScrollHandle, uniform_list,
+};
+use project::debugger::session::Session;
+use std::ops::Range;
+use ui::{Tooltip, WithScrollbar, prelude::*};
+
+use crate::{
+    StepIntoInstruction, StepOverInstruction,
+    session::running::stack_frame_list::{StackFrameList, StackFrameListEvent},
+};
+
+/// How many instructions to request before the instruction pointer.
+const INSTRUCTIONS_BEFORE: i64 = 50;
+/// How many instructions to request in total for a single disassembly.
+const INSTRUCTION_COUNT: u64 = 200;
+/// When the instruction pointer gets this close to the edge of the loaded
+/// instructions, we request a new window centered around it.
+const REFETCH_MARGIN: usize = 10;
+
+pub(crate) struct DisassemblyView {
+    session: Entity<Session>,
+    stack_frame_list: Entity<StackFrameList>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    instructions: Vec<DisassembledInstruction>,
+    instruction_pointer: Option<String>,
+    _fetch_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DisassemblyView {
+    pub(crate) fn new(
+        session: Entity<Session>,
+        stack_frame_list: Entity<StackFrameList>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let _subscriptions =
+            vec![cx.subscribe(&stack_frame_list, Self::handle_stack_frame_list_events)];
+
+        let mut this = Self {
+            session,
+            stack_frame_list,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            instructions: Vec::new(),
+            instruction_pointer: None,
+            _fetch_task: Task::ready(()),
+            _subscriptions,
+        };
+
+        if let Some(stack_frame_id) = this.stack_frame_list.read(cx).opened_stack_frame_id() {
+            this.show_stack_frame(stack_frame_id, cx);
+        }
+
+        this
+    }
+
+    fn handle_stack_frame_list_events(
+        &mut self,
+        _: Entity<StackFrameList>,
+        event: &StackFrameListEvent,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            StackFrameListEvent::SelectedStackFrameChanged(stack_frame_id) => {
+                self.show_stack_frame(*stack_frame_id, cx);
+            }
+            StackFrameListEvent::BuiltEntries => {}
+        }
+    }
+
+    fn show_stack_frame(&mut self, stack_frame_id: StackFrameId, cx: &mut Context<Self>) {
+        let instruction_pointer = self
+            .stack_frame_list
+            .read(cx)
+            .flatten_entries(true, true)
+            .into_iter()
+            .find(|stack_frame| stack_frame.id == stack_frame_id)
+            .and_then(|stack_frame| stack_frame.instruction_pointer_reference);
+
+        self.instruction_pointer = instruction_pointer.clone();
+        let Some(instruction_pointer) = instruction_pointer else {
+            cx.notify();
+            return;
+        };
+
+        // Stepping by instruction usually stays within the window we already have,
+        // so only go back to the adapter once the pointer gets close to its edges.
+        if let Some(ix) = self.current_instruction_ix()
+            && ix >= REFETCH_MARGIN
+            && ix + REFETCH_MARGIN < self.instructions.len()
+        {
+            self.scroll_handle
+                .scroll_to_item(ix, ScrollStrategy::Center);
+            cx.notify();
+            return;
+        }
+
+        let instructions = self.session.update(cx, |session, cx| {
+            session.disassemble(
+                instruction_pointer,
+                -INSTRUCTIONS_BEFORE,
+                INSTRUCTION_COUNT,
+                cx,
+            )
+        });
+        self._fetch_task = cx.spawn(async move |this, cx| {
+            let Some(instructions) = instructions.await else {
+                return;
+            };
+            this.update(cx, |this, cx| {
+                this.instructions = instructions;
+                if let Some(ix) = this.current_instruction_ix() {
+                    this.scroll_handle
+                        .scroll_to_item(ix, ScrollStrategy::Center);
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn current_instruction_ix(&self) -> Option<usize> {
+        let instruction_pointer = self.instruction_pointer.as_deref()?;
+        let parsed = parse_address(instruction_pointer);
+        self.instructions.iter().position(|instruction| {
+            instruction.address == instruction_pointer
+                || parsed.is_some() && parse_address(&instruction.address) == parsed
+        })
+    }
+
+    #[cfg(test)]
+    pub(crate) fn instructions(&self) -> &[DisassembledInstruction] {
+        &self.instructions
+    }
+
+    pub(crate) fn toggle_breakpoint(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let Some(instruction) = self.instructions.get(ix) else {
+            return;
+        };
+        let instruction_reference = instruction.address.clone();
+        self.session.update(cx, |session, cx| {
+            session.toggle_instruction_breakpoint(instruction_reference, cx);
+        });
+        cx.notify();
+    }
+
+    fn render_entry(
+        &mut self,
+        ix: usize,
+        current_ix: Option<usize>,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let instruction = &self.instructions[ix];
+        let session = self.session.read(cx);
+        let supports_breakpoints = session
+            .capabilities()
+            .supports_instruction_breakpoints
+            .unwrap_or_default();
+        let has_breakpoint = session.has_instruction_breakpoint(&instruction.address);
+        let is_current = current_ix == Some(ix);
+        let symbol = instruction
+            .symbol
+            .clone()
+            .filter(|symbol| ix == 0 || self.instructions[ix - 1].symbol.as_ref() != Some(symbol));
+        let source_line = instruction.line.map(|line| {
+            let file = instruction
+                .location
+                .as_ref()
+                .and_then(|source| source.name.clone())
+                .unwrap_or_default();
+            format!("{file}:{line}")
+        });
+
+        h_flex()
+            .id(("disassembly-instruction", ix))
+            .w_full()
+            .gap_2()
+            .px_1()
+            .hover(|style| style.bg(cx.theme().colors().element_hover))
+            .when(is_current, |this| {
+                this.bg(cx.theme().colors().editor_debugger_active_line_background)
+            })
+            .child(
+                div()
+                    .id(("disassembly-breakpoint", ix))
+                    .w_4()
+                    .flex_none()
+                    .when(has_breakpoint, |this| {
+                        this.child(
+                            Icon::new(IconName::DebugBreakpoint)
+                                .size(IconSize::XSmall)
+                                .color(Color::Debugger),
+                        )
+                    })
+                    .when(supports_breakpoints, |this| {
+                        this.cursor_pointer()
+                            .tooltip(Tooltip::text("Toggle Instruction Breakpoint"))
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.toggle_breakpoint(ix, cx);
+                            }))
+                    }),
+            )
+            .child(
+                Label::new(instruction.address.clone())
+                    .buffer_font(cx)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .when_some(instruction.instruction_bytes.clone(), |this, bytes| {
+                this.child(
+                    Label::new(bytes)
+                        .buffer_font(cx)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+            .child(
+                Label::new(instruction.instruction.clone())
+                    .buffer_font(cx)
+                    .size(LabelSize::Small)
+                    .when(is_current, |this| this.color(Color::Accent)),
+            )
+            .when_some(symbol, |this, symbol| {
+                this.child(
+                    Label::new(symbol)
+                        .buffer_font(cx)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+            .when_some(source_line, |this, source_line| {
+                this.child(
+                    Label::new(source_line)
+                        .size(LabelSize::Small)
+                        .color(Color::Placeholder),
+                )
+            })
+            .into_any()
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle.clone();
+        h_flex()
+            .w_full()
+            .px_1()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                IconButton::new("disassembly-step-over", IconName::DebugStepOver)
+                    .icon_size(IconSize::Small)
+                    .tooltip({
+                        let focus_handle = focus_handle.clone();
+                        move |_window, cx| {
+                            Tooltip::for_action_in(
+                                "Step Over Instruction",
+                                &StepOverInstruction,
+                                &focus_handle,
+                                cx,
+                            )
+                        }
+                    })
+                    .on_click(|_, window, cx| {
+                        window.dispatch_action(Box::new(StepOverInstruction), cx)
+                    }),
+            )
+            .child(
+                IconButton::new("disassembly-step-into", IconName::DebugStepInto)
+                    .icon_size(IconSize::Small)
+                    .tooltip(move |_window, cx| {
+                        Tooltip::for_action_in(
+                            "Step Into Instruction",
+                            &StepIntoInstruction,
+                            &focus_handle,
+                            cx,
+                        )
+                    })
+                    .on_click(|_, window, cx| {
+                        window.dispatch_action(Box::new(StepIntoInstruction), cx)
+                    }),
+            )
+    }
+
+    fn render_list(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
+        uniform_list(
+            "disassembly-view",
+            self.instructions.len(),
+            cx.processor(|this, range: Range<usize>, _window, cx| {
+                let current_ix = this.current_instruction_ix();
+                range
+                    .map(|ix| this.render_entry(ix, current_ix, cx))
+                    .collect()
+            }),
+        )
+        .track_scroll(&self.scroll_handle)
+        .size_full()
+    }
+}
+
+/// Parses the addresses adapters report, which are usually hex strings
+/// prefixed with `0x`, but may differ in zero-padding between requests.
+fn parse_address(address: &str) -> Option<u64> {
+    let address = address.trim();
+    match address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => address.parse().ok(),
+    }
+}
+
+impl Focusable for DisassemblyView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for DisassemblyView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = if self.instructions.is_empty() {
+            let message = if self.instruction_pointer.is_some() {
+                "Loading disassembly…"
+            } else {
+                "Disassembly is available when the selected stack frame is paused"
+            };
+            h_flex()
+                .size_full()
+                .justify_center()
+                .child(Label::new(message).color(Color::Muted))
+                .into_any_element()
+        } else {
+            div()
+                .size_full()
+                .child(self.render_list(cx))
+                .vertical_scrollbar_for(&self.scroll_handle, window, cx)
+                .into_any_element()
+        };
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(self.render_header(cx))
+            .child(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_address;
+
+    #[test]
+    fn test_parse_address() {
+        assert_eq!(parse_address("0x0000000100003f40"), Some(0x100003f40));
+        assert_eq!(parse_address("0X100003F40"), Some(0x100003f40));
+        assert_eq!(parse_address(" 4096 "), Some(4096));
+        assert_eq!(parse_address("main+12"), None);
+    }
+}
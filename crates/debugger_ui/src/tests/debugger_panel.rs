@@ -9,8 +9,8 @@ use dap::{
     adapters::DebugTaskDefinition,
     client::SessionId,
     requests::{
-        Continue, Disconnect, Launch, Next, RunInTerminal, SetBreakpoints, StackTrace,
-        StartDebugging, StepBack, StepIn, StepOut, Threads,
+        Continue, Disconnect, Launch, Next, RunInTerminal, SetBreakpoints, SetFunctionBreakpoints,
+        StackTrace, StartDebugging, StepBack, StepIn, StepOut, Threads,
     },
 };
 use editor::{
@@ -20,7 +20,13 @@ use editor::{
 use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
 use project::{
     FakeFs, Project,
-    debugger::session::{ThreadId, ThreadStatus},
+    debugger::{
+        breakpoint_store::{
+            BreakpointEditAction, BreakpointSet, ExportedFunctionBreakpoint,
+            ExportedSourceBreakpoint, FunctionBreakpoint,
+        },
+        session::{ThreadId, ThreadStatus},
+    },
 };
 use serde_json::json;
 use std::{
@@ -1950,3 +1956,194 @@ async fn test_adapter_shutdown_with_child_sessions_on_app_quit(
         "Child session should have received disconnect request"
     );
 }
+
+#[gpui::test]
+async fn test_function_breakpoints_are_sent_to_adapter(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<dap::requests::Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_function_breakpoints: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    let sent_breakpoints = Arc::new(parking_lot::Mutex::new(Vec::new()));
+    client.on_request::<SetFunctionBreakpoints, _>({
+        let sent_breakpoints = sent_breakpoints.clone();
+        move |_, args| {
+            *sent_breakpoints.lock() = args
+                .breakpoints
+                .iter()
+                .map(|breakpoint| (breakpoint.name.clone(), breakpoint.condition.clone()))
+                .collect();
+            Ok(dap::SetFunctionBreakpointsResponse {
+                breakpoints: Vec::default(),
+            })
+        }
+    });
+
+    let breakpoint_store = project.read_with(cx, |project, _| project.breakpoint_store());
+    breakpoint_store.update(cx, |store, cx| {
+        store.add_function_breakpoint(FunctionBreakpoint::new("rust_panic"), cx);
+        store.add_function_breakpoint(FunctionBreakpoint::new("std::process::abort"), cx);
+    });
+    cx.run_until_parked();
+    assert_eq!(
+        *sent_breakpoints.lock(),
+        vec![
+            ("rust_panic".to_string(), None),
+            ("std::process::abort".to_string(), None)
+        ]
+    );
+
+    breakpoint_store.update(cx, |store, cx| {
+        store.edit_function_breakpoint(
+            "rust_panic",
+            BreakpointEditAction::EditCondition("code != 0".into()),
+            cx,
+        );
+        store.edit_function_breakpoint(
+            "std::process::abort",
+            BreakpointEditAction::InvertState,
+            cx,
+        );
+    });
+    cx.run_until_parked();
+    assert_eq!(
+        *sent_breakpoints.lock(),
+        vec![("rust_panic".to_string(), Some("code != 0".to_string()))]
+    );
+
+    cx.dispatch_action(crate::ClearAllBreakpoints);
+    cx.run_until_parked();
+    assert!(sent_breakpoints.lock().is_empty());
+}
+
+#[gpui::test]
+async fn test_export_and_import_breakpoints(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "src": {
+                "main.rs": "First line\nSecond line\nThird line\nFourth line",
+            }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let worktree_id = project.read_with(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, rel_path("src/main.rs")), cx)
+        })
+        .await
+        .unwrap();
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(buffer, cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+    editor.update_in(cx, |editor, window, cx| {
+        editor.move_down(&zed_actions::editor::MoveDown, window, cx);
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+    });
+
+    let breakpoint_store = project.read_with(cx, |project, _| project.breakpoint_store());
+    breakpoint_store.update(cx, |store, cx| {
+        let mut breakpoint = FunctionBreakpoint::new("rust_panic");
+        breakpoint.hit_condition = Some("3".into());
+        store.add_function_breakpoint(breakpoint, cx);
+    });
+
+    let exported = breakpoint_store.read_with(cx, |store, cx| store.export_breakpoints(cx));
+    assert_eq!(
+        exported,
+        BreakpointSet {
+            source: vec![ExportedSourceBreakpoint {
+                path: "src/main.rs".into(),
+                line: 2,
+                condition: None,
+                hit_condition: None,
+                log_message: None,
+                disabled: false,
+            }],
+            functions: vec![ExportedFunctionBreakpoint {
+                name: "rust_panic".into(),
+                condition: None,
+                hit_condition: Some("3".into()),
+                disabled: false,
+            }],
+        }
+    );
+
+    let json = serde_json::to_string(&exported).unwrap();
+    breakpoint_store.update(cx, |store, cx| store.clear_breakpoints(cx));
+    cx.run_until_parked();
+
+    breakpoint_store
+        .update(cx, |store, cx| {
+            store.import_breakpoints(serde_json::from_str(&json).unwrap(), cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+
+    let imported = breakpoint_store.read_with(cx, |store, cx| store.export_breakpoints(cx));
+    assert_eq!(imported, exported);
+
+    let result = breakpoint_store
+        .update(cx, |store, cx| {
+            store.import_breakpoints(
+                BreakpointSet {
+                    source: vec![ExportedSourceBreakpoint {
+                        path: "src/missing.rs".into(),
+                        line: 1,
+                        condition: None,
+                        hit_condition: None,
+                        log_message: None,
+                        disabled: false,
+                    }],
+                    functions: Vec::new(),
+                },
+                cx,
+            )
+        })
+        .await;
+    assert!(result.is_err(), "unknown paths should be reported");
+}
@@ -0,0 +1,216 @@
+use crate::{
+    StepIntoInstruction,
+    debugger_panel::DebugPanel,
+    persistence::DebuggerPaneItem,
+    tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
+};
+use dap::{
+    SteppingGranularity, StoppedEvent,
+    requests::{Disassemble, Initialize, SetInstructionBreakpoints, StackTrace, StepIn, Threads},
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use parking_lot::Mutex;
+use project::{FakeFs, Project};
+use std::sync::Arc;
+use util::path;
+
+fn instruction(address: &str, instruction: &str) -> dap::DisassembledInstruction {
+    dap::DisassembledInstruction {
+        address: address.into(),
+        instruction_bytes: None,
+        instruction: instruction.into(),
+        symbol: None,
+        location: None,
+        line: None,
+        column: None,
+        end_line: None,
+        end_column: None,
+        presentation_hint: None,
+    }
+}
+
+#[gpui::test]
+async fn test_disassembly_view(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_disassemble_request: Some(true),
+                supports_instruction_breakpoints: Some(true),
+                supports_stepping_granularity: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![dap::StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: None,
+                line: 0,
+                column: 0,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: Some("0x1004".into()),
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    let instructions = vec![
+        instruction("0x1000", "push rbp"),
+        instruction("0x1004", "mov rbp, rsp"),
+        instruction("0x1008", "ret"),
+    ];
+
+    let disassemble_requests = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<Disassemble, _>({
+        let disassemble_requests = disassemble_requests.clone();
+        let instructions = instructions.clone();
+        move |_, args| {
+            disassemble_requests.lock().push(args);
+            Ok(dap::DisassembleResponse {
+                instructions: instructions.clone(),
+            })
+        }
+    });
+
+    let instruction_breakpoint_requests = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<SetInstructionBreakpoints, _>({
+        let instruction_breakpoint_requests = instruction_breakpoint_requests.clone();
+        move |_, args| {
+            instruction_breakpoint_requests
+                .lock()
+                .push(args.breakpoints);
+            Ok(dap::SetInstructionBreakpointsResponse {
+                breakpoints: Vec::new(),
+            })
+        }
+    });
+
+    let step_in_requests = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<StepIn, _>({
+        let step_in_requests = step_in_requests.clone();
+        move |_, args| {
+            step_in_requests.lock().push(args);
+            Ok(())
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.running_state().clone()
+        });
+
+    running_state.update_in(cx, |this, window, cx| {
+        this.activate_item(DebuggerPaneItem::Disassembly, window, cx);
+        cx.refresh_windows();
+    });
+
+    cx.run_until_parked();
+
+    {
+        let requests = disassemble_requests.lock();
+        let request = requests
+            .last()
+            .expect("Disassemble should be requested for the selected stack frame");
+        assert_eq!(request.memory_reference, "0x1004");
+        assert!(
+            request.instruction_offset.unwrap_or_default() < 0,
+            "Instructions before the instruction pointer should be requested too"
+        );
+        assert_eq!(request.resolve_symbols, Some(true));
+    }
+
+    let disassembly_view = running_state.update(cx, |state, _| state.disassembly_view().clone());
+    disassembly_view.update(cx, |view, _| {
+        let addresses = view
+            .instructions()
+            .iter()
+            .map(|instruction| instruction.address.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(addresses, ["0x1000", "0x1004", "0x1008"]);
+    });
+
+    disassembly_view.update(cx, |view, cx| view.toggle_breakpoint(1, cx));
+    cx.run_until_parked();
+
+    assert_eq!(
+        instruction_breakpoint_requests.lock().pop(),
+        Some(vec![dap::InstructionBreakpoint {
+            instruction_reference: "0x1004".into(),
+            offset: None,
+            condition: None,
+            hit_condition: None,
+            mode: None,
+        }])
+    );
+    session.update(cx, |session, _| {
+        assert!(session.has_instruction_breakpoint("0x1004"));
+    });
+
+    disassembly_view.update(cx, |view, cx| view.toggle_breakpoint(1, cx));
+    cx.run_until_parked();
+
+    assert_eq!(
+        instruction_breakpoint_requests.lock().pop(),
+        Some(Vec::new())
+    );
+    session.update(cx, |session, _| {
+        assert!(!session.has_instruction_breakpoint("0x1004"));
+    });
+
+    cx.dispatch_action(StepIntoInstruction);
+    cx.run_until_parked();
+
+    let requests = step_in_requests.lock();
+    let request = requests
+        .last()
+        .expect("StepIntoInstruction should send a stepIn request");
+    assert_eq!(request.thread_id, 1);
+    assert_eq!(request.granularity, Some(SteppingGranularity::Instruction));
+}
@@ -199,7 +199,7 @@ use std::{
     sync::Arc,
     time::{Duration, Instant},
 };
-use task::{ResolvedTask, RunnableTag, TaskTemplate, TaskVariables};
+use task::{ResolvedTask, RunnableTag, TaskTemplate, TaskVariables, TestOutcome};
 use text::{BufferId, FromAnchor, OffsetUtf16, Rope, ToOffset as _, ToPoint as _};
 use theme::{
     AccentColors, ActiveTheme, GlobalTheme, PlayerColor, StatusColors, SyntaxTheme, Theme,
@@ -1151,6 +1151,10 @@ pub struct Editor {
     autoclose_regions: Vec<AutocloseRegion>,
     snippet_stack: InvalidationStack<SnippetState>,
     select_syntax_node_history: SelectSyntaxNodeHistory,
+    select_larger_syntax_node_task: Option<Task<()>>,
+    /// How many times to expand the selections once the language server
+    /// responds with their selection ranges.
+    pending_larger_syntax_node_expansions: usize,
     ime_transaction: Option<TransactionId>,
     pub diagnostics_max_severity: DiagnosticSeverity,
     active_diagnostics: ActiveDiagnostic,
@@ -2387,6 +2391,8 @@ impl Editor {
             autoclose_regions: Vec::new(),
             snippet_stack: InvalidationStack::default(),
             select_syntax_node_history: SelectSyntaxNodeHistory::default(),
+            select_larger_syntax_node_task: None,
+            pending_larger_syntax_node_expansions: 0,
             ime_transaction: None,
             active_diagnostics: ActiveDiagnostic::None,
             show_inline_diagnostics: ProjectSettings::get_global(cx).diagnostics.inline.enabled,
@@ -9220,38 +9226,40 @@ impl Editor {
         is_active: bool,
         row: DisplayRow,
         breakpoint: Option<(Anchor, Breakpoint, Option<BreakpointSessionState>)>,
+        test_outcome: Option<TestOutcome>,
         cx: &mut Context<Self>,
     ) -> IconButton {
-        let color = Color::Muted;
+        let (icon, color) = match test_outcome {
+            Some(TestOutcome::Passed) => (ui::IconName::Check, Color::Success),
+            Some(TestOutcome::Failed) => (ui::IconName::XCircle, Color::Error),
+            Some(TestOutcome::Skipped) | None => (ui::IconName::PlayOutlined, Color::Muted),
+        };
         let position = breakpoint.as_ref().map(|(anchor, _, _)| *anchor);
 
-        IconButton::new(
-            ("run_indicator", row.0 as usize),
-            ui::IconName::PlayOutlined,
-        )
-        .shape(ui::IconButtonShape::Square)
-        .icon_size(IconSize::XSmall)
-        .icon_color(color)
-        .toggle_state(is_active)
-        .on_click(cx.listener(move |editor, e: &ClickEvent, window, cx| {
-            let quick_launch = match e {
-                ClickEvent::Keyboard(_) => true,
-                ClickEvent::Mouse(e) => e.down.button == MouseButton::Left,
-            };
+        IconButton::new(("run_indicator", row.0 as usize), icon)
+            .shape(ui::IconButtonShape::Square)
+            .icon_size(IconSize::XSmall)
+            .icon_color(color)
+            .toggle_state(is_active)
+            .on_click(cx.listener(move |editor, e: &ClickEvent, window, cx| {
+                let quick_launch = match e {
+                    ClickEvent::Keyboard(_) => true,
+                    ClickEvent::Mouse(e) => e.down.button == MouseButton::Left,
+                };
 
-            window.focus(&editor.focus_handle(cx), cx);
-            editor.toggle_code_actions(
-                &ToggleCodeActions {
-                    deployed_from: Some(CodeActionSource::RunMenu(row)),
-                    quick_launch,
-                },
-                window,
-                cx,
-            );
-        }))
-        .on_right_click(cx.listener(move |editor, event: &ClickEvent, window, cx| {
-            editor.set_breakpoint_context_menu(row, position, event.position(), window, cx);
-        }))
+                window.focus(&editor.focus_handle(cx), cx);
+                editor.toggle_code_actions(
+                    &ToggleCodeActions {
+                        deployed_from: Some(CodeActionSource::RunMenu(row)),
+                        quick_launch,
+                    },
+                    window,
+                    cx,
+                );
+            }))
+            .on_right_click(cx.listener(move |editor, event: &ClickEvent, window, cx| {
+                editor.set_breakpoint_context_menu(row, position, event.position(), window, cx);
+            }))
     }
 
     pub fn context_menu_visible(&self) -> bool {
@@ -16490,9 +16498,9 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let Some(visible_row_count) = self.visible_row_count() else {
+        if self.visible_row_count().is_none() {
             return;
-        };
+        }
         let old_selections: Box<[_]> = self
             .selections
             .all::<MultiBufferOffset>(&self.display_snapshot(cx))
@@ -16503,15 +16511,171 @@ impl Editor {
 
         self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
 
+        // The language server reports all the ranges enclosing the selections at once, so
+        // presses made while it is responding are applied together when it responds.
+        if self.pending_larger_syntax_node_expansions > 0 {
+            self.pending_larger_syntax_node_expansions += 1;
+            return;
+        }
+
+        let Some(lsp_ranges) = self.lsp_selection_ranges(&old_selections, cx) else {
+            self.select_larger_syntax_node_task = None;
+            self.expand_selections_to_larger_syntax_node(old_selections, Vec::new(), window, cx);
+            return;
+        };
+
+        self.pending_larger_syntax_node_expansions = 1;
+        self.select_larger_syntax_node_task = Some(cx.spawn_in(window, async move |editor, cx| {
+            let lsp_ranges = lsp_ranges.await;
+            editor
+                .update_in(cx, |editor, window, cx| {
+                    let expansions = mem::take(&mut editor.pending_larger_syntax_node_expansions);
+                    // The selections may have moved while the language server was responding.
+                    let current_selections = editor
+                        .selections
+                        .all::<MultiBufferOffset>(&editor.display_snapshot(cx));
+                    let selections_unchanged = current_selections.len() == old_selections.len()
+                        && current_selections
+                            .iter()
+                            .zip(old_selections.iter())
+                            .all(|(current, old)| current.range() == old.range());
+                    if !selections_unchanged {
+                        return;
+                    }
+
+                    let mut selections = old_selections;
+                    for _ in 0..expansions {
+                        // Expanding may merge selections, which no longer match the ranges.
+                        let lsp_ranges = if selections.len() == lsp_ranges.len() {
+                            lsp_ranges.clone()
+                        } else {
+                            Vec::new()
+                        };
+                        editor.expand_selections_to_larger_syntax_node(
+                            selections, lsp_ranges, window, cx,
+                        );
+                        selections = editor
+                            .selections
+                            .all::<MultiBufferOffset>(&editor.display_snapshot(cx))
+                            .into();
+                    }
+                })
+                .ok();
+        }));
+    }
+
+    /// Requests the ranges a language server considers meaningful around the head of each
+    /// selection, ordered from the innermost to the outermost one.
+    ///
+    /// Returns `None` when none of the selected buffers has a language server that
+    /// supports selection ranges, so that callers can fall back to the syntax tree.
+    fn lsp_selection_ranges(
+        &self,
+        selections: &[Selection<MultiBufferOffset>],
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Vec<Vec<(ExcerptId, Range<text::Anchor>)>>>> {
+        let provider = self.semantics_provider.clone()?;
+        let multi_buffer = self.buffer.read(cx);
+        let snapshot = multi_buffer.snapshot(cx);
+        let mut heads_by_buffer =
+            HashMap::<BufferId, (Entity<Buffer>, Vec<(usize, ExcerptId, text::Anchor)>)>::default();
+        for (ix, selection) in selections.iter().enumerate() {
+            let head = snapshot.anchor_before(selection.head());
+            let Some(buffer_id) = head.text_anchor.buffer_id else {
+                continue;
+            };
+            let Some(buffer) = multi_buffer.buffer(buffer_id) else {
+                continue;
+            };
+            heads_by_buffer
+                .entry(buffer_id)
+                .or_insert_with(|| (buffer, Vec::new()))
+                .1
+                .push((ix, head.excerpt_id, head.text_anchor));
+        }
+
+        let requests = heads_by_buffer
+            .into_values()
+            .filter(|(buffer, _)| provider.supports_selection_ranges(buffer, cx))
+            .filter_map(|(buffer, heads)| {
+                let positions = heads.iter().map(|(_, _, position)| *position).collect();
+                let task = provider.selection_ranges(&buffer, positions, cx)?;
+                Some((heads, task))
+            })
+            .collect::<Vec<_>>();
+        if requests.is_empty() {
+            return None;
+        }
+
+        let selection_count = selections.len();
+        Some(cx.background_spawn(async move {
+            let mut ranges = vec![Vec::new(); selection_count];
+            for (heads, task) in requests {
+                match task.await {
+                    Ok(buffer_ranges) => {
+                        for ((ix, excerpt_id, _), selection_ranges) in
+                            heads.into_iter().zip(buffer_ranges)
+                        {
+                            ranges[ix] = selection_ranges
+                                .into_iter()
+                                .map(|range| (excerpt_id, range))
+                                .collect();
+                        }
+                    }
+                    Err(error) => log::error!("failed to fetch selection ranges: {error:#}"),
+                }
+            }
+            ranges
+        }))
+    }
+
+    /// Grows each selection to the smallest enclosing range reported by the language server,
+    /// or to the enclosing syntax node when the server has no range for that selection.
+    fn expand_selections_to_larger_syntax_node(
+        &mut self,
+        old_selections: Box<[Selection<MultiBufferOffset>]>,
+        lsp_ranges: Vec<Vec<(ExcerptId, Range<text::Anchor>)>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(visible_row_count) = self.visible_row_count() else {
+            return;
+        };
+
         let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
         let buffer = self.buffer.read(cx).snapshot(cx);
 
         let mut selected_larger_node = false;
         let mut new_selections = old_selections
             .iter()
-            .map(|selection| {
+            .enumerate()
+            .map(|(ix, selection)| {
                 let old_range = selection.start..selection.end;
 
+                let lsp_range = lsp_ranges.get(ix).and_then(|ranges| {
+                    ranges.iter().find_map(|(excerpt_id, range)| {
+                        let range = buffer.anchor_range_in_excerpt(*excerpt_id, range.clone())?;
+                        let range = range.start.to_offset(&buffer)..range.end.to_offset(&buffer);
+                        let is_larger = range.start <= old_range.start
+                            && old_range.end <= range.end
+                            && range != old_range;
+                        (is_larger
+                            && !display_map.intersects_fold(range.start)
+                            && !display_map.intersects_fold(range.end))
+                        .then_some(range)
+                    })
+                });
+                if let Some(new_range) = lsp_range {
+                    selected_larger_node = true;
+                    return Selection {
+                        id: selection.id,
+                        start: new_range.start,
+                        end: new_range.end,
+                        goal: SelectionGoal::None,
+                        reversed: selection.reversed,
+                    };
+                }
+
                 if let Some((node, _)) = buffer.syntax_ancestor(old_range.clone()) {
                     // manually select word at selection
                     if ["string_content", "inline"].contains(&node.kind()) {
@@ -17140,6 +17304,15 @@ impl Editor {
                 .ok();
         })
     }
+    /// Outcome of the last run of the test whose runnable starts at the given buffer row, if any.
+    fn test_outcome(&self, buffer_id: BufferId, row: BufferRow, cx: &App) -> Option<TestOutcome> {
+        let buffer = self.buffer.read(cx).buffer(buffer_id)?;
+        let abs_path = buffer.read(cx).file()?.as_local()?.abs_path(cx);
+        let project = self.project()?.read(cx);
+        let inventory = project.task_store().read(cx).task_inventory()?;
+        inventory.read(cx).test_outcome(&abs_path, row)
+    }
+
     fn fetch_runnable_ranges(
         snapshot: &DisplaySnapshot,
         range: Range<Anchor>,
@@ -20627,6 +20800,66 @@ impl Editor {
         self.do_stage_or_unstage_and_next(false, window, cx);
     }
 
+    pub fn stage_selected_lines(
+        &mut self,
+        _: &::git::StageSelectedLines,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.stage_or_unstage_selected_lines(true, cx);
+    }
+
+    pub fn unstage_selected_lines(
+        &mut self,
+        _: &::git::UnstageSelectedLines,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.stage_or_unstage_selected_lines(false, cx);
+    }
+
+    /// Stages or unstages the selected lines, leaving the rest of their hunks untouched.
+    pub fn stage_or_unstage_selected_lines(&mut self, stage: bool, cx: &mut Context<Self>) {
+        // The selections of a delegating editor are in the diff base rather than the buffer.
+        if self.delegate_stage_and_restore {
+            return;
+        }
+        let ranges = self.selections.disjoint_anchor_ranges().collect::<Vec<_>>();
+        let task = self.save_buffers_for_ranges_if_needed(&ranges, cx);
+        cx.spawn(async move |this, cx| {
+            task.await?;
+            this.update(cx, |this, cx| {
+                let Some(project) = this.project.clone() else {
+                    return;
+                };
+                let snapshot = this.buffer.read(cx).snapshot(cx);
+                let mut ranges_by_buffer = HashMap::<BufferId, Vec<Range<Point>>>::default();
+                for (buffer, range, _) in snapshot.ranges_to_buffer_ranges(ranges.into_iter()) {
+                    ranges_by_buffer
+                        .entry(buffer.remote_id())
+                        .or_default()
+                        .push(
+                            buffer.offset_to_point(range.start.0)
+                                ..buffer.offset_to_point(range.end.0),
+                        );
+                }
+                for (buffer_id, ranges) in ranges_by_buffer {
+                    let Some(buffer) = project.read(cx).buffer_for_id(buffer_id, cx) else {
+                        continue;
+                    };
+                    let Some(diff) = this.buffer.read(cx).diff_for(buffer_id) else {
+                        continue;
+                    };
+                    let buffer_snapshot = buffer.read(cx).text_snapshot();
+                    diff.update(cx, |diff, cx| {
+                        diff.stage_or_unstage_lines(stage, &ranges, &buffer_snapshot, cx);
+                    });
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn stage_or_unstage_diff_hunks(
         &mut self,
         stage: bool,
@@ -26618,6 +26851,8 @@ pub trait SemanticsProvider {
 
     fn supports_semantic_tokens(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool;
 
+    fn supports_selection_ranges(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool;
+
     fn document_highlights(
         &self,
         buffer: &Entity<Buffer>,
@@ -26647,6 +26882,13 @@ pub trait SemanticsProvider {
         new_name: String,
         cx: &mut App,
     ) -> Option<Task<Result<ProjectTransaction>>>;
+
+    fn selection_ranges(
+        &self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<text::Anchor>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<Vec<Range<text::Anchor>>>>>>;
 }
 
 pub trait CompletionProvider {
@@ -27153,6 +27395,14 @@ impl SemanticsProvider for Entity<Project> {
         })
     }
 
+    fn supports_selection_ranges(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool {
+        self.update(cx, |project, cx| {
+            buffer.update(cx, |buffer, cx| {
+                project.any_language_server_supports_selection_ranges(buffer, cx)
+            })
+        })
+    }
+
     fn inline_values(
         &self,
         buffer_handle: Entity<Buffer>,
@@ -27250,6 +27500,17 @@ impl SemanticsProvider for Entity<Project> {
             project.perform_rename(buffer.clone(), position, new_name, cx)
         }))
     }
+
+    fn selection_ranges(
+        &self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<text::Anchor>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<Vec<Range<text::Anchor>>>>>> {
+        Some(self.update(cx, |project, cx| {
+            project.selection_ranges(buffer, positions, cx)
+        }))
+    }
 }
 
 fn consume_contiguous_rows(
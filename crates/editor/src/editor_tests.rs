@@ -10107,6 +10107,57 @@ async fn test_select_larger_syntax_node_for_cursor_at_symbol(cx: &mut TestAppCon
     });
 }
 
+#[gpui::test]
+async fn test_select_larger_syntax_node_with_lsp_selection_ranges(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            selection_range_provider: Some(lsp::SelectionRangeProviderCapability::Simple(true)),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+
+    cx.set_state("fn main() { let a = foo(bˇar); }");
+
+    let range =
+        |start, end| lsp::Range::new(lsp::Position::new(0, start), lsp::Position::new(0, end));
+    let mut requests = cx
+        .lsp
+        .set_request_handler::<lsp::request::SelectionRangeRequest, _, _>(move |_, _| async move {
+            let statement = lsp::SelectionRange {
+                range: range(12, 29),
+                parent: None,
+            };
+            let call = lsp::SelectionRange {
+                range: range(20, 28),
+                parent: Some(Box::new(statement)),
+            };
+            Ok(Some(vec![lsp::SelectionRange {
+                range: range(24, 27),
+                parent: Some(Box::new(call)),
+            }]))
+        });
+
+    // Presses made before the server responds are all applied once it does.
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    requests.next().await;
+    cx.run_until_parked();
+    cx.assert_editor_state("fn main() { let a = «foo(bar)ˇ»; }");
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    requests.next().await;
+    cx.run_until_parked();
+    cx.assert_editor_state("fn main() { «let a = foo(bar);ˇ» }");
+}
+
 #[gpui::test]
 async fn test_select_larger_smaller_syntax_node_for_string(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
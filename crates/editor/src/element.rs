@@ -525,6 +525,8 @@ impl EditorElement {
         register_action(editor, window, Editor::toggle_staged_selected_diff_hunks);
         register_action(editor, window, Editor::stage_and_next);
         register_action(editor, window, Editor::unstage_and_next);
+        register_action(editor, window, Editor::stage_selected_lines);
+        register_action(editor, window, Editor::unstage_selected_lines);
         register_action(editor, window, Editor::expand_all_diff_hunks);
         register_action(editor, window, Editor::collapse_all_diff_hunks);
         register_action(editor, window, Editor::toggle_review_comments_expanded);
@@ -3252,7 +3254,7 @@ impl EditorElement {
             editor
                 .tasks
                 .iter()
-                .filter_map(|(_, tasks)| {
+                .filter_map(|(&(buffer_id, buffer_row), tasks)| {
                     let multibuffer_point = tasks.offset.to_point(&snapshot.buffer_snapshot());
                     if multibuffer_point < offset_range_start
                         || multibuffer_point > offset_range_end
@@ -3295,11 +3297,13 @@ impl EditorElement {
                     }
 
                     let removed_breakpoint = breakpoints.remove(&display_row);
+                    let test_outcome = editor.test_outcome(buffer_id, buffer_row, cx);
                     let button = editor.render_run_indicator(
                         &self.style,
                         Some(display_row) == active_task_indicator_row,
                         display_row,
                         removed_breakpoint,
+                        test_outcome,
                         cx,
                     );
 
@@ -216,6 +216,17 @@ pub fn deploy_context_menu(
                     .is_some()
             });
 
+        let has_selected_diff_hunks = has_selections && has_git_repo && {
+            let ranges = editor
+                .selections
+                .disjoint_anchor_ranges()
+                .collect::<Vec<_>>();
+            editor
+                .diff_hunks_in_ranges(&ranges, buffer)
+                .next()
+                .is_some()
+        };
+
         let evaluate_selection = window.is_action_available(&EvaluateSelectedText, cx);
         let run_to_cursor = window.is_action_available(&RunToCursor, cx);
         let disable_ai = DisableAiSettings::is_ai_disabled_for_buffer(
@@ -284,6 +295,15 @@ pub fn deploy_context_menu(
                 .action("Copy and Trim", Box::new(CopyAndTrim))
                 .action("Paste", Box::new(Paste))
                 .separator()
+                .when(has_selected_diff_hunks, |builder| {
+                    builder
+                        .action("Stage Selected Lines", Box::new(git::StageSelectedLines))
+                        .action(
+                            "Unstage Selected Lines",
+                            Box::new(git::UnstageSelectedLines),
+                        )
+                        .separator()
+                })
                 .action_disabled_when(
                     !has_reveal_target,
                     if cfg!(target_os = "macos") {
@@ -6,13 +6,15 @@ use git::{
     Oid, RunHook,
     blame::Blame,
     repository::{
-        AskPassDelegate, Branch, CommitDataReader, CommitDetails, CommitOptions, FetchOptions,
-        GRAPH_CHUNK_SIZE, GitRepository, GitRepositoryCheckpoint, InitialGraphCommitData, LogOrder,
-        LogSource, PushOptions, Remote, RepoPath, ResetMode, Worktree,
+        ApplyCommitOutcome, AskPassDelegate, BisectState, BisectTerm, Branch, CommitDataReader,
+        CommitDetails, CommitOptions, FetchOptions, GRAPH_CHUNK_SIZE, GitRepository,
+        GitRepositoryCheckpoint, InitialGraphCommitData, LogOrder, LogSource, MergeStages,
+        PushOptions, RebaseAction, RebaseOperation, RebaseProgress, RebaseTodoEntry, Remote,
+        RepoPath, ResetMode, Submodule, Tag, Worktree,
     },
     status::{
         DiffTreeType, FileStatus, GitStatus, StatusCode, TrackedStatus, TreeDiff, TreeDiffStatus,
-        UnmergedStatus,
+        UnmergedStatus, UnmergedStatusCode,
     },
 };
 use gpui::{AsyncApp, BackgroundExecutor, SharedString, Task};
@@ -20,7 +22,12 @@ use ignore::gitignore::GitignoreBuilder;
 use parking_lot::Mutex;
 use rope::Rope;
 use smol::{channel::Sender, future::FutureExt as _};
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    collections::VecDeque,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::PathBuf,
+    sync::Arc,
+};
 use text::LineEnding;
 use util::{paths::PathStyle, rel_path::RelPath};
 
@@ -53,8 +60,52 @@ pub struct FakeGitRepositoryState {
     pub refs: HashMap<String, String>,
     pub graph_commits: Vec<Arc<InitialGraphCommitData>>,
     pub worktrees: Vec<Worktree>,
+    pub submodules: Vec<Submodule>,
+    /// The URLs the repository's config fetches submodules from, keyed by their path.
+    pub submodule_urls: HashMap<RepoPath, String>,
+    pub merge_stages: HashMap<RepoPath, MergeStages>,
+    /// The commits of the checked out branch, oldest first.
+    pub commits: Vec<FakeCommit>,
+    pub rebase: Option<FakeRebase>,
+    pub bisect: Option<FakeBisect>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FakeCommit {
+    pub sha: Oid,
+    pub message: String,
+    pub author_name: String,
+    pub author_email: String,
+    pub timestamp: i64,
+    /// The new contents of the files changed by the commit, `None` for the deleted ones.
+    pub changes: HashMap<RepoPath, Option<String>>,
+}
+
+/// A rebase that stopped on conflicts.
+#[derive(Debug, Clone)]
+pub struct FakeRebase {
+    /// The history from before the rebase, restored when it is aborted.
+    pub original_commits: Vec<FakeCommit>,
+    pub head_name: Option<String>,
+    pub todo: VecDeque<RebaseTodoEntry>,
+    /// The entry whose commit conflicted, which is committed once the conflicts are resolved.
+    pub stopped_at: Option<RebaseTodoEntry>,
+    pub step: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct FakeBisect {
+    /// The history of the branch the bisect started from, restored when it is reset.
+    pub commits: Vec<FakeCommit>,
+    pub head_name: Option<String>,
+    pub state: BisectState,
 }
 
+/// Files to write to the working tree after changing the repository state, or to remove
+/// when their contents are `None`.
+type Checkout = Vec<(RepoPath, Option<String>)>;
+
 impl FakeGitRepositoryState {
     pub fn new(event_emitter: smol::channel::Sender<PathBuf>) -> Self {
         FakeGitRepositoryState {
@@ -73,8 +124,364 @@ impl FakeGitRepositoryState {
             remotes: HashMap::default(),
             graph_commits: Vec::new(),
             worktrees: Vec::new(),
+            submodules: Vec::new(),
+            submodule_urls: HashMap::default(),
+            merge_stages: HashMap::default(),
+            commits: Vec::new(),
+            rebase: None,
+            bisect: None,
+        }
+    }
+
+    /// Resolves `HEAD`, a ref or a (possibly abbreviated) sha, optionally followed by `~n`,
+    /// to the index of a commit in the history.
+    fn commit_ix(&self, rev: &str) -> Result<usize> {
+        self.commit_ix_in(&self.commits, rev)
+    }
+
+    /// Like `commit_ix`, but looks the commit up in `commits`, of which the history is a prefix.
+    fn commit_ix_in(&self, commits: &[FakeCommit], rev: &str) -> Result<usize> {
+        let (name, generations) = match rev.split_once('~') {
+            Some((name, "")) => (name, 1),
+            Some((name, generations)) => (
+                name,
+                generations
+                    .parse::<usize>()
+                    .with_context(|| format!("invalid revision '{rev}'"))?,
+            ),
+            None => (rev, 0),
+        };
+        let ix = if name == "HEAD" {
+            self.commits.len().checked_sub(1)
+        } else {
+            let sha = ["", "refs/heads/", "refs/tags/"]
+                .iter()
+                .find_map(|prefix| self.refs.get(&format!("{prefix}{name}")))
+                .map_or(name, String::as_str);
+            (!sha.is_empty())
+                .then(|| {
+                    commits
+                        .iter()
+                        .rposition(|commit| commit.sha.to_string().starts_with(sha))
+                })
+                .flatten()
+        };
+        ix.and_then(|ix| ix.checked_sub(generations))
+            .with_context(|| format!("unknown revision '{rev}'"))
+    }
+
+    /// Returns the commits `git log` lists for `range`, oldest first. As the history is linear,
+    /// `base..head` are the commits after `base` up to `head`.
+    fn commits_in_range(&self, range: &str) -> Result<&[FakeCommit]> {
+        fn rev_or_head(rev: &str) -> &str {
+            if rev.is_empty() { "HEAD" } else { rev }
+        }
+        let (base, head) = match range.split_once("..") {
+            Some((base, head)) => (Some(rev_or_head(base)), rev_or_head(head)),
+            None => (None, range),
+        };
+        let end = self.commit_ix(head)? + 1;
+        let start = match base {
+            Some(base) => (self.commit_ix(base)? + 1).min(end),
+            None => 0,
+        };
+        Ok(&self.commits[start..end])
+    }
+
+    /// The contents of the files at `rev`.
+    fn tree_at(&self, rev: &str) -> Result<HashMap<RepoPath, String>> {
+        Ok(tree(&self.commits[..=self.commit_ix(rev)?]))
+    }
+
+    /// Adds `commit` on top of HEAD, updating HEAD and the index to match it.
+    pub(crate) fn push_commit(&mut self, mut commit: FakeCommit) -> Checkout {
+        commit.sha = fake_sha(
+            self.commits.last().map(|parent| parent.sha),
+            &commit.message,
+            commit.timestamp,
+        );
+        let mut checkout = Checkout::new();
+        for (path, content) in &commit.changes {
+            match content {
+                Some(content) => {
+                    self.head_contents.insert(path.clone(), content.clone());
+                    self.index_contents.insert(path.clone(), content.clone());
+                }
+                None => {
+                    self.head_contents.remove(path);
+                    self.index_contents.remove(path);
+                }
+            }
+            checkout.push((path.clone(), content.clone()));
+        }
+        self.commits.push(commit);
+        self.update_head_ref();
+        checkout
+    }
+
+    /// Resets HEAD and the index to the last commit of the history.
+    fn reset_to_history(&mut self) -> Checkout {
+        let tree = tree(&self.commits);
+        let checkout = self
+            .head_contents
+            .keys()
+            .filter(|path| !tree.contains_key(*path))
+            .map(|path| (path.clone(), None))
+            .chain(
+                tree.iter()
+                    .filter(|(path, content)| self.head_contents.get(*path) != Some(content))
+                    .map(|(path, content)| (path.clone(), Some(content.clone()))),
+            )
+            .collect();
+        self.head_contents = tree.clone();
+        self.index_contents = tree;
+        self.update_head_ref();
+        checkout
+    }
+
+    fn update_head_ref(&mut self) {
+        let Some(head) = self.commits.last() else {
+            return;
+        };
+        let sha = head.sha.to_string();
+        if let Some(branch) = &self.current_branch_name {
+            self.refs
+                .insert(format!("refs/heads/{branch}"), sha.clone());
+        }
+        self.refs.insert("HEAD".into(), sha);
+    }
+
+    /// Returns the paths on which `changes`, made on top of `base`, conflict with HEAD.
+    fn conflicting_paths(
+        &self,
+        base: &HashMap<RepoPath, String>,
+        changes: &HashMap<RepoPath, Option<String>>,
+    ) -> Vec<RepoPath> {
+        let mut paths = changes
+            .iter()
+            .filter(|(path, content)| {
+                let head = self.head_contents.get(*path);
+                head != base.get(*path) && head != content.as_ref()
+            })
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+        paths.sort();
+        paths
+    }
+
+    /// Marks `paths` as conflicted, writing conflict markers between the contents at HEAD and
+    /// the ones in `changes` to the working tree.
+    fn mark_conflicts(
+        &mut self,
+        paths: &[RepoPath],
+        changes: &HashMap<RepoPath, Option<String>>,
+        label: &str,
+    ) -> Checkout {
+        paths
+            .iter()
+            .map(|path| {
+                self.unmerged_paths.insert(
+                    path.clone(),
+                    UnmergedStatus {
+                        first_head: UnmergedStatusCode::Updated,
+                        second_head: UnmergedStatusCode::Updated,
+                    },
+                );
+                let ours = self.head_contents.get(path).cloned().unwrap_or_default();
+                let theirs = changes.get(path).cloned().flatten().unwrap_or_default();
+                let content = format!("<<<<<<< HEAD\n{ours}=======\n{theirs}>>>>>>> {label}\n");
+                (path.clone(), Some(content))
+            })
+            .collect()
+    }
+
+    /// The indices of the submodules at `paths`, or of all of them when `paths` is empty.
+    fn submodule_ixs(&self, paths: &[RepoPath]) -> Result<Vec<usize>> {
+        if paths.is_empty() {
+            return Ok((0..self.submodules.len()).collect());
+        }
+        paths
+            .iter()
+            .map(|path| {
+                self.submodules
+                    .iter()
+                    .position(|submodule| submodule.path == *path)
+                    .with_context(|| format!("no submodule at '{}'", path.as_unix_str()))
+            })
+            .collect()
+    }
+
+    /// Copies the URLs of the submodules at `paths` into the config, unless it has one already.
+    fn init_submodules(&mut self, paths: &[RepoPath]) -> Result<()> {
+        for ix in self.submodule_ixs(paths)? {
+            let submodule = &self.submodules[ix];
+            let url = submodule
+                .url
+                .clone()
+                .with_context(|| format!("no URL found for submodule '{}'", submodule.name))?;
+            self.submodule_urls
+                .entry(submodule.path.clone())
+                .or_insert(url.to_string());
+        }
+        Ok(())
+    }
+
+    /// Marks the commit at `ix` of the bisected history with `term`.
+    fn mark_bisect(&mut self, term: BisectTerm, ix: usize) -> Result<()> {
+        let bisect = self.bisect.as_mut().context("not bisecting")?;
+        let sha = bisect.commits[ix].sha;
+        match term {
+            BisectTerm::Good => bisect.state.good.push(sha),
+            BisectTerm::Bad => bisect.state.bad = Some(sha),
+            BisectTerm::Skip => bisect.state.skipped.push(sha),
         }
+        Ok(())
     }
+
+    /// Narrows the bisected range down, checking out the next commit to test until only the
+    /// first bad commit is left.
+    fn bisect_next(&mut self) -> Checkout {
+        let Some(bisect) = self.bisect.as_mut() else {
+            return Checkout::new();
+        };
+        let state = &mut bisect.state;
+        let position = |sha: &Oid| bisect.commits.iter().position(|commit| commit.sha == *sha);
+        let Some(bad_ix) = state.bad.as_ref().and_then(position) else {
+            return Checkout::new();
+        };
+        let Some(good_ix) = state
+            .good
+            .iter()
+            .filter_map(position)
+            .filter(|ix| *ix < bad_ix)
+            .max()
+        else {
+            return Checkout::new();
+        };
+
+        let untested = (good_ix + 1..bad_ix)
+            .filter(|ix| !state.skipped.contains(&bisect.commits[*ix].sha))
+            .collect::<Vec<_>>();
+        state.remaining = untested.len();
+        if good_ix + 1 == bad_ix {
+            state.first_bad = state.bad;
+        }
+        let Some(next_ix) = untested.get(untested.len() / 2) else {
+            return Checkout::new();
+        };
+        self.commits = bisect.commits[..=*next_ix].to_vec();
+        self.reset_to_history()
+    }
+
+    /// Replays the remaining steps of the rebase in progress, stopping on conflicts.
+    fn continue_rebase(&mut self, checkout: &mut Checkout) -> Result<()> {
+        loop {
+            let Some(rebase) = self.rebase.as_mut() else {
+                return Ok(());
+            };
+            let Some(entry) = rebase.todo.pop_front() else {
+                self.rebase = None;
+                return Ok(());
+            };
+            rebase.step += 1;
+            if entry.action == RebaseAction::Drop {
+                continue;
+            }
+
+            let ix = rebase
+                .original_commits
+                .iter()
+                .position(|commit| commit.sha.to_string().starts_with(entry.sha.as_ref()))
+                .with_context(|| format!("unknown commit '{}'", entry.sha))?;
+            let commit = rebase.original_commits[ix].clone();
+            let base = tree(&rebase.original_commits[..ix]);
+            let conflicts = self.conflicting_paths(&base, &commit.changes);
+            if !conflicts.is_empty() {
+                let label = commit.sha.display_short();
+                checkout.extend(self.mark_conflicts(&conflicts, &commit.changes, &label));
+                if let Some(rebase) = self.rebase.as_mut() {
+                    rebase.stopped_at = Some(entry);
+                }
+                bail!("could not apply {label}: conflicts in {conflicts:?}");
+            }
+            checkout.extend(self.replay(&entry, commit)?);
+        }
+    }
+
+    /// Commits `commit` on top of HEAD as the rebase `entry` says.
+    fn replay(&mut self, entry: &RebaseTodoEntry, mut commit: FakeCommit) -> Result<Checkout> {
+        match entry.action {
+            RebaseAction::Pick | RebaseAction::Drop => {}
+            RebaseAction::Reword => {
+                if let Some(message) = &entry.message {
+                    commit.message = message.to_string();
+                }
+            }
+            RebaseAction::Squash | RebaseAction::Fixup => {
+                let mut previous = self
+                    .commits
+                    .pop()
+                    .context("cannot squash without a previous commit")?;
+                if entry.action == RebaseAction::Squash {
+                    previous.message = format!("{}\n\n{}", previous.message, commit.message);
+                }
+                previous.changes.extend(commit.changes);
+                commit = previous;
+            }
+        }
+        Ok(self.push_commit(commit))
+    }
+}
+
+/// The contents of the files after applying the changes of `commits` in order.
+fn tree(commits: &[FakeCommit]) -> HashMap<RepoPath, String> {
+    let mut tree = HashMap::default();
+    for commit in commits {
+        for (path, content) in &commit.changes {
+            match content {
+                Some(content) => tree.insert(path.clone(), content.clone()),
+                None => tree.remove(path),
+            };
+        }
+    }
+    tree
+}
+
+/// Diffs the whole of the old contents of `path` against the whole of the new ones, in a single
+/// hunk. It's no minimal diff, but it's one `git apply` would accept.
+fn file_diff(path: &RepoPath, old: Option<&String>, new: Option<&String>) -> String {
+    let path = path.as_unix_str();
+    let old_lines = old.map_or(Vec::new(), |old| old.lines().collect());
+    let new_lines = new.map_or(Vec::new(), |new| new.lines().collect());
+    let mut diff = format!(
+        "diff --git a/{path} b/{path}\n--- {}\n+++ {}\n@@ -{} +{} @@\n",
+        old.map_or("/dev/null".into(), |_| format!("a/{path}")),
+        new.map_or("/dev/null".into(), |_| format!("b/{path}")),
+        hunk_range(old_lines.len()),
+        hunk_range(new_lines.len()),
+    );
+    for line in old_lines {
+        diff.push_str(&format!("-{line}\n"));
+    }
+    for line in new_lines {
+        diff.push_str(&format!("+{line}\n"));
+    }
+    diff
+}
+
+fn hunk_range(line_count: usize) -> String {
+    let start = if line_count == 0 { 0 } else { 1 };
+    format!("{start},{line_count}")
+}
+
+pub(crate) fn fake_sha(parent: Option<Oid>, message: &str, timestamp: i64) -> Oid {
+    let mut bytes = [0; 20];
+    for (ix, chunk) in bytes.chunks_mut(8).enumerate() {
+        let mut hasher = DefaultHasher::new();
+        (ix, parent, message, timestamp).hash(&mut hasher);
+        chunk.copy_from_slice(&hasher.finish().to_be_bytes()[..chunk.len()]);
+    }
+    Oid::from_bytes(&bytes).unwrap()
 }
 
 impl FakeGitRepository {
@@ -92,6 +499,90 @@ impl FakeGitRepository {
         }
         .boxed()
     }
+
+    /// Like `with_state_async`, but also writes the files `f` checks out to the working tree.
+    fn with_state_and_checkout<F, T>(&self, f: F) -> BoxFuture<'static, Result<T>>
+    where
+        F: 'static + Send + FnOnce(&mut FakeGitRepositoryState, &mut Checkout) -> Result<T>,
+        T: 'static + Send,
+    {
+        let fs = self.fs.clone();
+        let work_directory = self.dot_git_path.parent().unwrap().to_path_buf();
+        let update = self.with_state_async(true, move |state| {
+            let mut checkout = Checkout::new();
+            let result = f(state, &mut checkout);
+            Ok((result, checkout))
+        });
+        async move {
+            let (result, checkout) = update.await?;
+            for (path, content) in checkout {
+                let abs_path = work_directory.join(path.as_std_path());
+                match content {
+                    Some(content) => fs.insert_file(&abs_path, content.into_bytes()).await,
+                    None => {
+                        fs.remove_file(
+                            &abs_path,
+                            RemoveOptions {
+                                ignore_if_not_exists: true,
+                                ..Default::default()
+                            },
+                        )
+                        .await?
+                    }
+                }
+            }
+            result
+        }
+        .boxed()
+    }
+
+    /// Commits the changes of `commit`, or their inverse when reverting, on top of HEAD. When
+    /// they conflict with HEAD, the conflicts are left to resolve like git does.
+    fn apply_commit(
+        &self,
+        commit: String,
+        revert: bool,
+    ) -> BoxFuture<'static, Result<ApplyCommitOutcome>> {
+        self.with_state_and_checkout(move |state, checkout| {
+            let ix = state.commit_ix(&commit)?;
+            let applied = state.commits[ix].clone();
+            let parent_tree = tree(&state.commits[..ix]);
+            let (base, changes, message, sequencer_head) = if revert {
+                let changes = applied
+                    .changes
+                    .keys()
+                    .map(|path| (path.clone(), parent_tree.get(path).cloned()))
+                    .collect();
+                let subject = applied.message.lines().next().unwrap_or_default();
+                let message = format!(
+                    "Revert \"{subject}\"\n\nThis reverts commit {}.",
+                    applied.sha
+                );
+                let base = tree(&state.commits[..=ix]);
+                (base, changes, message, "REVERT_HEAD")
+            } else {
+                let changes = applied.changes.clone();
+                let message = applied.message.clone();
+                (parent_tree, changes, message, "CHERRY_PICK_HEAD")
+            };
+
+            let conflicts = state.conflicting_paths(&base, &changes);
+            if !conflicts.is_empty() {
+                let label = applied.sha.display_short();
+                checkout.extend(state.mark_conflicts(&conflicts, &changes, &label));
+                state
+                    .refs
+                    .insert(sequencer_head.into(), applied.sha.to_string());
+                return Ok(ApplyCommitOutcome::Conflicted);
+            }
+            checkout.extend(state.push_commit(FakeCommit {
+                message,
+                changes,
+                ..applied
+            }));
+            Ok(ApplyCommitOutcome::Applied)
+        })
+    }
 }
 
 impl GitRepository for FakeGitRepository {
@@ -119,6 +610,16 @@ impl GitRepository for FakeGitRepository {
         self.executor.spawn(async move { fut.await.ok() }).boxed()
     }
 
+    fn load_merge_stages(&self, path: RepoPath) -> BoxFuture<'_, Result<MergeStages>> {
+        self.with_state_async(false, move |state| {
+            state
+                .merge_stages
+                .get(&path)
+                .cloned()
+                .with_context(|| format!("{} has no merge conflict", path.as_unix_str()))
+        })
+    }
+
     fn load_blob_content(&self, oid: git::Oid) -> BoxFuture<'_, Result<String>> {
         self.with_state_async(false, move |state| {
             state.oids.get(&oid).cloned().context("oid does not exist")
@@ -231,6 +732,191 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn rebase_commits(&self, base: String) -> BoxFuture<'_, Result<Vec<CommitDetails>>> {
+        self.with_state_async(false, move |state| {
+            let base_ix = state.commit_ix(&base)?;
+            Ok(state.commits[base_ix + 1..]
+                .iter()
+                .map(|commit| CommitDetails {
+                    sha: commit.sha.to_string().into(),
+                    message: commit.message.clone().into(),
+                    commit_timestamp: commit.timestamp,
+                    author_email: commit.author_email.clone().into(),
+                    author_name: commit.author_name.clone().into(),
+                })
+                .collect())
+        })
+    }
+
+    fn rebase_interactive(
+        &self,
+        base: String,
+        todo: Vec<RebaseTodoEntry>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_and_checkout(move |state, checkout| {
+            anyhow::ensure!(state.rebase.is_none(), "a rebase is already in progress");
+            anyhow::ensure!(
+                todo.iter()
+                    .find(|entry| entry.action != RebaseAction::Drop)
+                    .is_none_or(|entry| !entry.action.melds_into_previous()),
+                "The first commit cannot be squashed into a previous commit"
+            );
+            let base_ix = state.commit_ix(&base)?;
+            let original_commits = state.commits.clone();
+            state.commits.truncate(base_ix + 1);
+            checkout.extend(state.reset_to_history());
+            state.rebase = Some(FakeRebase {
+                original_commits,
+                head_name: state.current_branch_name.clone(),
+                step: 0,
+                total: todo.len(),
+                todo: todo.into(),
+                stopped_at: None,
+            });
+            state.continue_rebase(checkout)
+        })
+    }
+
+    fn rebase_operation(
+        &self,
+        operation: RebaseOperation,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_and_checkout(move |state, checkout| {
+            let rebase = state.rebase.as_mut().context("no rebase in progress")?;
+            match operation {
+                RebaseOperation::Continue => {
+                    anyhow::ensure!(
+                        state.unmerged_paths.is_empty(),
+                        "you must resolve all conflicts before continuing"
+                    );
+                    if let Some(entry) = rebase.stopped_at.take() {
+                        let mut commit = rebase
+                            .original_commits
+                            .iter()
+                            .find(|commit| commit.sha.to_string().starts_with(entry.sha.as_ref()))
+                            .cloned()
+                            .with_context(|| format!("unknown commit '{}'", entry.sha))?;
+                        for (path, content) in &mut commit.changes {
+                            *content = state.index_contents.get(path).cloned();
+                        }
+                        checkout.extend(state.replay(&entry, commit)?);
+                    }
+                    state.continue_rebase(checkout)
+                }
+                RebaseOperation::Skip => {
+                    rebase.stopped_at = None;
+                    state.unmerged_paths.clear();
+                    checkout.extend(state.reset_to_history());
+                    state.continue_rebase(checkout)
+                }
+                RebaseOperation::Abort => {
+                    let rebase = state.rebase.take().context("no rebase in progress")?;
+                    state.commits = rebase.original_commits;
+                    if let Some(branch) = rebase.head_name {
+                        state.current_branch_name = Some(branch);
+                    }
+                    state.unmerged_paths.clear();
+                    checkout.extend(state.reset_to_history());
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    fn rebase_progress(&self) -> BoxFuture<'_, Option<RebaseProgress>> {
+        let state = self.with_state_async(false, |state| {
+            Ok(state.rebase.as_ref().map(|rebase| RebaseProgress {
+                step: rebase.step,
+                total: rebase.total,
+                head_name: rebase.head_name.clone().map(Into::into),
+            }))
+        });
+        async move { state.await.ok().flatten() }.boxed()
+    }
+
+    fn cherry_pick(
+        &self,
+        commit: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<ApplyCommitOutcome>> {
+        self.apply_commit(commit, false)
+    }
+
+    fn revert(
+        &self,
+        commit: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<ApplyCommitOutcome>> {
+        self.apply_commit(commit, true)
+    }
+
+    fn bisect_start(
+        &self,
+        bad: Option<String>,
+        good: Vec<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_and_checkout(move |state, checkout| {
+            anyhow::ensure!(state.bisect.is_none(), "a bisect is already in progress");
+            let bad_ix = bad.map(|bad| state.commit_ix(&bad)).transpose()?;
+            let good_ixs = good
+                .iter()
+                .map(|good| state.commit_ix(good))
+                .collect::<Result<Vec<_>>>()?;
+            state.bisect = Some(FakeBisect {
+                commits: state.commits.clone(),
+                head_name: state.current_branch_name.take(),
+                state: BisectState::default(),
+            });
+            if let Some(bad_ix) = bad_ix {
+                state.mark_bisect(BisectTerm::Bad, bad_ix)?;
+            }
+            for good_ix in good_ixs {
+                state.mark_bisect(BisectTerm::Good, good_ix)?;
+            }
+            checkout.extend(state.bisect_next());
+            Ok(())
+        })
+    }
+
+    fn bisect_mark(
+        &self,
+        term: BisectTerm,
+        commit: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_and_checkout(move |state, checkout| {
+            let bisect = state.bisect.as_ref().context("not bisecting")?;
+            let ix = match commit {
+                Some(commit) => state.commit_ix_in(&bisect.commits, &commit)?,
+                None => state.commit_ix("HEAD")?,
+            };
+            state.mark_bisect(term, ix)?;
+            checkout.extend(state.bisect_next());
+            Ok(())
+        })
+    }
+
+    fn bisect_reset(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.with_state_and_checkout(move |state, checkout| {
+            if let Some(bisect) = state.bisect.take() {
+                state.commits = bisect.commits;
+                state.current_branch_name = bisect.head_name;
+                checkout.extend(state.reset_to_history());
+            }
+            Ok(())
+        })
+    }
+
+    fn bisect_state(&self) -> BoxFuture<'_, Option<BisectState>> {
+        let state = self.with_state_async(false, |state| {
+            Ok(state.bisect.as_ref().map(|bisect| bisect.state.clone()))
+        });
+        async move { state.await.ok().flatten() }.boxed()
+    }
+
     fn path(&self) -> PathBuf {
         self.repository_dir_path.clone()
     }
@@ -571,6 +1257,98 @@ impl GitRepository for FakeGitRepository {
         })
     }
 
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>> {
+        self.with_state_async(false, |state| {
+            Ok(state
+                .refs
+                .iter()
+                .filter_map(|(ref_name, sha)| {
+                    Some(Tag {
+                        name: ref_name.strip_prefix("refs/tags/")?.to_string().into(),
+                        sha: sha.clone().into(),
+                        annotation: None,
+                        timestamp: 0,
+                    })
+                })
+                .collect())
+        })
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        target: Option<String>,
+        _message: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let ref_name = format!("refs/tags/{name}");
+            if state.refs.contains_key(&ref_name) {
+                bail!("tag '{name}' already exists");
+            }
+            let sha = state
+                .refs
+                .get(target.as_deref().unwrap_or("HEAD"))
+                .cloned()
+                .or(target)
+                .context("no commit to tag")?;
+            state.refs.insert(ref_name, sha);
+            Ok(())
+        })
+    }
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.refs.remove(&format!("refs/tags/{name}")).is_none() {
+                bail!("tag '{name}' not found");
+            }
+            Ok(())
+        })
+    }
+
+    fn checkout_tag(&self, name: String) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let sha = state
+                .refs
+                .get(&format!("refs/tags/{name}"))
+                .cloned()
+                .with_context(|| format!("tag '{name}' not found"))?;
+            state.refs.insert("HEAD".into(), sha);
+            state.current_branch_name = None;
+            Ok(())
+        })
+    }
+
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        self.with_state_async(false, |state| Ok(state.submodules.clone()))
+    }
+
+    fn submodule_init(
+        &self,
+        paths: Vec<RepoPath>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| state.init_submodules(&paths))
+    }
+
+    fn submodule_sync(
+        &self,
+        paths: Vec<RepoPath>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            for ix in state.submodule_ixs(&paths)? {
+                let submodule = &state.submodules[ix];
+                if let Some(url) = &submodule.url
+                    && let Some(configured_url) = state.submodule_urls.get_mut(&submodule.path)
+                {
+                    *configured_url = url.to_string();
+                }
+            }
+            Ok(())
+        })
+    }
+
     fn blame(
         &self,
         path: RepoPath,
@@ -605,6 +1383,47 @@ impl GitRepository for FakeGitRepository {
         .boxed()
     }
 
+    fn log(
+        &self,
+        range: Option<String>,
+        path: Option<RepoPath>,
+        limit: usize,
+    ) -> BoxFuture<'_, Result<Vec<git::repository::FileHistoryEntry>>> {
+        self.with_state_async(false, move |state| {
+            let commits = match range.as_deref() {
+                Some(range) => state.commits_in_range(range)?,
+                None => &state.commits,
+            };
+            Ok(commits
+                .iter()
+                .rev()
+                .filter(|commit| {
+                    path.as_ref().is_none_or(|path| {
+                        commit
+                            .changes
+                            .keys()
+                            .any(|changed| changed.starts_with(path))
+                    })
+                })
+                .take(limit)
+                .map(|commit| git::repository::FileHistoryEntry {
+                    sha: commit.sha.to_string().into(),
+                    subject: commit
+                        .message
+                        .lines()
+                        .next()
+                        .unwrap_or_default()
+                        .to_string()
+                        .into(),
+                    message: commit.message.clone().into(),
+                    commit_timestamp: commit.timestamp,
+                    author_name: commit.author_name.clone().into(),
+                    author_email: commit.author_email.clone().into(),
+                })
+                .collect())
+        })
+    }
+
     fn stage_paths(
         &self,
         paths: Vec<RepoPath>,
@@ -717,6 +1536,46 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn push_tag(
+        &self,
+        _tag_name: String,
+        _remote: String,
+        _askpass: AskPassDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
+        unimplemented!()
+    }
+
+    fn submodule_update(
+        &self,
+        paths: Vec<RepoPath>,
+        _askpass: AskPassDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
+        self.with_state_async(true, move |state| {
+            state.init_submodules(&paths)?;
+            let mut stdout = String::new();
+            for ix in state.submodule_ixs(&paths)? {
+                let submodule = &mut state.submodules[ix];
+                if let Some(sha) = submodule.recorded_sha
+                    && submodule.checked_out_sha != Some(sha)
+                {
+                    submodule.checked_out_sha = Some(sha);
+                    stdout.push_str(&format!(
+                        "Submodule path '{}': checked out '{sha}'\n",
+                        submodule.path.as_unix_str()
+                    ));
+                }
+            }
+            Ok(git::repository::RemoteCommandOutput {
+                stdout,
+                stderr: String::new(),
+            })
+        })
+    }
+
     fn pull(
         &self,
         _branch: Option<String>,
@@ -764,8 +1623,61 @@ impl GitRepository for FakeGitRepository {
         future::ready(Ok(Vec::new())).boxed()
     }
 
-    fn diff(&self, _diff: git::repository::DiffType) -> BoxFuture<'_, Result<String>> {
-        unimplemented!()
+    fn diff(&self, diff: git::repository::DiffType) -> BoxFuture<'_, Result<String>> {
+        let git::repository::DiffType::Refs {
+            base_ref,
+            head_ref,
+            path,
+        } = diff
+        else {
+            unimplemented!()
+        };
+        async move {
+            let (base, head, tracked_paths) = self
+                .with_state_async(false, move |state| {
+                    let base = state.tree_at(&base_ref)?;
+                    let head = head_ref
+                        .map(|head_ref| state.tree_at(&head_ref))
+                        .transpose()?;
+                    let tracked_paths = state
+                        .index_contents
+                        .keys()
+                        .chain(base.keys())
+                        .cloned()
+                        .collect::<HashSet<_>>();
+                    Ok((base, head, tracked_paths))
+                })
+                .await?;
+            let head = match head {
+                Some(head) => head,
+                None => {
+                    let work_directory = self.dot_git_path.parent().unwrap();
+                    let mut worktree = HashMap::default();
+                    for path in tracked_paths {
+                        let abs_path = work_directory.join(path.as_std_path());
+                        if let Ok(content) = self.fs.load(&abs_path).await {
+                            worktree.insert(path, content);
+                        }
+                    }
+                    worktree
+                }
+            };
+
+            let mut paths = base
+                .keys()
+                .chain(head.keys())
+                .filter(|changed| path.as_ref().is_none_or(|path| changed.starts_with(path)))
+                .filter(|changed| base.get(*changed) != head.get(*changed))
+                .cloned()
+                .collect::<Vec<_>>();
+            paths.sort();
+            paths.dedup();
+            Ok(paths
+                .iter()
+                .map(|path| file_diff(path, base.get(path), head.get(path)))
+                .collect())
+        }
+        .boxed()
     }
 
     fn checkpoint(&self) -> BoxFuture<'static, Result<GitRepositoryCheckpoint>> {
@@ -887,6 +1799,7 @@ impl GitRepository for FakeGitRepository {
 mod tests {
     use super::*;
     use crate::{FakeFs, Fs};
+    use git::repository::repo_path;
     use gpui::TestAppContext;
     use serde_json::json;
     use std::path::Path;
@@ -1023,4 +1936,157 @@ mod tests {
             );
         }
     }
+
+    #[gpui::test]
+    async fn test_fake_interactive_rebase(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/project",
+            json!({".git": {}, "a.txt": "a2\n", "b.txt": "b\n"}),
+        )
+        .await;
+        let dot_git = Path::new("/project/.git");
+        let shas = fs.set_commits_for_repo(
+            dot_git,
+            &[
+                ("initial", &[("a.txt", Some("a0\n"))]),
+                ("edit a", &[("a.txt", Some("a1\n"))]),
+                ("add b", &[("b.txt", Some("b\n"))]),
+                ("edit a again", &[("a.txt", Some("a2\n"))]),
+            ],
+        );
+        let repo = fs.open_repo(dot_git, None).unwrap();
+        let base = shas[0].to_string();
+        let entry = |ix: usize, action| RebaseTodoEntry {
+            sha: shas[ix].to_string().into(),
+            action,
+            message: None,
+        };
+        let messages = async || {
+            repo.rebase_commits(base.clone())
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|commit| commit.message.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(messages().await, ["edit a", "add b", "edit a again"]);
+
+        // Moving the second edit of a.txt before the first one conflicts.
+        let result = repo
+            .rebase_interactive(
+                base.clone(),
+                vec![entry(3, RebaseAction::Pick), entry(1, RebaseAction::Pick)],
+                Arc::default(),
+            )
+            .await;
+        assert!(result.is_err());
+        assert_eq!(
+            repo.rebase_progress().await,
+            Some(RebaseProgress {
+                step: 1,
+                total: 2,
+                head_name: None,
+            })
+        );
+        assert_eq!(
+            fs.load(Path::new("/project/a.txt")).await.unwrap(),
+            format!(
+                "<<<<<<< HEAD\na0\n=======\na2\n>>>>>>> {}\n",
+                shas[3].display_short()
+            )
+        );
+        assert!(!fs.is_file(Path::new("/project/b.txt")).await);
+
+        repo.rebase_operation(RebaseOperation::Abort, Arc::default())
+            .await
+            .unwrap();
+        assert_eq!(repo.rebase_progress().await, None);
+        assert_eq!(messages().await, ["edit a", "add b", "edit a again"]);
+        assert_eq!(fs.load(Path::new("/project/a.txt")).await.unwrap(), "a2\n");
+        assert!(fs.is_file(Path::new("/project/b.txt")).await);
+
+        // Continuing requires the conflicts to be resolved first.
+        repo.rebase_interactive(
+            base.clone(),
+            vec![
+                entry(3, RebaseAction::Reword),
+                entry(1, RebaseAction::Pick),
+                entry(2, RebaseAction::Drop),
+            ],
+            Arc::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            repo.rebase_operation(RebaseOperation::Continue, Arc::default())
+                .await
+                .is_err()
+        );
+        fs.with_git_state(dot_git, true, |state| {
+            state.unmerged_paths.clear();
+            state
+                .index_contents
+                .insert(repo_path("a.txt"), "resolved\n".into());
+        })
+        .unwrap();
+        // Applying the first edit on top of the resolution conflicts again, so skip it.
+        repo.rebase_operation(RebaseOperation::Continue, Arc::default())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            repo.rebase_progress().await.map(|progress| progress.step),
+            Some(2)
+        );
+        repo.rebase_operation(RebaseOperation::Skip, Arc::default())
+            .await
+            .unwrap();
+        assert_eq!(repo.rebase_progress().await, None);
+        assert_eq!(messages().await, ["edit a again"]);
+        assert_eq!(
+            repo.load_committed_text(repo_path("a.txt"))
+                .await
+                .as_deref(),
+            Some("resolved\n")
+        );
+        assert_eq!(repo.load_committed_text(repo_path("b.txt")).await, None);
+
+        // Fixups meld into the commit before them.
+        let shas = fs.set_commits_for_repo(
+            dot_git,
+            &[
+                ("initial", &[("a.txt", Some("a0\n"))]),
+                ("edit a", &[("a.txt", Some("a1\n"))]),
+                ("add b", &[("b.txt", Some("b\n"))]),
+            ],
+        );
+        repo.rebase_interactive(
+            shas[0].to_string(),
+            vec![
+                RebaseTodoEntry {
+                    sha: shas[1].to_string().into(),
+                    action: RebaseAction::Reword,
+                    message: Some("edit a and add b".into()),
+                },
+                RebaseTodoEntry {
+                    sha: shas[2].to_string().into(),
+                    action: RebaseAction::Fixup,
+                    message: None,
+                },
+            ],
+            Arc::default(),
+        )
+        .await
+        .unwrap();
+        let commits = repo.rebase_commits(shas[0].to_string()).await.unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].message.as_ref(), "edit a and add b");
+        assert_eq!(
+            repo.load_committed_text(repo_path("b.txt"))
+                .await
+                .as_deref(),
+            Some("b\n")
+        );
+    }
 }
@@ -50,10 +50,11 @@ mod fake_git_repo;
 #[cfg(feature = "test-support")]
 use collections::{BTreeMap, btree_map};
 #[cfg(feature = "test-support")]
-use fake_git_repo::FakeGitRepositoryState;
+use fake_git_repo::{FakeCommit, FakeGitRepositoryState};
 #[cfg(feature = "test-support")]
 use git::{
-    repository::{InitialGraphCommitData, RepoPath, repo_path},
+    Oid,
+    repository::{InitialGraphCommitData, MergeStages, RepoPath, Submodule, repo_path},
     status::{FileStatus, StatusCode, TrackedStatus, UnmergedStatus},
 };
 
@@ -1950,8 +1951,6 @@ impl FakeFs {
         contents_by_path: &[(&str, String)],
     ) {
         self.with_git_state(dot_git, true, |state| {
-            use git::Oid;
-
             state.merge_base_contents.clear();
             let oids = (1..)
                 .map(|n| n.to_string())
@@ -1972,6 +1971,52 @@ impl FakeFs {
         .unwrap();
     }
 
+    pub fn set_submodules_for_repo(&self, dot_git: &Path, submodules: Vec<Submodule>) {
+        self.with_git_state(dot_git, true, |state| {
+            state.submodules = submodules;
+        })
+        .unwrap();
+    }
+
+    pub fn set_merge_stages_for_repo(&self, dot_git: &Path, stages: &[(&str, MergeStages)]) {
+        self.with_git_state(dot_git, true, |state| {
+            state.merge_stages = stages
+                .iter()
+                .map(|(path, stages)| (repo_path(path), stages.clone()))
+                .collect();
+        })
+        .unwrap();
+    }
+
+    /// Replaces the history of the repository with commits making the given changes, oldest
+    /// first, and makes HEAD and the index match the last one. Returns the commits' shas.
+    pub fn set_commits_for_repo(
+        &self,
+        dot_git: &Path,
+        commits: &[(&str, &[(&str, Option<&str>)])],
+    ) -> Vec<Oid> {
+        self.with_git_state(dot_git, true, |state| {
+            state.commits.clear();
+            state.head_contents.clear();
+            state.index_contents.clear();
+            for (ix, (message, changes)) in commits.iter().enumerate() {
+                state.push_commit(FakeCommit {
+                    sha: Oid::default(),
+                    message: message.to_string(),
+                    author_name: "Test Author".into(),
+                    author_email: "test@example.com".into(),
+                    timestamp: ix as i64,
+                    changes: changes
+                        .iter()
+                        .map(|(path, content)| (repo_path(path), content.map(ToString::to_string)))
+                        .collect(),
+                });
+            }
+            state.commits.iter().map(|commit| commit.sha).collect()
+        })
+        .unwrap()
+    }
+
     pub fn set_graph_commits(&self, dot_git: &Path, commits: Vec<Arc<InitialGraphCommitData>>) {
         self.with_git_state(dot_git, true, |state| {
             state.graph_commits = commits;
@@ -37,6 +37,10 @@ actions!(
         StageAndNext,
         /// Unstages the current hunk and moves to the next one.
         UnstageAndNext,
+        /// Stages only the selected lines of the hunks they belong to.
+        StageSelectedLines,
+        /// Unstages only the selected lines of the hunks they belong to.
+        UnstageSelectedLines,
         /// Restores the selected hunks to their original state.
         #[action(deprecated_aliases = ["editor::RevertSelectedHunks"])]
         Restore,
@@ -81,6 +85,26 @@ actions!(
         Fetch,
         /// Fetches changes from a specific remote.
         FetchFrom,
+        /// Continues the rebase in progress after resolving conflicts.
+        RebaseContinue,
+        /// Skips the commit the rebase in progress stopped at.
+        RebaseSkip,
+        /// Aborts the rebase in progress, restoring the branch to its original state.
+        RebaseAbort,
+        /// Marks the commit being tested in the bisect in progress as good.
+        BisectGood,
+        /// Marks the commit being tested in the bisect in progress as bad.
+        BisectBad,
+        /// Skips the commit being tested in the bisect in progress.
+        BisectSkip,
+        /// Ends the bisect in progress, returning to the commit it was started from.
+        BisectReset,
+        /// Registers the repository's submodules so they can be cloned.
+        SubmoduleInit,
+        /// Checks out the recorded commit of each submodule, cloning any that are missing.
+        SubmoduleUpdate,
+        /// Updates the URLs of the repository's submodules from `.gitmodules`.
+        SubmoduleSync,
         /// Creates a new commit with staged changes.
         Commit,
         /// Amends the last commit with staged changes.
@@ -97,6 +121,8 @@ actions!(
         Init,
         /// Opens all modified files in the editor.
         OpenModifiedFiles,
+        /// Opens the conflicted file in a three-way merge editor.
+        OpenMergeEditor,
         /// Clones a repository.
         Clone,
         /// Adds a file to .gitignore.
@@ -116,6 +142,59 @@ pub struct RenameBranch {
     pub branch: Option<String>,
 }
 
+/// Creates a git tag.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema, Action)]
+#[action(namespace = git)]
+#[serde(deny_unknown_fields)]
+pub struct CreateTag {
+    /// The commit to tag.
+    ///
+    /// Default: the current HEAD.
+    #[serde(default)]
+    pub target: Option<String>,
+}
+
+/// Opens an editor to reorder, squash, reword or drop the commits of the current branch.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema, Action)]
+#[action(namespace = git)]
+#[serde(deny_unknown_fields)]
+pub struct InteractiveRebase {
+    /// The commit or branch to rebase onto.
+    ///
+    /// Default: the upstream of the current branch, or the default branch.
+    #[serde(default)]
+    pub base: Option<String>,
+}
+
+/// Starts bisecting to find the commit that introduced a change.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema, Action)]
+#[action(namespace = git)]
+#[serde(deny_unknown_fields)]
+pub struct BisectStart {
+    /// A commit that has the change.
+    ///
+    /// Default: none, the commit can be marked once the bisect has started.
+    #[serde(default)]
+    pub bad: Option<String>,
+    /// Commits that don't have the change yet.
+    #[serde(default)]
+    pub good: Vec<String>,
+}
+
+/// Tests each commit of the bisect in progress with a command, marking it by the exit status.
+///
+/// Exit status 0 marks the commit good, 125 skips it, and any other status below 128 marks it bad.
+#[derive(Clone, Debug, PartialEq, Deserialize, JsonSchema, Action)]
+#[action(namespace = git)]
+#[serde(deny_unknown_fields)]
+pub struct BisectRun {
+    /// The command to run in the repository's working directory.
+    pub command: String,
+    /// Arguments to the command.
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
 /// Restores a file to its last committed state, discarding local changes.
 #[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema, Action)]
 #[action(namespace = git, deprecated_aliases = ["editor::RevertFile"])]
@@ -29,6 +29,7 @@ use std::{
     future,
     path::{Path, PathBuf},
     sync::Arc,
+    time::SystemTime,
 };
 use sum_tree::MapSeekTarget;
 use thiserror::Error;
@@ -279,6 +280,84 @@ impl Branch {
     }
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Tag {
+    pub name: SharedString,
+    /// The commit the tag points at, with annotated tags peeled to their target.
+    pub sha: SharedString,
+    /// The subject of the tag message, or `None` for lightweight tags.
+    pub annotation: Option<SharedString>,
+    /// The tagger date of annotated tags, or the commit date of lightweight ones.
+    pub timestamp: i64,
+}
+
+impl Tag {
+    pub fn ref_name(&self) -> String {
+        format!("refs/tags/{}", self.name)
+    }
+
+    pub fn is_annotated(&self) -> bool {
+        self.annotation.is_some()
+    }
+}
+
+/// The versions of a conflicted file recorded in the index while a merge is in progress.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MergeStages {
+    /// The version of the merge base, or `None` if the file didn't exist there.
+    pub base: Option<String>,
+    pub ours: Option<String>,
+    pub theirs: Option<String>,
+}
+
+/// A submodule registered in the repository's `.gitmodules`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Submodule {
+    pub name: SharedString,
+    pub path: RepoPath,
+    pub url: Option<SharedString>,
+    /// The commit the repository's index records for the submodule.
+    pub recorded_sha: Option<Oid>,
+    /// The commit checked out in the submodule, or `None` when it isn't initialized.
+    pub checked_out_sha: Option<Oid>,
+    /// Whether the submodule's working tree has modified or untracked files.
+    pub has_changes: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmoduleStatus {
+    Uninitialized,
+    /// The submodule has a different commit checked out than the one recorded for it.
+    NewCommits,
+    Modified,
+    UpToDate,
+}
+
+impl Submodule {
+    pub fn status(&self) -> SubmoduleStatus {
+        if self.checked_out_sha.is_none() {
+            SubmoduleStatus::Uninitialized
+        } else if self.checked_out_sha != self.recorded_sha {
+            SubmoduleStatus::NewCommits
+        } else if self.has_changes {
+            SubmoduleStatus::Modified
+        } else {
+            SubmoduleStatus::UpToDate
+        }
+    }
+}
+
+impl SubmoduleStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SubmoduleStatus::Uninitialized => "Uninitialized",
+            SubmoduleStatus::NewCommits => "New commits",
+            SubmoduleStatus::Modified => "Modified",
+            SubmoduleStatus::UpToDate => "Up to date",
+        }
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct Worktree {
     pub path: PathBuf,
@@ -486,6 +565,119 @@ pub enum ResetMode {
     Mixed,
 }
 
+/// What to do with a commit when replaying it during an interactive rebase.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum RebaseAction {
+    #[default]
+    Pick,
+    /// Use the commit, but replace its message.
+    Reword,
+    /// Meld the commit into the previous one, keeping both messages.
+    Squash,
+    /// Meld the commit into the previous one, discarding its message.
+    Fixup,
+    Drop,
+}
+
+impl RebaseAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RebaseAction::Pick => "pick",
+            RebaseAction::Reword => "reword",
+            RebaseAction::Squash => "squash",
+            RebaseAction::Fixup => "fixup",
+            RebaseAction::Drop => "drop",
+        }
+    }
+
+    /// Whether the action folds the commit into the one preceding it in the todo list.
+    pub fn melds_into_previous(&self) -> bool {
+        matches!(self, RebaseAction::Squash | RebaseAction::Fixup)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct RebaseTodoEntry {
+    pub sha: SharedString,
+    pub action: RebaseAction,
+    /// The new message of a [`RebaseAction::Reword`] entry.
+    pub message: Option<SharedString>,
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum RebaseOperation {
+    Continue,
+    Skip,
+    Abort,
+}
+
+impl RebaseOperation {
+    fn as_arg(&self) -> &'static str {
+        match self {
+            RebaseOperation::Continue => "--continue",
+            RebaseOperation::Skip => "--skip",
+            RebaseOperation::Abort => "--abort",
+        }
+    }
+}
+
+/// The state of a rebase that has stopped, either to resolve conflicts or between steps.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct RebaseProgress {
+    /// The one-based index of the todo step being applied.
+    pub step: usize,
+    pub total: usize,
+    /// The branch being rebased, or `None` when the rebase started from a detached HEAD.
+    pub head_name: Option<SharedString>,
+}
+
+/// The result of applying a commit onto the current branch with cherry-pick or revert.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum ApplyCommitOutcome {
+    Applied,
+    /// The operation stopped on conflicts, which have to be resolved and committed.
+    Conflicted,
+}
+
+/// How a commit is marked while bisecting.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum BisectTerm {
+    Good,
+    Bad,
+    /// The commit can't be tested, so git picks a nearby one instead.
+    Skip,
+}
+
+impl BisectTerm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BisectTerm::Good => "good",
+            BisectTerm::Bad => "bad",
+            BisectTerm::Skip => "skip",
+        }
+    }
+}
+
+/// The state of the bisect in progress.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct BisectState {
+    /// The commit marked bad, which is the newest commit that may be the first bad one.
+    pub bad: Option<Oid>,
+    pub good: Vec<Oid>,
+    pub skipped: Vec<Oid>,
+    /// The number of untested commits between the good ones and `bad`, skipped ones excluded.
+    pub remaining: usize,
+    /// The commit that introduced the change, once git has narrowed the range down to it.
+    pub first_bad: Option<Oid>,
+}
+
+impl BisectState {
+    /// The number of commits that still have to be tested in the worst case.
+    pub fn steps_left(&self) -> u32 {
+        (self.remaining + 1).next_power_of_two().trailing_zeros()
+    }
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub enum FetchOptions {
     All,
@@ -680,6 +872,10 @@ pub trait GitRepository: Send + Sync {
     ///
     /// Also returns `None` for symlinks.
     fn load_committed_text(&self, path: RepoPath) -> BoxFuture<'_, Option<String>>;
+
+    /// Returns the base, ours and theirs versions of a conflicted file from the index.
+    fn load_merge_stages(&self, path: RepoPath) -> BoxFuture<'_, Result<MergeStages>>;
+
     fn load_blob_content(&self, oid: Oid) -> BoxFuture<'_, Result<String>>;
 
     fn set_index_text(
@@ -724,6 +920,44 @@ pub trait GitRepository: Send + Sync {
 
     fn delete_branch(&self, name: String) -> BoxFuture<'_, Result<()>>;
 
+    /// Lists the lightweight and annotated tags in the repository, newest first.
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>>;
+
+    /// Creates a tag pointing at `target`, or at `HEAD` when no target is given.
+    ///
+    /// The tag is annotated when a message is given and lightweight otherwise.
+    fn create_tag(
+        &self,
+        name: String,
+        target: Option<String>,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>>;
+
+    /// Checks out the commit the tag points at, detaching `HEAD`.
+    fn checkout_tag(&self, name: String) -> BoxFuture<'_, Result<()>>;
+
+    /// Lists the submodules registered in `.gitmodules`, in the order they appear there.
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>>;
+
+    /// Copies the URLs of the submodules at `paths`, or of all of them when empty, from
+    /// `.gitmodules` into the repository's config.
+    fn submodule_init(
+        &self,
+        paths: Vec<RepoPath>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Updates the URLs the submodules at `paths`, or all of them when empty, are fetched
+    /// from to match `.gitmodules`.
+    fn submodule_sync(
+        &self,
+        paths: Vec<RepoPath>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
     fn worktrees(&self) -> BoxFuture<'_, Result<Vec<Worktree>>>;
 
     fn create_worktree(
@@ -751,6 +985,63 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>>;
 
+    /// Lists the commits that an interactive rebase onto `base` would replay, oldest first.
+    fn rebase_commits(&self, base: String) -> BoxFuture<'_, Result<Vec<CommitDetails>>>;
+
+    /// Rebases the current branch onto `base`, replaying the commits as described by `todo`.
+    fn rebase_interactive(
+        &self,
+        base: String,
+        todo: Vec<RebaseTodoEntry>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Continues, skips the current step of, or aborts the rebase in progress.
+    fn rebase_operation(
+        &self,
+        operation: RebaseOperation,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the progress of the rebase in progress, if any.
+    fn rebase_progress(&self) -> BoxFuture<'_, Option<RebaseProgress>>;
+
+    /// Applies the changes introduced by `commit` onto the current branch as a new commit.
+    fn cherry_pick(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<ApplyCommitOutcome>>;
+
+    /// Creates a new commit on the current branch undoing the changes introduced by `commit`.
+    fn revert(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<ApplyCommitOutcome>>;
+
+    /// Starts bisecting, optionally marking the first bad and good commits right away.
+    fn bisect_start(
+        &self,
+        bad: Option<String>,
+        good: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Marks `commit`, or `HEAD` when none is given, and checks out the next commit to test.
+    fn bisect_mark(
+        &self,
+        term: BisectTerm,
+        commit: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Ends the bisect in progress, checking out the commit it was started from.
+    fn bisect_reset(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the state of the bisect in progress, if any.
+    fn bisect_state(&self) -> BoxFuture<'_, Option<BisectState>>;
+
     fn show(&self, commit: String) -> BoxFuture<'_, Result<CommitDetails>>;
 
     fn load_commit(&self, commit: String, cx: AsyncApp) -> BoxFuture<'_, Result<CommitDiff>>;
@@ -768,6 +1059,15 @@ pub trait GitRepository: Send + Sync {
         limit: Option<usize>,
     ) -> BoxFuture<'_, Result<FileHistory>>;
 
+    /// Lists up to `limit` commits of `range`, or of HEAD when it's `None`, newest first.
+    /// When a `path` is given, only the commits that touched it are listed.
+    fn log(
+        &self,
+        range: Option<String>,
+        path: Option<RepoPath>,
+        limit: usize,
+    ) -> BoxFuture<'_, Result<Vec<FileHistoryEntry>>>;
+
     /// Returns the absolute path to the repository. For worktrees, this will be the path to the
     /// worktree's gitdir within the main repository (typically `.git/worktrees/<name>`).
     fn path(&self) -> PathBuf;
@@ -843,6 +1143,29 @@ pub trait GitRepository: Send + Sync {
         cx: AsyncApp,
     ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
 
+    fn push_tag(
+        &self,
+        tag_name: String,
+        remote_name: String,
+        askpass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        // This method takes an AsyncApp to ensure it's invoked on the main thread,
+        // otherwise git-credentials-manager won't work.
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
+
+    /// Checks out the recorded commits in the submodules at `paths`, or in all of them when
+    /// empty, initializing and cloning them first when needed.
+    fn submodule_update(
+        &self,
+        paths: Vec<RepoPath>,
+        askpass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        // This method takes an AsyncApp to ensure it's invoked on the main thread,
+        // otherwise git-credentials-manager won't work.
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
+
     fn pull(
         &self,
         branch_name: Option<String>,
@@ -921,7 +1244,16 @@ pub trait GitRepository: Send + Sync {
 pub enum DiffType {
     HeadToIndex,
     HeadToWorktree,
-    MergeBase { base_ref: SharedString },
+    MergeBase {
+        base_ref: SharedString,
+    },
+    /// Compares `base_ref` to `head_ref`, or to the working tree when `head_ref` is `None`,
+    /// optionally only for the files under `path`.
+    Refs {
+        base_ref: SharedString,
+        head_ref: Option<SharedString>,
+        path: Option<RepoPath>,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
@@ -941,9 +1273,17 @@ pub struct RealGitRepository {
     pub system_git_binary_path: Option<PathBuf>,
     pub any_git_binary_path: PathBuf,
     any_git_binary_help_output: Arc<Mutex<Option<SharedString>>>,
+    submodules: Arc<Mutex<Option<CachedSubmodules>>>,
     executor: BackgroundExecutor,
 }
 
+/// The submodules last listed by [`RealGitRepository::submodules`], which are only listed again
+/// once one of the files returned by `submodule_sources` has been modified.
+struct CachedSubmodules {
+    mtimes: Vec<Option<SystemTime>>,
+    submodules: Vec<Submodule>,
+}
+
 impl RealGitRepository {
     pub fn new(
         dotgit_path: &Path,
@@ -960,6 +1300,7 @@ impl RealGitRepository {
             any_git_binary_path,
             executor,
             any_git_binary_help_output: Arc::new(Mutex::new(None)),
+            submodules: Arc::new(Mutex::new(None)),
         })
     }
 
@@ -991,6 +1332,113 @@ impl RealGitRepository {
         *self.any_git_binary_help_output.lock() = Some(output.clone());
         output
     }
+
+    /// Runs `git cherry-pick` or `git revert` for a single commit, treating a stop on
+    /// conflicts, recognized by the unmerged paths it leaves in the index, as a successful outcome.
+    fn apply_commit(
+        &self,
+        command: &'static str,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<ApplyCommitOutcome>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                ensure_revision_arg(&commit)?;
+                let working_directory = working_directory?;
+                let output = new_command(&git_binary_path)
+                    .current_dir(&working_directory)
+                    .envs(env.iter())
+                    .args([command, "--no-edit", &commit])
+                    .output()
+                    .await?;
+                if output.status.success() {
+                    return Ok(ApplyCommitOutcome::Applied);
+                }
+
+                let unmerged_paths = new_command(&git_binary_path)
+                    .current_dir(&working_directory)
+                    .args([
+                        "--no-optional-locks",
+                        "diff",
+                        "--name-only",
+                        "--diff-filter=U",
+                    ])
+                    .output()
+                    .await?;
+                if unmerged_paths.status.success() && !unmerged_paths.stdout.is_empty() {
+                    Ok(ApplyCommitOutcome::Conflicted)
+                } else {
+                    anyhow::bail!(
+                        "Failed to run git {command}:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    );
+                }
+            })
+            .boxed()
+    }
+
+    fn run_submodule_command(
+        &self,
+        command: &'static str,
+        paths: Vec<RepoPath>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let submodules = self.submodules.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .args(["submodule", command, "--"])
+                    .args(paths.iter().map(|path| path.as_unix_str()))
+                    .output()
+                    .await?;
+                submodules.lock().take();
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to run git submodule {command}:\n{}",
+                    String::from_utf8_lossy(&output.stderr),
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn run_bisect_command(
+        &self,
+        args: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .arg("bisect")
+                    .args(&args)
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to run git bisect {}:\n{}",
+                    args.first().map_or("", String::as_str),
+                    String::from_utf8_lossy(&output.stderr),
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+}
+
+/// Quotes a path for the POSIX shell that git runs editors and `exec` todo steps with.
+fn shell_quote_path(path: &Path) -> String {
+    format!("'{}'", path.to_string_lossy().replace('\'', "'\\''"))
 }
 
 #[derive(Clone, Debug)]
@@ -1063,6 +1511,7 @@ impl GitRepository for RealGitRepository {
         self.executor
             .spawn(async move {
                 let working_directory = working_directory?;
+                ensure_revision_arg(&commit)?;
                 let output = new_command(git_binary_path)
                     .current_dir(&working_directory)
                     .args([
@@ -1102,6 +1551,7 @@ impl GitRepository for RealGitRepository {
         };
         let git_binary_path = self.any_git_binary_path.clone();
         cx.background_spawn(async move {
+            ensure_revision_arg(&commit)?;
             let show_output = util::command::new_command(&git_binary_path)
                 .current_dir(&working_directory)
                 .args([
@@ -1288,33 +1738,359 @@ impl GitRepository for RealGitRepository {
         .boxed()
     }
 
-    fn load_index_text(&self, path: RepoPath) -> BoxFuture<'_, Option<String>> {
-        // https://git-scm.com/book/en/v2/Git-Internals-Git-Objects
-        const GIT_MODE_SYMLINK: u32 = 0o120000;
-
-        let repo = self.repository.clone();
+    fn rebase_commits(&self, base: String) -> BoxFuture<'_, Result<Vec<CommitDetails>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
         self.executor
             .spawn(async move {
-                fn logic(repo: &git2::Repository, path: &RepoPath) -> Result<Option<String>> {
-                    // This check is required because index.get_path() unwraps internally :(
-                    let mut index = repo.index()?;
-                    index.read(false)?;
+                ensure_revision_arg(&base)?;
+                let output = new_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args([
+                        "--no-optional-locks",
+                        "log",
+                        "--no-merges",
+                        "--topo-order",
+                        "--reverse",
+                        "--format=%H%x00%B%x00%ct%x00%ae%x00%an%x1e",
+                        &format!("{base}..HEAD"),
+                        "--",
+                    ])
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to list commits to rebase:\n{}",
+                    String::from_utf8_lossy(&output.stderr),
+                );
 
-                    const STAGE_NORMAL: i32 = 0;
-                    let path = path.as_std_path();
-                    // `RepoPath` contains a `RelPath` which normalizes `.` into an empty path
-                    // `get_path` unwraps on empty paths though, so undo that normalization here
-                    let path = if path.components().next().is_none() {
-                        ".".as_ref()
-                    } else {
-                        path
-                    };
-                    let oid = match index.get_path(path, STAGE_NORMAL) {
-                        Some(entry) if entry.mode != GIT_MODE_SYMLINK => entry.id,
-                        _ => return Ok(None),
-                    };
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                Ok(stdout
+                    .split('\x1e')
+                    .filter_map(|record| {
+                        let mut fields = record.trim_start_matches('\n').split('\0');
+                        let sha = fields.next().filter(|sha| !sha.is_empty())?;
+                        let message = fields.next()?;
+                        let commit_timestamp = fields.next()?.parse().ok()?;
+                        let author_email = fields.next()?;
+                        let author_name = fields.next()?;
+                        Some(CommitDetails {
+                            sha: sha.to_string().into(),
+                            message: message.trim_end().to_string().into(),
+                            commit_timestamp,
+                            author_email: author_email.to_string().into(),
+                            author_name: author_name.to_string().into(),
+                        })
+                    })
+                    .collect())
+            })
+            .boxed()
+    }
 
-                    let content = repo.find_blob(oid)?.content().to_owned();
+    fn rebase_interactive(
+        &self,
+        base: String,
+        todo: Vec<RebaseTodoEntry>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        // Reword messages are applied by `exec` steps that may only run after conflicts have
+        // been resolved, so they live in the git directory rather than in a temporary one.
+        let todo_dir = self.path().join("zed-rebase");
+        self.executor
+            .spawn(async move {
+                ensure_revision_arg(&base)?;
+                anyhow::ensure!(
+                    todo.iter()
+                        .find(|entry| entry.action != RebaseAction::Drop)
+                        .is_none_or(|entry| !entry.action.melds_into_previous()),
+                    "The first commit cannot be squashed into a previous commit"
+                );
+
+                smol::fs::remove_dir_all(&todo_dir).await.ok();
+                smol::fs::create_dir_all(&todo_dir).await?;
+
+                let mut todo_list = String::new();
+                for (ix, entry) in todo.iter().enumerate() {
+                    match (entry.action, &entry.message) {
+                        (RebaseAction::Reword, Some(message)) => {
+                            let message_path = todo_dir.join(format!("message-{ix}"));
+                            smol::fs::write(&message_path, message.as_bytes()).await?;
+                            todo_list.push_str(&format!("pick {}\n", entry.sha));
+                            todo_list.push_str(&format!(
+                                "exec {} commit --amend --allow-empty --no-verify --file {}\n",
+                                shell_quote_path(&git_binary_path),
+                                shell_quote_path(&message_path),
+                            ));
+                        }
+                        (action, _) => {
+                            todo_list.push_str(&format!("{} {}\n", action.as_str(), entry.sha));
+                        }
+                    }
+                }
+                let todo_path = todo_dir.join("git-rebase-todo");
+                smol::fs::write(&todo_path, todo_list).await?;
+
+                let output = new_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .env(
+                        "GIT_SEQUENCE_EDITOR",
+                        format!("cp {}", shell_quote_path(&todo_path)),
+                    )
+                    // Accept the combined message of squashed commits as is.
+                    .env("GIT_EDITOR", "true")
+                    .args(["rebase", "--interactive", &base])
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to rebase:\n{}",
+                    String::from_utf8_lossy(&output.stderr),
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn rebase_operation(
+        &self,
+        operation: RebaseOperation,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .env("GIT_EDITOR", "true")
+                    .args(["rebase", operation.as_arg()])
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to run git rebase {}:\n{}",
+                    operation.as_arg(),
+                    String::from_utf8_lossy(&output.stderr),
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn rebase_progress(&self) -> BoxFuture<'_, Option<RebaseProgress>> {
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move {
+                let read = |path: PathBuf| {
+                    std::fs::read_to_string(path)
+                        .ok()
+                        .map(|contents| contents.trim().to_string())
+                };
+                let merge_dir = git_dir.join("rebase-merge");
+                let apply_dir = git_dir.join("rebase-apply");
+                let (state_dir, step, total) = if merge_dir.is_dir() {
+                    (merge_dir, "msgnum", "end")
+                } else if apply_dir.join("rebasing").exists() {
+                    (apply_dir, "next", "last")
+                } else {
+                    return None;
+                };
+
+                let step = read(state_dir.join(step))?.parse().ok()?;
+                let total = read(state_dir.join(total))?.parse().ok()?;
+                let head_name = read(state_dir.join("head-name"))
+                    .filter(|head_name| head_name != "detached HEAD")
+                    .map(|head_name| {
+                        head_name
+                            .strip_prefix("refs/heads/")
+                            .unwrap_or(&head_name)
+                            .to_string()
+                            .into()
+                    });
+                Some(RebaseProgress {
+                    step,
+                    total,
+                    head_name,
+                })
+            })
+            .boxed()
+    }
+
+    fn cherry_pick(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<ApplyCommitOutcome>> {
+        self.apply_commit("cherry-pick", commit, env)
+    }
+
+    fn revert(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<ApplyCommitOutcome>> {
+        self.apply_commit("revert", commit, env)
+    }
+
+    fn bisect_start(
+        &self,
+        bad: Option<String>,
+        good: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        // `git bisect start` only accepts good commits after a bad one, so without a bad
+        // commit they are marked one by one once the bisect has started.
+        let mut args = vec!["start".to_string()];
+        let good_to_mark = match bad {
+            Some(bad) => {
+                args.push(bad);
+                args.extend(good);
+                Vec::new()
+            }
+            None => good,
+        };
+        async move {
+            for revision in &args[1..] {
+                ensure_revision_arg(revision)?;
+            }
+            // Anything after the revisions would be read as paths to restrict the bisect to.
+            args.push("--".to_string());
+            self.run_bisect_command(args, env.clone()).await?;
+            for commit in good_to_mark {
+                self.bisect_mark(BisectTerm::Good, Some(commit), env.clone())
+                    .await?;
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn bisect_mark(
+        &self,
+        term: BisectTerm,
+        commit: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        async move {
+            if let Some(commit) = &commit {
+                ensure_revision_arg(commit)?;
+            }
+            self.run_bisect_command(
+                std::iter::once(term.as_str().to_string())
+                    .chain(commit)
+                    .collect(),
+                env,
+            )
+            .await
+        }
+        .boxed()
+    }
+
+    fn bisect_reset(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.run_bisect_command(vec!["reset".to_string()], env)
+    }
+
+    fn bisect_state(&self) -> BoxFuture<'_, Option<BisectState>> {
+        let git_dir = self.path();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+        self.executor
+            .spawn(async move {
+                if !git_dir.join("BISECT_START").exists() {
+                    return None;
+                }
+
+                let git = GitBinary::new(git_binary_path, working_directory.ok()?, executor);
+                let refs = git
+                    .run(&[
+                        "for-each-ref",
+                        "refs/bisect",
+                        "--format=%(refname)%00%(objectname)",
+                    ])
+                    .await
+                    .log_err()?;
+                let mut state = BisectState::default();
+                for line in refs.lines() {
+                    let Some((ref_name, sha)) = line.split_once('\0') else {
+                        continue;
+                    };
+                    let Some(sha) = Oid::from_str(sha).log_err() else {
+                        continue;
+                    };
+                    match ref_name.strip_prefix("refs/bisect/") {
+                        Some("bad") => state.bad = Some(sha),
+                        Some(name) if name.starts_with("good-") => state.good.push(sha),
+                        Some(name) if name.starts_with("skip-") => state.skipped.push(sha),
+                        _ => {}
+                    }
+                }
+
+                if let Some(bad) = state.bad
+                    && !state.good.is_empty()
+                {
+                    let mut args = vec!["rev-list".to_string(), bad.to_string(), "--not".into()];
+                    args.extend(state.good.iter().map(Oid::to_string));
+                    let candidates = git.run(&args).await.log_err()?;
+                    let bad = bad.to_string();
+                    let skipped = state
+                        .skipped
+                        .iter()
+                        .map(Oid::to_string)
+                        .collect::<HashSet<_>>();
+                    state.remaining = candidates
+                        .lines()
+                        .filter(|sha| *sha != bad && !skipped.contains(*sha))
+                        .count();
+                }
+
+                // Only git knows whether skipped commits leave the verdict open, and it logs
+                // the first bad commit once they don't.
+                state.first_bad = std::fs::read_to_string(git_dir.join("BISECT_LOG"))
+                    .ok()
+                    .and_then(|log| {
+                        let line = log
+                            .lines()
+                            .rev()
+                            .find_map(|line| line.strip_prefix("# first bad commit: ["))?;
+                        Oid::from_str(line.split_once(']')?.0).ok()
+                    });
+                Some(state)
+            })
+            .boxed()
+    }
+
+    fn load_index_text(&self, path: RepoPath) -> BoxFuture<'_, Option<String>> {
+        // https://git-scm.com/book/en/v2/Git-Internals-Git-Objects
+        const GIT_MODE_SYMLINK: u32 = 0o120000;
+
+        let repo = self.repository.clone();
+        self.executor
+            .spawn(async move {
+                fn logic(repo: &git2::Repository, path: &RepoPath) -> Result<Option<String>> {
+                    // This check is required because index.get_path() unwraps internally :(
+                    let mut index = repo.index()?;
+                    index.read(false)?;
+
+                    const STAGE_NORMAL: i32 = 0;
+                    let path = path.as_std_path();
+                    // `RepoPath` contains a `RelPath` which normalizes `.` into an empty path
+                    // `get_path` unwraps on empty paths though, so undo that normalization here
+                    let path = if path.components().next().is_none() {
+                        ".".as_ref()
+                    } else {
+                        path
+                    };
+                    let oid = match index.get_path(path, STAGE_NORMAL) {
+                        Some(entry) if entry.mode != GIT_MODE_SYMLINK => entry.id,
+                        _ => return Ok(None),
+                    };
+
+                    let content = repo.find_blob(oid)?.content().to_owned();
                     Ok(String::from_utf8(content).ok())
                 }
 
@@ -1327,6 +2103,41 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn load_merge_stages(&self, path: RepoPath) -> BoxFuture<'_, Result<MergeStages>> {
+        const STAGE_BASE: i32 = 1;
+        const STAGE_OURS: i32 = 2;
+        const STAGE_THEIRS: i32 = 3;
+
+        let repo = self.repository.clone();
+        self.executor
+            .spawn(async move {
+                let repo = repo.lock();
+                let mut index = repo.index()?;
+                index.read(false)?;
+                let load_stage = |stage| -> Result<Option<String>> {
+                    let Some(entry) = index.get_path(path.as_std_path(), stage) else {
+                        return Ok(None);
+                    };
+                    let content = repo.find_blob(entry.id)?.content().to_owned();
+                    Ok(Some(
+                        String::from_utf8(content).context("file is not valid UTF-8")?,
+                    ))
+                };
+                let stages = MergeStages {
+                    base: load_stage(STAGE_BASE)?,
+                    ours: load_stage(STAGE_OURS)?,
+                    theirs: load_stage(STAGE_THEIRS)?,
+                };
+                anyhow::ensure!(
+                    stages.ours.is_some() || stages.theirs.is_some(),
+                    "{} has no merge conflict",
+                    path.as_unix_str()
+                );
+                Ok(stages)
+            })
+            .boxed()
+    }
+
     fn load_committed_text(&self, path: RepoPath) -> BoxFuture<'_, Option<String>> {
         let repo = self.repository.clone();
         self.executor
@@ -1862,6 +2673,151 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                // Unlike `short`, `lstrip` keeps the name of a tag that is also a branch name intact.
+                let fields = [
+                    "%(refname:lstrip=2)",
+                    "%(objecttype)",
+                    "%(objectname)",
+                    "%(*objectname)",
+                    "%(creatordate:unix)",
+                    "%(contents:subject)",
+                ]
+                .join("%00");
+                let output = GitBinary::new(git_binary_path, working_directory?, executor)
+                    .run(&[
+                        "for-each-ref",
+                        "refs/tags",
+                        "--sort=-creatordate",
+                        "--format",
+                        &fields,
+                    ])
+                    .await?;
+                Ok(parse_tag_input(&output))
+            })
+            .boxed()
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        target: Option<String>,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+
+        self.executor
+            .spawn(async move {
+                let mut command = new_command(&git_binary_path);
+                command
+                    .envs(env.iter())
+                    .current_dir(&working_directory?)
+                    .arg("tag");
+                if let Some(message) = message {
+                    command.args(["-a", "-m"]).arg(message);
+                }
+                let output = command.arg("--").arg(&name).args(target).output().await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to create tag {name}:\n{}",
+                    String::from_utf8_lossy(&output.stderr),
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                GitBinary::new(git_binary_path, working_directory?, executor)
+                    .run(&["tag", "-d", "--", &name])
+                    .await?;
+                anyhow::Ok(())
+            })
+            .boxed()
+    }
+
+    fn checkout_tag(&self, name: String) -> BoxFuture<'_, Result<()>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                GitBinary::new(git_binary_path, working_directory?, executor)
+                    .run(&["checkout", "--detach", &format!("refs/tags/{name}")])
+                    .await?;
+                anyhow::Ok(())
+            })
+            .boxed()
+    }
+
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let config_path = self.main_repository_path().join("config");
+        let index_path = self.path().join("index");
+        let cache = self.submodules.clone();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                let working_directory = working_directory?;
+                let mtimes = |submodules: &[Submodule]| {
+                    submodule_sources(&working_directory, &config_path, &index_path, submodules)
+                        .map(|path| {
+                            std::fs::metadata(path)
+                                .and_then(|metadata| metadata.modified())
+                                .ok()
+                        })
+                        .collect::<Vec<_>>()
+                };
+                if let Some(cached) = cache.lock().as_ref()
+                    && cached.mtimes == mtimes(&cached.submodules)
+                {
+                    return Ok(cached.submodules.clone());
+                }
+
+                let git = GitBinary::new(git_binary_path, working_directory.clone(), executor);
+                let submodules = load_submodules(&git).await?;
+                *cache.lock() = Some(CachedSubmodules {
+                    mtimes: mtimes(&submodules),
+                    submodules: submodules.clone(),
+                });
+                Ok(submodules)
+            })
+            .boxed()
+    }
+
+    fn submodule_init(
+        &self,
+        paths: Vec<RepoPath>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.run_submodule_command("init", paths, env)
+    }
+
+    fn submodule_sync(
+        &self,
+        paths: Vec<RepoPath>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.run_submodule_command("sync", paths, env)
+    }
+
     fn blame(
         &self,
         path: RepoPath,
@@ -1901,16 +2857,7 @@ impl GitRepository for RealGitRepository {
         self.executor
             .spawn(async move {
                 let working_directory = working_directory?;
-                // Use a unique delimiter with a hardcoded UUID to separate commits
-                // This essentially eliminates any chance of encountering the delimiter in actual commit data
-                let commit_delimiter =
-                    concat!("<<COMMIT_END-", "3f8a9c2e-7d4b-4e1a-9f6c-8b5d2a1e4c3f>>",);
-
-                let format_string = format!(
-                    "--pretty=format:%H%x00%s%x00%B%x00%at%x00%an%x00%ae{}",
-                    commit_delimiter
-                );
-
+                let format_string = log_format_arg();
                 let mut args = vec!["--no-optional-locks", "log", "--follow", &format_string];
 
                 let skip_str;
@@ -1941,35 +2888,47 @@ impl GitRepository for RealGitRepository {
                 }
 
                 let stdout = std::str::from_utf8(&output.stdout)?;
-                let mut entries = Vec::new();
+                let entries = parse_log_entries(stdout);
+                Ok(FileHistory { entries, path })
+            })
+            .boxed()
+    }
 
-                for commit_block in stdout.split(commit_delimiter) {
-                    let commit_block = commit_block.trim();
-                    if commit_block.is_empty() {
-                        continue;
-                    }
+    fn log(
+        &self,
+        range: Option<String>,
+        path: Option<RepoPath>,
+        limit: usize,
+    ) -> BoxFuture<'_, Result<Vec<FileHistoryEntry>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let working_directory = working_directory?;
+                let format_string = log_format_arg();
+                let limit = limit.to_string();
+                let mut args = vec!["--no-optional-locks", "log", &format_string, "-n", &limit];
+                if let Some(range) = range.as_deref() {
+                    ensure_revision_arg(range)?;
+                    args.push(range);
+                }
+                args.push("--");
+                if let Some(path) = path.as_ref() {
+                    args.push(path.as_unix_str());
+                }
 
-                    let fields: Vec<&str> = commit_block.split('\0').collect();
-                    if fields.len() >= 6 {
-                        let sha = fields[0].trim().to_string().into();
-                        let subject = fields[1].trim().to_string().into();
-                        let message = fields[2].trim().to_string().into();
-                        let commit_timestamp = fields[3].trim().parse().unwrap_or(0);
-                        let author_name = fields[4].trim().to_string().into();
-                        let author_email = fields[5].trim().to_string().into();
-
-                        entries.push(FileHistoryEntry {
-                            sha,
-                            subject,
-                            message,
-                            commit_timestamp,
-                            author_name,
-                            author_email,
-                        });
-                    }
+                let output = new_command(&git_binary_path)
+                    .current_dir(&working_directory)
+                    .args(&args)
+                    .output()
+                    .await?;
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    bail!("git log failed: {stderr}");
                 }
 
-                Ok(FileHistory { entries, path })
+                let stdout = std::str::from_utf8(&output.stdout)?;
+                Ok(parse_log_entries(stdout))
             })
             .boxed()
     }
@@ -2002,6 +2961,27 @@ impl GitRepository for RealGitRepository {
                             .output()
                             .await?
                     }
+                    DiffType::Refs {
+                        base_ref,
+                        head_ref,
+                        path,
+                    } => {
+                        ensure_revision_arg(&base_ref)?;
+                        let mut args = vec!["diff", base_ref.as_ref()];
+                        if let Some(head_ref) = head_ref.as_ref() {
+                            ensure_revision_arg(head_ref)?;
+                            args.push(head_ref);
+                        }
+                        args.push("--");
+                        if let Some(path) = path.as_ref() {
+                            args.push(path.as_unix_str());
+                        }
+                        new_command(&git_binary_path)
+                            .current_dir(&working_directory)
+                            .args(args)
+                            .output()
+                            .await?
+                    }
                 };
 
                 anyhow::ensure!(
@@ -2272,6 +3252,71 @@ impl GitRepository for RealGitRepository {
         .boxed()
     }
 
+    fn push_tag(
+        &self,
+        tag_name: String,
+        remote_name: String,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let executor = cx.background_executor().clone();
+        let git_binary_path = self.system_git_binary_path.clone();
+        // Note: Do not spawn this command on the background thread, it might pop open the credential helper
+        // which we want to block on.
+        async move {
+            let git_binary_path = git_binary_path.context("git not found on $PATH, can't push")?;
+            let mut command = new_command(git_binary_path);
+            command
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .arg("push")
+                .arg(remote_name)
+                .arg(format!("refs/tags/{tag_name}"))
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+
+            run_git_command(env, ask_pass, command, executor).await
+        }
+        .boxed()
+    }
+
+    fn submodule_update(
+        &self,
+        paths: Vec<RepoPath>,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let executor = cx.background_executor().clone();
+        let git_binary_path = self.system_git_binary_path.clone();
+        let submodules = self.submodules.clone();
+        // Note: Do not spawn this command on the background thread, it might pop open the credential helper
+        // which we want to block on.
+        async move {
+            let git_binary_path =
+                git_binary_path.context("git not found on $PATH, can't update submodules")?;
+            let mut command = new_command(git_binary_path);
+            command
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .args(["submodule", "update", "--init", "--"])
+                .args(paths.iter().map(|path| path.as_unix_str()))
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+
+            let output = run_git_command(env, ask_pass, command, executor).await;
+            // Checking submodules out doesn't touch the files the cache is keyed on.
+            submodules.lock().take();
+            output
+        }
+        .boxed()
+    }
+
     fn pull(
         &self,
         branch_name: Option<String>,
@@ -2951,6 +3996,55 @@ fn parse_initial_graph_output<'a>(
         .collect()
 }
 
+// Use a unique delimiter with a hardcoded UUID to separate commits
+// This essentially eliminates any chance of encountering the delimiter in actual commit data
+const LOG_COMMIT_DELIMITER: &str =
+    concat!("<<COMMIT_END-", "3f8a9c2e-7d4b-4e1a-9f6c-8b5d2a1e4c3f>>",);
+
+/// The `--pretty` argument of the `git log` invocations parsed by [`parse_log_entries`].
+fn log_format_arg() -> String {
+    format!("--pretty=format:%H%x00%s%x00%B%x00%at%x00%an%x00%ae{LOG_COMMIT_DELIMITER}")
+}
+
+fn parse_log_entries(output: &str) -> Vec<FileHistoryEntry> {
+    let mut entries = Vec::new();
+    for commit_block in output.split(LOG_COMMIT_DELIMITER) {
+        let commit_block = commit_block.trim();
+        if commit_block.is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = commit_block.split('\0').collect();
+        if fields.len() >= 6 {
+            let sha = fields[0].trim().to_string().into();
+            let subject = fields[1].trim().to_string().into();
+            let message = fields[2].trim().to_string().into();
+            let commit_timestamp = fields[3].trim().parse().unwrap_or(0);
+            let author_name = fields[4].trim().to_string().into();
+            let author_email = fields[5].trim().to_string().into();
+
+            entries.push(FileHistoryEntry {
+                sha,
+                subject,
+                message,
+                commit_timestamp,
+                author_name,
+                author_email,
+            });
+        }
+    }
+    entries
+}
+
+/// Rejects revisions that git would parse as options, since they may come from untrusted input.
+fn ensure_revision_arg(revision: &str) -> Result<()> {
+    anyhow::ensure!(
+        !revision.is_empty() && !revision.starts_with('-'),
+        "invalid revision: {revision:?}"
+    );
+    Ok(())
+}
+
 fn git_status_args(path_prefixes: &[RepoPath]) -> Vec<OsString> {
     let mut args = vec![
         OsString::from("--no-optional-locks"),
@@ -3352,6 +4446,226 @@ fn parse_branch_input(input: &str) -> Result<Vec<Branch>> {
     Ok(branches)
 }
 
+fn parse_tag_input(input: &str) -> Vec<Tag> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\x00');
+            let name = fields.next().filter(|name| !name.is_empty())?;
+            let object_type = fields.next()?;
+            let object_sha = fields.next()?;
+            let peeled_sha = fields.next()?;
+            let timestamp = fields.next()?.parse::<i64>().unwrap_or_default();
+            let subject = fields.next().unwrap_or_default();
+
+            let is_annotated = object_type == "tag";
+            Some(Tag {
+                name: name.to_string().into(),
+                sha: if is_annotated { peeled_sha } else { object_sha }
+                    .to_string()
+                    .into(),
+                annotation: is_annotated.then(|| subject.to_string().into()),
+                timestamp,
+            })
+        })
+        .collect()
+}
+
+/// The files whose modification invalidates the listed `submodules`: `.gitmodules`, the
+/// repository's config and index, and each submodule's working directory, `HEAD` and index.
+fn submodule_sources(
+    working_directory: &Path,
+    config_path: &Path,
+    index_path: &Path,
+    submodules: &[Submodule],
+) -> impl Iterator<Item = PathBuf> {
+    let submodule_sources = submodules.iter().flat_map(|submodule| {
+        let submodule_directory = working_directory.join(submodule.path.as_std_path());
+        let dot_git = submodule_directory.join(".git");
+        // Submodules usually keep their repository in the superproject's git directory.
+        let git_dir = std::fs::read_to_string(&dot_git)
+            .ok()
+            .and_then(|contents| {
+                let git_dir = contents.strip_prefix("gitdir:")?.trim();
+                Some(submodule_directory.join(git_dir))
+            })
+            .unwrap_or(dot_git);
+        [
+            git_dir.join("HEAD"),
+            git_dir.join("index"),
+            submodule_directory,
+        ]
+    });
+    [
+        working_directory.join(".gitmodules"),
+        config_path.to_path_buf(),
+        index_path.to_path_buf(),
+    ]
+    .into_iter()
+    .chain(submodule_sources.collect::<Vec<_>>())
+}
+
+/// Lists the submodules registered in `.gitmodules` along with their state.
+async fn load_submodules(git: &GitBinary) -> Result<Vec<Submodule>> {
+    if !git.working_directory.join(".gitmodules").exists() {
+        return Ok(Vec::new());
+    }
+
+    let config = git
+        .run(&["config", "--file", ".gitmodules", "--null", "--list"])
+        .await?;
+    let mut submodules = parse_submodule_config(&config);
+    if submodules.is_empty() {
+        return Ok(submodules);
+    }
+
+    let paths = submodules
+        .iter()
+        .map(|submodule| submodule.path.as_unix_str())
+        .collect::<Vec<_>>();
+    let index = git
+        .run(["ls-files", "--stage", "-z", "--"].into_iter().chain(paths))
+        .await?;
+    let recorded_shas = parse_gitlinks(&index);
+    // `git submodule status` fails on paths that aren't submodules in the index, such
+    // as ones that were removed without updating `.gitmodules`.
+    let paths = recorded_shas.keys().copied().collect::<Vec<_>>();
+    let (status, changes) = if paths.is_empty() {
+        Default::default()
+    } else {
+        let status = git
+            .run(
+                ["submodule", "status", "--"]
+                    .into_iter()
+                    .chain(paths.clone()),
+            )
+            .await?;
+        let changes = git
+            .run(
+                [
+                    "--no-optional-locks",
+                    "status",
+                    "--porcelain=v2",
+                    "-z",
+                    "--",
+                ]
+                .into_iter()
+                .chain(paths),
+            )
+            .await?;
+        (status, changes)
+    };
+
+    let checked_out_shas = parse_submodule_status(&status);
+    let changed_paths = parse_changed_submodules(&changes);
+    for submodule in &mut submodules {
+        let path = submodule.path.as_unix_str();
+        submodule.recorded_sha = recorded_shas.get(path).copied();
+        submodule.checked_out_sha = checked_out_shas.get(path).copied();
+        submodule.has_changes = changed_paths.contains(path);
+    }
+    Ok(submodules)
+}
+
+/// Parses the output of `git config --file .gitmodules --null --list` into submodules
+/// whose state is yet to be filled in.
+fn parse_submodule_config(config: &str) -> Vec<Submodule> {
+    let mut submodules = Vec::<Submodule>::new();
+    let mut urls = HashMap::default();
+    for entry in config.split('\0') {
+        let Some((key, value)) = entry.split_once('\n') else {
+            continue;
+        };
+        let Some((name, field)) = key
+            .strip_prefix("submodule.")
+            .and_then(|key| key.rsplit_once('.'))
+        else {
+            continue;
+        };
+        match field {
+            "path" => {
+                if let Some(path) = RepoPath::new(value).log_err() {
+                    submodules.push(Submodule {
+                        name: name.to_string().into(),
+                        path,
+                        url: None,
+                        recorded_sha: None,
+                        checked_out_sha: None,
+                        has_changes: false,
+                    });
+                }
+            }
+            "url" => {
+                urls.insert(name.to_string(), SharedString::from(value.to_string()));
+            }
+            _ => {}
+        }
+    }
+    for submodule in &mut submodules {
+        submodule.url = urls.get(submodule.name.as_ref()).cloned();
+    }
+    submodules
+}
+
+/// Parses the submodule entries out of the output of `git ls-files --stage -z`.
+fn parse_gitlinks(index: &str) -> HashMap<&str, Oid> {
+    const GIT_MODE_GITLINK: &str = "160000";
+
+    index
+        .split('\0')
+        .filter_map(|entry| {
+            let (info, path) = entry.split_once('\t')?;
+            let mut fields = info.split(' ');
+            if fields.next()? != GIT_MODE_GITLINK {
+                return None;
+            }
+            let sha = Oid::from_str(fields.next()?).ok()?;
+            // Skip the other sides of a conflict.
+            (fields.next()? == "0").then_some((path, sha))
+        })
+        .collect()
+}
+
+/// Parses the commits checked out in initialized submodules out of `git submodule status`.
+fn parse_submodule_status(status: &str) -> HashMap<&str, Oid> {
+    status
+        .lines()
+        .filter_map(|line| {
+            let mut chars = line.chars();
+            // Uninitialized submodules are prefixed with `-` and list their recorded commit.
+            if chars.next()? == '-' {
+                return None;
+            }
+            let (sha, path) = chars.as_str().split_once(' ')?;
+            let path = path
+                .strip_suffix(')')
+                .and_then(|path| path.rsplit_once(" ("))
+                .map_or(path, |(path, _)| path);
+            Some((path, Oid::from_str(sha).ok()?))
+        })
+        .collect()
+}
+
+/// Returns the paths of the submodules with modified or untracked files out of
+/// `git status --porcelain=v2 -z`.
+fn parse_changed_submodules(status: &str) -> HashSet<&str> {
+    status
+        .split('\0')
+        .filter_map(|entry| {
+            let mut fields = entry.splitn(9, ' ');
+            if fields.next()? != "1" {
+                return None;
+            }
+            let submodule_state = fields.nth(1)?.as_bytes();
+            let path = fields.nth(5)?;
+            // The state reads `S<commit><modified><untracked>`, with `.` for no change.
+            let has_changes = submodule_state.first() == Some(&b'S')
+                && (submodule_state.get(2) == Some(&b'M') || submodule_state.get(3) == Some(&b'U'));
+            has_changes.then_some(path)
+        })
+        .collect()
+}
+
 fn parse_upstream_track(upstream_track: &str) -> Result<UpstreamTracking> {
     if upstream_track.is_empty() {
         return Ok(UpstreamTracking::Tracked(UpstreamTrackingStatus {
@@ -4256,6 +5570,581 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_interactive_rebase(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+
+        for (file, message) in [
+            ("a.txt", "Add a"),
+            ("b.txt", "Add b"),
+            ("c.txt", "Add c"),
+            ("d.txt", "Add d"),
+        ] {
+            smol::fs::write(repo_dir.path().join(file), file)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path(file)], Arc::new(HashMap::default()))
+                .await
+                .unwrap();
+            repo.commit(
+                message.into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                Arc::new(checkpoint_author_envs()),
+            )
+            .await
+            .unwrap();
+        }
+
+        let commits = repo.rebase_commits("HEAD~3".into()).await.unwrap();
+        assert_eq!(
+            commits
+                .iter()
+                .map(|commit| commit.message.as_ref())
+                .collect::<Vec<_>>(),
+            ["Add b", "Add c", "Add d"]
+        );
+        assert_eq!(repo.rebase_progress().await, None);
+
+        let entry = |ix: usize, action: RebaseAction, message: Option<&str>| RebaseTodoEntry {
+            sha: commits[ix].sha.clone(),
+            action,
+            message: message.map(|message| message.to_string().into()),
+        };
+        repo.rebase_interactive(
+            "HEAD~3".into(),
+            vec![
+                entry(2, RebaseAction::Reword, Some("Add d first")),
+                entry(0, RebaseAction::Pick, None),
+                entry(1, RebaseAction::Fixup, None),
+            ],
+            Arc::new(checkpoint_author_envs()),
+        )
+        .await
+        .unwrap();
+
+        let commits = repo.rebase_commits("HEAD~2".into()).await.unwrap();
+        assert_eq!(
+            commits
+                .iter()
+                .map(|commit| commit.message.as_ref())
+                .collect::<Vec<_>>(),
+            ["Add d first", "Add b"]
+        );
+        assert!(repo_dir.path().join("c.txt").exists());
+        assert_eq!(repo.rebase_progress().await, None);
+
+        // Squashing into nothing is rejected before git runs.
+        repo.rebase_interactive(
+            "HEAD~1".into(),
+            vec![entry(0, RebaseAction::Squash, None)],
+            Arc::new(checkpoint_author_envs()),
+        )
+        .await
+        .unwrap_err();
+    }
+
+    #[gpui::test]
+    async fn test_rebase_progress_and_abort(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+
+        for (contents, message) in [("one", "First"), ("two", "Second"), ("three", "Third")] {
+            smol::fs::write(repo_dir.path().join("file.txt"), contents)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path("file.txt")], Arc::new(HashMap::default()))
+                .await
+                .unwrap();
+            repo.commit(
+                message.into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                Arc::new(checkpoint_author_envs()),
+            )
+            .await
+            .unwrap();
+        }
+        let head = repo.head_sha().await;
+        let branch = repo
+            .branches()
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|branch| branch.is_head)
+            .unwrap();
+
+        // Swapping two commits that edit the same line stops on a conflict.
+        let commits = repo.rebase_commits("HEAD~2".into()).await.unwrap();
+        let todo = commits
+            .iter()
+            .rev()
+            .map(|commit| RebaseTodoEntry {
+                sha: commit.sha.clone(),
+                action: RebaseAction::Pick,
+                message: None,
+            })
+            .collect();
+        repo.rebase_interactive("HEAD~2".into(), todo, Arc::new(checkpoint_author_envs()))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            repo.rebase_progress().await,
+            Some(RebaseProgress {
+                step: 1,
+                total: 2,
+                head_name: Some(branch.name().to_string().into()),
+            })
+        );
+
+        repo.rebase_operation(RebaseOperation::Abort, Arc::new(checkpoint_author_envs()))
+            .await
+            .unwrap();
+        assert_eq!(repo.rebase_progress().await, None);
+        assert_eq!(repo.head_sha().await, head);
+    }
+
+    #[gpui::test]
+    async fn test_cherry_pick_and_revert(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+
+        for (file, contents, message) in [
+            ("file.txt", "one", "First"),
+            ("file.txt", "two", "Second"),
+            ("a.txt", "a", "Add a"),
+        ] {
+            smol::fs::write(repo_dir.path().join(file), contents)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path(file)], Arc::new(HashMap::default()))
+                .await
+                .unwrap();
+            repo.commit(
+                message.into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                Arc::new(checkpoint_author_envs()),
+            )
+            .await
+            .unwrap();
+        }
+        let first = repo.show("HEAD~2".into()).await.unwrap();
+        let add_a = repo.show("HEAD".into()).await.unwrap();
+
+        let outcome = repo
+            .revert(add_a.sha.to_string(), Arc::new(checkpoint_author_envs()))
+            .await
+            .unwrap();
+        assert_eq!(outcome, ApplyCommitOutcome::Applied);
+        assert!(!repo_dir.path().join("a.txt").exists());
+        assert_eq!(
+            repo.show("HEAD".into()).await.unwrap().message.trim_end(),
+            format!("Revert \"Add a\"\n\nThis reverts commit {}.", add_a.sha)
+        );
+
+        let outcome = repo
+            .cherry_pick(add_a.sha.to_string(), Arc::new(checkpoint_author_envs()))
+            .await
+            .unwrap();
+        assert_eq!(outcome, ApplyCommitOutcome::Applied);
+        assert!(repo_dir.path().join("a.txt").exists());
+        assert_eq!(
+            repo.show("HEAD".into()).await.unwrap().message.trim_end(),
+            "Add a"
+        );
+
+        // Reverting the commit that created a file modified later stops on a conflict.
+        let outcome = repo
+            .revert(first.sha.to_string(), Arc::new(checkpoint_author_envs()))
+            .await
+            .unwrap();
+        assert_eq!(outcome, ApplyCommitOutcome::Conflicted);
+        assert!(repo_dir.path().join(".git/REVERT_HEAD").exists());
+        let status = std::process::Command::new("git")
+            .current_dir(repo_dir.path())
+            .args(["revert", "--abort"])
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        // Picking a commit whose changes are already there leaves the cherry-pick in progress
+        // without any conflict.
+        repo.cherry_pick(add_a.sha.to_string(), Arc::new(checkpoint_author_envs()))
+            .await
+            .unwrap_err();
+        assert!(repo_dir.path().join(".git/CHERRY_PICK_HEAD").exists());
+
+        repo.cherry_pick("0".repeat(40), Arc::new(checkpoint_author_envs()))
+            .await
+            .unwrap_err();
+        repo.cherry_pick("--abort".into(), Arc::new(checkpoint_author_envs()))
+            .await
+            .unwrap_err();
+        assert!(repo_dir.path().join(".git/CHERRY_PICK_HEAD").exists());
+    }
+
+    #[gpui::test]
+    async fn test_tags(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+
+        for (contents, message) in [("one", "First"), ("two", "Second")] {
+            smol::fs::write(repo_dir.path().join("file.txt"), contents)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path("file.txt")], Arc::new(HashMap::default()))
+                .await
+                .unwrap();
+            repo.commit(
+                message.into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                Arc::new(checkpoint_author_envs()),
+            )
+            .await
+            .unwrap();
+        }
+        let first = repo.show("HEAD~1".into()).await.unwrap();
+        let second = repo.show("HEAD".into()).await.unwrap();
+
+        repo.create_tag(
+            "v1.0".into(),
+            Some(first.sha.to_string()),
+            None,
+            Arc::new(checkpoint_author_envs()),
+        )
+        .await
+        .unwrap();
+        repo.create_tag(
+            "v2.0".into(),
+            None,
+            Some("Release 2.0\n\nWith notes".into()),
+            Arc::new(checkpoint_author_envs()),
+        )
+        .await
+        .unwrap();
+        // A branch with the same name makes the short name of the tag's ref ambiguous.
+        repo.create_branch("v1.0".into(), None).await.unwrap();
+
+        let mut tags = repo.tags().await.unwrap();
+        tags.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].name, "v1.0");
+        assert_eq!(tags[0].sha, first.sha);
+        assert_eq!(tags[0].annotation, None);
+        assert_eq!(tags[1].name, "v2.0");
+        assert_eq!(tags[1].sha, second.sha);
+        assert_eq!(tags[1].annotation.as_deref(), Some("Release 2.0"));
+
+        repo.checkout_tag("v1.0".into()).await.unwrap();
+        assert_eq!(repo.head_sha().await.as_deref(), Some(first.sha.as_ref()));
+
+        repo.delete_tag("v1.0".into()).await.unwrap();
+        let tags = repo.tags().await.unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].name, "v2.0");
+
+        repo.delete_tag("v1.0".into()).await.unwrap_err();
+    }
+
+    #[gpui::test]
+    async fn test_submodules(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        async fn git(dir: &Path, args: &[&str]) {
+            let output = new_command("git")
+                .current_dir(dir)
+                .envs(checkpoint_author_envs())
+                .args(["-c", "protocol.file.allow=always"])
+                .args(args)
+                .output()
+                .await
+                .unwrap();
+            assert!(
+                output.status.success(),
+                "git {args:?} failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let lib_dir = dir.path().join("lib");
+        let main_dir = dir.path().join("main");
+        for repo_dir in [&lib_dir, &main_dir] {
+            git2::Repository::init(repo_dir).unwrap();
+            smol::fs::write(repo_dir.join("file.txt"), "contents")
+                .await
+                .unwrap();
+            git(repo_dir, &["add", "file.txt"]).await;
+            git(repo_dir, &["commit", "-m", "Initial commit"]).await;
+        }
+        git(
+            &main_dir,
+            &[
+                "submodule",
+                "add",
+                lib_dir.to_str().unwrap(),
+                "vendor/my lib",
+            ],
+        )
+        .await;
+        git(&main_dir, &["commit", "-m", "Add submodule"]).await;
+
+        let repo = RealGitRepository::new(
+            &main_dir.join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let lib_repo = RealGitRepository::new(
+            &lib_dir.join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let lib_head = Oid::from_str(&lib_repo.head_sha().await.unwrap()).unwrap();
+
+        let submodules = repo.submodules().await.unwrap();
+        assert_eq!(submodules.len(), 1);
+        assert_eq!(submodules[0].name, "vendor/my lib");
+        assert_eq!(submodules[0].path, repo_path("vendor/my lib"));
+        assert_eq!(
+            submodules[0].url.as_deref(),
+            Some(lib_dir.to_str().unwrap())
+        );
+        assert_eq!(submodules[0].recorded_sha, Some(lib_head));
+        assert_eq!(submodules[0].checked_out_sha, Some(lib_head));
+        assert_eq!(submodules[0].status(), SubmoduleStatus::UpToDate);
+
+        let submodule_dir = main_dir.join("vendor/my lib");
+        smol::fs::write(submodule_dir.join("untracked.txt"), "untracked")
+            .await
+            .unwrap();
+        let submodules = repo.submodules().await.unwrap();
+        assert_eq!(submodules[0].status(), SubmoduleStatus::Modified);
+
+        git(&submodule_dir, &["add", "untracked.txt"]).await;
+        git(&submodule_dir, &["commit", "-m", "Second commit"]).await;
+        let submodules = repo.submodules().await.unwrap();
+        assert_eq!(submodules[0].recorded_sha, Some(lib_head));
+        assert_ne!(submodules[0].checked_out_sha, Some(lib_head));
+        assert_eq!(submodules[0].status(), SubmoduleStatus::NewCommits);
+
+        git(&main_dir, &["submodule", "deinit", "--force", "--all"]).await;
+        let submodules = repo.submodules().await.unwrap();
+        assert_eq!(submodules[0].checked_out_sha, None);
+        assert_eq!(submodules[0].status(), SubmoduleStatus::Uninitialized);
+
+        repo.submodule_init(Vec::new(), Arc::new(HashMap::default()))
+            .await
+            .unwrap();
+        repo.submodule_sync(
+            vec![repo_path("vendor/my lib")],
+            Arc::new(HashMap::default()),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[gpui::test]
+    async fn test_bisect(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+
+        let mut shas = Vec::new();
+        for index in 0..5 {
+            smol::fs::write(repo_dir.path().join("file.txt"), index.to_string())
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path("file.txt")], Arc::new(HashMap::default()))
+                .await
+                .unwrap();
+            repo.commit(
+                format!("Commit {index}").into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                Arc::new(checkpoint_author_envs()),
+            )
+            .await
+            .unwrap();
+            let sha = repo.head_sha().await.unwrap();
+            shas.push(Oid::from_str(&sha).unwrap());
+        }
+        assert_eq!(repo.bisect_state().await, None);
+
+        repo.bisect_start(
+            Some("--term-bad=broken".into()),
+            vec![shas[0].to_string()],
+            Arc::new(checkpoint_author_envs()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(repo.bisect_state().await, None);
+
+        repo.bisect_start(
+            Some(shas[4].to_string()),
+            vec![shas[0].to_string()],
+            Arc::new(checkpoint_author_envs()),
+        )
+        .await
+        .unwrap();
+        let state = repo.bisect_state().await.unwrap();
+        assert_eq!(state.bad, Some(shas[4]));
+        assert_eq!(state.good, vec![shas[0]]);
+        assert_eq!(state.remaining, 3);
+        assert_eq!(state.first_bad, None);
+
+        repo.bisect_mark(
+            BisectTerm::Good,
+            Some("--help".into()),
+            Arc::new(checkpoint_author_envs()),
+        )
+        .await
+        .unwrap_err();
+
+        // Commit 2 is the first bad one.
+        while repo.bisect_state().await.unwrap().first_bad.is_none() {
+            let head = Oid::from_str(&repo.head_sha().await.unwrap()).unwrap();
+            let index = shas.iter().position(|sha| *sha == head).unwrap();
+            let term = if index >= 2 {
+                BisectTerm::Bad
+            } else {
+                BisectTerm::Good
+            };
+            repo.bisect_mark(term, None, Arc::new(checkpoint_author_envs()))
+                .await
+                .unwrap();
+        }
+        let state = repo.bisect_state().await.unwrap();
+        assert_eq!(state.first_bad, Some(shas[2]));
+        assert_eq!(state.bad, Some(shas[2]));
+        assert_eq!(state.remaining, 0);
+
+        repo.bisect_reset(Arc::new(checkpoint_author_envs()))
+            .await
+            .unwrap();
+        assert_eq!(repo.bisect_state().await, None);
+        assert_eq!(
+            repo.head_sha().await.as_deref(),
+            Some(shas[4].to_string().as_str())
+        );
+    }
+
+    #[gpui::test]
+    async fn test_load_merge_stages(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        async fn git(dir: &Path, args: &[&str]) -> bool {
+            new_command("git")
+                .current_dir(dir)
+                .envs(checkpoint_author_envs())
+                .args(args)
+                .output()
+                .await
+                .unwrap()
+                .status
+                .success()
+        }
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        let dir = repo_dir.path();
+        git2::Repository::init(dir).unwrap();
+        smol::fs::write(dir.join("file.txt"), "base\n")
+            .await
+            .unwrap();
+        assert!(git(dir, &["add", "file.txt"]).await);
+        assert!(git(dir, &["commit", "-m", "Base"]).await);
+        assert!(git(dir, &["checkout", "-b", "other"]).await);
+        smol::fs::write(dir.join("file.txt"), "theirs\n")
+            .await
+            .unwrap();
+        assert!(git(dir, &["commit", "-am", "Theirs"]).await);
+        assert!(git(dir, &["checkout", "-"]).await);
+        smol::fs::write(dir.join("file.txt"), "ours\n")
+            .await
+            .unwrap();
+        assert!(git(dir, &["commit", "-am", "Ours"]).await);
+        assert!(!git(dir, &["merge", "other"]).await);
+
+        let repo =
+            RealGitRepository::new(&dir.join(".git"), None, Some("git".into()), cx.executor())
+                .unwrap();
+        assert_eq!(
+            repo.load_merge_stages(repo_path("file.txt")).await.unwrap(),
+            MergeStages {
+                base: Some("base\n".into()),
+                ours: Some("ours\n".into()),
+                theirs: Some("theirs\n".into()),
+            }
+        );
+
+        repo.stage_paths(vec![repo_path("file.txt")], Arc::new(HashMap::default()))
+            .await
+            .unwrap();
+        assert!(repo.load_merge_stages(repo_path("file.txt")).await.is_err());
+    }
+
     impl RealGitRepository {
         /// Force a Git garbage collection on the repository.
         fn gc(&self) -> BoxFuture<'_, Result<()>> {
@@ -1,17 +1,22 @@
-use collections::{BTreeMap, HashMap};
+use collections::{BTreeMap, HashMap, HashSet};
 use feature_flags::{FeatureFlag, FeatureFlagAppExt as _};
 use git::{
     BuildCommitPermalinkParams, GitHostingProviderRegistry, GitRemote, Oid, ParsedGitRemote,
     parse_git_remote_url,
-    repository::{CommitDiff, InitialGraphCommitData, LogOrder, LogSource},
+    repository::{
+        BisectState, BisectTerm, CommitDiff, InitialGraphCommitData, LogOrder, LogSource,
+    },
+};
+use git_ui::{
+    commit_tooltip::CommitAvatar,
+    commit_view::{CommitOperation, CommitView, apply_commit},
 };
-use git_ui::{commit_tooltip::CommitAvatar, commit_view::CommitView};
 use gpui::{
-    AnyElement, App, Bounds, ClickEvent, ClipboardItem, Context, Corner, DefiniteLength,
-    DragMoveEvent, ElementId, Entity, EventEmitter, FocusHandle, Focusable, FontWeight, Hsla,
-    InteractiveElement, ParentElement, PathBuilder, Pixels, Point, Render, ScrollStrategy,
-    ScrollWheelEvent, SharedString, Styled, Subscription, Task, WeakEntity, Window, actions,
-    anchored, deferred, point, px,
+    Action as _, AnyElement, App, Bounds, ClickEvent, ClipboardItem, Context, Corner,
+    DefiniteLength, DismissEvent, DragMoveEvent, ElementId, Entity, EventEmitter, FocusHandle,
+    Focusable, FontWeight, Hsla, InteractiveElement, MouseButton, MouseDownEvent, ParentElement,
+    PathBuilder, Pixels, Point, Render, ScrollStrategy, ScrollWheelEvent, SharedString, Styled,
+    Subscription, Task, WeakEntity, Window, actions, anchored, deferred, point, px,
 };
 use menu::{SelectNext, SelectPrevious};
 use project::{
@@ -30,6 +35,7 @@ use ui::{
 use workspace::{
     Workspace,
     item::{Item, ItemEvent, SerializableItem},
+    notifications::DetachAndPromptErr,
 };
 
 const COMMIT_CIRCLE_RADIUS: Pixels = px(4.5);
@@ -90,6 +96,16 @@ actions!(
     [
         /// Opens the commit view for the selected commit.
         OpenCommitView,
+        /// Applies the changes of the selected commit onto the current branch.
+        CherryPickCommit,
+        /// Creates a commit undoing the changes of the selected commit.
+        RevertCommit,
+        /// Marks the selected commit as good, starting a bisect if none is in progress.
+        MarkCommitGood,
+        /// Marks the selected commit as bad, starting a bisect if none is in progress.
+        MarkCommitBad,
+        /// Skips the selected commit in the bisect in progress.
+        SkipCommit,
     ]
 );
 
@@ -297,6 +313,72 @@ struct CommitEntry {
 
 type ActiveLaneIdx = usize;
 
+/// The bisect in progress, mapped onto the commits loaded into the graph.
+struct BisectRange {
+    state: BisectState,
+    /// The commits that may still be the first bad one.
+    candidates: HashSet<Oid>,
+}
+
+impl BisectRange {
+    fn new(state: BisectState, commits: &[Rc<CommitEntry>]) -> Self {
+        let candidates = if let Some(first_bad) = state.first_bad {
+            HashSet::from_iter([first_bad])
+        } else if let Some(bad) = state.bad
+            && !state.good.is_empty()
+        {
+            let parents = commits
+                .iter()
+                .map(|commit| (commit.data.sha, &commit.data.parents))
+                .collect::<HashMap<_, _>>();
+            let good_ancestors = ancestors(&parents, state.good.iter().copied());
+            ancestors(&parents, [bad])
+                .into_iter()
+                .filter(|sha| !good_ancestors.contains(sha))
+                .collect()
+        } else {
+            HashSet::default()
+        };
+        Self { state, candidates }
+    }
+
+    /// Whether the commit can't be the first bad one, which is only known once both a good
+    /// and a bad commit have been marked.
+    fn excludes(&self, sha: &Oid) -> bool {
+        !self.candidates.is_empty() && !self.candidates.contains(sha)
+    }
+
+    fn mark(&self, sha: &Oid) -> Option<(SharedString, Color)> {
+        if self.state.first_bad.as_ref() == Some(sha) {
+            Some(("first bad".into(), Color::Error))
+        } else if self.state.bad.as_ref() == Some(sha) {
+            Some(("bad".into(), Color::Error))
+        } else if self.state.good.contains(sha) {
+            Some(("good".into(), Color::Success))
+        } else if self.state.skipped.contains(sha) {
+            Some(("skipped".into(), Color::Warning))
+        } else {
+            None
+        }
+    }
+}
+
+fn ancestors(
+    parents: &HashMap<Oid, &SmallVec<[Oid; 1]>>,
+    commits: impl IntoIterator<Item = Oid>,
+) -> HashSet<Oid> {
+    let mut ancestors = HashSet::default();
+    let mut stack = commits.into_iter().collect::<Vec<_>>();
+    while let Some(sha) = stack.pop() {
+        if ancestors.insert(sha)
+            && let Some(commit_parents) = parents.get(&sha)
+        {
+            stack.extend(commit_parents.iter().copied());
+        }
+    }
+    ancestors
+}
+
 enum AllCommitCount {
     NotLoaded,
     Loaded(usize),
@@ -644,6 +726,7 @@ pub struct GitGraph {
     log_source: LogSource,
     log_order: LogOrder,
     selected_commit_diff: Option<CommitDiff>,
+    bisect_range: Option<BisectRange>,
     _commit_diff_task: Option<Task<()>>,
     _load_task: Option<Task<()>>,
     commit_details_split_state: Entity<SplitState>,
@@ -686,6 +769,7 @@ impl GitGraph {
             }
             GitStoreEvent::ActiveRepositoryChanged(_) => {
                 this.graph_data.clear();
+                this.refresh_bisect_range(cx);
                 cx.notify();
             }
             _ => {}
@@ -720,7 +804,7 @@ impl GitGraph {
         })
         .detach();
 
-        GitGraph {
+        let mut git_graph = GitGraph {
             focus_handle,
             project,
             workspace,
@@ -735,10 +819,13 @@ impl GitGraph {
             graph_viewport_width: px(88.),
             selected_entry_idx: None,
             selected_commit_diff: None,
+            bisect_range: None,
             log_source,
             log_order,
             commit_details_split_state: cx.new(|_cx| SplitState::new()),
-        }
+        };
+        git_graph.refresh_bisect_range(cx);
+        git_graph
     }
 
     fn on_repository_event(
@@ -762,34 +849,61 @@ impl GitGraph {
                 });
 
                 self.graph_data.max_commit_count = AllCommitCount::Loaded(*commit_count);
+                self.refresh_bisect_range(cx);
             }
             RepositoryEvent::BranchChanged => {
                 self.graph_data.clear();
+                self.refresh_bisect_range(cx);
                 cx.notify();
             }
+            RepositoryEvent::BisectStateChanged => {
+                self.refresh_bisect_range(cx);
+            }
             _ => {}
         }
 
         cx.notify();
     }
 
+    fn refresh_bisect_range(&mut self, cx: &App) {
+        let bisect = self
+            .project
+            .read(cx)
+            .active_repository(cx)
+            .and_then(|repository| repository.read(cx).bisect.clone());
+        self.bisect_range = bisect.map(|state| BisectRange::new(state, &self.graph_data.commits));
+    }
+
     fn render_badge(&self, name: &SharedString, accent_color: gpui::Hsla) -> impl IntoElement {
+        // `git log --decorate` prefixes tags with "tag: ", which is replaced with an icon.
+        let tag_name = name.strip_prefix("tag: ");
+
         div()
             .px_1p5()
             .py_0p5()
             .h(self.row_height - px(4.0))
             .flex()
+            .gap_0p5()
             .items_center()
             .justify_center()
             .rounded_md()
             .bg(accent_color.opacity(0.18))
             .border_1()
             .border_color(accent_color.opacity(0.55))
+            .when(tag_name.is_some(), |this| {
+                this.border_dashed().child(
+                    Icon::new(IconName::Tag)
+                        .size(IconSize::XSmall)
+                        .color(Color::Muted),
+                )
+            })
             .child(
-                Label::new(name.clone())
-                    .size(LabelSize::Small)
-                    .color(Color::Default)
-                    .single_line(),
+                Label::new(
+                    tag_name.map_or_else(|| name.clone(), |tag_name| tag_name.to_string().into()),
+                )
+                .size(LabelSize::Small)
+                .color(Color::Default)
+                .single_line(),
             )
     }
 
@@ -858,8 +972,12 @@ impl GitGraph {
                     .copied()
                     .unwrap_or_else(|| accent_colors.0.first().copied().unwrap_or_default());
                 let is_selected = self.selected_entry_idx == Some(idx);
+                let bisect_range = self.bisect_range.as_ref();
+                let bisect_mark = bisect_range.and_then(|range| range.mark(&commit.data.sha));
                 let text_color = if is_selected {
                     Color::Default
+                } else if bisect_range.is_some_and(|range| range.excludes(&commit.data.sha)) {
+                    Color::Disabled
                 } else {
                     Color::Muted
                 };
@@ -874,15 +992,21 @@ impl GitGraph {
                                 .gap_1()
                                 .items_center()
                                 .overflow_hidden()
-                                .children((!commit.data.ref_names.is_empty()).then(|| {
-                                    h_flex().flex_shrink().gap_2().items_center().children(
-                                        commit
-                                            .data
-                                            .ref_names
-                                            .iter()
-                                            .map(|name| self.render_badge(name, accent_color)),
-                                    )
-                                }))
+                                .children(
+                                    (!commit.data.ref_names.is_empty() || bisect_mark.is_some())
+                                        .then(|| {
+                                            h_flex()
+                                                .flex_shrink()
+                                                .gap_2()
+                                                .items_center()
+                                                .children(bisect_mark.map(|(name, color)| {
+                                                    self.render_badge(&name, color.color(cx))
+                                                }))
+                                                .children(commit.data.ref_names.iter().map(
+                                                    |name| self.render_badge(name, accent_color),
+                                                ))
+                                        }),
+                                )
                                 .child(
                                     Label::new(subject)
                                         .color(text_color)
@@ -1003,6 +1127,126 @@ impl GitGraph {
         );
     }
 
+    fn apply_selected_commit(
+        &mut self,
+        operation: CommitOperation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(commit_entry) = self
+            .selected_entry_idx
+            .and_then(|idx| self.graph_data.commits.get(idx))
+        else {
+            return;
+        };
+
+        let repository = self
+            .project
+            .read_with(cx, |project, cx| project.active_repository(cx));
+
+        let Some(repository) = repository else {
+            return;
+        };
+
+        apply_commit(
+            operation,
+            commit_entry.data.sha.to_string().into(),
+            repository,
+            self.workspace.clone(),
+            window,
+            cx,
+        );
+    }
+
+    fn bisect_selected_commit(
+        &mut self,
+        term: BisectTerm,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(commit_entry) = self
+            .selected_entry_idx
+            .and_then(|idx| self.graph_data.commits.get(idx))
+        else {
+            return;
+        };
+
+        let repository = self
+            .project
+            .read_with(cx, |project, cx| project.active_repository(cx));
+
+        let Some(repository) = repository else {
+            return;
+        };
+
+        let sha = commit_entry.data.sha.to_string();
+        let is_bisecting = repository.read(cx).bisect.is_some();
+        let bisect = repository.update(cx, |repository, _| match term {
+            BisectTerm::Bad if !is_bisecting => repository.bisect_start(Some(sha), Vec::new()),
+            BisectTerm::Good if !is_bisecting => repository.bisect_start(None, vec![sha]),
+            _ => repository.bisect_mark(term, Some(sha)),
+        });
+        cx.spawn(async move |_, _| {
+            bisect.await??;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to bisect", window, cx, |_, _, _| None);
+    }
+
+    fn deploy_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        idx: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_entry(idx, cx);
+        let sha = self
+            .graph_data
+            .commits
+            .get(idx)
+            .map(|commit| commit.data.sha.to_string());
+        let is_bisecting = self.bisect_range.is_some();
+        let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
+            context_menu
+                .context(self.focus_handle.clone())
+                .action("Open Commit", OpenCommitView.boxed_clone())
+                .separator()
+                .action("Cherry-pick Commit", CherryPickCommit.boxed_clone())
+                .action("Revert Commit", RevertCommit.boxed_clone())
+                .when_some(sha, |context_menu, sha| {
+                    context_menu.separator().action(
+                        "Create Tag…",
+                        git::CreateTag { target: Some(sha) }.boxed_clone(),
+                    )
+                })
+                .separator()
+                .header("Bisect")
+                .action("Mark as Good", MarkCommitGood.boxed_clone())
+                .action("Mark as Bad", MarkCommitBad.boxed_clone())
+                .when(is_bisecting, |context_menu| {
+                    context_menu
+                        .action("Skip", SkipCommit.boxed_clone())
+                        .action("Reset Bisect", git::BisectReset.boxed_clone())
+                })
+        });
+        let subscription = cx.subscribe_in(
+            &context_menu,
+            window,
+            |this, _, _: &DismissEvent, window, cx| {
+                if this.context_menu.as_ref().is_some_and(|context_menu| {
+                    context_menu.0.focus_handle(cx).contains_focused(window, cx)
+                }) {
+                    cx.focus_self(window);
+                }
+                this.context_menu.take();
+                cx.notify();
+            },
+        );
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
     fn get_remote(
         &self,
         repository: &Repository,
@@ -1739,16 +1983,33 @@ impl Render for GitGraph {
                                     .when(is_selected, |row| {
                                         row.bg(cx.theme().colors().element_selected)
                                     })
-                                    .on_click(move |event, window, cx| {
-                                        let click_count = event.click_count();
-                                        weak.update(cx, |this, cx| {
-                                            this.select_entry(index, cx);
-                                            if click_count >= 2 {
-                                                this.open_commit_view(index, window, cx);
-                                            }
-                                        })
-                                        .ok();
+                                    .on_click({
+                                        let weak = weak.clone();
+                                        move |event, window, cx| {
+                                            let click_count = event.click_count();
+                                            weak.update(cx, |this, cx| {
+                                                this.select_entry(index, cx);
+                                                if click_count >= 2 {
+                                                    this.open_commit_view(index, window, cx);
+                                                }
+                                            })
+                                            .ok();
+                                        }
                                     })
+                                    .on_mouse_down(
+                                        MouseButton::Right,
+                                        move |event: &MouseDownEvent, window, cx| {
+                                            weak.update(cx, |this, cx| {
+                                                this.deploy_context_menu(
+                                                    event.position,
+                                                    index,
+                                                    window,
+                                                    cx,
+                                                );
+                                            })
+                                            .ok();
+                                        },
+                                    )
                                     .into_any_element()
                             })
                             .uniform_list(
@@ -1782,6 +2043,21 @@ impl Render for GitGraph {
             .on_action(cx.listener(|this, _: &OpenCommitView, window, cx| {
                 this.open_selected_commit_view(window, cx);
             }))
+            .on_action(cx.listener(|this, _: &CherryPickCommit, window, cx| {
+                this.apply_selected_commit(CommitOperation::CherryPick, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &RevertCommit, window, cx| {
+                this.apply_selected_commit(CommitOperation::Revert, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &MarkCommitGood, window, cx| {
+                this.bisect_selected_commit(BisectTerm::Good, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &MarkCommitBad, window, cx| {
+                this.bisect_selected_commit(BisectTerm::Bad, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &SkipCommit, window, cx| {
+                this.bisect_selected_commit(BisectTerm::Skip, window, cx);
+            }))
             .on_action(cx.listener(Self::select_prev))
             .on_action(cx.listener(Self::select_next))
             .child(content)
@@ -2491,6 +2767,75 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bisect_range() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let oids: Vec<Oid> = (0..6).map(|_| Oid::random(&mut rng)).collect();
+        let commit = |ix: usize, parents: &[usize]| {
+            Arc::new(InitialGraphCommitData {
+                sha: oids[ix],
+                parents: parents.iter().map(|&parent| oids[parent]).collect(),
+                ref_names: vec![],
+            })
+        };
+        // 1 merges 3, which branched off before the good commit 4.
+        let commits = vec![
+            commit(0, &[1]),
+            commit(1, &[2, 3]),
+            commit(2, &[4]),
+            commit(3, &[5]),
+            commit(4, &[5]),
+            commit(5, &[]),
+        ];
+        let mut graph_data = GraphData::new(8);
+        graph_data.add_commits(&commits);
+
+        let state = BisectState {
+            bad: Some(oids[1]),
+            good: vec![oids[4]],
+            skipped: vec![oids[2]],
+            remaining: 1,
+            first_bad: None,
+        };
+        let range = BisectRange::new(state.clone(), &graph_data.commits);
+        let excluded = oids
+            .iter()
+            .map(|sha| range.excludes(sha))
+            .collect::<Vec<_>>();
+        assert_eq!(excluded, [true, false, false, false, true, true]);
+        assert_eq!(range.mark(&oids[1]), Some(("bad".into(), Color::Error)));
+        assert_eq!(
+            range.mark(&oids[2]),
+            Some(("skipped".into(), Color::Warning))
+        );
+        assert_eq!(range.mark(&oids[4]), Some(("good".into(), Color::Success)));
+        assert_eq!(range.mark(&oids[3]), None);
+
+        let range = BisectRange::new(
+            BisectState {
+                first_bad: Some(oids[3]),
+                ..state.clone()
+            },
+            &graph_data.commits,
+        );
+        assert!(range.excludes(&oids[1]));
+        assert!(!range.excludes(&oids[3]));
+        assert_eq!(
+            range.mark(&oids[3]),
+            Some(("first bad".into(), Color::Error))
+        );
+
+        // Nothing is excluded until both a good and a bad commit are marked.
+        let range = BisectRange::new(
+            BisectState {
+                good: Vec::new(),
+                ..state
+            },
+            &graph_data.commits,
+        );
+        assert!(oids.iter().all(|sha| !range.excludes(sha)));
+    }
+
     #[test]
     fn test_git_graph_random_commits() {
         for seed in 0..100 {
@@ -0,0 +1,108 @@
+use anyhow::{Context as _, anyhow};
+use git::repository::BisectTerm;
+use gpui::{Context, Window};
+use task::{HideStrategy, RevealStrategy, TaskContext, TaskTemplate};
+use workspace::{Workspace, notifications::DetachAndPromptErr};
+
+use crate::resolve_active_repository;
+
+pub(crate) fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, action: &git::BisectStart, window, cx| {
+        start(workspace, action, window, cx);
+    });
+    workspace.register_action(|workspace, action: &git::BisectRun, window, cx| {
+        run(workspace, action, window, cx);
+    });
+}
+
+fn start(
+    workspace: &mut Workspace,
+    action: &git::BisectStart,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(repo) = resolve_active_repository(workspace, cx) else {
+        return;
+    };
+    let bisect_start = repo.update(cx, |repo, _| {
+        repo.bisect_start(action.bad.clone(), action.good.clone())
+    });
+    cx.spawn(async move |_, _| {
+        bisect_start.await??;
+        anyhow::Ok(())
+    })
+    .detach_and_prompt_err("Failed to start bisect", window, cx, |_, _, _| None);
+}
+
+/// Tests the commits of the bisect in progress one after another in a terminal, the way
+/// `git bisect run` would, until the first bad commit is found.
+fn run(
+    workspace: &mut Workspace,
+    action: &git::BisectRun,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(repo) = resolve_active_repository(workspace, cx) else {
+        return;
+    };
+    let command_label = std::iter::once(&action.command)
+        .chain(&action.args)
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let template = TaskTemplate {
+        label: format!("git bisect run {command_label}"),
+        command: action.command.clone(),
+        args: action.args.clone(),
+        cwd: Some(
+            repo.read(cx)
+                .work_directory_abs_path
+                .to_string_lossy()
+                .into_owned(),
+        ),
+        reveal: RevealStrategy::NoFocus,
+        hide: HideStrategy::Never,
+        ..TaskTemplate::default()
+    };
+
+    cx.spawn_in(window, async move |workspace, cx| {
+        let mut state = repo
+            .read_with(cx, |repo, _| repo.bisect.clone())
+            .context("There is no bisect in progress")?;
+        if state.bad.is_none() || state.good.is_empty() {
+            return Err(anyhow!(
+                "Mark a good and a bad commit before running the bisect"
+            ));
+        }
+
+        while state.first_bad.is_none() {
+            if state.remaining == 0 {
+                return Err(anyhow!("Only skipped commits are left to test"));
+            }
+            let spawn_in_terminal = template
+                .resolve_task("git-bisect-run", &TaskContext::default())
+                .context("Invalid bisect command")?
+                .resolved;
+            let status = workspace
+                .update_in(cx, |workspace, window, cx| {
+                    workspace.spawn_in_terminal(spawn_in_terminal, window, cx)
+                })?
+                .await
+                .context("The bisect command was cancelled")??;
+            // Mirror `git bisect run`: 125 means the commit can't be tested, and any exit
+            // status from 128 on, such as a crash, aborts the bisect instead of marking it.
+            let term = match status.code() {
+                Some(0) => BisectTerm::Good,
+                Some(125) => BisectTerm::Skip,
+                Some(code) if (1..128).contains(&code) => BisectTerm::Bad,
+                _ => return Err(anyhow!("The bisect command exited with {status}")),
+            };
+            state = repo
+                .update(cx, |repo, _| repo.bisect_mark(term, None))
+                .await??
+                .context("The bisect was reset")?;
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_prompt_err("Failed to run bisect", window, cx, |_, _, _| None);
+}
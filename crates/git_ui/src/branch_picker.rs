@@ -3,7 +3,7 @@ use editor::Editor;
 use fuzzy::StringMatchCandidate;
 
 use collections::HashSet;
-use git::repository::Branch;
+use git::repository::{Branch, Tag};
 use gpui::http_client::Url;
 use gpui::{
     Action, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
@@ -25,7 +25,11 @@ use util::ResultExt;
 use workspace::notifications::DetachAndPromptErr;
 use workspace::{ModalView, Workspace};
 
-use crate::{branch_picker, git_panel::show_error_toast, resolve_active_repository};
+use crate::{
+    branch_picker,
+    git_panel::{GitPanel, show_error_toast},
+    resolve_active_repository,
+};
 
 actions!(
     branch_picker,
@@ -33,7 +37,9 @@ actions!(
         /// Deletes the selected git branch or remote.
         DeleteBranch,
         /// Filter the list of remotes
-        FilterRemotes
+        FilterRemotes,
+        /// Pushes the selected git tag to a remote.
+        PushTag
     ]
 );
 
@@ -153,6 +159,10 @@ impl BranchList {
             repository.update(cx, |repository, _| repository.default_branch(false))
         });
 
+        let all_tags_request = repository
+            .clone()
+            .map(|repository| repository.update(cx, |repository, _| repository.tags()));
+
         cx.spawn_in(window, async move |this, cx| {
             let mut all_branches = all_branches_request
                 .context("No active repository")?
@@ -164,6 +174,11 @@ impl BranchList {
                 .ok()
                 .flatten()
                 .flatten();
+            // Tags are only an addition to the list, so failing to load them shouldn't hide branches.
+            let all_tags = match all_tags_request {
+                Some(request) => request.await?.log_err().unwrap_or_default(),
+                None => Vec::new(),
+            };
 
             let all_branches = cx
                 .background_spawn(async move {
@@ -198,6 +213,7 @@ impl BranchList {
                 this.picker.update(cx, |picker, cx| {
                     picker.delegate.default_branch = default_branch;
                     picker.delegate.all_branches = Some(all_branches);
+                    picker.delegate.all_tags = all_tags;
                     picker.refresh(window, cx);
                 })
             });
@@ -272,6 +288,19 @@ impl BranchList {
         })
     }
 
+    pub fn handle_push_tag(
+        &mut self,
+        _: &branch_picker::PushTag,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .push_tag_at(picker.delegate.selected_index, window, cx)
+        })
+    }
+
     pub fn handle_filter(
         &mut self,
         _: &branch_picker::FilterRemotes,
@@ -302,6 +331,7 @@ impl Render for BranchList {
             .w(self.width)
             .on_modifiers_changed(cx.listener(Self::handle_modifiers_changed))
             .on_action(cx.listener(Self::handle_delete))
+            .on_action(cx.listener(Self::handle_push_tag))
             .on_action(cx.listener(Self::handle_filter))
             .child(self.picker.clone())
             .when(!self.embedded, |this| {
@@ -322,6 +352,10 @@ enum Entry {
         branch: Branch,
         positions: Vec<usize>,
     },
+    Tag {
+        tag: Tag,
+        positions: Vec<usize>,
+    },
     NewUrl {
         url: String,
     },
@@ -342,9 +376,26 @@ impl Entry {
         }
     }
 
+    fn as_tag(&self) -> Option<&Tag> {
+        match self {
+            Entry::Tag { tag, .. } => Some(tag),
+            _ => None,
+        }
+    }
+
+    /// Local branches are listed first, followed by remote branches and tags.
+    fn group(&self) -> u8 {
+        match self {
+            Entry::Branch { branch, .. } if branch.is_remote() => 1,
+            Entry::Tag { .. } => 2,
+            _ => 0,
+        }
+    }
+
     fn name(&self) -> &str {
         match self {
             Entry::Branch { branch, .. } => branch.name(),
+            Entry::Tag { tag, .. } => tag.name.as_ref(),
             Entry::NewUrl { url, .. } => url.as_str(),
             Entry::NewBranch { name, .. } => name.as_str(),
             Entry::NewRemoteName { name, .. } => name.as_str(),
@@ -362,9 +413,19 @@ impl Entry {
     }
 }
 
+fn tag_description(tag: &Tag) -> String {
+    match &tag.annotation {
+        Some(annotation) => annotation.to_string(),
+        None => format!(
+            "Lightweight tag on {}",
+            tag.sha.get(..git::SHORT_SHA_LENGTH).unwrap_or(&tag.sha)
+        ),
+    }
+}
+
 #[derive(Clone, Copy, PartialEq)]
 enum BranchFilter {
-    /// Show both local and remote branches.
+    /// Show local and remote branches, and tags.
     All,
     /// Only show remote branches.
     Remote,
@@ -383,6 +444,7 @@ pub struct BranchListDelegate {
     workspace: WeakEntity<Workspace>,
     matches: Vec<Entry>,
     all_branches: Option<Vec<Branch>>,
+    all_tags: Vec<Tag>,
     default_branch: Option<SharedString>,
     repo: Option<Entity<Repository>>,
     style: BranchListStyle,
@@ -419,6 +481,7 @@ impl BranchListDelegate {
             repo,
             style,
             all_branches: None,
+            all_tags: Vec::new(),
             default_branch: None,
             selected_index: 0,
             last_query: Default::default(),
@@ -487,6 +550,7 @@ impl BranchListDelegate {
 
         cx.spawn_in(window, async move |picker, cx| {
             let mut is_remote = false;
+            let mut is_tag = false;
             let result = match &entry {
                 Entry::Branch { branch, .. } => match branch.remote_name() {
                     Some(remote_name) => {
@@ -499,6 +563,11 @@ impl BranchListDelegate {
                             .await?
                     }
                 },
+                Entry::Tag { tag, .. } => {
+                    is_tag = true;
+                    repo.update(cx, |repo, _| repo.delete_tag(tag.name.to_string()))
+                        .await?
+                }
                 _ => {
                     log::error!("Failed to delete remote: wrong entry to delete");
                     return Ok(());
@@ -508,6 +577,8 @@ impl BranchListDelegate {
             if let Err(e) = result {
                 if is_remote {
                     log::error!("Failed to delete remote: {}", e);
+                } else if is_tag {
+                    log::error!("Failed to delete tag: {}", e);
                 } else {
                     log::error!("Failed to delete branch: {}", e);
                 }
@@ -521,6 +592,8 @@ impl BranchListDelegate {
                                 e,
                                 cx,
                             )
+                        } else if is_tag {
+                            show_error_toast(workspace, format!("tag -d {}", entry.name()), e, cx)
                         } else {
                             show_error_toast(
                                 workspace,
@@ -538,10 +611,16 @@ impl BranchListDelegate {
             picker.update_in(cx, |picker, _, cx| {
                 picker.delegate.matches.retain(|e| e != &entry);
 
-                if let Entry::Branch { branch, .. } = &entry {
-                    if let Some(all_branches) = &mut picker.delegate.all_branches {
-                        all_branches.retain(|e| e.ref_name != branch.ref_name);
+                match &entry {
+                    Entry::Branch { branch, .. } => {
+                        if let Some(all_branches) = &mut picker.delegate.all_branches {
+                            all_branches.retain(|e| e.ref_name != branch.ref_name);
+                        }
                     }
+                    Entry::Tag { tag, .. } => {
+                        picker.delegate.all_tags.retain(|e| e.name != tag.name);
+                    }
+                    _ => {}
                 }
 
                 if picker.delegate.matches.is_empty() {
@@ -557,6 +636,22 @@ impl BranchListDelegate {
         })
         .detach();
     }
+
+    fn push_tag_at(&self, idx: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(tag) = self.matches.get(idx).and_then(Entry::as_tag) else {
+            return;
+        };
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let tag_name = tag.name.clone();
+        cx.emit(DismissEvent);
+        workspace.update(cx, |workspace, cx| {
+            if let Some(panel) = workspace.panel::<GitPanel>(cx) {
+                panel.update(cx, |panel, cx| panel.push_tag(tag_name, window, cx));
+            }
+        });
+    }
 }
 
 impl PickerDelegate for BranchListDelegate {
@@ -566,7 +661,7 @@ impl PickerDelegate for BranchListDelegate {
         match self.state {
             PickerState::List | PickerState::NewRemote | PickerState::NewBranch => {
                 match self.branch_filter {
-                    BranchFilter::All => "Select branch or remote…",
+                    BranchFilter::All => "Select branch, tag or remote…",
                     BranchFilter::Remote => "Select remote…",
                 }
             }
@@ -673,6 +768,10 @@ impl PickerDelegate for BranchListDelegate {
         let Some(all_branches) = self.all_branches.clone() else {
             return Task::ready(());
         };
+        let all_tags = match self.branch_filter {
+            BranchFilter::All => self.all_tags.clone(),
+            BranchFilter::Remote => Vec::new(),
+        };
 
         let branch_filter = self.branch_filter;
         cx.spawn_in(window, async move |picker, cx| {
@@ -681,29 +780,31 @@ impl PickerDelegate for BranchListDelegate {
                 BranchFilter::Remote => branch.is_remote(),
             };
 
+            let entries = all_branches
+                .into_iter()
+                .filter(|branch| branch_matches_filter(branch))
+                .map(|branch| Entry::Branch {
+                    branch,
+                    positions: Vec::new(),
+                })
+                .chain(all_tags.into_iter().map(|tag| Entry::Tag {
+                    tag,
+                    positions: Vec::new(),
+                }))
+                .collect::<Vec<_>>();
+
             let mut matches: Vec<Entry> = if query.is_empty() {
-                let mut matches: Vec<Entry> = all_branches
-                    .into_iter()
-                    .filter(|branch| branch_matches_filter(branch))
-                    .map(|branch| Entry::Branch {
-                        branch,
-                        positions: Vec::new(),
-                    })
-                    .collect();
+                let mut matches = entries;
 
                 // Keep the existing recency sort within each group, but show local branches first.
-                matches.sort_by_key(|entry| entry.as_branch().is_some_and(|b| b.is_remote()));
+                matches.sort_by_key(Entry::group);
 
                 matches
             } else {
-                let branches = all_branches
-                    .iter()
-                    .filter(|branch| branch_matches_filter(branch))
-                    .collect::<Vec<_>>();
-                let candidates = branches
+                let candidates = entries
                     .iter()
                     .enumerate()
-                    .map(|(ix, branch)| StringMatchCandidate::new(ix, branch.name()))
+                    .map(|(ix, entry)| StringMatchCandidate::new(ix, entry.name()))
                     .collect::<Vec<StringMatchCandidate>>();
                 let mut matches: Vec<Entry> = fuzzy::match_strings(
                     &candidates,
@@ -716,14 +817,21 @@ impl PickerDelegate for BranchListDelegate {
                 )
                 .await
                 .into_iter()
-                .map(|candidate| Entry::Branch {
-                    branch: branches[candidate.candidate_id].clone(),
-                    positions: candidate.positions,
+                .map(|candidate| match &entries[candidate.candidate_id] {
+                    Entry::Branch { branch, .. } => Entry::Branch {
+                        branch: branch.clone(),
+                        positions: candidate.positions,
+                    },
+                    Entry::Tag { tag, .. } => Entry::Tag {
+                        tag: tag.clone(),
+                        positions: candidate.positions,
+                    },
+                    entry => entry.clone(),
                 })
                 .collect();
 
-                // Keep fuzzy-relevance ordering within local/remote groups, but show locals first.
-                matches.sort_by_key(|entry| entry.as_branch().is_some_and(|b| b.is_remote()));
+                // Keep fuzzy-relevance ordering within each group, but show locals first.
+                matches.sort_by_key(Entry::group);
 
                 matches
             };
@@ -823,6 +931,25 @@ impl PickerDelegate for BranchListDelegate {
                     |_, _, _| None,
                 );
             }
+            Entry::Tag { tag, .. } => {
+                let Some(repo) = self.repo.clone() else {
+                    return;
+                };
+
+                let tag_name = tag.name.to_string();
+                cx.spawn(async move |_, cx| {
+                    repo.update(cx, |repo, _| repo.checkout_tag(tag_name))
+                        .await??;
+
+                    anyhow::Ok(())
+                })
+                .detach_and_prompt_err(
+                    "Failed to check out tag",
+                    window,
+                    cx,
+                    |e, _, _| Some(e.to_string()),
+                );
+            }
             Entry::NewUrl { url } => {
                 self.state = PickerState::CreateRemote(url.clone().into());
                 self.matches = Vec::new();
@@ -887,6 +1014,21 @@ impl PickerDelegate for BranchListDelegate {
                     (Some(formatted_time), Some(author), Some(subject))
                 })
             })
+            .or_else(|| {
+                entry.as_tag().map(|tag| {
+                    let tag_time = OffsetDateTime::from_unix_timestamp(tag.timestamp)
+                        .unwrap_or_else(|_| OffsetDateTime::now_utc());
+                    let local_offset =
+                        time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
+                    let formatted_time = time_format::format_localized_timestamp(
+                        tag_time,
+                        OffsetDateTime::now_utc(),
+                        local_offset,
+                        time_format::TimestampFormat::Relative,
+                    );
+                    (Some(formatted_time), None, None)
+                })
+            })
             .unwrap_or_else(|| (None, None, None));
 
         let entry_icon = match entry {
@@ -900,6 +1042,7 @@ impl PickerDelegate for BranchListDelegate {
                     Icon::new(IconName::GitBranchAlt).color(Color::Muted)
                 }
             }
+            Entry::Tag { .. } => Icon::new(IconName::Tag).color(Color::Muted),
         };
 
         let entry_title = match entry {
@@ -921,6 +1064,12 @@ impl PickerDelegate for BranchListDelegate {
                     .truncate()
                     .into_any_element()
             }
+            Entry::Tag { tag, positions } => {
+                HighlightedLabel::new(tag.name.clone(), positions.clone())
+                    .single_line()
+                    .truncate()
+                    .into_any_element()
+            }
         };
 
         let focus_handle = self.focus_handle.clone();
@@ -929,11 +1078,16 @@ impl PickerDelegate for BranchListDelegate {
             Entry::NewUrl { .. } | Entry::NewBranch { .. } | Entry::NewRemoteName { .. }
         );
 
+        let delete_tooltip = if entry.as_tag().is_some() {
+            "Delete Tag"
+        } else {
+            "Delete Branch"
+        };
         let deleted_branch_icon = |entry_ix: usize, is_head_branch: bool| {
             IconButton::new(("delete", entry_ix), IconName::Trash)
                 .tooltip(move |_, cx| {
                     Tooltip::for_action_in(
-                        "Delete Branch",
+                        delete_tooltip,
                         &branch_picker::DeleteBranch,
                         &focus_handle,
                         cx,
@@ -1033,6 +1187,7 @@ impl PickerDelegate for BranchListDelegate {
                                                             },
                                                         )
                                                     }
+                                                    Entry::Tag { tag, .. } => tag_description(tag),
                                                 };
 
                                                 Label::new(message)
@@ -1050,8 +1205,11 @@ impl PickerDelegate for BranchListDelegate {
                                         }),
                                 )
                                 .when_some(
-                                    entry.as_branch().map(|b| b.name().to_string()),
-                                    |this, branch_name| this.tooltip(Tooltip::text(branch_name)),
+                                    entry
+                                        .as_branch()
+                                        .map(|b| b.name().to_string())
+                                        .or_else(|| entry.as_tag().map(|tag| tag.name.to_string())),
+                                    |this, ref_name| this.tooltip(Tooltip::text(ref_name)),
                                 ),
                         ),
                 )
@@ -1142,8 +1300,25 @@ impl PickerDelegate for BranchListDelegate {
                             }))
                     });
 
+                let push_tag_button =
+                    matches!(selected_entry, Some(Entry::Tag { .. })).then(|| {
+                        Button::new("push-tag", "Push")
+                            .key_binding(
+                                KeyBinding::for_action_in(
+                                    &branch_picker::PushTag,
+                                    &focus_handle,
+                                    cx,
+                                )
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(branch_picker::PushTag.boxed_clone(), cx);
+                            })
+                    });
+
                 let delete_and_select_btns = h_flex()
                     .gap_1()
+                    .children(push_tag_button)
                     .child(
                         Button::new("delete-branch", "Delete")
                             .key_binding(
@@ -1459,6 +1634,90 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_tags_are_listed_after_branches(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let branches = vec![
+            create_test_branch("main", true, None, Some(1000)),
+            create_test_branch("release-notes", false, Some("origin"), Some(900)),
+        ];
+        let (branch_list, mut ctx) = init_branch_list_test(None, branches, cx).await;
+        let cx = &mut ctx;
+
+        branch_list.update(cx, |branch_list, cx| {
+            branch_list.picker.update(cx, |picker, _| {
+                picker.delegate.all_tags = vec![
+                    Tag {
+                        name: "release-1.0".into(),
+                        sha: "abc123".into(),
+                        annotation: Some("First release".into()),
+                        timestamp: 500,
+                    },
+                    Tag {
+                        name: "nightly".into(),
+                        sha: "def456".into(),
+                        annotation: None,
+                        timestamp: 400,
+                    },
+                ];
+            })
+        });
+
+        update_branch_list_matches_with_empty_query(&branch_list, cx).await;
+        branch_list.update(cx, |branch_list, cx| {
+            branch_list.picker.update(cx, |picker, _| {
+                let names = picker
+                    .delegate
+                    .matches
+                    .iter()
+                    .map(|entry| entry.name())
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    names,
+                    ["main", "origin/release-notes", "release-1.0", "nightly"]
+                );
+            })
+        });
+
+        branch_list
+            .update_in(cx, |branch_list, window, cx| {
+                branch_list.picker.update(cx, |picker, cx| {
+                    picker
+                        .delegate
+                        .update_matches("release".to_string(), window, cx)
+                })
+            })
+            .await;
+        cx.run_until_parked();
+        branch_list.update(cx, |branch_list, cx| {
+            branch_list.picker.update(cx, |picker, _| {
+                let matches = &picker.delegate.matches;
+                assert_eq!(matches[0].name(), "origin/release-notes");
+                assert_eq!(matches[1].name(), "release-1.0");
+                assert!(matches[1].as_tag().is_some());
+                assert!(matches.last().unwrap().is_new_branch());
+            })
+        });
+
+        // Tags are hidden while only remotes are shown.
+        branch_list.update_in(cx, |branch_list, window, cx| {
+            branch_list.handle_filter(&branch_picker::FilterRemotes, window, cx);
+        });
+        update_branch_list_matches_with_empty_query(&branch_list, cx).await;
+        branch_list.update(cx, |branch_list, cx| {
+            branch_list.picker.update(cx, |picker, _| {
+                assert!(
+                    picker
+                        .delegate
+                        .matches
+                        .iter()
+                        .all(|entry| entry.as_tag().is_none())
+                );
+            })
+        });
+    }
+
     async fn update_branch_list_matches_with_empty_query(
         branch_list: &Entity<BranchList>,
         cx: &mut VisualTestContext,
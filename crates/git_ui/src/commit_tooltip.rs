@@ -6,7 +6,7 @@ use git::repository::CommitSummary;
 use git::{GitRemote, commit::ParsedCommitMessage};
 use gpui::{
     App, Asset, Element, Entity, MouseButton, ParentElement, Render, ScrollHandle,
-    StatefulInteractiveElement, WeakEntity, prelude::*,
+    StatefulInteractiveElement, Task, WeakEntity, prelude::*,
 };
 use markdown::{Markdown, MarkdownElement};
 use project::git_store::Repository;
@@ -15,6 +15,7 @@ use std::hash::Hash;
 use theme::ThemeSettings;
 use time::{OffsetDateTime, UtcOffset};
 use ui::{Avatar, CopyButton, Divider, prelude::*, tooltip_container};
+use util::ResultExt as _;
 use workspace::Workspace;
 
 #[derive(Clone, Debug)]
@@ -140,6 +141,8 @@ pub struct CommitTooltip {
     markdown: Entity<Markdown>,
     repository: Entity<Repository>,
     workspace: WeakEntity<Workspace>,
+    tags: Vec<SharedString>,
+    _load_tags: Task<()>,
 }
 
 impl CommitTooltip {
@@ -191,12 +194,33 @@ impl CommitTooltip {
                 cx,
             )
         });
+        let tags = repository.update(cx, |repository, _| repository.tags());
+        let sha = commit.sha.clone();
+        let load_tags = cx.spawn(async move |this, cx| {
+            let Some(tags) = tags.await.ok().and_then(|tags| tags.log_err()) else {
+                return;
+            };
+            let tags = tags
+                .into_iter()
+                .filter(|tag| tag.sha == sha)
+                .map(|tag| tag.name)
+                .collect::<Vec<_>>();
+            if !tags.is_empty() {
+                this.update(cx, |this, cx| {
+                    this.tags = tags;
+                    cx.notify();
+                })
+                .ok();
+            }
+        });
         Self {
             commit,
             repository,
             workspace,
             scroll_handle: ScrollHandle::new(),
             markdown,
+            tags: Vec::new(),
+            _load_tags: load_tags,
         }
     }
 }
@@ -241,6 +265,8 @@ impl Render for CommitTooltip {
             .as_ref()
             .and_then(|details| details.pull_request.clone());
 
+        let tags = self.tags.clone();
+
         let ui_font_size = ThemeSettings::get_global(cx).ui_font_size(cx);
         let message_max_height = window.line_height() * 12 + (ui_font_size / 0.4);
         let repo = self.repository.clone();
@@ -300,6 +326,20 @@ impl Render for CommitTooltip {
                                 .overflow_y_scroll()
                                 .track_scroll(&self.scroll_handle),
                         )
+                        .when(!tags.is_empty(), |this| {
+                            this.child(h_flex().gap_2().flex_wrap().children(tags.into_iter().map(
+                                |tag| {
+                                    h_flex()
+                                        .gap_1()
+                                        .child(
+                                            Icon::new(IconName::Tag)
+                                                .size(IconSize::Small)
+                                                .color(Color::Muted),
+                                        )
+                                        .child(Label::new(tag).size(LabelSize::Small))
+                                },
+                            )))
+                        })
                         .child(
                             h_flex()
                                 .text_color(cx.theme().colors().text_muted)
@@ -3,7 +3,7 @@ use buffer_diff::BufferDiff;
 use collections::HashMap;
 use editor::display_map::{BlockPlacement, BlockProperties, BlockStyle};
 use editor::{Addon, Editor, EditorEvent, ExcerptRange, MultiBuffer, multibuffer_context_lines};
-use git::repository::{CommitDetails, CommitDiff, RepoPath, is_binary_content};
+use git::repository::{ApplyCommitOutcome, CommitDetails, CommitDiff, RepoPath, is_binary_content};
 use git::status::{FileStatus, StatusCode, TrackedStatus};
 use git::{
     BuildCommitPermalinkParams, GitHostingProviderRegistry, GitRemote, ParsedGitRemote,
@@ -19,6 +19,7 @@ use language::{
     Point, ReplicaId, Rope, TextBuffer,
 };
 use multi_buffer::PathKey;
+use notifications::status_toast::{StatusToast, ToastIcon};
 use project::{Project, WorktreeId, git_store::Repository};
 use std::{
     any::{Any, TypeId},
@@ -41,9 +42,20 @@ use workspace::{
 
 use crate::commit_tooltip::CommitAvatar;
 use crate::git_panel::GitPanel;
+use crate::project_diff::ProjectDiff;
 
 actions!(git, [ApplyCurrentStash, PopCurrentStash, DropCurrentStash,]);
 
+actions!(
+    git,
+    [
+        /// Applies the changes of the commit shown in the active commit view onto the current branch.
+        CherryPickCurrentCommit,
+        /// Creates a commit undoing the changes of the commit shown in the active commit view.
+        RevertCurrentCommit,
+    ]
+);
+
 pub fn init(cx: &mut App) {
     cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
         workspace.register_action(|workspace, _: &ApplyCurrentStash, window, cx| {
@@ -55,16 +67,78 @@ pub fn init(cx: &mut App) {
         workspace.register_action(|workspace, _: &PopCurrentStash, window, cx| {
             CommitView::pop_stash(workspace, window, cx);
         });
+        workspace.register_action(|workspace, _: &CherryPickCurrentCommit, window, cx| {
+            CommitView::apply_current_commit(workspace, CommitOperation::CherryPick, window, cx);
+        });
+        workspace.register_action(|workspace, _: &RevertCurrentCommit, window, cx| {
+            CommitView::apply_current_commit(workspace, CommitOperation::Revert, window, cx);
+        });
     })
     .detach();
 }
 
+/// An operation that applies an existing commit onto the current branch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommitOperation {
+    CherryPick,
+    Revert,
+}
+
+impl CommitOperation {
+    fn name(&self) -> &'static str {
+        match self {
+            CommitOperation::CherryPick => "cherry-pick",
+            CommitOperation::Revert => "revert",
+        }
+    }
+}
+
+/// Cherry-picks or reverts `sha` onto the current branch of `repository`.
+///
+/// When the operation stops on conflicts, the project diff is opened so they can be
+/// resolved and committed from the git panel.
+pub fn apply_commit(
+    operation: CommitOperation,
+    sha: SharedString,
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let outcome = repository.update(cx, |repository, _| match operation {
+        CommitOperation::CherryPick => repository.cherry_pick(sha.to_string()),
+        CommitOperation::Revert => repository.revert(sha.to_string()),
+    });
+
+    let workspace_handle = workspace.clone();
+    window
+        .spawn(cx, async move |cx| {
+            if outcome.await?? == ApplyCommitOutcome::Conflicted {
+                workspace.update_in(cx, |workspace, window, cx| {
+                    ProjectDiff::deploy_at(workspace, None, window, cx);
+                    let toast = StatusToast::new(
+                        format!("git {} stopped on conflicts", operation.name()),
+                        cx,
+                        |this, _| {
+                            this.icon(ToastIcon::new(IconName::Warning).color(Color::Warning))
+                                .dismiss_button(true)
+                        },
+                    );
+                    workspace.toggle_status_toast(toast, cx);
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_notify_err(workspace_handle, window, cx);
+}
+
 pub struct CommitView {
     commit: CommitDetails,
     editor: Entity<Editor>,
     stash: Option<usize>,
     multibuffer: Entity<MultiBuffer>,
     repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
     remote: Option<GitRemote>,
 }
 
@@ -136,6 +210,7 @@ impl CommitView {
                                 commit_diff,
                                 repo,
                                 project.clone(),
+                                workspace.weak_handle(),
                                 stash,
                                 window,
                                 cx,
@@ -166,6 +241,7 @@ impl CommitView {
         commit_diff: CommitDiff,
         repository: Entity<Repository>,
         project: Entity<Project>,
+        workspace: WeakEntity<Workspace>,
         stash: Option<usize>,
         window: &mut Window,
         cx: &mut Context<Self>,
@@ -392,6 +468,7 @@ impl CommitView {
             multibuffer,
             stash,
             repository,
+            workspace,
             remote,
         }
     }
@@ -617,22 +694,70 @@ impl CommitView {
                                     .children(commit_diff_stat),
                             ),
                     )
-                    .children(remote_info.map(|(provider_name, url)| {
-                        let icon = match provider_name.as_str() {
-                            "GitHub" => IconName::Github,
-                            _ => IconName::Link,
-                        };
-
-                        Button::new("view_on_provider", format!("View on {}", provider_name))
-                            .icon(icon)
-                            .icon_color(Color::Muted)
-                            .icon_size(IconSize::Small)
-                            .icon_position(IconPosition::Start)
-                            .on_click(move |_, _, cx| cx.open_url(&url))
-                    })),
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .when(self.stash.is_none(), |this| {
+                                this.child(
+                                    Button::new("cherry-pick-commit", "Cherry-pick")
+                                        .icon(IconName::GitBranchAlt)
+                                        .icon_color(Color::Muted)
+                                        .icon_size(IconSize::Small)
+                                        .icon_position(IconPosition::Start)
+                                        .tooltip(Tooltip::for_action_title(
+                                            "Apply This Commit to the Current Branch",
+                                            &CherryPickCurrentCommit,
+                                        ))
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.apply(CommitOperation::CherryPick, window, cx)
+                                        })),
+                                )
+                                .child(
+                                    Button::new("revert-commit", "Revert")
+                                        .icon(IconName::Undo)
+                                        .icon_color(Color::Muted)
+                                        .icon_size(IconSize::Small)
+                                        .icon_position(IconPosition::Start)
+                                        .tooltip(Tooltip::for_action_title(
+                                            "Undo This Commit on the Current Branch",
+                                            &RevertCurrentCommit,
+                                        ))
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.apply(CommitOperation::Revert, window, cx)
+                                        })),
+                                )
+                            })
+                            .children(remote_info.map(|(provider_name, url)| {
+                                let icon = match provider_name.as_str() {
+                                    "GitHub" => IconName::Github,
+                                    _ => IconName::Link,
+                                };
+
+                                Button::new(
+                                    "view_on_provider",
+                                    format!("View on {}", provider_name),
+                                )
+                                .icon(icon)
+                                .icon_color(Color::Muted)
+                                .icon_size(IconSize::Small)
+                                .icon_position(IconPosition::Start)
+                                .on_click(move |_, _, cx| cx.open_url(&url))
+                            })),
+                    ),
             )
     }
 
+    fn apply(&self, operation: CommitOperation, window: &mut Window, cx: &mut Context<Self>) {
+        apply_commit(
+            operation,
+            self.commit.sha.clone(),
+            self.repository.clone(),
+            self.workspace.clone(),
+            window,
+            cx,
+        );
+    }
+
     fn apply_stash(workspace: &mut Workspace, window: &mut Window, cx: &mut App) {
         Self::stash_action(
             workspace,
@@ -714,6 +839,29 @@ impl CommitView {
         );
     }
 
+    fn apply_current_commit(
+        workspace: &mut Workspace,
+        operation: CommitOperation,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(commit_view) = workspace.active_item_as::<CommitView>(cx) else {
+            return;
+        };
+        let commit_view = commit_view.read(cx);
+        if commit_view.stash.is_some() {
+            return;
+        }
+        apply_commit(
+            operation,
+            commit_view.commit.sha.clone(),
+            commit_view.repository.clone(),
+            workspace.weak_handle(),
+            window,
+            cx,
+        );
+    }
+
     fn stash_action<AsyncFn>(
         workspace: &mut Workspace,
         str_action: &str,
@@ -1067,6 +1215,7 @@ impl Item for CommitView {
                 commit: self.commit.clone(),
                 stash: self.stash,
                 repository: self.repository.clone(),
+                workspace: self.workspace.clone(),
                 remote: self.remote.clone(),
             }
         })))
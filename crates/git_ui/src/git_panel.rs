@@ -7,7 +7,7 @@ use crate::remote_output::{self, RemoteAction, SuccessMessage};
 use crate::{branch_picker, picker_prompt, render_remote_button};
 use crate::{
     file_history_view::FileHistoryView, git_panel_settings::GitPanelSettings, git_status_icon,
-    repository_selector::RepositorySelector,
+    merge_editor::MergeEditor, repository_selector::RepositorySelector,
 };
 use agent_settings::AgentSettings;
 use anyhow::Context as _;
@@ -23,9 +23,9 @@ use editor::{EditorStyle, RewrapOptions};
 use futures::StreamExt as _;
 use git::commit::ParsedCommitMessage;
 use git::repository::{
-    Branch, CommitDetails, CommitOptions, CommitSummary, DiffType, FetchOptions, GitCommitter,
-    PushOptions, Remote, RemoteCommandOutput, ResetMode, Upstream, UpstreamTracking,
-    UpstreamTrackingStatus, get_git_committer,
+    BisectTerm, Branch, CommitDetails, CommitOptions, CommitSummary, DiffType, FetchOptions,
+    GitCommitter, PushOptions, RebaseOperation, Remote, RemoteCommandOutput, ResetMode,
+    SubmoduleStatus, Upstream, UpstreamTracking, UpstreamTrackingStatus, get_git_committer,
 };
 use git::stash::GitStash;
 use git::status::StageStatus;
@@ -749,7 +749,10 @@ impl GitPanel {
                         _,
                         RepositoryEvent::StatusesChanged
                         | RepositoryEvent::BranchChanged
-                        | RepositoryEvent::MergeHeadsChanged,
+                        | RepositoryEvent::MergeHeadsChanged
+                        | RepositoryEvent::RebaseProgressChanged
+                        | RepositoryEvent::BisectStateChanged
+                        | RepositoryEvent::SubmodulesChanged,
                         true,
                     )
                     | GitStoreEvent::RepositoryAdded
@@ -1262,6 +1265,35 @@ impl GitPanel {
         });
     }
 
+    fn open_merge_editor(
+        &mut self,
+        _: &git::OpenMergeEditor,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        maybe!({
+            let entry = self.entries.get(self.selected_entry?)?.status_entry()?;
+            if !entry.status.is_conflicted() {
+                return None;
+            }
+            let active_repo = self.active_repository.clone()?;
+            MergeEditor::open(
+                active_repo,
+                entry.repo_path.clone(),
+                self.workspace.clone(),
+                window,
+                cx,
+            )
+            .detach_and_prompt_err(
+                "Failed to open merge editor",
+                window,
+                cx,
+                |_, _, _| None,
+            );
+            Some(())
+        });
+    }
+
     fn file_history(&mut self, _: &git::FileHistory, window: &mut Window, cx: &mut Context<Self>) {
         maybe!({
             let entry = self.entries.get(self.selected_entry?)?.status_entry()?;
@@ -1990,6 +2022,147 @@ impl GitPanel {
         .detach();
     }
 
+    pub(crate) fn rebase_operation(&mut self, operation: RebaseOperation, cx: &mut Context<Self>) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+        let action = match operation {
+            RebaseOperation::Continue => "rebase --continue",
+            RebaseOperation::Skip => "rebase --skip",
+            RebaseOperation::Abort => "rebase --abort",
+        };
+
+        cx.spawn(async move |this, cx| {
+            let result = active_repository
+                .update(cx, |repo, _| repo.rebase_operation(operation))
+                .await;
+            this.update(cx, |this, cx| {
+                match result {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => this.show_error_toast(action, e, cx),
+                    Err(e) => this.show_error_toast(action, e.into(), cx),
+                }
+                cx.notify();
+            })
+        })
+        .detach();
+    }
+
+    pub(crate) fn bisect_mark(&mut self, term: BisectTerm, cx: &mut Context<Self>) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+        let action = format!("bisect {}", term.as_str());
+
+        cx.spawn(async move |this, cx| {
+            let result = active_repository
+                .update(cx, |repo, _| repo.bisect_mark(term, None))
+                .await;
+            this.update(cx, |this, cx| {
+                match result {
+                    Ok(Ok(_)) => {}
+                    Ok(Err(e)) => this.show_error_toast(action, e, cx),
+                    Err(e) => this.show_error_toast(action, e.into(), cx),
+                }
+                cx.notify();
+            })
+        })
+        .detach();
+    }
+
+    pub(crate) fn bisect_reset(&mut self, cx: &mut Context<Self>) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+
+        cx.spawn(async move |this, cx| {
+            let result = active_repository
+                .update(cx, |repo, _| repo.bisect_reset())
+                .await;
+            this.update(cx, |this, cx| {
+                match result {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => this.show_error_toast("bisect reset", e, cx),
+                    Err(e) => this.show_error_toast("bisect reset", e.into(), cx),
+                }
+                cx.notify();
+            })
+        })
+        .detach();
+    }
+
+    pub(crate) fn submodule_init(&mut self, cx: &mut Context<Self>) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+
+        cx.spawn(async move |this, cx| {
+            let result = active_repository
+                .update(cx, |repo, _| repo.submodule_init(Vec::new()))
+                .await;
+            this.update(cx, |this, cx| match result {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => this.show_error_toast("submodule init", e, cx),
+                Err(e) => this.show_error_toast("submodule init", e.into(), cx),
+            })
+        })
+        .detach();
+    }
+
+    pub(crate) fn submodule_sync(&mut self, cx: &mut Context<Self>) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+
+        cx.spawn(async move |this, cx| {
+            let result = active_repository
+                .update(cx, |repo, _| repo.submodule_sync(Vec::new()))
+                .await;
+            this.update(cx, |this, cx| match result {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => this.show_error_toast("submodule sync", e, cx),
+                Err(e) => this.show_error_toast("submodule sync", e.into(), cx),
+            })
+        })
+        .detach();
+    }
+
+    /// Updates the submodules at `paths`, or all of them when `paths` is empty.
+    pub(crate) fn submodule_update(
+        &mut self,
+        paths: Vec<RepoPath>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_push_and_pull(cx) {
+            return;
+        }
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let action = RemoteAction::SubmoduleUpdate(match paths.as_slice() {
+            [path] => Some(path.as_unix_str().to_string().into()),
+            _ => None,
+        });
+        let askpass_delegate = self.askpass_delegate("git submodule update", window, cx);
+
+        cx.spawn(async move |this, cx| {
+            let update = repo.update(cx, |repo, _| repo.submodule_update(paths, askpass_delegate));
+            let remote_output = update.await?;
+
+            this.update(cx, |this, cx| match remote_output {
+                Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
+                Err(e) => {
+                    log::error!("Error while updating submodules {:?}", e);
+                    this.show_error_toast(action.name(), e, cx)
+                }
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn stash_apply(&mut self, _: &StashApply, _window: &mut Window, cx: &mut Context<Self>) {
         let Some(active_repository) = self.active_repository.clone() else {
             return;
@@ -2733,6 +2906,7 @@ impl GitPanel {
                     tool_choice: None,
                     stop: Vec::new(),
                     temperature,
+                    max_output_tokens: None,
                     thinking_allowed: false,
                     thinking_effort: None,
                 };
@@ -3103,6 +3277,59 @@ impl GitPanel {
         .detach_and_log_err(cx);
     }
 
+    pub(crate) fn push_tag(
+        &mut self,
+        tag_name: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_push_and_pull(cx) {
+            return;
+        }
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        telemetry::event!("Git Tag Pushed");
+        let remote = self.get_remote(true, true, window, cx);
+
+        cx.spawn_in(window, async move |this, cx| {
+            let remote = match remote.await {
+                Ok(Some(remote)) => remote,
+                Ok(None) => {
+                    return Ok(());
+                }
+                Err(e) => {
+                    log::error!("Failed to get current remote: {}", e);
+                    this.update(cx, |this, cx| this.show_error_toast("push", e, cx))
+                        .ok();
+                    return Ok(());
+                }
+            };
+
+            let askpass_delegate = this.update_in(cx, |this, window, cx| {
+                this.askpass_delegate(format!("git push {}", remote.name), window, cx)
+            })?;
+
+            let push = repo.update(cx, |repo, _| {
+                repo.push_tag(tag_name.clone(), remote.name.clone(), askpass_delegate)
+            });
+
+            let remote_output = push.await?;
+
+            let action = RemoteAction::PushTag(tag_name, remote);
+            this.update(cx, |this, cx| match remote_output {
+                Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
+                Err(e) => {
+                    log::error!("Error while pushing tag {:?}", e);
+                    this.show_error_toast(action.name(), e, cx)
+                }
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn create_pull_request(&self, window: &mut Window, cx: &mut Context<Self>) {
         let result = (|| -> anyhow::Result<()> {
             let repo = self
@@ -4479,6 +4706,305 @@ impl GitPanel {
             )
     }
 
+    fn render_rebase_in_progress(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let repo = self.active_repository.as_ref()?.read(cx);
+        let rebase = repo.rebase.as_ref()?;
+        let branch_name = rebase
+            .head_name
+            .as_ref()
+            .map(|name| name.strip_prefix("refs/heads/").unwrap_or(name).to_string())
+            .or_else(|| repo.branch.as_ref().map(|branch| branch.name().to_string()));
+        let label = match branch_name {
+            Some(branch_name) => {
+                format!("Rebasing {branch_name} ({}/{})", rebase.step, rebase.total)
+            }
+            None => format!("Rebasing ({}/{})", rebase.step, rebase.total),
+        };
+        let has_conflicts = self.conflicted_count > 0;
+
+        Some(
+            h_flex()
+                .py_1p5()
+                .px_2()
+                .gap_1p5()
+                .justify_between()
+                .border_t_1()
+                .border_color(cx.theme().colors().border.opacity(0.8))
+                .child(
+                    div()
+                        .flex_grow()
+                        .overflow_hidden()
+                        .child(Label::new(label).size(LabelSize::Small).truncate()),
+                )
+                .child(
+                    h_flex()
+                        .gap_0p5()
+                        .child(
+                            panel_button("Abort")
+                                .size(ButtonSize::Default)
+                                .tooltip(Tooltip::for_action_title(
+                                    "Abort Rebase",
+                                    &git::RebaseAbort,
+                                ))
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.rebase_operation(RebaseOperation::Abort, cx)
+                                })),
+                        )
+                        .child(
+                            panel_button("Skip")
+                                .size(ButtonSize::Default)
+                                .tooltip(Tooltip::for_action_title(
+                                    "Skip Current Commit",
+                                    &git::RebaseSkip,
+                                ))
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.rebase_operation(RebaseOperation::Skip, cx)
+                                })),
+                        )
+                        .child(
+                            panel_button("Continue")
+                                .size(ButtonSize::Default)
+                                .disabled(has_conflicts)
+                                .tooltip(Tooltip::for_action_title(
+                                    "Continue Rebase",
+                                    &git::RebaseContinue,
+                                ))
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.rebase_operation(RebaseOperation::Continue, cx)
+                                })),
+                        ),
+                ),
+        )
+    }
+
+    fn render_bisect_in_progress(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let active_repository = self.active_repository.as_ref()?;
+        let bisect = active_repository.read(cx).bisect.as_ref()?;
+
+        let container = h_flex()
+            .py_1p5()
+            .px_2()
+            .gap_1p5()
+            .justify_between()
+            .border_t_1()
+            .border_color(cx.theme().colors().border.opacity(0.8));
+        let reset_button = panel_button("Reset")
+            .size(ButtonSize::Default)
+            .tooltip(Tooltip::for_action_title("Reset Bisect", &git::BisectReset))
+            .on_click(cx.listener(|this, _, _, cx| this.bisect_reset(cx)));
+
+        if let Some(first_bad) = bisect.first_bad {
+            let workspace = self.workspace.clone();
+            let repo = active_repository.downgrade();
+            return Some(
+                container
+                    .child(
+                        div().flex_grow().overflow_hidden().child(
+                            Label::new(format!(
+                                "{} is the first bad commit",
+                                first_bad.display_short()
+                            ))
+                            .size(LabelSize::Small)
+                            .truncate(),
+                        ),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_0p5()
+                            .child(panel_button("View").size(ButtonSize::Default).on_click(
+                                move |_, window, cx| {
+                                    CommitView::open(
+                                        first_bad.to_string(),
+                                        repo.clone(),
+                                        workspace.clone(),
+                                        None,
+                                        None,
+                                        window,
+                                        cx,
+                                    );
+                                },
+                            ))
+                            .child(reset_button),
+                    )
+                    .into_any_element(),
+            );
+        }
+
+        let label = if bisect.bad.is_none() || bisect.good.is_empty() {
+            "Bisecting: mark a good and a bad commit".to_string()
+        } else {
+            let remaining = bisect.remaining;
+            let steps = bisect.steps_left();
+            format!(
+                "Bisecting: {remaining} {} left (roughly {steps} {})",
+                if remaining == 1 {
+                    "revision"
+                } else {
+                    "revisions"
+                },
+                if steps == 1 { "step" } else { "steps" },
+            )
+        };
+
+        Some(
+            container
+                .child(
+                    div()
+                        .flex_grow()
+                        .overflow_hidden()
+                        .child(Label::new(label).size(LabelSize::Small).truncate()),
+                )
+                .child(
+                    h_flex()
+                        .gap_0p5()
+                        .child(reset_button)
+                        .child(
+                            panel_button("Skip")
+                                .size(ButtonSize::Default)
+                                .tooltip(Tooltip::for_action_title(
+                                    "Skip Current Commit",
+                                    &git::BisectSkip,
+                                ))
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.bisect_mark(BisectTerm::Skip, cx)
+                                })),
+                        )
+                        .child(
+                            panel_button("Bad")
+                                .size(ButtonSize::Default)
+                                .tooltip(Tooltip::for_action_title(
+                                    "Mark Current Commit as Bad",
+                                    &git::BisectBad,
+                                ))
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.bisect_mark(BisectTerm::Bad, cx)
+                                })),
+                        )
+                        .child(
+                            panel_button("Good")
+                                .size(ButtonSize::Default)
+                                .tooltip(Tooltip::for_action_title(
+                                    "Mark Current Commit as Good",
+                                    &git::BisectGood,
+                                ))
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.bisect_mark(BisectTerm::Good, cx)
+                                })),
+                        ),
+                )
+                .into_any_element(),
+        )
+    }
+
+    fn render_submodules(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let active_repository = self.active_repository.as_ref()?;
+        let submodules = active_repository
+            .read(cx)
+            .submodules
+            .iter()
+            .filter(|submodule| submodule.status() != SubmoduleStatus::UpToDate)
+            .cloned()
+            .collect::<Vec<_>>();
+        if submodules.is_empty() {
+            return None;
+        }
+
+        let header = h_flex()
+            .gap_1p5()
+            .justify_between()
+            .child(
+                Label::new("Submodules")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                panel_button("Update All")
+                    .size(ButtonSize::Default)
+                    .tooltip(Tooltip::for_action_title(
+                        "Update All Submodules",
+                        &git::SubmoduleUpdate,
+                    ))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.submodule_update(Vec::new(), window, cx)
+                    })),
+            );
+
+        Some(
+            v_flex()
+                .py_1p5()
+                .px_2()
+                .gap_1()
+                .border_t_1()
+                .border_color(cx.theme().colors().border.opacity(0.8))
+                .child(header)
+                .children(submodules.into_iter().enumerate().map(|(ix, submodule)| {
+                    let status = submodule.status();
+                    let shas = match (submodule.recorded_sha, submodule.checked_out_sha) {
+                        (Some(recorded), Some(checked_out)) if recorded != checked_out => format!(
+                            "{} → {}",
+                            recorded.display_short(),
+                            checked_out.display_short()
+                        ),
+                        (Some(recorded), _) => recorded.display_short(),
+                        (None, _) => String::new(),
+                    };
+                    let path = submodule.path.clone();
+
+                    h_flex()
+                        .id(("submodule", ix))
+                        .gap_1p5()
+                        .justify_between()
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .flex_grow()
+                                .overflow_hidden()
+                                .child(
+                                    Icon::new(IconName::Box)
+                                        .size(IconSize::Small)
+                                        .color(Color::Muted),
+                                )
+                                .child(
+                                    Label::new(submodule.path.as_unix_str().to_string())
+                                        .size(LabelSize::Small)
+                                        .truncate(),
+                                )
+                                .child(Label::new(status.label()).size(LabelSize::Small).color(
+                                    match status {
+                                        SubmoduleStatus::Uninitialized => Color::Muted,
+                                        SubmoduleStatus::NewCommits => Color::Warning,
+                                        SubmoduleStatus::Modified => Color::Modified,
+                                        SubmoduleStatus::UpToDate => Color::Default,
+                                    },
+                                )),
+                        )
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .child(
+                                    Label::new(shas)
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted)
+                                        .buffer_font(cx),
+                                )
+                                .when(status != SubmoduleStatus::Modified, |this| {
+                                    this.child(
+                                        panel_button("Update").size(ButtonSize::Default).on_click(
+                                            cx.listener(move |this, _, window, cx| {
+                                                this.submodule_update(
+                                                    vec![path.clone()],
+                                                    window,
+                                                    cx,
+                                                )
+                                            }),
+                                        ),
+                                    )
+                                }),
+                        )
+                })),
+        )
+    }
+
     fn render_previous_commit(
         &self,
         window: &mut Window,
@@ -4928,6 +5454,7 @@ impl GitPanel {
         };
         let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
             let is_created = entry.status.is_created();
+            let is_conflicted = entry.status.is_conflicted();
             context_menu
                 .context(self.focus_handle.clone())
                 .action(stage_title, ToggleStaged.boxed_clone())
@@ -4940,6 +5467,9 @@ impl GitPanel {
                 .separator()
                 .action("Open Diff", menu::Confirm.boxed_clone())
                 .action("Open File", menu::SecondaryConfirm.boxed_clone())
+                .when(is_conflicted, |menu| {
+                    menu.action("Open Merge Editor", git::OpenMergeEditor.boxed_clone())
+                })
                 .separator()
                 .action_disabled_when(is_created, "View File History", Box::new(git::FileHistory))
         });
@@ -5571,6 +6101,7 @@ impl Render for GitPanel {
             .on_action(cx.listener(Self::open_diff))
             .on_action(cx.listener(Self::open_file))
             .on_action(cx.listener(Self::file_history))
+            .on_action(cx.listener(Self::open_merge_editor))
             .on_action(cx.listener(Self::focus_changes_list))
             .on_action(cx.listener(Self::focus_editor))
             .on_action(cx.listener(Self::expand_commit_editor))
@@ -5596,6 +6127,9 @@ impl Render for GitPanel {
                         }
                     })
                     .children(self.render_footer(window, cx))
+                    .children(self.render_rebase_in_progress(cx))
+                    .children(self.render_bisect_in_progress(cx))
+                    .children(self.render_submodules(cx))
                     .when(self.amend_pending, |this| {
                         this.child(self.render_pending_amend(cx))
                     })
@@ -12,7 +12,9 @@ mod blame_ui;
 pub mod clone;
 
 use git::{
-    repository::{Branch, Upstream, UpstreamTracking, UpstreamTrackingStatus},
+    repository::{
+        BisectTerm, Branch, RebaseOperation, Upstream, UpstreamTracking, UpstreamTrackingStatus,
+    },
     status::{FileStatus, StatusCode, UnmergedStatus, UnmergedStatusCode},
 };
 use gpui::{
@@ -28,6 +30,7 @@ use zed_actions;
 use crate::{git_panel::GitPanel, text_diff_view::TextDiffView};
 
 mod askpass_modal;
+mod bisect;
 pub mod branch_picker;
 mod commit_modal;
 pub mod commit_tooltip;
@@ -38,6 +41,8 @@ pub mod file_history_view;
 pub mod git_panel;
 mod git_panel_settings;
 pub mod git_picker;
+mod interactive_rebase;
+pub mod merge_editor;
 pub mod multi_diff_view;
 pub mod picker_prompt;
 pub mod project_diff;
@@ -135,6 +140,89 @@ pub fn init(cx: &mut App) {
                 });
             });
         }
+        interactive_rebase::register(workspace);
+        workspace.register_action(|workspace, _: &git::RebaseContinue, _, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.rebase_operation(RebaseOperation::Continue, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::RebaseSkip, _, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.rebase_operation(RebaseOperation::Skip, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::RebaseAbort, _, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.rebase_operation(RebaseOperation::Abort, cx);
+            });
+        });
+        bisect::register(workspace);
+        merge_editor::register(workspace);
+        workspace.register_action(|workspace, _: &git::BisectGood, _, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.bisect_mark(BisectTerm::Good, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::BisectBad, _, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.bisect_mark(BisectTerm::Bad, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::BisectSkip, _, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.bisect_mark(BisectTerm::Skip, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::BisectReset, _, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.bisect_reset(cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::SubmoduleInit, _, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.submodule_init(cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::SubmoduleUpdate, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.submodule_update(Vec::new(), window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::SubmoduleSync, _, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.submodule_sync(cx);
+            });
+        });
         workspace.register_action(|workspace, action: &git::StashAll, window, cx| {
             let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
                 return;
@@ -206,6 +294,9 @@ pub fn init(cx: &mut App) {
         workspace.register_action(|workspace, _: &git::RenameBranch, window, cx| {
             rename_current_branch(workspace, window, cx);
         });
+        workspace.register_action(|workspace, action: &git::CreateTag, window, cx| {
+            create_tag(workspace, action.target.clone(), window, cx);
+        });
         workspace.register_action(
             |workspace, action: &DiffClipboardWithSelectionData, window, cx| {
                 if let Some(task) = TextDiffView::open(action, workspace, window, cx) {
@@ -426,6 +517,151 @@ fn rename_current_branch(
     });
 }
 
+struct CreateTagModal {
+    target: Option<String>,
+    name_editor: Entity<Editor>,
+    message_editor: Entity<Editor>,
+    repo: Entity<Repository>,
+}
+
+impl CreateTagModal {
+    fn new(
+        target: Option<String>,
+        repo: Entity<Repository>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let name_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Tag name", window, cx);
+            editor
+        });
+        let message_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Message (creates an annotated tag)", window, cx);
+            editor
+        });
+        Self {
+            target,
+            name_editor,
+            message_editor,
+            repo,
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let name = self.name_editor.read(cx).text(cx).trim().replace(' ', "-");
+        if name.is_empty() {
+            cx.emit(DismissEvent);
+            return;
+        }
+        let message = self.message_editor.read(cx).text(cx);
+        let message = (!message.trim().is_empty()).then_some(message);
+
+        let repo = self.repo.clone();
+        let target = self.target.clone();
+        cx.spawn(async move |_, cx| {
+            match repo
+                .update(cx, |repo, _| repo.create_tag(name, target, message))
+                .await
+            {
+                Ok(Ok(_)) => Ok(()),
+                Ok(Err(error)) => Err(error),
+                Err(_) => Err(anyhow!("Operation was canceled")),
+            }
+        })
+        .detach_and_prompt_err("Failed to create tag", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn toggle_editor_focus(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        // Both editors are single line, so `Tab` and `Backtab` propagate up to the modal.
+        if self.name_editor.focus_handle(cx).is_focused(window) {
+            self.message_editor.focus_handle(cx).focus(window, cx);
+        } else {
+            self.name_editor.focus_handle(cx).focus(window, cx);
+        }
+    }
+}
+
+impl EventEmitter<DismissEvent> for CreateTagModal {}
+impl ModalView for CreateTagModal {}
+impl Focusable for CreateTagModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.name_editor.focus_handle(cx)
+    }
+}
+
+impl Render for CreateTagModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let title = match &self.target {
+            Some(target) => format!(
+                "Create Tag on {}",
+                target.get(..git::SHORT_SHA_LENGTH).unwrap_or(target)
+            ),
+            None => "Create Tag".to_string(),
+        };
+
+        v_flex()
+            .key_context("CreateTagModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(|this, _: &editor::actions::Tab, window, cx| {
+                this.toggle_editor_focus(window, cx)
+            }))
+            .on_action(
+                cx.listener(|this, _: &editor::actions::Backtab, window, cx| {
+                    this.toggle_editor_focus(window, cx)
+                }),
+            )
+            .elevation_2(cx)
+            .w(rems(34.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::Tag).size(IconSize::XSmall))
+                    .child(Headline::new(title).size(HeadlineSize::XSmall)),
+            )
+            .child(
+                v_flex()
+                    .px_3()
+                    .pb_3()
+                    .w_full()
+                    .gap_2()
+                    .child(self.name_editor.clone())
+                    .child(self.message_editor.clone()),
+            )
+    }
+}
+
+fn create_tag(
+    workspace: &mut Workspace,
+    target: Option<String>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+        return;
+    };
+    let Some(repo) = panel.read(cx).active_repository.clone() else {
+        return;
+    };
+
+    workspace.toggle_modal(window, cx, |window, cx| {
+        CreateTagModal::new(target, repo, window, cx)
+    });
+}
+
 fn render_remote_button(
     id: impl Into<SharedString>,
     branch: &Branch,
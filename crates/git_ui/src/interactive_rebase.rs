@@ -0,0 +1,429 @@
+use std::ops::Range;
+
+use anyhow::anyhow;
+use editor::{Editor, EditorEvent};
+use git::repository::{CommitDetails, RebaseAction, RebaseTodoEntry};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, ScrollStrategy,
+    SharedString, Subscription, UniformListScrollHandle, Window, actions, uniform_list,
+};
+use menu::{Cancel, Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use project::git_store::Repository;
+use ui::{KeyBinding, ListItem, ListItemSpacing, prelude::*};
+use workspace::{ModalView, Workspace, notifications::DetachAndPromptErr};
+
+use crate::resolve_active_repository;
+
+actions!(
+    interactive_rebase,
+    [
+        /// Keeps the selected commit as it is.
+        Pick,
+        /// Keeps the selected commit and edits its message.
+        Reword,
+        /// Melds the selected commit into the previous one, keeping both messages.
+        Squash,
+        /// Melds the selected commit into the previous one, discarding its message.
+        Fixup,
+        /// Removes the selected commit from the history.
+        DropCommit,
+        /// Moves the selected commit before the previous one.
+        MoveUp,
+        /// Moves the selected commit after the next one.
+        MoveDown,
+        /// Replays the commits as planned.
+        StartRebase,
+    ]
+);
+
+pub(crate) fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, action: &git::InteractiveRebase, window, cx| {
+        open(workspace, action.base.clone(), window, cx);
+    });
+}
+
+fn open(
+    workspace: &mut Workspace,
+    base: Option<String>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(repo) = resolve_active_repository(workspace, cx) else {
+        return;
+    };
+    let upstream = repo
+        .read(cx)
+        .branch
+        .as_ref()
+        .and_then(|branch| branch.upstream.as_ref())
+        .map(|upstream| upstream.ref_name.to_string());
+
+    cx.spawn_in(window, async move |workspace, cx| {
+        let base = match base.or(upstream) {
+            Some(base) => base,
+            None => repo
+                .update(cx, |repo, _| repo.default_branch(true))
+                .await??
+                .map(|branch| branch.to_string())
+                .ok_or_else(|| anyhow!("Could not determine the commit to rebase onto"))?,
+        };
+        let commits = repo
+            .update(cx, |repo, _| repo.rebase_commits(base.clone()))
+            .await??;
+        if commits.is_empty() {
+            return Err(anyhow!("There are no commits on top of {base} to rebase"));
+        }
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_modal(window, cx, |window, cx| {
+                InteractiveRebaseModal::new(repo, base.into(), commits, window, cx)
+            });
+        })
+    })
+    .detach_and_prompt_err(
+        "Failed to start interactive rebase",
+        window,
+        cx,
+        |_, _, _| None,
+    );
+}
+
+struct RebaseEntry {
+    commit: CommitDetails,
+    action: RebaseAction,
+    message: SharedString,
+}
+
+pub struct InteractiveRebaseModal {
+    repo: Entity<Repository>,
+    base: SharedString,
+    entries: Vec<RebaseEntry>,
+    selected_ix: usize,
+    message_editor: Entity<Editor>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    _message_editor_subscription: Subscription,
+}
+
+impl InteractiveRebaseModal {
+    fn new(
+        repo: Entity<Repository>,
+        base: SharedString,
+        commits: Vec<CommitDetails>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let entries = commits
+            .into_iter()
+            .map(|commit| RebaseEntry {
+                message: commit.message.trim_end().to_string().into(),
+                action: RebaseAction::Pick,
+                commit,
+            })
+            .collect::<Vec<_>>();
+        let message_editor = cx.new(|cx| {
+            let mut editor = Editor::auto_height(4, 8, window, cx);
+            editor.set_text(entries[0].message.clone(), window, cx);
+            editor
+        });
+        let subscription = cx.subscribe(&message_editor, |this, _, event, cx| {
+            if let EditorEvent::BufferEdited = event {
+                this.message_edited(cx);
+            }
+        });
+        let focus_handle = cx.focus_handle();
+        focus_handle.focus(window, cx);
+
+        Self {
+            repo,
+            base,
+            entries,
+            selected_ix: 0,
+            message_editor,
+            focus_handle,
+            scroll_handle: UniformListScrollHandle::new(),
+            _message_editor_subscription: subscription,
+        }
+    }
+
+    fn message_edited(&mut self, cx: &mut Context<Self>) {
+        let message = self.message_editor.read(cx).text(cx);
+        let entry = &mut self.entries[self.selected_ix];
+        if message == entry.message.as_ref() {
+            return;
+        }
+        entry.message = message.into();
+        if entry.action == RebaseAction::Pick {
+            entry.action = RebaseAction::Reword;
+        }
+        cx.notify();
+    }
+
+    fn select(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.selected_ix = ix;
+        let message = self.entries[ix].message.clone();
+        self.message_editor
+            .update(cx, |editor, cx| editor.set_text(message, window, cx));
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &SelectNext, window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_ix + 1 < self.entries.len() {
+            self.select(self.selected_ix + 1, window, cx);
+        }
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_ix > 0 {
+            self.select(self.selected_ix - 1, window, cx);
+        }
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, window: &mut Window, cx: &mut Context<Self>) {
+        self.select(0, window, cx);
+    }
+
+    fn select_last(&mut self, _: &SelectLast, window: &mut Window, cx: &mut Context<Self>) {
+        self.select(self.entries.len() - 1, window, cx);
+    }
+
+    fn set_action(&mut self, action: RebaseAction, cx: &mut Context<Self>) {
+        self.entries[self.selected_ix].action = action;
+        cx.notify();
+    }
+
+    fn pick(&mut self, _: &Pick, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseAction::Pick, cx);
+    }
+
+    fn reword(&mut self, _: &Reword, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseAction::Reword, cx);
+        window.focus(&self.message_editor.focus_handle(cx), cx);
+    }
+
+    fn squash(&mut self, _: &Squash, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseAction::Squash, cx);
+    }
+
+    fn fixup(&mut self, _: &Fixup, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseAction::Fixup, cx);
+    }
+
+    fn drop_commit(&mut self, _: &DropCommit, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseAction::Drop, cx);
+    }
+
+    fn move_up(&mut self, _: &MoveUp, window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_ix > 0 {
+            self.entries.swap(self.selected_ix, self.selected_ix - 1);
+            self.select(self.selected_ix - 1, window, cx);
+        }
+    }
+
+    fn move_down(&mut self, _: &MoveDown, window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_ix + 1 < self.entries.len() {
+            self.entries.swap(self.selected_ix, self.selected_ix + 1);
+            self.select(self.selected_ix + 1, window, cx);
+        }
+    }
+
+    fn focus_list(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        window.focus(&self.focus_handle, cx);
+        cx.notify();
+    }
+
+    fn cancel(&mut self, _: &Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        window.focus(&self.message_editor.focus_handle(cx), cx);
+    }
+
+    fn start_rebase(&mut self, _: &StartRebase, window: &mut Window, cx: &mut Context<Self>) {
+        let todo = self
+            .entries
+            .iter()
+            .map(|entry| RebaseTodoEntry {
+                sha: entry.commit.sha.clone(),
+                action: entry.action,
+                message: (entry.action == RebaseAction::Reword).then(|| entry.message.clone()),
+            })
+            .collect::<Vec<_>>();
+
+        let repo = self.repo.clone();
+        let base = self.base.to_string();
+        cx.spawn(async move |_, cx| {
+            match repo
+                .update(cx, |repo, _| repo.rebase_interactive(base, todo))
+                .await
+            {
+                Ok(Ok(())) => Ok(()),
+                Ok(Err(error)) => Err(error),
+                Err(_) => Err(anyhow!("Operation was canceled")),
+            }
+        })
+        .detach_and_prompt_err("Failed to rebase", window, cx, |_, _, _| None);
+        cx.emit(DismissEvent);
+    }
+
+    fn render_entries(&self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<AnyElement> {
+        range
+            .map(|ix| {
+                let entry = &self.entries[ix];
+                let (action_color, subject_color) = match entry.action {
+                    RebaseAction::Pick => (Color::Muted, Color::Default),
+                    RebaseAction::Reword => (Color::Accent, Color::Default),
+                    RebaseAction::Squash | RebaseAction::Fixup => (Color::Warning, Color::Muted),
+                    RebaseAction::Drop => (Color::Error, Color::Disabled),
+                };
+                let subject = entry.message.lines().next().unwrap_or_default().to_string();
+                let short_sha = entry.commit.sha.get(..7).unwrap_or(&entry.commit.sha);
+
+                ListItem::new(ix)
+                    .spacing(ListItemSpacing::Dense)
+                    .inset(true)
+                    .toggle_state(ix == self.selected_ix)
+                    .on_click(cx.listener(move |this, _, window, cx| this.select(ix, window, cx)))
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .gap_2()
+                            .when(entry.action.melds_into_previous(), |this| this.pl_4())
+                            .child(
+                                div().w(rems(3.5)).child(
+                                    Label::new(entry.action.as_str())
+                                        .size(LabelSize::Small)
+                                        .color(action_color),
+                                ),
+                            )
+                            .child(
+                                Label::new(short_sha.to_string())
+                                    .size(LabelSize::Small)
+                                    .buffer_font(cx)
+                                    .color(Color::Muted),
+                            )
+                            .child(
+                                Label::new(subject)
+                                    .color(subject_color)
+                                    .when(entry.action == RebaseAction::Drop, |label| {
+                                        label.strikethrough()
+                                    })
+                                    .truncate(),
+                            ),
+                    )
+                    .end_slot(
+                        Label::new(entry.commit.author_name.clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .into_any_element()
+            })
+            .collect()
+    }
+
+    fn render_footer(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle.clone();
+        h_flex()
+            .w_full()
+            .p_1p5()
+            .gap_0p5()
+            .justify_end()
+            .border_t_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                Button::new("cancel-rebase", "Cancel")
+                    .key_binding(
+                        KeyBinding::for_action_in(&Cancel, &focus_handle, cx)
+                            .map(|kb| kb.size(rems_from_px(12.))),
+                    )
+                    .on_click(cx.listener(|_, _, _, cx| cx.emit(DismissEvent))),
+            )
+            .child(
+                Button::new("start-rebase", "Rebase")
+                    .key_binding(
+                        KeyBinding::for_action_in(&StartRebase, &focus_handle, cx)
+                            .map(|kb| kb.size(rems_from_px(12.))),
+                    )
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.start_rebase(&StartRebase, window, cx)
+                    })),
+            )
+    }
+}
+
+impl EventEmitter<DismissEvent> for InteractiveRebaseModal {}
+impl ModalView for InteractiveRebaseModal {}
+impl Focusable for InteractiveRebaseModal {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for InteractiveRebaseModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("InteractiveRebaseModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::start_rebase))
+            .elevation_2(cx)
+            .w(rems(44.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::GitBranch).size(IconSize::XSmall))
+                    .child(
+                        Headline::new(format!("Rebase onto {}", self.base))
+                            .size(HeadlineSize::XSmall),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .id("interactive-rebase-list")
+                    .key_context("InteractiveRebaseList")
+                    .track_focus(&self.focus_handle)
+                    .on_action(cx.listener(Self::select_next))
+                    .on_action(cx.listener(Self::select_previous))
+                    .on_action(cx.listener(Self::select_first))
+                    .on_action(cx.listener(Self::select_last))
+                    .on_action(cx.listener(Self::pick))
+                    .on_action(cx.listener(Self::reword))
+                    .on_action(cx.listener(Self::squash))
+                    .on_action(cx.listener(Self::fixup))
+                    .on_action(cx.listener(Self::drop_commit))
+                    .on_action(cx.listener(Self::move_up))
+                    .on_action(cx.listener(Self::move_down))
+                    .on_action(cx.listener(Self::confirm))
+                    .h(rems(18.))
+                    .child(
+                        uniform_list(
+                            "interactive-rebase-entries",
+                            self.entries.len(),
+                            cx.processor(|this, range: Range<usize>, _, cx| {
+                                this.render_entries(range, cx)
+                            }),
+                        )
+                        .track_scroll(&self.scroll_handle)
+                        .size_full()
+                        .p_1(),
+                    ),
+            )
+            .child(
+                div()
+                    .on_action(cx.listener(Self::focus_list))
+                    .px_3()
+                    .py_2()
+                    .w_full()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(self.message_editor.clone()),
+            )
+            .child(self.render_footer(cx))
+    }
+}
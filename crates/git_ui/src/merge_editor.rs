@@ -0,0 +1,926 @@
+//! MergeEditor shows both sides of a conflicted file next to their merge base, above an
+//! editor for the merge result.
+
+use anyhow::{Context as _, Result, anyhow};
+use buffer_diff::BufferDiff;
+use editor::{Editor, EditorEvent, EditorSettings, SelectionEffects, SplittableEditor};
+use git::repository::{MergeStages, RepoPath};
+use gpui::{
+    Action, AnyElement, App, AppContext as _, AsyncWindowContext, Context, Entity, EventEmitter,
+    FocusHandle, Focusable, IntoElement, Render, Subscription, Task, WeakEntity, Window, actions,
+};
+use language::{Buffer, Capability, LanguageRegistry, OffsetRangeExt as _, Point, ToPoint as _};
+use multi_buffer::{MultiBuffer, PathKey};
+use project::{
+    ConflictRegion, ConflictSet, ConflictSetUpdate, Project, ProjectPath, git_store::Repository,
+};
+use settings::Settings as _;
+use std::{
+    any::{Any, TypeId},
+    ops::Range,
+    sync::Arc,
+};
+use ui::{Divider, Tooltip, prelude::*};
+use workspace::{
+    Item, ItemNavHistory, ToolbarItemLocation, Workspace,
+    item::{BreadcrumbText, ItemEvent, SaveOptions, TabContentParams},
+    notifications::DetachAndPromptErr,
+    searchable::SearchableItemHandle,
+};
+
+actions!(
+    merge_editor,
+    [
+        /// Moves the cursor to the next conflict in the result.
+        NextConflict,
+        /// Moves the cursor to the previous conflict in the result.
+        PreviousConflict,
+        /// Resolves the conflict at the cursor with our version.
+        AcceptOurs,
+        /// Resolves the conflict at the cursor with their version.
+        AcceptTheirs,
+        /// Resolves the conflict at the cursor with the version of the merge base.
+        AcceptBase,
+        /// Resolves the conflict at the cursor with our version followed by theirs.
+        AcceptOursThenTheirs,
+        /// Resolves the conflict at the cursor with their version followed by ours.
+        AcceptTheirsThenOurs,
+        /// Saves the result and stages the file.
+        MarkResolved,
+    ]
+);
+
+pub(crate) fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::OpenMergeEditor, window, cx| {
+        let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+            return;
+        };
+        let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+            return;
+        };
+        let Some(file) = buffer.read(cx).file() else {
+            return;
+        };
+        let project_path = ProjectPath {
+            worktree_id: file.worktree_id(cx),
+            path: file.path().clone(),
+        };
+        let Some((repository, repo_path)) = workspace
+            .project()
+            .read(cx)
+            .git_store()
+            .read(cx)
+            .repository_and_path_for_project_path(&project_path, cx)
+        else {
+            return;
+        };
+        MergeEditor::open(repository, repo_path, workspace.weak_handle(), window, cx)
+            .detach_and_prompt_err("Failed to open merge editor", window, cx, |_, _, _| None);
+    });
+}
+
+pub struct MergeEditor {
+    repo_path: RepoPath,
+    repository: WeakEntity<Repository>,
+    project: Entity<Project>,
+    buffer: Entity<Buffer>,
+    conflict_set: Entity<ConflictSet>,
+    ours_editor: Entity<SplittableEditor>,
+    theirs_editor: Entity<SplittableEditor>,
+    result_editor: Entity<Editor>,
+    _subscriptions: Vec<Subscription>,
+}
+
+/// One side of the merge, shown as a diff against the merge base.
+struct MergeSide {
+    buffer: Entity<Buffer>,
+    diff: Entity<BufferDiff>,
+}
+
+impl MergeEditor {
+    pub fn open(
+        repository: Entity<Repository>,
+        repo_path: RepoPath,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let project_path = repository
+            .read(cx)
+            .repo_path_to_project_path(&repo_path, cx);
+        let stages = repository.update(cx, |repository, _| {
+            repository.load_merge_stages(repo_path.clone())
+        });
+        window.spawn(cx, async move |cx| {
+            let project_path = project_path.context("file is outside of the project")?;
+            let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
+            let buffer = project
+                .update(cx, |project, cx| project.open_buffer(project_path, cx))
+                .await?;
+            let stages = stages.await??;
+            let MergeStages { base, ours, theirs } = stages;
+            let base = base.unwrap_or_default();
+            let language = buffer.read_with(cx, |buffer, _| buffer.language().cloned());
+            let languages = project.read_with(cx, |project, _| project.languages().clone());
+            let ours = build_merge_side(
+                ours.unwrap_or_default(),
+                &base,
+                language.clone(),
+                languages.clone(),
+                cx,
+            )
+            .await;
+            let theirs =
+                build_merge_side(theirs.unwrap_or_default(), &base, language, languages, cx).await;
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let existing = workspace
+                    .active_pane()
+                    .read(cx)
+                    .items_of_type::<Self>()
+                    .find(|item| item.read(cx).buffer == buffer);
+                if let Some(existing) = existing {
+                    workspace.activate_item(&existing, true, true, window, cx);
+                    return existing;
+                }
+
+                let workspace_entity = cx.entity();
+                let merge_editor = cx.new(|cx| {
+                    Self::new(
+                        repository.downgrade(),
+                        repo_path,
+                        buffer,
+                        ours,
+                        theirs,
+                        project,
+                        workspace_entity,
+                        window,
+                        cx,
+                    )
+                });
+                workspace.active_pane().update(cx, |pane, cx| {
+                    pane.add_item(Box::new(merge_editor.clone()), true, true, None, window, cx);
+                });
+                merge_editor
+            })
+        })
+    }
+
+    fn new(
+        repository: WeakEntity<Repository>,
+        repo_path: RepoPath,
+        buffer: Entity<Buffer>,
+        ours: MergeSide,
+        theirs: MergeSide,
+        project: Entity<Project>,
+        workspace: Entity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let ours_editor = new_side_editor(ours, project.clone(), workspace.clone(), window, cx);
+        let theirs_editor = new_side_editor(theirs, project.clone(), workspace, window, cx);
+        let result_editor =
+            cx.new(|cx| Editor::for_buffer(buffer.clone(), Some(project.clone()), window, cx));
+        let conflict_set = project
+            .read(cx)
+            .git_store()
+            .clone()
+            .update(cx, |git_store, cx| {
+                git_store.open_conflict_set(buffer.clone(), cx)
+            });
+
+        let subscriptions = vec![
+            cx.subscribe(&result_editor, |_, _, event: &EditorEvent, cx| {
+                cx.emit(event.clone())
+            }),
+            cx.subscribe(&conflict_set, |_, _, _: &ConflictSetUpdate, cx| cx.notify()),
+        ];
+
+        Self {
+            repo_path,
+            repository,
+            project,
+            buffer,
+            conflict_set,
+            ours_editor,
+            theirs_editor,
+            result_editor,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn cursor_position(&self, cx: &mut App) -> Point {
+        self.result_editor.update(cx, |editor, cx| {
+            editor
+                .selections
+                .newest::<Point>(&editor.display_snapshot(cx))
+                .head()
+        })
+    }
+
+    /// Returns the conflict containing the cursor, or the first one after it.
+    fn conflict_at_cursor(&self, cx: &mut App) -> Option<ConflictRegion> {
+        let cursor = self.cursor_position(cx);
+        let buffer = self.buffer.read(cx).snapshot();
+        self.conflict_set
+            .read(cx)
+            .snapshot()
+            .conflicts
+            .iter()
+            .find(|conflict| conflict.range.to_point(&buffer).end >= cursor)
+            .cloned()
+    }
+
+    fn select_conflict(&mut self, reverse: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let cursor = self.cursor_position(cx);
+        let buffer = self.buffer.read(cx).snapshot();
+        let starts = self
+            .conflict_set
+            .read(cx)
+            .snapshot()
+            .conflicts
+            .iter()
+            .map(|conflict| conflict.range.start.to_point(&buffer))
+            .collect::<Vec<_>>();
+        let target = if reverse {
+            starts
+                .iter()
+                .rev()
+                .find(|start| **start < cursor)
+                .or(starts.last())
+        } else {
+            starts
+                .iter()
+                .find(|start| **start > cursor)
+                .or(starts.first())
+        };
+        let Some(&target) = target else {
+            return;
+        };
+        self.result_editor.update(cx, |editor, cx| {
+            editor.change_selections(SelectionEffects::default(), window, cx, |selections| {
+                selections.select_ranges([target..target])
+            });
+        });
+        window.focus(&self.result_editor.focus_handle(cx), cx);
+    }
+
+    fn next_conflict(&mut self, _: &NextConflict, window: &mut Window, cx: &mut Context<Self>) {
+        self.select_conflict(false, window, cx);
+    }
+
+    fn previous_conflict(
+        &mut self,
+        _: &PreviousConflict,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_conflict(true, window, cx);
+    }
+
+    /// Replaces the conflict at the cursor with the given parts of it, in order.
+    fn accept(
+        &mut self,
+        parts: impl FnOnce(&ConflictRegion) -> Option<Vec<Range<language::Anchor>>>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(conflict) = self.conflict_at_cursor(cx) else {
+            return;
+        };
+        let Some(parts) = parts(&conflict) else {
+            return;
+        };
+        let buffer = self.buffer.read(cx).snapshot();
+        let is_sorted = parts
+            .windows(2)
+            .all(|pair| pair[0].end.cmp(&pair[1].start, &buffer).is_le());
+        if is_sorted {
+            conflict.resolve(self.buffer.clone(), &parts, cx);
+        } else {
+            let text = parts
+                .iter()
+                .flat_map(|range| buffer.text_for_range(range.clone()))
+                .collect::<String>();
+            self.buffer.update(cx, |buffer, cx| {
+                buffer.edit([(conflict.range.clone(), text)], None, cx);
+            });
+        }
+    }
+
+    fn accept_ours(&mut self, _: &AcceptOurs, _: &mut Window, cx: &mut Context<Self>) {
+        self.accept(|conflict| Some(vec![conflict.ours.clone()]), cx);
+    }
+
+    fn accept_theirs(&mut self, _: &AcceptTheirs, _: &mut Window, cx: &mut Context<Self>) {
+        self.accept(|conflict| Some(vec![conflict.theirs.clone()]), cx);
+    }
+
+    fn accept_base(&mut self, _: &AcceptBase, _: &mut Window, cx: &mut Context<Self>) {
+        self.accept(|conflict| Some(vec![conflict.base.clone()?]), cx);
+    }
+
+    fn accept_ours_then_theirs(
+        &mut self,
+        _: &AcceptOursThenTheirs,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.accept(
+            |conflict| Some(vec![conflict.ours.clone(), conflict.theirs.clone()]),
+            cx,
+        );
+    }
+
+    fn accept_theirs_then_ours(
+        &mut self,
+        _: &AcceptTheirsThenOurs,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.accept(
+            |conflict| Some(vec![conflict.theirs.clone(), conflict.ours.clone()]),
+            cx,
+        );
+    }
+
+    fn mark_resolved(&mut self, _: &MarkResolved, window: &mut Window, cx: &mut Context<Self>) {
+        let remaining = self.conflict_set.read(cx).snapshot().conflicts.len();
+        if remaining > 0 {
+            Task::ready(Err(anyhow!(
+                "{remaining} conflict{} left to resolve",
+                if remaining == 1 { " is" } else { "s are" }
+            )))
+            .detach_and_prompt_err(
+                "Failed to mark file as resolved",
+                window,
+                cx,
+                |_, _, _| None,
+            );
+            return;
+        }
+
+        let repository = self.repository.clone();
+        let repo_path = self.repo_path.clone();
+        let save = self.project.update(cx, |project, cx| {
+            project.save_buffer(self.buffer.clone(), cx)
+        });
+        cx.spawn(async move |_, cx| {
+            save.await?;
+            repository
+                .update(cx, |repository, cx| {
+                    repository.stage_entries(vec![repo_path], cx)
+                })?
+                .await
+        })
+        .detach_and_prompt_err(
+            "Failed to mark file as resolved",
+            window,
+            cx,
+            |_, _, _| None,
+        );
+    }
+
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.result_editor.focus_handle(cx);
+        let conflicts = self.conflict_set.read(cx).snapshot().conflicts;
+        let has_conflicts = !conflicts.is_empty();
+        let has_base = conflicts.iter().any(|conflict| conflict.base.is_some());
+        let label = match conflicts.len() {
+            0 => "No conflicts left".to_string(),
+            1 => "1 conflict".to_string(),
+            count => format!("{count} conflicts"),
+        };
+        let button = |id: &'static str, label: &'static str, action: Box<dyn Action>| {
+            Button::new(id, label)
+                .label_size(LabelSize::Small)
+                .disabled(!has_conflicts)
+                .tooltip(Tooltip::for_action_title_in(
+                    label,
+                    action.as_ref(),
+                    &focus_handle,
+                ))
+                .on_click(move |_, window, cx| window.dispatch_action(action.boxed_clone(), cx))
+        };
+
+        h_flex()
+            .px_2()
+            .py_1()
+            .gap_1()
+            .flex_wrap()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(Label::new(label).size(LabelSize::Small).color(Color::Muted))
+            .child(Divider::vertical())
+            .child(
+                IconButton::new("previous-conflict", IconName::ArrowUp)
+                    .disabled(!has_conflicts)
+                    .tooltip(Tooltip::for_action_title_in(
+                        "Previous Conflict",
+                        &PreviousConflict,
+                        &focus_handle,
+                    ))
+                    .on_click(|_, window, cx| {
+                        window.dispatch_action(Box::new(PreviousConflict), cx)
+                    }),
+            )
+            .child(
+                IconButton::new("next-conflict", IconName::ArrowDown)
+                    .disabled(!has_conflicts)
+                    .tooltip(Tooltip::for_action_title_in(
+                        "Next Conflict",
+                        &NextConflict,
+                        &focus_handle,
+                    ))
+                    .on_click(|_, window, cx| window.dispatch_action(Box::new(NextConflict), cx)),
+            )
+            .child(Divider::vertical())
+            .child(button("accept-ours", "Accept Ours", Box::new(AcceptOurs)))
+            .child(button(
+                "accept-theirs",
+                "Accept Theirs",
+                Box::new(AcceptTheirs),
+            ))
+            .child(button("accept-base", "Accept Base", Box::new(AcceptBase)).disabled(!has_base))
+            .child(button(
+                "accept-ours-then-theirs",
+                "Ours Then Theirs",
+                Box::new(AcceptOursThenTheirs),
+            ))
+            .child(button(
+                "accept-theirs-then-ours",
+                "Theirs Then Ours",
+                Box::new(AcceptTheirsThenOurs),
+            ))
+            .child(div().flex_1())
+            .child(
+                Button::new("mark-resolved", "Mark Resolved")
+                    .label_size(LabelSize::Small)
+                    .style(ButtonStyle::Filled)
+                    .disabled(has_conflicts)
+                    .tooltip(Tooltip::for_action_title_in(
+                        "Save and stage the file",
+                        &MarkResolved,
+                        &focus_handle,
+                    ))
+                    .on_click(|_, window, cx| window.dispatch_action(Box::new(MarkResolved), cx)),
+            )
+    }
+
+    fn render_pane(
+        &self,
+        title: SharedString,
+        content: AnyElement,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        v_flex()
+            .flex_1()
+            .size_full()
+            .overflow_hidden()
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_0p5()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(Label::new(title).size(LabelSize::Small).color(Color::Muted)),
+            )
+            .child(div().flex_1().size_full().overflow_hidden().child(content))
+    }
+}
+
+fn new_side_editor(
+    side: MergeSide,
+    project: Entity<Project>,
+    workspace: Entity<Workspace>,
+    window: &mut Window,
+    cx: &mut Context<MergeEditor>,
+) -> Entity<SplittableEditor> {
+    let multibuffer = cx.new(|cx| {
+        let mut multibuffer = MultiBuffer::new(Capability::ReadOnly);
+        multibuffer.set_all_diff_hunks_expanded(cx);
+        multibuffer
+    });
+    cx.new(|cx| {
+        let mut editor = SplittableEditor::new(
+            EditorSettings::get_global(cx).diff_view_style,
+            multibuffer,
+            project,
+            workspace,
+            window,
+            cx,
+        );
+        editor.set_render_diff_hunk_controls(
+            Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+            cx,
+        );
+        editor.rhs_editor().update(cx, |editor, cx| {
+            editor.start_temporary_diff_override();
+            editor.disable_diagnostics(cx);
+            editor.set_read_only(true);
+        });
+        let path = PathKey::for_buffer(&side.buffer, cx);
+        let max_point = side.buffer.read(cx).max_point();
+        editor.set_excerpts_for_path(
+            path,
+            side.buffer,
+            [Point::zero()..max_point],
+            0,
+            side.diff,
+            cx,
+        );
+        editor
+    })
+}
+
+async fn build_merge_side(
+    text: String,
+    base: &str,
+    language: Option<Arc<language::Language>>,
+    language_registry: Arc<LanguageRegistry>,
+    cx: &mut AsyncWindowContext,
+) -> MergeSide {
+    let buffer = cx.new(|cx| {
+        let mut buffer = Buffer::local(text, cx);
+        buffer.set_language_registry(language_registry.clone());
+        buffer.set_language(language.clone(), cx);
+        buffer.set_capability(Capability::ReadOnly, cx);
+        buffer
+    });
+    let snapshot = buffer.read_with(cx, |buffer, _| buffer.text_snapshot());
+    let diff = cx.new(|cx| BufferDiff::new(&snapshot, cx));
+    let update = diff
+        .update(cx, |diff, cx| {
+            diff.update_diff(
+                snapshot.clone(),
+                Some(base.into()),
+                Some(true),
+                language.clone(),
+                cx,
+            )
+        })
+        .await;
+    diff.update(cx, |diff, cx| {
+        diff.language_changed(language, Some(language_registry), cx);
+        diff.set_snapshot(update, &snapshot, cx)
+    })
+    .await;
+    MergeSide { buffer, diff }
+}
+
+impl EventEmitter<EditorEvent> for MergeEditor {}
+
+impl Focusable for MergeEditor {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.result_editor.focus_handle(cx)
+    }
+}
+
+impl Item for MergeEditor {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        let file_name = self
+            .repo_path
+            .file_name()
+            .unwrap_or(self.repo_path.as_unix_str());
+        format!("{file_name} (Merge)").into()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        Some(format!("Merging {}", self.repo_path.as_unix_str()).into())
+    }
+
+    fn to_item_events(event: &EditorEvent, f: &mut dyn FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Merge Editor Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.result_editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<gpui::AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.result_editor.clone().into())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>, _: &App) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.result_editor.clone()))
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        self.result_editor.for_each_project_item(cx, f)
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.result_editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn navigate(
+        &mut self,
+        data: Arc<dyn Any + Send>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.result_editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn breadcrumb_location(&self, _: &App) -> ToolbarItemLocation {
+        ToolbarItemLocation::PrimaryLeft
+    }
+
+    fn breadcrumbs(&self, cx: &App) -> Option<Vec<BreadcrumbText>> {
+        self.result_editor.breadcrumbs(cx)
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.result_editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.buffer.read(cx).is_dirty()
+    }
+
+    fn has_conflict(&self, cx: &App) -> bool {
+        self.buffer.read(cx).has_conflict()
+    }
+
+    fn can_save(&self, cx: &App) -> bool {
+        self.result_editor.read(cx).can_save(cx)
+    }
+
+    fn save(
+        &mut self,
+        options: SaveOptions,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.result_editor
+            .update(cx, |editor, cx| editor.save(options, project, window, cx))
+    }
+}
+
+impl Render for MergeEditor {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let conflict = self
+            .conflict_set
+            .read(cx)
+            .snapshot()
+            .conflicts
+            .first()
+            .cloned();
+        let (ours_title, theirs_title) = match conflict {
+            Some(conflict) => (
+                format!("Ours ({})", conflict.ours_branch_name),
+                format!("Theirs ({})", conflict.theirs_branch_name),
+            ),
+            None => ("Ours".to_string(), "Theirs".to_string()),
+        };
+
+        v_flex()
+            .key_context("MergeEditor")
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .on_action(cx.listener(Self::next_conflict))
+            .on_action(cx.listener(Self::previous_conflict))
+            .on_action(cx.listener(Self::accept_ours))
+            .on_action(cx.listener(Self::accept_theirs))
+            .on_action(cx.listener(Self::accept_base))
+            .on_action(cx.listener(Self::accept_ours_then_theirs))
+            .on_action(cx.listener(Self::accept_theirs_then_ours))
+            .on_action(cx.listener(Self::mark_resolved))
+            .child(self.render_toolbar(cx))
+            .child(
+                h_flex()
+                    .flex_1()
+                    .size_full()
+                    .overflow_hidden()
+                    .child(self.render_pane(
+                        ours_title.into(),
+                        self.ours_editor.clone().into_any_element(),
+                        cx,
+                    ))
+                    .child(Divider::vertical())
+                    .child(self.render_pane(
+                        theirs_title.into(),
+                        self.theirs_editor.clone().into_any_element(),
+                        cx,
+                    )),
+            )
+            .child(Divider::horizontal())
+            .child(self.render_pane(
+                "Result".into(),
+                self.result_editor.clone().into_any_element(),
+                cx,
+            ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git::{
+        repository::repo_path,
+        status::{UnmergedStatus, UnmergedStatusCode},
+    };
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::{DiffViewStyle, SettingsStore};
+    use std::path::Path;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            cx.update_global::<SettingsStore, _>(|store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings.editor.diff_view_style = Some(DiffViewStyle::Unified);
+                });
+            });
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_merge_editor(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "foo": "<<<<<<< HEAD\nours one\n=======\ntheirs one\n>>>>>>> other\nmiddle\n<<<<<<< HEAD\nours two\n=======\ntheirs two\n>>>>>>> other\n",
+            }),
+        )
+        .await;
+        let dot_git = Path::new(path!("/project/.git"));
+        fs.set_status_for_repo(
+            dot_git,
+            &[(
+                "foo",
+                UnmergedStatus {
+                    first_head: UnmergedStatusCode::Updated,
+                    second_head: UnmergedStatusCode::Updated,
+                }
+                .into(),
+            )],
+        );
+        fs.set_merge_stages_for_repo(
+            dot_git,
+            &[(
+                "foo",
+                MergeStages {
+                    base: Some("base one\nmiddle\nbase two\n".into()),
+                    ours: Some("ours one\nmiddle\nours two\n".into()),
+                    theirs: Some("theirs one\nmiddle\ntheirs two\n".into()),
+                },
+            )],
+        );
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+        cx.run_until_parked();
+
+        let repository = project.read_with(cx, |project, cx| {
+            project.git_store().read(cx).active_repository().unwrap()
+        });
+        let merge_editor = workspace
+            .update_in(cx, |workspace, window, cx| {
+                MergeEditor::open(
+                    repository,
+                    repo_path("foo"),
+                    workspace.weak_handle(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        merge_editor.read_with(cx, |merge_editor, cx| {
+            let text = |editor: &Entity<SplittableEditor>| {
+                editor
+                    .read(cx)
+                    .rhs_editor()
+                    .read(cx)
+                    .buffer()
+                    .read(cx)
+                    .snapshot(cx)
+                    .text()
+            };
+            assert_eq!(
+                text(&merge_editor.ours_editor),
+                "ours one\nmiddle\nours two\n"
+            );
+            assert_eq!(
+                text(&merge_editor.theirs_editor),
+                "theirs one\nmiddle\ntheirs two\n"
+            );
+            assert_eq!(
+                merge_editor
+                    .conflict_set
+                    .read(cx)
+                    .snapshot()
+                    .conflicts
+                    .len(),
+                2
+            );
+        });
+
+        merge_editor.update_in(cx, |merge_editor, window, cx| {
+            merge_editor.accept_theirs(&AcceptTheirs, window, cx);
+        });
+        cx.run_until_parked();
+        merge_editor.update_in(cx, |merge_editor, window, cx| {
+            merge_editor.next_conflict(&NextConflict, window, cx);
+            merge_editor.accept_theirs_then_ours(&AcceptTheirsThenOurs, window, cx);
+        });
+        cx.run_until_parked();
+
+        let resolved = "theirs one\nmiddle\ntheirs two\nours two\n";
+        merge_editor.read_with(cx, |merge_editor, cx| {
+            assert_eq!(merge_editor.buffer.read(cx).text(), resolved);
+            assert!(
+                merge_editor
+                    .conflict_set
+                    .read(cx)
+                    .snapshot()
+                    .conflicts
+                    .is_empty()
+            );
+        });
+
+        merge_editor.update_in(cx, |merge_editor, window, cx| {
+            merge_editor.mark_resolved(&MarkResolved, window, cx);
+        });
+        cx.run_until_parked();
+
+        assert_eq!(
+            String::from_utf8(fs.read_file_sync(path!("/project/foo")).unwrap()).unwrap(),
+            resolved
+        );
+        let index_text = fs
+            .with_git_state(dot_git, false, |state| {
+                state.index_contents.get(&repo_path("foo")).cloned()
+            })
+            .unwrap();
+        assert_eq!(index_text.as_deref(), Some(resolved));
+    }
+}
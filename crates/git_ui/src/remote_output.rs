@@ -10,6 +10,9 @@ pub enum RemoteAction {
     Fetch(Option<Remote>),
     Pull(Remote),
     Push(SharedString, Remote),
+    PushTag(SharedString, Remote),
+    /// Updates the submodule at the given path, or all of them.
+    SubmoduleUpdate(Option<SharedString>),
 }
 
 impl RemoteAction {
@@ -17,7 +20,8 @@ impl RemoteAction {
         match self {
             RemoteAction::Fetch(_) => "fetch",
             RemoteAction::Pull(_) => "pull",
-            RemoteAction::Push(_, _) => "push",
+            RemoteAction::Push(_, _) | RemoteAction::PushTag(_, _) => "push",
+            RemoteAction::SubmoduleUpdate(_) => "submodule update",
         }
     }
 }
@@ -157,6 +161,36 @@ pub fn format_output(action: &RemoteAction, output: RemoteCommandOutput) -> Succ
                 style: style.unwrap_or(SuccessStyle::ToastWithLog { output }),
             }
         }
+        RemoteAction::PushTag(tag_name, remote_ref) => {
+            if output.stderr.ends_with("Everything up-to-date\n") {
+                SuccessMessage {
+                    message: "Push: Everything is up-to-date".into(),
+                    style: SuccessStyle::Toast,
+                }
+            } else {
+                SuccessMessage {
+                    message: format!("Pushed tag {} to {}", tag_name, remote_ref.name),
+                    style: SuccessStyle::ToastWithLog { output },
+                }
+            }
+        }
+        RemoteAction::SubmoduleUpdate(path) => {
+            if output.stdout.is_empty() {
+                SuccessMessage {
+                    message: "Submodules: Already up to date".into(),
+                    style: SuccessStyle::Toast,
+                }
+            } else {
+                let message = match path {
+                    Some(path) => format!("Updated submodule {path}"),
+                    None => "Updated submodules".into(),
+                };
+                SuccessMessage {
+                    message,
+                    style: SuccessStyle::ToastWithLog { output },
+                }
+            }
+        }
     }
 }
 
@@ -0,0 +1,121 @@
+use gpui::{Entity, Task, actions};
+use project::{CallHierarchyCall, Location, Project};
+use ui::prelude::*;
+use workspace::Workspace;
+
+use crate::hierarchy_tree::{HierarchyKind, HierarchyView, show_hierarchy};
+
+actions!(
+    call_hierarchy,
+    [
+        /// Shows the functions that call the symbol under the cursor.
+        ShowIncomingCalls,
+        /// Shows the functions called by the symbol under the cursor.
+        ShowOutgoingCalls,
+        /// Switches the call hierarchy between incoming and outgoing calls.
+        ToggleDirection,
+    ]
+);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ShowIncomingCalls, window, cx| {
+            show_hierarchy::<CallHierarchy>(workspace, CallDirection::Incoming, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowOutgoingCalls, window, cx| {
+            show_hierarchy::<CallHierarchy>(workspace, CallDirection::Outgoing, window, cx);
+        });
+    })
+    .detach();
+}
+
+/// A tree of the calls made to or by the symbols at a given position, expanded lazily.
+pub type CallHierarchyView = HierarchyView<CallHierarchy>;
+
+pub struct CallHierarchy;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallDirection {
+    Incoming,
+    Outgoing,
+}
+
+impl HierarchyKind for CallHierarchy {
+    type Entry = CallHierarchyCall;
+    type Direction = CallDirection;
+    type ToggleDirection = ToggleDirection;
+
+    const KEY_CONTEXT: &'static str = "CallHierarchyView";
+    const TITLE: &'static str = "Call Hierarchy";
+    const TAB_PREFIX: &'static str = "Calls";
+    const EMPTY_MESSAGE: &'static str = "No call hierarchy available at the cursor";
+
+    fn directions() -> [(CallDirection, &'static str); 2] {
+        [
+            (CallDirection::Incoming, "Incoming Calls"),
+            (CallDirection::Outgoing, "Outgoing Calls"),
+        ]
+    }
+
+    fn prepare(
+        project: &mut Project,
+        buffer: &Entity<language::Buffer>,
+        position: language::Anchor,
+        cx: &mut Context<Project>,
+    ) -> Task<anyhow::Result<Option<Vec<CallHierarchyCall>>>> {
+        let items = project.prepare_call_hierarchy(buffer, position, cx);
+        cx.background_spawn(async move {
+            Ok(items.await?.map(|items| {
+                items
+                    .into_iter()
+                    // The roots are the symbols at the position, not calls, so they have no
+                    // call sites.
+                    .map(|item| CallHierarchyCall {
+                        item,
+                        call_sites: Vec::new(),
+                    })
+                    .collect()
+            }))
+        })
+    }
+
+    fn children(
+        project: &mut Project,
+        call: &CallHierarchyCall,
+        direction: CallDirection,
+        cx: &mut Context<Project>,
+    ) -> Task<anyhow::Result<Vec<CallHierarchyCall>>> {
+        match direction {
+            CallDirection::Incoming => project.incoming_calls(&call.item, cx),
+            CallDirection::Outgoing => project.outgoing_calls(&call.item, cx),
+        }
+    }
+
+    fn name(call: &CallHierarchyCall) -> &str {
+        &call.item.name
+    }
+
+    fn detail(call: &CallHierarchyCall) -> Option<&str> {
+        call.item.detail.as_deref()
+    }
+
+    fn location(call: &CallHierarchyCall) -> &Location {
+        &call.item.location
+    }
+
+    /// Callers are opened at the call expression, callees and roots at their definition.
+    fn target(call: &CallHierarchyCall, direction: CallDirection) -> Location {
+        match (direction, call.call_sites.first()) {
+            (CallDirection::Incoming, Some(call_site)) => call_site.clone(),
+            _ => Location {
+                buffer: call.item.location.buffer.clone(),
+                range: call.item.selection_range.clone(),
+            },
+        }
+    }
+
+    fn annotation(call: &CallHierarchyCall) -> Option<String> {
+        let call_count = call.call_sites.len();
+        (call_count > 1).then(|| format!("{call_count} calls"))
+    }
+}
@@ -0,0 +1,503 @@
+use std::ops::Range;
+
+use editor::Editor;
+use gpui::{
+    Action, Entity, EventEmitter, FocusHandle, Focusable, ScrollStrategy, Task,
+    UniformListScrollHandle, WeakEntity, uniform_list,
+};
+use project::{Location, Project};
+use ui::{ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    Pane, SplitDirection, Workspace,
+    item::{Item, ItemEvent},
+};
+
+use crate::{CollapseSelectedEntry, ExpandSelectedEntry, open_location};
+
+/// The language server requests and the labels of a hierarchy shown in a [`HierarchyView`].
+pub trait HierarchyKind: 'static {
+    /// A symbol in the tree, along with how it relates to its parent.
+    type Entry: 'static;
+    /// Which relatives of the symbols the tree shows, e.g. callers or callees.
+    type Direction: 'static + Copy + PartialEq;
+    /// The action that switches the tree to the other direction.
+    type ToggleDirection: Action;
+
+    const KEY_CONTEXT: &'static str;
+    const TITLE: &'static str;
+    /// The tab title is this prefix followed by the name of the root, when there's a single root.
+    const TAB_PREFIX: &'static str;
+    const EMPTY_MESSAGE: &'static str;
+
+    /// The two directions of the tree, along with the labels of their buttons.
+    fn directions() -> [(Self::Direction, &'static str); 2];
+
+    fn prepare(
+        project: &mut Project,
+        buffer: &Entity<language::Buffer>,
+        position: language::Anchor,
+        cx: &mut Context<Project>,
+    ) -> Task<anyhow::Result<Option<Vec<Self::Entry>>>>;
+
+    fn children(
+        project: &mut Project,
+        entry: &Self::Entry,
+        direction: Self::Direction,
+        cx: &mut Context<Project>,
+    ) -> Task<anyhow::Result<Vec<Self::Entry>>>;
+
+    fn name(entry: &Self::Entry) -> &str;
+
+    fn detail(entry: &Self::Entry) -> Option<&str>;
+
+    /// Where the symbol is defined.
+    fn location(entry: &Self::Entry) -> &Location;
+
+    /// Where to navigate to when the entry is opened.
+    fn target(entry: &Self::Entry, direction: Self::Direction) -> Location;
+
+    /// A short note shown at the end of the entry.
+    fn annotation(_entry: &Self::Entry) -> Option<String> {
+        None
+    }
+}
+
+pub(crate) fn show_hierarchy<K: HierarchyKind>(
+    workspace: &mut Workspace,
+    direction: K::Direction,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let Some((buffer, position)) = editor.update(cx, |editor, cx| {
+        let head = editor.selections.newest_anchor().head();
+        editor.buffer().read(cx).text_anchor_for_position(head, cx)
+    }) else {
+        return;
+    };
+
+    let project = workspace.project().clone();
+    let target_pane = workspace.active_pane().downgrade();
+    let entries = project.update(cx, |project, cx| K::prepare(project, &buffer, position, cx));
+    cx.spawn_in(window, async move |workspace, cx| {
+        let entries = entries.await?.unwrap_or_default();
+        workspace.update_in(cx, |workspace, window, cx| {
+            if let Some(view) = workspace.item_of_type::<HierarchyView<K>>(cx) {
+                view.update(cx, |view, cx| {
+                    view.target_pane = target_pane;
+                    view.direction = direction;
+                    view.set_roots(entries, cx);
+                });
+                workspace.activate_item(&view, true, true, window, cx);
+            } else {
+                let view = cx.new(|cx| {
+                    HierarchyView::<K>::new(
+                        workspace.weak_handle(),
+                        project,
+                        target_pane,
+                        entries,
+                        direction,
+                        cx,
+                    )
+                });
+                workspace.split_item(SplitDirection::Right, Box::new(view), window, cx);
+            }
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+enum Children {
+    NotLoaded,
+    Loading(Task<()>),
+    Loaded(Vec<usize>),
+}
+
+struct Node<K: HierarchyKind> {
+    entry: K::Entry,
+    depth: usize,
+    is_expanded: bool,
+    children: Children,
+}
+
+/// A tree of the relatives of the symbols at a given position, expanded lazily.
+pub struct HierarchyView<K: HierarchyKind> {
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    /// The pane the hierarchy was requested from, where entries are opened.
+    target_pane: WeakEntity<Pane>,
+    direction: K::Direction,
+    /// The number of roots, which are the first nodes.
+    root_count: usize,
+    /// All nodes fetched so far; children refer to their index in this list.
+    nodes: Vec<Node<K>>,
+    visible_entries: Vec<usize>,
+    selected_entry: Option<usize>,
+    scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+}
+
+impl<K: HierarchyKind> HierarchyView<K> {
+    fn new(
+        workspace: WeakEntity<Workspace>,
+        project: Entity<Project>,
+        target_pane: WeakEntity<Pane>,
+        roots: Vec<K::Entry>,
+        direction: K::Direction,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut this = Self {
+            workspace,
+            project,
+            target_pane,
+            direction,
+            root_count: 0,
+            nodes: Vec::new(),
+            visible_entries: Vec::new(),
+            selected_entry: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+        };
+        this.set_roots(roots, cx);
+        this
+    }
+
+    fn set_roots(&mut self, roots: Vec<K::Entry>, cx: &mut Context<Self>) {
+        self.nodes.clear();
+        self.root_count = roots.len();
+        for entry in roots {
+            self.push_node(entry, 0);
+        }
+        self.rebuild(cx);
+    }
+
+    /// Discards all fetched relatives and starts over from the roots, which are expanded eagerly.
+    fn rebuild(&mut self, cx: &mut Context<Self>) {
+        self.nodes.truncate(self.root_count);
+        self.selected_entry = None;
+        for ix in 0..self.root_count {
+            self.nodes[ix].is_expanded = true;
+            self.fetch_children(ix, cx);
+        }
+        self.update_visible_entries(cx);
+    }
+
+    fn push_node(&mut self, entry: K::Entry, depth: usize) -> usize {
+        self.nodes.push(Node {
+            entry,
+            depth,
+            is_expanded: false,
+            children: Children::NotLoaded,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn fetch_children(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let entry = &self.nodes[ix].entry;
+        let direction = self.direction;
+        let children = self
+            .project
+            .update(cx, |project, cx| K::children(project, entry, direction, cx));
+        let task = cx.spawn(async move |this, cx| {
+            let children = children.await.log_err().unwrap_or_default();
+            this.update(cx, |this, cx| this.insert_children(ix, children, cx))
+                .ok();
+        });
+        self.nodes[ix].children = Children::Loading(task);
+    }
+
+    fn insert_children(&mut self, ix: usize, entries: Vec<K::Entry>, cx: &mut Context<Self>) {
+        let depth = self.nodes[ix].depth + 1;
+        let children = entries
+            .into_iter()
+            .map(|entry| self.push_node(entry, depth))
+            .collect();
+        self.nodes[ix].children = Children::Loaded(children);
+        self.update_visible_entries(cx);
+    }
+
+    fn update_visible_entries(&mut self, cx: &mut Context<Self>) {
+        let selected_node = self
+            .selected_entry
+            .and_then(|ix| self.visible_entries.get(ix).copied());
+
+        let mut stack = (0..self.root_count).rev().collect::<Vec<_>>();
+        self.visible_entries.clear();
+        while let Some(ix) = stack.pop() {
+            self.visible_entries.push(ix);
+            let node = &self.nodes[ix];
+            if let (true, Children::Loaded(children)) = (node.is_expanded, &node.children) {
+                stack.extend(children.iter().rev());
+            }
+        }
+
+        self.selected_entry =
+            selected_node.and_then(|node| self.visible_entries.iter().position(|ix| *ix == node));
+        cx.notify();
+    }
+
+    fn toggle_node(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let node = &mut self.nodes[ix];
+        node.is_expanded = !node.is_expanded;
+        if node.is_expanded && matches!(node.children, Children::NotLoaded) {
+            self.fetch_children(ix, cx);
+        }
+        self.update_visible_entries(cx);
+    }
+
+    fn has_children(&self, ix: usize) -> bool {
+        match &self.nodes[ix].children {
+            Children::Loaded(children) => !children.is_empty(),
+            Children::NotLoaded | Children::Loading(_) => true,
+        }
+    }
+
+    fn toggle_direction(&mut self, _: &K::ToggleDirection, _: &mut Window, cx: &mut Context<Self>) {
+        let [(first, _), (second, _)] = K::directions();
+        self.direction = if self.direction == first {
+            second
+        } else {
+            first
+        };
+        self.rebuild(cx);
+    }
+
+    fn set_direction(&mut self, direction: K::Direction, cx: &mut Context<Self>) {
+        if self.direction != direction {
+            self.direction = direction;
+            self.rebuild(cx);
+        }
+    }
+
+    fn select_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_entry = Some(ix);
+        self.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+        cx.notify();
+    }
+
+    fn select_first(&mut self, _: &menu::SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.visible_entries.is_empty() {
+            self.select_entry(0, cx);
+        }
+    }
+
+    fn select_last(&mut self, _: &menu::SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.visible_entries.len().checked_sub(1) {
+            self.select_entry(ix, cx);
+        }
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match self.selected_entry {
+            Some(ix) if ix > 0 => self.select_entry(ix - 1, cx),
+            _ => self.select_last(&menu::SelectLast, window, cx),
+        }
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, window: &mut Window, cx: &mut Context<Self>) {
+        match self.selected_entry {
+            Some(ix) if ix + 1 < self.visible_entries.len() => self.select_entry(ix + 1, cx),
+            _ => self.select_first(&menu::SelectFirst, window, cx),
+        }
+    }
+
+    fn expand_selected_entry(
+        &mut self,
+        _: &ExpandSelectedEntry,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ix) = self.selected_entry else {
+            return;
+        };
+        let node_ix = self.visible_entries[ix];
+        if self.nodes[node_ix].is_expanded || !self.has_children(node_ix) {
+            self.select_next(&menu::SelectNext, window, cx);
+        } else {
+            self.toggle_node(node_ix, cx);
+        }
+    }
+
+    fn collapse_selected_entry(
+        &mut self,
+        _: &CollapseSelectedEntry,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ix) = self.selected_entry else {
+            return;
+        };
+        let node_ix = self.visible_entries[ix];
+        if self.nodes[node_ix].is_expanded && self.has_children(node_ix) {
+            self.toggle_node(node_ix, cx);
+        } else {
+            self.select_previous(&menu::SelectPrevious, window, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_entry {
+            self.open_entry(ix, window, cx);
+        }
+    }
+
+    fn open_entry(&self, ix: usize, window: &mut Window, cx: &mut App) {
+        let node = &self.nodes[self.visible_entries[ix]];
+        let location = K::target(&node.entry, self.direction);
+        open_location(&self.workspace, &self.target_pane, &location, window, cx);
+    }
+
+    fn render_entries(&self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<AnyElement> {
+        range
+            .map(|ix| {
+                let node_ix = self.visible_entries[ix];
+                let node = &self.nodes[node_ix];
+                let path = K::location(&node.entry)
+                    .buffer
+                    .read(cx)
+                    .file()
+                    .map(|file| file.file_name(cx).to_string());
+
+                ListItem::new(ix)
+                    .spacing(ListItemSpacing::Dense)
+                    .indent_level(node.depth)
+                    .indent_step_size(px(12.))
+                    .toggle(self.has_children(node_ix).then_some(node.is_expanded))
+                    .on_toggle(cx.listener(move |this, _, _, cx| this.toggle_node(node_ix, cx)))
+                    .toggle_state(self.selected_entry == Some(ix))
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.selected_entry = Some(ix);
+                        this.open_entry(ix, window, cx);
+                        cx.notify();
+                    }))
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(Label::new(K::name(&node.entry).to_string()))
+                            .when_some(K::detail(&node.entry), |this, detail| {
+                                this.child(
+                                    Label::new(detail.to_string())
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted)
+                                        .truncate(),
+                                )
+                            }),
+                    )
+                    .end_slot(
+                        h_flex()
+                            .gap_2()
+                            .when(matches!(node.children, Children::Loading(_)), |this| {
+                                this.child(
+                                    LoadingLabel::new("Loading")
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted),
+                                )
+                            })
+                            .when_some(K::annotation(&node.entry), |this, annotation| {
+                                this.child(
+                                    Label::new(annotation)
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted),
+                                )
+                            })
+                            .when_some(path, |this, path| {
+                                this.child(
+                                    Label::new(path).size(LabelSize::Small).color(Color::Muted),
+                                )
+                            }),
+                    )
+                    .into_any_element()
+            })
+            .collect()
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .p_1()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .children(K::directions().map(|(direction, label)| {
+                Button::new(label, label)
+                    .label_size(LabelSize::Small)
+                    .toggle_state(self.direction == direction)
+                    .on_click(cx.listener(move |this, _, _, cx| this.set_direction(direction, cx)))
+            }))
+    }
+}
+
+impl<K: HierarchyKind> Render for HierarchyView<K> {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .id("hierarchy-view")
+            .key_context(K::KEY_CONTEXT)
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::expand_selected_entry))
+            .on_action(cx.listener(Self::collapse_selected_entry))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::toggle_direction))
+            .child(self.render_header(cx))
+            .map(|this| {
+                if self.root_count == 0 {
+                    this.child(
+                        h_flex()
+                            .size_full()
+                            .justify_center()
+                            .child(Label::new(K::EMPTY_MESSAGE).color(Color::Muted)),
+                    )
+                } else {
+                    this.child(
+                        uniform_list(
+                            "hierarchy-entries",
+                            self.visible_entries.len(),
+                            cx.processor(|this, range: Range<usize>, _, cx| {
+                                this.render_entries(range, cx)
+                            }),
+                        )
+                        .track_scroll(&self.scroll_handle)
+                        .size_full()
+                        .p_1(),
+                    )
+                }
+            })
+    }
+}
+
+impl<K: HierarchyKind> EventEmitter<()> for HierarchyView<K> {}
+
+impl<K: HierarchyKind> Focusable for HierarchyView<K> {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl<K: HierarchyKind> Item for HierarchyView<K> {
+    type Event = ();
+
+    fn to_item_events(_: &Self::Event, _: &mut dyn FnMut(ItemEvent)) {}
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        match self.nodes.get(..self.root_count) {
+            Some([root]) => format!("{}: {}", K::TAB_PREFIX, K::name(&root.entry)).into(),
+            _ => K::TITLE.into(),
+        }
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+}
@@ -0,0 +1,67 @@
+mod call_hierarchy;
+mod hierarchy_tree;
+mod type_hierarchy;
+
+use editor::{Editor, SelectionEffects, scroll::Autoscroll};
+use gpui::{App, WeakEntity, Window, actions};
+use language::ToPoint as _;
+use project::Location;
+use workspace::{Pane, Workspace};
+
+pub use call_hierarchy::CallHierarchyView;
+pub use hierarchy_tree::{HierarchyKind, HierarchyView};
+pub use type_hierarchy::TypeHierarchyView;
+
+actions!(
+    hierarchy_view,
+    [
+        /// Expands the selected entry to show its children.
+        ExpandSelectedEntry,
+        /// Collapses the selected entry to hide its children.
+        CollapseSelectedEntry,
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    call_hierarchy::init(cx);
+    type_hierarchy::init(cx);
+}
+
+/// Opens the location in an editor within the pane the hierarchy was requested from,
+/// falling back to the active pane when that one has been closed.
+fn open_location(
+    workspace: &WeakEntity<Workspace>,
+    target_pane: &WeakEntity<Pane>,
+    location: &Location,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let Some(workspace) = workspace.upgrade() else {
+        return;
+    };
+    workspace.update(cx, |workspace, cx| {
+        let pane = target_pane
+            .upgrade()
+            .filter(|pane| workspace.panes().contains(pane))
+            .unwrap_or_else(|| workspace.active_pane().clone());
+        let editor = workspace.open_project_item::<Editor>(
+            pane,
+            location.buffer.clone(),
+            true,
+            true,
+            true,
+            true,
+            window,
+            cx,
+        );
+        let position = location.range.start.to_point(location.buffer.read(cx));
+        editor.update(cx, |editor, cx| {
+            editor.change_selections(
+                SelectionEffects::scroll(Autoscroll::center()),
+                window,
+                cx,
+                |s| s.select_ranges([position..position]),
+            );
+        });
+    });
+}
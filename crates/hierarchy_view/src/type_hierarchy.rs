@@ -0,0 +1,99 @@
+use gpui::{Entity, Task, actions};
+use project::{Location, Project, TypeHierarchyItem};
+use ui::prelude::*;
+use workspace::Workspace;
+
+use crate::hierarchy_tree::{HierarchyKind, HierarchyView, show_hierarchy};
+
+actions!(
+    type_hierarchy,
+    [
+        /// Shows the types that the type under the cursor derives from.
+        ShowSupertypes,
+        /// Shows the types that derive from the type under the cursor.
+        ShowSubtypes,
+        /// Switches the type hierarchy between supertypes and subtypes.
+        ToggleDirection,
+    ]
+);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ShowSupertypes, window, cx| {
+            show_hierarchy::<TypeHierarchy>(workspace, TypeDirection::Supertypes, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowSubtypes, window, cx| {
+            show_hierarchy::<TypeHierarchy>(workspace, TypeDirection::Subtypes, window, cx);
+        });
+    })
+    .detach();
+}
+
+/// A tree of the supertypes or subtypes of the types at a given position, expanded lazily.
+pub type TypeHierarchyView = HierarchyView<TypeHierarchy>;
+
+pub struct TypeHierarchy;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeDirection {
+    Supertypes,
+    Subtypes,
+}
+
+impl HierarchyKind for TypeHierarchy {
+    type Entry = TypeHierarchyItem;
+    type Direction = TypeDirection;
+    type ToggleDirection = ToggleDirection;
+
+    const KEY_CONTEXT: &'static str = "TypeHierarchyView";
+    const TITLE: &'static str = "Type Hierarchy";
+    const TAB_PREFIX: &'static str = "Types";
+    const EMPTY_MESSAGE: &'static str = "No type hierarchy available at the cursor";
+
+    fn directions() -> [(TypeDirection, &'static str); 2] {
+        [
+            (TypeDirection::Supertypes, "Supertypes"),
+            (TypeDirection::Subtypes, "Subtypes"),
+        ]
+    }
+
+    fn prepare(
+        project: &mut Project,
+        buffer: &Entity<language::Buffer>,
+        position: language::Anchor,
+        cx: &mut Context<Project>,
+    ) -> Task<anyhow::Result<Option<Vec<TypeHierarchyItem>>>> {
+        project.prepare_type_hierarchy(buffer, position, cx)
+    }
+
+    fn children(
+        project: &mut Project,
+        item: &TypeHierarchyItem,
+        direction: TypeDirection,
+        cx: &mut Context<Project>,
+    ) -> Task<anyhow::Result<Vec<TypeHierarchyItem>>> {
+        match direction {
+            TypeDirection::Supertypes => project.supertypes(item, cx),
+            TypeDirection::Subtypes => project.subtypes(item, cx),
+        }
+    }
+
+    fn name(item: &TypeHierarchyItem) -> &str {
+        &item.name
+    }
+
+    fn detail(item: &TypeHierarchyItem) -> Option<&str> {
+        item.detail.as_deref()
+    }
+
+    fn location(item: &TypeHierarchyItem) -> &Location {
+        &item.location
+    }
+
+    fn target(item: &TypeHierarchyItem, _: TypeDirection) -> Location {
+        Location {
+            buffer: item.location.buffer.clone(),
+            range: item.selection_range.clone(),
+        }
+    }
+}
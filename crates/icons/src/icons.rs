@@ -232,6 +232,7 @@ pub enum IconName {
     SweepAiError,
     SweepAiUp,
     Tab,
+    Tag,
     Terminal,
     TerminalAlt,
     TerminalGhost,
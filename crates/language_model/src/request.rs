@@ -450,10 +450,27 @@ pub struct LanguageModelRequest {
     pub tool_choice: Option<LanguageModelToolChoice>,
     pub stop: Vec<String>,
     pub temperature: Option<f32>,
+    /// The most tokens the model may generate, when it should stop before its own limit.
+    pub max_output_tokens: Option<u64>,
     pub thinking_allowed: bool,
     pub thinking_effort: Option<String>,
 }
 
+impl LanguageModelRequest {
+    /// Returns the most tokens the model may generate for this request, given the model's own
+    /// limit.
+    pub fn output_token_limit(&self, model_max_output_tokens: Option<u64>) -> Option<u64> {
+        match (self.max_output_tokens, model_max_output_tokens) {
+            (Some(max_output_tokens), Some(model_max_output_tokens)) => {
+                Some(max_output_tokens.min(model_max_output_tokens))
+            }
+            (max_output_tokens, model_max_output_tokens) => {
+                max_output_tokens.or(model_max_output_tokens)
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
 pub struct LanguageModelResponseMessage {
     pub role: Option<Role>,
@@ -692,4 +709,16 @@ mod tests {
         let result: Result<LanguageModelToolResultContent, _> = serde_json::from_str(json);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_output_token_limit() {
+        let mut request = LanguageModelRequest::default();
+        assert_eq!(request.output_token_limit(None), None);
+        assert_eq!(request.output_token_limit(Some(8192)), Some(8192));
+
+        request.max_output_tokens = Some(100);
+        assert_eq!(request.output_token_limit(None), Some(100));
+        assert_eq!(request.output_token_limit(Some(8192)), Some(100));
+        assert_eq!(request.output_token_limit(Some(50)), Some(50));
+    }
 }
@@ -623,6 +623,11 @@ pub fn into_anthropic(
     max_output_tokens: u64,
     mode: AnthropicModelMode,
 ) -> anthropic::Request {
+    let max_output_tokens = request
+        .max_output_tokens
+        .map_or(max_output_tokens, |max_tokens| {
+            max_tokens.min(max_output_tokens)
+        });
     let mut new_messages: Vec<anthropic::Message> = Vec::new();
     let mut system_message = String::new();
 
@@ -1101,6 +1106,7 @@ mod tests {
             temperature: None,
             tools: vec![],
             tool_choice: None,
+            max_output_tokens: None,
             thinking_allowed: true,
             thinking_effort: None,
         };
@@ -1164,6 +1170,7 @@ mod tests {
             temperature: None,
             tools: vec![],
             tool_choice: None,
+            max_output_tokens: None,
             thinking_allowed: true,
         };
         request.messages.push(LanguageModelRequestMessage {
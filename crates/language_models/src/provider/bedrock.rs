@@ -812,6 +812,11 @@ pub fn into_bedrock(
     supports_tool_use: bool,
     allow_extended_context: bool,
 ) -> Result<bedrock::Request> {
+    let max_output_tokens = request
+        .max_output_tokens
+        .map_or(max_output_tokens, |max_tokens| {
+            max_tokens.min(max_output_tokens)
+        });
     let mut new_messages: Vec<BedrockMessage> = Vec::new();
     let mut system_message = String::new();
 
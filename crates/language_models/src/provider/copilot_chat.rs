@@ -910,6 +910,7 @@ fn into_copilot_chat(
             LanguageModelToolChoice::Any => ToolChoice::Any,
             LanguageModelToolChoice::None => ToolChoice::None,
         }),
+        max_tokens: request.max_output_tokens,
     })
 }
 
@@ -928,6 +929,7 @@ fn into_copilot_responses(
         tool_choice,
         stop: _,
         temperature,
+        max_output_tokens,
         thinking_allowed: _,
         thinking_effort: _,
     } = request;
@@ -1104,6 +1106,7 @@ fn into_copilot_responses(
         input: input_items,
         stream: model.uses_streaming(),
         temperature,
+        max_output_tokens,
         tools: converted_tools,
         tool_choice: mapped_tool_choice,
         reasoning: None, // We would need to add support for setting from user settings.
@@ -1482,4 +1485,44 @@ mod tests {
             "Should capture reasoning_text"
         );
     }
+
+    #[test]
+    fn requests_limit_output_tokens() {
+        let model: CopilotChatModel = serde_json::from_value(serde_json::json!({
+            "billing": { "is_premium": false, "multiplier": 0 },
+            "capabilities": {
+                "family": "gpt-4o",
+                "limits": { "max_output_tokens": 4096 },
+                "supports": { "streaming": true, "tool_calls": true },
+                "type": "chat"
+            },
+            "id": "gpt-4o",
+            "name": "GPT-4o",
+            "vendor": "Azure OpenAI",
+            "is_chat_default": true,
+            "is_chat_fallback": false,
+            "model_picker_enabled": true
+        }))
+        .unwrap();
+        let request = |max_output_tokens| LanguageModelRequest {
+            messages: vec![LanguageModelRequestMessage {
+                role: Role::User,
+                content: vec![MessageContent::Text("message".into())],
+                cache: false,
+                reasoning_details: None,
+            }],
+            max_output_tokens,
+            ..Default::default()
+        };
+
+        let chat_request = into_copilot_chat(&model, request(Some(100))).unwrap();
+        assert_eq!(chat_request.max_tokens, Some(100));
+        let chat_request = into_copilot_chat(&model, request(None)).unwrap();
+        assert_eq!(chat_request.max_tokens, None);
+
+        let responses_request = into_copilot_responses(&model, request(Some(100)));
+        assert_eq!(responses_request.max_output_tokens, Some(100));
+        let responses_request = into_copilot_responses(&model, request(None));
+        assert_eq!(responses_request.max_output_tokens, None);
+    }
 }
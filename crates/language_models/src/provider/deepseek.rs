@@ -319,6 +319,7 @@ pub fn into_deepseek(
     model: &deepseek::Model,
     max_output_tokens: Option<u64>,
 ) -> deepseek::Request {
+    let max_output_tokens = request.output_token_limit(max_output_tokens);
     let is_reasoner = model == &deepseek::Model::Reasoner;
 
     let mut messages = Vec::new();
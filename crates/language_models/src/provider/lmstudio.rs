@@ -342,7 +342,11 @@ impl LmStudioLanguageModel {
             model: self.model.name.clone(),
             messages,
             stream: true,
-            max_tokens: Some(-1),
+            max_tokens: Some(
+                request
+                    .max_output_tokens
+                    .map_or(-1, |max_tokens| max_tokens as i32),
+            ),
             stop: Some(request.stop),
             // In LM Studio you can configure specific settings you'd like to use for your model.
             // For example Qwen3 is recommended to be used with 0.7 temperature.
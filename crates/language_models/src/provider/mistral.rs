@@ -355,6 +355,7 @@ pub fn into_mistral(
     model: mistral::Model,
     max_output_tokens: Option<u64>,
 ) -> (mistral::Request, Option<String>) {
+    let max_output_tokens = request.output_token_limit(max_output_tokens);
     let stream = true;
 
     let mut messages = Vec::new();
@@ -881,6 +882,7 @@ mod tests {
             prompt_id: None,
             intent: None,
             stop: vec![],
+            max_output_tokens: None,
             thinking_allowed: true,
             thinking_effort: None,
         };
@@ -917,6 +919,7 @@ mod tests {
             prompt_id: None,
             intent: None,
             stop: vec![],
+            max_output_tokens: None,
             thinking_allowed: true,
             thinking_effort: None,
         };
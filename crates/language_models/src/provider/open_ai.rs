@@ -411,6 +411,7 @@ pub fn into_open_ai(
     max_output_tokens: Option<u64>,
     reasoning_effort: Option<ReasoningEffort>,
 ) -> open_ai::Request {
+    let max_output_tokens = request.output_token_limit(max_output_tokens);
     let stream = !model_id.starts_with("o1-");
 
     let mut messages = Vec::new();
@@ -541,6 +542,7 @@ pub fn into_open_ai_response(
     max_output_tokens: Option<u64>,
     reasoning_effort: Option<ReasoningEffort>,
 ) -> ResponseRequest {
+    let max_output_tokens = request.output_token_limit(max_output_tokens);
     let stream = !model_id.starts_with("o1-");
 
     let LanguageModelRequest {
@@ -552,6 +554,7 @@ pub fn into_open_ai_response(
         tool_choice,
         stop: _,
         temperature,
+        max_output_tokens: _,
         thinking_allowed: _,
         thinking_effort: _,
     } = request;
@@ -1429,6 +1432,7 @@ mod tests {
             tool_choice: None,
             stop: vec![],
             temperature: None,
+            max_output_tokens: None,
             thinking_allowed: true,
             thinking_effort: None,
         };
@@ -1566,6 +1570,7 @@ mod tests {
             tool_choice: Some(LanguageModelToolChoice::Any),
             stop: vec!["<STOP>".into()],
             temperature: None,
+            max_output_tokens: None,
             thinking_allowed: false,
             thinking_effort: None,
         };
@@ -37,7 +37,7 @@ use std::{
     pin::Pin,
     sync::{
         Arc, Weak,
-        atomic::{AtomicI32, Ordering::SeqCst},
+        atomic::{AtomicBool, AtomicI32, Ordering::SeqCst},
     },
     task::Poll,
     time::{Duration, Instant},
@@ -106,6 +106,9 @@ pub struct LanguageServer {
     process_name: Arc<str>,
     binary: LanguageServerBinary,
     capabilities: RwLock<ServerCapabilities>,
+    /// `lsp-types` has no server capability for type hierarchies, so whether the server
+    /// advertised `typeHierarchyProvider` is tracked separately.
+    type_hierarchy_provider: AtomicBool,
     /// Configuration sent to the server, stored for display in the language server logs
     /// buffer. This is represented as the message sent to the LSP in order to avoid cloning it (can
     /// be large in cases like sending schemas to the json server).
@@ -339,6 +342,18 @@ pub struct AdapterServerCapabilities {
     pub server_capabilities: ServerCapabilities,
     // List of code actions supported by the LspAdapter matching the server
     pub code_action_kinds: Option<Vec<CodeActionKind>>,
+    // Whether the server supports `textDocument/prepareTypeHierarchy`
+    pub type_hierarchy_provider: bool,
+}
+
+/// The `initialize` request, with the result left untyped so that capabilities
+/// unknown to `lsp-types` can still be read from it.
+enum RawInitialize {}
+
+impl request::Request for RawInitialize {
+    type Params = InitializeParams;
+    type Result = Value;
+    const METHOD: &'static str = <request::Initialize as request::Request>::METHOD;
 }
 
 // See the VSCode docs [1] and the LSP Spec [2]
@@ -593,6 +608,7 @@ impl LanguageServer {
                 .unwrap_or_default(),
             binary,
             capabilities: Default::default(),
+            type_hierarchy_provider: AtomicBool::new(false),
             configuration,
             code_action_kinds,
             next_id: Default::default(),
@@ -1015,6 +1031,15 @@ impl LanguageServer {
                             ]),
                         }),
                     }),
+                    call_hierarchy: Some(DynamicRegistrationClientCapabilities {
+                        dynamic_registration: Some(true),
+                    }),
+                    type_hierarchy: Some(DynamicRegistrationClientCapabilities {
+                        dynamic_registration: Some(true),
+                    }),
+                    selection_range: Some(SelectionRangeClientCapabilities {
+                        dynamic_registration: Some(true),
+                    }),
                     ..TextDocumentClientCapabilities::default()
                 }),
                 experimental: Some(json!({
@@ -1057,7 +1082,7 @@ impl LanguageServer {
     ) -> Task<Result<Arc<Self>>> {
         cx.background_spawn(async move {
             let response = self
-                .request::<request::Initialize>(params, timeout)
+                .request::<RawInitialize>(params, timeout)
                 .await
                 .into_response()
                 .with_context(|| {
@@ -1067,11 +1092,17 @@ impl LanguageServer {
                         self.server_id()
                     )
                 })?;
+            let type_hierarchy_provider = response
+                .pointer("/capabilities/typeHierarchyProvider")
+                .is_some_and(|provider| !matches!(provider, Value::Null | Value::Bool(false)));
+            let response = serde_json::from_value::<InitializeResult>(response)
+                .context("parsing initialize response")?;
             if let Some(info) = response.server_info {
                 self.version = info.version.map(SharedString::from);
                 self.process_name = info.name.into();
             }
             self.capabilities = RwLock::new(response.capabilities);
+            self.type_hierarchy_provider = AtomicBool::new(type_hierarchy_provider);
             self.configuration = configuration;
 
             self.notify::<notification::Initialized>(InitializedParams {})?;
@@ -1322,9 +1353,21 @@ impl LanguageServer {
         AdapterServerCapabilities {
             server_capabilities: self.capabilities(),
             code_action_kinds: self.code_action_kinds(),
+            type_hierarchy_provider: self.type_hierarchy_provider(),
         }
     }
 
+    /// Whether the server supports type hierarchies, either from its initial
+    /// capabilities or from a dynamic registration.
+    pub fn type_hierarchy_provider(&self) -> bool {
+        self.type_hierarchy_provider.load(SeqCst)
+    }
+
+    /// Update whether the server supports type hierarchies.
+    pub fn set_type_hierarchy_provider(&self, enabled: bool) {
+        self.type_hierarchy_provider.store(enabled, SeqCst);
+    }
+
     /// Update the capabilities of the running language server.
     pub fn update_capabilities(&self, update: impl FnOnce(&mut ServerCapabilities)) {
         update(self.capabilities.write().deref_mut());
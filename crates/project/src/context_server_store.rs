@@ -1,5 +1,6 @@
 pub mod extension;
 pub mod registry;
+pub mod server_requests;
 
 use std::path::Path;
 use std::sync::Arc;
@@ -9,11 +10,14 @@ use anyhow::{Context as _, Result};
 use collections::{HashMap, HashSet};
 use context_server::{ContextServer, ContextServerCommand, ContextServerId};
 use futures::{FutureExt as _, future::join_all};
-use gpui::{App, AsyncApp, Context, Entity, EventEmitter, Subscription, Task, WeakEntity, actions};
+use gpui::{
+    App, AsyncApp, Context, Entity, EntityId, EventEmitter, Subscription, Task, WeakEntity, actions,
+};
 use itertools::Itertools;
 use registry::ContextServerDescriptorRegistry;
 use remote::RemoteClient;
 use rpc::{AnyProtoClient, TypedEnvelope, proto};
+use server_requests::PendingServerRequest;
 use settings::{Settings as _, SettingsStore};
 use util::{ResultExt as _, rel_path::RelPath};
 
@@ -211,6 +215,10 @@ pub struct ContextServerStore {
     update_servers_task: Option<Task<Result<()>>>,
     context_server_factory: Option<ContextServerFactory>,
     needs_server_update: bool,
+    pending_requests: Vec<PendingServerRequest>,
+    next_request_id: usize,
+    /// The entities showing the requests of context servers to the user.
+    request_handlers: HashSet<EntityId>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -397,6 +405,9 @@ impl ContextServerStore {
             server_ids: Default::default(),
             update_servers_task: None,
             context_server_factory,
+            pending_requests: Vec::new(),
+            next_request_id: 0,
+            request_handlers: HashSet::default(),
         };
         if maintain_server_loop {
             this.available_context_servers_changed(cx);
@@ -562,13 +573,14 @@ impl ContextServerStore {
         ) {
             self.stop_server(&id, cx).log_err();
         }
+        let capabilities = self.client_capabilities();
         let task = cx.spawn({
             let id = server.id();
             let server = server.clone();
             let configuration = configuration.clone();
 
             async move |this, cx| {
-                match server.clone().start(cx).await {
+                match server.clone().start(capabilities, cx).await {
                     Ok(_) => {
                         debug_assert!(server.client().is_some());
 
@@ -620,6 +632,7 @@ impl ContextServerStore {
             .remove(id)
             .context("Context server not found")?;
         drop(state);
+        self.cancel_pending_requests(id, cx);
         cx.emit(ServerStatusChangedEvent {
             server_id: id.clone(),
             status: ContextServerStatus::Stopped,
@@ -836,6 +849,11 @@ impl ContextServerStore {
         cx: &mut Context<Self>,
     ) {
         let status = ContextServerStatus::from_state(&state);
+        if let ContextServerState::Running { server, .. } = &state {
+            self.handle_server_requests(server, cx);
+        } else {
+            self.cancel_pending_requests(&id, cx);
+        }
         self.servers.insert(id.clone(), state);
         cx.emit(ServerStatusChangedEvent {
             server_id: id,
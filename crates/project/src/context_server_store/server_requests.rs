@@ -0,0 +1,286 @@
+//! Requests that context servers send to Zed, like sampling and elicitation requests.
+//!
+//! These requests are answered by the user, so they are kept in the [`ContextServerStore`]
+//! until the UI responds to them, until their server stops, or until no UI is left to show them.
+
+use anyhow::{Context as _, Result, anyhow};
+use context_server::{
+    ContextServer, ContextServerId,
+    types::{
+        ClientCapabilities, CreateMessageRequest, CreateMessageResult, ElicitationAction,
+        ElicitationCreateParams, ElicitationCreateResult, requests,
+    },
+};
+use futures::channel::oneshot;
+use gpui::{App, AppContext as _, Context, Entity, Task};
+use settings::{ContextServerSamplingPolicy, Settings as _};
+
+use super::{ContextServerStore, ContextServerStoreState};
+use crate::project_settings::ProjectSettings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ServerRequestId(pub usize);
+
+pub struct ServerRequest {
+    pub id: ServerRequestId,
+    pub server_id: ContextServerId,
+    pub kind: ServerRequestKind,
+}
+
+pub enum ServerRequestKind {
+    /// The server asks to generate a message with the user's language model.
+    Sampling {
+        params: CreateMessageRequest,
+        /// Whether the user allowed the message to be generated, either for this request or
+        /// for every request of the server.
+        approved: bool,
+    },
+    /// The server asks the user to fill in a form.
+    Elicitation { params: ElicitationCreateParams },
+}
+
+pub(super) struct PendingServerRequest {
+    request: ServerRequest,
+    responder: ServerRequestResponder,
+}
+
+enum ServerRequestResponder {
+    Sampling(oneshot::Sender<Result<CreateMessageResult>>),
+    Elicitation(oneshot::Sender<ElicitationCreateResult>),
+}
+
+impl ContextServerStore {
+    /// Returns the requests from context servers that are waiting for the user, oldest first.
+    pub fn pending_requests(&self) -> impl Iterator<Item = &ServerRequest> {
+        self.pending_requests.iter().map(|pending| &pending.request)
+    }
+
+    /// Returns whether the user allowed the server to generate messages with their language model.
+    ///
+    /// The policy is only read from the user's settings, as a project's own settings could
+    /// otherwise let its servers spend the user's tokens without asking.
+    pub fn sampling_policy(
+        &self,
+        server_id: &ContextServerId,
+        cx: &App,
+    ) -> ContextServerSamplingPolicy {
+        ProjectSettings::get_global(cx)
+            .context_server_sampling
+            .get(&server_id.0)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Allows a sampling request to run, once the user approved it.
+    pub fn approve_sampling_request(&mut self, id: ServerRequestId, cx: &mut Context<Self>) {
+        if let Some(PendingServerRequest {
+            request:
+                ServerRequest {
+                    kind: ServerRequestKind::Sampling { approved, .. },
+                    ..
+                },
+            ..
+        }) = self
+            .pending_requests
+            .iter_mut()
+            .find(|pending| pending.request.id == id)
+        {
+            *approved = true;
+            cx.notify();
+        }
+    }
+
+    /// Answers a sampling request with the generated message, or with the reason it couldn't be generated.
+    pub fn respond_to_sampling_request(
+        &mut self,
+        id: ServerRequestId,
+        result: Result<CreateMessageResult>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ServerRequestResponder::Sampling(tx)) = self.take_pending_request(id, cx) {
+            tx.send(result).ok();
+        }
+    }
+
+    pub fn respond_to_elicitation_request(
+        &mut self,
+        id: ServerRequestId,
+        result: ElicitationCreateResult,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ServerRequestResponder::Elicitation(tx)) = self.take_pending_request(id, cx) {
+            tx.send(result).ok();
+        }
+    }
+
+    fn take_pending_request(
+        &mut self,
+        id: ServerRequestId,
+        cx: &mut Context<Self>,
+    ) -> Option<ServerRequestResponder> {
+        let ix = self
+            .pending_requests
+            .iter()
+            .position(|pending| pending.request.id == id)?;
+        cx.notify();
+        Some(self.pending_requests.remove(ix).responder)
+    }
+
+    /// Registers an entity that shows the requests of context servers to the user, until it's
+    /// released.
+    ///
+    /// Servers are only told that they may send sampling and elicitation requests when they start
+    /// while a handler is registered, and the pending requests are cancelled once the last
+    /// handler is released, so that no request waits for an answer that can't come.
+    pub fn register_request_handler<T: 'static>(
+        &mut self,
+        handler: &Entity<T>,
+        cx: &mut Context<Self>,
+    ) {
+        let handler_id = handler.entity_id();
+        self.request_handlers.insert(handler_id);
+        cx.observe_release(handler, move |this, _, cx| {
+            this.request_handlers.remove(&handler_id);
+            if this.request_handlers.is_empty() && !this.pending_requests.is_empty() {
+                this.pending_requests.clear();
+                cx.notify();
+            }
+        })
+        .detach();
+    }
+
+    /// The capabilities advertised to the servers that start, which include the requests they
+    /// may send when someone can answer them.
+    pub(super) fn client_capabilities(&self) -> ClientCapabilities {
+        let handles_requests = !self.request_handlers.is_empty();
+        ClientCapabilities {
+            experimental: None,
+            sampling: handles_requests.then(|| serde_json::json!({})),
+            elicitation: handles_requests.then(|| serde_json::json!({})),
+            roots: None,
+        }
+    }
+
+    /// Drops the requests of a server that stopped, which cancels them.
+    pub(super) fn cancel_pending_requests(
+        &mut self,
+        server_id: &ContextServerId,
+        cx: &mut Context<Self>,
+    ) {
+        let request_count = self.pending_requests.len();
+        self.pending_requests
+            .retain(|pending| &pending.request.server_id != server_id);
+        if self.pending_requests.len() != request_count {
+            cx.notify();
+        }
+    }
+
+    /// Handles the sampling and elicitation requests of a server that started running.
+    ///
+    /// Headless stores have no user to answer these requests, so they leave them unhandled.
+    pub(super) fn handle_server_requests(
+        &mut self,
+        server: &ContextServer,
+        cx: &mut Context<Self>,
+    ) {
+        if let ContextServerStoreState::Local {
+            is_headless: true, ..
+        } = &self.state
+        {
+            return;
+        }
+        let Some(client) = server.client() else {
+            return;
+        };
+
+        client.on_request::<requests::CreateMessage>({
+            let this = cx.weak_entity();
+            let server_id = server.id();
+            move |params, cx| {
+                let this = this.clone();
+                let server_id = server_id.clone();
+                cx.spawn(async move |cx| {
+                    this.update(cx, |this, cx| this.request_sampling(server_id, params, cx))?
+                        .await
+                })
+            }
+        });
+        client.on_request::<requests::ElicitationCreate>({
+            let this = cx.weak_entity();
+            let server_id = server.id();
+            move |params, cx| {
+                let this = this.clone();
+                let server_id = server_id.clone();
+                cx.spawn(async move |cx| {
+                    let response = this.update(cx, |this, cx| {
+                        let (tx, rx) = oneshot::channel();
+                        if this.request_handlers.is_empty() {
+                            return rx;
+                        }
+                        this.push_pending_request(
+                            server_id,
+                            ServerRequestKind::Elicitation { params },
+                            ServerRequestResponder::Elicitation(tx),
+                            cx,
+                        );
+                        rx
+                    })?;
+                    Ok(response.await.unwrap_or(ElicitationCreateResult {
+                        action: ElicitationAction::Cancel,
+                        content: None,
+                    }))
+                })
+            }
+        });
+    }
+
+    fn request_sampling(
+        &mut self,
+        server_id: ContextServerId,
+        params: CreateMessageRequest,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<CreateMessageResult>> {
+        if self.request_handlers.is_empty() {
+            return Task::ready(Err(anyhow!(
+                "Sampling requests can't be answered right now"
+            )));
+        }
+        let approved = match self.sampling_policy(&server_id, cx) {
+            ContextServerSamplingPolicy::Ask => false,
+            ContextServerSamplingPolicy::Allow => true,
+            ContextServerSamplingPolicy::Deny => {
+                return Task::ready(Err(anyhow!(
+                    "Sampling is not allowed for the {server_id} context server"
+                )));
+            }
+        };
+        let (tx, rx) = oneshot::channel();
+        self.push_pending_request(
+            server_id,
+            ServerRequestKind::Sampling { params, approved },
+            ServerRequestResponder::Sampling(tx),
+            cx,
+        );
+        cx.background_spawn(async move { rx.await.context("Sampling request was cancelled")? })
+    }
+
+    fn push_pending_request(
+        &mut self,
+        server_id: ContextServerId,
+        kind: ServerRequestKind,
+        responder: ServerRequestResponder,
+        cx: &mut Context<Self>,
+    ) {
+        let id = ServerRequestId(self.next_request_id);
+        self.next_request_id += 1;
+        self.pending_requests.push(PendingServerRequest {
+            request: ServerRequest {
+                id,
+                server_id,
+                kind,
+            },
+            responder,
+        });
+        cx.notify();
+    }
+}
@@ -13,9 +13,10 @@ use rpc::{
     AnyProtoClient, TypedEnvelope,
     proto::{self},
 };
+use serde::{Deserialize, Serialize};
 use std::{hash::Hash, ops::Range, path::Path, sync::Arc, u32};
 use text::{Point, PointUtf16};
-use util::maybe;
+use util::{maybe, rel_path::RelPath};
 
 use crate::{ProjectPath, buffer_store::BufferStore, worktree_store::WorktreeStore};
 
@@ -152,6 +153,7 @@ pub struct BreakpointStore {
     buffer_store: Entity<BufferStore>,
     worktree_store: Entity<WorktreeStore>,
     breakpoints: BTreeMap<Arc<Path>, BreakpointsInFile>,
+    function_breakpoints: Vec<FunctionBreakpoint>,
     downstream_client: Option<(AnyProtoClient, u64)>,
     active_stack_frame: Option<ActiveStackFrame>,
     // E.g ssh
@@ -166,6 +168,7 @@ impl BreakpointStore {
     pub fn local(worktree_store: Entity<WorktreeStore>, buffer_store: Entity<BufferStore>) -> Self {
         BreakpointStore {
             breakpoints: BTreeMap::new(),
+            function_breakpoints: Vec::new(),
             mode: BreakpointStoreMode::Local,
             buffer_store,
             worktree_store,
@@ -182,6 +185,7 @@ impl BreakpointStore {
     ) -> Self {
         BreakpointStore {
             breakpoints: BTreeMap::new(),
+            function_breakpoints: Vec::new(),
             mode: BreakpointStoreMode::Remote(RemoteBreakpointStore {
                 upstream_client,
                 upstream_project_id,
@@ -606,6 +610,73 @@ impl BreakpointStore {
         let breakpoint_paths = self.breakpoints.keys().cloned().collect();
         self.breakpoints.clear();
         cx.emit(BreakpointStoreEvent::BreakpointsCleared(breakpoint_paths));
+        if !self.function_breakpoints.is_empty() {
+            self.function_breakpoints.clear();
+            cx.emit(BreakpointStoreEvent::FunctionBreakpointsUpdated);
+        }
+    }
+
+    pub fn function_breakpoints(&self) -> &[FunctionBreakpoint] {
+        &self.function_breakpoints
+    }
+
+    /// Adds a function breakpoint, replacing an existing one for the same function.
+    pub fn add_function_breakpoint(
+        &mut self,
+        breakpoint: FunctionBreakpoint,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(existing) = self
+            .function_breakpoints
+            .iter_mut()
+            .find(|existing| existing.name == breakpoint.name)
+        {
+            *existing = breakpoint;
+        } else {
+            self.function_breakpoints.push(breakpoint);
+        }
+        cx.emit(BreakpointStoreEvent::FunctionBreakpointsUpdated);
+        cx.notify();
+    }
+
+    pub fn edit_function_breakpoint(
+        &mut self,
+        name: &str,
+        edit_action: BreakpointEditAction,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ix) = self
+            .function_breakpoints
+            .iter()
+            .position(|breakpoint| breakpoint.name.as_ref() == name)
+        else {
+            return;
+        };
+
+        let breakpoint = &mut self.function_breakpoints[ix];
+        match edit_action {
+            BreakpointEditAction::Toggle => {
+                self.function_breakpoints.remove(ix);
+            }
+            BreakpointEditAction::InvertState => {
+                breakpoint.state = if breakpoint.state.is_enabled() {
+                    BreakpointState::Disabled
+                } else {
+                    BreakpointState::Enabled
+                };
+            }
+            BreakpointEditAction::EditCondition(condition) => {
+                breakpoint.condition = (!condition.is_empty()).then_some(condition);
+            }
+            BreakpointEditAction::EditHitCondition(hit_condition) => {
+                breakpoint.hit_condition = (!hit_condition.is_empty()).then_some(hit_condition);
+            }
+            // DAP has no notion of log messages for function breakpoints.
+            BreakpointEditAction::EditLogMessage(_) => return,
+        }
+
+        cx.emit(BreakpointStoreEvent::FunctionBreakpointsUpdated);
+        cx.notify();
     }
 
     pub fn breakpoints<'a>(
@@ -858,6 +929,180 @@ impl BreakpointStore {
         }
     }
 
+    pub fn with_serialized_function_breakpoints(
+        &mut self,
+        breakpoints: Vec<FunctionBreakpoint>,
+        cx: &mut Context<Self>,
+    ) {
+        if let BreakpointStoreMode::Local = &self.mode {
+            log::debug!("Deserialized {} function breakpoints", breakpoints.len());
+            self.function_breakpoints = breakpoints;
+            cx.notify();
+        }
+    }
+
+    /// Collects all source and function breakpoints into a [`BreakpointSet`].
+    ///
+    /// Paths of files within a visible worktree are stored relative to the worktree root,
+    /// so that the set can be imported into a checkout of the same project elsewhere.
+    pub fn export_breakpoints(&self, cx: &App) -> BreakpointSet {
+        let worktree_store = self.worktree_store.read(cx);
+        let source = self
+            .all_source_breakpoints(cx)
+            .into_iter()
+            .flat_map(|(abs_path, breakpoints)| {
+                let path = worktree_store
+                    .find_worktree(&abs_path, cx)
+                    .filter(|(worktree, _)| worktree.read(cx).is_visible())
+                    .map(|(_, relative_path)| relative_path.as_unix_str().to_owned())
+                    .unwrap_or_else(|| abs_path.to_string_lossy().into_owned());
+                breakpoints
+                    .into_iter()
+                    .sorted_by_key(|breakpoint| breakpoint.row)
+                    .map(move |breakpoint| ExportedSourceBreakpoint {
+                        path: path.clone(),
+                        line: breakpoint.row + 1,
+                        condition: breakpoint.condition.map(|s| s.to_string()),
+                        hit_condition: breakpoint.hit_condition.map(|s| s.to_string()),
+                        log_message: breakpoint.message.map(|s| s.to_string()),
+                        disabled: breakpoint.state.is_disabled(),
+                    })
+            })
+            .collect();
+        let functions = self
+            .function_breakpoints
+            .iter()
+            .map(|breakpoint| ExportedFunctionBreakpoint {
+                name: breakpoint.name.to_string(),
+                condition: breakpoint.condition.as_ref().map(|s| s.to_string()),
+                hit_condition: breakpoint.hit_condition.as_ref().map(|s| s.to_string()),
+                disabled: breakpoint.state.is_disabled(),
+            })
+            .collect();
+
+        BreakpointSet { source, functions }
+    }
+
+    /// Adds the breakpoints from a [`BreakpointSet`] to the existing ones.
+    ///
+    /// Source breakpoints on lines that already have a breakpoint are skipped. Relative paths
+    /// are resolved against the first visible worktree containing them.
+    pub fn import_breakpoints(
+        &mut self,
+        breakpoint_set: BreakpointSet,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        for breakpoint in breakpoint_set.functions {
+            self.add_function_breakpoint(
+                FunctionBreakpoint {
+                    name: breakpoint.name.into(),
+                    condition: breakpoint.condition.map(Into::into),
+                    hit_condition: breakpoint.hit_condition.map(Into::into),
+                    state: if breakpoint.disabled {
+                        BreakpointState::Disabled
+                    } else {
+                        BreakpointState::Enabled
+                    },
+                },
+                cx,
+            );
+        }
+
+        let mut unresolved_paths = Vec::new();
+        let mut breakpoints_by_path = BTreeMap::<Arc<Path>, Vec<ExportedSourceBreakpoint>>::new();
+        for breakpoint in breakpoint_set.source {
+            match self.resolve_exported_path(&breakpoint.path, cx) {
+                Some(abs_path) => breakpoints_by_path
+                    .entry(abs_path)
+                    .or_default()
+                    .push(breakpoint),
+                None => unresolved_paths.push(breakpoint.path),
+            }
+        }
+
+        let worktree_store = self.worktree_store.downgrade();
+        let buffer_store = self.buffer_store.downgrade();
+        cx.spawn(async move |this, cx| {
+            for (abs_path, breakpoints) in breakpoints_by_path {
+                let (worktree, relative_path) = worktree_store
+                    .update(cx, |this, cx| {
+                        this.find_or_create_worktree(&abs_path, false, cx)
+                    })?
+                    .await?;
+                let buffer = buffer_store
+                    .update(cx, |this, cx| {
+                        let path = ProjectPath {
+                            worktree_id: worktree.read(cx).id(),
+                            path: relative_path,
+                        };
+                        this.open_buffer(path, cx)
+                    })?
+                    .await
+                    .with_context(|| format!("opening {abs_path:?} to import breakpoints"))?;
+
+                this.update(cx, |this, cx| {
+                    let snapshot = buffer.read(cx).snapshot();
+                    for breakpoint in breakpoints {
+                        let row = breakpoint.line.saturating_sub(1);
+                        let point = PointUtf16::new(row, 0);
+                        if point > snapshot.max_point_utf16() {
+                            log::warn!("skipping an imported breakpoint that's out of range");
+                            continue;
+                        }
+                        if this.breakpoint_at_row(&abs_path, row, cx).is_some() {
+                            continue;
+                        }
+                        let breakpoint = BreakpointWithPosition {
+                            position: snapshot.anchor_after(point),
+                            bp: Breakpoint {
+                                message: breakpoint.log_message.map(Into::into),
+                                condition: breakpoint.condition.map(Into::into),
+                                hit_condition: breakpoint.hit_condition.map(Into::into),
+                                state: if breakpoint.disabled {
+                                    BreakpointState::Disabled
+                                } else {
+                                    BreakpointState::Enabled
+                                },
+                            },
+                        };
+                        this.toggle_breakpoint(
+                            buffer.clone(),
+                            breakpoint,
+                            BreakpointEditAction::Toggle,
+                            cx,
+                        );
+                    }
+                })?;
+            }
+
+            if !unresolved_paths.is_empty() {
+                unresolved_paths.sort();
+                unresolved_paths.dedup();
+                anyhow::bail!(
+                    "could not find {} in the project",
+                    unresolved_paths.join(", ")
+                );
+            }
+            Ok(())
+        })
+    }
+
+    fn resolve_exported_path(&self, path: &str, cx: &App) -> Option<Arc<Path>> {
+        if Path::new(path).is_absolute() {
+            return Some(Path::new(path).into());
+        }
+        let relative_path = RelPath::unix(path).ok()?;
+        self.worktree_store
+            .read(cx)
+            .visible_worktrees(cx)
+            .find_map(|worktree| {
+                let worktree = worktree.read(cx);
+                worktree
+                    .entry_for_path(relative_path)
+                    .map(|_| worktree.absolutize(relative_path).into())
+            })
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) fn breakpoint_paths(&self) -> Vec<Arc<Path>> {
         self.breakpoints.keys().cloned().collect()
@@ -875,6 +1120,7 @@ pub enum BreakpointStoreEvent {
     ClearDebugLines,
     BreakpointsUpdated(Arc<Path>, BreakpointUpdatedReason),
     BreakpointsCleared(Vec<Arc<Path>>),
+    FunctionBreakpointsUpdated,
 }
 
 impl EventEmitter<BreakpointStoreEvent> for BreakpointStore {}
@@ -1036,3 +1282,74 @@ impl From<SourceBreakpoint> for dap::SourceBreakpoint {
         }
     }
 }
+
+/// Breakpoint that stops whenever a function with the given name is entered,
+/// without having to open the file it is defined in.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct FunctionBreakpoint {
+    pub name: Arc<str>,
+    pub condition: Option<Arc<str>>,
+    pub hit_condition: Option<Arc<str>>,
+    pub state: BreakpointState,
+}
+
+impl FunctionBreakpoint {
+    pub fn new(name: impl Into<Arc<str>>) -> Self {
+        Self {
+            name: name.into(),
+            condition: None,
+            hit_condition: None,
+            state: BreakpointState::Enabled,
+        }
+    }
+}
+
+impl From<FunctionBreakpoint> for dap::FunctionBreakpoint {
+    fn from(bp: FunctionBreakpoint) -> Self {
+        Self {
+            name: String::from(bp.name.as_ref()),
+            condition: bp
+                .condition
+                .map(|condition| String::from(condition.as_ref())),
+            hit_condition: bp
+                .hit_condition
+                .map(|hit_condition| String::from(hit_condition.as_ref())),
+        }
+    }
+}
+
+/// A set of breakpoints in a form that can be written to a JSON file and shared.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BreakpointSet {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub source: Vec<ExportedSourceBreakpoint>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub functions: Vec<ExportedFunctionBreakpoint>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedSourceBreakpoint {
+    /// Path relative to the worktree root, or an absolute path for files outside of the project.
+    pub path: String,
+    /// 1-based line number.
+    pub line: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hit_condition: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_message: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedFunctionBreakpoint {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hit_condition: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+}
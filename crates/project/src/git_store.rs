@@ -29,10 +29,12 @@ use git::{
     blame::Blame,
     parse_git_remote_url,
     repository::{
-        Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions, DiffType, FetchOptions,
-        GitRepository, GitRepositoryCheckpoint, GraphCommitData, InitialGraphCommitData, LogOrder,
-        LogSource, PushOptions, Remote, RemoteCommandOutput, RepoPath, ResetMode,
-        UpstreamTrackingStatus, Worktree as GitWorktree,
+        ApplyCommitOutcome, BisectState, BisectTerm, Branch, CommitDetails, CommitDiff, CommitFile,
+        CommitOptions, DiffType, FetchOptions, GitRepository, GitRepositoryCheckpoint,
+        GraphCommitData, InitialGraphCommitData, LogOrder, LogSource, MergeStages, PushOptions,
+        RebaseAction, RebaseOperation, RebaseProgress, RebaseTodoEntry, Remote,
+        RemoteCommandOutput, RepoPath, ResetMode, Submodule, Tag, UpstreamTrackingStatus,
+        Worktree as GitWorktree,
     },
     stash::{GitStash, StashEntry},
     status::{
@@ -53,7 +55,10 @@ use pending_op::{PendingOp, PendingOpId, PendingOps, PendingOpsSummary};
 use postage::stream::Stream as _;
 use rpc::{
     AnyProtoClient, TypedEnvelope,
-    proto::{self, git_reset, split_repository_update},
+    proto::{
+        self, git_bisect_mark, git_rebase_operation, git_rebase_todo_entry, git_reset,
+        split_repository_update,
+    },
 };
 use serde::Deserialize;
 use settings::WorktreeId;
@@ -188,6 +193,15 @@ pub struct GitStoreCheckpoint {
     checkpoints_by_work_dir_abs_path: HashMap<Arc<Path>, GitRepositoryCheckpoint>,
 }
 
+impl GitStoreCheckpoint {
+    /// Returns the commit that captured the repository at the given working directory.
+    pub fn commit_sha(&self, work_dir_abs_path: &Path) -> Option<Oid> {
+        self.checkpoints_by_work_dir_abs_path
+            .get(work_dir_abs_path)
+            .map(|checkpoint| checkpoint.commit_sha)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct StatusEntry {
     pub repo_path: RepoPath,
@@ -272,6 +286,11 @@ pub struct RepositorySnapshot {
     pub head_commit: Option<CommitDetails>,
     pub scan_id: u64,
     pub merge: MergeDetails,
+    /// The progress of the rebase in progress, if any.
+    pub rebase: Option<RebaseProgress>,
+    /// The state of the bisect in progress, if any.
+    pub bisect: Option<BisectState>,
+    pub submodules: Arc<[Submodule]>,
     pub remote_origin_url: Option<String>,
     pub remote_upstream_url: Option<String>,
     pub stash_entries: GitStash,
@@ -394,6 +413,9 @@ pub enum RepositoryState {
 pub enum RepositoryEvent {
     StatusesChanged,
     MergeHeadsChanged,
+    RebaseProgressChanged,
+    BisectStateChanged,
+    SubmodulesChanged,
     BranchChanged,
     StashEntriesChanged,
     PendingOpsChanged { pending_ops: SumTree<PendingOps> },
@@ -520,9 +542,27 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_commit);
         client.add_entity_request_handler(Self::handle_run_hook);
         client.add_entity_request_handler(Self::handle_reset);
+        client.add_entity_request_handler(Self::handle_rebase_commits);
+        client.add_entity_request_handler(Self::handle_rebase_interactive);
+        client.add_entity_request_handler(Self::handle_rebase_operation);
+        client.add_entity_request_handler(Self::handle_cherry_pick);
+        client.add_entity_request_handler(Self::handle_revert);
+        client.add_entity_request_handler(Self::handle_get_tags);
+        client.add_entity_request_handler(Self::handle_create_tag);
+        client.add_entity_request_handler(Self::handle_delete_tag);
+        client.add_entity_request_handler(Self::handle_checkout_tag);
+        client.add_entity_request_handler(Self::handle_push_tag);
+        client.add_entity_request_handler(Self::handle_bisect_start);
+        client.add_entity_request_handler(Self::handle_bisect_mark);
+        client.add_entity_request_handler(Self::handle_bisect_reset);
+        client.add_entity_request_handler(Self::handle_submodule_init);
+        client.add_entity_request_handler(Self::handle_submodule_sync);
+        client.add_entity_request_handler(Self::handle_submodule_update);
+        client.add_entity_request_handler(Self::handle_load_merge_stages);
         client.add_entity_request_handler(Self::handle_show);
         client.add_entity_request_handler(Self::handle_load_commit_diff);
         client.add_entity_request_handler(Self::handle_file_history);
+        client.add_entity_request_handler(Self::handle_log);
         client.add_entity_request_handler(Self::handle_checkout_files);
         client.add_entity_request_handler(Self::handle_open_commit_message_buffer);
         client.add_entity_request_handler(Self::handle_set_index_text);
@@ -1742,6 +1782,19 @@ impl GitStore {
             .max_by_key(|(repo, _)| repo.read(cx).work_directory_abs_path.clone())
     }
 
+    /// Returns the submodule checked out at the given path, as recorded by its superproject.
+    ///
+    /// Unlike [`Self::repository_and_path_for_project_path`], this doesn't resolve to the
+    /// repository of the submodule itself.
+    pub fn submodule_for_project_path(&self, path: &ProjectPath, cx: &App) -> Option<Submodule> {
+        let abs_path = self.worktree_store.read(cx).absolutize(path, cx)?;
+        self.repositories.values().find_map(|repo| {
+            let repo = repo.read(cx);
+            let repo_path = repo.abs_path_to_repo_path(&abs_path)?;
+            repo.submodule_for_path(&repo_path).cloned()
+        })
+    }
+
     pub fn git_init(
         &self,
         path: Arc<Path>,
@@ -2466,6 +2519,27 @@ impl GitStore {
         })
     }
 
+    async fn handle_load_merge_stages(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitLoadMergeStages>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitLoadMergeStagesResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = RepoPath::from_proto(&envelope.payload.path)?;
+
+        let stages = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.load_merge_stages(path)
+            })
+            .await??;
+        Ok(proto::GitLoadMergeStagesResponse {
+            base: stages.base,
+            ours: stages.ours,
+            theirs: stages.theirs,
+        })
+    }
+
     async fn handle_load_commit_diff(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::LoadCommitDiff>,
@@ -2527,6 +2601,43 @@ impl GitStore {
         })
     }
 
+    async fn handle_log(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitLog>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitLogResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = envelope
+            .payload
+            .path
+            .as_deref()
+            .map(RepoPath::from_proto)
+            .transpose()?;
+        let range = envelope.payload.range;
+        let limit = envelope.payload.limit as usize;
+
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.log(range, path, limit)
+            })
+            .await??;
+
+        Ok(proto::GitLogResponse {
+            entries: entries
+                .into_iter()
+                .map(|entry| proto::FileHistoryEntry {
+                    sha: entry.sha.to_string(),
+                    subject: entry.subject.to_string(),
+                    message: entry.message.to_string(),
+                    commit_timestamp: entry.commit_timestamp,
+                    author_name: entry.author_name.to_string(),
+                    author_email: entry.author_email.to_string(),
+                })
+                .collect(),
+        })
+    }
+
     async fn handle_reset(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::GitReset>,
@@ -2548,237 +2659,588 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
-    async fn handle_checkout_files(
+    async fn handle_rebase_commits(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitCheckoutFiles>,
+        envelope: TypedEnvelope<proto::GitRebaseCommits>,
         mut cx: AsyncApp,
-    ) -> Result<proto::Ack> {
+    ) -> Result<proto::GitRebaseCommitsResponse> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
-        let paths = envelope
-            .payload
-            .paths
-            .iter()
-            .map(|s| RepoPath::from_proto(s))
-            .collect::<Result<Vec<_>>>()?;
 
-        repository_handle
-            .update(&mut cx, |repository_handle, cx| {
-                repository_handle.checkout_files(&envelope.payload.commit, paths, cx)
+        let commits = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_commits(envelope.payload.base)
             })
-            .await?;
-        Ok(proto::Ack {})
+            .await??;
+        Ok(proto::GitRebaseCommitsResponse {
+            commits: commits.iter().map(commit_details_to_proto).collect(),
+        })
     }
 
-    async fn handle_open_commit_message_buffer(
+    async fn handle_rebase_interactive(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::OpenCommitMessageBuffer>,
+        envelope: TypedEnvelope<proto::GitRebaseInteractive>,
         mut cx: AsyncApp,
-    ) -> Result<proto::OpenBufferResponse> {
+    ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
-        let buffer = repository
-            .update(&mut cx, |repository, cx| {
-                repository.open_commit_buffer(None, this.read(cx).buffer_store.clone(), cx)
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let todo = envelope
+            .payload
+            .todo
+            .into_iter()
+            .map(|entry| RebaseTodoEntry {
+                action: proto_to_rebase_action(entry.action()),
+                sha: entry.sha.into(),
+                message: entry.message.map(SharedString::from),
             })
-            .await?;
+            .collect();
 
-        let buffer_id = buffer.read_with(&cx, |buffer, _| buffer.remote_id());
-        this.update(&mut cx, |this, cx| {
-            this.buffer_store.update(cx, |buffer_store, cx| {
-                buffer_store
-                    .create_buffer_for_peer(
-                        &buffer,
-                        envelope.original_sender_id.unwrap_or(envelope.sender_id),
-                        cx,
-                    )
-                    .detach_and_log_err(cx);
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_interactive(envelope.payload.base, todo)
             })
-        });
-
-        Ok(proto::OpenBufferResponse {
-            buffer_id: buffer_id.to_proto(),
-        })
+            .await??;
+        Ok(proto::Ack {})
     }
 
-    async fn handle_askpass(
+    async fn handle_rebase_operation(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::AskPassRequest>,
+        envelope: TypedEnvelope<proto::GitRebaseOperation>,
         mut cx: AsyncApp,
-    ) -> Result<proto::AskPassResponse> {
+    ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
-
-        let delegates = cx.update(|cx| repository.read(cx).askpass_delegates.clone());
-        let Some(mut askpass) = delegates.lock().remove(&envelope.payload.askpass_id) else {
-            debug_panic!("no askpass found");
-            anyhow::bail!("no askpass found");
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let operation = match envelope.payload.operation() {
+            git_rebase_operation::Operation::Continue => RebaseOperation::Continue,
+            git_rebase_operation::Operation::Skip => RebaseOperation::Skip,
+            git_rebase_operation::Operation::Abort => RebaseOperation::Abort,
         };
 
-        let response = askpass
-            .ask_password(envelope.payload.prompt)
-            .await
-            .ok_or_else(|| anyhow::anyhow!("askpass cancelled"))?;
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_operation(operation)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
 
-        delegates
-            .lock()
-            .insert(envelope.payload.askpass_id, askpass);
+    async fn handle_cherry_pick(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCherryPick>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitApplyCommitResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-        // In fact, we don't quite know what we're doing here, as we're sending askpass password unencrypted, but..
-        Ok(proto::AskPassResponse {
-            response: response.decrypt(IKnowWhatIAmDoingAndIHaveReadTheDocs)?,
+        let outcome = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.cherry_pick(envelope.payload.commit)
+            })
+            .await??;
+        Ok(proto::GitApplyCommitResponse {
+            conflicted: outcome == ApplyCommitOutcome::Conflicted,
         })
     }
 
-    async fn handle_check_for_pushed_commits(
+    async fn handle_revert(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::CheckForPushedCommits>,
+        envelope: TypedEnvelope<proto::GitRevert>,
         mut cx: AsyncApp,
-    ) -> Result<proto::CheckForPushedCommitsResponse> {
+    ) -> Result<proto::GitApplyCommitResponse> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-        let branches = repository_handle
+        let outcome = repository_handle
             .update(&mut cx, |repository_handle, _| {
-                repository_handle.check_for_pushed_commits()
+                repository_handle.revert(envelope.payload.commit)
             })
             .await??;
-        Ok(proto::CheckForPushedCommitsResponse {
-            pushed_to: branches
-                .into_iter()
-                .map(|commit| commit.to_string())
-                .collect(),
+        Ok(proto::GitApplyCommitResponse {
+            conflicted: outcome == ApplyCommitOutcome::Conflicted,
         })
     }
 
-    async fn handle_git_diff(
+    async fn handle_bisect_start(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitDiff>,
+        envelope: TypedEnvelope<proto::GitBisectStart>,
         mut cx: AsyncApp,
-    ) -> Result<proto::GitDiffResponse> {
+    ) -> Result<proto::GitBisectStateResponse> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
-        let diff_type = match envelope.payload.diff_type() {
-            proto::git_diff::DiffType::HeadToIndex => DiffType::HeadToIndex,
-            proto::git_diff::DiffType::HeadToWorktree => DiffType::HeadToWorktree,
-            proto::git_diff::DiffType::MergeBase => {
-                let base_ref = envelope
-                    .payload
-                    .merge_base_ref
-                    .ok_or_else(|| anyhow!("merge_base_ref is required for MergeBase diff type"))?;
-                DiffType::MergeBase {
-                    base_ref: base_ref.into(),
-                }
-            }
-        };
 
-        let mut diff = repository_handle
-            .update(&mut cx, |repository_handle, cx| {
-                repository_handle.diff(diff_type, cx)
+        let state = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_start(envelope.payload.bad, envelope.payload.good)
             })
             .await??;
-        const ONE_MB: usize = 1_000_000;
-        if diff.len() > ONE_MB {
-            diff = diff.chars().take(ONE_MB).collect()
-        }
-
-        Ok(proto::GitDiffResponse { diff })
+        Ok(proto::GitBisectStateResponse {
+            state: state.as_ref().map(bisect_state_to_proto),
+        })
     }
 
-    async fn handle_tree_diff(
+    async fn handle_bisect_mark(
         this: Entity<Self>,
-        request: TypedEnvelope<proto::GetTreeDiff>,
+        envelope: TypedEnvelope<proto::GitBisectMark>,
         mut cx: AsyncApp,
-    ) -> Result<proto::GetTreeDiffResponse> {
-        let repository_id = RepositoryId(request.payload.repository_id);
-        let diff_type = if request.payload.is_merge {
-            DiffTreeType::MergeBase {
-                base: request.payload.base.into(),
-                head: request.payload.head.into(),
-            }
-        } else {
-            DiffTreeType::Since {
-                base: request.payload.base.into(),
-                head: request.payload.head.into(),
-            }
+    ) -> Result<proto::GitBisectStateResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let term = match envelope.payload.term() {
+            git_bisect_mark::Term::Good => BisectTerm::Good,
+            git_bisect_mark::Term::Bad => BisectTerm::Bad,
+            git_bisect_mark::Term::Skip => BisectTerm::Skip,
         };
 
-        let diff = this
-            .update(&mut cx, |this, cx| {
-                let repository = this.repositories().get(&repository_id)?;
-                Some(repository.update(cx, |repo, cx| repo.diff_tree(diff_type, cx)))
+        let state = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_mark(term, envelope.payload.commit)
             })
-            .context("missing repository")?
             .await??;
-
-        Ok(proto::GetTreeDiffResponse {
-            entries: diff
-                .entries
-                .into_iter()
-                .map(|(path, status)| proto::TreeDiffStatus {
-                    path: path.as_ref().to_proto(),
-                    status: match status {
-                        TreeDiffStatus::Added {} => proto::tree_diff_status::Status::Added.into(),
-                        TreeDiffStatus::Modified { .. } => {
-                            proto::tree_diff_status::Status::Modified.into()
-                        }
-                        TreeDiffStatus::Deleted { .. } => {
-                            proto::tree_diff_status::Status::Deleted.into()
-                        }
-                    },
-                    oid: match status {
-                        TreeDiffStatus::Deleted { old } | TreeDiffStatus::Modified { old } => {
-                            Some(old.to_string())
-                        }
-                        TreeDiffStatus::Added => None,
-                    },
-                })
-                .collect(),
+        Ok(proto::GitBisectStateResponse {
+            state: state.as_ref().map(bisect_state_to_proto),
         })
     }
 
-    async fn handle_get_blob_content(
+    async fn handle_bisect_reset(
         this: Entity<Self>,
-        request: TypedEnvelope<proto::GetBlobContent>,
+        envelope: TypedEnvelope<proto::GitBisectReset>,
         mut cx: AsyncApp,
-    ) -> Result<proto::GetBlobContentResponse> {
-        let oid = git::Oid::from_str(&request.payload.oid)?;
-        let repository_id = RepositoryId(request.payload.repository_id);
-        let content = this
-            .update(&mut cx, |this, cx| {
-                let repository = this.repositories().get(&repository_id)?;
-                Some(repository.update(cx, |repo, cx| repo.load_blob_content(oid, cx)))
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_reset()
             })
-            .context("missing repository")?
-            .await?;
-        Ok(proto::GetBlobContentResponse { content })
+            .await??;
+        Ok(proto::Ack {})
     }
 
-    async fn handle_open_unstaged_diff(
+    async fn handle_get_tags(
         this: Entity<Self>,
-        request: TypedEnvelope<proto::OpenUnstagedDiff>,
+        envelope: TypedEnvelope<proto::GitGetTags>,
         mut cx: AsyncApp,
-    ) -> Result<proto::OpenUnstagedDiffResponse> {
-        let buffer_id = BufferId::new(request.payload.buffer_id)?;
-        let diff = this
-            .update(&mut cx, |this, cx| {
-                let buffer = this.buffer_store.read(cx).get(buffer_id)?;
-                Some(this.open_unstaged_diff(buffer, cx))
-            })
-            .context("missing buffer")?
-            .await?;
-        this.update(&mut cx, |this, _| {
-            let shared_diffs = this
-                .shared_diffs
-                .entry(request.original_sender_id.unwrap_or(request.sender_id))
-                .or_default();
-            shared_diffs.entry(buffer_id).or_default().unstaged = Some(diff.clone());
-        });
-        let staged_text = diff.read_with(&cx, |diff, cx| diff.base_text_string(cx));
-        Ok(proto::OpenUnstagedDiffResponse { staged_text })
+    ) -> Result<proto::GitTagsResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let tags = repository_handle
+            .update(&mut cx, |repository_handle, _| repository_handle.tags())
+            .await??;
+
+        Ok(proto::GitTagsResponse {
+            tags: tags.iter().map(tag_to_proto).collect(),
+        })
     }
 
-    async fn handle_open_uncommitted_diff(
+    async fn handle_create_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCreateTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.create_tag(
+                    envelope.payload.name,
+                    envelope.payload.target,
+                    envelope.payload.message,
+                )
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_delete_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitDeleteTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.delete_tag(envelope.payload.name)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_checkout_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCheckoutTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.checkout_tag(envelope.payload.name)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_push_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitPushTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let askpass = make_remote_delegate(
+            this,
+            envelope.payload.project_id,
+            repository_id,
+            envelope.payload.askpass_id,
+            &mut cx,
+        );
+
+        let remote_output = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.push_tag(
+                    envelope.payload.tag_name.into(),
+                    envelope.payload.remote_name.into(),
+                    askpass,
+                )
+            })
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: remote_output.stdout,
+            stderr: remote_output.stderr,
+        })
+    }
+
+    async fn handle_submodule_init(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitSubmoduleInit>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let paths = envelope
+            .payload
+            .paths
+            .iter()
+            .map(|s| RepoPath::from_proto(s))
+            .collect::<Result<Vec<_>>>()?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.submodule_init(paths)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_submodule_sync(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitSubmoduleSync>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let paths = envelope
+            .payload
+            .paths
+            .iter()
+            .map(|s| RepoPath::from_proto(s))
+            .collect::<Result<Vec<_>>>()?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.submodule_sync(paths)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_submodule_update(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitSubmoduleUpdate>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let paths = envelope
+            .payload
+            .paths
+            .iter()
+            .map(|s| RepoPath::from_proto(s))
+            .collect::<Result<Vec<_>>>()?;
+
+        let askpass = make_remote_delegate(
+            this,
+            envelope.payload.project_id,
+            repository_id,
+            envelope.payload.askpass_id,
+            &mut cx,
+        );
+
+        let remote_output = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.submodule_update(paths, askpass)
+            })
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: remote_output.stdout,
+            stderr: remote_output.stderr,
+        })
+    }
+
+    async fn handle_checkout_files(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCheckoutFiles>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let paths = envelope
+            .payload
+            .paths
+            .iter()
+            .map(|s| RepoPath::from_proto(s))
+            .collect::<Result<Vec<_>>>()?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.checkout_files(&envelope.payload.commit, paths, cx)
+            })
+            .await?;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_open_commit_message_buffer(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::OpenCommitMessageBuffer>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::OpenBufferResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let buffer = repository
+            .update(&mut cx, |repository, cx| {
+                repository.open_commit_buffer(None, this.read(cx).buffer_store.clone(), cx)
+            })
+            .await?;
+
+        let buffer_id = buffer.read_with(&cx, |buffer, _| buffer.remote_id());
+        this.update(&mut cx, |this, cx| {
+            this.buffer_store.update(cx, |buffer_store, cx| {
+                buffer_store
+                    .create_buffer_for_peer(
+                        &buffer,
+                        envelope.original_sender_id.unwrap_or(envelope.sender_id),
+                        cx,
+                    )
+                    .detach_and_log_err(cx);
+            })
+        });
+
+        Ok(proto::OpenBufferResponse {
+            buffer_id: buffer_id.to_proto(),
+        })
+    }
+
+    async fn handle_askpass(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::AskPassRequest>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::AskPassResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let delegates = cx.update(|cx| repository.read(cx).askpass_delegates.clone());
+        let Some(mut askpass) = delegates.lock().remove(&envelope.payload.askpass_id) else {
+            debug_panic!("no askpass found");
+            anyhow::bail!("no askpass found");
+        };
+
+        let response = askpass
+            .ask_password(envelope.payload.prompt)
+            .await
+            .ok_or_else(|| anyhow::anyhow!("askpass cancelled"))?;
+
+        delegates
+            .lock()
+            .insert(envelope.payload.askpass_id, askpass);
+
+        // In fact, we don't quite know what we're doing here, as we're sending askpass password unencrypted, but..
+        Ok(proto::AskPassResponse {
+            response: response.decrypt(IKnowWhatIAmDoingAndIHaveReadTheDocs)?,
+        })
+    }
+
+    async fn handle_check_for_pushed_commits(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::CheckForPushedCommits>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::CheckForPushedCommitsResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let branches = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.check_for_pushed_commits()
+            })
+            .await??;
+        Ok(proto::CheckForPushedCommitsResponse {
+            pushed_to: branches
+                .into_iter()
+                .map(|commit| commit.to_string())
+                .collect(),
+        })
+    }
+
+    async fn handle_git_diff(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitDiff>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitDiffResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let diff_type = match envelope.payload.diff_type() {
+            proto::git_diff::DiffType::HeadToIndex => DiffType::HeadToIndex,
+            proto::git_diff::DiffType::HeadToWorktree => DiffType::HeadToWorktree,
+            proto::git_diff::DiffType::MergeBase => {
+                let base_ref = envelope
+                    .payload
+                    .merge_base_ref
+                    .ok_or_else(|| anyhow!("merge_base_ref is required for MergeBase diff type"))?;
+                DiffType::MergeBase {
+                    base_ref: base_ref.into(),
+                }
+            }
+            proto::git_diff::DiffType::Refs => {
+                let base_ref = envelope
+                    .payload
+                    .base_ref
+                    .ok_or_else(|| anyhow!("base_ref is required for Refs diff type"))?;
+                DiffType::Refs {
+                    base_ref: base_ref.into(),
+                    head_ref: envelope.payload.head_ref.map(Into::into),
+                    path: envelope
+                        .payload
+                        .path
+                        .as_deref()
+                        .map(RepoPath::from_proto)
+                        .transpose()?,
+                }
+            }
+        };
+
+        let mut diff = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.diff(diff_type, cx)
+            })
+            .await??;
+        const ONE_MB: usize = 1_000_000;
+        if diff.len() > ONE_MB {
+            diff = diff.chars().take(ONE_MB).collect()
+        }
+
+        Ok(proto::GitDiffResponse { diff })
+    }
+
+    async fn handle_tree_diff(
+        this: Entity<Self>,
+        request: TypedEnvelope<proto::GetTreeDiff>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GetTreeDiffResponse> {
+        let repository_id = RepositoryId(request.payload.repository_id);
+        let diff_type = if request.payload.is_merge {
+            DiffTreeType::MergeBase {
+                base: request.payload.base.into(),
+                head: request.payload.head.into(),
+            }
+        } else {
+            DiffTreeType::Since {
+                base: request.payload.base.into(),
+                head: request.payload.head.into(),
+            }
+        };
+
+        let diff = this
+            .update(&mut cx, |this, cx| {
+                let repository = this.repositories().get(&repository_id)?;
+                Some(repository.update(cx, |repo, cx| repo.diff_tree(diff_type, cx)))
+            })
+            .context("missing repository")?
+            .await??;
+
+        Ok(proto::GetTreeDiffResponse {
+            entries: diff
+                .entries
+                .into_iter()
+                .map(|(path, status)| proto::TreeDiffStatus {
+                    path: path.as_ref().to_proto(),
+                    status: match status {
+                        TreeDiffStatus::Added {} => proto::tree_diff_status::Status::Added.into(),
+                        TreeDiffStatus::Modified { .. } => {
+                            proto::tree_diff_status::Status::Modified.into()
+                        }
+                        TreeDiffStatus::Deleted { .. } => {
+                            proto::tree_diff_status::Status::Deleted.into()
+                        }
+                    },
+                    oid: match status {
+                        TreeDiffStatus::Deleted { old } | TreeDiffStatus::Modified { old } => {
+                            Some(old.to_string())
+                        }
+                        TreeDiffStatus::Added => None,
+                    },
+                })
+                .collect(),
+        })
+    }
+
+    async fn handle_get_blob_content(
+        this: Entity<Self>,
+        request: TypedEnvelope<proto::GetBlobContent>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GetBlobContentResponse> {
+        let oid = git::Oid::from_str(&request.payload.oid)?;
+        let repository_id = RepositoryId(request.payload.repository_id);
+        let content = this
+            .update(&mut cx, |this, cx| {
+                let repository = this.repositories().get(&repository_id)?;
+                Some(repository.update(cx, |repo, cx| repo.load_blob_content(oid, cx)))
+            })
+            .context("missing repository")?
+            .await?;
+        Ok(proto::GetBlobContentResponse { content })
+    }
+
+    async fn handle_open_unstaged_diff(
+        this: Entity<Self>,
+        request: TypedEnvelope<proto::OpenUnstagedDiff>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::OpenUnstagedDiffResponse> {
+        let buffer_id = BufferId::new(request.payload.buffer_id)?;
+        let diff = this
+            .update(&mut cx, |this, cx| {
+                let buffer = this.buffer_store.read(cx).get(buffer_id)?;
+                Some(this.open_unstaged_diff(buffer, cx))
+            })
+            .context("missing buffer")?
+            .await?;
+        this.update(&mut cx, |this, _| {
+            let shared_diffs = this
+                .shared_diffs
+                .entry(request.original_sender_id.unwrap_or(request.sender_id))
+                .or_default();
+            shared_diffs.entry(buffer_id).or_default().unstaged = Some(diff.clone());
+        });
+        let staged_text = diff.read_with(&cx, |diff, cx| diff.base_text_string(cx));
+        Ok(proto::OpenUnstagedDiffResponse { staged_text })
+    }
+
+    async fn handle_open_uncommitted_diff(
         this: Entity<Self>,
         request: TypedEnvelope<proto::OpenUncommittedDiff>,
         mut cx: AsyncApp,
@@ -3439,6 +3901,9 @@ impl RepositorySnapshot {
             head_commit: None,
             scan_id: 0,
             merge: Default::default(),
+            rebase: None,
+            bisect: None,
+            submodules: Arc::default(),
             remote_origin_url: None,
             remote_upstream_url: None,
             stash_entries: Default::default(),
@@ -3475,6 +3940,9 @@ impl RepositorySnapshot {
                 .iter()
                 .map(stash_to_proto)
                 .collect(),
+            rebase: self.rebase.as_ref().map(rebase_progress_to_proto),
+            bisect: self.bisect.as_ref().map(bisect_state_to_proto),
+            submodules: self.submodules.iter().map(submodule_to_proto).collect(),
             remote_upstream_url: self.remote_upstream_url.clone(),
             remote_origin_url: self.remote_origin_url.clone(),
         }
@@ -3546,6 +4014,9 @@ impl RepositorySnapshot {
                 .iter()
                 .map(stash_to_proto)
                 .collect(),
+            rebase: self.rebase.as_ref().map(rebase_progress_to_proto),
+            bisect: self.bisect.as_ref().map(bisect_state_to_proto),
+            submodules: self.submodules.iter().map(submodule_to_proto).collect(),
             remote_upstream_url: self.remote_upstream_url.clone(),
             remote_origin_url: self.remote_origin_url.clone(),
         }
@@ -3565,6 +4036,12 @@ impl RepositorySnapshot {
             .cloned()
     }
 
+    pub fn submodule_for_path(&self, path: &RepoPath) -> Option<&Submodule> {
+        self.submodules
+            .iter()
+            .find(|submodule| &submodule.path == path)
+    }
+
     pub fn abs_path_to_repo_path(&self, abs_path: &Path) -> Option<RepoPath> {
         Self::abs_path_to_repo_path_inner(&self.work_directory_abs_path, abs_path, self.path_style)
     }
@@ -4250,6 +4727,324 @@ impl Repository {
         })
     }
 
+    /// Lists the commits between `base` and HEAD that an interactive rebase would replay.
+    pub fn rebase_commits(
+        &mut self,
+        base: String,
+    ) -> oneshot::Receiver<Result<Vec<CommitDetails>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.rebase_commits(base).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitRebaseCommits {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            base,
+                        })
+                        .await?;
+                    Ok(response
+                        .commits
+                        .iter()
+                        .map(proto_to_commit_details)
+                        .collect())
+                }
+            }
+        })
+    }
+
+    pub fn rebase_interactive(
+        &mut self,
+        base: String,
+        todo: Vec<RebaseTodoEntry>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(Some("git rebase".into()), move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => backend.rebase_interactive(base, todo, environment).await,
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    client
+                        .request(proto::GitRebaseInteractive {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            base,
+                            todo: todo
+                                .into_iter()
+                                .map(|entry| proto::GitRebaseTodoEntry {
+                                    sha: entry.sha.to_string(),
+                                    action: rebase_action_to_proto(entry.action).into(),
+                                    message: entry.message.map(|message| message.to_string()),
+                                })
+                                .collect(),
+                        })
+                        .await?;
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    /// Continues, skips the current step of, or aborts the rebase in progress.
+    pub fn rebase_operation(
+        &mut self,
+        operation: RebaseOperation,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let status = match operation {
+            RebaseOperation::Continue => "git rebase --continue",
+            RebaseOperation::Skip => "git rebase --skip",
+            RebaseOperation::Abort => "git rebase --abort",
+        };
+        self.send_job(Some(status.into()), move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => backend.rebase_operation(operation, environment).await,
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    client
+                        .request(proto::GitRebaseOperation {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            operation: match operation {
+                                RebaseOperation::Continue => {
+                                    git_rebase_operation::Operation::Continue.into()
+                                }
+                                RebaseOperation::Skip => {
+                                    git_rebase_operation::Operation::Skip.into()
+                                }
+                                RebaseOperation::Abort => {
+                                    git_rebase_operation::Operation::Abort.into()
+                                }
+                            },
+                        })
+                        .await?;
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    /// Applies the changes introduced by `commit` onto the current branch as a new commit.
+    pub fn cherry_pick(&mut self, commit: String) -> oneshot::Receiver<Result<ApplyCommitOutcome>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git cherry-pick {commit}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.cherry_pick(commit, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        let response = client
+                            .request(proto::GitCherryPick {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commit,
+                            })
+                            .await?;
+                        Ok(apply_commit_outcome_from_proto(response))
+                    }
+                }
+            },
+        )
+    }
+
+    /// Creates a new commit on the current branch undoing the changes introduced by `commit`.
+    pub fn revert(&mut self, commit: String) -> oneshot::Receiver<Result<ApplyCommitOutcome>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git revert {commit}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.revert(commit, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        let response = client
+                            .request(proto::GitRevert {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commit,
+                            })
+                            .await?;
+                        Ok(apply_commit_outcome_from_proto(response))
+                    }
+                }
+            },
+        )
+    }
+
+    /// Starts bisecting between `bad` and the `good` commits, either of which can be marked later.
+    ///
+    /// Resolves to the state of the bisect once it has started.
+    pub fn bisect_start(
+        &mut self,
+        bad: Option<String>,
+        good: Vec<String>,
+    ) -> oneshot::Receiver<Result<Option<BisectState>>> {
+        let id = self.id;
+        self.send_job(
+            Some("git bisect start".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        backend.bisect_start(bad, good, environment).await?;
+                        Ok(backend.bisect_state().await)
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        let response = client
+                            .request(proto::GitBisectStart {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                bad,
+                                good,
+                            })
+                            .await?;
+                        Ok(response.state.as_ref().map(proto_to_bisect_state))
+                    }
+                }
+            },
+        )
+    }
+
+    /// Marks `commit`, or the commit being tested when `None`, and checks out the next one.
+    ///
+    /// Resolves to the state of the bisect after the mark.
+    pub fn bisect_mark(
+        &mut self,
+        term: BisectTerm,
+        commit: Option<String>,
+    ) -> oneshot::Receiver<Result<Option<BisectState>>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git bisect {}", term.as_str()).into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        backend.bisect_mark(term, commit, environment).await?;
+                        Ok(backend.bisect_state().await)
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        let response = client
+                            .request(proto::GitBisectMark {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                term: match term {
+                                    BisectTerm::Good => git_bisect_mark::Term::Good.into(),
+                                    BisectTerm::Bad => git_bisect_mark::Term::Bad.into(),
+                                    BisectTerm::Skip => git_bisect_mark::Term::Skip.into(),
+                                },
+                                commit,
+                            })
+                            .await?;
+                        Ok(response.state.as_ref().map(proto_to_bisect_state))
+                    }
+                }
+            },
+        )
+    }
+
+    /// Ends the bisect in progress and returns to the commit it was started from.
+    pub fn bisect_reset(&mut self) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some("git bisect reset".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_reset(environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitBisectReset {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    /// Registers the URLs of the given submodules, or of all of them when `paths` is empty.
+    pub fn submodule_init(&mut self, paths: Vec<RepoPath>) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some("git submodule init".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.submodule_init(paths, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitSubmoduleInit {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                paths: paths.iter().map(|path| path.to_proto()).collect(),
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    /// Copies the URLs of the given submodules from `.gitmodules` into the repository's config.
+    pub fn submodule_sync(&mut self, paths: Vec<RepoPath>) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some("git submodule sync".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.submodule_sync(paths, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitSubmoduleSync {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                paths: paths.iter().map(|path| path.to_proto()).collect(),
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
     pub fn show(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDetails>> {
         let id = self.id;
         self.send_job(None, move |git_repo, _cx| async move {
@@ -4278,6 +5073,32 @@ impl Repository {
         })
     }
 
+    /// Loads the base, ours and theirs versions of a conflicted file from the index.
+    pub fn load_merge_stages(&mut self, path: RepoPath) -> oneshot::Receiver<Result<MergeStages>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.load_merge_stages(path).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitLoadMergeStages {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            path: path.to_proto(),
+                        })
+                        .await?;
+                    Ok(MergeStages {
+                        base: response.base,
+                        ours: response.ours,
+                        theirs: response.theirs,
+                    })
+                }
+            }
+        })
+    }
+
     pub fn load_commit_diff(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDiff>> {
         let id = self.id;
         self.send_job(None, move |git_repo, cx| async move {
@@ -4321,6 +5142,45 @@ impl Repository {
         self.file_history_paginated(path, 0, None)
     }
 
+    pub fn log(
+        &mut self,
+        range: Option<String>,
+        path: Option<RepoPath>,
+        limit: usize,
+    ) -> oneshot::Receiver<Result<Vec<git::repository::FileHistoryEntry>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.log(range, path, limit).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                    let response = client
+                        .request(proto::GitLog {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            range,
+                            path: path.map(|path| path.to_proto()),
+                            limit: limit as u64,
+                        })
+                        .await?;
+                    Ok(response
+                        .entries
+                        .into_iter()
+                        .map(|entry| git::repository::FileHistoryEntry {
+                            sha: entry.sha.into(),
+                            subject: entry.subject.into(),
+                            message: entry.message.into(),
+                            commit_timestamp: entry.commit_timestamp,
+                            author_name: entry.author_name.into(),
+                            author_email: entry.author_email.into(),
+                        })
+                        .collect())
+                }
+            }
+        })
+    }
+
     pub fn file_history_paginated(
         &mut self,
         path: RepoPath,
@@ -5662,26 +6522,34 @@ impl Repository {
                     backend.diff(diff_type).await
                 }
                 RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
-                    let (proto_diff_type, merge_base_ref) = match &diff_type {
+                    let mut request = proto::GitDiff {
+                        project_id: project_id.0,
+                        repository_id: id.to_proto(),
+                        ..Default::default()
+                    };
+                    match &diff_type {
                         DiffType::HeadToIndex => {
-                            (proto::git_diff::DiffType::HeadToIndex.into(), None)
+                            request.set_diff_type(proto::git_diff::DiffType::HeadToIndex);
                         }
                         DiffType::HeadToWorktree => {
-                            (proto::git_diff::DiffType::HeadToWorktree.into(), None)
+                            request.set_diff_type(proto::git_diff::DiffType::HeadToWorktree);
                         }
-                        DiffType::MergeBase { base_ref } => (
-                            proto::git_diff::DiffType::MergeBase.into(),
-                            Some(base_ref.to_string()),
-                        ),
-                    };
-                    let response = client
-                        .request(proto::GitDiff {
-                            project_id: project_id.0,
-                            repository_id: id.to_proto(),
-                            diff_type: proto_diff_type,
-                            merge_base_ref,
-                        })
-                        .await?;
+                        DiffType::MergeBase { base_ref } => {
+                            request.set_diff_type(proto::git_diff::DiffType::MergeBase);
+                            request.merge_base_ref = Some(base_ref.to_string());
+                        }
+                        DiffType::Refs {
+                            base_ref,
+                            head_ref,
+                            path,
+                        } => {
+                            request.set_diff_type(proto::git_diff::DiffType::Refs);
+                            request.base_ref = Some(base_ref.to_string());
+                            request.head_ref = head_ref.as_ref().map(ToString::to_string);
+                            request.path = path.as_ref().map(|path| path.to_proto());
+                        }
+                    }
+                    let response = client.request(request).await?;
 
                     Ok(response.diff)
                 }
@@ -5768,6 +6636,223 @@ impl Repository {
         )
     }
 
+    pub fn tags(&mut self) -> oneshot::Receiver<Result<Vec<Tag>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.tags().await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitGetTags {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+
+                    Ok(response.tags.iter().map(proto_to_tag).collect())
+                }
+            }
+        })
+    }
+
+    /// Creates a tag pointing at `target`, or at `HEAD` when no target is given.
+    ///
+    /// The tag is annotated when a message is given and lightweight otherwise.
+    pub fn create_tag(
+        &mut self,
+        name: String,
+        target: Option<String>,
+        message: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let status = if message.is_some() {
+            format!("git tag -a {name}")
+        } else {
+            format!("git tag {name}")
+        };
+        self.send_job(Some(status.into()), move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => backend.create_tag(name, target, message, environment).await,
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    client
+                        .request(proto::GitCreateTag {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            name,
+                            target,
+                            message,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn delete_tag(&mut self, name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git tag -d {name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                        backend.delete_tag(name).await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitDeleteTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    /// Checks out the commit the tag points at, detaching `HEAD`.
+    pub fn checkout_tag(&mut self, name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git checkout {name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                        backend.checkout_tag(name).await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitCheckoutTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn push_tag(
+        &mut self,
+        tag_name: SharedString,
+        remote: SharedString,
+        askpass: AskPassDelegate,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git push {remote} refs/tags/{tag_name}").into()),
+            move |git_repo, cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        backend
+                            .push_tag(
+                                tag_name.to_string(),
+                                remote.to_string(),
+                                askpass,
+                                environment.clone(),
+                                cx,
+                            )
+                            .await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        askpass_delegates.lock().insert(askpass_id, askpass);
+                        let _defer = util::defer(|| {
+                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                            debug_assert!(askpass_delegate.is_some());
+                        });
+                        let response = client
+                            .request(proto::GitPushTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                tag_name: tag_name.to_string(),
+                                remote_name: remote.to_string(),
+                                askpass_id,
+                            })
+                            .await
+                            .context("sending push tag request")?;
+
+                        Ok(RemoteCommandOutput {
+                            stdout: response.stdout,
+                            stderr: response.stderr,
+                        })
+                    }
+                }
+            },
+        )
+    }
+
+    /// Checks out the recorded commit of the given submodules, initializing and cloning them
+    /// first if needed.
+    pub fn submodule_update(
+        &mut self,
+        paths: Vec<RepoPath>,
+        askpass: AskPassDelegate,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let id = self.id;
+
+        self.send_job(
+            Some("git submodule update".into()),
+            move |git_repo, cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        backend
+                            .submodule_update(paths, askpass, environment.clone(), cx)
+                            .await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        askpass_delegates.lock().insert(askpass_id, askpass);
+                        let _defer = util::defer(|| {
+                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                            debug_assert!(askpass_delegate.is_some());
+                        });
+                        let response = client
+                            .request(proto::GitSubmoduleUpdate {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                paths: paths.iter().map(|path| path.to_proto()).collect(),
+                                askpass_id,
+                            })
+                            .await
+                            .context("sending submodule update request")?;
+
+                        Ok(RemoteCommandOutput {
+                            stdout: response.stdout,
+                            stderr: response.stderr,
+                        })
+                    }
+                }
+            },
+        )
+    }
+
     pub fn rename_branch(
         &mut self,
         branch: String,
@@ -5881,6 +6966,25 @@ impl Repository {
             cx.emit(RepositoryEvent::StashEntriesChanged)
         }
         self.snapshot.stash_entries = new_stash_entries;
+        let new_rebase = update.rebase.as_ref().map(proto_to_rebase_progress);
+        if self.snapshot.rebase != new_rebase {
+            cx.emit(RepositoryEvent::RebaseProgressChanged)
+        }
+        self.snapshot.rebase = new_rebase;
+        let new_bisect = update.bisect.as_ref().map(proto_to_bisect_state);
+        if self.snapshot.bisect != new_bisect {
+            cx.emit(RepositoryEvent::BisectStateChanged)
+        }
+        self.snapshot.bisect = new_bisect;
+        let new_submodules = update
+            .submodules
+            .iter()
+            .filter_map(|submodule| proto_to_submodule(submodule).log_err())
+            .collect::<Arc<[_]>>();
+        if self.snapshot.submodules != new_submodules {
+            cx.emit(RepositoryEvent::SubmodulesChanged)
+        }
+        self.snapshot.submodules = new_submodules;
         self.snapshot.remote_upstream_url = update.remote_upstream_url;
         self.snapshot.remote_origin_url = update.remote_origin_url;
 
@@ -6566,6 +7670,112 @@ fn proto_to_branch(proto: &proto::Branch) -> git::repository::Branch {
     }
 }
 
+fn rebase_progress_to_proto(rebase: &RebaseProgress) -> proto::RebaseProgress {
+    proto::RebaseProgress {
+        step: rebase.step as u64,
+        total: rebase.total as u64,
+        head_name: rebase.head_name.as_ref().map(|name| name.to_string()),
+    }
+}
+
+fn proto_to_rebase_progress(proto: &proto::RebaseProgress) -> RebaseProgress {
+    RebaseProgress {
+        step: proto.step as usize,
+        total: proto.total as usize,
+        head_name: proto.head_name.clone().map(SharedString::from),
+    }
+}
+
+fn bisect_state_to_proto(bisect: &BisectState) -> proto::BisectState {
+    proto::BisectState {
+        bad: bisect.bad.map(|sha| sha.to_string()),
+        good: bisect.good.iter().map(|sha| sha.to_string()).collect(),
+        skipped: bisect.skipped.iter().map(|sha| sha.to_string()).collect(),
+        remaining: bisect.remaining as u64,
+        first_bad: bisect.first_bad.map(|sha| sha.to_string()),
+    }
+}
+
+fn proto_to_bisect_state(proto: &proto::BisectState) -> BisectState {
+    let parse = |sha: &String| Oid::from_str(sha).log_err();
+    BisectState {
+        bad: proto.bad.as_ref().and_then(parse),
+        good: proto.good.iter().filter_map(parse).collect(),
+        skipped: proto.skipped.iter().filter_map(parse).collect(),
+        remaining: proto.remaining as usize,
+        first_bad: proto.first_bad.as_ref().and_then(parse),
+    }
+}
+
+fn submodule_to_proto(submodule: &Submodule) -> proto::Submodule {
+    proto::Submodule {
+        name: submodule.name.to_string(),
+        path: submodule.path.to_proto(),
+        url: submodule.url.as_ref().map(|url| url.to_string()),
+        recorded_sha: submodule.recorded_sha.map(|sha| sha.to_string()),
+        checked_out_sha: submodule.checked_out_sha.map(|sha| sha.to_string()),
+        has_changes: submodule.has_changes,
+    }
+}
+
+fn proto_to_submodule(proto: &proto::Submodule) -> Result<Submodule> {
+    let parse = |sha: &String| Oid::from_str(sha).log_err();
+    Ok(Submodule {
+        name: proto.name.clone().into(),
+        path: RepoPath::from_proto(&proto.path)?,
+        url: proto.url.clone().map(SharedString::from),
+        recorded_sha: proto.recorded_sha.as_ref().and_then(parse),
+        checked_out_sha: proto.checked_out_sha.as_ref().and_then(parse),
+        has_changes: proto.has_changes,
+    })
+}
+
+fn rebase_action_to_proto(action: RebaseAction) -> git_rebase_todo_entry::Action {
+    match action {
+        RebaseAction::Pick => git_rebase_todo_entry::Action::Pick,
+        RebaseAction::Reword => git_rebase_todo_entry::Action::Reword,
+        RebaseAction::Squash => git_rebase_todo_entry::Action::Squash,
+        RebaseAction::Fixup => git_rebase_todo_entry::Action::Fixup,
+        RebaseAction::Drop => git_rebase_todo_entry::Action::Drop,
+    }
+}
+
+fn proto_to_rebase_action(action: git_rebase_todo_entry::Action) -> RebaseAction {
+    match action {
+        git_rebase_todo_entry::Action::Pick => RebaseAction::Pick,
+        git_rebase_todo_entry::Action::Reword => RebaseAction::Reword,
+        git_rebase_todo_entry::Action::Squash => RebaseAction::Squash,
+        git_rebase_todo_entry::Action::Fixup => RebaseAction::Fixup,
+        git_rebase_todo_entry::Action::Drop => RebaseAction::Drop,
+    }
+}
+
+fn tag_to_proto(tag: &Tag) -> proto::GitTag {
+    proto::GitTag {
+        name: tag.name.to_string(),
+        sha: tag.sha.to_string(),
+        annotation: tag.annotation.as_deref().map(str::to_string),
+        timestamp: tag.timestamp,
+    }
+}
+
+fn proto_to_tag(proto: &proto::GitTag) -> Tag {
+    Tag {
+        name: proto.name.clone().into(),
+        sha: proto.sha.clone().into(),
+        annotation: proto.annotation.clone().map(Into::into),
+        timestamp: proto.timestamp,
+    }
+}
+
+fn apply_commit_outcome_from_proto(response: proto::GitApplyCommitResponse) -> ApplyCommitOutcome {
+    if response.conflicted {
+        ApplyCommitOutcome::Conflicted
+    } else {
+        ApplyCommitOutcome::Applied
+    }
+}
+
 fn commit_details_to_proto(commit: &CommitDetails) -> proto::GitCommitDetails {
     proto::GitCommitDetails {
         sha: commit.sha.to_string(),
@@ -6619,6 +7829,26 @@ async fn compute_snapshot(
         events.push(RepositoryEvent::MergeHeadsChanged);
     }
 
+    let rebase = backend.rebase_progress().await;
+    if rebase != prev_snapshot.rebase {
+        events.push(RepositoryEvent::RebaseProgressChanged);
+    }
+
+    let bisect = backend.bisect_state().await;
+    if bisect != prev_snapshot.bisect {
+        events.push(RepositoryEvent::BisectStateChanged);
+    }
+
+    let submodules: Arc<[Submodule]> = backend
+        .submodules()
+        .await
+        .log_err()
+        .unwrap_or_default()
+        .into();
+    if submodules != prev_snapshot.submodules {
+        events.push(RepositoryEvent::SubmodulesChanged);
+    }
+
     if statuses_by_path != prev_snapshot.statuses_by_path {
         events.push(RepositoryEvent::StatusesChanged)
     }
@@ -6645,6 +7875,9 @@ async fn compute_snapshot(
         branch,
         head_commit,
         merge: merge_details,
+        rebase,
+        bisect,
+        submodules,
         remote_origin_url,
         remote_upstream_url,
         stash_entries,
@@ -9,6 +9,7 @@
 //! - [`LspStore`], which unifies the two under one consistent interface for interacting with language servers.
 //!
 //! Most of the interesting work happens at the local layer, as bulk of the complexity is with managing the lifecycle of language servers. The actual implementation of the LSP protocol is handled by [`lsp`] crate.
+mod call_hierarchy;
 pub mod clangd_ext;
 mod code_lens;
 mod document_colors;
@@ -19,7 +20,10 @@ pub mod json_language_server_ext;
 pub mod log_store;
 pub mod lsp_ext_command;
 pub mod rust_analyzer_ext;
+mod selection_ranges;
 mod semantic_tokens;
+mod task_diagnostics;
+mod type_hierarchy;
 pub mod vue_language_server_ext;
 
 use self::code_lens::CodeLensData;
@@ -153,6 +157,7 @@ pub use prettier::FORMAT_SUFFIX as TEST_PRETTIER_FORMAT_SUFFIX;
 pub use semantic_tokens::{
     BufferSemanticToken, BufferSemanticTokens, RefreshForServer, SemanticTokenStylizer, TokenType,
 };
+pub use task_diagnostics::{TASK_DIAGNOSTICS_SERVER_ID, TaskProblem};
 
 pub use worktree::{
     Entry, EntryKind, FS_WATCH_LATENCY, File, LocalWorktree, PathChange, ProjectEntryId,
@@ -315,6 +320,9 @@ pub struct LocalLspStore {
             )>,
         >,
     >,
+    /// Diagnostics produced by tasks' problem matchers, keyed by the task label.
+    task_diagnostics:
+        HashMap<SharedString, HashMap<PathBuf, Vec<DiagnosticEntry<Unclipped<PointUtf16>>>>>,
     buffer_snapshots: HashMap<BufferId, HashMap<LanguageServerId, Vec<LspBufferSnapshot>>>, // buffer_id -> server_id -> vec of snapshots
     _subscription: gpui::Subscription,
     lsp_tree: LanguageServerTree,
@@ -4202,6 +4210,7 @@ impl LspStore {
                 yarn,
                 next_diagnostic_group_id: Default::default(),
                 diagnostics: Default::default(),
+                task_diagnostics: Default::default(),
                 _subscription: cx.on_app_quit(|this, _| {
                     this.as_local_mut()
                         .unwrap()
@@ -4849,6 +4858,7 @@ impl LspStore {
                 request.check_capabilities(AdapterServerCapabilities {
                     server_capabilities: capabilities.clone(),
                     code_action_kinds: None,
+                    type_hierarchy_provider: false,
                 })
             },
             cx,
@@ -5271,6 +5281,119 @@ impl LspStore {
         }
     }
 
+    /// Asks the language server for the edits that renaming the symbol at `position` would make,
+    /// without making them. Returns `None` if no language server can rename the symbol.
+    ///
+    /// Only local projects can propose edits, since remote language servers apply theirs.
+    pub fn propose_rename(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: PointUtf16,
+        new_name: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<ProposedWorkspaceEdit>>> {
+        let Some(local) = self.as_local() else {
+            return Task::ready(Err(anyhow!("only local projects can propose renames")));
+        };
+        let request = PerformRename {
+            position,
+            new_name,
+            push_to_history: false,
+        };
+        let Some(language_server) = buffer.update(cx, |buffer, cx| {
+            local
+                .language_servers_for_buffer(buffer, cx)
+                .find(|(_, server)| {
+                    request.check_capabilities(server.adapter_server_capabilities())
+                })
+                .map(|(_, server)| server.clone())
+        }) else {
+            return Task::ready(Ok(None));
+        };
+        let Some(file) = File::from_dyn(buffer.read(cx).file()).and_then(File::as_local) else {
+            return Task::ready(Ok(None));
+        };
+        let params = request.to_lsp(&file.abs_path(cx), buffer.read(cx), &language_server, cx);
+        let request_timeout = ProjectSettings::get_global(cx)
+            .global_lsp_settings
+            .get_request_timeout();
+
+        cx.background_spawn(async move {
+            let edit = language_server
+                .request::<lsp::request::Rename>(params?, request_timeout)
+                .await
+                .into_response()?;
+            Ok(edit.map(|edit| ProposedWorkspaceEdit {
+                server_id: language_server.server_id(),
+                edit,
+            }))
+        })
+    }
+
+    /// Resolves the edits that the code action would make, without making them. Returns `None`
+    /// if the code action runs a command instead, whose edits are only known once it runs, or
+    /// doesn't do anything.
+    ///
+    /// Only local projects can propose edits, since remote language servers apply theirs.
+    pub fn propose_code_action(
+        &self,
+        buffer: Entity<Buffer>,
+        mut action: CodeAction,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<ProposedWorkspaceEdit>>> {
+        if self.as_local().is_none() {
+            return Task::ready(Err(anyhow!("only local projects can propose code actions")));
+        }
+        let Some((language_server, request_timeout)) = buffer.update(cx, |buffer, cx| {
+            let request_timeout = ProjectSettings::get_global(cx)
+                .global_lsp_settings
+                .get_request_timeout();
+            self.language_server_for_local_buffer(buffer, action.server_id, cx)
+                .map(|(_, server)| (server.clone(), request_timeout))
+        }) else {
+            return Task::ready(Ok(None));
+        };
+
+        cx.background_spawn(async move {
+            LocalLspStore::try_resolve_code_action(&language_server, &mut action, request_timeout)
+                .await
+                .context("resolving a code action")?;
+            Ok(action
+                .lsp_action
+                .edit()
+                .filter(|edit| edit.changes.is_some() || edit.document_changes.is_some())
+                .map(|edit| ProposedWorkspaceEdit {
+                    server_id: language_server.server_id(),
+                    edit: edit.clone(),
+                }))
+        })
+    }
+
+    /// Applies the edits that a language server proposed.
+    pub fn apply_workspace_edit(
+        &self,
+        proposed_edit: ProposedWorkspaceEdit,
+        push_to_history: bool,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<ProjectTransaction>> {
+        let Some(language_server) = self.language_server_for_id(proposed_edit.server_id) else {
+            return Task::ready(Err(anyhow!(
+                "language server {} is not running",
+                proposed_edit.server_id
+            )));
+        };
+        cx.spawn(async move |this, cx| {
+            LocalLspStore::deserialize_workspace_edit(
+                this.upgrade().context("no app present")?,
+                proposed_edit.edit,
+                push_to_history,
+                language_server,
+                cx,
+            )
+            .await
+        })
+    }
+
     pub fn apply_code_action(
         &self,
         buffer_handle: Entity<Buffer>,
@@ -8940,6 +9063,103 @@ impl LspStore {
                 )
                 .await?;
             }
+            Request::PrepareCallHierarchy(prepare_call_hierarchy) => {
+                let position = prepare_call_hierarchy
+                    .position
+                    .clone()
+                    .and_then(deserialize_anchor);
+                Self::query_lsp_locally::<PrepareCallHierarchy>(
+                    lsp_store,
+                    server_id,
+                    sender_id,
+                    lsp_request_id,
+                    prepare_call_hierarchy,
+                    position,
+                    &mut cx,
+                )
+                .await?;
+            }
+            Request::GetIncomingCalls(get_incoming_calls) => {
+                Self::query_lsp_locally::<GetIncomingCalls>(
+                    lsp_store,
+                    server_id,
+                    sender_id,
+                    lsp_request_id,
+                    get_incoming_calls,
+                    None,
+                    &mut cx,
+                )
+                .await?;
+            }
+            Request::GetOutgoingCalls(get_outgoing_calls) => {
+                Self::query_lsp_locally::<GetOutgoingCalls>(
+                    lsp_store,
+                    server_id,
+                    sender_id,
+                    lsp_request_id,
+                    get_outgoing_calls,
+                    None,
+                    &mut cx,
+                )
+                .await?;
+            }
+            Request::PrepareTypeHierarchy(prepare_type_hierarchy) => {
+                let position = prepare_type_hierarchy
+                    .position
+                    .clone()
+                    .and_then(deserialize_anchor);
+                Self::query_lsp_locally::<PrepareTypeHierarchy>(
+                    lsp_store,
+                    server_id,
+                    sender_id,
+                    lsp_request_id,
+                    prepare_type_hierarchy,
+                    position,
+                    &mut cx,
+                )
+                .await?;
+            }
+            Request::GetSupertypes(get_supertypes) => {
+                Self::query_lsp_locally::<GetSupertypes>(
+                    lsp_store,
+                    server_id,
+                    sender_id,
+                    lsp_request_id,
+                    get_supertypes,
+                    None,
+                    &mut cx,
+                )
+                .await?;
+            }
+            Request::GetSubtypes(get_subtypes) => {
+                Self::query_lsp_locally::<GetSubtypes>(
+                    lsp_store,
+                    server_id,
+                    sender_id,
+                    lsp_request_id,
+                    get_subtypes,
+                    None,
+                    &mut cx,
+                )
+                .await?;
+            }
+            Request::GetSelectionRanges(get_selection_ranges) => {
+                let position = get_selection_ranges
+                    .positions
+                    .first()
+                    .cloned()
+                    .and_then(deserialize_anchor);
+                Self::query_lsp_locally::<GetSelectionRanges>(
+                    lsp_store,
+                    server_id,
+                    sender_id,
+                    lsp_request_id,
+                    get_selection_ranges,
+                    position,
+                    &mut cx,
+                )
+                .await?;
+            }
             Request::InlayHints(inlay_hints) => {
                 let query_start = inlay_hints
                     .start
@@ -12529,6 +12749,20 @@ impl LspStore {
                     });
                     notify_server_capabilities_updated(&server, cx);
                 }
+                "textDocument/prepareCallHierarchy" => {
+                    let options = parse_register_capabilities(reg)?;
+                    let provider = match options {
+                        OneOf::Left(value) => lsp::CallHierarchyServerCapability::Simple(value),
+                        OneOf::Right(caps) => caps,
+                    };
+                    server.update_capabilities(|capabilities| {
+                        capabilities.call_hierarchy_provider = Some(provider);
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/prepareTypeHierarchy" => {
+                    server.set_type_hierarchy_provider(true);
+                }
                 "textDocument/foldingRange" => {
                     let options = parse_register_capabilities(reg)?;
                     let provider = match options {
@@ -12540,6 +12774,17 @@ impl LspStore {
                     });
                     notify_server_capabilities_updated(&server, cx);
                 }
+                "textDocument/selectionRange" => {
+                    let options = parse_register_capabilities(reg)?;
+                    let provider = match options {
+                        OneOf::Left(value) => lsp::SelectionRangeProviderCapability::Simple(value),
+                        OneOf::Right(caps) => caps,
+                    };
+                    server.update_capabilities(|capabilities| {
+                        capabilities.selection_range_provider = Some(provider);
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
                 _ => log::warn!("unhandled capability registration: {reg:?}"),
             }
         }
@@ -12743,6 +12988,21 @@ impl LspStore {
                     });
                     notify_server_capabilities_updated(&server, cx);
                 }
+                "textDocument/prepareCallHierarchy" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.call_hierarchy_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/prepareTypeHierarchy" => {
+                    server.set_type_hierarchy_provider(false);
+                }
+                "textDocument/selectionRange" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.selection_range_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
                 _ => log::warn!("unhandled capability unregistration: {unreg:?}"),
             }
         }
@@ -13408,6 +13668,53 @@ pub enum LanguageServerToQuery {
     Other(LanguageServerId),
 }
 
+/// Edits that a language server proposed, which are only made once
+/// [`LspStore::apply_workspace_edit`] applies them.
+#[derive(Clone, Debug)]
+pub struct ProposedWorkspaceEdit {
+    pub server_id: LanguageServerId,
+    pub edit: lsp::WorkspaceEdit,
+}
+
+impl ProposedWorkspaceEdit {
+    /// Returns the paths of the files that the edit changes, creates, renames or deletes.
+    pub fn abs_paths(&self) -> Vec<PathBuf> {
+        let mut uris = Vec::new();
+        if let Some(changes) = &self.edit.changes {
+            uris.extend(changes.keys());
+        }
+        match &self.edit.document_changes {
+            Some(lsp::DocumentChanges::Edits(edits)) => {
+                uris.extend(edits.iter().map(|edit| &edit.text_document.uri));
+            }
+            Some(lsp::DocumentChanges::Operations(operations)) => {
+                for operation in operations {
+                    match operation {
+                        lsp::DocumentChangeOperation::Edit(edit) => {
+                            uris.push(&edit.text_document.uri)
+                        }
+                        lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Create(op)) => {
+                            uris.push(&op.uri)
+                        }
+                        lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Rename(op)) => {
+                            uris.push(&op.old_uri);
+                            uris.push(&op.new_uri);
+                        }
+                        lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Delete(op)) => {
+                            uris.push(&op.uri)
+                        }
+                    }
+                }
+            }
+            None => {}
+        }
+        uris.into_iter()
+            .filter_map(|uri| uri.to_file_path().ok())
+            .unique()
+            .collect()
+    }
+}
+
 #[derive(Default)]
 struct RenamePathsWatchedForServer {
     did_rename: Vec<RenameActionPredicate>,
@@ -0,0 +1,77 @@
+use std::ops::Range;
+
+use anyhow::Result;
+use futures::future::join_all;
+use gpui::{Context, Entity, Task};
+use language::{Anchor, Buffer, PointUtf16};
+use settings::Settings as _;
+
+use crate::lsp_command::{GetSelectionRanges, LspCommand as _};
+use crate::lsp_store::{LanguageServerToQuery, LspStore};
+use crate::project_settings::ProjectSettings;
+
+impl LspStore {
+    /// Queries the first capable language server for the ranges enclosing each position,
+    /// ordered from the innermost to the outermost one.
+    ///
+    /// The result contains one entry per position, or is empty when no language server
+    /// for the buffer supports selection ranges.
+    pub fn selection_ranges(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<PointUtf16>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Vec<Range<Anchor>>>>> {
+        let request = GetSelectionRanges { positions };
+        if let Some((upstream_client, project_id)) = self.upstream_client() {
+            if !self.is_capable_for_proto_request(buffer, &request, cx) {
+                return Task::ready(Ok(Vec::new()));
+            }
+
+            let request_timeout = ProjectSettings::get_global(cx)
+                .global_lsp_settings
+                .get_request_timeout();
+            let request_task = upstream_client.request_lsp(
+                project_id,
+                None,
+                request_timeout,
+                cx.background_executor().clone(),
+                request.to_proto(project_id, buffer.read(cx)),
+            );
+            let buffer = buffer.clone();
+            cx.spawn(async move |weak_lsp_store, cx| {
+                let Some(lsp_store) = weak_lsp_store.upgrade() else {
+                    return Ok(Vec::new());
+                };
+                let Some(responses) = request_task.await? else {
+                    return Ok(Vec::new());
+                };
+                let selection_ranges = join_all(responses.payload.into_iter().map(|response| {
+                    request.clone().response_from_proto(
+                        response.response,
+                        lsp_store.clone(),
+                        buffer.clone(),
+                        cx.clone(),
+                    )
+                }))
+                .await;
+
+                // Selection ranges from different servers cannot be merged meaningfully,
+                // so use the first server that produced any.
+                Ok(selection_ranges
+                    .into_iter()
+                    .collect::<Result<Vec<_>>>()?
+                    .into_iter()
+                    .find(|selection_ranges| !selection_ranges.is_empty())
+                    .unwrap_or_default())
+            })
+        } else {
+            self.request_lsp(
+                buffer.clone(),
+                LanguageServerToQuery::FirstCapable,
+                request,
+                cx,
+            )
+        }
+    }
+}
@@ -268,6 +268,7 @@ impl LspStore {
                     let capabilities = AdapterServerCapabilities {
                         server_capabilities: self.lsp_server_capabilities.get(&server_id)?.clone(),
                         code_action_kinds: None,
+                        type_hierarchy_provider: false,
                     };
                     let request_task = match self.semantic_tokens_result_id(server_id, buffer, cx) {
                         Some(result_id) => {
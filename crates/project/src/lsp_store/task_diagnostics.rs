@@ -0,0 +1,112 @@
+use std::path::PathBuf;
+
+use anyhow::{Context as _, Result};
+use collections::{HashMap, HashSet};
+use gpui::{Context, SharedString};
+use language::{Diagnostic, DiagnosticEntry, DiagnosticSourceKind, PointUtf16, Unclipped};
+use lsp::{DiagnosticSeverity, LanguageServerId, NumberOrString};
+use task::{Problem, ProblemSeverity};
+use util::{ResultExt as _, post_inc};
+
+use crate::LspStore;
+
+/// Diagnostics found by tasks' problem matchers are stored under this id,
+/// so that they never get replaced by the ones coming from the actual language servers.
+pub const TASK_DIAGNOSTICS_SERVER_ID: LanguageServerId = LanguageServerId(usize::MAX);
+
+/// A problem found in a task's output, with its file path resolved.
+#[derive(Clone, Debug)]
+pub struct TaskProblem {
+    pub abs_path: PathBuf,
+    /// Name to show as the source of the diagnostic.
+    pub source: String,
+    pub problem: Problem,
+}
+
+impl LspStore {
+    /// Replaces all diagnostics previously reported for the task with the given label with the problems given.
+    /// Passing no problems clears the task's diagnostics.
+    pub fn update_task_diagnostics(
+        &mut self,
+        task_label: SharedString,
+        problems: Vec<TaskProblem>,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let local = self
+            .as_local_mut()
+            .context("cannot report task diagnostics on a remote LspStore")?;
+
+        let mut diagnostics_by_path = HashMap::<PathBuf, Vec<_>>::default();
+        for TaskProblem {
+            abs_path,
+            source,
+            problem,
+        } in problems
+        {
+            let start = PointUtf16::new(
+                problem.line.saturating_sub(1),
+                problem.column.unwrap_or(1).saturating_sub(1),
+            );
+            let end = match problem.end_line {
+                Some(end_line) => PointUtf16::new(
+                    end_line.saturating_sub(1),
+                    problem.end_column.unwrap_or(1).saturating_sub(1),
+                ),
+                None => start,
+            };
+            diagnostics_by_path
+                .entry(abs_path)
+                .or_default()
+                .push(DiagnosticEntry {
+                    range: Unclipped(start)..Unclipped(end.max(start)),
+                    diagnostic: Diagnostic {
+                        source: Some(source),
+                        code: problem.code.map(NumberOrString::String),
+                        severity: match problem.severity {
+                            ProblemSeverity::Error => DiagnosticSeverity::ERROR,
+                            ProblemSeverity::Warning => DiagnosticSeverity::WARNING,
+                            ProblemSeverity::Info => DiagnosticSeverity::INFORMATION,
+                            ProblemSeverity::Hint => DiagnosticSeverity::HINT,
+                        },
+                        message: problem.message,
+                        group_id: post_inc(&mut local.next_diagnostic_group_id),
+                        is_primary: true,
+                        is_disk_based: true,
+                        source_kind: DiagnosticSourceKind::Other,
+                        ..Diagnostic::default()
+                    },
+                });
+        }
+
+        let mut changed_paths = diagnostics_by_path.keys().cloned().collect::<HashSet<_>>();
+        if let Some(previous_diagnostics) = local.task_diagnostics.remove(&task_label) {
+            changed_paths.extend(previous_diagnostics.into_keys());
+        }
+        if !diagnostics_by_path.is_empty() {
+            local
+                .task_diagnostics
+                .insert(task_label, diagnostics_by_path);
+        }
+
+        for abs_path in changed_paths {
+            // Several tasks may report problems in the same file, all of them are stored under the same id.
+            let diagnostics = local
+                .task_diagnostics
+                .values()
+                .filter_map(|diagnostics_by_path| diagnostics_by_path.get(&abs_path))
+                .flatten()
+                .cloned()
+                .collect();
+            self.update_diagnostic_entries(
+                TASK_DIAGNOSTICS_SERVER_ID,
+                abs_path,
+                None,
+                None,
+                diagnostics,
+                cx,
+            )
+            .log_err();
+        }
+        Ok(())
+    }
+}
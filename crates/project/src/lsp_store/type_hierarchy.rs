@@ -0,0 +1,179 @@
+use anyhow::Result;
+use futures::future::join_all;
+use gpui::{AppContext as _, Context, Entity, Task};
+use language::{Buffer, PointUtf16};
+use settings::Settings as _;
+
+use crate::TypeHierarchyItem;
+use crate::lsp_command::{GetSubtypes, GetSupertypes, LspCommand as _, PrepareTypeHierarchy};
+use crate::lsp_store::{LanguageServerToQuery, LspStore};
+use crate::project_settings::ProjectSettings;
+
+impl LspStore {
+    /// Resolves the types at the given position that a type hierarchy can be built from.
+    ///
+    /// Returns `None` when no language server for the buffer supports type hierarchies.
+    pub fn prepare_type_hierarchy(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: PointUtf16,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<Vec<TypeHierarchyItem>>>> {
+        if let Some((upstream_client, project_id)) = self.upstream_client() {
+            // Type hierarchy support is not part of the capabilities shared with remote
+            // clients, so leave it to the host to pick the servers that support it.
+            let request = PrepareTypeHierarchy { position };
+            let request_timeout = ProjectSettings::get_global(cx)
+                .global_lsp_settings
+                .get_request_timeout();
+            let request_task = upstream_client.request_lsp(
+                project_id,
+                None,
+                request_timeout,
+                cx.background_executor().clone(),
+                request.to_proto(project_id, buffer.read(cx)),
+            );
+            let buffer = buffer.clone();
+            cx.spawn(async move |weak_lsp_store, cx| {
+                let Some(lsp_store) = weak_lsp_store.upgrade() else {
+                    return Ok(None);
+                };
+                let Some(responses) = request_task.await? else {
+                    return Ok(None);
+                };
+                let items = join_all(responses.payload.into_iter().map(|response| {
+                    request.response_from_proto(
+                        response.response,
+                        lsp_store.clone(),
+                        buffer.clone(),
+                        cx.clone(),
+                    )
+                }))
+                .await;
+
+                Ok(Some(
+                    items
+                        .into_iter()
+                        .collect::<Result<Vec<Vec<_>>>>()?
+                        .into_iter()
+                        .flatten()
+                        .collect(),
+                ))
+            })
+        } else {
+            let items_task = self.request_multiple_lsp_locally(
+                buffer,
+                Some(position),
+                PrepareTypeHierarchy { position },
+                cx,
+            );
+            cx.background_spawn(async move {
+                Ok(Some(
+                    items_task
+                        .await
+                        .into_iter()
+                        .flat_map(|(_, items)| items)
+                        .collect(),
+                ))
+            })
+        }
+    }
+
+    /// Queries the language server that produced the item for the types it derives from.
+    pub fn supertypes(
+        &mut self,
+        item: TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        let buffer = item.location.buffer.clone();
+        let server_id = item.language_server_id;
+        let request = GetSupertypes { item };
+        if let Some((upstream_client, project_id)) = self.upstream_client() {
+            let request_timeout = ProjectSettings::get_global(cx)
+                .global_lsp_settings
+                .get_request_timeout();
+            let request_task = upstream_client.request_lsp(
+                project_id,
+                Some(server_id.to_proto()),
+                request_timeout,
+                cx.background_executor().clone(),
+                request.to_proto(project_id, buffer.read(cx)),
+            );
+            cx.spawn(async move |weak_lsp_store, cx| {
+                let Some(lsp_store) = weak_lsp_store.upgrade() else {
+                    return Ok(Vec::new());
+                };
+                let Some(responses) = request_task.await? else {
+                    return Ok(Vec::new());
+                };
+                let items = join_all(responses.payload.into_iter().map(|response| {
+                    request.clone().response_from_proto(
+                        response.response,
+                        lsp_store.clone(),
+                        buffer.clone(),
+                        cx.clone(),
+                    )
+                }))
+                .await;
+
+                Ok(items
+                    .into_iter()
+                    .collect::<Result<Vec<Vec<_>>>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect())
+            })
+        } else {
+            self.request_lsp(buffer, LanguageServerToQuery::Other(server_id), request, cx)
+        }
+    }
+
+    /// Queries the language server that produced the item for the types deriving from it.
+    pub fn subtypes(
+        &mut self,
+        item: TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        let buffer = item.location.buffer.clone();
+        let server_id = item.language_server_id;
+        let request = GetSubtypes { item };
+        if let Some((upstream_client, project_id)) = self.upstream_client() {
+            let request_timeout = ProjectSettings::get_global(cx)
+                .global_lsp_settings
+                .get_request_timeout();
+            let request_task = upstream_client.request_lsp(
+                project_id,
+                Some(server_id.to_proto()),
+                request_timeout,
+                cx.background_executor().clone(),
+                request.to_proto(project_id, buffer.read(cx)),
+            );
+            cx.spawn(async move |weak_lsp_store, cx| {
+                let Some(lsp_store) = weak_lsp_store.upgrade() else {
+                    return Ok(Vec::new());
+                };
+                let Some(responses) = request_task.await? else {
+                    return Ok(Vec::new());
+                };
+                let items = join_all(responses.payload.into_iter().map(|response| {
+                    request.clone().response_from_proto(
+                        response.response,
+                        lsp_store.clone(),
+                        buffer.clone(),
+                        cx.clone(),
+                    )
+                }))
+                .await;
+
+                Ok(items
+                    .into_iter()
+                    .collect::<Result<Vec<Vec<_>>>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect())
+            })
+        } else {
+            self.request_lsp(buffer, LanguageServerToQuery::Other(server_id), request, cx)
+        }
+    }
+}
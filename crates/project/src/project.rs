@@ -151,14 +151,14 @@ pub use language::Location;
 pub use prettier::FORMAT_SUFFIX as TEST_PRETTIER_FORMAT_SUFFIX;
 pub use task_inventory::{
     BasicContextProvider, ContextProviderWithTasks, DebugScenarioContext, Inventory, TaskContexts,
-    TaskSourceKind,
+    TaskGraph, TaskSourceKind,
 };
 
 pub use buffer_store::ProjectTransaction;
 pub use lsp_store::{
     DiagnosticSummary, InvalidationStrategy, LanguageServerLogType, LanguageServerProgress,
     LanguageServerPromptRequest, LanguageServerStatus, LanguageServerToQuery, LspStore,
-    LspStoreEvent, ProgressToken, SERVER_PROGRESS_THROTTLE_TIMEOUT,
+    LspStoreEvent, ProgressToken, ProposedWorkspaceEdit, SERVER_PROGRESS_THROTTLE_TIMEOUT,
 };
 pub use toolchain_store::{ToolchainStore, Toolchains};
 const MAX_PROJECT_SEARCH_HISTORY_SIZE: usize = 500;
@@ -817,6 +817,48 @@ pub struct LocationLink {
     pub target: Location,
 }
 
+/// A symbol that can be expanded into the calls it makes or receives,
+/// as returned by `textDocument/prepareCallHierarchy`.
+#[derive(Clone, Debug)]
+pub struct CallHierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    /// The range enclosing the whole symbol, including its body.
+    pub location: Location,
+    /// The range to reveal when navigating to the symbol, e.g. its name.
+    pub selection_range: Range<language::Anchor>,
+    pub language_server_id: LanguageServerId,
+    /// The item as sent by the language server, which has to be passed back
+    /// verbatim when querying for its calls.
+    pub lsp_item: lsp::CallHierarchyItem,
+}
+
+#[derive(Clone, Debug)]
+pub struct CallHierarchyCall {
+    /// The caller for incoming calls, or the callee for outgoing calls.
+    pub item: CallHierarchyItem,
+    /// The ranges of the call expressions, which are always located in the caller.
+    pub call_sites: Vec<Location>,
+}
+
+/// A type that can be expanded into its supertypes and subtypes,
+/// as returned by `textDocument/prepareTypeHierarchy`.
+#[derive(Clone, Debug)]
+pub struct TypeHierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    /// The range enclosing the whole type declaration.
+    pub location: Location,
+    /// The range to reveal when navigating to the type, e.g. its name.
+    pub selection_range: Range<language::Anchor>,
+    pub language_server_id: LanguageServerId,
+    /// The item as sent by the language server, which has to be passed back
+    /// verbatim when querying for its supertypes or subtypes.
+    pub lsp_item: lsp::TypeHierarchyItem,
+}
+
 #[derive(Debug)]
 pub struct DocumentHighlight {
     pub range: Range<language::Anchor>,
@@ -4052,6 +4094,124 @@ impl Project {
         })
     }
 
+    pub fn prepare_call_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<Vec<CallHierarchyItem>>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        let guard = self.retain_remotely_created_models(cx);
+        let task = self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.prepare_call_hierarchy(buffer, position, cx)
+        });
+        cx.background_spawn(async move {
+            let result = task.await;
+            drop(guard);
+            result
+        })
+    }
+
+    pub fn incoming_calls(
+        &mut self,
+        item: &CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        let guard = self.retain_remotely_created_models(cx);
+        let task = self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.incoming_calls(item.clone(), cx)
+        });
+        cx.background_spawn(async move {
+            let result = task.await;
+            drop(guard);
+            result
+        })
+    }
+
+    pub fn outgoing_calls(
+        &mut self,
+        item: &CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        let guard = self.retain_remotely_created_models(cx);
+        let task = self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.outgoing_calls(item.clone(), cx)
+        });
+        cx.background_spawn(async move {
+            let result = task.await;
+            drop(guard);
+            result
+        })
+    }
+
+    pub fn prepare_type_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<Vec<TypeHierarchyItem>>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        let guard = self.retain_remotely_created_models(cx);
+        let task = self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.prepare_type_hierarchy(buffer, position, cx)
+        });
+        cx.background_spawn(async move {
+            let result = task.await;
+            drop(guard);
+            result
+        })
+    }
+
+    pub fn supertypes(
+        &mut self,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        let guard = self.retain_remotely_created_models(cx);
+        let task = self
+            .lsp_store
+            .update(cx, |lsp_store, cx| lsp_store.supertypes(item.clone(), cx));
+        cx.background_spawn(async move {
+            let result = task.await;
+            drop(guard);
+            result
+        })
+    }
+
+    pub fn subtypes(
+        &mut self,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        let guard = self.retain_remotely_created_models(cx);
+        let task = self
+            .lsp_store
+            .update(cx, |lsp_store, cx| lsp_store.subtypes(item.clone(), cx));
+        cx.background_spawn(async move {
+            let result = task.await;
+            drop(guard);
+            result
+        })
+    }
+
+    /// Returns, for each position, the ranges enclosing it from the innermost to the
+    /// outermost one, as reported by the buffer's language server.
+    pub fn selection_ranges<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<T>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Vec<Range<Anchor>>>>> {
+        let snapshot = buffer.read(cx);
+        let positions = positions
+            .into_iter()
+            .map(|position| position.to_point_utf16(snapshot))
+            .collect();
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.selection_ranges(buffer, positions, cx)
+        })
+    }
+
     pub fn references<T: ToPointUtf16>(
         &mut self,
         buffer: &Entity<Buffer>,
@@ -4255,6 +4415,31 @@ impl Project {
         })
     }
 
+    /// Resolves the edits that the code action would make, without making them. Returns `None` if
+    /// the code action runs a command instead, or doesn't do anything.
+    pub fn propose_code_action(
+        &self,
+        buffer: Entity<Buffer>,
+        action: CodeAction,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<ProposedWorkspaceEdit>>> {
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.propose_code_action(buffer, action, cx)
+        })
+    }
+
+    /// Applies the edits that a language server proposed.
+    pub fn apply_workspace_edit(
+        &self,
+        proposed_edit: ProposedWorkspaceEdit,
+        push_to_history: bool,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<ProjectTransaction>> {
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.apply_workspace_edit(proposed_edit, push_to_history, cx)
+        })
+    }
+
     pub fn apply_code_action_kind(
         &self,
         buffers: HashSet<Entity<Buffer>>,
@@ -4303,6 +4488,21 @@ impl Project {
         )
     }
 
+    /// Asks the language server for the edits that renaming the symbol at `position` would make,
+    /// without making them. Returns `None` if no language server can rename the symbol.
+    pub fn propose_rename<T: ToPointUtf16>(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: T,
+        new_name: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<ProposedWorkspaceEdit>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.propose_rename(buffer, position, new_name, cx)
+        })
+    }
+
     pub fn on_type_format<T: ToPointUtf16>(
         &mut self,
         buffer: Entity<Buffer>,
@@ -5777,6 +5977,41 @@ impl Project {
             .any(|capabilities| capabilities.semantic_tokens_provider.is_some())
     }
 
+    pub fn any_language_server_supports_selection_ranges(
+        &self,
+        buffer: &Buffer,
+        cx: &mut App,
+    ) -> bool {
+        let Some(language) = buffer.language().cloned() else {
+            return false;
+        };
+        let lsp_store = self.lsp_store.read(cx);
+        let relevant_language_servers = lsp_store
+            .languages
+            .lsp_adapters(&language.name())
+            .into_iter()
+            .map(|lsp_adapter| lsp_adapter.name())
+            .collect::<HashSet<_>>();
+        lsp_store
+            .language_server_statuses()
+            .filter_map(|(server_id, server_status)| {
+                relevant_language_servers
+                    .contains(&server_status.name)
+                    .then_some(server_id)
+            })
+            .filter_map(|server_id| lsp_store.lsp_server_capabilities.get(&server_id))
+            .any(|capabilities| {
+                capabilities
+                    .selection_range_provider
+                    .as_ref()
+                    .is_some_and(|capability| match capability {
+                        lsp::SelectionRangeProviderCapability::Simple(supported) => *supported,
+                        lsp::SelectionRangeProviderCapability::Options(..)
+                        | lsp::SelectionRangeProviderCapability::RegistrationOptions(..) => true,
+                    })
+            })
+    }
+
     pub fn language_server_id_for_name(
         &self,
         buffer: &Buffer,
@@ -19,6 +19,7 @@ use rpc::{
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 pub use settings::BinarySettings;
+pub use settings::ContextServerSamplingPolicy;
 pub use settings::DirenvSettings;
 pub use settings::LspSettings;
 use settings::{
@@ -65,6 +66,9 @@ pub struct ProjectSettings {
     /// Default timeout for context server requests in seconds.
     pub context_server_timeout: u64,
 
+    /// Whether context servers may generate messages with the user's language model.
+    pub context_server_sampling: HashMap<Arc<str>, ContextServerSamplingPolicy>,
+
     /// Configuration for Diagnostics-related features.
     pub diagnostics: DiagnosticsSettings,
 
@@ -664,6 +668,7 @@ impl Settings for ProjectSettings {
                 .map(|(key, value)| (key, value.into()))
                 .collect(),
             context_server_timeout: project.context_server_timeout.unwrap_or(60),
+            context_server_sampling: project.context_server_sampling.clone(),
             lsp: project
                 .lsp
                 .clone()
@@ -4,11 +4,11 @@ use std::{
     borrow::Cow,
     cmp::{self, Reverse},
     collections::hash_map,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use collections::{HashMap, HashSet, VecDeque};
 use dap::DapRegistry;
 use gpui::{App, AppContext as _, Context, Entity, SharedString, Task, WeakEntity};
@@ -22,7 +22,7 @@ use paths::{debug_task_file_name, task_file_name};
 use settings::{InvalidSettingsError, parse_json_with_comments};
 use task::{
     DebugScenario, ResolvedTask, SharedTaskContext, TaskContext, TaskId, TaskTemplate,
-    TaskTemplates, TaskVariables, VariableName,
+    TaskTemplates, TaskVariables, TestOutcome, VariableName,
 };
 use text::{BufferId, Point, ToPoint};
 use util::{NumericPrefixWithSuffix, ResultExt as _, post_inc, rel_path::RelPath};
@@ -43,6 +43,8 @@ pub struct Inventory {
     last_scheduled_scenarios: VecDeque<(DebugScenario, DebugScenarioContext)>,
     templates_from_settings: InventoryFor<TaskTemplate>,
     scenarios_from_settings: InventoryFor<DebugScenario>,
+    /// Outcomes of the tests that were run last, by the file and the row of the test's runnable.
+    test_outcomes: HashMap<PathBuf, HashMap<u32, TestOutcome>>,
 }
 
 impl std::fmt::Debug for Inventory {
@@ -52,6 +54,7 @@ impl std::fmt::Debug for Inventory {
             .field("last_scheduled_scenarios", &self.last_scheduled_scenarios)
             .field("templates_from_settings", &self.templates_from_settings)
             .field("scenarios_from_settings", &self.scenarios_from_settings)
+            .field("test_outcomes", &self.test_outcomes)
             .finish()
     }
 }
@@ -155,6 +158,16 @@ pub enum TaskSourceKind {
     },
 }
 
+/// A resolved task together with the tasks it `depends_on`, resolved with the same [`TaskContext`].
+#[derive(Debug, Clone)]
+pub struct TaskGraph {
+    pub source_kind: TaskSourceKind,
+    pub task: ResolvedTask,
+    /// Dependencies in the order they are listed in the task's `depends_on`.
+    /// A task that several tasks of the graph depend on is resolved once and shared between them.
+    pub dependencies: Vec<Arc<TaskGraph>>,
+}
+
 /// A collection of task contexts, derived from the current state of the workspace.
 /// Only contains worktrees that are visible and with their root being a directory.
 #[derive(Debug, Default)]
@@ -246,6 +259,7 @@ impl Inventory {
             last_scheduled_scenarios: VecDeque::default(),
             templates_from_settings: InventoryFor::default(),
             scenarios_from_settings: InventoryFor::default(),
+            test_outcomes: HashMap::default(),
         })
     }
 
@@ -373,6 +387,41 @@ impl Inventory {
         })
     }
 
+    /// Resolves everything the task given `depends_on`, recursively.
+    /// Dependencies are looked up by their labels among the worktree and global tasks, worktree tasks taking precedence.
+    ///
+    /// Fails if a dependency cannot be found or resolved, or if the dependencies form a cycle.
+    pub fn resolve_task_graph(
+        &self,
+        source_kind: TaskSourceKind,
+        task: ResolvedTask,
+        cx: &App,
+    ) -> Task<Result<TaskGraph>> {
+        let Some(task_context) = task.dependency_context().cloned() else {
+            return Task::ready(Ok(TaskGraph {
+                source_kind,
+                task,
+                dependencies: Vec::new(),
+            }));
+        };
+        let worktree = match &source_kind {
+            TaskSourceKind::Worktree { id, .. } => Some(*id),
+            _ => None,
+        };
+        let templates = self.list_tasks(None, None, worktree, cx);
+        cx.background_spawn(async move {
+            let templates = templates.await;
+            resolve_task_graph(
+                source_kind,
+                task,
+                &templates,
+                &task_context,
+                &mut Vec::new(),
+                &mut HashMap::default(),
+            )
+        })
+    }
+
     /// Pulls its task sources relevant to the worktree and the language given,
     /// returns all task templates with their source kinds, worktree tasks first, language tasks second
     /// and global tasks last. No specific order inside source kinds groups.
@@ -604,6 +653,31 @@ impl Inventory {
         }
     }
 
+    /// Outcome of the last run of the test whose runnable starts at the given row of the file.
+    pub fn test_outcome(&self, abs_path: &Path, row: u32) -> Option<TestOutcome> {
+        self.test_outcomes.get(abs_path)?.get(&row).copied()
+    }
+
+    /// Replaces the outcomes of tests in the file, passing none clears them.
+    /// Editors refresh their run indicators when the outcomes change.
+    pub fn set_test_outcomes(
+        &mut self,
+        abs_path: PathBuf,
+        outcomes: HashMap<u32, TestOutcome>,
+        cx: &mut Context<Self>,
+    ) {
+        if outcomes.is_empty() {
+            if self.test_outcomes.remove(&abs_path).is_none() {
+                return;
+            }
+        } else if self.test_outcomes.get(&abs_path) == Some(&outcomes) {
+            return;
+        } else {
+            self.test_outcomes.insert(abs_path, outcomes);
+        }
+        cx.notify();
+    }
+
     /// Deletes a resolved task from history, using its id.
     /// A similar may still resurface in `used_and_current_resolved_tasks` when its [`TaskTemplate`] is resolved again.
     pub fn delete_previously_used(&mut self, id: &TaskId) {
@@ -861,6 +935,62 @@ fn task_lru_comparator(
         })
 }
 
+fn resolve_task_graph(
+    source_kind: TaskSourceKind,
+    task: ResolvedTask,
+    templates: &[(TaskSourceKind, TaskTemplate)],
+    task_context: &TaskContext,
+    dependents: &mut Vec<String>,
+    resolved: &mut HashMap<TaskId, Arc<TaskGraph>>,
+) -> Result<TaskGraph> {
+    let label = task.original_task().label.clone();
+    if dependents.contains(&label) {
+        dependents.push(label);
+        anyhow::bail!("Task dependency cycle: {}", dependents.join(" -> "));
+    }
+    dependents.push(label);
+
+    let dependencies = task
+        .original_task()
+        .depends_on
+        .iter()
+        .map(|dependency| {
+            let (dependency_source_kind, template) = templates
+                .iter()
+                .find(|(_, template)| &template.label == dependency)
+                .with_context(|| {
+                    format!(
+                        "Task `{}` depends on unknown task `{dependency}`",
+                        task.resolved_label
+                    )
+                })?;
+            let resolved_dependency = template
+                .resolve_task(&dependency_source_kind.to_id_base(), task_context)
+                .with_context(|| format!("Failed to resolve task `{dependency}`"))?;
+            if let Some(graph) = resolved.get(&resolved_dependency.id) {
+                return Ok(graph.clone());
+            }
+            let graph = Arc::new(resolve_task_graph(
+                dependency_source_kind.clone(),
+                resolved_dependency,
+                templates,
+                task_context,
+                dependents,
+                resolved,
+            )?);
+            resolved.insert(graph.task.id.clone(), graph.clone());
+            Ok(graph)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    dependents.pop();
+    Ok(TaskGraph {
+        source_kind,
+        task,
+        dependencies,
+    })
+}
+
 pub fn task_source_kind_preference(kind: &TaskSourceKind) -> u32 {
     match kind {
         TaskSourceKind::Lsp { .. } => 0,
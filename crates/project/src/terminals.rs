@@ -1,6 +1,6 @@
 use anyhow::Result;
 use collections::HashMap;
-use gpui::{App, AppContext as _, Context, Entity, Task, WeakEntity};
+use gpui::{App, AppContext as _, Context, Entity, SharedString, Task, WeakEntity};
 
 use futures::{FutureExt, future::Shared};
 use itertools::Itertools as _;
@@ -11,15 +11,23 @@ use smol::channel::bounded;
 use std::{
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
-use task::{Shell, ShellBuilder, ShellKind, SpawnInTerminal};
+use task::{ProblemMatcher, Shell, ShellBuilder, ShellKind, SpawnInTerminal};
 use terminal::{
     TaskState, TaskStatus, Terminal, TerminalBuilder, insert_zed_terminal_env,
     terminal_settings::TerminalSettings,
 };
-use util::{command::new_std_command, get_default_system_shell, maybe, rel_path::RelPath};
+use util::{
+    ResultExt as _, command::new_std_command, get_default_system_shell, maybe, paths::PathStyle,
+    rel_path::RelPath,
+};
+
+use crate::{Project, ProjectPath, lsp_store::TaskProblem};
 
-use crate::{Project, ProjectPath};
+/// How often the output of a running task is checked with its problem matchers,
+/// so that tasks which never exit (e.g. watchers) still report their problems.
+const TASK_PROBLEMS_POLL_INTERVAL: Duration = Duration::from_secs(2);
 
 pub struct Terminals {
     pub(crate) local_handles: Vec<WeakEntity<terminal::Terminal>>,
@@ -92,6 +100,21 @@ impl Project {
         let (completion_tx, completion_rx) = bounded(1);
 
         let local_path = if is_via_remote { None } else { path.clone() };
+        let problem_matchers = if is_via_remote {
+            Vec::new()
+        } else {
+            spawn_task.problem_matchers.clone()
+        };
+        let task_label = SharedString::from(spawn_task.label.clone());
+        if !problem_matchers.is_empty() {
+            // Whatever the previous run of this task has reported is stale now.
+            self.lsp_store
+                .update(cx, |lsp_store, cx| {
+                    lsp_store.update_task_diagnostics(task_label.clone(), Vec::new(), cx)
+                })
+                .log_err();
+        }
+        let problems_base_directory = local_path.clone();
         let task_state = Some(TaskState {
             spawned_task: spawn_task.clone(),
             status: TaskStatus::Running,
@@ -256,6 +279,7 @@ impl Project {
                         cx,
                         activation_script,
                         path_style,
+                        None,
                     ))
                 })??
                 .await?;
@@ -280,17 +304,97 @@ impl Project {
                 })
                 .detach();
 
+                if !problem_matchers.is_empty() {
+                    this.report_task_problems(
+                        &terminal_handle,
+                        task_label,
+                        problem_matchers,
+                        problems_base_directory,
+                        cx,
+                    );
+                }
+
                 terminal_handle
             })
         })
     }
 
+    /// Runs the task's problem matchers over its terminal output, reporting the problems found as diagnostics
+    /// while the task runs and once more after it finishes.
+    fn report_task_problems(
+        &self,
+        terminal: &Entity<Terminal>,
+        task_label: SharedString,
+        problem_matchers: Vec<ProblemMatcher>,
+        base_directory: Option<Arc<Path>>,
+        cx: &mut Context<Self>,
+    ) {
+        let completion = terminal.read(cx).wait_for_completed_task(cx);
+        let terminal = terminal.downgrade();
+        cx.spawn(async move |project, cx| {
+            let mut completion = completion.fuse();
+            let mut last_output = None;
+            loop {
+                let mut timer = cx
+                    .background_executor()
+                    .timer(TASK_PROBLEMS_POLL_INTERVAL)
+                    .fuse();
+                let finished = futures::select_biased! {
+                    _ = completion => true,
+                    _ = timer => false,
+                };
+
+                let output = terminal.read_with(cx, |terminal, _| terminal.get_content())?;
+                if last_output.as_ref() != Some(&output) {
+                    let problems = cx.background_spawn({
+                        let output = output.clone();
+                        let task_label = task_label.clone();
+                        let problem_matchers = problem_matchers.clone();
+                        let base_directory = base_directory.clone();
+                        async move {
+                            match_task_problems(
+                                &output,
+                                &task_label,
+                                &problem_matchers,
+                                base_directory.as_deref(),
+                            )
+                        }
+                    });
+                    let problems = problems.await;
+                    project.update(cx, |project, cx| {
+                        project.lsp_store.update(cx, |lsp_store, cx| {
+                            lsp_store.update_task_diagnostics(task_label.clone(), problems, cx)
+                        })
+                    })??;
+                    last_output = Some(output);
+                }
+
+                if finished {
+                    break;
+                }
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn create_terminal_shell(
         &mut self,
         cwd: Option<PathBuf>,
         cx: &mut Context<Self>,
     ) -> Task<Result<Entity<Terminal>>> {
-        self.create_terminal_shell_internal(cwd, false, cx)
+        self.create_terminal_shell_internal(cwd, false, None, cx)
+    }
+
+    /// Creates a terminal attached to the persistent session with the given id, as saved when the
+    /// terminal was last open, or to a new one if that session is gone.
+    pub fn attach_terminal_shell(
+        &mut self,
+        cwd: Option<PathBuf>,
+        session_id: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<Terminal>>> {
+        self.create_terminal_shell_internal(cwd, false, Some(session_id), cx)
     }
 
     /// Creates a local terminal even if the project is remote.
@@ -307,16 +411,18 @@ impl Project {
             // Local project: use project directory like normal terminals
             self.active_project_directory(cx).map(|p| p.to_path_buf())
         };
-        self.create_terminal_shell_internal(working_directory, true, cx)
+        self.create_terminal_shell_internal(working_directory, true, None, cx)
     }
 
     /// Internal method for creating terminal shells.
     /// If force_local is true, creates a local terminal even if the project has a remote client.
     /// This allows "breaking out" to a local shell in remote projects.
+    /// With persistent sessions enabled, the shell runs in the session with the given id, or in a new one.
     fn create_terminal_shell_internal(
         &mut self,
         cwd: Option<PathBuf>,
         force_local: bool,
+        session_id: Option<String>,
         cx: &mut Context<Self>,
     ) -> Task<Result<Entity<Terminal>>> {
         let path = cwd.map(|p| Arc::from(&*p));
@@ -334,6 +440,11 @@ impl Project {
         let settings = TerminalSettings::get(settings_location, cx).clone();
         let detect_venv = settings.detect_venv.as_option().is_some();
         let local_path = if is_via_remote { None } else { path.clone() };
+        // A reattached session has already run its activation script.
+        let is_reattaching = session_id.is_some();
+        let persistent_session = settings
+            .persistent_sessions
+            .then(|| session_id.unwrap_or_else(terminal_host::new_session_id));
 
         let project_path_contexts = self
             .active_entry()
@@ -395,14 +506,46 @@ impl Project {
             .await
             .unwrap_or_default();
 
+            let activation_script = if is_reattaching && persistent_session.is_some() {
+                Vec::new()
+            } else {
+                activation_script
+            };
+
             let builder = project
                 .update(cx, move |_, cx| {
-                    let (shell, env) = {
+                    let (shell, env, persistent_session) = {
                         match remote_client {
                             Some(remote_client) => {
-                                create_remote_shell(None, env, path, remote_client, cx)?
+                                let attach_command =
+                                    persistent_session.as_deref().and_then(|session_id| {
+                                        remote_attach_command(
+                                            session_id,
+                                            settings.persistent_session_timeout,
+                                            &shell,
+                                            remote_client.read(cx),
+                                        )
+                                    });
+                                let (shell, env) = create_remote_shell(
+                                    attach_command
+                                        .as_ref()
+                                        .map(|(program, args)| (program, args)),
+                                    env,
+                                    path,
+                                    remote_client,
+                                    cx,
+                                )?;
+                                (
+                                    shell,
+                                    env,
+                                    persistent_session.filter(|_| attach_command.is_some()),
+                                )
                             }
-                            None => (settings.shell, env),
+                            None => (
+                                settings.shell,
+                                env,
+                                persistent_session.filter(|_| terminal_host::is_supported()),
+                            ),
                         }
                     };
                     anyhow::Ok(TerminalBuilder::new(
@@ -421,6 +564,7 @@ impl Project {
                         cx,
                         activation_script,
                         path_style,
+                        persistent_session,
                     ))
                 })??
                 .await?;
@@ -457,8 +601,9 @@ impl Project {
         cwd: Option<PathBuf>,
     ) -> Task<Result<Entity<Terminal>>> {
         // We cannot clone the task's terminal, as it will effectively re-spawn the task, which might not be desirable.
-        // For now, create a new shell instead.
-        if terminal.read(cx).task().is_some() {
+        // For now, create a new shell instead. The same goes for a persistent session's terminal,
+        // which would attach to the same session rather than start a new one.
+        if terminal.read(cx).task().is_some() || terminal.read(cx).persistent_session().is_some() {
             return self.create_terminal_shell(cwd, cx);
         }
         let local_path = if self.is_via_remote_server() {
@@ -638,3 +783,63 @@ fn create_remote_shell(
         command.env,
     ))
 }
+
+/// The command attaching a remote terminal to its persistent session through the remote server
+/// binary, if the remote host supports persistent sessions.
+fn remote_attach_command(
+    session_id: &str,
+    detached_timeout: Option<Duration>,
+    shell: &str,
+    remote_client: &RemoteClient,
+) -> Option<(String, Vec<String>)> {
+    if remote_client.path_style().is_windows() {
+        return None;
+    }
+    let binary_path = remote_client.remote_binary_path()?;
+    let binary_path = binary_path.display(PathStyle::Posix);
+    let login_shell = ["-l".to_string()];
+    let attach_args =
+        terminal_host::attach_args(session_id, detached_timeout, shell.to_string(), login_shell)
+            .iter()
+            .map(|arg| ShellKind::Posix.try_quote(arg).map(|arg| arg.into_owned()))
+            .collect::<Option<Vec<_>>>()?;
+    // The binary path is relative to the home directory, while the terminal starts in the
+    // project's directory.
+    let command = format!(
+        "exec \"$HOME\"/{} {}",
+        ShellKind::Posix.try_quote(&binary_path)?,
+        attach_args.join(" ")
+    );
+    Some(("sh".to_string(), vec!["-c".to_string(), command]))
+}
+
+fn match_task_problems(
+    output: &str,
+    task_label: &str,
+    problem_matchers: &[ProblemMatcher],
+    base_directory: Option<&Path>,
+) -> Vec<TaskProblem> {
+    problem_matchers
+        .iter()
+        .filter_map(|matcher| {
+            let problems = matcher.match_output(output).log_err()?;
+            let base_directory = matcher
+                .base_directory
+                .as_deref()
+                .map(Path::new)
+                .or(base_directory);
+            Some(problems.into_iter().filter_map(move |problem| {
+                let abs_path = matcher.resolve_path(&problem.file, base_directory)?;
+                Some(TaskProblem {
+                    abs_path,
+                    source: matcher
+                        .owner
+                        .clone()
+                        .unwrap_or_else(|| task_label.to_string()),
+                    problem,
+                })
+            }))
+        })
+        .flatten()
+        .collect()
+}
@@ -1,21 +1,33 @@
 use anyhow::Result;
-use context_server::test::create_fake_transport;
+use context_server::test::{FakeTransport, create_fake_transport};
+use context_server::types::{
+    CreateMessageRequest, CreateMessageResult, ElicitationAction, ElicitationCreateParams,
+    ElicitationCreateResult, ElicitationSchema, Implementation, InitializeResponse,
+    LATEST_PROTOCOL_VERSION, MessageContent, ProtocolVersion, Role, SamplingMessage,
+    ServerCapabilities, requests,
+};
 use context_server::{ContextServer, ContextServerId};
 use gpui::{AppContext, AsyncApp, Entity, Subscription, Task, TestAppContext, UpdateGlobal as _};
 use http_client::{FakeHttpClient, Response};
 use project::context_server_store::registry::ContextServerDescriptorRegistry;
+use project::context_server_store::server_requests::ServerRequestKind;
 use project::context_server_store::*;
-use project::project_settings::ContextServerSettings;
+use project::project_settings::{ContextServerSamplingPolicy, ContextServerSettings};
 use project::worktree_store::WorktreeStore;
 use project::{
     FakeFs, Project, context_server_store::registry::ContextServerDescriptor,
     project_settings::ProjectSettings,
 };
 use serde_json::json;
-use settings::{ContextServerCommand, Settings, SettingsStore};
-use std::sync::Arc;
+use settings::{
+    ContextServerCommand, LocalSettingsKind, LocalSettingsPath, Settings, SettingsStore,
+};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering::SeqCst},
+};
 use std::{cell::RefCell, path::PathBuf, rc::Rc};
-use util::path;
+use util::{path, rel_path::RelPath};
 
 #[gpui::test]
 async fn test_context_server_status(cx: &mut TestAppContext) {
@@ -715,6 +727,262 @@ async fn test_remote_context_server(cx: &mut TestAppContext) {
     cx.run_until_parked();
 }
 
+#[gpui::test]
+async fn test_context_server_sampling_and_elicitation(cx: &mut TestAppContext) {
+    const SERVER_1_ID: &str = "mcp-1";
+    const SERVER_2_ID: &str = "mcp-2";
+
+    let (_fs, project) = setup_context_server_test(cx, json!({"code.rs": ""}), vec![]).await;
+    cx.update(|cx| {
+        let mut settings = ProjectSettings::get_global(cx).clone();
+        settings
+            .context_server_sampling
+            .insert(SERVER_2_ID.into(), ContextServerSamplingPolicy::Deny);
+        ProjectSettings::override_global(settings, cx);
+    });
+    // A project can't allow its servers to sample without asking the user.
+    let worktree_id = project.read_with(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    cx.update_global::<SettingsStore, _>(|store, cx| {
+        store
+            .set_local_settings(
+                worktree_id,
+                LocalSettingsPath::InWorktree(RelPath::empty().into_arc()),
+                LocalSettingsKind::Settings,
+                Some(r#"{ "context_server_sampling": { "mcp-1": "allow", "mcp-2": "allow" } }"#),
+                cx,
+            )
+            .unwrap();
+    });
+
+    let registry = cx.new(|_| ContextServerDescriptorRegistry::new());
+    let store = cx.new(|cx| {
+        ContextServerStore::test(
+            registry.clone(),
+            project.read(cx).worktree_store(),
+            Some(project.downgrade()),
+            cx,
+        )
+    });
+    let request_handler = cx.new(|_| ());
+    store.update(cx, |store, cx| {
+        store.register_request_handler(&request_handler, cx)
+    });
+
+    let server_1_id = ContextServerId(SERVER_1_ID.into());
+    let transport_1 = Arc::new(create_fake_transport(SERVER_1_ID, cx.executor()));
+    let transport_2 = Arc::new(create_fake_transport(SERVER_2_ID, cx.executor()));
+    let server_1 = Arc::new(ContextServer::new(server_1_id.clone(), transport_1.clone()));
+    let server_2 = Arc::new(ContextServer::new(
+        ContextServerId(SERVER_2_ID.into()),
+        transport_2.clone(),
+    ));
+    store.update(cx, |store, cx| {
+        store.test_start_server(server_1, cx);
+        store.test_start_server(server_2, cx);
+    });
+    cx.run_until_parked();
+
+    // Servers whose sampling policy is "deny" get an error without the user being asked.
+    let denied_sampling = cx.executor().spawn({
+        let transport_2 = transport_2.clone();
+        async move {
+            transport_2
+                .request_client::<requests::CreateMessage>(sampling_request("Hello"))
+                .await
+        }
+    });
+    let sampling = cx.executor().spawn({
+        let transport_1 = transport_1.clone();
+        async move {
+            transport_1
+                .request_client::<requests::CreateMessage>(sampling_request("Hello"))
+                .await
+        }
+    });
+    cx.run_until_parked();
+    assert!(denied_sampling.await.is_err());
+
+    let request_id = store.read_with(cx, |store, _| {
+        let requests = store.pending_requests().collect::<Vec<_>>();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].server_id, server_1_id);
+        assert!(matches!(
+            requests[0].kind,
+            ServerRequestKind::Sampling {
+                approved: false,
+                ..
+            }
+        ));
+        requests[0].id
+    });
+    store.update(cx, |store, cx| {
+        store.approve_sampling_request(request_id, cx);
+        store.respond_to_sampling_request(
+            request_id,
+            Ok(CreateMessageResult {
+                role: Role::Assistant,
+                content: MessageContent::Text {
+                    text: "Hi!".into(),
+                    annotations: None,
+                },
+                model: "fake-model".into(),
+                stop_reason: Some("endTurn".into()),
+            }),
+            cx,
+        );
+    });
+    cx.run_until_parked();
+    let result = sampling.await.unwrap();
+    assert_eq!(result.model, "fake-model");
+    assert!(matches!(result.content, MessageContent::Text { text, .. } if text == "Hi!"));
+
+    let elicitation = cx.executor().spawn({
+        let transport_1 = transport_1.clone();
+        async move {
+            transport_1
+                .request_client::<requests::ElicitationCreate>(elicitation_request())
+                .await
+        }
+    });
+    cx.run_until_parked();
+    let request_id = store.read_with(cx, |store, _| {
+        let request = store.pending_requests().next().unwrap();
+        let ServerRequestKind::Elicitation { params } = &request.kind else {
+            panic!("expected an elicitation request");
+        };
+        assert_eq!(params.requested_schema.required, ["name"]);
+        request.id
+    });
+    store.update(cx, |store, cx| {
+        store.respond_to_elicitation_request(
+            request_id,
+            ElicitationCreateResult {
+                action: ElicitationAction::Accept,
+                content: Some([("name".to_string(), json!("Ada"))].into_iter().collect()),
+            },
+            cx,
+        )
+    });
+    cx.run_until_parked();
+    let result = elicitation.await.unwrap();
+    assert_eq!(result.action, ElicitationAction::Accept);
+    assert_eq!(result.content.unwrap()["name"], json!("Ada"));
+
+    // Stopping a server drops its pending requests.
+    let _pending_sampling = cx.executor().spawn({
+        let transport_1 = transport_1.clone();
+        async move {
+            transport_1
+                .request_client::<requests::CreateMessage>(sampling_request("Hello again"))
+                .await
+        }
+    });
+    cx.run_until_parked();
+    assert_eq!(
+        store.read_with(cx, |store, _| store.pending_requests().count()),
+        1
+    );
+    store
+        .update(cx, |store, cx| store.stop_server(&server_1_id, cx))
+        .unwrap();
+    assert_eq!(
+        store.read_with(cx, |store, _| store.pending_requests().count()),
+        0
+    );
+
+    // Releasing the last request handler cancels the requests it would have shown.
+    let elicitation = cx.executor().spawn({
+        let transport_2 = transport_2.clone();
+        async move {
+            transport_2
+                .request_client::<requests::ElicitationCreate>(elicitation_request())
+                .await
+        }
+    });
+    cx.run_until_parked();
+    assert_eq!(
+        store.read_with(cx, |store, _| store.pending_requests().count()),
+        1
+    );
+    drop(request_handler);
+    cx.run_until_parked();
+    assert_eq!(
+        store.read_with(cx, |store, _| store.pending_requests().count()),
+        0
+    );
+    assert_eq!(elicitation.await.unwrap().action, ElicitationAction::Cancel);
+
+    // Without a handler, new requests are refused right away, and servers that start aren't
+    // told they may send them.
+    let elicitation =
+        transport_2.request_client::<requests::ElicitationCreate>(elicitation_request());
+    assert_eq!(elicitation.await.unwrap().action, ElicitationAction::Cancel);
+    let advertised_requests = Arc::new(AtomicBool::new(true));
+    let transport_3 = Arc::new(
+        FakeTransport::new(cx.executor()).on_request::<requests::Initialize, _>({
+            let advertised_requests = advertised_requests.clone();
+            move |params| {
+                advertised_requests.store(
+                    params.capabilities.sampling.is_some()
+                        || params.capabilities.elicitation.is_some(),
+                    SeqCst,
+                );
+                async move {
+                    InitializeResponse {
+                        protocol_version: ProtocolVersion(LATEST_PROTOCOL_VERSION.to_string()),
+                        capabilities: ServerCapabilities::default(),
+                        server_info: Implementation {
+                            name: "mcp-3".into(),
+                            version: "1.0.0".into(),
+                        },
+                        meta: None,
+                    }
+                }
+            }
+        }),
+    );
+    let server_3 = Arc::new(ContextServer::new(
+        ContextServerId("mcp-3".into()),
+        transport_3,
+    ));
+    store.update(cx, |store, cx| store.test_start_server(server_3, cx));
+    cx.run_until_parked();
+    assert!(!advertised_requests.load(SeqCst));
+}
+
+fn elicitation_request() -> ElicitationCreateParams {
+    ElicitationCreateParams {
+        message: "What's your name?".into(),
+        requested_schema: serde_json::from_value::<ElicitationSchema>(json!({
+            "type": "object",
+            "properties": { "name": { "type": "string" } },
+            "required": ["name"]
+        }))
+        .unwrap(),
+    }
+}
+
+fn sampling_request(text: &str) -> CreateMessageRequest {
+    CreateMessageRequest {
+        messages: vec![SamplingMessage {
+            role: Role::User,
+            content: MessageContent::Text {
+                text: text.into(),
+                annotations: None,
+            },
+        }],
+        model_preferences: None,
+        system_prompt: None,
+        include_context: None,
+        temperature: None,
+        max_tokens: 100,
+        stop_sequences: None,
+        metadata: None,
+    }
+}
+
 struct ServerEvents {
     received_event_count: Rc<RefCell<usize>>,
     expected_event_count: usize,
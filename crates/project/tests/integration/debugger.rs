@@ -3,7 +3,7 @@ mod go_locator {
     use dap::{DapLocator, adapters::DebugAdapterName};
     use gpui::TestAppContext;
     use project::debugger::locators::go::{DelveLaunchRequest, GoLocator};
-    use task::{HideStrategy, RevealStrategy, RevealTarget, Shell, TaskTemplate};
+    use task::{DependsOrder, HideStrategy, RevealStrategy, RevealTarget, Shell, TaskTemplate};
     #[gpui::test]
     async fn test_create_scenario_for_go_build(_: &mut TestAppContext) {
         let locator = GoLocator;
@@ -22,6 +22,9 @@ mod go_locator {
             tags: vec![],
             show_summary: true,
             show_command: true,
+            depends_on: vec![],
+            depends_order: DependsOrder::Parallel,
+            problem_matchers: vec![],
         };
 
         let scenario = locator
@@ -49,6 +52,9 @@ mod go_locator {
             tags: vec![],
             show_summary: true,
             show_command: true,
+            depends_on: vec![],
+            depends_order: DependsOrder::Parallel,
+            problem_matchers: vec![],
         };
 
         let scenario = locator
@@ -187,6 +193,9 @@ mod go_locator {
             tags: vec![],
             show_summary: true,
             show_command: true,
+            depends_on: vec![],
+            depends_order: DependsOrder::Parallel,
+            problem_matchers: vec![],
         };
 
         let scenario = locator
@@ -221,6 +230,9 @@ mod python_locator {
             shell: task::Shell::System,
             show_summary: false,
             show_command: false,
+            depends_on: vec![],
+            depends_order: task::DependsOrder::Parallel,
+            problem_matchers: vec![],
         };
 
         let expected_scenario = DebugScenario {
@@ -29,8 +29,11 @@ use encoding_rs;
 use fs::FakeFs;
 use futures::{StreamExt, future};
 use git::{
-    GitHostingProviderRegistry,
-    repository::{RepoPath, repo_path},
+    GitHostingProviderRegistry, Oid,
+    repository::{
+        ApplyCommitOutcome, AskPassDelegate, BisectTerm, RepoPath, Submodule, SubmoduleStatus,
+        repo_path,
+    },
     status::{FileStatus, StatusCode, TrackedStatus},
 };
 use git2::RepositoryInitOptions;
@@ -59,6 +62,7 @@ use pretty_assertions::{assert_eq, assert_matches};
 use project::{
     Event, TaskContexts,
     git_store::{GitStoreEvent, Repository, RepositoryEvent, StatusEntry, pending_op},
+    lsp_store::TaskProblem,
     search::{SearchQuery, SearchResult},
     task_store::{TaskSettingsLocation, TaskStore},
     *,
@@ -3610,6 +3614,79 @@ async fn test_diagnostics_from_multiple_language_servers(cx: &mut gpui::TestAppC
     });
 }
 
+#[gpui::test]
+async fn test_task_diagnostics(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({ "a.rs": "one two three", "b.rs": "four five" }),
+    )
+    .await;
+
+    let project = Project::test(fs, [Path::new(path!("/dir"))], cx).await;
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store());
+    let problem = |file: &str, line, severity, message: &str| TaskProblem {
+        abs_path: Path::new(path!("/dir")).join(file),
+        source: "rustc".to_string(),
+        problem: task::Problem {
+            file: file.to_string(),
+            line,
+            column: Some(1),
+            end_line: None,
+            end_column: None,
+            severity,
+            code: None,
+            message: message.to_string(),
+        },
+    };
+
+    lsp_store.update(cx, |lsp_store, cx| {
+        lsp_store
+            .update_task_diagnostics(
+                "check".into(),
+                vec![
+                    problem("a.rs", 1, task::ProblemSeverity::Error, "error in a"),
+                    problem("b.rs", 1, task::ProblemSeverity::Warning, "warning in b"),
+                ],
+                cx,
+            )
+            .unwrap();
+        lsp_store
+            .update_task_diagnostics(
+                "lint".into(),
+                vec![problem(
+                    "a.rs",
+                    1,
+                    task::ProblemSeverity::Warning,
+                    "warning in a",
+                )],
+                cx,
+            )
+            .unwrap();
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 1,
+                warning_count: 2,
+            }
+        );
+
+        // Rerunning a task replaces only the problems it has reported.
+        lsp_store
+            .update_task_diagnostics("check".into(), Vec::new(), cx)
+            .unwrap();
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 0,
+                warning_count: 1,
+            }
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_edits_from_lsp2_with_past_version(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -4158,6 +4235,354 @@ async fn test_definition(cx: &mut gpui::TestAppContext) {
     }
 }
 
+#[gpui::test]
+async fn test_call_hierarchy(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn a() { b() }",
+            "b.rs": "fn b() {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+                ..lsp::ServerCapabilities::default()
+            },
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/b.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    cx.executor().run_until_parked();
+
+    let lsp_item = |name: &str, path: &str, end: u32| lsp::CallHierarchyItem {
+        name: name.to_string(),
+        kind: lsp::SymbolKind::FUNCTION,
+        tags: None,
+        detail: Some(format!("fn {name}()")),
+        uri: lsp::Uri::from_file_path(path).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, end)),
+        selection_range: lsp::Range::new(lsp::Position::new(0, 3), lsp::Position::new(0, 4)),
+        data: None,
+    };
+    let b_item = lsp_item("b", path!("/dir/b.rs"), 9);
+    let a_item = lsp_item("a", path!("/dir/a.rs"), 14);
+
+    fake_server.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>({
+        let b_item = b_item.clone();
+        move |params, _| {
+            let b_item = b_item.clone();
+            async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(0, 3)
+                );
+                Ok(Some(vec![b_item]))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>({
+        let b_item = b_item.clone();
+        move |params, _| {
+            let b_item = b_item.clone();
+            let a_item = a_item.clone();
+            async move {
+                assert_eq!(params.item, b_item);
+                Ok(Some(vec![lsp::CallHierarchyIncomingCall {
+                    from: a_item,
+                    from_ranges: vec![lsp::Range::new(
+                        lsp::Position::new(0, 9),
+                        lsp::Position::new(0, 10),
+                    )],
+                }]))
+            }
+        }
+    });
+
+    let mut items = project
+        .update(cx, |project, cx| {
+            project.prepare_call_hierarchy(&buffer, 3, cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    let item = items.pop().unwrap();
+    assert_eq!(item.name, "b");
+    assert_eq!(item.lsp_item, b_item);
+    cx.update(|cx| {
+        let buffer = item.location.buffer.read(cx);
+        assert_eq!(item.location.range.to_offset(buffer), 0..9);
+        assert_eq!(item.selection_range.to_offset(buffer), 3..4);
+    });
+
+    let calls = project
+        .update(cx, |project, cx| project.incoming_calls(&item, cx))
+        .await
+        .unwrap();
+    assert_eq!(calls.len(), 1);
+    let call = &calls[0];
+    assert_eq!(call.item.name, "a");
+    assert_eq!(call.item.detail.as_deref(), Some("fn a()"));
+    cx.update(|cx| {
+        let caller_buffer = call.item.location.buffer.read(cx);
+        assert_eq!(
+            caller_buffer
+                .file()
+                .unwrap()
+                .as_local()
+                .unwrap()
+                .abs_path(cx),
+            Path::new(path!("/dir/a.rs")),
+        );
+        let call_sites = call
+            .call_sites
+            .iter()
+            .map(|location| {
+                assert_eq!(location.buffer, call.item.location.buffer);
+                location.range.to_offset(caller_buffer)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(call_sites, [9..10]);
+    });
+}
+
+#[gpui::test]
+async fn test_type_hierarchy(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "shape.ts": "interface Shape {}",
+            "circle.ts": "class Circle implements Shape {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(typescript_lang());
+    let mut fake_servers =
+        language_registry.register_fake_lsp("TypeScript", FakeLspAdapter::default());
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/shape.ts"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    cx.executor().run_until_parked();
+
+    let shape_item = lsp::TypeHierarchyItem {
+        name: "Shape".to_string(),
+        kind: lsp::SymbolKind::INTERFACE,
+        tags: None,
+        detail: None,
+        uri: lsp::Uri::from_file_path(path!("/dir/shape.ts")).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 18)),
+        selection_range: lsp::Range::new(lsp::Position::new(0, 10), lsp::Position::new(0, 15)),
+        data: None,
+    };
+    let circle_item = lsp::TypeHierarchyItem {
+        name: "Circle".to_string(),
+        kind: lsp::SymbolKind::CLASS,
+        tags: None,
+        detail: None,
+        uri: lsp::Uri::from_file_path(path!("/dir/circle.ts")).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 32)),
+        selection_range: lsp::Range::new(lsp::Position::new(0, 6), lsp::Position::new(0, 12)),
+        data: None,
+    };
+    fake_server.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>({
+        let shape_item = shape_item.clone();
+        move |_, _| {
+            let shape_item = shape_item.clone();
+            async move { Ok(Some(vec![shape_item])) }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::TypeHierarchySubtypes, _, _>({
+        let shape_item = shape_item.clone();
+        move |params, _| {
+            let shape_item = shape_item.clone();
+            let circle_item = circle_item.clone();
+            async move {
+                assert_eq!(params.item, shape_item);
+                Ok(Some(vec![circle_item]))
+            }
+        }
+    });
+
+    // The server has not advertised type hierarchy support yet.
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_type_hierarchy(&buffer, 10, cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(items.is_empty());
+
+    fake_server
+        .request::<lsp::request::RegisterCapability>(
+            lsp::RegistrationParams {
+                registrations: vec![lsp::Registration {
+                    id: Default::default(),
+                    method: "textDocument/prepareTypeHierarchy".to_string(),
+                    register_options: None,
+                }],
+            },
+            DEFAULT_LSP_REQUEST_TIMEOUT,
+        )
+        .await
+        .into_response()
+        .unwrap();
+    cx.executor().run_until_parked();
+
+    let mut items = project
+        .update(cx, |project, cx| {
+            project.prepare_type_hierarchy(&buffer, 10, cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    let item = items.pop().unwrap();
+    assert_eq!(item.name, "Shape");
+    assert_eq!(item.lsp_item, shape_item);
+
+    let subtypes = project
+        .update(cx, |project, cx| project.subtypes(&item, cx))
+        .await
+        .unwrap();
+    assert_eq!(subtypes.len(), 1);
+    cx.update(|cx| {
+        let subtype = &subtypes[0];
+        let subtype_buffer = subtype.location.buffer.read(cx);
+        assert_eq!(subtype.name, "Circle");
+        assert_eq!(subtype.kind, lsp::SymbolKind::CLASS);
+        assert_eq!(
+            subtype_buffer
+                .file()
+                .unwrap()
+                .as_local()
+                .unwrap()
+                .abs_path(cx),
+            Path::new(path!("/dir/circle.ts")),
+        );
+        assert_eq!(subtype.selection_range.to_offset(subtype_buffer), 6..12);
+    });
+}
+
+#[gpui::test]
+async fn test_selection_ranges(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn a() { b(1) }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                selection_range_provider: Some(lsp::SelectionRangeProviderCapability::Simple(true)),
+                ..lsp::ServerCapabilities::default()
+            },
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    cx.executor().run_until_parked();
+
+    let range = |start: u32, end: u32| {
+        lsp::Range::new(lsp::Position::new(0, start), lsp::Position::new(0, end))
+    };
+    fake_server.set_request_handler::<lsp::request::SelectionRangeRequest, _, _>(
+        move |params, _| async move {
+            assert_eq!(
+                params.positions,
+                [lsp::Position::new(0, 11), lsp::Position::new(0, 3)]
+            );
+            Ok(Some(vec![
+                lsp::SelectionRange {
+                    range: range(11, 12),
+                    parent: Some(Box::new(lsp::SelectionRange {
+                        range: range(9, 13),
+                        parent: Some(Box::new(lsp::SelectionRange {
+                            range: range(0, 15),
+                            parent: None,
+                        })),
+                    })),
+                },
+                lsp::SelectionRange {
+                    range: range(3, 4),
+                    parent: None,
+                },
+            ]))
+        },
+    );
+
+    let selection_ranges = project
+        .update(cx, |project, cx| {
+            project.selection_ranges(&buffer, vec![11, 3], cx)
+        })
+        .await
+        .unwrap();
+    buffer.read_with(cx, |buffer, _| {
+        let selection_ranges = selection_ranges
+            .iter()
+            .map(|ranges| {
+                ranges
+                    .iter()
+                    .map(|range| range.to_offset(buffer))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(selection_ranges, [vec![11..12, 9..13, 0..15], vec![3..4]]);
+    });
+}
+
 #[gpui::test]
 async fn test_completions_with_text_edit(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -11739,6 +12164,284 @@ async fn test_git_worktree_remove(cx: &mut gpui::TestAppContext) {
     assert!(active_repo_path.is_none());
 }
 
+#[gpui::test]
+async fn test_repository_submodules(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        path!("/root"),
+        json!({
+            ".git": {},
+            ".gitmodules": "",
+            "vendor": {
+                "lib": {},
+            },
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+    cx.run_until_parked();
+
+    let repository = project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+    repository.read_with(cx, |repository, _| {
+        assert!(repository.snapshot().submodules.is_empty());
+    });
+
+    let recorded_sha = Oid::from_str(&"a".repeat(40)).unwrap();
+    let submodule = Submodule {
+        name: "vendor/lib".into(),
+        path: repo_path("vendor/lib"),
+        url: Some("https://example.com/lib.git".into()),
+        recorded_sha: Some(recorded_sha),
+        checked_out_sha: None,
+        has_changes: false,
+    };
+    fs.set_submodules_for_repo(path!("/root/.git").as_ref(), vec![submodule.clone()]);
+    cx.run_until_parked();
+
+    repository.read_with(cx, |repository, _| {
+        let snapshot = repository.snapshot();
+        assert_eq!(snapshot.submodules.as_ref(), [submodule.clone()]);
+        let submodule = snapshot
+            .submodule_for_path(&repo_path("vendor/lib"))
+            .unwrap();
+        assert_eq!(submodule.status(), SubmoduleStatus::Uninitialized);
+        assert!(snapshot.submodule_for_path(&repo_path("vendor")).is_none());
+    });
+
+    repository
+        .update(cx, |repository, cx| {
+            repository.submodule_update(
+                vec![repo_path("vendor/lib")],
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+            )
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    cx.run_until_parked();
+    repository.read_with(cx, |repository, _| {
+        let snapshot = repository.snapshot();
+        let submodule = snapshot
+            .submodule_for_path(&repo_path("vendor/lib"))
+            .unwrap();
+        assert_eq!(submodule.checked_out_sha, Some(recorded_sha));
+        assert_eq!(submodule.status(), SubmoduleStatus::UpToDate);
+    });
+
+    // Initializing keeps the configured URL, while syncing copies the one from `.gitmodules`.
+    let configured_url = || {
+        fs.with_git_state(path!("/root/.git").as_ref(), false, |state| {
+            state.submodule_urls.get(&repo_path("vendor/lib")).cloned()
+        })
+        .unwrap()
+    };
+    assert_eq!(
+        configured_url().as_deref(),
+        Some("https://example.com/lib.git")
+    );
+    fs.with_git_state(path!("/root/.git").as_ref(), true, |state| {
+        state.submodules[0].url = Some("https://example.com/moved/lib.git".into());
+    })
+    .unwrap();
+    repository
+        .update(cx, |repository, _| repository.submodule_init(Vec::new()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        configured_url().as_deref(),
+        Some("https://example.com/lib.git")
+    );
+    repository
+        .update(cx, |repository, _| {
+            repository.submodule_sync(vec![repo_path("vendor/lib")])
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        configured_url().as_deref(),
+        Some("https://example.com/moved/lib.git")
+    );
+}
+
+#[gpui::test]
+async fn test_repository_cherry_pick_and_revert(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        path!("/root"),
+        json!({
+            ".git": {},
+            "a.txt": "two\n",
+            "b.txt": "b\n",
+        }),
+    )
+    .await;
+    let shas = fs.set_commits_for_repo(
+        path!("/root/.git").as_ref(),
+        &[
+            ("initial", &[("a.txt", Some("one\n"))]),
+            ("change a", &[("a.txt", Some("two\n"))]),
+            ("add b", &[("b.txt", Some("b\n"))]),
+        ],
+    );
+
+    let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+    cx.run_until_parked();
+    let repository = project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+
+    let outcome = repository
+        .update(cx, |repository, _| repository.revert(shas[2].to_string()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(outcome, ApplyCommitOutcome::Applied);
+    assert!(!fs.is_file(path!("/root/b.txt").as_ref()).await);
+
+    let outcome = repository
+        .update(cx, |repository, _| {
+            repository.cherry_pick(shas[2].to_string())
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(outcome, ApplyCommitOutcome::Applied);
+    assert_eq!(fs.load(path!("/root/b.txt").as_ref()).await.unwrap(), "b\n");
+    cx.run_until_parked();
+    repository.read_with(cx, |repository, _| {
+        let snapshot = repository.snapshot();
+        assert!(snapshot.status().next().is_none());
+        assert!(snapshot.merge.heads.iter().all(Option::is_none));
+    });
+
+    // Reverting the commit that added a.txt conflicts with the later change to it.
+    let outcome = repository
+        .update(cx, |repository, _| repository.revert(shas[0].to_string()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(outcome, ApplyCommitOutcome::Conflicted);
+    cx.run_until_parked();
+    repository.read_with(cx, |repository, _| {
+        let snapshot = repository.snapshot();
+        assert!(snapshot.has_conflict(&repo_path("a.txt")));
+        assert!(!snapshot.has_conflict(&repo_path("b.txt")));
+        assert!(
+            snapshot
+                .merge
+                .heads
+                .contains(&Some(shas[0].to_string().into()))
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_repository_bisect(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        path!("/root"),
+        json!({
+            ".git": {},
+            "version.txt": "5\n",
+        }),
+    )
+    .await;
+    let dot_git = Path::new(path!("/root/.git"));
+    fs.set_branch_name(dot_git, Some("main"));
+    let shas = fs.set_commits_for_repo(
+        dot_git,
+        &[
+            ("version 0", &[("version.txt", Some("0\n"))]),
+            ("version 1", &[("version.txt", Some("1\n"))]),
+            ("version 2", &[("version.txt", Some("2\n"))]),
+            ("version 3", &[("version.txt", Some("3\n"))]),
+            ("version 4", &[("version.txt", Some("4\n"))]),
+            ("version 5", &[("version.txt", Some("5\n"))]),
+        ],
+    );
+
+    let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+    cx.run_until_parked();
+    let repository = project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+    let version = async || fs.load(path!("/root/version.txt").as_ref()).await.unwrap();
+
+    let state = repository
+        .update(cx, |repository, _| {
+            repository.bisect_start(Some("HEAD".into()), vec![shas[0].to_string()])
+        })
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(state.bad, Some(shas[5]));
+    assert_eq!(state.good, [shas[0]]);
+    assert_eq!(state.remaining, 4);
+    assert_eq!(state.first_bad, None);
+    assert_eq!(version().await, "3\n");
+
+    let state = repository
+        .update(cx, |repository, _| {
+            repository.bisect_mark(BisectTerm::Bad, None)
+        })
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(state.bad, Some(shas[3]));
+    assert_eq!(state.remaining, 2);
+    assert_eq!(version().await, "2\n");
+
+    let state = repository
+        .update(cx, |repository, _| {
+            repository.bisect_mark(BisectTerm::Skip, None)
+        })
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(state.skipped, [shas[2]]);
+    assert_eq!(state.remaining, 1);
+    assert_eq!(version().await, "1\n");
+
+    let state = repository
+        .update(cx, |repository, _| {
+            repository.bisect_mark(BisectTerm::Good, Some(shas[2].to_string()))
+        })
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(state.remaining, 0);
+    assert_eq!(state.first_bad, Some(shas[3]));
+    cx.run_until_parked();
+    repository.read_with(cx, |repository, _| {
+        assert_eq!(repository.snapshot().bisect, Some(state.clone()));
+    });
+
+    repository
+        .update(cx, |repository, _| repository.bisect_reset())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(version().await, "5\n");
+    cx.run_until_parked();
+    repository.read_with(cx, |repository, _| {
+        let snapshot = repository.snapshot();
+        assert_eq!(snapshot.bisect, None);
+        assert_eq!(
+            snapshot.branch.as_ref().map(|branch| branch.name()),
+            Some("main")
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_optimistic_hunks_in_staged_files(cx: &mut gpui::TestAppContext) {
     use DiffHunkSecondaryStatus::*;
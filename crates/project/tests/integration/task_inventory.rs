@@ -6,6 +6,7 @@ use serde_json::json;
 use settings::SettingsLocation;
 use std::path::Path;
 use std::sync::Arc;
+use task::TaskContext;
 use util::rel_path::rel_path;
 
 use project::task_store::{TaskSettingsLocation, TaskStore};
@@ -560,6 +561,126 @@ async fn test_inventory_static_task_filters(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_resolving_task_graph(cx: &mut TestAppContext) {
+    init_test(cx);
+    let inventory = cx.update(|cx| Inventory::new(cx));
+    let worktree_id = WorktreeId::from_usize(1);
+
+    inventory.update(cx, |inventory, _| {
+        inventory
+            .update_file_based_tasks(
+                TaskSettingsLocation::Global(tasks_file()),
+                Some(
+                    &json!([
+                        { "label": "build", "command": "cargo build" },
+                        { "label": "cycle a", "command": "echo", "depends_on": ["cycle b"] },
+                        { "label": "cycle b", "command": "echo", "depends_on": ["cycle a"] },
+                    ])
+                    .to_string(),
+                ),
+            )
+            .unwrap();
+        inventory
+            .update_file_based_tasks(
+                TaskSettingsLocation::Worktree(SettingsLocation {
+                    worktree_id,
+                    path: rel_path(".zed"),
+                }),
+                Some(
+                    &json!([
+                        { "label": "migrate db", "command": "./migrate", "depends_on": ["build"] },
+                        {
+                            "label": "start server",
+                            "command": "./server",
+                            "depends_on": ["build", "migrate db"],
+                            "depends_order": "sequence",
+                        },
+                        { "label": "everything", "depends_on": ["start server", "missing"] },
+                    ])
+                    .to_string(),
+                ),
+            )
+            .unwrap();
+    });
+
+    let resolve = |label: &str, cx: &mut TestAppContext| {
+        let tasks = inventory.update(cx, |inventory, cx| {
+            inventory.list_tasks(None, None, Some(worktree_id), cx)
+        });
+        let inventory = inventory.clone();
+        let label = label.to_owned();
+        cx.spawn(|mut cx| async move {
+            let (source_kind, template) = tasks
+                .await
+                .into_iter()
+                .find(|(_, template)| template.label == label)
+                .unwrap();
+            let task = template
+                .resolve_task(&source_kind.to_id_base(), &TaskContext::default())
+                .unwrap();
+            inventory
+                .update(&mut cx, |inventory, cx| {
+                    inventory.resolve_task_graph(source_kind, task, cx)
+                })
+                .await
+        })
+    };
+
+    fn labels(graph: &TaskGraph) -> serde_json::Value {
+        json!({
+            "label": graph.task.resolved_label,
+            "depends_on": graph
+                .dependencies
+                .iter()
+                .map(|dependency| labels(dependency))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    let graph = resolve("start server", cx).await.unwrap();
+    assert_eq!(
+        labels(&graph),
+        json!({
+            "label": "start server",
+            "depends_on": [
+                { "label": "build", "depends_on": [] },
+                {
+                    "label": "migrate db",
+                    "depends_on": [{ "label": "build", "depends_on": [] }],
+                },
+            ],
+        })
+    );
+    assert!(matches!(
+        graph.dependencies[0].source_kind,
+        TaskSourceKind::AbsPath { .. }
+    ));
+    assert!(matches!(
+        graph.dependencies[1].source_kind,
+        TaskSourceKind::Worktree { .. }
+    ));
+    assert!(
+        Arc::ptr_eq(
+            &graph.dependencies[0],
+            &graph.dependencies[1].dependencies[0]
+        ),
+        "`build` should be resolved once for both tasks depending on it"
+    );
+
+    let error = resolve("everything", cx).await.unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Task `everything` depends on unknown task `missing`"
+    );
+
+    let error = resolve("cycle a", cx).await.unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Task dependency cycle: cycle a -> cycle b -> cycle a"
+    );
+}
+
 fn init_test(_cx: &mut TestAppContext) {
     zlog::init_test();
     TaskStore::init(None);
@@ -15,6 +15,7 @@ use editor::{
 };
 use file_icons::FileIcons;
 use git;
+use git::repository::{Submodule, SubmoduleStatus};
 use git::status::GitSummary;
 use git_ui;
 use git_ui::file_diff_view::FileDiffView;
@@ -253,6 +254,7 @@ struct EntryDetails {
     is_private: bool,
     worktree_id: WorktreeId,
     canonical_path: Option<Arc<Path>>,
+    submodule: Option<Submodule>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -632,7 +634,11 @@ impl ProjectPanel {
                 &git_store,
                 window,
                 |this, _, event, window, cx| match event {
-                    GitStoreEvent::RepositoryUpdated(_, RepositoryEvent::StatusesChanged, _)
+                    GitStoreEvent::RepositoryUpdated(
+                        _,
+                        RepositoryEvent::StatusesChanged | RepositoryEvent::SubmodulesChanged,
+                        _,
+                    )
                     | GitStoreEvent::RepositoryAdded
                     | GitStoreEvent::RepositoryRemoved(_) => {
                         this.update_visible_entries(None, false, false, window, cx);
@@ -5050,6 +5056,7 @@ impl ProjectPanel {
             .canonical_path
             .as_ref()
             .map(|f| f.to_string_lossy().into_owned());
+        let submodule = details.submodule.clone();
         let path_style = self.project.read(cx).path_style(cx);
         let path = details.path.clone();
         let path_for_external_paths = path.clone();
@@ -5498,6 +5505,42 @@ impl ProjectPanel {
                                 .into_any_element(),
                         )
                     })
+                    .when_some(submodule, |this, submodule| {
+                        let status = submodule.status();
+                        let meta = match (submodule.recorded_sha, submodule.checked_out_sha) {
+                            (Some(recorded), Some(checked_out)) if recorded != checked_out => {
+                                format!(
+                                    "Recorded {}, checked out {}",
+                                    recorded.display_short(),
+                                    checked_out.display_short()
+                                )
+                            }
+                            (Some(recorded), _) => format!("Recorded {}", recorded.display_short()),
+                            (None, _) => String::new(),
+                        };
+                        this.end_slot::<AnyElement>(
+                            div()
+                                .id("submodule_icon")
+                                .pr_3()
+                                .tooltip(move |_window, cx| {
+                                    Tooltip::with_meta(
+                                        format!("Submodule: {}", status.label()),
+                                        None,
+                                        meta.clone(),
+                                        cx,
+                                    )
+                                })
+                                .child(Icon::new(IconName::Box).size(IconSize::Indicator).color(
+                                    match status {
+                                        SubmoduleStatus::UpToDate => filename_text_color,
+                                        SubmoduleStatus::Uninitialized => Color::Muted,
+                                        SubmoduleStatus::NewCommits => Color::Warning,
+                                        SubmoduleStatus::Modified => Color::Modified,
+                                    },
+                                ))
+                                .into_any_element(),
+                        )
+                    })
                     .child(if let Some(icon) = &icon {
                         if let Some((_, decoration_color)) =
                             entry_diagnostic_aware_icon_decoration_and_color(diagnostic_severity)
@@ -5915,6 +5958,20 @@ impl ProjectPanel {
             .as_ref()
             .is_some_and(|e| e.is_cut() && e.items().contains(&selection));
 
+        let submodule = if entry.is_dir() {
+            let project_path = ProjectPath {
+                worktree_id,
+                path: entry.path.clone(),
+            };
+            self.project
+                .read(cx)
+                .git_store()
+                .read(cx)
+                .submodule_for_project_path(&project_path, cx)
+        } else {
+            None
+        };
+
         EntryDetails {
             filename,
             icon,
@@ -5935,6 +5992,7 @@ impl ProjectPanel {
             is_private: entry.is_private,
             worktree_id,
             canonical_path: entry.canonical_path.clone(),
+            submodule,
         }
     }
 
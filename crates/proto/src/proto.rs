@@ -231,6 +231,20 @@ messages!(
     (GetColorPresentationResponse, Background),
     (GetFoldingRanges, Background),
     (GetFoldingRangesResponse, Background),
+    (PrepareCallHierarchy, Background),
+    (PrepareCallHierarchyResponse, Background),
+    (GetIncomingCalls, Background),
+    (GetIncomingCallsResponse, Background),
+    (GetOutgoingCalls, Background),
+    (GetOutgoingCallsResponse, Background),
+    (PrepareTypeHierarchy, Background),
+    (PrepareTypeHierarchyResponse, Background),
+    (GetSupertypes, Background),
+    (GetSupertypesResponse, Background),
+    (GetSubtypes, Background),
+    (GetSubtypesResponse, Background),
+    (GetSelectionRanges, Background),
+    (GetSelectionRangesResponse, Background),
     (RefreshCodeLens, Background),
     (GetCodeLens, Background),
     (GetCodeLensResponse, Background),
@@ -302,6 +316,30 @@ messages!(
     (GitCommitDetails, Background),
     (GitFileHistory, Background),
     (GitFileHistoryResponse, Background),
+    (GitLog, Background),
+    (GitLogResponse, Background),
+    (GitRebaseCommits, Background),
+    (GitRebaseCommitsResponse, Background),
+    (GitRebaseInteractive, Background),
+    (GitRebaseOperation, Background),
+    (GitCherryPick, Background),
+    (GitRevert, Background),
+    (GitApplyCommitResponse, Background),
+    (GitGetTags, Background),
+    (GitTagsResponse, Background),
+    (GitCreateTag, Background),
+    (GitDeleteTag, Background),
+    (GitCheckoutTag, Background),
+    (GitPushTag, Background),
+    (GitBisectStart, Background),
+    (GitBisectMark, Background),
+    (GitBisectReset, Background),
+    (GitBisectStateResponse, Background),
+    (GitSubmoduleInit, Background),
+    (GitSubmoduleSync, Background),
+    (GitSubmoduleUpdate, Background),
+    (GitLoadMergeStages, Background),
+    (GitLoadMergeStagesResponse, Background),
     (SetIndexText, Background),
     (Push, Background),
     (Fetch, Background),
@@ -455,6 +493,13 @@ request_messages!(
     (ResolveInlayHint, ResolveInlayHintResponse),
     (GetDocumentColor, GetDocumentColorResponse),
     (GetFoldingRanges, GetFoldingRangesResponse),
+    (PrepareCallHierarchy, PrepareCallHierarchyResponse),
+    (GetIncomingCalls, GetIncomingCallsResponse),
+    (GetOutgoingCalls, GetOutgoingCallsResponse),
+    (PrepareTypeHierarchy, PrepareTypeHierarchyResponse),
+    (GetSupertypes, GetSupertypesResponse),
+    (GetSubtypes, GetSubtypesResponse),
+    (GetSelectionRanges, GetSelectionRangesResponse),
     (GetColorPresentation, GetColorPresentationResponse),
     (RespondToChannelInvite, Ack),
     (RespondToContactRequest, Ack),
@@ -520,7 +565,25 @@ request_messages!(
     (RegisterBufferWithLanguageServers, Ack),
     (GitShow, GitCommitDetails),
     (GitFileHistory, GitFileHistoryResponse),
+    (GitLog, GitLogResponse),
     (GitReset, Ack),
+    (GitRebaseCommits, GitRebaseCommitsResponse),
+    (GitRebaseInteractive, Ack),
+    (GitRebaseOperation, Ack),
+    (GitCherryPick, GitApplyCommitResponse),
+    (GitRevert, GitApplyCommitResponse),
+    (GitGetTags, GitTagsResponse),
+    (GitCreateTag, Ack),
+    (GitDeleteTag, Ack),
+    (GitCheckoutTag, Ack),
+    (GitPushTag, RemoteMessageResponse),
+    (GitBisectStart, GitBisectStateResponse),
+    (GitBisectMark, GitBisectStateResponse),
+    (GitBisectReset, Ack),
+    (GitSubmoduleInit, Ack),
+    (GitSubmoduleSync, Ack),
+    (GitSubmoduleUpdate, RemoteMessageResponse),
+    (GitLoadMergeStages, GitLoadMergeStagesResponse),
     (GitDeleteBranch, Ack),
     (GitCheckoutFiles, Ack),
     (SetIndexText, Ack),
@@ -574,6 +637,13 @@ lsp_messages!(
     (GetDeclaration, GetDeclarationResponse, true),
     (GetTypeDefinition, GetTypeDefinitionResponse, true),
     (GetImplementation, GetImplementationResponse, true),
+    (PrepareCallHierarchy, PrepareCallHierarchyResponse, true),
+    (GetIncomingCalls, GetIncomingCallsResponse, false),
+    (GetOutgoingCalls, GetOutgoingCallsResponse, false),
+    (PrepareTypeHierarchy, PrepareTypeHierarchyResponse, true),
+    (GetSupertypes, GetSupertypesResponse, false),
+    (GetSubtypes, GetSubtypesResponse, false),
+    (GetSelectionRanges, GetSelectionRangesResponse, true),
     (InlayHints, InlayHintsResponse, false),
     (SemanticTokens, SemanticTokensResponse, true)
 );
@@ -611,6 +681,13 @@ entity_messages!(
     GetDefinition,
     GetDeclaration,
     GetImplementation,
+    PrepareCallHierarchy,
+    GetIncomingCalls,
+    GetOutgoingCalls,
+    PrepareTypeHierarchy,
+    GetSupertypes,
+    GetSubtypes,
+    GetSelectionRanges,
     GetDocumentHighlights,
     GetDocumentSymbols,
     GetHover,
@@ -701,7 +778,25 @@ entity_messages!(
     RegisterBufferWithLanguageServers,
     GitShow,
     GitFileHistory,
+    GitLog,
     GitReset,
+    GitRebaseCommits,
+    GitRebaseInteractive,
+    GitRebaseOperation,
+    GitCherryPick,
+    GitRevert,
+    GitGetTags,
+    GitCreateTag,
+    GitDeleteTag,
+    GitCheckoutTag,
+    GitPushTag,
+    GitBisectStart,
+    GitBisectMark,
+    GitBisectReset,
+    GitSubmoduleInit,
+    GitSubmoduleSync,
+    GitSubmoduleUpdate,
+    GitLoadMergeStages,
     GitDeleteBranch,
     GitCheckoutFiles,
     SetIndexText,
@@ -928,6 +1023,13 @@ impl LspQuery {
             Some(lsp_query::Request::GetDeclaration(_)) => ("GetDeclaration", false),
             Some(lsp_query::Request::GetTypeDefinition(_)) => ("GetTypeDefinition", false),
             Some(lsp_query::Request::GetImplementation(_)) => ("GetImplementation", false),
+            Some(lsp_query::Request::PrepareCallHierarchy(_)) => ("PrepareCallHierarchy", false),
+            Some(lsp_query::Request::GetIncomingCalls(_)) => ("GetIncomingCalls", false),
+            Some(lsp_query::Request::GetOutgoingCalls(_)) => ("GetOutgoingCalls", false),
+            Some(lsp_query::Request::PrepareTypeHierarchy(_)) => ("PrepareTypeHierarchy", false),
+            Some(lsp_query::Request::GetSupertypes(_)) => ("GetSupertypes", false),
+            Some(lsp_query::Request::GetSubtypes(_)) => ("GetSubtypes", false),
+            Some(lsp_query::Request::GetSelectionRanges(_)) => ("GetSelectionRanges", false),
             Some(lsp_query::Request::GetReferences(_)) => ("GetReferences", false),
             Some(lsp_query::Request::GetDocumentColor(_)) => ("GetDocumentColor", false),
             Some(lsp_query::Request::GetFoldingRanges(_)) => ("GetFoldingRanges", false),
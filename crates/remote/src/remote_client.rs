@@ -49,6 +49,7 @@ use std::{
 use util::{
     ResultExt,
     paths::{PathStyle, RemotePathBuf},
+    rel_path::RelPath,
 };
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -908,6 +909,10 @@ impl RemoteClient {
             .map_or(false, |connection| connection.has_wsl_interop())
     }
 
+    pub fn remote_binary_path(&self) -> Option<Arc<RelPath>> {
+        self.remote_connection()?.remote_binary_path()
+    }
+
     pub fn build_command(
         &self,
         program: Option<String>,
@@ -1346,6 +1351,10 @@ pub trait RemoteConnection: Send + Sync {
     fn shell(&self) -> String;
     fn default_system_shell(&self) -> String;
     fn has_wsl_interop(&self) -> bool;
+    /// The remote server binary, relative to the home directory on the remote host.
+    fn remote_binary_path(&self) -> Option<Arc<RelPath>> {
+        None
+    }
 
     #[cfg(any(test, feature = "test-support"))]
     fn simulate_disconnect(&self, _: &AsyncApp) {}
@@ -492,6 +492,10 @@ impl RemoteConnection for SshRemoteConnection {
     fn has_wsl_interop(&self) -> bool {
         false
     }
+
+    fn remote_binary_path(&self) -> Option<Arc<RelPath>> {
+        self.remote_binary_path.clone()
+    }
 }
 
 impl SshRemoteConnection {
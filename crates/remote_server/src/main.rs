@@ -16,6 +16,16 @@ struct Cli {
     /// process communicating over a socket.
     #[arg(long, hide = true)]
     crash_handler: Option<PathBuf>,
+    /// Used for persistent terminal sessions, by having the server run a separate
+    /// process that owns the terminals' shells, listening on a Unix socket.
+    #[cfg(unix)]
+    #[arg(long, hide = true)]
+    terminal_host: Option<PathBuf>,
+    /// Used for persistent terminal sessions, to attach the terminal the server runs in
+    /// to the session with the given id, starting it with the given command if needed.
+    #[cfg(unix)]
+    #[arg(long, hide = true, num_args = 1.., allow_hyphen_values = true)]
+    attach_terminal: Option<Vec<String>>,
     /// Used for loading the environment from the project.
     #[arg(long, hide = true)]
     printenv: bool,
@@ -34,6 +44,16 @@ fn main() -> anyhow::Result<()> {
         return Ok(());
     }
 
+    #[cfg(unix)]
+    if let Some(socket) = &cli.terminal_host {
+        return terminal_host::run_host(socket);
+    }
+
+    #[cfg(unix)]
+    if let Some(attach_args) = &cli.attach_terminal {
+        std::process::exit(terminal_host::attach(attach_args));
+    }
+
     if cli.printenv {
         util::shell_env::print_env();
         return Ok(());
@@ -385,6 +385,27 @@ impl AnyProtoClient {
                             Response::GetDocumentSymbolsResponse(response) => {
                                 to_any_envelope(&envelope, response)
                             }
+                            Response::PrepareCallHierarchyResponse(response) => {
+                                to_any_envelope(&envelope, response)
+                            }
+                            Response::GetIncomingCallsResponse(response) => {
+                                to_any_envelope(&envelope, response)
+                            }
+                            Response::GetOutgoingCallsResponse(response) => {
+                                to_any_envelope(&envelope, response)
+                            }
+                            Response::PrepareTypeHierarchyResponse(response) => {
+                                to_any_envelope(&envelope, response)
+                            }
+                            Response::GetSupertypesResponse(response) => {
+                                to_any_envelope(&envelope, response)
+                            }
+                            Response::GetSubtypesResponse(response) => {
+                                to_any_envelope(&envelope, response)
+                            }
+                            Response::GetSelectionRangesResponse(response) => {
+                                to_any_envelope(&envelope, response)
+                            }
                         };
                         Some(proto::ProtoLspResponse {
                             server_id,
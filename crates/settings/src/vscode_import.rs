@@ -507,6 +507,7 @@ impl VsCodeSettings {
             dap: Default::default(),
             context_servers: self.context_servers(),
             context_server_timeout: None,
+            context_server_sampling: Default::default(),
             load_direnv: None,
             slash_commands: None,
             git_hosting_providers: None,
@@ -871,6 +872,9 @@ impl VsCodeSettings {
             project: self.project_terminal_settings_content(),
             scrollbar: None,
             scroll_multiplier: None,
+            shell_integration: self.read_bool("terminal.integrated.shellIntegration.enabled"),
+            persistent_sessions: self.read_bool("terminal.integrated.enablePersistentSessions"),
+            persistent_session_timeout_minutes: None,
             toolbar: None,
         })
     }
@@ -71,6 +71,13 @@ pub struct ProjectSettingsContent {
     /// Default: 60
     pub context_server_timeout: Option<u64>,
 
+    /// Whether context servers may generate messages with your language model,
+    /// keyed by the id of the context server.
+    ///
+    /// Default: ask before each request
+    #[serde(default)]
+    pub context_server_sampling: HashMap<Arc<str>, ContextServerSamplingPolicy>,
+
     /// Configuration for how direnv configuration should be loaded
     pub load_direnv: Option<DirenvSettings>,
 
@@ -705,6 +712,19 @@ pub struct NodeBinarySettings {
     pub ignore_system_version: Option<bool>,
 }
 
+/// Whether a context server may generate messages with your language model, through MCP sampling requests.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextServerSamplingPolicy {
+    /// Ask before generating each message
+    #[default]
+    Ask,
+    /// Generate messages without asking
+    Allow,
+    /// Refuse to generate messages
+    Deny,
+}
+
 #[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom)]
 #[serde(rename_all = "snake_case")]
 pub enum DirenvSettings {
@@ -124,6 +124,21 @@ pub struct TerminalSettingsContent {
     ///
     /// Default: true
     pub keep_selection_on_copy: Option<bool>,
+    /// Whether to load Zed's shell integration into bash, zsh and fish, so that they report
+    /// the commands they run, their exit codes and their working directory.
+    ///
+    /// Default: true
+    pub shell_integration: Option<bool>,
+    /// Whether terminal shells run in a separate process that keeps them running when Zed quits,
+    /// so that Zed reattaches to them and restores their recent output when it restarts.
+    ///
+    /// Default: false
+    pub persistent_sessions: Option<bool>,
+    /// How many minutes a persistent session keeps running while no terminal is attached to it,
+    /// e.g. after Zed quit. 0 keeps it running until its shell exits.
+    ///
+    /// Default: 1440
+    pub persistent_session_timeout_minutes: Option<u64>,
     /// Whether to show the terminal button in the status bar.
     ///
     /// Default: true
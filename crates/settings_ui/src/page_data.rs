@@ -6140,7 +6140,7 @@ fn terminal_page() -> SettingsPage {
         ]
     }
 
-    fn behavior_settings_section() -> [SettingsPageItem; 4] {
+    fn behavior_settings_section() -> [SettingsPageItem; 7] {
         [
             SettingsPageItem::SectionHeader("Behavior Settings"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -6201,6 +6201,72 @@ fn terminal_page() -> SettingsPage {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Shell Integration",
+                description: "Whether to load Zed's shell integration into bash, zsh and fish.",
+                field: Box::new(SettingField {
+                    json_path: Some("terminal.shell_integration"),
+                    pick: |settings_content| {
+                        settings_content
+                            .terminal
+                            .as_ref()?
+                            .shell_integration
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .terminal
+                            .get_or_insert_default()
+                            .shell_integration = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Persistent Sessions",
+                description: "Whether terminal shells keep running when Zed quits, to be reattached to when it restarts.",
+                field: Box::new(SettingField {
+                    json_path: Some("terminal.persistent_sessions"),
+                    pick: |settings_content| {
+                        settings_content
+                            .terminal
+                            .as_ref()?
+                            .persistent_sessions
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .terminal
+                            .get_or_insert_default()
+                            .persistent_sessions = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Persistent Session Timeout",
+                description: "Minutes a persistent session keeps running while detached. 0 keeps it running until its shell exits.",
+                field: Box::new(SettingField {
+                    json_path: Some("terminal.persistent_session_timeout_minutes"),
+                    pick: |settings_content| {
+                        settings_content
+                            .terminal
+                            .as_ref()?
+                            .persistent_session_timeout_minutes
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .terminal
+                            .get_or_insert_default()
+                            .persistent_session_timeout_minutes = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
         ]
     }
 
@@ -80,6 +80,18 @@ const TOOLS: &[ToolInfo] = &[
         description: "Discards unsaved changes by reloading from disk",
         regex_explanation: "Patterns are matched against the file path being restored.",
     },
+    ToolInfo {
+        id: "rename_symbol",
+        name: "Rename Symbol",
+        description: "Project-wide symbol renames by language servers",
+        regex_explanation: "Patterns are matched against the path of the file the renamed symbol is in.",
+    },
+    ToolInfo {
+        id: "apply_code_action",
+        name: "Apply Code Action",
+        description: "Language server code actions, like quick fixes and refactorings",
+        regex_explanation: "Patterns are matched against the path of the file the code action is applied in.",
+    },
 ];
 
 pub(crate) struct ToolInfo {
@@ -307,6 +319,8 @@ fn get_tool_render_fn(
         "fetch" => render_fetch_tool_config,
         "web_search" => render_web_search_tool_config,
         "restore_file_from_disk" => render_restore_file_from_disk_tool_config,
+        "rename_symbol" => render_rename_symbol_tool_config,
+        "apply_code_action" => render_apply_code_action_tool_config,
         _ => render_terminal_tool_config, // fallback
     }
 }
@@ -1386,6 +1400,8 @@ tool_config_page_fn!(
     render_restore_file_from_disk_tool_config,
     "restore_file_from_disk"
 );
+tool_config_page_fn!(render_rename_symbol_tool_config, "rename_symbol");
+tool_config_page_fn!(render_apply_code_action_tool_config, "apply_code_action");
 
 #[cfg(test)]
 mod tests {
@@ -1399,14 +1415,23 @@ mod tests {
         //   2. Add it to this list with a comment explaining why it's excluded.
         const EXCLUDED_TOOLS: &[&str] = &[
             // Read-only / low-risk tools that don't call decide_permission_from_settings
+            "code_actions",
             "diagnostics",
             "find_path",
+            "find_references",
+            "git_blame",
+            "git_diff",
+            "git_log",
+            "git_show",
+            "go_to_definition",
             "grep",
+            "hover",
             "list_directory",
             "now",
             "open",
             "read_file",
             "thinking",
+            "workspace_symbols",
             // streaming_edit_file uses "edit_file" for permission lookups,
             // so its rules are configured under the edit_file entry.
             "streaming_edit_file",
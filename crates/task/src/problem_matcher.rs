@@ -0,0 +1,508 @@
+use anyhow::Context as _;
+use regex::{Captures, Regex};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A problem matcher to apply to the task's output: either a name of a built-in one, or a custom definition.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ProblemMatcherTemplate {
+    /// One of the built-in matchers: `$rustc` (also `$cargo`), `$tsc`, `$gcc`, `$eslint-stylish` or `$eslint-compact`.
+    Preset(String),
+    /// A custom matcher definition.
+    Custom(ProblemMatcher),
+}
+
+impl ProblemMatcherTemplate {
+    /// Returns the matcher definition, looking up the built-in matchers by name.
+    pub fn to_problem_matcher(&self) -> Option<ProblemMatcher> {
+        match self {
+            Self::Preset(name) => {
+                let matcher = ProblemMatcher::preset(name);
+                if matcher.is_none() {
+                    log::warn!("Unknown problem matcher `{name}`");
+                }
+                matcher
+            }
+            Self::Custom(matcher) => Some(matcher.clone()),
+        }
+    }
+}
+
+/// Describes how to extract problems (errors, warnings, etc.) from a task's output.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProblemMatcher {
+    /// Name to show as the source of the diagnostics produced by the matcher, e.g. `rustc`.
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Severity of problems whose pattern does not capture one.
+    #[serde(default)]
+    pub severity: ProblemSeverity,
+    /// How to interpret the file paths in the output:
+    /// * `auto_detect` — absolute paths are used as is, others are relative to the base directory (default)
+    /// * `absolute` — paths are always absolute
+    /// * `relative` — paths are always relative to the base directory
+    #[serde(default)]
+    pub file_location: FileLocation,
+    /// A directory relative paths are resolved against, defaults to the task's working directory.
+    #[serde(default)]
+    pub base_directory: Option<String>,
+    /// Patterns to match against consecutive lines of the output.
+    /// A problem is reported once the last pattern matches; all patterns contribute to it.
+    pub pattern: Vec<ProblemPattern>,
+}
+
+/// A regular expression, matched against a single line of the task output,
+/// along with the indices of the capture groups to read the problem's details from.
+#[derive(Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProblemPattern {
+    pub regexp: String,
+    #[serde(default)]
+    pub file: Option<usize>,
+    #[serde(default)]
+    pub line: Option<usize>,
+    #[serde(default)]
+    pub column: Option<usize>,
+    #[serde(default)]
+    pub end_line: Option<usize>,
+    #[serde(default)]
+    pub end_column: Option<usize>,
+    #[serde(default)]
+    pub severity: Option<usize>,
+    #[serde(default)]
+    pub code: Option<usize>,
+    #[serde(default)]
+    pub message: Option<usize>,
+    /// For the last pattern of a multi-line matcher: whether it may match several lines in a row,
+    /// producing a problem for each of them.
+    #[serde(default)]
+    pub r#loop: bool,
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FileLocation {
+    Absolute,
+    Relative,
+    #[default]
+    AutoDetect,
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemSeverity {
+    #[default]
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+impl ProblemSeverity {
+    pub(crate) fn parse(severity: &str) -> Option<Self> {
+        let severity = severity.trim().to_lowercase();
+        if severity.starts_with("err") || severity.starts_with("fatal") {
+            Some(Self::Error)
+        } else if severity.starts_with("warn") {
+            Some(Self::Warning)
+        } else if severity.starts_with("info") || severity.starts_with("note") {
+            Some(Self::Info)
+        } else if severity.starts_with("hint") || severity.starts_with("help") {
+            Some(Self::Hint)
+        } else {
+            None
+        }
+    }
+}
+
+/// A problem found in the task output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    /// The file path, as printed by the task.
+    pub file: String,
+    /// 1-based line of the problem.
+    pub line: u32,
+    /// 1-based column of the problem, if known.
+    pub column: Option<u32>,
+    pub end_line: Option<u32>,
+    pub end_column: Option<u32>,
+    pub severity: ProblemSeverity,
+    pub code: Option<String>,
+    pub message: String,
+}
+
+#[derive(Clone, Default)]
+struct PartialProblem {
+    file: Option<String>,
+    line: Option<u32>,
+    column: Option<u32>,
+    end_line: Option<u32>,
+    end_column: Option<u32>,
+    severity: Option<ProblemSeverity>,
+    code: Option<String>,
+    message: Option<String>,
+}
+
+impl PartialProblem {
+    fn fill(&mut self, pattern: &ProblemPattern, captures: &Captures) {
+        let text = |group: Option<usize>| {
+            group
+                .and_then(|group| captures.get(group))
+                .map(|capture| capture.as_str().trim())
+                .filter(|text| !text.is_empty())
+        };
+        let number = |group: Option<usize>| text(group).and_then(|text| text.parse().ok());
+
+        if let Some(file) = text(pattern.file) {
+            self.file = Some(file.to_owned());
+        }
+        self.line = number(pattern.line).or(self.line);
+        self.column = number(pattern.column).or(self.column);
+        self.end_line = number(pattern.end_line).or(self.end_line);
+        self.end_column = number(pattern.end_column).or(self.end_column);
+        if let Some(severity) = text(pattern.severity).and_then(ProblemSeverity::parse) {
+            self.severity = Some(severity);
+        }
+        if let Some(code) = text(pattern.code) {
+            self.code = Some(code.to_owned());
+        }
+        match text(pattern.message) {
+            Some(message) => self.message = Some(message.to_owned()),
+            None if pattern.message.is_none() && self.message.is_none() => {
+                self.message = captures.get(0).map(|line| line.as_str().trim().to_owned());
+            }
+            None => {}
+        }
+    }
+
+    fn build(self, default_severity: ProblemSeverity) -> Option<Problem> {
+        Some(Problem {
+            file: self.file?,
+            line: self.line.unwrap_or(1),
+            column: self.column,
+            end_line: self.end_line,
+            end_column: self.end_column,
+            severity: self.severity.unwrap_or(default_severity),
+            code: self.code,
+            message: self.message.unwrap_or_default(),
+        })
+    }
+}
+
+impl ProblemMatcher {
+    /// Looks up a built-in matcher by its name, with or without the leading `$`.
+    pub fn preset(name: &str) -> Option<Self> {
+        let name = name.strip_prefix('$').unwrap_or(name);
+        let matcher = match name {
+            "rustc" | "cargo" => Self {
+                owner: Some("rustc".to_owned()),
+                severity: ProblemSeverity::Error,
+                file_location: FileLocation::AutoDetect,
+                base_directory: None,
+                pattern: vec![
+                    ProblemPattern {
+                        regexp: r"^(warning|error)(?:\[(\S+)\])?: (.*)$".to_owned(),
+                        severity: Some(1),
+                        code: Some(2),
+                        message: Some(3),
+                        ..ProblemPattern::default()
+                    },
+                    ProblemPattern {
+                        regexp: r"^\s*--> (.*?):(\d+):(\d+)$".to_owned(),
+                        file: Some(1),
+                        line: Some(2),
+                        column: Some(3),
+                        ..ProblemPattern::default()
+                    },
+                ],
+            },
+            "tsc" | "tsc-watch" => Self {
+                owner: Some("tsc".to_owned()),
+                severity: ProblemSeverity::Error,
+                file_location: FileLocation::Relative,
+                base_directory: None,
+                pattern: vec![ProblemPattern {
+                    regexp: r"^([^\s].*)[\(:](\d+)[,:](\d+)(?:\):\s+|\s+-\s+)(error|warning|info)\s+(TS\d+)\s*:\s*(.*)$".to_owned(),
+                    file: Some(1),
+                    line: Some(2),
+                    column: Some(3),
+                    severity: Some(4),
+                    code: Some(5),
+                    message: Some(6),
+                    ..ProblemPattern::default()
+                }],
+            },
+            "gcc" => Self {
+                owner: Some("gcc".to_owned()),
+                severity: ProblemSeverity::Error,
+                file_location: FileLocation::AutoDetect,
+                base_directory: None,
+                pattern: vec![ProblemPattern {
+                    regexp: r"^(.*?):(\d+):(\d*):?\s+(?:fatal\s+)?(warning|error|note):\s+(.*)$"
+                        .to_owned(),
+                    file: Some(1),
+                    line: Some(2),
+                    column: Some(3),
+                    severity: Some(4),
+                    message: Some(5),
+                    ..ProblemPattern::default()
+                }],
+            },
+            "eslint" | "eslint-stylish" => Self {
+                owner: Some("eslint".to_owned()),
+                severity: ProblemSeverity::Error,
+                file_location: FileLocation::Absolute,
+                base_directory: None,
+                pattern: vec![
+                    ProblemPattern {
+                        regexp: r"^([^\s].*)$".to_owned(),
+                        file: Some(1),
+                        ..ProblemPattern::default()
+                    },
+                    ProblemPattern {
+                        regexp: r"^\s+(\d+):(\d+)\s+(error|warning|info)\s+(.*?)(?:\s\s+(\S+))?$"
+                            .to_owned(),
+                        line: Some(1),
+                        column: Some(2),
+                        severity: Some(3),
+                        message: Some(4),
+                        code: Some(5),
+                        r#loop: true,
+                        ..ProblemPattern::default()
+                    },
+                ],
+            },
+            "eslint-compact" => Self {
+                owner: Some("eslint".to_owned()),
+                severity: ProblemSeverity::Error,
+                file_location: FileLocation::Relative,
+                base_directory: None,
+                pattern: vec![ProblemPattern {
+                    regexp: r"^(.+):\sline\s(\d+),\scol\s(\d+),\s(Error|Warning|Info)\s-\s(.+)\s\((.+)\)$"
+                        .to_owned(),
+                    file: Some(1),
+                    line: Some(2),
+                    column: Some(3),
+                    severity: Some(4),
+                    message: Some(5),
+                    code: Some(6),
+                    ..ProblemPattern::default()
+                }],
+            },
+            _ => return None,
+        };
+        Some(matcher)
+    }
+
+    /// Finds all problems in the output given, processing it line by line.
+    pub fn match_output(&self, output: &str) -> anyhow::Result<Vec<Problem>> {
+        let patterns = self
+            .pattern
+            .iter()
+            .map(|pattern| {
+                Regex::new(&pattern.regexp)
+                    .with_context(|| format!("invalid problem pattern `{}`", pattern.regexp))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let Some(last_ix) = patterns.len().checked_sub(1) else {
+            return Ok(Vec::new());
+        };
+
+        let mut problems = Vec::new();
+        // Index of the pattern the next line is expected to match,
+        // and the details gathered from the lines matched so far.
+        let mut next_ix = 0;
+        let mut partial_problem = PartialProblem::default();
+        for line in output.lines() {
+            let line = line.trim_end();
+            let mut captures = patterns[next_ix].captures(line);
+            if captures.is_none() && next_ix > 0 {
+                next_ix = 0;
+                partial_problem = PartialProblem::default();
+                captures = patterns[0].captures(line);
+            }
+            let Some(captures) = captures else {
+                continue;
+            };
+
+            if next_ix == last_ix {
+                let mut problem = partial_problem.clone();
+                problem.fill(&self.pattern[next_ix], &captures);
+                problems.extend(problem.build(self.severity));
+                if !(self.pattern[next_ix].r#loop && last_ix > 0) {
+                    next_ix = 0;
+                    partial_problem = PartialProblem::default();
+                }
+            } else {
+                partial_problem.fill(&self.pattern[next_ix], &captures);
+                next_ix += 1;
+            }
+        }
+        Ok(problems)
+    }
+
+    /// Resolves the path of a problem's file, relative paths are resolved against the base directory given.
+    pub fn resolve_path(&self, file: &str, base_directory: Option<&Path>) -> Option<PathBuf> {
+        let path = Path::new(file);
+        match self.file_location {
+            FileLocation::Absolute => path.is_absolute().then(|| path.to_path_buf()),
+            FileLocation::Relative => Some(base_directory?.join(path)),
+            FileLocation::AutoDetect if path.is_absolute() => Some(path.to_path_buf()),
+            FileLocation::AutoDetect => Some(base_directory?.join(path)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_rustc_matcher() {
+        let output = "\
+   Compiling demo v0.1.0 (/project)
+warning: unused variable: `x`
+ --> src/main.rs:2:9
+  |
+2 |     let x = 5;
+  |         ^ help: if this is intentional, prefix it with an underscore: `_x`
+
+error[E0308]: mismatched types
+  --> src/lib.rs:10:18
+   |
+warning: `demo` (bin \"demo\") generated 1 warning
+";
+        let problems = ProblemMatcher::preset("$rustc")
+            .unwrap()
+            .match_output(output)
+            .unwrap();
+        assert_eq!(
+            problems,
+            vec![
+                Problem {
+                    file: "src/main.rs".to_owned(),
+                    line: 2,
+                    column: Some(9),
+                    end_line: None,
+                    end_column: None,
+                    severity: ProblemSeverity::Warning,
+                    code: None,
+                    message: "unused variable: `x`".to_owned(),
+                },
+                Problem {
+                    file: "src/lib.rs".to_owned(),
+                    line: 10,
+                    column: Some(18),
+                    end_line: None,
+                    end_column: None,
+                    severity: ProblemSeverity::Error,
+                    code: Some("E0308".to_owned()),
+                    message: "mismatched types".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_single_line_matchers() {
+        let tsc = ProblemMatcher::preset("tsc").unwrap();
+        assert_eq!(
+            tsc.match_output(
+                "src/index.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'."
+            )
+            .unwrap(),
+            vec![Problem {
+                file: "src/index.ts".to_owned(),
+                line: 3,
+                column: Some(7),
+                end_line: None,
+                end_column: None,
+                severity: ProblemSeverity::Error,
+                code: Some("TS2322".to_owned()),
+                message: "Type 'string' is not assignable to type 'number'.".to_owned(),
+            }]
+        );
+
+        let gcc = ProblemMatcher::preset("gcc").unwrap();
+        assert_eq!(
+            gcc.match_output("main.c: In function 'main':\nmain.c:4:5: warning: implicit declaration of function 'foo'")
+                .unwrap(),
+            vec![Problem {
+                file: "main.c".to_owned(),
+                line: 4,
+                column: Some(5),
+                end_line: None,
+                end_column: None,
+                severity: ProblemSeverity::Warning,
+                code: None,
+                message: "implicit declaration of function 'foo'".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_looping_matcher() {
+        let output = "\
+/project/src/a.js
+  1:10  error    'foo' is defined but never used  no-unused-vars
+  3:1   warning  Unexpected console statement     no-console
+
+/project/src/b.js
+  7:3  error  Missing semicolon  semi
+
+✖ 3 problems (2 errors, 1 warning)
+";
+        let problems = ProblemMatcher::preset("$eslint-stylish")
+            .unwrap()
+            .match_output(output)
+            .unwrap();
+        assert_eq!(
+            problems
+                .iter()
+                .map(|problem| (
+                    problem.file.as_str(),
+                    problem.line,
+                    problem.severity,
+                    problem.code.as_deref()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "/project/src/a.js",
+                    1,
+                    ProblemSeverity::Error,
+                    Some("no-unused-vars")
+                ),
+                (
+                    "/project/src/a.js",
+                    3,
+                    ProblemSeverity::Warning,
+                    Some("no-console")
+                ),
+                ("/project/src/b.js", 7, ProblemSeverity::Error, Some("semi")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolving_problem_paths() {
+        let mut matcher = ProblemMatcher::preset("gcc").unwrap();
+        let base = Path::new("/project");
+        assert_eq!(
+            matcher.resolve_path("src/main.c", Some(base)),
+            Some(PathBuf::from("/project/src/main.c"))
+        );
+        assert_eq!(
+            matcher.resolve_path("/usr/include/stdio.h", Some(base)),
+            Some(PathBuf::from("/usr/include/stdio.h"))
+        );
+        assert_eq!(matcher.resolve_path("src/main.c", None), None);
+
+        matcher.file_location = FileLocation::Absolute;
+        assert_eq!(matcher.resolve_path("src/main.c", Some(base)), None);
+    }
+}
@@ -9,8 +9,9 @@ use util::serde::default_true;
 use util::{ResultExt, truncate_and_remove_front};
 
 use crate::{
-    AttachRequest, ResolvedTask, RevealTarget, Shell, SpawnInTerminal, TaskContext, TaskId,
-    VariableName, ZED_VARIABLE_NAME_PREFIX, serde_helpers::non_empty_string_vec,
+    AttachRequest, ProblemMatcherTemplate, ResolvedTask, RevealTarget, Shell, SpawnInTerminal,
+    TaskContext, TaskId, VariableName, ZED_VARIABLE_NAME_PREFIX,
+    serde_helpers::non_empty_string_vec,
 };
 
 /// A template definition of a Zed task to run.
@@ -72,6 +73,20 @@ pub struct TaskTemplate {
     /// Whether to show the command line in the task output.
     #[serde(default = "default_true")]
     pub show_command: bool,
+    /// Labels of the tasks that have to finish successfully before this task is spawned.
+    /// A task with dependencies may omit its `command`, in which case it only runs its dependencies.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// How the `depends_on` tasks are run:
+    /// * `parallel` — all dependencies are spawned at once (default)
+    /// * `sequence` — dependencies are spawned one after another, in the order they are listed
+    #[serde(default)]
+    pub depends_order: DependsOrder,
+    /// Matchers to find problems in the task output, reported as diagnostics after the task runs.
+    /// Either names of the built-in matchers (`$rustc`, `$tsc`, `$gcc`, `$eslint-stylish`, `$eslint-compact`),
+    /// or custom definitions with regular expressions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub problem_matchers: Vec<ProblemMatcherTemplate>,
 }
 
 #[derive(Deserialize, Eq, PartialEq, Clone, Debug)]
@@ -96,6 +111,17 @@ pub enum RevealStrategy {
     Never,
 }
 
+/// How the dependencies of a task are run before the task itself.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DependsOrder {
+    /// Spawn all dependencies at once and wait for all of them to finish.
+    #[default]
+    Parallel,
+    /// Spawn dependencies one after another, stopping at the first failure.
+    Sequence,
+}
+
 /// What to do with the terminal pane and tab, after the command has finished.
 #[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -132,7 +158,8 @@ impl TaskTemplate {
     /// Every [`ResolvedTask`] gets a [`TaskId`], based on the `id_base` (to avoid collision with various task sources),
     /// and hashes of its template and [`TaskContext`], see [`ResolvedTask`] fields' documentation for more details.
     pub fn resolve_task(&self, id_base: &str, cx: &TaskContext) -> Option<ResolvedTask> {
-        if self.label.trim().is_empty() || self.command.trim().is_empty() {
+        if self.label.trim().is_empty() || (self.command.trim().is_empty() && !self.is_composite())
+        {
             return None;
         }
 
@@ -212,6 +239,23 @@ impl TaskTemplate {
             &mut substituted_variables,
         )?;
 
+        let problem_matchers = self
+            .problem_matchers
+            .iter()
+            .filter_map(|template| {
+                let mut matcher = template.to_problem_matcher()?;
+                if let Some(base_directory) = &matcher.base_directory {
+                    matcher.base_directory = Some(substitute_all_template_variables_in_str(
+                        base_directory,
+                        &task_variables,
+                        &variable_names,
+                        &mut substituted_variables,
+                    )?);
+                }
+                Some(matcher)
+            })
+            .collect();
+
         let task_hash = to_hex_hash(self)
             .context("hashing task template")
             .log_err()?;
@@ -243,6 +287,7 @@ impl TaskTemplate {
         Some(ResolvedTask {
             id: id.clone(),
             substituted_variables,
+            dependency_context: (!self.depends_on.is_empty()).then(|| cx.clone()),
             original_task: self.clone(),
             resolved_label: full_label.clone(),
             resolved: SpawnInTerminal {
@@ -270,10 +315,16 @@ impl TaskTemplate {
                 show_summary: self.show_summary,
                 show_command: self.show_command,
                 show_rerun: true,
+                problem_matchers,
             },
         })
     }
 
+    /// Whether this template only groups other tasks via `depends_on` and has no command of its own.
+    pub fn is_composite(&self) -> bool {
+        self.command.trim().is_empty() && !self.depends_on.is_empty()
+    }
+
     /// Validates that all `$ZED_*` variables used in this template are known
     /// variable names, returning a vector with all of the unique unknown
     /// variables.
@@ -523,6 +574,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolving_composite_templates() {
+        let composite_task = TaskTemplate {
+            label: "build and run".to_string(),
+            depends_on: vec!["build".to_string(), "run".to_string()],
+            depends_order: DependsOrder::Sequence,
+            ..TaskTemplate::default()
+        };
+        assert!(composite_task.is_composite());
+
+        let cx = TaskContext {
+            cwd: Some(PathBuf::from("/project")),
+            ..TaskContext::default()
+        };
+        let resolved_task = composite_task
+            .resolve_task(TEST_ID_BASE, &cx)
+            .expect("tasks with dependencies should resolve without a command");
+        assert_eq!(resolved_task.resolved_label, "build and run");
+        assert_eq!(resolved_task.dependency_context(), Some(&cx));
+
+        let task_with_command = TaskTemplate {
+            label: "run".to_string(),
+            command: "cargo run".to_string(),
+            ..TaskTemplate::default()
+        };
+        assert!(!task_with_command.is_composite());
+        let resolved_task = task_with_command.resolve_task(TEST_ID_BASE, &cx).unwrap();
+        assert_eq!(
+            resolved_task.dependency_context(),
+            None,
+            "tasks without dependencies should not keep their context around"
+        );
+    }
+
     #[test]
     fn test_template_cwd_resolution() {
         let task_without_cwd = TaskTemplate {
@@ -0,0 +1,460 @@
+use anyhow::Context as _;
+use collections::HashMap;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Structured test output formats Zed knows how to read results from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TestReportFormat {
+    /// libtest's default output, printed by `cargo test`.
+    Libtest,
+    /// libtest's `--format json` events, printed by `cargo test` when unstable options are allowed.
+    LibtestJson,
+    /// `go test -json` events.
+    GoTestJson,
+    /// JUnit XML report, written by e.g. `pytest --junitxml`.
+    JunitXml,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TestOutcome {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+/// Result of a single test, as reported by the test runner.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestResult {
+    /// Name of the test, e.g. `tests::it_works`, `TestParse/empty` or `test_parse[empty]`.
+    pub name: String,
+    /// Package, module or class the test belongs to, when reported separately from its name.
+    pub suite: Option<String>,
+    pub outcome: TestOutcome,
+    pub duration: Option<Duration>,
+    /// Failure message or the output captured from the test.
+    pub message: Option<String>,
+}
+
+impl TestReportFormat {
+    /// Reads all test results from the report.
+    /// JSON formats are read from the runner's output, ignoring lines that are not test events.
+    pub fn parse(self, report: &str) -> anyhow::Result<Vec<TestResult>> {
+        match self {
+            Self::Libtest => Ok(parse_libtest(report)),
+            Self::LibtestJson => Ok(parse_libtest_json(report)),
+            Self::GoTestJson => Ok(parse_go_test_json(report)),
+            Self::JunitXml => parse_junit_xml(report),
+        }
+    }
+}
+
+fn json_lines<'a, T: Deserialize<'a>>(output: &'a str) -> impl Iterator<Item = T> + 'a {
+    output.lines().filter_map(|line| {
+        let line = line.trim();
+        if line.starts_with('{') {
+            serde_json::from_str(line).ok()
+        } else {
+            None
+        }
+    })
+}
+
+/// Reads the `test <name> ... <outcome>` lines, and the captured output printed for the failed tests:
+///
+/// ```text
+/// test tests::fails ... FAILED
+///
+/// failures:
+///
+/// ---- tests::fails stdout ----
+/// thread 'tests::fails' panicked at src/lib.rs:12:9:
+/// boom
+/// ```
+fn parse_libtest(output: &str) -> Vec<TestResult> {
+    let mut results = Vec::new();
+    let mut outputs = HashMap::<&str, String>::default();
+    let mut current_output = None;
+    for line in output.lines() {
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|line| line.strip_suffix(" ----"))
+            .and_then(|line| {
+                line.strip_suffix(" stdout")
+                    .or_else(|| line.strip_suffix(" stderr"))
+            })
+        {
+            current_output = Some(name);
+            continue;
+        }
+        if let Some(name) = current_output {
+            if line == "failures:" || line.starts_with("test result:") {
+                current_output = None;
+            } else {
+                let output = outputs.entry(name).or_default();
+                output.push_str(line);
+                output.push('\n');
+            }
+            continue;
+        }
+
+        let Some((name, outcome)) = line
+            .strip_prefix("test ")
+            .and_then(|line| line.split_once(" ... "))
+        else {
+            continue;
+        };
+        let outcome = match outcome.trim() {
+            "ok" => TestOutcome::Passed,
+            "FAILED" => TestOutcome::Failed,
+            outcome if outcome.starts_with("ignored") => TestOutcome::Skipped,
+            _ => continue,
+        };
+        let name = name.strip_suffix(" - should panic").unwrap_or(name);
+        results.push(TestResult {
+            name: name.to_string(),
+            suite: None,
+            outcome,
+            duration: None,
+            message: None,
+        });
+    }
+
+    for result in &mut results {
+        result.message = outputs
+            .remove(result.name.as_str())
+            .map(|output| output.trim_end().to_string())
+            .filter(|output| !output.is_empty());
+    }
+    results
+}
+
+#[derive(Deserialize)]
+struct LibtestEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    event: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    exec_time: Option<f64>,
+    #[serde(default)]
+    stdout: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+fn parse_libtest_json(output: &str) -> Vec<TestResult> {
+    json_lines::<LibtestEvent>(output)
+        .filter(|event| event.kind == "test")
+        .filter_map(|event| {
+            let outcome = match event.event.as_str() {
+                "ok" => TestOutcome::Passed,
+                "failed" | "timeout" => TestOutcome::Failed,
+                "ignored" => TestOutcome::Skipped,
+                _ => return None,
+            };
+            Some(TestResult {
+                name: event.name?,
+                suite: None,
+                outcome,
+                duration: event.exec_time.map(Duration::from_secs_f64),
+                message: event
+                    .stdout
+                    .or(event.message)
+                    .filter(|message| !message.is_empty()),
+            })
+        })
+        .collect()
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GoTestEvent {
+    action: String,
+    #[serde(default)]
+    package: Option<String>,
+    #[serde(default)]
+    test: Option<String>,
+    #[serde(default)]
+    elapsed: Option<f64>,
+    #[serde(default)]
+    output: Option<String>,
+}
+
+fn parse_go_test_json(output: &str) -> Vec<TestResult> {
+    let mut outputs = HashMap::<(Option<String>, String), String>::default();
+    let mut results = Vec::new();
+    for event in json_lines::<GoTestEvent>(output) {
+        // Package-level events carry no test name.
+        let Some(test) = event.test else {
+            continue;
+        };
+        let outcome = match event.action.as_str() {
+            "output" => {
+                if let Some(output) = event.output {
+                    outputs
+                        .entry((event.package, test))
+                        .or_default()
+                        .push_str(&output);
+                }
+                continue;
+            }
+            "pass" => TestOutcome::Passed,
+            "fail" => TestOutcome::Failed,
+            "skip" => TestOutcome::Skipped,
+            _ => continue,
+        };
+        let message = outputs
+            .remove(&(event.package.clone(), test.clone()))
+            .filter(|_| outcome != TestOutcome::Passed);
+        results.push(TestResult {
+            name: test,
+            suite: event.package,
+            outcome,
+            duration: event.elapsed.map(Duration::from_secs_f64),
+            message,
+        });
+    }
+    results
+}
+
+fn parse_junit_xml(report: &str) -> anyhow::Result<Vec<TestResult>> {
+    let document = roxmltree::Document::parse(report).context("parsing JUnit XML report")?;
+    let results = document
+        .descendants()
+        .filter(|node| node.has_tag_name("testcase"))
+        .filter_map(|test_case| {
+            let name = test_case.attribute("name")?.to_string();
+            let problem = test_case.children().find(|child| {
+                child.has_tag_name("failure")
+                    || child.has_tag_name("error")
+                    || child.has_tag_name("skipped")
+            });
+            let (outcome, message) = match problem {
+                Some(problem) => {
+                    let outcome = if problem.has_tag_name("skipped") {
+                        TestOutcome::Skipped
+                    } else {
+                        TestOutcome::Failed
+                    };
+                    let message = problem
+                        .text()
+                        .map(str::trim)
+                        .filter(|text| !text.is_empty())
+                        .or_else(|| problem.attribute("message"))
+                        .map(ToOwned::to_owned);
+                    (outcome, message)
+                }
+                None => (TestOutcome::Passed, None),
+            };
+            Some(TestResult {
+                name,
+                suite: test_case.attribute("classname").map(ToOwned::to_owned),
+                outcome,
+                duration: test_case
+                    .attribute("time")
+                    .and_then(|time| time.parse::<f64>().ok())
+                    .map(Duration::from_secs_f64),
+                message,
+            })
+        })
+        .collect();
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_libtest() {
+        let output = r#"
+   Compiling demo v0.1.0 (/project)
+    Finished `test` profile [unoptimized + debuginfo] target(s) in 0.50s
+     Running unittests src/lib.rs (target/debug/deps/demo-1234)
+
+running 4 tests
+test tests::slow ... ignored, needs network
+test tests::passes ... ok
+test tests::panics - should panic ... ok
+test tests::fails ... FAILED
+
+failures:
+
+---- tests::fails stdout ----
+thread 'tests::fails' panicked at src/lib.rs:12:9:
+boom
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+failures:
+    tests::fails
+
+test result: FAILED. 2 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.75s
+"#;
+        assert_eq!(
+            TestReportFormat::Libtest.parse(output).unwrap(),
+            vec![
+                TestResult {
+                    name: "tests::slow".to_string(),
+                    suite: None,
+                    outcome: TestOutcome::Skipped,
+                    duration: None,
+                    message: None,
+                },
+                TestResult {
+                    name: "tests::passes".to_string(),
+                    suite: None,
+                    outcome: TestOutcome::Passed,
+                    duration: None,
+                    message: None,
+                },
+                TestResult {
+                    name: "tests::panics".to_string(),
+                    suite: None,
+                    outcome: TestOutcome::Passed,
+                    duration: None,
+                    message: None,
+                },
+                TestResult {
+                    name: "tests::fails".to_string(),
+                    suite: None,
+                    outcome: TestOutcome::Failed,
+                    duration: None,
+                    message: Some(
+                        "thread 'tests::fails' panicked at src/lib.rs:12:9:\nboom\nnote: run with `RUST_BACKTRACE=1` environment variable to display a backtrace".to_string()
+                    ),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_libtest_json() {
+        let output = r#"
+   Compiling demo v0.1.0 (/project)
+    Finished `test` profile [unoptimized + debuginfo] target(s) in 0.50s
+     Running unittests src/lib.rs (target/debug/deps/demo-1234)
+{ "type": "suite", "event": "started", "test_count": 3 }
+{ "type": "test", "event": "started", "name": "tests::passes" }
+{ "type": "test", "event": "started", "name": "tests::fails" }
+{ "type": "test", "name": "tests::passes", "event": "ok", "exec_time": 0.5 }
+{ "type": "test", "name": "tests::fails", "event": "failed", "exec_time": 0.25, "stdout": "thread 'tests::fails' panicked at src/lib.rs:12:9:\nboom\n" }
+{ "type": "test", "event": "ignored", "name": "tests::slow" }
+{ "type": "suite", "event": "failed", "passed": 1, "failed": 1, "ignored": 1, "measured": 0, "filtered_out": 0, "exec_time": 0.75 }
+"#;
+        assert_eq!(
+            TestReportFormat::LibtestJson.parse(output).unwrap(),
+            vec![
+                TestResult {
+                    name: "tests::passes".to_string(),
+                    suite: None,
+                    outcome: TestOutcome::Passed,
+                    duration: Some(Duration::from_millis(500)),
+                    message: None,
+                },
+                TestResult {
+                    name: "tests::fails".to_string(),
+                    suite: None,
+                    outcome: TestOutcome::Failed,
+                    duration: Some(Duration::from_millis(250)),
+                    message: Some(
+                        "thread 'tests::fails' panicked at src/lib.rs:12:9:\nboom\n".to_string()
+                    ),
+                },
+                TestResult {
+                    name: "tests::slow".to_string(),
+                    suite: None,
+                    outcome: TestOutcome::Skipped,
+                    duration: None,
+                    message: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_go_test_json() {
+        let output = r#"
+{"Action":"start","Package":"example.com/demo"}
+{"Action":"run","Package":"example.com/demo","Test":"TestPasses"}
+{"Action":"output","Package":"example.com/demo","Test":"TestPasses","Output":"=== RUN   TestPasses\n"}
+{"Action":"pass","Package":"example.com/demo","Test":"TestPasses","Elapsed":0.5}
+{"Action":"run","Package":"example.com/demo","Test":"TestFails"}
+{"Action":"output","Package":"example.com/demo","Test":"TestFails","Output":"=== RUN   TestFails\n"}
+{"Action":"output","Package":"example.com/demo","Test":"TestFails","Output":"    demo_test.go:12: boom\n"}
+{"Action":"fail","Package":"example.com/demo","Test":"TestFails","Elapsed":0.25}
+{"Action":"fail","Package":"example.com/demo","Elapsed":0.75}
+"#;
+        assert_eq!(
+            TestReportFormat::GoTestJson.parse(output).unwrap(),
+            vec![
+                TestResult {
+                    name: "TestPasses".to_string(),
+                    suite: Some("example.com/demo".to_string()),
+                    outcome: TestOutcome::Passed,
+                    duration: Some(Duration::from_millis(500)),
+                    message: None,
+                },
+                TestResult {
+                    name: "TestFails".to_string(),
+                    suite: Some("example.com/demo".to_string()),
+                    outcome: TestOutcome::Failed,
+                    duration: Some(Duration::from_millis(250)),
+                    message: Some("=== RUN   TestFails\n    demo_test.go:12: boom\n".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_junit_xml() {
+        let report = r#"<?xml version="1.0" encoding="utf-8"?>
+<testsuites>
+  <testsuite name="pytest" errors="0" failures="1" skipped="1" tests="3" time="0.75">
+    <testcase classname="tests.test_demo" name="test_passes" time="0.5" />
+    <testcase classname="tests.test_demo" name="test_fails" time="0.25">
+      <failure message="assert 1 == 2">def test_fails():
+&gt;       assert 1 == 2
+E       assert 1 == 2</failure>
+    </testcase>
+    <testcase classname="tests.test_demo" name="test_skipped[slow]" time="0">
+      <skipped type="pytest.skip" message="too slow" />
+    </testcase>
+  </testsuite>
+</testsuites>
+"#;
+        assert_eq!(
+            TestReportFormat::JunitXml.parse(report).unwrap(),
+            vec![
+                TestResult {
+                    name: "test_passes".to_string(),
+                    suite: Some("tests.test_demo".to_string()),
+                    outcome: TestOutcome::Passed,
+                    duration: Some(Duration::from_millis(500)),
+                    message: None,
+                },
+                TestResult {
+                    name: "test_fails".to_string(),
+                    suite: Some("tests.test_demo".to_string()),
+                    outcome: TestOutcome::Failed,
+                    duration: Some(Duration::from_millis(250)),
+                    message: Some(
+                        "def test_fails():\n>       assert 1 == 2\nE       assert 1 == 2"
+                            .to_string()
+                    ),
+                },
+                TestResult {
+                    name: "test_skipped[slow]".to_string(),
+                    suite: Some("tests.test_demo".to_string()),
+                    outcome: TestOutcome::Skipped,
+                    duration: Some(Duration::ZERO),
+                    message: Some("too slow".to_string()),
+                },
+            ]
+        );
+        assert!(TestReportFormat::JunitXml.parse("not xml").is_err());
+    }
+}
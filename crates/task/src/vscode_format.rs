@@ -1,9 +1,12 @@
-use anyhow::bail;
+use anyhow::{Context as _, bail};
 use collections::HashMap;
 use serde::Deserialize;
 use util::ResultExt;
 
-use crate::{EnvVariableReplacer, TaskTemplate, TaskTemplates, VariableName};
+use crate::{
+    DependsOrder, EnvVariableReplacer, FileLocation, ProblemMatcher, ProblemMatcherTemplate,
+    ProblemPattern, ProblemSeverity, TaskTemplate, TaskTemplates, VariableName,
+};
 
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -90,19 +93,30 @@ impl VsCodeTaskDefinition {
         self,
         replacer: &EnvVariableReplacer,
     ) -> anyhow::Result<Option<TaskTemplate>> {
-        if self.other_attributes.contains_key("dependsOn") {
-            log::warn!(
-                "Skipping deserializing of a task `{}` with the unsupported `dependsOn` key",
-                self.label
-            );
-            return Ok(None);
-        }
-        // `type` might not be set in e.g. tasks that use `dependsOn`; we still want to deserialize the whole object though (hence command is an Option),
+        let depends_on = self.depends_on();
+        let problem_matchers = self.problem_matchers(replacer);
+        let depends_order = match self
+            .other_attributes
+            .get("dependsOrder")
+            .and_then(|order| order.as_str())
+        {
+            Some("sequence") => DependsOrder::Sequence,
+            _ => DependsOrder::Parallel,
+        };
+        // `type` might not be set in tasks that use `dependsOn`: those only group other tasks and run nothing themselves.
+        // For the rest, we still want to deserialize the whole object (hence command is an Option),
         // as that way we can provide more specific description of why deserialization failed.
-        // E.g. if the command is missing due to `dependsOn` presence, we can check other_attributes first before doing this (and provide nice error message)
-        // catch-all if on value.command presence.
         let Some(command) = self.command else {
-            bail!("Missing `type` field in task");
+            if depends_on.is_empty() {
+                bail!("Missing `type` field in task");
+            }
+            return Ok(Some(TaskTemplate {
+                label: self.label,
+                depends_on,
+                depends_order,
+                problem_matchers,
+                ..TaskTemplate::default()
+            }));
         };
 
         let (command, args) = match command {
@@ -117,6 +131,9 @@ impl VsCodeTaskDefinition {
             label: self.label,
             command,
             args,
+            depends_on,
+            depends_order,
+            problem_matchers,
             ..TaskTemplate::default()
         };
         if let Some(options) = self.options {
@@ -125,6 +142,175 @@ impl VsCodeTaskDefinition {
         }
         Ok(Some(template))
     }
+
+    /// `dependsOn` is either a single task label or a list of them.
+    /// Dependencies referring to tasks by their type rather than the label are not supported.
+    fn depends_on(&self) -> Vec<String> {
+        let labels = match self.other_attributes.get("dependsOn") {
+            Some(serde_json_lenient::Value::String(label)) => return vec![label.clone()],
+            Some(serde_json_lenient::Value::Array(labels)) => labels,
+            Some(_) | None => return Vec::new(),
+        };
+        labels
+            .iter()
+            .filter_map(|label| match label.as_str() {
+                Some(label) => Some(label.to_owned()),
+                None => {
+                    log::warn!(
+                        "Skipping unsupported `dependsOn` entry {label} of a task `{}`",
+                        self.label
+                    );
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// `problemMatcher` is either a single matcher or a list of them, each being a name of a predefined matcher,
+    /// or a definition that may extend one.
+    fn problem_matchers(&self, replacer: &EnvVariableReplacer) -> Vec<ProblemMatcherTemplate> {
+        let matchers = match self.other_attributes.get("problemMatcher") {
+            Some(serde_json_lenient::Value::Array(matchers)) => matchers.iter().collect(),
+            Some(matcher) => vec![matcher],
+            None => Vec::new(),
+        };
+        matchers
+            .into_iter()
+            .filter_map(|matcher| match matcher {
+                serde_json_lenient::Value::String(name) => {
+                    Some(ProblemMatcherTemplate::Preset(name.clone()))
+                }
+                matcher => {
+                    let matcher =
+                        serde_json_lenient::from_value::<VsCodeProblemMatcher>(matcher.clone())
+                            .log_err()?;
+                    matcher
+                        .into_zed_format(replacer)
+                        .with_context(|| {
+                            format!("Skipping a problem matcher of a task `{}`", self.label)
+                        })
+                        .log_err()
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VsCodeProblemMatcher {
+    base: Option<String>,
+    owner: Option<String>,
+    severity: Option<String>,
+    file_location: Option<serde_json_lenient::Value>,
+    pattern: Option<VsCodeProblemPatterns>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum VsCodeProblemPatterns {
+    Single(VsCodeProblemPattern),
+    Multiple(Vec<VsCodeProblemPattern>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VsCodeProblemPattern {
+    regexp: String,
+    file: Option<usize>,
+    line: Option<usize>,
+    column: Option<usize>,
+    end_line: Option<usize>,
+    end_column: Option<usize>,
+    severity: Option<usize>,
+    code: Option<usize>,
+    message: Option<usize>,
+    #[serde(default)]
+    r#loop: bool,
+}
+
+impl VsCodeProblemMatcher {
+    fn into_zed_format(
+        self,
+        replacer: &EnvVariableReplacer,
+    ) -> anyhow::Result<ProblemMatcherTemplate> {
+        if let Some(base) = &self.base
+            && self.owner.is_none()
+            && self.severity.is_none()
+            && self.file_location.is_none()
+            && self.pattern.is_none()
+        {
+            return Ok(ProblemMatcherTemplate::Preset(base.clone()));
+        }
+
+        let mut matcher = match &self.base {
+            Some(base) => ProblemMatcher::preset(base)
+                .with_context(|| format!("unsupported base problem matcher `{base}`"))?,
+            None => ProblemMatcher {
+                owner: None,
+                severity: ProblemSeverity::default(),
+                file_location: FileLocation::default(),
+                base_directory: None,
+                pattern: Vec::new(),
+            },
+        };
+        if let Some(owner) = self.owner {
+            matcher.owner = Some(owner);
+        }
+        if let Some(severity) = self.severity.as_deref().and_then(ProblemSeverity::parse) {
+            matcher.severity = severity;
+        }
+        match self.file_location {
+            Some(serde_json_lenient::Value::String(file_location)) => {
+                matcher.file_location = parse_file_location(&file_location)?;
+            }
+            Some(serde_json_lenient::Value::Array(file_location)) => {
+                let kind = file_location
+                    .first()
+                    .and_then(|kind| kind.as_str())
+                    .context("missing `fileLocation` kind")?;
+                matcher.file_location = parse_file_location(kind)?;
+                matcher.base_directory = file_location
+                    .get(1)
+                    .and_then(|base_directory| base_directory.as_str())
+                    .map(|base_directory| replacer.replace(base_directory));
+            }
+            Some(file_location) => bail!("unsupported `fileLocation` {file_location}"),
+            None => {}
+        }
+        if let Some(patterns) = self.pattern {
+            let patterns = match patterns {
+                VsCodeProblemPatterns::Single(pattern) => vec![pattern],
+                VsCodeProblemPatterns::Multiple(patterns) => patterns,
+            };
+            matcher.pattern = patterns
+                .into_iter()
+                .map(|pattern| ProblemPattern {
+                    regexp: pattern.regexp,
+                    file: pattern.file,
+                    line: pattern.line,
+                    column: pattern.column,
+                    end_line: pattern.end_line,
+                    end_column: pattern.end_column,
+                    severity: pattern.severity,
+                    code: pattern.code,
+                    message: pattern.message,
+                    r#loop: pattern.r#loop,
+                })
+                .collect();
+        }
+        anyhow::ensure!(!matcher.pattern.is_empty(), "missing `pattern`");
+        Ok(ProblemMatcherTemplate::Custom(matcher))
+    }
+}
+
+fn parse_file_location(file_location: &str) -> anyhow::Result<FileLocation> {
+    match file_location {
+        "absolute" => Ok(FileLocation::Absolute),
+        "relative" => Ok(FileLocation::Relative),
+        "autoDetect" => Ok(FileLocation::AutoDetect),
+        _ => bail!("unsupported `fileLocation` `{file_location}`"),
+    }
 }
 
 /// [`VsCodeTaskFile`] is a superset of Code's task definition format.
@@ -168,7 +354,8 @@ mod tests {
     use std::collections::HashMap;
 
     use crate::{
-        TaskTemplate, TaskTemplates, VsCodeTaskFile,
+        DependsOrder, FileLocation, ProblemMatcher, ProblemMatcherTemplate, ProblemPattern,
+        ProblemSeverity, TaskTemplate, TaskTemplates, VsCodeTaskFile,
         vscode_format::{Command, VsCodeTaskDefinition},
     };
 
@@ -369,33 +556,61 @@ mod tests {
             .iter()
             .zip(expected)
             .for_each(|(lhs, rhs)| compare_without_other_attributes(lhs.clone(), rhs));
+        let tsc_matcher = |base: &str| {
+            ProblemMatcherTemplate::Custom(ProblemMatcher {
+                file_location: FileLocation::Relative,
+                base_directory: Some("${ZED_WORKTREE_ROOT}/editors/code/".to_string()),
+                ..ProblemMatcher::preset(base).unwrap()
+            })
+        };
+        let rustc_matcher = || ProblemMatcherTemplate::Preset("$rustc".to_string());
         let expected = vec![
             TaskTemplate {
                 label: "Build Extension in Background".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "watch".to_string()],
+                problem_matchers: vec![tsc_matcher("$tsc-watch")],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Extension".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build".to_string()],
+                problem_matchers: vec![tsc_matcher("$tsc")],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server".to_string(),
                 command: "cargo build --package rust-analyzer".to_string(),
+                problem_matchers: vec![rustc_matcher()],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server (Release)".to_string(),
                 command: "cargo build --release --package rust-analyzer".to_string(),
+                problem_matchers: vec![rustc_matcher()],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Pretest".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "pretest".to_string()],
+                problem_matchers: vec![tsc_matcher("$tsc")],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server and Extension".to_string(),
+                depends_on: vec!["Build Server".to_string(), "Build Extension".to_string()],
+                problem_matchers: vec![rustc_matcher()],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server (Release) and Extension".to_string(),
+                depends_on: vec![
+                    "Build Server (Release)".to_string(),
+                    "Build Extension".to_string(),
+                ],
+                problem_matchers: vec![rustc_matcher()],
                 ..Default::default()
             },
         ];
@@ -416,6 +631,111 @@ mod tests {
         assert_eq!(vscode_definitions.tasks[3].label, "echo hello");
     }
 
+    #[test]
+    fn can_deserialize_task_dependencies() {
+        let vscode_definitions: VsCodeTaskFile = serde_json_lenient::from_str(
+            r#"{
+                "tasks": [
+                    {
+                        "label": "start server",
+                        "type": "shell",
+                        "command": "./server",
+                        "dependsOn": ["build", "migrate db"],
+                        "dependsOrder": "sequence",
+                    },
+                    {
+                        "label": "check",
+                        "dependsOn": "lint",
+                    },
+                    {
+                        "label": "lint and test",
+                        "dependsOn": ["lint", { "type": "npm", "script": "test" }],
+                    },
+                ]
+            }"#,
+        )
+        .unwrap();
+        let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
+        assert_eq!(
+            tasks.0,
+            vec![
+                TaskTemplate {
+                    label: "start server".to_string(),
+                    command: "./server".to_string(),
+                    depends_on: vec!["build".to_string(), "migrate db".to_string()],
+                    depends_order: DependsOrder::Sequence,
+                    ..Default::default()
+                },
+                TaskTemplate {
+                    label: "check".to_string(),
+                    depends_on: vec!["lint".to_string()],
+                    ..Default::default()
+                },
+                TaskTemplate {
+                    label: "lint and test".to_string(),
+                    depends_on: vec!["lint".to_string()],
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn can_deserialize_custom_problem_matchers() {
+        let vscode_definitions: VsCodeTaskFile = serde_json_lenient::from_str(
+            r#"{
+                "tasks": [
+                    {
+                        "label": "make",
+                        "type": "shell",
+                        "command": "make",
+                        "problemMatcher": [
+                            "$gcc",
+                            {
+                                "owner": "make",
+                                "fileLocation": ["relative", "${workspaceFolder}/build"],
+                                "pattern": [
+                                    { "regexp": "^In (.*):$", "file": 1 },
+                                    { "regexp": "^(\\d+): (.*)$", "line": 1, "message": 2, "loop": true },
+                                ],
+                            },
+                            { "pattern": { "regexp": "^(.*)$" }, "fileLocation": "unknown" },
+                        ],
+                    },
+                ]
+            }"#,
+        )
+        .unwrap();
+        let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
+        assert_eq!(
+            tasks.0[0].problem_matchers,
+            vec![
+                ProblemMatcherTemplate::Preset("$gcc".to_string()),
+                ProblemMatcherTemplate::Custom(ProblemMatcher {
+                    owner: Some("make".to_string()),
+                    severity: ProblemSeverity::Error,
+                    file_location: FileLocation::Relative,
+                    base_directory: Some("${ZED_WORKTREE_ROOT}/build".to_string()),
+                    pattern: vec![
+                        ProblemPattern {
+                            regexp: "^In (.*):$".to_string(),
+                            file: Some(1),
+                            ..Default::default()
+                        },
+                        ProblemPattern {
+                            regexp: "^(\\d+): (.*)$".to_string(),
+                            line: Some(1),
+                            message: Some(2),
+                            r#loop: true,
+                            ..Default::default()
+                        },
+                    ],
+                }),
+            ],
+            "matchers with unsupported settings should be skipped"
+        );
+    }
+
     #[test]
     fn test_generate_label() {
         assert_eq!(
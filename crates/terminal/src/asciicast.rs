@@ -0,0 +1,278 @@
+//! Recording terminal output in the asciicast format, described in
+//! https://docs.asciinema.org/manual/asciicast/v2.
+//!
+//! Recordings are written in version 2, and both versions 2 and 3 are read.
+
+use std::{
+    io,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use alacritty_terminal::{event::WindowSize, tty::EventedReadWrite};
+use anyhow::{Context as _, Result, bail};
+use collections::HashMap;
+use parking_lot::Mutex;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::pty_filter::PtyFilter;
+
+#[derive(Serialize)]
+struct Header<'a> {
+    version: u32,
+    width: usize,
+    height: usize,
+    timestamp: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<&'a str>,
+    env: HashMap<&'a str, String>,
+}
+
+/// Records the output of a terminal, along with its resizes.
+pub(crate) struct AsciicastRecorder {
+    started_at: Instant,
+    contents: String,
+    /// The end of the last output, when it stopped in the middle of a UTF-8 character.
+    incomplete_char: Vec<u8>,
+}
+
+impl AsciicastRecorder {
+    pub fn new(columns: usize, lines: usize, title: Option<&str>) -> Self {
+        let env = ["SHELL", "TERM"]
+            .into_iter()
+            .filter_map(|name| Some((name, std::env::var(name).ok()?)))
+            .collect();
+        let header = Header {
+            version: 2,
+            width: columns,
+            height: lines,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs()),
+            title,
+            env,
+        };
+        let mut contents = serde_json::to_string(&header).unwrap_or_default();
+        contents.push('\n');
+        Self {
+            started_at: Instant::now(),
+            contents,
+            incomplete_char: Vec::new(),
+        }
+    }
+
+    pub fn output(&mut self, bytes: &[u8]) {
+        let mut bytes = bytes;
+        let joined;
+        if !self.incomplete_char.is_empty() {
+            self.incomplete_char.extend_from_slice(bytes);
+            joined = std::mem::take(&mut self.incomplete_char);
+            bytes = &joined;
+        }
+
+        // Output is split in events at arbitrary bytes, but each event holds a string.
+        let complete_len = match std::str::from_utf8(bytes) {
+            Ok(_) => bytes.len(),
+            Err(error) if error.error_len().is_none() => error.valid_up_to(),
+            Err(_) => bytes.len(),
+        };
+        let (complete, incomplete) = bytes.split_at(complete_len);
+        self.incomplete_char = incomplete.to_vec();
+        if !complete.is_empty() {
+            self.push_event("o", &String::from_utf8_lossy(complete));
+        }
+    }
+
+    pub fn resize(&mut self, columns: u16, lines: u16) {
+        self.push_event("r", &format!("{columns}x{lines}"));
+    }
+
+    fn push_event(&mut self, code: &str, data: &str) {
+        let time = self.started_at.elapsed().as_secs_f64();
+        let event = (time, code, data);
+        if let Ok(event) = serde_json::to_string(&event) {
+            self.contents.push_str(&event);
+            self.contents.push('\n');
+        }
+    }
+
+    /// Ends the recording, returning the contents of the `.cast` file.
+    pub fn finish(mut self) -> String {
+        if !self.incomplete_char.is_empty() {
+            let incomplete_char = std::mem::take(&mut self.incomplete_char);
+            self.push_event("o", &String::from_utf8_lossy(&incomplete_char));
+        }
+        self.contents
+    }
+}
+
+/// A recorded terminal session, read from a `.cast` file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Asciicast {
+    pub columns: usize,
+    pub lines: usize,
+    pub title: Option<String>,
+    /// The output of the session, with the time it was printed at since the start of the
+    /// recording.
+    pub events: Vec<(Duration, String)>,
+}
+
+impl Asciicast {
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+        let header: Value = serde_json::from_str(lines.next().context("the recording is empty")?)
+            .context("parsing the recording's header")?;
+        let version = header["version"].as_u64();
+        let (columns, rows) = match version {
+            Some(2) => (&header["width"], &header["height"]),
+            Some(3) => (&header["term"]["cols"], &header["term"]["rows"]),
+            _ => bail!("unsupported asciicast version {}", header["version"]),
+        };
+        let columns = columns.as_u64().context("missing terminal width")? as usize;
+        let rows = rows.as_u64().context("missing terminal height")? as usize;
+        let idle_time_limit = header["idle_time_limit"].as_f64();
+
+        let mut events = Vec::new();
+        let mut recorded_time = 0.;
+        let mut time = 0.;
+        for line in lines {
+            // Version 3 allows comments between events.
+            if line.starts_with('#') {
+                continue;
+            }
+            let (event_time, code, data): (f64, String, Value) =
+                serde_json::from_str(line).with_context(|| format!("parsing event {line:?}"))?;
+            // Version 2 times events since the start of the recording, and version 3 since the
+            // previous event.
+            let interval = if version == Some(2) {
+                let interval = event_time - recorded_time;
+                recorded_time = event_time;
+                interval
+            } else {
+                event_time
+            };
+            let interval = match idle_time_limit {
+                Some(limit) => interval.min(limit),
+                None => interval,
+            };
+            time += interval.max(0.);
+            if code == "o"
+                && let Value::String(output) = data
+            {
+                events.push((Duration::from_secs_f64(time), output));
+            }
+        }
+
+        Ok(Self {
+            columns,
+            lines: rows,
+            title: header["title"].as_str().map(ToString::to_string),
+            events,
+        })
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.events.last().map_or(Duration::ZERO, |(time, _)| *time)
+    }
+}
+
+/// Records the output of a terminal's PTY while a recording is in progress.
+pub(crate) struct RecordingFilter {
+    recorder: Arc<Mutex<Option<AsciicastRecorder>>>,
+}
+
+impl RecordingFilter {
+    pub fn new(recorder: Arc<Mutex<Option<AsciicastRecorder>>>) -> Self {
+        Self { recorder }
+    }
+}
+
+impl PtyFilter for RecordingFilter {
+    fn read(&mut self, pty: &mut impl EventedReadWrite, buf: &mut [u8]) -> io::Result<usize> {
+        let len = pty.reader().read(buf)?;
+        if let Some(recorder) = self.recorder.lock().as_mut() {
+            recorder.output(&buf[..len]);
+        }
+        Ok(len)
+    }
+
+    fn resized(&mut self, window_size: WindowSize) {
+        if let Some(recorder) = self.recorder.lock().as_mut() {
+            recorder.resize(window_size.num_cols, window_size.num_lines);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_record_and_parse() {
+        let mut recorder = AsciicastRecorder::new(80, 24, Some("zsh"));
+        recorder.output(b"$ echo \xc3");
+        recorder.output(b"\xa9t\xc3\xa9\r\n");
+        recorder.resize(100, 30);
+        recorder.output(b"\x1b[1mbold\x1b[0m\xff");
+        let contents = recorder.finish();
+
+        let header: Value = serde_json::from_str(contents.lines().next().unwrap()).unwrap();
+        assert_eq!(header["version"], 2);
+        assert_eq!(header["width"], 80);
+        assert_eq!(header["height"], 24);
+        assert_eq!(header["title"], "zsh");
+        assert!(contents.lines().nth(3).unwrap().contains(r#""r","100x30""#));
+
+        let cast = Asciicast::parse(&contents).unwrap();
+        assert_eq!((cast.columns, cast.lines), (80, 24));
+        assert_eq!(cast.title.as_deref(), Some("zsh"));
+        assert_eq!(
+            cast.events
+                .iter()
+                .map(|(_, output)| output.as_str())
+                .collect::<Vec<_>>(),
+            ["$ echo ", "été\r\n", "\x1b[1mbold\x1b[0m\u{FFFD}"]
+        );
+    }
+
+    #[test]
+    fn test_parse_versions() {
+        let v2 = r#"{"version": 2, "width": 100, "height": 40, "idle_time_limit": 1.5}
+[0.5, "o", "a"]
+[1.0, "i", "b"]
+[5.0, "o", "c"]
+"#;
+        let cast = Asciicast::parse(v2).unwrap();
+        assert_eq!((cast.columns, cast.lines, cast.title), (100, 40, None));
+        assert_eq!(
+            cast.events,
+            vec![
+                (Duration::from_millis(500), "a".to_string()),
+                (Duration::from_millis(2500), "c".to_string()),
+            ]
+        );
+        assert_eq!(cast.duration(), Duration::from_millis(2500));
+
+        let v3 = r#"{"version": 3, "term": {"cols": 90, "rows": 30}, "title": "demo"}
+# a comment
+[0.25, "o", "a"]
+[0.5, "m", ""]
+[0.25, "o", "b"]
+"#;
+        let cast = Asciicast::parse(v3).unwrap();
+        assert_eq!((cast.columns, cast.lines), (90, 30));
+        assert_eq!(cast.title.as_deref(), Some("demo"));
+        assert_eq!(
+            cast.events,
+            vec![
+                (Duration::from_millis(250), "a".to_string()),
+                (Duration::from_millis(1000), "b".to_string()),
+            ]
+        );
+
+        assert!(Asciicast::parse("").is_err());
+        assert!(Asciicast::parse(r#"{"version": 1}"#).is_err());
+    }
+}
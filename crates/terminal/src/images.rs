@@ -0,0 +1,756 @@
+//! Inline images, printed with the sixel, kitty graphics and iTerm2 image protocols.
+//!
+//! Alacritty does not understand any of these, so the PTY's output is scanned for image
+//! sequences before it reaches the terminal. Each decoded image is replaced with an anchor: a
+//! single cell carrying a hyperlink to the image, followed by the cursor movement the image would
+//! have caused. The anchor lives in the grid like any other cell, so images scroll with the text
+//! around them, stay in the scrollback, and are erased along with it.
+
+mod iterm;
+mod kitty;
+mod sixel;
+
+use std::{io, sync::Arc};
+
+use alacritty_terminal::{
+    Term,
+    event::WindowSize,
+    grid::Dimensions as _,
+    index::{Column, Line, Point as AlacPoint},
+    term::cell::Hyperlink,
+    tty::EventedReadWrite,
+};
+use base64::{
+    Engine as _, alphabet,
+    engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+};
+use collections::{HashMap, VecDeque};
+use gpui::RenderImage;
+use image::{Frame, RgbaImage};
+use parking_lot::Mutex;
+
+use crate::{IndexedCell, ZedListener, pty_filter::PtyFilter};
+
+/// The scheme of the hyperlinks anchoring images to the grid.
+const ANCHOR_SCHEME: &str = "zed-terminal-image:";
+
+/// The oldest images are dropped past these limits. Their anchors stay in the grid, but no
+/// longer draw anything.
+const MAX_PLACEMENTS: usize = 1024;
+const MAX_IMAGE_BYTES: usize = 320 * 1024 * 1024;
+
+/// Image sequences longer than this are dropped without being buffered further.
+const MAX_SEQUENCE_LEN: usize = 64 * 1024 * 1024;
+
+/// Decoded images larger than this in either dimension are rejected.
+pub(crate) const MAX_IMAGE_DIMENSION: u32 = 10_000;
+
+/// The primary device attributes Alacritty reports, and the ones reported in their place to tell
+/// programs that the terminal displays sixel images.
+pub(crate) const PRIMARY_DEVICE_ATTRIBUTES: &str = "\x1b[?6c";
+pub(crate) const SIXEL_DEVICE_ATTRIBUTES: &str = "\x1b[?62;4c";
+
+type PlacementId = u64;
+
+/// An image displayed in the terminal.
+#[derive(Clone)]
+pub struct ImagePlacement {
+    /// The cell at the top left corner of the image.
+    pub point: AlacPoint,
+    /// The number of columns and lines the image covers. It is drawn as large as fits in them,
+    /// keeping its aspect ratio.
+    pub columns: usize,
+    pub lines: usize,
+    pub image: Arc<RenderImage>,
+}
+
+#[derive(Clone)]
+struct StoredPlacement {
+    image: Arc<RenderImage>,
+    columns: usize,
+    lines: usize,
+}
+
+impl StoredPlacement {
+    fn byte_len(&self) -> usize {
+        let size = self.image.size(0);
+        size.width.0.max(0) as usize * size.height.0.max(0) as usize * 4
+    }
+}
+
+/// The images anchored in a terminal's grid, shared between the PTY that decodes them and the
+/// terminal that displays them.
+#[derive(Default)]
+pub(crate) struct ImageStore {
+    placements: HashMap<PlacementId, StoredPlacement>,
+    order: VecDeque<PlacementId>,
+    byte_len: usize,
+    max_lines: usize,
+}
+
+impl ImageStore {
+    fn insert(&mut self, id: PlacementId, placement: StoredPlacement) {
+        self.byte_len += placement.byte_len();
+        self.max_lines = self.max_lines.max(placement.lines);
+        self.placements.insert(id, placement);
+        self.order.push_back(id);
+        while self.order.len() > MAX_PLACEMENTS || self.byte_len > MAX_IMAGE_BYTES {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            self.remove(oldest);
+        }
+    }
+
+    fn remove(&mut self, id: PlacementId) {
+        if let Some(placement) = self.placements.remove(&id) {
+            self.byte_len -= placement.byte_len();
+        }
+    }
+
+    /// Finds the images covering the visible cells, hiding their anchors' hyperlinks.
+    pub fn placements(
+        &self,
+        term: &Term<ZedListener>,
+        cells: &mut [IndexedCell],
+    ) -> Vec<ImagePlacement> {
+        let mut placements = Vec::new();
+        for cell in cells.iter_mut() {
+            if let Some(id) = cell.hyperlink().as_ref().and_then(anchor_id) {
+                cell.cell.set_hyperlink(None);
+                placements.extend(self.placement(id, cell.point));
+            }
+        }
+
+        // Images anchored above the viewport may still reach into it.
+        let Some(top) = cells.first().map(|cell| cell.point.line) else {
+            return placements;
+        };
+        let start = (top.0 - self.max_lines as i32 + 1).max(term.topmost_line().0);
+        for line in start..top.0 {
+            let row = &term.grid()[Line(line)];
+            for column in 0..term.columns() {
+                let Some(id) = row[Column(column)].hyperlink().as_ref().and_then(anchor_id) else {
+                    continue;
+                };
+                if let Some(placement) =
+                    self.placement(id, AlacPoint::new(Line(line), Column(column)))
+                    && line + placement.lines as i32 > top.0
+                {
+                    placements.push(placement);
+                }
+            }
+        }
+        placements
+    }
+
+    fn placement(&self, id: PlacementId, point: AlacPoint) -> Option<ImagePlacement> {
+        let placement = self.placements.get(&id)?;
+        Some(ImagePlacement {
+            point,
+            columns: placement.columns,
+            lines: placement.lines,
+            image: placement.image.clone(),
+        })
+    }
+}
+
+/// Whether the hyperlink is the anchor of an image, rather than a link printed by a program.
+pub(crate) fn is_anchor(hyperlink: &Hyperlink) -> bool {
+    hyperlink.uri().starts_with(ANCHOR_SCHEME)
+}
+
+fn anchor_id(hyperlink: &Hyperlink) -> Option<PlacementId> {
+    hyperlink.uri().strip_prefix(ANCHOR_SCHEME)?.parse().ok()
+}
+
+/// How the cursor moves after an image is printed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CursorMovement {
+    /// To the first column of the line below the image, as with sixel images.
+    BelowImage,
+    /// To the column after the image, on its last line, as with kitty and iTerm2 images.
+    AfterImage,
+    /// Nowhere.
+    None,
+}
+
+/// The output replacing an image: its anchor, then the cursor movement.
+fn anchor_sequence(
+    id: PlacementId,
+    columns: usize,
+    lines: usize,
+    movement: CursorMovement,
+) -> Vec<u8> {
+    let mut sequence = format!("\x1b]8;id=zed-image-{id};{ANCHOR_SCHEME}{id}\x1b\\ \x1b]8;;\x1b\\");
+    match movement {
+        CursorMovement::BelowImage => {
+            sequence.push_str("\x1b[D");
+            sequence.push_str(&"\n".repeat(lines));
+            sequence.push('\r');
+        }
+        CursorMovement::AfterImage => {
+            sequence.push_str(&"\n".repeat(lines - 1));
+            if columns > 1 {
+                sequence.push_str(&format!("\x1b[{}C", columns - 1));
+            }
+        }
+        CursorMovement::None => sequence.push_str("\x1b[D"),
+    }
+    sequence.into_bytes()
+}
+
+/// The number of columns and lines an image covers, given its size in pixels and the columns or
+/// lines it was asked to cover. Images wider than the terminal are scaled down to fit in it.
+fn image_span(
+    width: u32,
+    height: u32,
+    columns: Option<usize>,
+    lines: Option<usize>,
+    window_size: WindowSize,
+) -> (usize, usize) {
+    let natural_columns = width as f32 / window_size.cell_width.max(1) as f32;
+    let natural_lines = height as f32 / window_size.cell_height.max(1) as f32;
+    let (columns, lines) = match (columns, lines) {
+        (Some(columns), Some(lines)) => (columns as f32, lines as f32),
+        (Some(columns), None) => (
+            columns as f32,
+            columns as f32 * natural_lines / natural_columns,
+        ),
+        (None, Some(lines)) => (lines as f32 * natural_columns / natural_lines, lines as f32),
+        (None, None) => (natural_columns, natural_lines),
+    };
+    let max_columns = window_size.num_cols.max(1) as f32;
+    let (columns, lines) = if columns > max_columns {
+        (max_columns, lines * max_columns / columns)
+    } else {
+        (columns, lines)
+    };
+    (
+        (columns.ceil() as usize).max(1),
+        (lines.ceil() as usize).max(1),
+    )
+}
+
+/// Decodes base64 data, whether or not it is padded.
+fn decode_base64(data: &[u8]) -> Result<Vec<u8>, base64::DecodeError> {
+    const ENGINE: GeneralPurpose = GeneralPurpose::new(
+        &alphabet::STANDARD,
+        GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+    );
+    ENGINE.decode(data)
+}
+
+fn render_image(mut image: RgbaImage) -> Arc<RenderImage> {
+    // GPUI expects BGRA pixels.
+    for pixel in image.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+    Arc::new(RenderImage::new(vec![Frame::new(image)]))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum StringKind {
+    Dcs,
+    Apc,
+    Osc,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ImageKind {
+    Sixel,
+    Kitty,
+    Iterm,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum ScanState {
+    #[default]
+    Ground,
+    Escape,
+    /// At the start of a control string, which may or may not hold an image.
+    Introducer(StringKind),
+    /// In a control string holding an image, which is buffered until its end.
+    Image(ImageKind),
+    ImageEscape(ImageKind),
+    /// In a control string that is not an image, which is passed through.
+    Other(StringKind),
+    OtherEscape(StringKind),
+}
+
+/// The start of the OSC sequences holding iTerm2 images.
+const ITERM_PREFIX: &[u8] = b"1337;File=";
+
+/// Separates the image sequences from the rest of the PTY's output.
+#[derive(Default)]
+pub(crate) struct ImageScanner {
+    state: ScanState,
+    sequence: Vec<u8>,
+    too_long: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ImageSequence {
+    pub kind: ImageKind,
+    /// The content of the control string, without its introducer and terminator.
+    pub payload: Vec<u8>,
+}
+
+impl ImageScanner {
+    /// Appends the bytes that are not part of an image sequence to the output, up to the end of
+    /// the next image sequence. Returns the number of bytes consumed, along with the image
+    /// sequence if one ended.
+    pub fn next_image(
+        &mut self,
+        bytes: &[u8],
+        output: &mut Vec<u8>,
+    ) -> (usize, Option<ImageSequence>) {
+        let mut ix = 0;
+        while ix < bytes.len() {
+            if self.state == ScanState::Ground {
+                let end = bytes[ix..]
+                    .iter()
+                    .position(|byte| *byte == 0x1b)
+                    .map_or(bytes.len(), |position| ix + position);
+                output.extend_from_slice(&bytes[ix..end]);
+                ix = end;
+                if ix == bytes.len() {
+                    break;
+                }
+            }
+
+            let byte = bytes[ix];
+            ix += 1;
+            if let Some(sequence) = self.advance(byte, output) {
+                return (ix, Some(sequence));
+            }
+        }
+        (ix, None)
+    }
+
+    fn advance(&mut self, byte: u8, output: &mut Vec<u8>) -> Option<ImageSequence> {
+        match self.state {
+            ScanState::Ground => {
+                if byte == 0x1b {
+                    self.state = ScanState::Escape;
+                } else {
+                    output.push(byte);
+                }
+            }
+            ScanState::Escape => {
+                let kind = match byte {
+                    b'P' => Some(StringKind::Dcs),
+                    b'_' => Some(StringKind::Apc),
+                    b']' => Some(StringKind::Osc),
+                    _ => None,
+                };
+                if let Some(kind) = kind {
+                    self.state = ScanState::Introducer(kind);
+                    self.sequence.clear();
+                } else {
+                    output.push(0x1b);
+                    if byte != 0x1b {
+                        output.push(byte);
+                        self.state = ScanState::Ground;
+                    }
+                }
+            }
+            ScanState::Introducer(kind) => {
+                let image = match kind {
+                    // Sixel images start with numeric parameters, followed by `q`.
+                    StringKind::Dcs => match byte {
+                        b'0'..=b'9' | b';' if self.sequence.len() < 32 => {
+                            self.sequence.push(byte);
+                            return None;
+                        }
+                        b'q' => Some(ImageKind::Sixel),
+                        _ => None,
+                    },
+                    StringKind::Apc => (byte == b'G').then_some(ImageKind::Kitty),
+                    StringKind::Osc => {
+                        if ITERM_PREFIX.get(self.sequence.len()) == Some(&byte) {
+                            self.sequence.push(byte);
+                            if self.sequence.len() < ITERM_PREFIX.len() {
+                                return None;
+                            }
+                            self.sequence.clear();
+                            Some(ImageKind::Iterm)
+                        } else {
+                            None
+                        }
+                    }
+                };
+                match image {
+                    Some(image) => {
+                        self.state = ScanState::Image(image);
+                        self.too_long = false;
+                        if image == ImageKind::Sixel {
+                            self.sequence.push(byte);
+                        }
+                    }
+                    None => {
+                        output.push(0x1b);
+                        output.push(match kind {
+                            StringKind::Dcs => b'P',
+                            StringKind::Apc => b'_',
+                            StringKind::Osc => b']',
+                        });
+                        output.append(&mut self.sequence);
+                        self.state = ScanState::Other(kind);
+                        return self.advance(byte, output);
+                    }
+                }
+            }
+            ScanState::Image(kind) => match byte {
+                0x1b => self.state = ScanState::ImageEscape(kind),
+                0x07 if kind == ImageKind::Iterm => return self.finish_image(kind),
+                _ if self.sequence.len() < MAX_SEQUENCE_LEN => self.sequence.push(byte),
+                _ => self.too_long = true,
+            },
+            ScanState::ImageEscape(kind) => {
+                if byte == b'\\' {
+                    return self.finish_image(kind);
+                }
+                // An escape aborts the control string, and starts another sequence.
+                self.sequence.clear();
+                self.state = ScanState::Escape;
+                return self.advance(byte, output);
+            }
+            ScanState::Other(kind) => match byte {
+                0x1b => self.state = ScanState::OtherEscape(kind),
+                0x07 if kind == StringKind::Osc => {
+                    output.push(byte);
+                    self.state = ScanState::Ground;
+                }
+                _ => output.push(byte),
+            },
+            ScanState::OtherEscape(_) => {
+                if byte == b'\\' {
+                    output.extend_from_slice(b"\x1b\\");
+                    self.state = ScanState::Ground;
+                } else {
+                    self.state = ScanState::Escape;
+                    return self.advance(byte, output);
+                }
+            }
+        }
+        None
+    }
+
+    fn finish_image(&mut self, kind: ImageKind) -> Option<ImageSequence> {
+        self.state = ScanState::Ground;
+        let payload = std::mem::take(&mut self.sequence);
+        if self.too_long {
+            log::debug!("dropping a terminal image sequence longer than {MAX_SEQUENCE_LEN} bytes");
+            return None;
+        }
+        Some(ImageSequence { kind, payload })
+    }
+}
+
+/// Replaces the images in the output of a terminal's PTY with their anchors.
+pub(crate) struct ImageFilter {
+    store: Arc<Mutex<ImageStore>>,
+    scanner: ImageScanner,
+    kitty: kitty::KittyGraphics,
+    /// The placements of each kitty image, to delete them along with it.
+    kitty_placements: HashMap<u32, Vec<PlacementId>>,
+    next_placement_id: PlacementId,
+    window_size: WindowSize,
+    input: Vec<u8>,
+    output: VecDeque<u8>,
+}
+
+impl ImageFilter {
+    pub fn new(store: Arc<Mutex<ImageStore>>, window_size: WindowSize) -> Self {
+        Self {
+            store,
+            scanner: ImageScanner::default(),
+            kitty: kitty::KittyGraphics::default(),
+            kitty_placements: HashMap::default(),
+            next_placement_id: 0,
+            window_size,
+            input: Vec::new(),
+            output: VecDeque::new(),
+        }
+    }
+}
+
+impl ImageFilter {
+    /// Scans the input read from the PTY, writing the responses to image queries to it.
+    fn scan(&mut self, len: usize, pty_writer: &mut impl io::Write) {
+        let input = std::mem::take(&mut self.input);
+        let mut output = Vec::with_capacity(len);
+        let mut scanned = 0;
+        while scanned < len {
+            let (sequence_len, sequence) =
+                self.scanner.next_image(&input[scanned..len], &mut output);
+            scanned += sequence_len;
+            if let Some(sequence) = sequence {
+                self.handle_image(sequence, &mut output, pty_writer);
+            }
+        }
+        self.output.extend(output);
+        self.input = input;
+    }
+
+    fn handle_image(
+        &mut self,
+        sequence: ImageSequence,
+        output: &mut Vec<u8>,
+        pty_writer: &mut impl io::Write,
+    ) {
+        match sequence.kind {
+            ImageKind::Sixel => match sixel::decode(&sequence.payload) {
+                Some(image) => {
+                    let (columns, lines) =
+                        image_span(image.width(), image.height(), None, None, self.window_size);
+                    self.place(image, columns, lines, CursorMovement::BelowImage, output);
+                }
+                None => log::debug!("failed to decode a sixel image"),
+            },
+            ImageKind::Iterm => match iterm::parse(&sequence.payload) {
+                Ok(Some(inline_image)) => {
+                    let (columns, lines) = image_span(
+                        inline_image.image.width(),
+                        inline_image.image.height(),
+                        inline_image
+                            .width
+                            .to_cells(self.window_size.cell_width, self.window_size.num_cols),
+                        inline_image
+                            .height
+                            .to_cells(self.window_size.cell_height, self.window_size.num_lines),
+                        self.window_size,
+                    );
+                    self.place(
+                        inline_image.image,
+                        columns,
+                        lines,
+                        CursorMovement::AfterImage,
+                        output,
+                    );
+                }
+                Ok(None) => {}
+                Err(error) => log::debug!("failed to decode an iTerm2 image: {error:#}"),
+            },
+            ImageKind::Kitty => {
+                let (action, response) = self.kitty.handle(&sequence.payload);
+                match action {
+                    kitty::Action::None => {}
+                    kitty::Action::Place(placement) => {
+                        let (columns, lines) = image_span(
+                            placement.width,
+                            placement.height,
+                            placement.columns,
+                            placement.rows,
+                            self.window_size,
+                        );
+                        let movement = if placement.move_cursor {
+                            CursorMovement::AfterImage
+                        } else {
+                            CursorMovement::None
+                        };
+                        let id = self.place_render_image(
+                            placement.image,
+                            columns,
+                            lines,
+                            movement,
+                            output,
+                        );
+                        self.kitty_placements
+                            .entry(placement.image_id)
+                            .or_default()
+                            .push(id);
+                    }
+                    kitty::Action::DeleteAll => {
+                        let mut store = self.store.lock();
+                        for (_, ids) in self.kitty_placements.drain() {
+                            for id in ids {
+                                store.remove(id);
+                            }
+                        }
+                    }
+                    kitty::Action::Delete(image_id) => {
+                        let mut store = self.store.lock();
+                        for id in self.kitty_placements.remove(&image_id).unwrap_or_default() {
+                            store.remove(id);
+                        }
+                    }
+                }
+                if let Some(response) = response {
+                    pty_writer.write_all(&response).ok();
+                }
+            }
+        }
+    }
+
+    fn place(
+        &mut self,
+        image: RgbaImage,
+        columns: usize,
+        lines: usize,
+        movement: CursorMovement,
+        output: &mut Vec<u8>,
+    ) {
+        self.place_render_image(render_image(image), columns, lines, movement, output);
+    }
+
+    /// Stores the image and writes its anchor to the output. The image is stored first, so that
+    /// it is there by the time the terminal finds its anchor.
+    fn place_render_image(
+        &mut self,
+        image: Arc<RenderImage>,
+        columns: usize,
+        lines: usize,
+        movement: CursorMovement,
+        output: &mut Vec<u8>,
+    ) -> PlacementId {
+        let id = self.next_placement_id;
+        self.next_placement_id += 1;
+        self.store.lock().insert(
+            id,
+            StoredPlacement {
+                image,
+                columns,
+                lines,
+            },
+        );
+        output.extend(anchor_sequence(id, columns, lines, movement));
+        id
+    }
+}
+
+impl PtyFilter for ImageFilter {
+    fn read(&mut self, pty: &mut impl EventedReadWrite, buf: &mut [u8]) -> io::Result<usize> {
+        while self.output.is_empty() {
+            // Anchors are longer than the images they replace only for tiny images, so reading
+            // half as much as asked for leaves room for them in most reads.
+            let len = (buf.len() / 2).max(1);
+            self.input.resize(len, 0);
+            let len = pty.reader().read(&mut self.input)?;
+            if len == 0 {
+                return Ok(0);
+            }
+            self.scan(len, pty.writer());
+        }
+        let len = self.output.len().min(buf.len());
+        for (target, byte) in buf.iter_mut().zip(self.output.drain(..len)) {
+            *target = byte;
+        }
+        Ok(len)
+    }
+
+    fn resized(&mut self, window_size: WindowSize) {
+        self.window_size = window_size;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn scan(scanner: &mut ImageScanner, bytes: &[u8]) -> (Vec<u8>, Vec<ImageSequence>) {
+        let mut output = Vec::new();
+        let mut sequences = Vec::new();
+        let mut scanned = 0;
+        while scanned < bytes.len() {
+            let (len, sequence) = scanner.next_image(&bytes[scanned..], &mut output);
+            scanned += len;
+            sequences.extend(sequence);
+        }
+        (output, sequences)
+    }
+
+    #[test]
+    fn test_scanner_passes_other_sequences_through() {
+        let bytes = b"a\x1b[1mb\x1b]0;title\x07c\x1bPtmux;x\x1b\\d\x1b_other\x1b\\\x1b]8;;url\x1b\\e\x1b\x1b[A";
+        let (output, sequences) = scan(&mut ImageScanner::default(), bytes);
+        assert_eq!(output, bytes.to_vec());
+        assert_eq!(sequences, Vec::new());
+
+        // Byte by byte, as when each byte arrives in its own read.
+        let mut scanner = ImageScanner::default();
+        let mut output = Vec::new();
+        for byte in bytes {
+            let (byte_output, sequences) = scan(&mut scanner, &[*byte]);
+            assert_eq!(sequences, Vec::new());
+            output.extend(byte_output);
+        }
+        assert_eq!(output, bytes.to_vec());
+    }
+
+    #[test]
+    fn test_scanner_extracts_images() {
+        let mut scanner = ImageScanner::default();
+        let (output, sequences) = scan(
+            &mut scanner,
+            b"a\x1bP0;1q#0~~\x1b\\b\x1b_Ga=T,f=100;AAAA\x1b\\c\x1b]1337;File=inline=1:AAAA\x07d",
+        );
+        assert_eq!(output, b"abcd".to_vec());
+        assert_eq!(
+            sequences,
+            vec![
+                ImageSequence {
+                    kind: ImageKind::Sixel,
+                    payload: b"0;1q#0~~".to_vec(),
+                },
+                ImageSequence {
+                    kind: ImageKind::Kitty,
+                    payload: b"a=T,f=100;AAAA".to_vec(),
+                },
+                ImageSequence {
+                    kind: ImageKind::Iterm,
+                    payload: b"inline=1:AAAA".to_vec(),
+                },
+            ]
+        );
+
+        let (output, sequences) = scan(&mut scanner, b"\x1bPq~-");
+        assert_eq!((output, sequences), (Vec::new(), Vec::new()));
+        let (output, sequences) = scan(&mut scanner, b"~\x1b");
+        assert_eq!((output, sequences), (Vec::new(), Vec::new()));
+        let (output, sequences) = scan(&mut scanner, b"\\e");
+        assert_eq!(output, b"e".to_vec());
+        assert_eq!(
+            sequences,
+            vec![ImageSequence {
+                kind: ImageKind::Sixel,
+                payload: b"q~-~".to_vec(),
+            }]
+        );
+
+        // An escape that is not a string terminator aborts the image.
+        let (output, sequences) = scan(&mut scanner, b"\x1bPq~~\x1b[1mf");
+        assert_eq!(output, b"\x1b[1mf".to_vec());
+        assert_eq!(sequences, Vec::new());
+    }
+
+    #[test]
+    fn test_image_span() {
+        let window_size = WindowSize {
+            num_lines: 24,
+            num_cols: 80,
+            cell_width: 10,
+            cell_height: 20,
+        };
+        assert_eq!(image_span(100, 100, None, None, window_size), (10, 5));
+        assert_eq!(image_span(101, 1, None, None, window_size), (11, 1));
+        assert_eq!(image_span(100, 100, Some(20), None, window_size), (20, 10));
+        assert_eq!(image_span(100, 100, None, Some(2), window_size), (4, 2));
+        assert_eq!(image_span(100, 100, Some(3), Some(7), window_size), (3, 7));
+        assert_eq!(image_span(1600, 400, None, None, window_size), (80, 10));
+    }
+
+    #[test]
+    fn test_anchor_sequence() {
+        assert_eq!(
+            String::from_utf8(anchor_sequence(3, 4, 2, CursorMovement::AfterImage)).unwrap(),
+            "\x1b]8;id=zed-image-3;zed-terminal-image:3\x1b\\ \x1b]8;;\x1b\\\n\x1b[3C"
+        );
+        assert_eq!(
+            String::from_utf8(anchor_sequence(3, 4, 2, CursorMovement::BelowImage)).unwrap(),
+            "\x1b]8;id=zed-image-3;zed-terminal-image:3\x1b\\ \x1b]8;;\x1b\\\x1b[D\n\n\r"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! The iTerm2 inline images protocol, described in https://iterm2.com/documentation-images.html.
+//!
+//! Images always keep their aspect ratio, and files transferred in multiple parts are not
+//! supported.
+
+use anyhow::{Context as _, Result, bail};
+use image::RgbaImage;
+
+use super::{MAX_IMAGE_DIMENSION, decode_base64};
+
+/// The width or height an image is asked to be displayed at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Dimension {
+    Auto,
+    Cells(usize),
+    Pixels(u32),
+    Percent(u32),
+}
+
+impl Dimension {
+    fn parse(value: &str) -> Option<Self> {
+        if value == "auto" {
+            Some(Self::Auto)
+        } else if let Some(pixels) = value.strip_suffix("px") {
+            pixels.parse().ok().map(Self::Pixels)
+        } else if let Some(percent) = value.strip_suffix('%') {
+            percent.parse().ok().map(Self::Percent)
+        } else {
+            value.parse().ok().map(Self::Cells)
+        }
+    }
+
+    /// The number of cells the dimension spans, given the size of a cell in pixels and the number
+    /// of cells across the terminal, or `None` when the image's own size is used.
+    pub fn to_cells(self, cell_size: u16, terminal_cells: u16) -> Option<usize> {
+        match self {
+            Self::Auto => None,
+            Self::Cells(cells) => Some(cells),
+            Self::Pixels(pixels) => Some(pixels.div_ceil(cell_size.max(1) as u32) as usize),
+            Self::Percent(percent) => Some(terminal_cells as usize * percent as usize / 100),
+        }
+        .filter(|cells| *cells > 0)
+    }
+}
+
+pub(super) struct InlineImage {
+    pub image: RgbaImage,
+    pub width: Dimension,
+    pub height: Dimension,
+}
+
+/// Parses the content of a `File=` sequence, after the `File=`. Returns `None` for the files
+/// that are not meant to be displayed.
+pub(super) fn parse(sequence: &[u8]) -> Result<Option<InlineImage>> {
+    let colon = sequence
+        .iter()
+        .position(|byte| *byte == b':')
+        .context("missing file contents")?;
+    let arguments = std::str::from_utf8(&sequence[..colon]).context("invalid arguments")?;
+    let mut inline = false;
+    let mut width = Dimension::Auto;
+    let mut height = Dimension::Auto;
+    for argument in arguments.split(';') {
+        let Some((key, value)) = argument.split_once('=') else {
+            continue;
+        };
+        match key {
+            "inline" => inline = value == "1",
+            "width" => width = Dimension::parse(value).context("invalid width")?,
+            "height" => height = Dimension::parse(value).context("invalid height")?,
+            _ => {}
+        }
+    }
+    if !inline {
+        return Ok(None);
+    }
+
+    let contents: Vec<u8> = sequence[colon + 1..]
+        .iter()
+        .copied()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .collect();
+    let data = decode_base64(&contents).context("invalid base64 data")?;
+    let image = image::load_from_memory(&data)
+        .context("invalid image data")?
+        .into_rgba8();
+    if image.width() > MAX_IMAGE_DIMENSION || image.height() > MAX_IMAGE_DIMENSION {
+        bail!("image too large");
+    }
+    Ok(Some(InlineImage {
+        image,
+        width,
+        height,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_dimensions() {
+        assert_eq!(Dimension::parse("auto"), Some(Dimension::Auto));
+        assert_eq!(Dimension::parse("12"), Some(Dimension::Cells(12)));
+        assert_eq!(Dimension::parse("100px"), Some(Dimension::Pixels(100)));
+        assert_eq!(Dimension::parse("50%"), Some(Dimension::Percent(50)));
+        assert_eq!(Dimension::parse("wide"), None);
+
+        assert_eq!(Dimension::Auto.to_cells(10, 80), None);
+        assert_eq!(Dimension::Cells(12).to_cells(10, 80), Some(12));
+        assert_eq!(Dimension::Pixels(101).to_cells(10, 80), Some(11));
+        assert_eq!(Dimension::Percent(50).to_cells(10, 80), Some(40));
+        assert_eq!(Dimension::Percent(0).to_cells(10, 80), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert!(parse(b"name=Zm9v;size=3:Zm9v").unwrap().is_none());
+        assert!(parse(b"inline=1;width=wide:Zm9v").is_err());
+        assert!(parse(b"inline=1:not an image").is_err());
+    }
+}
@@ -0,0 +1,402 @@
+//! The kitty graphics protocol, described in https://sw.kovidgoyal.net/kitty/graphics-protocol.
+//!
+//! Images are only transmitted directly in the escape sequences: a program could otherwise have
+//! the terminal read files from the machine Zed runs on, which is not the one the program runs on
+//! in remote terminals. Animations, source rectangles and pixel offsets are not supported.
+
+use std::{io::Read as _, sync::Arc};
+
+use anyhow::{Context as _, Result, anyhow, bail};
+use collections::HashMap;
+use flate2::read::ZlibDecoder;
+use gpui::RenderImage;
+use image::RgbaImage;
+
+use super::{MAX_IMAGE_BYTES, MAX_IMAGE_DIMENSION, MAX_SEQUENCE_LEN, decode_base64, render_image};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Command {
+    action: u8,
+    /// 1 to suppress the response on success, 2 to also suppress it on failure.
+    quiet: u32,
+    format: u32,
+    medium: u8,
+    compressed: bool,
+    /// Whether more chunks of the payload follow.
+    more: bool,
+    /// The ids and numbers are 0 when missing.
+    image_id: u32,
+    image_number: u32,
+    placement_id: u32,
+    /// The size in pixels of raw RGB and RGBA images.
+    width: u32,
+    height: u32,
+    columns: Option<usize>,
+    rows: Option<usize>,
+    move_cursor: bool,
+    delete: u8,
+    /// The base64 encoded payload.
+    payload: Vec<u8>,
+}
+
+impl Default for Command {
+    fn default() -> Self {
+        Self {
+            action: b't',
+            quiet: 0,
+            format: 32,
+            medium: b'd',
+            compressed: false,
+            more: false,
+            image_id: 0,
+            image_number: 0,
+            placement_id: 0,
+            width: 0,
+            height: 0,
+            columns: None,
+            rows: None,
+            move_cursor: true,
+            delete: b'a',
+            payload: Vec::new(),
+        }
+    }
+}
+
+/// Parses the content of a graphics command, after the `G` starting it.
+fn parse_command(sequence: &[u8]) -> Command {
+    let (control, payload) = match sequence.iter().position(|byte| *byte == b';') {
+        Some(ix) => (&sequence[..ix], &sequence[ix + 1..]),
+        None => (sequence, &[][..]),
+    };
+    let mut command = Command {
+        payload: payload.to_vec(),
+        ..Command::default()
+    };
+    for pair in control.split(|byte| *byte == b',') {
+        let Some((&key, value)) = pair.split_first() else {
+            continue;
+        };
+        let Some(value) = value.strip_prefix(b"=") else {
+            continue;
+        };
+        let number = || {
+            std::str::from_utf8(value)
+                .ok()
+                .and_then(|value| value.parse::<u32>().ok())
+                .unwrap_or(0)
+        };
+        let character = value.first().copied().unwrap_or(0);
+        match key {
+            b'a' => command.action = character,
+            b'q' => command.quiet = number(),
+            b'f' => command.format = number(),
+            b't' => command.medium = character,
+            b'o' => command.compressed = character == b'z',
+            b'm' => command.more = number() == 1,
+            b'i' => command.image_id = number(),
+            b'I' => command.image_number = number(),
+            b'p' => command.placement_id = number(),
+            b's' => command.width = number(),
+            b'v' => command.height = number(),
+            b'c' => command.columns = Some(number() as usize).filter(|columns| *columns > 0),
+            b'r' => command.rows = Some(number() as usize).filter(|rows| *rows > 0),
+            b'C' => command.move_cursor = number() != 1,
+            b'd' => command.delete = character,
+            _ => {}
+        }
+    }
+    command
+}
+
+#[derive(Clone)]
+struct KittyImage {
+    image: Arc<RenderImage>,
+    width: u32,
+    height: u32,
+}
+
+/// An image to display at the cursor.
+pub(super) struct Placement {
+    pub image_id: u32,
+    pub image: Arc<RenderImage>,
+    pub width: u32,
+    pub height: u32,
+    pub columns: Option<usize>,
+    pub rows: Option<usize>,
+    pub move_cursor: bool,
+}
+
+/// What a command does to the images displayed in the terminal.
+pub(super) enum Action {
+    None,
+    Place(Placement),
+    /// Deletes the placements of an image.
+    Delete(u32),
+    /// Deletes the placements of all images.
+    DeleteAll,
+}
+
+/// The images transmitted by the programs running in a terminal.
+#[derive(Default)]
+pub(super) struct KittyGraphics {
+    images: HashMap<u32, KittyImage>,
+    /// The ids assigned to the images transmitted with a number instead of an id.
+    numbered_images: HashMap<u32, u32>,
+    assigned_ids: u32,
+    /// The command whose payload is being transmitted in chunks.
+    pending: Option<Command>,
+}
+
+impl KittyGraphics {
+    /// Handles a graphics command, returning what it does to the displayed images, along with the
+    /// response to write back to the program.
+    pub fn handle(&mut self, sequence: &[u8]) -> (Action, Option<Vec<u8>>) {
+        let mut command = parse_command(sequence);
+        if let Some(mut pending) = self.pending.take() {
+            // The chunks after the first one only carry more of the payload.
+            pending.payload.extend_from_slice(&command.payload);
+            pending.more = command.more;
+            command = pending;
+        }
+        if command.more {
+            if command.payload.len() <= MAX_SEQUENCE_LEN {
+                self.pending = Some(command);
+            }
+            return (Action::None, None);
+        }
+
+        if command.image_id == 0 && command.image_number != 0 {
+            command.image_id = if matches!(command.action, b't' | b'T' | b'q') {
+                // Assigned ids count down from the largest one, away from the ids programs pick.
+                self.assigned_ids += 1;
+                let image_id = u32::MAX - self.assigned_ids;
+                self.numbered_images.insert(command.image_number, image_id);
+                image_id
+            } else {
+                self.numbered_images
+                    .get(&command.image_number)
+                    .copied()
+                    .unwrap_or(0)
+            };
+        }
+
+        let result = self.execute(&command);
+        let response = response(&command, &result);
+        (result.unwrap_or(Action::None), response)
+    }
+
+    fn execute(&mut self, command: &Command) -> Result<Action> {
+        match command.action {
+            b't' | b'T' | b'q' => {
+                let image = decode_image(command)?;
+                let image = KittyImage {
+                    width: image.width(),
+                    height: image.height(),
+                    image: render_image(image),
+                };
+                if command.action == b'q' {
+                    return Ok(Action::None);
+                }
+                if command.image_id != 0 {
+                    self.images.insert(command.image_id, image.clone());
+                }
+                Ok(if command.action == b'T' {
+                    Action::Place(placement(command, &image))
+                } else {
+                    Action::None
+                })
+            }
+            b'p' => {
+                let image = self
+                    .images
+                    .get(&command.image_id)
+                    .ok_or_else(|| anyhow!("ENOENT:image not found"))?;
+                Ok(Action::Place(placement(command, image)))
+            }
+            b'd' => Ok(match command.delete {
+                b'a' => Action::DeleteAll,
+                b'A' => {
+                    self.images.clear();
+                    self.numbered_images.clear();
+                    Action::DeleteAll
+                }
+                b'i' | b'n' => Action::Delete(command.image_id),
+                b'I' | b'N' => {
+                    self.images.remove(&command.image_id);
+                    Action::Delete(command.image_id)
+                }
+                _ => Action::None,
+            }),
+            _ => bail!("EINVAL:unsupported action"),
+        }
+    }
+}
+
+fn placement(command: &Command, image: &KittyImage) -> Placement {
+    Placement {
+        image_id: command.image_id,
+        image: image.image.clone(),
+        width: image.width,
+        height: image.height,
+        columns: command.columns,
+        rows: command.rows,
+        move_cursor: command.move_cursor,
+    }
+}
+
+/// The response to a command, which is only sent for commands that identify their image.
+fn response(command: &Command, result: &Result<Action>) -> Option<Vec<u8>> {
+    if command.image_id == 0 {
+        return None;
+    }
+    let message = match result {
+        Ok(_) if command.quiet == 0 => "OK".to_string(),
+        Err(error) if command.quiet < 2 => error.to_string(),
+        _ => return None,
+    };
+    let mut keys = format!("i={}", command.image_id);
+    if command.image_number != 0 {
+        keys.push_str(&format!(",I={}", command.image_number));
+    }
+    if command.placement_id != 0 {
+        keys.push_str(&format!(",p={}", command.placement_id));
+    }
+    Some(format!("\x1b_G{keys};{message}\x1b\\").into_bytes())
+}
+
+fn decode_image(command: &Command) -> Result<RgbaImage> {
+    if command.medium != b'd' {
+        bail!("EINVAL:only direct transmission is supported");
+    }
+    let mut data = decode_base64(&command.payload).context("EINVAL:invalid base64 data")?;
+    if command.compressed {
+        let mut decompressed = Vec::new();
+        ZlibDecoder::new(data.as_slice())
+            .take(MAX_IMAGE_BYTES as u64)
+            .read_to_end(&mut decompressed)
+            .context("EINVAL:invalid compressed data")?;
+        data = decompressed;
+    }
+
+    let image = match command.format {
+        100 => image::load_from_memory(&data)
+            .context("EBADPNG:invalid PNG data")?
+            .into_rgba8(),
+        24 | 32 => {
+            let (width, height) = (command.width, command.height);
+            if width == 0 || height == 0 {
+                bail!("EINVAL:missing image size");
+            }
+            if width > MAX_IMAGE_DIMENSION || height > MAX_IMAGE_DIMENSION {
+                bail!("EINVAL:image too large");
+            }
+            let pixel_count = width as usize * height as usize;
+            let pixels = if command.format == 32 {
+                data.get(..pixel_count * 4).map(<[u8]>::to_vec)
+            } else {
+                data.get(..pixel_count * 3).map(|data| {
+                    data.chunks_exact(3)
+                        .flat_map(|pixel| [pixel[0], pixel[1], pixel[2], u8::MAX])
+                        .collect()
+                })
+            };
+            let pixels = pixels.context("ENODATA:insufficient image data")?;
+            RgbaImage::from_raw(width, height, pixels).context("EINVAL:invalid image data")?
+        }
+        _ => bail!("EINVAL:unsupported format"),
+    };
+    if image.width() > MAX_IMAGE_DIMENSION || image.height() > MAX_IMAGE_DIMENSION {
+        bail!("EINVAL:image too large");
+    }
+    Ok(image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine as _;
+    use pretty_assertions::assert_eq;
+
+    fn encode(data: &[u8]) -> String {
+        base64::engine::general_purpose::STANDARD.encode(data)
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(
+            parse_command(b"a=T,f=24,s=2,v=1,i=7,c=10,C=1,q=2;AAAA"),
+            Command {
+                action: b'T',
+                quiet: 2,
+                format: 24,
+                image_id: 7,
+                width: 2,
+                height: 1,
+                columns: Some(10),
+                move_cursor: false,
+                payload: b"AAAA".to_vec(),
+                ..Command::default()
+            }
+        );
+        assert_eq!(
+            parse_command(b"a=d,d=I,i=3,x"),
+            Command {
+                action: b'd',
+                delete: b'I',
+                image_id: 3,
+                ..Command::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_chunked_transmission() {
+        let mut graphics = KittyGraphics::default();
+        let pixels = encode(&[255, 0, 0, 0, 255, 0]);
+        let (first, second) = pixels.split_at(4);
+
+        let (action, response) =
+            graphics.handle(format!("a=T,f=24,s=2,v=1,i=5,m=1;{first}").as_bytes());
+        assert!(matches!(action, Action::None));
+        assert_eq!(response, None);
+
+        let (action, response) = graphics.handle(format!("m=0;{second}").as_bytes());
+        let Action::Place(placement) = action else {
+            panic!("expected the image to be placed");
+        };
+        assert_eq!(
+            (placement.image_id, placement.width, placement.height),
+            (5, 2, 1)
+        );
+        assert_eq!(response, Some(b"\x1b_Gi=5;OK\x1b\\".to_vec()));
+
+        let (action, response) = graphics.handle(b"a=p,i=5,q=1");
+        assert!(matches!(action, Action::Place(_)));
+        assert_eq!(response, None);
+
+        let (action, _) = graphics.handle(b"a=d,d=I,i=5");
+        assert!(matches!(action, Action::Delete(5)));
+        let (action, response) = graphics.handle(b"a=p,i=5");
+        assert!(matches!(action, Action::None));
+        assert_eq!(
+            response,
+            Some(b"\x1b_Gi=5;ENOENT:image not found\x1b\\".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_query() {
+        let mut graphics = KittyGraphics::default();
+        let pixel = encode(&[0, 0, 0]);
+        let (action, response) =
+            graphics.handle(format!("a=q,i=31,s=1,v=1,f=24,t=d;{pixel}").as_bytes());
+        assert!(matches!(action, Action::None));
+        assert_eq!(response, Some(b"\x1b_Gi=31;OK\x1b\\".to_vec()));
+
+        let (_, response) = graphics.handle(b"a=q,i=31,s=1,v=1,t=f;L3RtcC9pbWFnZQ==");
+        assert_eq!(
+            response,
+            Some(b"\x1b_Gi=31;EINVAL:only direct transmission is supported\x1b\\".to_vec())
+        );
+    }
+}
@@ -0,0 +1,282 @@
+//! Sixel images, as described in the VT330/VT340 programmer reference manual.
+//!
+//! Pixels that are not drawn are left transparent, whatever background the image asks for, so
+//! the terminal's background shows through them.
+
+use image::{Rgba, RgbaImage};
+
+use super::MAX_IMAGE_DIMENSION;
+
+/// The VT340's default color registers, in percents, for the colors images use without defining.
+const DEFAULT_PALETTE: [[u8; 3]; 16] = [
+    [0, 0, 0],
+    [20, 20, 80],
+    [80, 13, 13],
+    [20, 80, 20],
+    [80, 20, 80],
+    [20, 80, 80],
+    [80, 80, 20],
+    [53, 53, 53],
+    [26, 26, 26],
+    [33, 33, 60],
+    [60, 26, 26],
+    [33, 60, 33],
+    [60, 33, 60],
+    [33, 60, 60],
+    [60, 60, 33],
+    [80, 80, 80],
+];
+
+const PALETTE_SIZE: usize = 256;
+
+/// Decodes the content of a sixel DCS sequence, from its parameters to the end of its data.
+pub(super) fn decode(sequence: &[u8]) -> Option<RgbaImage> {
+    let data_start = sequence.iter().position(|byte| *byte == b'q')? + 1;
+    let mut decoder = Decoder::default();
+    decoder.decode(&sequence[data_start..]);
+    decoder.finish()
+}
+
+struct Decoder {
+    palette: [Rgba<u8>; PALETTE_SIZE],
+    color: Rgba<u8>,
+    x: usize,
+    /// The band of six pixel rows being drawn.
+    band: usize,
+    rows: Vec<Vec<Rgba<u8>>>,
+    /// The size declared by the raster attributes, if any.
+    declared_size: Option<(usize, usize)>,
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        let mut palette = [Rgba([0, 0, 0, u8::MAX]); PALETTE_SIZE];
+        for (color, [r, g, b]) in palette.iter_mut().zip(DEFAULT_PALETTE) {
+            *color = rgb_from_percents(r as usize, g as usize, b as usize);
+        }
+        Self {
+            color: palette[0],
+            palette,
+            x: 0,
+            band: 0,
+            rows: Vec::new(),
+            declared_size: None,
+        }
+    }
+}
+
+impl Decoder {
+    fn decode(&mut self, data: &[u8]) {
+        let mut ix = 0;
+        while ix < data.len() {
+            let byte = data[ix];
+            ix += 1;
+            match byte {
+                b'?'..=b'~' => self.draw(byte - b'?', 1),
+                b'!' => {
+                    let (parameters, len) = parse_parameters(&data[ix..]);
+                    ix += len;
+                    if let Some(&sixel @ b'?'..=b'~') = data.get(ix) {
+                        ix += 1;
+                        let count = parameters.first().copied().unwrap_or(1).max(1);
+                        self.draw(sixel - b'?', count);
+                    }
+                }
+                b'#' => {
+                    let (parameters, len) = parse_parameters(&data[ix..]);
+                    ix += len;
+                    self.select_color(&parameters);
+                }
+                b'"' => {
+                    let (parameters, len) = parse_parameters(&data[ix..]);
+                    ix += len;
+                    if let [_, _, width, height, ..] = parameters[..]
+                        && width > 0
+                        && height > 0
+                    {
+                        self.declared_size = Some((width, height));
+                    }
+                }
+                b'$' => self.x = 0,
+                b'-' => {
+                    self.x = 0;
+                    self.band += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn select_color(&mut self, parameters: &[usize]) {
+        let Some(&register) = parameters.first() else {
+            return;
+        };
+        let register = register % PALETTE_SIZE;
+        if let [_, space, x, y, z, ..] = *parameters {
+            self.palette[register] = match space {
+                1 => rgb_from_hls(x, y, z),
+                2 => rgb_from_percents(x, y, z),
+                _ => self.palette[register],
+            };
+        }
+        self.color = self.palette[register];
+    }
+
+    /// Draws a sixel, a column of six pixels with a bit each, repeated across `count` columns.
+    fn draw(&mut self, sixel: u8, count: usize) {
+        let max = MAX_IMAGE_DIMENSION as usize;
+        let count = count.min(max.saturating_sub(self.x));
+        for bit in 0..6 {
+            let y = self.band * 6 + bit;
+            if sixel & (1 << bit) == 0 || y >= max {
+                continue;
+            }
+            if self.rows.len() <= y {
+                self.rows.resize_with(y + 1, Vec::new);
+            }
+            let row = &mut self.rows[y];
+            if row.len() < self.x + count {
+                row.resize(self.x + count, Rgba([0, 0, 0, 0]));
+            }
+            row[self.x..self.x + count].fill(self.color);
+        }
+        self.x += count;
+    }
+
+    fn finish(self) -> Option<RgbaImage> {
+        let (width, height) = self.declared_size.unwrap_or_else(|| {
+            let width = self.rows.iter().map(Vec::len).max().unwrap_or(0);
+            (width, self.rows.len())
+        });
+        let (width, height) = (
+            width.min(MAX_IMAGE_DIMENSION as usize),
+            height.min(MAX_IMAGE_DIMENSION as usize),
+        );
+        if width == 0 || height == 0 {
+            return None;
+        }
+        let mut image = RgbaImage::new(width as u32, height as u32);
+        for (y, row) in self.rows.iter().enumerate().take(height) {
+            for (x, pixel) in row.iter().enumerate().take(width) {
+                image.put_pixel(x as u32, y as u32, *pixel);
+            }
+        }
+        Some(image)
+    }
+}
+
+/// Parses numeric parameters separated by semicolons, returning them along with their length.
+fn parse_parameters(data: &[u8]) -> (Vec<usize>, usize) {
+    let mut parameters = Vec::new();
+    let mut current = None::<usize>;
+    let mut len = 0;
+    for &byte in data {
+        match byte {
+            b'0'..=b'9' => {
+                let digit = (byte - b'0') as usize;
+                current = Some(
+                    current
+                        .unwrap_or(0)
+                        .saturating_mul(10)
+                        .saturating_add(digit),
+                );
+            }
+            b';' => parameters.push(current.take().unwrap_or(0)),
+            _ => break,
+        }
+        len += 1;
+    }
+    if let Some(current) = current {
+        parameters.push(current);
+    }
+    (parameters, len)
+}
+
+fn rgb_from_percents(r: usize, g: usize, b: usize) -> Rgba<u8> {
+    let channel = |percent: usize| (percent.min(100) * 255 / 100) as u8;
+    Rgba([channel(r), channel(g), channel(b), u8::MAX])
+}
+
+/// Converts a color from the sixel HLS color space, where hues start at blue rather than red.
+fn rgb_from_hls(hue: usize, lightness: usize, saturation: usize) -> Rgba<u8> {
+    let hue = ((hue % 360 + 240) % 360) as f32 / 360.;
+    let lightness = lightness.min(100) as f32 / 100.;
+    let saturation = saturation.min(100) as f32 / 100.;
+    if saturation == 0. {
+        let value = (lightness * 255.).round() as u8;
+        return Rgba([value, value, value, u8::MAX]);
+    }
+
+    let q = if lightness < 0.5 {
+        lightness * (1. + saturation)
+    } else {
+        lightness + saturation - lightness * saturation
+    };
+    let p = 2. * lightness - q;
+    let channel = |mut t: f32| {
+        if t < 0. {
+            t += 1.;
+        }
+        if t > 1. {
+            t -= 1.;
+        }
+        let value = if t < 1. / 6. {
+            p + (q - p) * 6. * t
+        } else if t < 1. / 2. {
+            q
+        } else if t < 2. / 3. {
+            p + (q - p) * (2. / 3. - t) * 6.
+        } else {
+            p
+        };
+        (value * 255.).round() as u8
+    };
+    Rgba([
+        channel(hue + 1. / 3.),
+        channel(hue),
+        channel(hue - 1. / 3.),
+        u8::MAX,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const TRANSPARENT: Rgba<u8> = Rgba([0, 0, 0, 0]);
+    const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+    const BLUE: Rgba<u8> = Rgba([0, 0, 255, 255]);
+
+    #[test]
+    fn test_decode() {
+        // A red pixel on the second row, then two blue columns of six pixels, then a band with a
+        // single red pixel.
+        let image = decode(b"0;1;0q#1;2;100;0;0#2;2;0;0;100#1A#2!2~-#1@").unwrap();
+        assert_eq!((image.width(), image.height()), (3, 7));
+        assert_eq!(*image.get_pixel(0, 0), TRANSPARENT);
+        assert_eq!(*image.get_pixel(0, 1), RED);
+        assert_eq!(*image.get_pixel(0, 2), TRANSPARENT);
+        assert_eq!(*image.get_pixel(1, 0), BLUE);
+        assert_eq!(*image.get_pixel(2, 5), BLUE);
+        assert_eq!(*image.get_pixel(0, 6), RED);
+        assert_eq!(*image.get_pixel(1, 6), TRANSPARENT);
+
+        // Overstriking after a carriage return, with an HLS color.
+        let image = decode(b"q#3;1;120;50;100~$#0@").unwrap();
+        assert_eq!((image.width(), image.height()), (1, 6));
+        assert_eq!(*image.get_pixel(0, 0), Rgba([0, 0, 0, 255]));
+        assert_eq!(*image.get_pixel(0, 1), RED);
+    }
+
+    #[test]
+    fn test_declared_size() {
+        let image = decode(b"q\"1;1;4;2~").unwrap();
+        assert_eq!((image.width(), image.height()), (4, 2));
+        assert_eq!(*image.get_pixel(0, 1), Rgba([0, 0, 0, 255]));
+        assert_eq!(*image.get_pixel(3, 0), TRANSPARENT);
+
+        assert_eq!(decode(b"q"), None);
+        assert_eq!(decode(b"no data"), None);
+    }
+}
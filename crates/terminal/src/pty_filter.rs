@@ -0,0 +1,115 @@
+//! Processing the output of a terminal's PTY as the event loop reads it, before the terminal
+//! parses it.
+
+use std::{io, sync::Arc};
+
+use alacritty_terminal::{
+    event::{OnResize, WindowSize},
+    tty::{ChildEvent, EventedPty, EventedReadWrite},
+};
+use polling::{Event as PollingEvent, PollMode, Poller};
+
+/// Sees, or changes, the output of a PTY wrapped in a [`FilteredPty`].
+pub(crate) trait PtyFilter {
+    /// Reads output from the PTY into the buffer, like [`io::Read::read`].
+    fn read(&mut self, pty: &mut impl EventedReadWrite, buf: &mut [u8]) -> io::Result<usize>;
+
+    /// Called before the PTY is resized.
+    fn resized(&mut self, _window_size: WindowSize) {}
+
+    /// Called before the PTY is registered with the event loop's poller.
+    fn registered(
+        &mut self,
+        _poll: &Arc<Poller>,
+        _interest: PollingEvent,
+        _mode: PollMode,
+    ) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Called before the PTY is reregistered, which the event loop does after parsing the output
+    /// it read.
+    fn reregistered(
+        &mut self,
+        _poll: &Arc<Poller>,
+        _interest: PollingEvent,
+        _mode: PollMode,
+    ) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Called before the PTY is deregistered.
+    fn deregistered(&mut self, _poll: &Arc<Poller>) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A PTY whose output is read through a [`PtyFilter`].
+pub(crate) struct FilteredPty<T, F> {
+    pty: T,
+    filter: F,
+}
+
+impl<T, F> FilteredPty<T, F> {
+    pub fn new(pty: T, filter: F) -> Self {
+        Self { pty, filter }
+    }
+}
+
+impl<T: EventedReadWrite, F: PtyFilter> io::Read for FilteredPty<T, F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.filter.read(&mut self.pty, buf)
+    }
+}
+
+impl<T: EventedReadWrite, F: PtyFilter> EventedReadWrite for FilteredPty<T, F> {
+    type Reader = Self;
+    type Writer = T::Writer;
+
+    unsafe fn register(
+        &mut self,
+        poll: &Arc<Poller>,
+        interest: PollingEvent,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        self.filter.registered(poll, interest, mode)?;
+        // SAFETY: the PTY is owned by this wrapper and lives as long as the registration does.
+        unsafe { self.pty.register(poll, interest, mode) }
+    }
+
+    fn reregister(
+        &mut self,
+        poll: &Arc<Poller>,
+        interest: PollingEvent,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        self.filter.reregistered(poll, interest, mode)?;
+        self.pty.reregister(poll, interest, mode)
+    }
+
+    fn deregister(&mut self, poll: &Arc<Poller>) -> io::Result<()> {
+        self.filter.deregistered(poll)?;
+        self.pty.deregister(poll)
+    }
+
+    fn reader(&mut self) -> &mut Self::Reader {
+        self
+    }
+
+    fn writer(&mut self) -> &mut Self::Writer {
+        self.pty.writer()
+    }
+}
+
+impl<T: EventedPty, F: PtyFilter> EventedPty for FilteredPty<T, F> {
+    fn next_child_event(&mut self) -> Option<ChildEvent> {
+        self.pty.next_child_event()
+    }
+}
+
+impl<T: OnResize, F: PtyFilter> OnResize for FilteredPty<T, F> {
+    fn on_resize(&mut self, window_size: WindowSize) {
+        self.filter.resized(window_size);
+        self.pty.on_resize(window_size)
+    }
+}
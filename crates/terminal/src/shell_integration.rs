@@ -0,0 +1,1006 @@
+//! Shell integration: shells mark where their prompts, commands and command output start and
+//! end with `OSC 133` (FinalTerm) or `OSC 633` (VS Code) sequences, and report their working
+//! directory with `OSC 7` or `OSC 633 ; P`. Alacritty ignores these sequences, so they are picked
+//! out of the PTY output before it reaches the parser.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
+};
+
+use alacritty_terminal::{
+    Term,
+    grid::Dimensions as _,
+    index::{Column, Line, Point as AlacPoint},
+    sync::FairMutex,
+    tty::EventedReadWrite,
+};
+use collections::{HashMap, VecDeque};
+use futures::channel::mpsc::UnboundedSender;
+use polling::{Event as PollingEvent, PollMode, Poller};
+use task::Shell;
+
+use crate::{ZedListener, pty_filter::PtyFilter};
+
+/// Older commands are forgotten past this number, like scrollback lines are.
+const MAX_COMMAND_BLOCKS: usize = 1_000;
+/// Longer sequences are not shell integration markers, and are skipped without being buffered.
+const MAX_SEQUENCE_LEN: usize = 4_096;
+
+/// A shell integration sequence found in the terminal output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ShellMarker {
+    /// `A`: the shell is about to print its prompt.
+    PromptStart,
+    /// `B`: the prompt was printed and the command is typed after it.
+    CommandStart,
+    /// `C`: the command was submitted and its output follows.
+    OutputStart,
+    /// `D [; exit code]`: the command finished.
+    CommandFinished { exit_code: Option<i32> },
+    /// `633 ; E ; command line`: the command line about to run.
+    CommandLine(String),
+    /// `OSC 7` or `633 ; P ; Cwd=path`: the shell's working directory.
+    WorkingDirectory(PathBuf),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum ScanState {
+    #[default]
+    Ground,
+    Escape,
+    Osc,
+    OscEscape,
+}
+
+/// Finds shell integration sequences in a stream of terminal output,
+/// including the ones split across several reads.
+#[derive(Debug, Default)]
+pub(crate) struct ShellMarkerScanner {
+    state: ScanState,
+    sequence: Vec<u8>,
+    sequence_too_long: bool,
+}
+
+impl ShellMarkerScanner {
+    /// Returns the next marker in the bytes, along with the number of bytes up to the end of its sequence.
+    /// Call again with the bytes that follow to find the markers after it.
+    pub fn next_marker(&mut self, bytes: &[u8]) -> Option<(usize, ShellMarker)> {
+        for (ix, &byte) in bytes.iter().enumerate() {
+            match self.state {
+                ScanState::Ground => {
+                    if byte == 0x1b {
+                        self.state = ScanState::Escape;
+                    }
+                }
+                ScanState::Escape => match byte {
+                    b']' => self.start_sequence(),
+                    0x1b => {}
+                    _ => self.state = ScanState::Ground,
+                },
+                ScanState::Osc => match byte {
+                    // BEL
+                    0x07 => {
+                        if let Some(marker) = self.finish_sequence() {
+                            return Some((ix + 1, marker));
+                        }
+                    }
+                    0x1b => self.state = ScanState::OscEscape,
+                    // CAN and SUB cancel the sequence.
+                    0x18 | 0x1a => self.state = ScanState::Ground,
+                    _ => {
+                        if self.sequence.len() < MAX_SEQUENCE_LEN {
+                            self.sequence.push(byte);
+                        } else {
+                            self.sequence_too_long = true;
+                        }
+                    }
+                },
+                ScanState::OscEscape => match byte {
+                    // ST
+                    b'\\' => {
+                        if let Some(marker) = self.finish_sequence() {
+                            return Some((ix + 1, marker));
+                        }
+                    }
+                    b']' => self.start_sequence(),
+                    0x1b => self.state = ScanState::Escape,
+                    _ => self.state = ScanState::Ground,
+                },
+            }
+        }
+        None
+    }
+
+    fn start_sequence(&mut self) {
+        self.state = ScanState::Osc;
+        self.sequence.clear();
+        self.sequence_too_long = false;
+    }
+
+    fn finish_sequence(&mut self) -> Option<ShellMarker> {
+        self.state = ScanState::Ground;
+        if self.sequence_too_long {
+            return None;
+        }
+        parse_marker(&self.sequence)
+    }
+}
+
+fn parse_marker(sequence: &[u8]) -> Option<ShellMarker> {
+    let sequence = std::str::from_utf8(sequence).ok()?;
+    let (code, params) = sequence.split_once(';')?;
+    match code {
+        "7" => working_directory_from_url(params).map(ShellMarker::WorkingDirectory),
+        "133" | "633" => {
+            let mut params = params.split(';');
+            match params.next()? {
+                "A" => Some(ShellMarker::PromptStart),
+                "B" => Some(ShellMarker::CommandStart),
+                "C" => Some(ShellMarker::OutputStart),
+                "D" => Some(ShellMarker::CommandFinished {
+                    exit_code: params.next().and_then(|code| code.parse().ok()),
+                }),
+                "E" if code == "633" => Some(ShellMarker::CommandLine(unescape_value(
+                    params.next().unwrap_or_default(),
+                ))),
+                "P" if code == "633" => {
+                    let cwd = params.next()?.strip_prefix("Cwd=")?;
+                    Some(ShellMarker::WorkingDirectory(PathBuf::from(
+                        unescape_value(cwd),
+                    )))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Reads the path out of a `file://host/path` URL, ignoring the host.
+fn working_directory_from_url(url: &str) -> Option<PathBuf> {
+    let url = url.strip_prefix("file://")?;
+    let path = &url[url.find('/')?..];
+    let path = urlencoding::decode(path).ok()?;
+    // Windows paths are reported as `/C:/path`.
+    let path = match path.strip_prefix('/') {
+        Some(windows_path) if windows_path.as_bytes().get(1) == Some(&b':') => windows_path,
+        _ => &path,
+    };
+    Some(PathBuf::from(path))
+}
+
+/// Unescapes the values of `OSC 633` sequences, where `\\` is a backslash and `\xAB` is a byte.
+fn unescape_value(value: &str) -> String {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+        if byte != b'\\' {
+            bytes.push(byte);
+            continue;
+        }
+        match rest {
+            [b'\\', tail @ ..] => {
+                bytes.push(b'\\');
+                rest = tail;
+            }
+            [b'x', high, low, tail @ ..] => {
+                match std::str::from_utf8(&[*high, *low])
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                {
+                    Some(escaped) => {
+                        bytes.push(escaped);
+                        rest = tail;
+                    }
+                    None => bytes.push(b'\\'),
+                }
+            }
+            _ => bytes.push(b'\\'),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Reports the shell integration markers in the output of a terminal's PTY, along with where the
+/// terminal's cursor was when they were parsed.
+///
+/// Reads end after each marker, and the one after fails with `WouldBlock` until the marker is
+/// recorded: the event loop then parses the output up to the marker and unlocks the terminal, and
+/// the cursor is read when the PTY is reregistered. The output after the marker is kept until then,
+/// and the event loop is woken up to read it.
+pub(crate) struct ShellIntegrationFilter {
+    term: Arc<FairMutex<Term<ZedListener>>>,
+    scanner: ShellMarkerScanner,
+    /// The marker at the end of the output last read, until it is recorded.
+    pending_marker: Option<ShellMarker>,
+    /// The output read past a marker, returned by the reads after it.
+    unread: Vec<u8>,
+    waker: Option<Waker>,
+    markers_tx: UnboundedSender<(ShellMarker, HistoryPoint)>,
+}
+
+impl ShellIntegrationFilter {
+    pub fn new(
+        term: Arc<FairMutex<Term<ZedListener>>>,
+        markers_tx: UnboundedSender<(ShellMarker, HistoryPoint)>,
+    ) -> Self {
+        Self {
+            term,
+            scanner: ShellMarkerScanner::default(),
+            pending_marker: None,
+            unread: Vec::new(),
+            waker: None,
+            markers_tx,
+        }
+    }
+}
+
+impl PtyFilter for ShellIntegrationFilter {
+    fn read(&mut self, pty: &mut impl EventedReadWrite, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pending_marker.is_some() {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        let len = if self.unread.is_empty() {
+            pty.reader().read(buf)?
+        } else {
+            let len = self.unread.len().min(buf.len());
+            buf[..len].copy_from_slice(&self.unread[..len]);
+            self.unread.drain(..len);
+            if self.unread.is_empty()
+                && let Some(waker) = &self.waker
+            {
+                waker.reset();
+            }
+            len
+        };
+        match self.scanner.next_marker(&buf[..len]) {
+            Some((marker_len, marker)) => {
+                self.unread
+                    .splice(..0, buf[marker_len..len].iter().copied());
+                self.pending_marker = Some(marker);
+                Ok(marker_len)
+            }
+            None => Ok(len),
+        }
+    }
+
+    fn registered(
+        &mut self,
+        poll: &Arc<Poller>,
+        interest: PollingEvent,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        self.waker = Some(Waker::new(poll, interest, mode)?);
+        Ok(())
+    }
+
+    fn reregistered(
+        &mut self,
+        _poll: &Arc<Poller>,
+        interest: PollingEvent,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        // The event loop parsed everything it read before reregistering, and the terminal is unlocked.
+        if let Some(marker) = self.pending_marker.take() {
+            let cursor = HistoryPoint::cursor(&self.term.lock());
+            self.markers_tx.unbounded_send((marker, cursor)).ok();
+            if !self.unread.is_empty()
+                && let Some(waker) = &self.waker
+            {
+                waker.wake(interest)?;
+            }
+        }
+        if let Some(waker) = &self.waker {
+            waker.reregister(interest, mode)?;
+        }
+        Ok(())
+    }
+
+    fn deregistered(&mut self, _poll: &Arc<Poller>) -> io::Result<()> {
+        self.waker = None;
+        Ok(())
+    }
+}
+
+/// Makes the event loop read from the PTY while output is left after a marker,
+/// which the PTY itself may not be readable for anymore.
+#[cfg(unix)]
+struct Waker {
+    reader: std::os::unix::net::UnixStream,
+    writer: std::os::unix::net::UnixStream,
+    poll: Arc<Poller>,
+}
+
+#[cfg(unix)]
+impl Waker {
+    fn new(poll: &Arc<Poller>, interest: PollingEvent, mode: PollMode) -> io::Result<Self> {
+        let (reader, writer) = std::os::unix::net::UnixStream::pair()?;
+        reader.set_nonblocking(true)?;
+        writer.set_nonblocking(true)?;
+        // SAFETY: the reader is deleted from the poller when the waker is dropped.
+        unsafe { poll.add_with_mode(&reader, PollingEvent::readable(interest.key), mode)? };
+        Ok(Self {
+            reader,
+            writer,
+            poll: poll.clone(),
+        })
+    }
+
+    fn reregister(&self, interest: PollingEvent, mode: PollMode) -> io::Result<()> {
+        self.poll
+            .modify_with_mode(&self.reader, PollingEvent::readable(interest.key), mode)
+    }
+
+    fn wake(&self, _interest: PollingEvent) -> io::Result<()> {
+        use std::io::Write as _;
+        match (&self.writer).write(&[0]) {
+            // The waker is already readable when its buffer is full.
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => Ok(()),
+            result => result.map(|_| ()),
+        }
+    }
+
+    fn reset(&self) {
+        use std::io::Read as _;
+        let mut buf = [0; 64];
+        while matches!((&self.reader).read(&mut buf), Ok(len) if len > 0) {}
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Waker {
+    fn drop(&mut self) {
+        self.poll.delete(&self.reader).ok();
+    }
+}
+
+#[cfg(windows)]
+struct Waker {
+    poll: Arc<Poller>,
+}
+
+#[cfg(windows)]
+impl Waker {
+    fn new(poll: &Arc<Poller>, _interest: PollingEvent, _mode: PollMode) -> io::Result<Self> {
+        Ok(Self { poll: poll.clone() })
+    }
+
+    fn reregister(&self, _interest: PollingEvent, _mode: PollMode) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn wake(&self, interest: PollingEvent) -> io::Result<()> {
+        use polling::os::iocp::{CompletionPacket, PollerIocpExt as _};
+        self.poll
+            .post(CompletionPacket::new(PollingEvent::readable(interest.key)))
+    }
+
+    fn reset(&self) {}
+}
+
+/// A position in the terminal, with lines counted from the top of the scrollback.
+///
+/// Lines keep their position while the scrollback grows, but drift once it is full
+/// and the oldest lines start being dropped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct HistoryPoint {
+    line: usize,
+    column: usize,
+}
+
+impl HistoryPoint {
+    pub fn cursor(term: &Term<ZedListener>) -> Self {
+        let cursor = term.grid().cursor.point;
+        Self {
+            line: (term.history_size() as i32 + cursor.line.0).max(0) as usize,
+            column: cursor.column.0,
+        }
+    }
+
+    fn to_grid_line(self, term: &Term<ZedListener>) -> Option<Line> {
+        let line = Line(self.line as i32 - term.history_size() as i32);
+        (line <= term.bottommost_line()).then_some(line)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommandStatus {
+    /// The command is running, or is still being typed.
+    Running,
+    /// The command finished, with the exit code the shell reported.
+    Finished { exit_code: Option<i32> },
+}
+
+/// A command run in the shell, delimited by the shell integration markers.
+#[derive(Clone, Debug)]
+pub struct CommandBlock {
+    prompt_start: HistoryPoint,
+    command_start: Option<HistoryPoint>,
+    output_start: Option<HistoryPoint>,
+    output_end: Option<HistoryPoint>,
+    command: Option<String>,
+    status: CommandStatus,
+    working_directory: Option<PathBuf>,
+}
+
+impl CommandBlock {
+    /// The command line, if the shell reported it.
+    pub fn command(&self) -> Option<&str> {
+        self.command.as_deref()
+    }
+
+    pub fn status(&self) -> CommandStatus {
+        self.status
+    }
+
+    /// The working directory the command ran in, if the shell reported it.
+    pub fn working_directory(&self) -> Option<&Path> {
+        self.working_directory.as_deref()
+    }
+
+    /// Whether the command was submitted, as opposed to a prompt still waiting for input.
+    pub fn has_run(&self) -> bool {
+        self.output_start.is_some()
+    }
+}
+
+/// The exit status of a command, to show next to its prompt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommandMarker {
+    /// The line of the prompt, in the terminal's grid.
+    pub line: Line,
+    pub status: CommandStatus,
+}
+
+/// The commands the shell reported running in the terminal, and its working directory.
+#[derive(Debug, Default)]
+pub(crate) struct ShellIntegration {
+    blocks: VecDeque<CommandBlock>,
+    working_directory: Option<PathBuf>,
+    /// Scans the output written directly to display-only terminals.
+    pub output_scanner: ShellMarkerScanner,
+}
+
+impl ShellIntegration {
+    pub fn working_directory(&self) -> Option<&Path> {
+        self.working_directory.as_deref()
+    }
+
+    pub fn blocks(&self) -> impl DoubleEndedIterator<Item = &CommandBlock> {
+        self.blocks.iter()
+    }
+
+    /// Records a marker at the position of the cursor when the marker was parsed.
+    pub fn handle_marker(
+        &mut self,
+        marker: ShellMarker,
+        cursor: HistoryPoint,
+        term: &Term<ZedListener>,
+    ) {
+        // Clearing the scrollback removes the lines of older commands.
+        self.blocks
+            .retain(|block| block.prompt_start.line < term.total_lines());
+
+        match marker {
+            ShellMarker::PromptStart => {
+                if let Some(block) = self.blocks.back_mut() {
+                    if !block.has_run() {
+                        // Nothing was run from the previous prompt.
+                        self.blocks.pop_back();
+                    } else if block.status == CommandStatus::Running {
+                        // The shell did not report the end of the command.
+                        block.output_end = Some(cursor);
+                        block.status = CommandStatus::Finished { exit_code: None };
+                    }
+                }
+                if self.blocks.len() == MAX_COMMAND_BLOCKS {
+                    self.blocks.pop_front();
+                }
+                self.blocks.push_back(CommandBlock {
+                    prompt_start: cursor,
+                    command_start: None,
+                    output_start: None,
+                    output_end: None,
+                    command: None,
+                    status: CommandStatus::Running,
+                    working_directory: self.working_directory.clone(),
+                });
+            }
+            ShellMarker::CommandStart => {
+                self.current_block(cursor).command_start = Some(cursor);
+            }
+            ShellMarker::CommandLine(command) => {
+                self.current_block(cursor).command = Some(command);
+            }
+            ShellMarker::OutputStart => {
+                self.current_block(cursor).output_start = Some(cursor);
+            }
+            ShellMarker::CommandFinished { exit_code } => {
+                if let Some(block) = self
+                    .blocks
+                    .back_mut()
+                    .filter(|block| block.status == CommandStatus::Running && block.has_run())
+                {
+                    block.output_end = Some(cursor);
+                    block.status = CommandStatus::Finished { exit_code };
+                }
+            }
+            ShellMarker::WorkingDirectory(working_directory) => {
+                if let Some(block) = self.blocks.back_mut()
+                    && !block.has_run()
+                {
+                    block.working_directory = Some(working_directory.clone());
+                }
+                self.working_directory = Some(working_directory);
+            }
+        }
+    }
+
+    fn current_block(&mut self, cursor: HistoryPoint) -> &mut CommandBlock {
+        if self.blocks.is_empty() {
+            self.blocks.push_back(CommandBlock {
+                prompt_start: cursor,
+                command_start: None,
+                output_start: None,
+                output_end: None,
+                command: None,
+                status: CommandStatus::Running,
+                working_directory: self.working_directory.clone(),
+            });
+        }
+        self.blocks.back_mut().unwrap()
+    }
+
+    /// Forgets the commands whose lines were cleared from the terminal,
+    /// keeping the current prompt on the line it was moved to.
+    pub fn clear(&mut self, term: &Term<ZedListener>) {
+        let current_prompt = self.blocks.pop_back().filter(|block| !block.has_run());
+        self.blocks.clear();
+        if let Some(mut block) = current_prompt {
+            let cursor = HistoryPoint::cursor(term);
+            block.prompt_start = HistoryPoint {
+                line: cursor.line,
+                column: 0,
+            };
+            block.command_start = block.command_start.map(|command_start| HistoryPoint {
+                line: cursor.line,
+                column: command_start.column,
+            });
+            self.blocks.push_back(block);
+        }
+    }
+
+    /// Lines of the prompts of the commands that have run, counted from the top of the scrollback.
+    pub fn prompt_lines(&self) -> impl DoubleEndedIterator<Item = usize> + '_ {
+        self.blocks
+            .iter()
+            .filter(|block| block.has_run())
+            .map(|block| block.prompt_start.line)
+    }
+
+    /// Markers for the commands whose prompts are within the given grid lines.
+    pub fn command_markers(
+        &self,
+        term: &Term<ZedListener>,
+        lines: std::ops::RangeInclusive<Line>,
+    ) -> Vec<CommandMarker> {
+        self.blocks
+            .iter()
+            .filter(|block| block.has_run())
+            .filter_map(|block| {
+                let line = block.prompt_start.to_grid_line(term)?;
+                lines.contains(&line).then_some(CommandMarker {
+                    line,
+                    status: block.status,
+                })
+            })
+            .collect()
+    }
+
+    /// The text the command printed, up to the cursor if it is still running.
+    pub fn command_output(&self, block: &CommandBlock, term: &Term<ZedListener>) -> Option<String> {
+        let output_start = block.output_start?;
+        let output_end = block
+            .output_end
+            .unwrap_or_else(|| HistoryPoint::cursor(term));
+        let start = AlacPoint::new(output_start.to_grid_line(term)?, Column(0));
+        // The end is reported once the shell got back to a new line, which the next prompt
+        // may have already been printed on.
+        let end = AlacPoint::new(output_end.to_grid_line(term)? - 1, term.last_column());
+        if end < start {
+            return Some(String::new());
+        }
+        Some(term.bounds_to_string(start, end).trim_end().to_string())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ShellScript {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl ShellScript {
+    fn for_program(program: &str) -> Option<Self> {
+        let name = Path::new(program).file_name()?.to_str()?;
+        match name.strip_prefix('-').unwrap_or(name) {
+            "bash" => Some(Self::Bash),
+            "zsh" => Some(Self::Zsh),
+            "fish" => Some(Self::Fish),
+            _ => None,
+        }
+    }
+}
+
+const BASH_SCRIPT: &str = include_str!("shell_integration/zed.bash");
+const ZSH_SCRIPT: &str = include_str!("shell_integration/zed.zsh");
+const ZSH_ENV: &str = include_str!("shell_integration/zsh/.zshenv");
+const ZSH_PROFILE: &str = include_str!("shell_integration/zsh/.zprofile");
+const ZSH_RC: &str = include_str!("shell_integration/zsh/.zshrc");
+const FISH_SCRIPT: &str = include_str!("shell_integration/zed.fish");
+
+/// Writes the integration scripts once per process, returning the directory they were written to.
+fn scripts_dir() -> Option<&'static Path> {
+    static SCRIPTS_DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
+    SCRIPTS_DIR
+        .get_or_init(|| {
+            let dir = paths::temp_dir().join("shell_integration");
+            let files = [
+                ("zed.bash", BASH_SCRIPT),
+                ("zed.zsh", ZSH_SCRIPT),
+                ("zsh/.zshenv", ZSH_ENV),
+                ("zsh/.zprofile", ZSH_PROFILE),
+                ("zsh/.zshrc", ZSH_RC),
+                ("zed.fish", FISH_SCRIPT),
+            ];
+            let result = files.iter().try_for_each(|(name, contents)| {
+                let path = dir.join(name);
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(path, contents)
+            });
+            match result {
+                Ok(()) => Some(dir),
+                Err(error) => {
+                    log::error!("Failed to write the shell integration scripts: {error}");
+                    None
+                }
+            }
+        })
+        .as_deref()
+}
+
+/// Returns the program and arguments that start the shell with Zed's integration script loaded
+/// after the user's configuration, adding the variables the script needs to the environment.
+///
+/// Shells started with custom arguments are left alone, as are the ones without a script.
+pub(crate) fn integrated_shell(
+    shell: &Shell,
+    env: &mut HashMap<String, String>,
+) -> Option<(String, Vec<String>)> {
+    if !cfg!(unix) {
+        return None;
+    }
+    let (program, login) = match shell {
+        // Like Alacritty does, start the system shell as a login shell on macOS.
+        Shell::System => (util::shell::get_system_shell(), cfg!(target_os = "macos")),
+        Shell::Program(program) => (program.clone(), false),
+        Shell::WithArguments { .. } => return None,
+    };
+    let args = shell_integration_args(&program, login, env)?;
+    Some((program, args))
+}
+
+fn shell_integration_args(
+    program: &str,
+    login: bool,
+    env: &mut HashMap<String, String>,
+) -> Option<Vec<String>> {
+    let script = ShellScript::for_program(program)?;
+    let dir = scripts_dir()?;
+    env.insert("ZED_SHELL_INTEGRATION".to_string(), "1".to_string());
+    let mut args = Vec::new();
+    match script {
+        ShellScript::Bash => {
+            // Bash does not read `--init-file` in login shells, so the script loads the profile itself.
+            if login {
+                env.insert("ZED_SHELL_LOGIN".to_string(), "1".to_string());
+            }
+            args.push("--init-file".to_string());
+            args.push(dir.join("zed.bash").to_string_lossy().into_owned());
+        }
+        ShellScript::Zsh => {
+            let user_zdotdir = env
+                .get("ZDOTDIR")
+                .cloned()
+                .or_else(|| std::env::var("ZDOTDIR").ok())
+                .or_else(|| {
+                    util::paths::home_dir()
+                        .to_str()
+                        .map(|home_dir| home_dir.to_string())
+                });
+            if let Some(user_zdotdir) = user_zdotdir {
+                env.insert("ZED_USER_ZDOTDIR".to_string(), user_zdotdir);
+            }
+            env.insert(
+                "ZDOTDIR".to_string(),
+                dir.join("zsh").to_string_lossy().into_owned(),
+            );
+            env.insert(
+                "ZED_SHELL_INTEGRATION_SCRIPT".to_string(),
+                dir.join("zed.zsh").to_string_lossy().into_owned(),
+            );
+            if login {
+                args.push("-l".to_string());
+            }
+        }
+        ShellScript::Fish => {
+            if login {
+                args.push("-l".to_string());
+            }
+            args.push("--init-command".to_string());
+            args.push(format!(
+                "source {}",
+                util::shell::ShellKind::Fish.try_quote(&dir.join("zed.fish").to_string_lossy())?
+            ));
+        }
+    }
+    Some(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn scan(scanner: &mut ShellMarkerScanner, mut bytes: &[u8]) -> Vec<(usize, ShellMarker)> {
+        let mut markers = Vec::new();
+        let mut offset = 0;
+        while let Some((len, marker)) = scanner.next_marker(bytes) {
+            offset += len;
+            markers.push((offset, marker));
+            bytes = &bytes[len..];
+        }
+        markers
+    }
+
+    #[test]
+    fn test_scan_markers() {
+        let mut scanner = ShellMarkerScanner::default();
+        let output = b"\x1b]133;A\x07$ \x1b]133;B\x07ls\r\n\x1b]633;E;ls \\x3b echo \\\\\x1b\\\x1b]133;C\x07file\r\n\x1b]133;D;2\x07\x1b]0;title\x07\x1b]7;file://host/home/user/my%20dir\x07";
+        assert_eq!(
+            scan(&mut scanner, output),
+            vec![
+                (8, ShellMarker::PromptStart),
+                (18, ShellMarker::CommandStart),
+                (47, ShellMarker::CommandLine("ls ; echo \\".to_string())),
+                (55, ShellMarker::OutputStart),
+                (71, ShellMarker::CommandFinished { exit_code: Some(2) }),
+                (
+                    output.len(),
+                    ShellMarker::WorkingDirectory(PathBuf::from("/home/user/my dir"))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_markers_split_across_reads() {
+        let mut scanner = ShellMarkerScanner::default();
+        assert_eq!(scan(&mut scanner, b"output\x1b]13"), vec![]);
+        assert_eq!(scan(&mut scanner, b"3;D"), vec![]);
+        assert_eq!(
+            scan(&mut scanner, b";0\x1b"),
+            vec![],
+            "an escape inside the sequence may start its terminator"
+        );
+        assert_eq!(
+            scan(&mut scanner, b"\\\x1b]633;P;Cwd=/tmp\x07"),
+            vec![
+                (1, ShellMarker::CommandFinished { exit_code: Some(0) }),
+                (18, ShellMarker::WorkingDirectory(PathBuf::from("/tmp"))),
+            ]
+        );
+
+        // Other sequences, including cancelled ones, are skipped.
+        assert_eq!(
+            scan(
+                &mut scanner,
+                b"\x1b]133;A\x18\x1b[31mred\x1b]8;;https://zed.dev\x07link\x1b]133;X\x07"
+            ),
+            vec![]
+        );
+    }
+
+    /// A PTY that has all of its output to read at once.
+    struct FakePty {
+        output: io::Cursor<Vec<u8>>,
+        input: io::Sink,
+    }
+
+    impl EventedReadWrite for FakePty {
+        type Reader = io::Cursor<Vec<u8>>;
+        type Writer = io::Sink;
+
+        unsafe fn register(
+            &mut self,
+            _poll: &Arc<Poller>,
+            _interest: PollingEvent,
+            _mode: PollMode,
+        ) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn reregister(
+            &mut self,
+            _poll: &Arc<Poller>,
+            _interest: PollingEvent,
+            _mode: PollMode,
+        ) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn deregister(&mut self, _poll: &Arc<Poller>) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn reader(&mut self) -> &mut Self::Reader {
+            &mut self.output
+        }
+
+        fn writer(&mut self) -> &mut Self::Writer {
+            &mut self.input
+        }
+    }
+
+    #[test]
+    fn test_markers_recorded_where_they_are_parsed() {
+        use crate::pty_filter::FilteredPty;
+        use alacritty_terminal::{
+            term::{Config, test::TermSize},
+            vte::ansi::{Processor, StdSyncHandler},
+        };
+        use futures::channel::mpsc::unbounded;
+        use std::io::Read as _;
+
+        let (events_tx, _events_rx) = unbounded();
+        let term = Arc::new(FairMutex::new(Term::new(
+            Config::default(),
+            &TermSize::new(80, 24),
+            ZedListener(events_tx),
+        )));
+        let (markers_tx, mut markers_rx) = unbounded();
+        let mut pty = FilteredPty::new(
+            FakePty {
+                output: io::Cursor::new(
+                    b"output\r\n\x1b]133;A\x07$ \x1b]133;B\x07ls\r\n\x1b]133;C\x07file\r\n\x1b]133;D;0\x07\x1b]133;A\x07$ "
+                        .to_vec(),
+                ),
+                input: io::sink(),
+            },
+            ShellIntegrationFilter::new(term.clone(), markers_tx),
+        );
+        let poll = Arc::new(Poller::new().unwrap());
+        let interest = PollingEvent::readable(0);
+        unsafe { pty.register(&poll, interest, PollMode::Level) }.unwrap();
+
+        // Read and parse the output like the event loop does, until there is nothing left to read.
+        let mut processor = Processor::<StdSyncHandler>::new();
+        let mut buf = [0; 1024];
+        let mut read_any = true;
+        while read_any {
+            read_any = false;
+            loop {
+                match pty.reader().read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(len) => {
+                        processor.advance(&mut *term.lock(), &buf[..len]);
+                        read_any = true;
+                    }
+                    Err(error) if error.kind() == io::ErrorKind::WouldBlock => break,
+                    Err(error) => panic!("failed to read: {error}"),
+                }
+            }
+            pty.reregister(&poll, interest, PollMode::Level).unwrap();
+        }
+        pty.deregister(&poll).unwrap();
+
+        let point = |line, column| HistoryPoint { line, column };
+        assert_eq!(
+            std::iter::from_fn(|| markers_rx.try_next().ok().flatten()).collect::<Vec<_>>(),
+            vec![
+                (ShellMarker::PromptStart, point(1, 0)),
+                (ShellMarker::CommandStart, point(1, 2)),
+                (ShellMarker::OutputStart, point(2, 0)),
+                (
+                    ShellMarker::CommandFinished { exit_code: Some(0) },
+                    point(3, 0)
+                ),
+                (ShellMarker::PromptStart, point(3, 0)),
+            ]
+        );
+        assert_eq!(
+            term.lock().grid().cursor.point,
+            AlacPoint::new(Line(3), Column(2)),
+            "the output after the last marker should be read too"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_zsh_integration() {
+        use std::io::Write as _;
+        use std::process::{Command, Stdio};
+
+        let user_zdotdir =
+            std::env::temp_dir().join(format!("zed-zsh-integration-{}", std::process::id()));
+        std::fs::create_dir_all(&user_zdotdir).unwrap();
+        for (name, contents) in [
+            (".zshenv", "export ZED_TEST_ZSHENV=1\n"),
+            (".zprofile", "export ZED_TEST_ZPROFILE=1\n"),
+            (".zshrc", "export ZED_TEST_ZSHRC=1\nPS1='$ '\n"),
+        ] {
+            std::fs::write(user_zdotdir.join(name), contents).unwrap();
+        }
+
+        let mut env = HashMap::default();
+        env.insert(
+            "ZDOTDIR".to_string(),
+            user_zdotdir.to_string_lossy().into_owned(),
+        );
+        let args = shell_integration_args("zsh", true, &mut env).unwrap();
+        let child = Command::new("zsh")
+            .args(&args)
+            .arg("-i")
+            .env_remove("ZDOTDIR")
+            .envs(&env)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn();
+        let mut child = match child {
+            Ok(child) => child,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                eprintln!("zsh is not installed, skipping the test");
+                std::fs::remove_dir_all(&user_zdotdir).ok();
+                return;
+            }
+            Err(error) => panic!("failed to start zsh: {error}"),
+        };
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(
+                b"print -r -- \"user files: $ZED_TEST_ZSHENV$ZED_TEST_ZPROFILE$ZED_TEST_ZSHRC, ZDOTDIR: $ZDOTDIR\"\nexit\n",
+            )
+            .unwrap();
+        let output = child.wait_with_output().unwrap();
+        std::fs::remove_dir_all(&user_zdotdir).ok();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        assert!(
+            stdout.contains(&format!(
+                "user files: 111, ZDOTDIR: {}",
+                user_zdotdir.to_string_lossy()
+            )),
+            "the user's files should be loaded, and their ZDOTDIR restored: {stdout:?}"
+        );
+        for marker in [
+            "\x1b]133;A\x07",
+            "\x1b]633;E;",
+            "\x1b]133;C\x07",
+            "\x1b]133;D;0\x07",
+        ] {
+            assert!(
+                stdout.contains(marker),
+                "{marker:?} should be printed by the integration script: {stdout:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,74 @@
+# Zed shell integration for bash, loaded with `--init-file` in place of ~/.bashrc.
+# Marks prompts, commands and their output with OSC 133 sequences.
+
+if [ -n "$ZED_SHELL_LOGIN" ]; then
+    unset ZED_SHELL_LOGIN
+    # Bash ignores `--init-file` in login shells, so load the profile the way a login shell would.
+    [ -r /etc/profile ] && . /etc/profile
+    if [ -r ~/.bash_profile ]; then
+        . ~/.bash_profile
+    elif [ -r ~/.bash_login ]; then
+        . ~/.bash_login
+    elif [ -r ~/.profile ]; then
+        . ~/.profile
+    fi
+else
+    [ -r ~/.bashrc ] && . ~/.bashrc
+fi
+
+if [ -n "$__zed_shell_integration_loaded" ] || [[ $- != *i* ]]; then
+    return 0 2>/dev/null
+fi
+__zed_shell_integration_loaded=1
+unset ZED_SHELL_INTEGRATION
+
+__zed_command_running=
+# Set until the first prompt is shown, so that the commands run before it are not marked.
+__zed_in_precmd=1
+
+__zed_escape_value() {
+    local value="${1//\\/\\\\}"
+    value="${value//;/\\x3b}"
+    value="${value//$'\n'/\\x0a}"
+    printf '%s' "$value"
+}
+
+__zed_preexec() {
+    if [ -n "$__zed_in_precmd" ] || [ -n "$__zed_command_running" ] || [ -n "$COMP_LINE" ]; then
+        return
+    fi
+    # An empty command line runs the prompt command straight away.
+    if [[ $BASH_COMMAND == __zed_precmd* ]]; then
+        return
+    fi
+    __zed_command_running=1
+    local command
+    command="$(HISTTIMEFORMAT= builtin history 1)"
+    if [[ $command =~ ^[[:space:]]*[0-9]+[*[:space:]]*(.*)$ ]]; then
+        command="${BASH_REMATCH[1]}"
+    fi
+    printf '\e]633;E;%s\a\e]133;C\a' "$(__zed_escape_value "$command")"
+}
+
+__zed_precmd() {
+    local status=$?
+    __zed_in_precmd=1
+    if [ -n "$__zed_command_running" ]; then
+        printf '\e]133;D;%s\a' "$status"
+        __zed_command_running=
+    fi
+    printf '\e]633;P;Cwd=%s\a\e]133;A\a' "$(__zed_escape_value "$PWD")"
+    return $status
+}
+
+__zed_prompt_end() {
+    local status=$?
+    if [[ $PS1 != *'\e]133;B\a'* ]]; then
+        PS1="$PS1"'\[\e]133;B\a\]'
+    fi
+    __zed_in_precmd=
+    return $status
+}
+
+trap '__zed_preexec' DEBUG
+PROMPT_COMMAND="__zed_precmd${PROMPT_COMMAND:+; $PROMPT_COMMAND}; __zed_prompt_end"
@@ -0,0 +1,37 @@
+# Zed shell integration for fish, loaded with `--init-command` before the user's configuration.
+# Marks prompts, commands and their output with OSC 133 sequences.
+
+status is-interactive; or exit
+set -q __zed_shell_integration_loaded; and exit
+set -g __zed_shell_integration_loaded 1
+set -e ZED_SHELL_INTEGRATION
+
+function __zed_escape_value
+    string replace --all '\\' '\\\\' -- $argv[1] | string replace --all ';' '\\x3b' | string join '\\x0a'
+end
+
+function __zed_preexec --on-event fish_preexec
+    printf '\e]633;E;%s\a\e]133;C\a' (__zed_escape_value "$argv[1]")
+end
+
+function __zed_postexec --on-event fish_postexec
+    printf '\e]133;D;%s\a' $status
+end
+
+function __zed_report_cwd --on-variable PWD
+    printf '\e]633;P;Cwd=%s\a' (__zed_escape_value "$PWD")
+end
+__zed_report_cwd
+
+# The user's configuration is loaded after this script, and may define its own prompt,
+# so the prompt is only wrapped before it is first shown.
+function __zed_wrap_prompt --on-event fish_prompt
+    functions -e __zed_wrap_prompt
+    functions -q fish_prompt; or return
+    functions -c fish_prompt __zed_user_prompt
+    function fish_prompt
+        printf '\e]133;A\a'
+        __zed_user_prompt
+        printf '\e]133;B\a'
+    end
+end
@@ -2,7 +2,11 @@ pub mod mappings;
 
 pub use alacritty_terminal;
 
+mod asciicast;
+mod images;
+mod pty_filter;
 mod pty_info;
+mod shell_integration;
 mod terminal_hyperlinks;
 pub mod terminal_settings;
 
@@ -23,9 +27,12 @@ use alacritty_terminal::{
     vi_mode::{ViModeCursor, ViMotion},
     vte::ansi::{
         ClearMode, CursorStyle as AlacCursorStyle, Handler, NamedPrivateMode, PrivateMode,
+        Processor, StdSyncHandler,
     },
 };
 use anyhow::{Context as _, Result, bail};
+pub use asciicast::Asciicast;
+use asciicast::{AsciicastRecorder, RecordingFilter};
 use log::trace;
 
 use futures::{
@@ -41,9 +48,15 @@ use mappings::mouse::{
 
 use collections::{HashMap, VecDeque};
 use futures::StreamExt;
+pub use images::ImagePlacement;
+use images::{ImageFilter, ImageStore};
+use parking_lot::Mutex;
+use pty_filter::FilteredPty;
 use pty_info::{ProcessIdGetter, PtyProcessInfo};
 use serde::{Deserialize, Serialize};
 use settings::Settings;
+pub use shell_integration::{CommandBlock, CommandMarker, CommandStatus};
+use shell_integration::{HistoryPoint, ShellIntegration, ShellIntegrationFilter, ShellMarker};
 use smol::channel::{Receiver, Sender};
 use task::{HideStrategy, Shell, SpawnInTerminal};
 use terminal_hyperlinks::RegexSearches;
@@ -59,7 +72,7 @@ use std::{
     cmp::{self, min},
     fmt::Display,
     ops::{Deref, RangeInclusive},
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::ExitStatus,
     sync::Arc,
     time::{Duration, Instant},
@@ -69,7 +82,7 @@ use thiserror::Error;
 use gpui::{
     App, AppContext as _, BackgroundExecutor, Bounds, ClipboardItem, Context, EventEmitter, Hsla,
     Keystroke, Modifiers, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Point,
-    Rgba, ScrollWheelEvent, Size, Task, TouchPhase, Window, actions, black, px,
+    Rgba, ScrollWheelEvent, Size, Subscription, Task, TouchPhase, Window, actions, black, px,
 };
 
 use crate::mappings::{colors::to_alac_rgb, keys::to_esc_str};
@@ -107,6 +120,15 @@ actions!(
         ToggleViMode,
         /// Selects all text in the terminal.
         SelectAll,
+        /// Scrolls to the prompt of the previous command.
+        ScrollToPreviousPrompt,
+        /// Scrolls to the prompt of the next command.
+        ScrollToNextPrompt,
+        /// Copies the output of the last command run in the shell to the clipboard.
+        CopyLastCommandOutput,
+        /// Starts recording the terminal's output, or stops and saves the recording as an
+        /// asciicast file.
+        ToggleRecording,
     ]
 );
 
@@ -340,6 +362,7 @@ pub const MAX_SCROLL_HISTORY_LINES: usize = 100_000;
 pub struct TerminalBuilder {
     terminal: Terminal,
     events_rx: UnboundedReceiver<AlacTermEvent>,
+    shell_markers_rx: Option<UnboundedReceiver<(ShellMarker, HistoryPoint)>>,
 }
 
 impl TerminalBuilder {
@@ -413,13 +436,20 @@ impl TerminalBuilder {
             },
             child_exited: None,
             event_loop_task: Task::ready(Ok(())),
+            shell_integration: ShellIntegration::default(),
+            shell_markers_task: Task::ready(Ok(())),
+            images: Arc::default(),
+            recorder: Arc::default(),
+            output_processor: Processor::new(),
             background_executor: background_executor.clone(),
             path_style,
+            persistent_session: None,
         };
 
         Ok(TerminalBuilder {
             terminal,
             events_rx,
+            shell_markers_rx: None,
         })
     }
 
@@ -439,9 +469,15 @@ impl TerminalBuilder {
         cx: &App,
         activation_script: Vec<String>,
         path_style: PathStyle,
+        persistent_session: Option<String>,
     ) -> Task<Result<TerminalBuilder>> {
         let version = release_channel::AppVersion::global(cx);
         let background_executor = cx.background_executor().clone();
+        let enable_shell_integration = task.is_none()
+            && !is_remote_terminal
+            && TerminalSettings::try_get(cx).is_some_and(|settings| settings.shell_integration);
+        let persistent_session_timeout =
+            TerminalSettings::try_get(cx).and_then(|settings| settings.persistent_session_timeout);
         let fut = async move {
             // Remove SHLVL so the spawned shell initializes it to 1, matching
             // the behavior of standalone terminal emulators like iTerm2/Kitty/Alacritty.
@@ -480,7 +516,16 @@ impl TerminalBuilder {
                 }
             }
 
+            // Only the spawned shell gets the integration's variables, the template keeps
+            // the user's environment so that cloned terminals set the integration up anew.
+            let mut pty_env = env.clone();
+            let integrated_shell = enable_shell_integration
+                .then(|| shell_integration::integrated_shell(&shell, &mut pty_env))
+                .flatten();
+
             let shell_params = match shell.clone() {
+                _ if integrated_shell.is_some() => integrated_shell
+                    .map(|(program, args)| ShellParams::new(program, Some(args), None)),
                 Shell::System => {
                     if cfg!(windows) {
                         Some(ShellParams::new(
@@ -499,6 +544,47 @@ impl TerminalBuilder {
                     title_override,
                 } => Some(ShellParams::new(program, Some(args), title_override)),
             };
+            // The shell of a persistent session runs in the terminal host, and the terminal
+            // runs the client attaching to it instead. Remote sessions are attached to by the
+            // command the project spawns over the remote connection.
+            let shell_params = match persistent_session.as_deref() {
+                Some(session_id) if !is_remote_terminal => {
+                    let (program, args, title_override) = match shell_params {
+                        Some(params) => (
+                            params.program,
+                            params.args.unwrap_or_default(),
+                            params.title_override,
+                        ),
+                        None => (
+                            util::shell::get_system_shell(),
+                            if cfg!(target_os = "macos") {
+                                vec!["-l".to_string()]
+                            } else {
+                                Vec::new()
+                            },
+                            None,
+                        ),
+                    };
+                    // The attach client would otherwise give the terminal its title.
+                    let title_override = title_override.or_else(|| {
+                        Path::new(&program)
+                            .file_name()
+                            .map(|name| name.to_string_lossy().into_owned())
+                    });
+                    let zed = std::env::current_exe().context("locating the Zed binary")?;
+                    Some(ShellParams::new(
+                        zed.to_string_lossy().into_owned(),
+                        Some(terminal_host::attach_args(
+                            session_id,
+                            persistent_session_timeout,
+                            program,
+                            args,
+                        )),
+                        title_override,
+                    ))
+                }
+                _ => shell_params,
+            };
             let terminal_title_override =
                 shell_params.as_ref().and_then(|e| e.title_override.clone());
 
@@ -530,7 +616,7 @@ impl TerminalBuilder {
                     shell: alac_shell,
                     working_directory: working_directory.clone(),
                     drain_on_exit: true,
-                    env: env.clone().into_iter().collect(),
+                    env: pty_env.into_iter().collect(),
                     #[cfg(windows)]
                     escape_args: shell_kind.tty_escape_args(),
                 }
@@ -585,6 +671,18 @@ impl TerminalBuilder {
             let term = Arc::new(FairMutex::new(term));
 
             let pty_info = PtyProcessInfo::new(&pty);
+            let (shell_markers_tx, shell_markers_rx) = unbounded();
+            let recorder = Arc::<Mutex<Option<AsciicastRecorder>>>::default();
+            let pty = FilteredPty::new(pty, RecordingFilter::new(recorder.clone()));
+            let images = Arc::<Mutex<ImageStore>>::default();
+            let pty = FilteredPty::new(
+                pty,
+                ImageFilter::new(images.clone(), TerminalBounds::default().into()),
+            );
+            let pty = FilteredPty::new(
+                pty,
+                ShellIntegrationFilter::new(term.clone(), shell_markers_tx),
+            );
 
             //And connect them together
             let event_loop = EventLoop::new(
@@ -644,8 +742,18 @@ impl TerminalBuilder {
                 },
                 child_exited: None,
                 event_loop_task: Task::ready(Ok(())),
+                shell_integration: ShellIntegration::default(),
+                shell_markers_task: Task::ready(Ok(())),
+                images,
+                recorder,
+                output_processor: Processor::new(),
                 background_executor,
                 path_style,
+                persistent_session: persistent_session.map(|id| PersistentSession {
+                    id,
+                    keep_running: false,
+                    _app_quit_subscription: None,
+                }),
             };
 
             if !activation_script.is_empty() && no_task {
@@ -672,6 +780,7 @@ impl TerminalBuilder {
             Ok(TerminalBuilder {
                 terminal,
                 events_rx,
+                shell_markers_rx: Some(shell_markers_rx),
             })
         };
         // the thread we spawn things on has an effect on signal handling
@@ -743,6 +852,24 @@ impl TerminalBuilder {
             }
             anyhow::Ok(())
         });
+        if let Some(persistent_session) = &mut self.terminal.persistent_session {
+            persistent_session._app_quit_subscription = Some(cx.on_app_quit(|terminal, _| {
+                if let Some(persistent_session) = &mut terminal.persistent_session {
+                    persistent_session.keep_running = true;
+                }
+                async {}
+            }));
+        }
+        if let Some(mut shell_markers_rx) = self.shell_markers_rx {
+            self.terminal.shell_markers_task = cx.spawn(async move |terminal, cx| {
+                while let Some((marker, cursor)) = shell_markers_rx.next().await {
+                    terminal.update(cx, |terminal, cx| {
+                        terminal.process_shell_marker(marker, cursor, cx);
+                    })?;
+                }
+                anyhow::Ok(())
+            });
+        }
         self.terminal
     }
 
@@ -794,6 +921,10 @@ pub struct TerminalContent {
     pub last_hovered_word: Option<HoveredWord>,
     pub scrolled_to_top: bool,
     pub scrolled_to_bottom: bool,
+    /// The exit statuses of the commands whose prompts are visible.
+    pub command_markers: Vec<CommandMarker>,
+    /// The images covering the visible cells.
+    pub images: Vec<ImagePlacement>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -820,6 +951,8 @@ impl Default for TerminalContent {
             last_hovered_word: None,
             scrolled_to_top: false,
             scrolled_to_bottom: false,
+            command_markers: Vec::new(),
+            images: Vec::new(),
         }
     }
 }
@@ -868,8 +1001,24 @@ pub struct Terminal {
     activation_script: Vec<String>,
     child_exited: Option<ExitStatus>,
     event_loop_task: Task<Result<(), anyhow::Error>>,
+    shell_integration: ShellIntegration,
+    shell_markers_task: Task<Result<(), anyhow::Error>>,
+    images: Arc<Mutex<ImageStore>>,
+    recorder: Arc<Mutex<Option<AsciicastRecorder>>>,
+    /// Parses the output written to display-only terminals, keeping the state of sequences split
+    /// across writes.
+    output_processor: Processor<StdSyncHandler>,
     background_executor: BackgroundExecutor,
     path_style: PathStyle,
+    persistent_session: Option<PersistentSession>,
+}
+
+struct PersistentSession {
+    id: String,
+    /// Set when Zed quits, so that the session keeps running to be reattached to on the next
+    /// start. Otherwise, dropping the terminal ends the session, as closing it does.
+    keep_running: bool,
+    _app_quit_subscription: Option<Subscription>,
 }
 
 struct CopyTemplate {
@@ -956,7 +1105,14 @@ impl Terminal {
                     .into_bytes(),
                 )
             }
-            AlacTermEvent::PtyWrite(out) => self.write_to_pty(out.into_bytes()),
+            AlacTermEvent::PtyWrite(out) => {
+                let out = if out == images::PRIMARY_DEVICE_ATTRIBUTES {
+                    images::SIXEL_DEVICE_ATTRIBUTES.to_string()
+                } else {
+                    out
+                };
+                self.write_to_pty(out.into_bytes())
+            }
             AlacTermEvent::TextAreaSizeRequest(format) => {
                 self.write_to_pty(format(self.last_content.terminal_bounds.into()).into_bytes())
             }
@@ -998,6 +1154,18 @@ impl Terminal {
         }
     }
 
+    fn process_shell_marker(
+        &mut self,
+        marker: ShellMarker,
+        cursor: HistoryPoint,
+        cx: &mut Context<Self>,
+    ) {
+        let term = self.term.lock();
+        self.shell_integration.handle_marker(marker, cursor, &term);
+        drop(term);
+        cx.emit(Event::Wakeup);
+    }
+
     pub fn selection_started(&self) -> bool {
         self.selection_phase == SelectionPhase::Selecting
     }
@@ -1061,6 +1229,8 @@ impl Terminal {
                     term.grid_mut().reset_region((new_cursor.line + 1)..);
                 }
 
+                self.shell_integration.clear(term);
+
                 cx.emit(Event::Wakeup);
             }
             InternalEvent::Scroll(scroll) => {
@@ -1303,17 +1473,61 @@ impl Terminal {
             converted.push(byte);
             prev_byte = byte;
         }
+        self.process_output(&converted, cx);
+    }
 
-        let mut processor = alacritty_terminal::vte::ansi::Processor::<
-            alacritty_terminal::vte::ansi::StdSyncHandler,
-        >::new();
+    /// Writes output recorded from a process running in a terminal, as when replaying a
+    /// recording. Unlike [`Self::write_output`], line endings are kept as they are.
+    pub fn write_recorded_output(&mut self, bytes: &[u8], cx: &mut Context<Self>) {
+        self.process_output(bytes, cx);
+    }
+
+    fn process_output(&mut self, bytes: &[u8], cx: &mut Context<Self>) {
         {
             let mut term = self.term.lock();
-            processor.advance(&mut *term, &converted);
+            // Parse the output up to each shell integration marker, to record the markers exactly where they are.
+            let mut remaining = bytes;
+            while let Some((len, marker)) =
+                self.shell_integration.output_scanner.next_marker(remaining)
+            {
+                self.output_processor.advance(&mut *term, &remaining[..len]);
+                let cursor = HistoryPoint::cursor(&term);
+                self.shell_integration.handle_marker(marker, cursor, &term);
+                remaining = &remaining[len..];
+            }
+            self.output_processor.advance(&mut *term, remaining);
         }
         cx.emit(Event::Wakeup);
     }
 
+    /// Starts recording the output of the terminal's process, along with its resizes. Terminals
+    /// without a process cannot be recorded.
+    pub fn start_recording(&mut self) {
+        if !self.can_record() {
+            return;
+        }
+        let bounds = self.last_content.terminal_bounds;
+        let title = self.title(false);
+        *self.recorder.lock() = Some(AsciicastRecorder::new(
+            bounds.num_columns(),
+            bounds.num_lines(),
+            Some(&title),
+        ));
+    }
+
+    pub fn can_record(&self) -> bool {
+        matches!(self.terminal_type, TerminalType::Pty { .. })
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorder.lock().is_some()
+    }
+
+    /// Stops recording, returning the contents of the recording's asciicast file.
+    pub fn stop_recording(&mut self) -> Option<String> {
+        Some(self.recorder.lock().take()?.finish())
+    }
+
     pub fn total_lines(&self) -> usize {
         self.term.lock_unfair().total_lines()
     }
@@ -1415,6 +1629,46 @@ impl Terminal {
             .push_back(InternalEvent::Scroll(AlacScroll::Bottom));
     }
 
+    pub fn scroll_to_previous_prompt(&mut self) {
+        let term = self.term.lock_unfair();
+        let top_line = term.history_size() - term.grid().display_offset();
+        let prompt_line = self
+            .shell_integration
+            .prompt_lines()
+            .rev()
+            .find(|line| *line < top_line);
+        drop(term);
+        if let Some(prompt_line) = prompt_line {
+            self.scroll_to_history_line(prompt_line);
+        }
+    }
+
+    pub fn scroll_to_next_prompt(&mut self) {
+        let term = self.term.lock_unfair();
+        let top_line = term.history_size() - term.grid().display_offset();
+        let prompt_line = self
+            .shell_integration
+            .prompt_lines()
+            .find(|line| *line > top_line);
+        drop(term);
+        match prompt_line {
+            Some(prompt_line) => self.scroll_to_history_line(prompt_line),
+            None => self.scroll_to_bottom(),
+        }
+    }
+
+    /// Scrolls so that the line, counted from the top of the scrollback, is at the top of the viewport.
+    fn scroll_to_history_line(&mut self, line: usize) {
+        let term = self.term.lock_unfair();
+        let display_offset = term.grid().display_offset() as i32;
+        let target_offset = term.history_size().saturating_sub(line) as i32;
+        drop(term);
+        self.events
+            .push_back(InternalEvent::Scroll(AlacScroll::Delta(
+                target_offset - display_offset,
+            )));
+    }
+
     pub fn scrolled_to_top(&self) -> bool {
         self.last_content.scrolled_to_top
     }
@@ -1603,6 +1857,16 @@ impl Terminal {
         }
 
         self.last_content = Self::make_content(&terminal, &self.last_content);
+        let display_offset = self.last_content.display_offset as i32;
+        let visible_lines =
+            Line(-display_offset)..=Line(terminal.screen_lines() as i32 - 1 - display_offset);
+        self.last_content.command_markers = self
+            .shell_integration
+            .command_markers(&terminal, visible_lines);
+        self.last_content.images = self
+            .images
+            .lock()
+            .placements(&terminal, &mut self.last_content.cells);
     }
 
     fn make_content(term: &Term<ZedListener>, last_content: &TerminalContent) -> TerminalContent {
@@ -1635,6 +1899,8 @@ impl Terminal {
             last_hovered_word: last_content.last_hovered_word.clone(),
             scrolled_to_top: content.display_offset == term.history_size(),
             scrolled_to_bottom: content.display_offset == 0,
+            command_markers: Vec::new(),
+            images: Vec::new(),
         }
     }
 
@@ -1645,6 +1911,29 @@ impl Terminal {
         term.bounds_to_string(start, end)
     }
 
+    /// The commands run in the shell, oldest first, if the shell reports them.
+    pub fn command_blocks(&self) -> Vec<CommandBlock> {
+        self.shell_integration.blocks().cloned().collect()
+    }
+
+    /// The text printed by a command, if its lines are still in the terminal.
+    pub fn command_output(&self, block: &CommandBlock) -> Option<String> {
+        let term = self.term.lock_unfair();
+        self.shell_integration.command_output(block, &term)
+    }
+
+    /// The last command run in the shell, along with the text it printed.
+    pub fn last_command_output(&self) -> Option<(CommandBlock, String)> {
+        let term = self.term.lock_unfair();
+        let block = self
+            .shell_integration
+            .blocks()
+            .rev()
+            .find(|block| block.has_run())?;
+        let output = self.shell_integration.command_output(block, &term)?;
+        Some((block.clone(), output))
+    }
+
     pub fn last_n_non_empty_lines(&self, n: usize) -> Vec<String> {
         let term = self.term.clone();
         let terminal = term.lock_unfair();
@@ -2081,13 +2370,21 @@ impl Terminal {
     }
 
     pub fn working_directory(&self) -> Option<PathBuf> {
+        let reported_working_directory = self
+            .shell_integration
+            .working_directory()
+            .map(|path| path.to_path_buf());
         if self.is_remote_terminal {
-            // We can't yet reliably detect the working directory of a shell on the
-            // SSH host. Until we can do that, it doesn't make sense to display
-            // the working directory on the client and persist that.
-            None
+            // The processes on the SSH host can't be inspected, so only a shell reporting
+            // its working directory tells where it is.
+            reported_working_directory
+        } else if self.persistent_session.is_some() {
+            // The process connected to the PTY is the attach client, which stays where the
+            // session was started.
+            reported_working_directory.or_else(|| self.client_side_working_directory())
         } else {
             self.client_side_working_directory()
+                .or(reported_working_directory)
         }
     }
 
@@ -2295,8 +2592,16 @@ impl Terminal {
             cx,
             self.activation_script.clone(),
             self.path_style,
+            None,
         )
     }
+
+    /// The id of the persistent session the terminal is attached to, if any.
+    pub fn persistent_session(&self) -> Option<&str> {
+        self.persistent_session
+            .as_ref()
+            .map(|session| session.id.as_str())
+    }
 }
 
 // Helper function to convert a grid row to a string
@@ -2389,12 +2694,30 @@ impl Drop for Terminal {
         if let TerminalType::Pty { pty_tx, info } =
             std::mem::replace(&mut self.terminal_type, TerminalType::DisplayOnly)
         {
-            pty_tx.0.send(Msg::Shutdown).ok();
+            // Closing the terminal ends its persistent session. Shutting the event loop down
+            // right away would drop the request before it is written to the attach client.
+            let end_session = self
+                .persistent_session
+                .as_ref()
+                .is_some_and(|session| !session.keep_running);
+            if end_session {
+                pty_tx
+                    .0
+                    .send(Msg::Input(Cow::Borrowed(
+                        terminal_host::END_SESSION_SEQUENCE,
+                    )))
+                    .ok();
+            } else {
+                pty_tx.0.send(Msg::Shutdown).ok();
+            }
 
             let timer = self.background_executor.timer(Duration::from_millis(100));
             self.background_executor
                 .spawn(async move {
                     timer.await;
+                    if end_session {
+                        pty_tx.0.send(Msg::Shutdown).ok();
+                    }
                     info.kill_child_process();
                 })
                 .detach();
@@ -2582,6 +2905,7 @@ mod tests {
                     cx,
                     vec![],
                     PathStyle::local(),
+                    None,
                 )
             })
             .await
@@ -2728,6 +3052,7 @@ mod tests {
                     cx,
                     Vec::new(),
                     PathStyle::local(),
+                    None,
                 )
             })
             .await
@@ -2804,6 +3129,7 @@ mod tests {
                     cx,
                     Vec::new(),
                     PathStyle::local(),
+                    None,
                 )
             })
             .await
@@ -3071,6 +3397,66 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_write_output_command_blocks(cx: &mut TestAppContext) {
+        let terminal = cx.new(|cx| {
+            TerminalBuilder::new_display_only(
+                CursorShape::default(),
+                AlternateScroll::On,
+                None,
+                0,
+                cx.background_executor(),
+                PathStyle::local(),
+            )
+            .unwrap()
+            .subscribe(cx)
+        });
+
+        terminal.update(cx, |terminal, cx| {
+            terminal.write_output(b"\x1b]7;file://host/tmp/project\x07", cx);
+            terminal.write_output(
+                b"\x1b]133;A\x07$ \x1b]133;B\x07echo hi\n\x1b]133;C\x07hi\nthere\n",
+                cx,
+            );
+            terminal.write_output(b"\x1b]133;D;0\x07\x1b]133;A\x07$ \x1b]133;B\x07false\n", cx);
+            terminal.write_output(b"\x1b]133;C\x07\x1b]133;D;1\x07\x1b]133;A\x07$ ", cx);
+        });
+
+        terminal.update(cx, |terminal, _| {
+            let blocks = terminal.command_blocks();
+            assert_eq!(blocks.len(), 3);
+            assert_eq!(
+                blocks[0].status(),
+                CommandStatus::Finished { exit_code: Some(0) }
+            );
+            assert_eq!(
+                blocks[0].working_directory(),
+                Some(PathBuf::from("/tmp/project").as_path())
+            );
+            assert_eq!(
+                terminal.command_output(&blocks[0]).as_deref(),
+                Some("hi\nthere")
+            );
+            assert_eq!(
+                blocks[1].status(),
+                CommandStatus::Finished { exit_code: Some(1) }
+            );
+            assert_eq!(blocks[2].status(), CommandStatus::Running);
+            assert!(!blocks[2].has_run(), "the last prompt is waiting for input");
+
+            let (last_block, output) = terminal.last_command_output().unwrap();
+            assert_eq!(
+                last_block.status(),
+                CommandStatus::Finished { exit_code: Some(1) }
+            );
+            assert_eq!(output, "");
+            assert_eq!(
+                terminal.working_directory(),
+                Some(PathBuf::from("/tmp/project"))
+            );
+        });
+    }
+
     #[gpui::test]
     async fn test_write_output_preserves_bare_cr(cx: &mut TestAppContext) {
         let terminal = cx.new(|cx| {
@@ -3292,6 +3678,7 @@ mod tests {
                         cx,
                         vec![],
                         PathStyle::local(),
+                        None,
                     )
                 })
                 .await
@@ -5,6 +5,7 @@ use collections::HashMap;
 use gpui::{FontFallbacks, FontFeatures, FontWeight, Pixels, px};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 pub use settings::AlternateScroll;
 
@@ -38,6 +39,10 @@ pub struct TerminalSettings {
     pub option_as_meta: bool,
     pub copy_on_select: bool,
     pub keep_selection_on_copy: bool,
+    pub shell_integration: bool,
+    pub persistent_sessions: bool,
+    /// How long a persistent session keeps running while detached, if not indefinitely.
+    pub persistent_session_timeout: Option<Duration>,
     pub button: bool,
     pub dock: TerminalDockPosition,
     pub default_width: Pixels,
@@ -105,6 +110,13 @@ impl settings::Settings for TerminalSettings {
             option_as_meta: user_content.option_as_meta.unwrap(),
             copy_on_select: user_content.copy_on_select.unwrap(),
             keep_selection_on_copy: user_content.keep_selection_on_copy.unwrap(),
+            shell_integration: user_content.shell_integration.unwrap(),
+            persistent_sessions: user_content.persistent_sessions.unwrap(),
+            persistent_session_timeout: Some(
+                user_content.persistent_session_timeout_minutes.unwrap(),
+            )
+            .filter(|minutes| *minutes > 0)
+            .map(|minutes| Duration::from_secs(minutes * 60)),
             button: user_content.button.unwrap(),
             dock: user_content.dock.unwrap(),
             default_width: px(user_content.default_width.unwrap()),
@@ -0,0 +1,256 @@
+use std::{
+    env,
+    io::{self, Read, Write},
+    os::{fd::RawFd, unix::net::UnixStream},
+    path::Path,
+    process::{Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+use anyhow::{Context as _, Result, anyhow, bail};
+
+use crate::{
+    END_SESSION_SEQUENCE, HOST_FLAG,
+    protocol::{AttachRequest, Frame, PROTOCOL_VERSION, WindowSize},
+    socket_path,
+};
+
+/// Terminals report resizes with SIGWINCH, which the client polls for instead of installing a
+/// signal handler.
+const RESIZE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+const HOST_STARTUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Runs the attach client with the arguments following [`crate::ATTACH_FLAG`]: the session id,
+/// the seconds to keep the session running while detached (or 0 to keep it indefinitely), then
+/// the program and arguments to start the session with. Returns the exit code of the session's
+/// program.
+pub fn attach(args: &[String]) -> i32 {
+    match run_client(args) {
+        Ok(exit_code) => exit_code,
+        Err(error) => {
+            eprintln!("zed: failed to attach to the terminal session: {error:#}");
+            1
+        }
+    }
+}
+
+fn run_client(args: &[String]) -> Result<i32> {
+    let [session_id, detached_timeout_secs, program, args @ ..] = args else {
+        bail!("expected a session id, a detached timeout and a program to run");
+    };
+    let detached_timeout_secs = detached_timeout_secs
+        .parse::<u64>()
+        .with_context(|| format!("invalid detached timeout {detached_timeout_secs:?}"))?;
+    let mut stream = connect_or_start_host(&socket_path())?;
+
+    let size = window_size(libc::STDIN_FILENO).unwrap_or(WindowSize {
+        rows: 24,
+        columns: 80,
+    });
+    Frame::Attach(AttachRequest {
+        version: PROTOCOL_VERSION,
+        session_id: session_id.clone(),
+        program: program.clone(),
+        args: args.to_vec(),
+        working_directory: env::current_dir().ok(),
+        env: env::vars().collect(),
+        size,
+        detached_timeout_secs: Some(detached_timeout_secs).filter(|secs| *secs > 0),
+    })
+    .write_to(&mut stream)?;
+
+    // Pass everything through to the session, including the keys that would otherwise signal
+    // the client.
+    let _raw_mode = RawMode::enable(libc::STDIN_FILENO);
+
+    let mut input_stream = stream.try_clone()?;
+    thread::spawn(move || {
+        if let Err(error) = forward_input(&mut input_stream) {
+            log::error!("failed to forward input to the terminal session: {error}");
+        }
+    });
+    let mut resize_stream = stream.try_clone()?;
+    thread::spawn(move || {
+        let mut size = size;
+        loop {
+            thread::sleep(RESIZE_POLL_INTERVAL);
+            if let Some(new_size) = window_size(libc::STDIN_FILENO)
+                && new_size != size
+            {
+                size = new_size;
+                if Frame::Resize(size).write_to(&mut resize_stream).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    let mut stdout = io::stdout().lock();
+    loop {
+        match Frame::read_from(&mut stream)? {
+            Some(Frame::Output(bytes)) => {
+                stdout.write_all(&bytes)?;
+                stdout.flush()?;
+            }
+            Some(Frame::Exited(exit_code)) => return Ok(exit_code.unwrap_or(1)),
+            Some(Frame::Error(message)) => return Err(anyhow!(message)),
+            Some(frame) => bail!("unexpected frame from the terminal host: {frame:?}"),
+            None => bail!("the terminal host closed the connection"),
+        }
+    }
+}
+
+/// Connects to the host, starting one if none is listening. Clients racing to start the host may
+/// each start one; the host holds a lock while it runs, so all but one of them exit.
+fn connect_or_start_host(socket_path: &Path) -> Result<UnixStream> {
+    if let Ok(stream) = UnixStream::connect(socket_path) {
+        return Ok(stream);
+    }
+
+    let mut command = Command::new(env::current_exe().context("locating the Zed binary")?);
+    command
+        .arg(HOST_FLAG)
+        .arg(socket_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    // Detach the host from the terminal, so that it outlives the terminal and the editor.
+    util::set_pre_exec_to_start_new_session(&mut command);
+    command.spawn().context("starting the terminal host")?;
+
+    let poll_interval = Duration::from_millis(20);
+    for _ in 0..HOST_STARTUP_TIMEOUT.as_millis() / poll_interval.as_millis() {
+        thread::sleep(poll_interval);
+        if let Ok(stream) = UnixStream::connect(socket_path) {
+            return Ok(stream);
+        }
+    }
+    bail!("timed out waiting for the terminal host to start")
+}
+
+fn forward_input(stream: &mut UnixStream) -> io::Result<()> {
+    let mut stdin = io::stdin().lock();
+    let mut buffer = [0; 4096];
+    let mut detector = EndSessionDetector::default();
+    loop {
+        let len = match stdin.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(len) => len,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+        let (input, end_session) = detector.feed(&buffer[..len]);
+        if !input.is_empty() {
+            Frame::Input(input).write_to(stream)?;
+        }
+        if end_session {
+            return Frame::EndSession.write_to(stream);
+        }
+    }
+}
+
+/// Finds [`END_SESSION_SEQUENCE`] in the client's input.
+#[derive(Default)]
+struct EndSessionDetector {
+    matched: usize,
+}
+
+impl EndSessionDetector {
+    /// Returns the input to forward to the session, and whether the input ends the session.
+    ///
+    /// The sequence is written to the terminal at once, so a prefix of it at the end of a read is
+    /// forwarded rather than held back, which would delay a lone escape key press.
+    fn feed(&mut self, input: &[u8]) -> (Vec<u8>, bool) {
+        let mut forward = Vec::with_capacity(input.len());
+        for &byte in input {
+            if byte == END_SESSION_SEQUENCE[self.matched] {
+                self.matched += 1;
+                if self.matched == END_SESSION_SEQUENCE.len() {
+                    self.matched = 0;
+                    return (forward, true);
+                }
+            } else {
+                forward.extend_from_slice(&END_SESSION_SEQUENCE[..self.matched]);
+                if byte == END_SESSION_SEQUENCE[0] {
+                    self.matched = 1;
+                } else {
+                    self.matched = 0;
+                    forward.push(byte);
+                }
+            }
+        }
+        forward.extend_from_slice(&END_SESSION_SEQUENCE[..self.matched]);
+        self.matched = 0;
+        (forward, false)
+    }
+}
+
+fn window_size(fd: RawFd) -> Option<WindowSize> {
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    if unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, &mut size) } != 0 || size.ws_col == 0 {
+        return None;
+    }
+    Some(WindowSize {
+        rows: size.ws_row,
+        columns: size.ws_col,
+    })
+}
+
+/// Puts the terminal in raw mode, restoring its previous mode when dropped.
+struct RawMode {
+    fd: RawFd,
+    original: libc::termios,
+}
+
+impl RawMode {
+    fn enable(fd: RawFd) -> Option<Self> {
+        unsafe {
+            let mut termios: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(fd, &mut termios) != 0 {
+                return None;
+            }
+            let original = termios;
+            libc::cfmakeraw(&mut termios);
+            if libc::tcsetattr(fd, libc::TCSANOW, &termios) != 0 {
+                return None;
+            }
+            Some(Self { fd, original })
+        }
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        unsafe {
+            libc::tcsetattr(self.fd, libc::TCSANOW, &self.original);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_end_session_detector() {
+        let mut detector = EndSessionDetector::default();
+        assert_eq!(detector.feed(b"ls\r"), (b"ls\r".to_vec(), false));
+        assert_eq!(detector.feed(b"\x1b"), (b"\x1b".to_vec(), false));
+        assert_eq!(
+            detector.feed(b"\x1b\x1b[A"),
+            (b"\x1b\x1b[A".to_vec(), false)
+        );
+
+        let mut input = b"exit".to_vec();
+        input.extend_from_slice(END_SESSION_SEQUENCE);
+        input.extend_from_slice(b"ignored");
+        assert_eq!(detector.feed(&input), (b"exit".to_vec(), true));
+
+        let mut input = b"\x1b".to_vec();
+        input.extend_from_slice(END_SESSION_SEQUENCE);
+        assert_eq!(detector.feed(&input), (b"\x1b".to_vec(), true));
+    }
+}
@@ -0,0 +1,516 @@
+use std::{
+    collections::VecDeque,
+    fs::{self, File, OpenOptions},
+    io::{self, Read, Write},
+    os::{
+        fd::AsRawFd as _,
+        unix::{
+            fs::{OpenOptionsExt as _, PermissionsExt as _},
+            net::{UnixListener, UnixStream},
+        },
+    },
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context as _, Result, bail};
+use collections::HashMap;
+use portable_pty::{Child, ChildKiller, CommandBuilder, MasterPty, PtySize};
+
+use crate::protocol::{AttachRequest, Frame, PROTOCOL_VERSION, WindowSize};
+
+/// How much of each session's output is kept to replay when a client reattaches.
+const MAX_SCROLLBACK_BYTES: usize = 2 * 1024 * 1024;
+
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+/// How often the host looks for sessions that were detached for longer than their timeout.
+const REAP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long a starting host waits for the lock held by a host that is exiting.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Runs the terminal host, listening for attach clients on the socket. The host exits once it has
+/// no sessions left and no clients connected.
+pub fn run_host(socket_path: &Path) -> Result<()> {
+    if let Some(parent) = socket_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // Held until the host exits, so that the socket is only bound, and removed, by one host.
+    let _lock = lock_host(socket_path)?;
+    fs::remove_file(socket_path).ok();
+    let listener = UnixListener::bind(socket_path)
+        .with_context(|| format!("binding the terminal host socket {socket_path:?}"))?;
+    fs::set_permissions(socket_path, fs::Permissions::from_mode(0o600))?;
+
+    let host = Arc::new(Host {
+        socket_path: socket_path.to_path_buf(),
+        state: Mutex::default(),
+    });
+    thread::spawn({
+        let host = host.clone();
+        move || {
+            loop {
+                thread::sleep(REAP_INTERVAL);
+                host.end_expired_sessions();
+            }
+        }
+    });
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(error) => {
+                log::error!("failed to accept a terminal host connection: {error}");
+                continue;
+            }
+        };
+        host.state.lock().unwrap().connections += 1;
+        let host = host.clone();
+        thread::spawn(move || {
+            if let Err(error) = host.handle_connection(stream) {
+                log::error!("terminal host connection failed: {error:#}");
+            }
+            host.state.lock().unwrap().connections -= 1;
+            host.exit_if_idle();
+        });
+    }
+    Ok(())
+}
+
+/// Takes the lock next to the socket. Clients that race to start a host may start several, and
+/// all but the first one to take the lock exit here.
+fn lock_host(socket_path: &Path) -> Result<File> {
+    let lock_path = socket_path.with_extension("lock");
+    let file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .mode(0o600)
+        .open(&lock_path)
+        .with_context(|| format!("opening the terminal host lock {lock_path:?}"))?;
+    let deadline = Instant::now() + LOCK_TIMEOUT;
+    loop {
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+            return Ok(file);
+        }
+        let error = io::Error::last_os_error();
+        if error.kind() != io::ErrorKind::WouldBlock {
+            return Err(error).with_context(|| format!("locking {lock_path:?}"));
+        }
+        // The host holding the lock may be about to exit, in which case it no longer accepts
+        // connections and this host takes over once it is gone.
+        if UnixStream::connect(socket_path).is_ok() || Instant::now() >= deadline {
+            bail!("a terminal host is already running on {socket_path:?}");
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
+struct Host {
+    socket_path: PathBuf,
+    state: Mutex<HostState>,
+}
+
+#[derive(Default)]
+struct HostState {
+    sessions: HashMap<String, Arc<Session>>,
+    connections: usize,
+}
+
+impl Host {
+    fn handle_connection(self: &Arc<Self>, mut stream: UnixStream) -> Result<()> {
+        let Some(Frame::Attach(request)) = Frame::read_from(&mut stream)? else {
+            bail!("expected an attach request");
+        };
+        if request.version != PROTOCOL_VERSION {
+            Frame::Error(format!(
+                "the terminal host speaks protocol version {PROTOCOL_VERSION}, but the client speaks version {}",
+                request.version
+            ))
+            .write_to(&mut stream)?;
+            return Ok(());
+        }
+
+        let session = match self.session(&request) {
+            Ok(session) => session,
+            Err(error) => {
+                Frame::Error(format!("{error:#}")).write_to(&mut stream)?;
+                return Err(error);
+            }
+        };
+        let detached_timeout = request.detached_timeout_secs.map(Duration::from_secs);
+        let client_id = session.attach(stream.try_clone()?, detached_timeout);
+        session.resize(request.size);
+
+        loop {
+            match Frame::read_from(&mut stream) {
+                Ok(Some(Frame::Input(bytes))) => session.write_input(&bytes)?,
+                Ok(Some(Frame::Resize(size))) => session.resize(size),
+                Ok(Some(Frame::EndSession)) => {
+                    session.kill();
+                    return Ok(());
+                }
+                Ok(Some(frame)) => {
+                    session.detach(client_id);
+                    bail!("unexpected frame from the attach client: {frame:?}");
+                }
+                // The client went away, e.g. because Zed quit. Keep the session running for the
+                // next client to reattach to.
+                Ok(None) | Err(_) => {
+                    session.detach(client_id);
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Returns the requested session, starting it if it does not exist.
+    fn session(self: &Arc<Self>, request: &AttachRequest) -> Result<Arc<Session>> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(session) = state.sessions.get(&request.session_id) {
+            return Ok(session.clone());
+        }
+
+        let pair = portable_pty::native_pty_system()
+            .openpty(request.size.into())
+            .context("opening a pty")?;
+        let mut command = CommandBuilder::new(&request.program);
+        command.args(&request.args);
+        command.env_clear();
+        for (key, value) in &request.env {
+            command.env(key, value);
+        }
+        if let Some(working_directory) = &request.working_directory {
+            command.cwd(working_directory);
+        }
+        let child = pair
+            .slave
+            .spawn_command(command)
+            .with_context(|| format!("spawning {:?}", request.program))?;
+        drop(pair.slave);
+
+        let reader = pair.master.try_clone_reader()?;
+        let session = Arc::new(Session {
+            writer: Mutex::new(pair.master.take_writer()?),
+            master: Mutex::new(pair.master),
+            killer: Mutex::new(child.clone_killer()),
+            output: Mutex::default(),
+        });
+        state
+            .sessions
+            .insert(request.session_id.clone(), session.clone());
+
+        let host = self.clone();
+        let session_id = request.session_id.clone();
+        let output_session = session.clone();
+        thread::spawn(move || {
+            output_session.forward_output(reader, child);
+            host.state.lock().unwrap().sessions.remove(&session_id);
+            host.exit_if_idle();
+        });
+        Ok(session)
+    }
+
+    /// Kills the sessions that were detached for longer than their timeout. They are removed once
+    /// their program exited, like any other session.
+    fn end_expired_sessions(&self) {
+        let now = Instant::now();
+        let state = self.state.lock().unwrap();
+        for (session_id, session) in &state.sessions {
+            if session.output.lock().unwrap().is_expired(now) {
+                log::info!("ending terminal session {session_id}, which was detached for too long");
+                session.kill();
+            }
+        }
+    }
+
+    fn exit_if_idle(&self) {
+        let state = self.state.lock().unwrap();
+        if state.sessions.is_empty() && state.connections == 0 {
+            fs::remove_file(&self.socket_path).ok();
+            std::process::exit(0);
+        }
+    }
+}
+
+struct Session {
+    master: Mutex<Box<dyn MasterPty + Send>>,
+    writer: Mutex<Box<dyn Write + Send>>,
+    killer: Mutex<Box<dyn ChildKiller + Send + Sync>>,
+    /// The scrollback and the attached client share a lock, so that a reattaching client receives
+    /// the replayed output and everything after it exactly once.
+    output: Mutex<SessionOutput>,
+}
+
+#[derive(Default)]
+struct SessionOutput {
+    scrollback: Scrollback,
+    client: Option<AttachedClient>,
+    next_client_id: usize,
+    /// When the last client detached, if none is attached.
+    detached_at: Option<Instant>,
+    /// How long the session keeps running while detached, as requested by the last client.
+    detached_timeout: Option<Duration>,
+}
+
+impl SessionOutput {
+    fn detach_client(&mut self) {
+        self.client = None;
+        self.detached_at = Some(Instant::now());
+    }
+
+    fn is_expired(&self, now: Instant) -> bool {
+        self.client.is_none()
+            && self
+                .detached_at
+                .zip(self.detached_timeout)
+                .is_some_and(|(detached_at, timeout)| now.duration_since(detached_at) >= timeout)
+    }
+}
+
+struct AttachedClient {
+    id: usize,
+    stream: UnixStream,
+}
+
+impl Session {
+    /// Attaches the client to the session, replacing the previously attached one, and replays the
+    /// session's scrollback to it.
+    fn attach(&self, mut stream: UnixStream, detached_timeout: Option<Duration>) -> usize {
+        let mut output = self.output.lock().unwrap();
+        let id = output.next_client_id;
+        output.next_client_id += 1;
+        output.detached_timeout = detached_timeout;
+        if let Some(previous) = output.client.take() {
+            previous.stream.shutdown(std::net::Shutdown::Both).ok();
+        }
+        let replay = output.scrollback.replay();
+        if replay.is_empty() || Frame::Output(replay).write_to(&mut stream).is_ok() {
+            output.client = Some(AttachedClient { id, stream });
+            output.detached_at = None;
+        } else {
+            output.detach_client();
+        }
+        id
+    }
+
+    fn detach(&self, client_id: usize) {
+        let mut output = self.output.lock().unwrap();
+        if output
+            .client
+            .as_ref()
+            .is_some_and(|client| client.id == client_id)
+        {
+            output.detach_client();
+        }
+    }
+
+    fn write_input(&self, bytes: &[u8]) -> io::Result<()> {
+        self.writer.lock().unwrap().write_all(bytes)
+    }
+
+    fn resize(&self, size: WindowSize) {
+        if let Err(error) = self.master.lock().unwrap().resize(size.into()) {
+            log::error!("failed to resize a terminal session: {error:#}");
+        }
+    }
+
+    fn kill(&self) {
+        self.killer.lock().unwrap().kill().ok();
+    }
+
+    fn forward_output(
+        &self,
+        mut reader: Box<dyn Read + Send>,
+        mut child: Box<dyn Child + Send + Sync>,
+    ) {
+        let mut buffer = vec![0; READ_BUFFER_SIZE];
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(len) => {
+                    let mut output = self.output.lock().unwrap();
+                    output.scrollback.push(&buffer[..len]);
+                    if let Some(client) = &mut output.client
+                        && Frame::Output(buffer[..len].to_vec())
+                            .write_to(&mut client.stream)
+                            .is_err()
+                    {
+                        output.detach_client();
+                    }
+                }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                // Reading the pty fails rather than returning EOF once the program exits on Linux.
+                Err(_) => break,
+            }
+        }
+
+        let exit_code = child.wait().ok().map(|status| status.exit_code() as i32);
+        if let Some(mut client) = self.output.lock().unwrap().client.take() {
+            Frame::Exited(exit_code).write_to(&mut client.stream).ok();
+        }
+    }
+}
+
+impl From<WindowSize> for PtySize {
+    fn from(size: WindowSize) -> Self {
+        PtySize {
+            rows: size.rows,
+            cols: size.columns,
+            pixel_width: 0,
+            pixel_height: 0,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Scrollback {
+    bytes: VecDeque<u8>,
+    truncated: bool,
+}
+
+impl Scrollback {
+    fn push(&mut self, bytes: &[u8]) {
+        self.bytes.extend(bytes);
+        if self.bytes.len() > MAX_SCROLLBACK_BYTES {
+            self.bytes.drain(..self.bytes.len() - MAX_SCROLLBACK_BYTES);
+            self.truncated = true;
+        }
+    }
+
+    /// The output to replay to a reattaching client. Once the scrollback was truncated, it starts
+    /// at a line boundary rather than in the middle of a line or escape sequence.
+    fn replay(&self) -> Vec<u8> {
+        let (front, back) = self.bytes.as_slices();
+        let bytes = [front, back].concat();
+        let start = if self.truncated {
+            bytes
+                .iter()
+                .position(|byte| *byte == b'\n')
+                .map_or(0, |newline| newline + 1)
+        } else {
+            0
+        };
+        strip_terminal_queries(&bytes[start..])
+    }
+}
+
+/// Removes the escape sequences that make the terminal reply from the replayed output. They were
+/// answered when the output was first printed, and replies to them now would be typed into the
+/// session as input.
+fn strip_terminal_queries(bytes: &[u8]) -> Vec<u8> {
+    let mut stripped = Vec::with_capacity(bytes.len());
+    let mut ix = 0;
+    while ix < bytes.len() {
+        if bytes[ix] == 0x1b
+            && let Some(len) = terminal_query_len(&bytes[ix..])
+        {
+            ix += len;
+        } else {
+            stripped.push(bytes[ix]);
+            ix += 1;
+        }
+    }
+    stripped
+}
+
+/// The length of the terminal query at the start of the bytes, if there is one.
+fn terminal_query_len(bytes: &[u8]) -> Option<usize> {
+    match bytes.get(1)? {
+        b'[' => {
+            let end = bytes[2..]
+                .iter()
+                .position(|byte| (0x40..=0x7e).contains(byte))?
+                + 2;
+            let params = &bytes[2..end];
+            let is_query = match bytes[end] {
+                // Device status reports and device attributes.
+                b'n' | b'c' => true,
+                // Window size reports.
+                b't' => true,
+                // Mode and keyboard protocol reports.
+                b'p' => params.ends_with(b"$"),
+                b'u' => params == b"?",
+                _ => false,
+            };
+            is_query.then_some(end + 1)
+        }
+        // Color queries, e.g. `OSC 11 ; ? ST`.
+        b']' => {
+            let payload_len = bytes[2..]
+                .iter()
+                .position(|byte| *byte == 0x07 || *byte == 0x1b)?;
+            let payload = &bytes[2..2 + payload_len];
+            let terminator_len = if bytes[2 + payload_len] == 0x07 { 1 } else { 2 };
+            payload
+                .ends_with(b";?")
+                .then_some(2 + payload_len + terminator_len)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_strip_terminal_queries() {
+        assert_eq!(
+            strip_terminal_queries(b"before\x1b[6nafter"),
+            b"beforeafter"
+        );
+        assert_eq!(strip_terminal_queries(b"\x1b[c\x1b[>0c$ "), b"$ ");
+        assert_eq!(
+            strip_terminal_queries(b"\x1b]11;?\x07\x1b]10;?\x1b\\prompt"),
+            b"prompt"
+        );
+        assert_eq!(strip_terminal_queries(b"\x1b[?u\x1b[?2004$p"), b"");
+        assert_eq!(
+            strip_terminal_queries(b"\x1b[1;31mred\x1b[0m\x1b]0;title\x07"),
+            b"\x1b[1;31mred\x1b[0m\x1b]0;title\x07"
+        );
+        assert_eq!(
+            strip_terminal_queries(b"incomplete\x1b["),
+            b"incomplete\x1b["
+        );
+    }
+
+    #[test]
+    fn test_scrollback_replay() {
+        let mut scrollback = Scrollback::default();
+        scrollback.push(b"first\r\nsecond\x1b[6n\r\n");
+        assert_eq!(scrollback.replay(), b"first\r\nsecond\r\n");
+
+        let mut scrollback = Scrollback::default();
+        let long_line = vec![b'x'; MAX_SCROLLBACK_BYTES];
+        scrollback.push(&long_line);
+        scrollback.push(b"\r\nlast line");
+        assert_eq!(scrollback.replay(), b"last line");
+    }
+
+    #[test]
+    fn test_detached_session_expiry() {
+        let timeout = Duration::from_secs(60);
+        let mut output = SessionOutput {
+            detached_timeout: Some(timeout),
+            ..Default::default()
+        };
+        let now = Instant::now();
+        assert!(!output.is_expired(now + timeout * 2));
+
+        let (stream, _) = UnixStream::pair().unwrap();
+        output.client = Some(AttachedClient { id: 0, stream });
+        assert!(!output.is_expired(now + timeout * 2));
+
+        output.detach_client();
+        let detached_at = output.detached_at.unwrap();
+        assert!(!output.is_expired(detached_at + timeout / 2));
+        assert!(output.is_expired(detached_at + timeout));
+
+        output.detached_timeout = None;
+        assert!(!output.is_expired(detached_at + timeout * 100));
+    }
+}
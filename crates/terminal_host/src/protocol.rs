@@ -0,0 +1,173 @@
+use std::{
+    borrow::Cow,
+    io::{self, Read, Write},
+    path::PathBuf,
+};
+
+use collections::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// Bumped on incompatible changes, so that a client does not attach to a host started by another
+/// version of Zed that speaks a different protocol.
+pub(crate) const PROTOCOL_VERSION: u32 = 1;
+
+/// Frames larger than this are rejected, rather than allocated.
+const MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct AttachRequest {
+    pub version: u32,
+    pub session_id: String,
+    /// The program to start the session with, if it does not exist yet.
+    pub program: String,
+    pub args: Vec<String>,
+    pub working_directory: Option<PathBuf>,
+    pub env: HashMap<String, String>,
+    pub size: WindowSize,
+    /// How long the host keeps the session running while no client is attached to it. Updated
+    /// each time a client attaches.
+    #[serde(default)]
+    pub detached_timeout_secs: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct WindowSize {
+    pub rows: u16,
+    pub columns: u16,
+}
+
+/// A message between an attach client and the host. Each one is written as its kind, the length
+/// of its payload as a big endian `u32`, and the payload.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Frame {
+    /// Sent by the client first, to attach to a session.
+    Attach(AttachRequest),
+    /// Input for the session, sent by the client.
+    Input(Vec<u8>),
+    /// Sent by the client when its terminal is resized.
+    Resize(WindowSize),
+    /// Sent by the client to kill the session.
+    EndSession,
+    /// Output of the session, sent by the host.
+    Output(Vec<u8>),
+    /// Sent by the host when the session's program exits, with its exit code.
+    Exited(Option<i32>),
+    /// Sent by the host when it could not attach the client.
+    Error(String),
+}
+
+impl Frame {
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let (kind, payload): (u8, Cow<[u8]>) = match self {
+            Frame::Attach(request) => (0, serde_json::to_vec(request)?.into()),
+            Frame::Input(bytes) => (1, bytes.into()),
+            Frame::Resize(size) => (2, serde_json::to_vec(size)?.into()),
+            Frame::EndSession => (3, Cow::Borrowed(&[])),
+            Frame::Output(bytes) => (4, bytes.into()),
+            Frame::Exited(exit_code) => (5, serde_json::to_vec(exit_code)?.into()),
+            Frame::Error(message) => (6, message.as_bytes().into()),
+        };
+        let mut header = [0; 5];
+        header[0] = kind;
+        header[1..].copy_from_slice(&(payload.len() as u32).to_be_bytes());
+        writer.write_all(&header)?;
+        writer.write_all(&payload)?;
+        writer.flush()
+    }
+
+    /// Reads the next frame, or `None` once the other side closed the connection.
+    pub fn read_from(reader: &mut impl Read) -> io::Result<Option<Self>> {
+        let mut header = [0; 5];
+        match reader.read_exact(&mut header) {
+            Ok(()) => {}
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(error) => return Err(error),
+        }
+        let len = u32::from_be_bytes([header[1], header[2], header[3], header[4]]) as usize;
+        if len > MAX_FRAME_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("frame of {len} bytes exceeds the maximum frame size"),
+            ));
+        }
+        let mut payload = vec![0; len];
+        reader.read_exact(&mut payload)?;
+
+        let frame = match header[0] {
+            0 => Frame::Attach(serde_json::from_slice(&payload)?),
+            1 => Frame::Input(payload),
+            2 => Frame::Resize(serde_json::from_slice(&payload)?),
+            3 => Frame::EndSession,
+            4 => Frame::Output(payload),
+            5 => Frame::Exited(serde_json::from_slice(&payload)?),
+            6 => Frame::Error(String::from_utf8_lossy(&payload).into_owned()),
+            kind => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("unknown frame kind {kind}"),
+                ));
+            }
+        };
+        Ok(Some(frame))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_frame_roundtrip() {
+        let frames = vec![
+            Frame::Attach(AttachRequest {
+                version: PROTOCOL_VERSION,
+                session_id: "session".into(),
+                program: "/bin/zsh".into(),
+                args: vec!["-l".into()],
+                working_directory: Some("/tmp".into()),
+                env: HashMap::from_iter([("TERM".to_string(), "xterm-256color".to_string())]),
+                size: WindowSize {
+                    rows: 24,
+                    columns: 80,
+                },
+                detached_timeout_secs: Some(3600),
+            }),
+            Frame::Input(b"ls\r".to_vec()),
+            Frame::Resize(WindowSize {
+                rows: 50,
+                columns: 120,
+            }),
+            Frame::EndSession,
+            Frame::Output(b"\x1b[1mbold\x1b[0m\r\n".to_vec()),
+            Frame::Output(Vec::new()),
+            Frame::Exited(Some(130)),
+            Frame::Exited(None),
+            Frame::Error("protocol mismatch".into()),
+        ];
+
+        let mut bytes = Vec::new();
+        for frame in &frames {
+            frame.write_to(&mut bytes).unwrap();
+        }
+
+        let mut reader = bytes.as_slice();
+        let mut read_frames = Vec::new();
+        while let Some(frame) = Frame::read_from(&mut reader).unwrap() {
+            read_frames.push(frame);
+        }
+        assert_eq!(read_frames, frames);
+    }
+
+    #[test]
+    fn test_invalid_frames() {
+        let unknown_kind = [42, 0, 0, 0, 0];
+        assert!(Frame::read_from(&mut unknown_kind.as_slice()).is_err());
+
+        let oversized = [4, 0xff, 0xff, 0xff, 0xff];
+        assert!(Frame::read_from(&mut oversized.as_slice()).is_err());
+
+        let truncated_payload = [1, 0, 0, 0, 4, b'l', b's'];
+        assert!(Frame::read_from(&mut truncated_payload.as_slice()).is_err());
+    }
+}
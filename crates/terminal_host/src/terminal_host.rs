@@ -0,0 +1,70 @@
+//! Persistent terminal sessions.
+//!
+//! The shells of persistent terminals are owned by a detached host process, so that they keep
+//! running when Zed quits, restarts or updates. The terminal runs an attach client in place of
+//! the shell, which connects to the host, starts the session or reattaches to it, and replays the
+//! output the session printed while nothing was attached.
+//!
+//! Both the host and the client are the Zed (or remote server) binary itself, started with
+//! [`HOST_FLAG`] and [`ATTACH_FLAG`].
+
+#[cfg(unix)]
+mod attach;
+#[cfg(unix)]
+mod host;
+#[cfg(unix)]
+mod protocol;
+
+use std::{path::PathBuf, time::Duration};
+
+#[cfg(unix)]
+pub use attach::attach;
+#[cfg(unix)]
+pub use host::run_host;
+
+/// Runs the binary as the terminal host, listening on the socket given as the flag's value.
+pub const HOST_FLAG: &str = "--terminal-host";
+
+/// Runs the binary as the attach client of the session given as the flag's first value, starting
+/// it with the program and arguments that follow the detached timeout if it does not exist yet.
+pub const ATTACH_FLAG: &str = "--attach-terminal";
+
+/// Ends the session when written to the attach client's input. Without it, the session keeps
+/// running in the host when the client exits.
+pub const END_SESSION_SEQUENCE: &[u8] = b"\x1b_zed:end-session\x1b\\";
+
+/// Whether persistent sessions are available on this platform.
+pub const fn is_supported() -> bool {
+    cfg!(unix)
+}
+
+/// The socket the attach clients connect to the host over.
+pub fn socket_path() -> PathBuf {
+    paths::temp_dir().join("terminal_host.sock")
+}
+
+pub fn new_session_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// The arguments to run the Zed binary with to attach to a session, starting it with the given
+/// program and arguments if needed.
+///
+/// The host ends the session once nothing was attached to it for `detached_timeout`, if given.
+pub fn attach_args(
+    session_id: &str,
+    detached_timeout: Option<Duration>,
+    program: String,
+    args: impl IntoIterator<Item = String>,
+) -> Vec<String> {
+    let detached_timeout_secs = detached_timeout.map_or(0, |timeout| timeout.as_secs());
+    [
+        ATTACH_FLAG.to_string(),
+        session_id.to_string(),
+        detached_timeout_secs.to_string(),
+        program,
+    ]
+    .into_iter()
+    .chain(args)
+    .collect()
+}
@@ -0,0 +1,435 @@
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use gpui::{
+    AnyElement, App, Entity, EventEmitter, FocusHandle, Focusable, Render, Task, WeakEntity,
+};
+use project::{Project, ProjectEntryId, ProjectPath};
+use settings::Settings;
+use terminal::{
+    Asciicast, Terminal, TerminalBuilder,
+    terminal_settings::{AlternateScroll, TerminalSettings},
+};
+use ui::{Tooltip, prelude::*};
+use workspace::{
+    Pane,
+    item::{Item, ProjectItem, TabContentParams},
+};
+
+use crate::{SeekBackward, SeekForward, TerminalView, TogglePlayback};
+
+const SEEK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How often the playback position is updated while the recording prints nothing.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Resets the terminal to its initial state.
+const RESET: &[u8] = b"\x1bc";
+
+pub fn init(cx: &mut App) {
+    workspace::register_project_item::<AsciicastPlayer>(cx);
+}
+
+/// A terminal recording opened from the project, as an asciicast `.cast` file.
+pub struct AsciicastItem {
+    abs_path: PathBuf,
+    project_path: ProjectPath,
+    entry_id: Option<ProjectEntryId>,
+    cast: Arc<Asciicast>,
+}
+
+impl project::ProjectItem for AsciicastItem {
+    fn try_open(
+        project: &Entity<Project>,
+        path: &ProjectPath,
+        cx: &mut App,
+    ) -> Option<Task<anyhow::Result<Entity<Self>>>> {
+        if path.path.extension() != Some("cast") {
+            return None;
+        }
+
+        let path = path.clone();
+        let project = project.clone();
+        let fs = project.read(cx).fs().clone();
+        Some(cx.spawn(async move |cx| {
+            let abs_path = project
+                .read_with(cx, |project, cx| project.absolute_path(&path, cx))
+                .with_context(|| format!("finding the absolute path of {path:?}"))?;
+            let contents = fs.load(&abs_path).await?;
+            let cast = Asciicast::parse(&contents)
+                .with_context(|| format!("reading the recording {abs_path:?}"))?;
+            let entry_id = project.read_with(cx, |project, cx| {
+                project.entry_for_path(&path, cx).map(|entry| entry.id)
+            });
+
+            Ok(cx.new(|_| Self {
+                abs_path,
+                project_path: path,
+                entry_id,
+                cast: Arc::new(cast),
+            }))
+        }))
+    }
+
+    fn entry_id(&self, _: &App) -> Option<ProjectEntryId> {
+        self.entry_id
+    }
+
+    fn project_path(&self, _: &App) -> Option<ProjectPath> {
+        Some(self.project_path.clone())
+    }
+
+    fn is_dirty(&self) -> bool {
+        false
+    }
+}
+
+/// Plays back a terminal recording in a terminal without a process.
+///
+/// Output is replayed in the terminal's current size, and the resizes of the recording are not.
+pub struct AsciicastPlayer {
+    project: Entity<Project>,
+    item: Entity<AsciicastItem>,
+    cast: Arc<Asciicast>,
+    terminal: Entity<Terminal>,
+    terminal_view: Entity<TerminalView>,
+    /// The index of the first event not yet written to the terminal.
+    next_event: usize,
+    /// The playback position when playback was last resumed, paused or moved.
+    position: Duration,
+    resumed_at: Option<Instant>,
+    _playback: Task<()>,
+}
+
+impl AsciicastPlayer {
+    fn new(
+        project: Entity<Project>,
+        item: Entity<AsciicastItem>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let cast = item.read(cx).cast.clone();
+        let builder = TerminalBuilder::new_display_only(
+            TerminalSettings::get_global(cx).cursor_shape,
+            AlternateScroll::On,
+            None,
+            window.window_handle().window_id().as_u64(),
+            cx.background_executor(),
+            project.read(cx).path_style(cx),
+        )
+        .expect("terminals without a process are always created");
+        let terminal = cx.new(|cx| builder.subscribe(cx));
+        let terminal_view = cx.new(|cx| {
+            TerminalView::new(
+                terminal.clone(),
+                WeakEntity::new_invalid(),
+                None,
+                project.downgrade(),
+                window,
+                cx,
+            )
+        });
+
+        let mut this = Self {
+            project,
+            item,
+            cast,
+            terminal,
+            terminal_view,
+            next_event: 0,
+            position: Duration::ZERO,
+            resumed_at: None,
+            _playback: Task::ready(()),
+        };
+        this.play(cx);
+        this
+    }
+
+    fn position(&self) -> Duration {
+        let elapsed = self
+            .resumed_at
+            .map_or(Duration::ZERO, |resumed_at| resumed_at.elapsed());
+        (self.position + elapsed).min(self.cast.duration())
+    }
+
+    fn is_playing(&self) -> bool {
+        self.resumed_at.is_some()
+    }
+
+    fn play(&mut self, cx: &mut Context<Self>) {
+        if self.position() >= self.cast.duration() {
+            self.seek(Duration::ZERO, cx);
+        }
+        self.resumed_at = Some(Instant::now());
+        self._playback = cx.spawn(async move |this, cx| {
+            while let Ok(Some(delay)) = this.update(cx, |this, cx| this.advance(cx)) {
+                cx.background_executor().timer(delay).await;
+            }
+        });
+        cx.notify();
+    }
+
+    fn pause(&mut self, cx: &mut Context<Self>) {
+        self.position = self.position();
+        self.resumed_at = None;
+        self._playback = Task::ready(());
+        cx.notify();
+    }
+
+    /// Writes the output that is due, returning how long to wait before the next update, or
+    /// `None` once the recording has ended.
+    fn advance(&mut self, cx: &mut Context<Self>) -> Option<Duration> {
+        let position = self.position();
+        self.write_events_until(position, cx);
+        cx.notify();
+        if position >= self.cast.duration() {
+            self.position = position;
+            self.resumed_at = None;
+            return None;
+        }
+
+        let until_next_event = self
+            .cast
+            .events
+            .get(self.next_event)
+            .map_or(REFRESH_INTERVAL, |(time, _)| time.saturating_sub(position));
+        Some(until_next_event.min(REFRESH_INTERVAL))
+    }
+
+    fn seek(&mut self, position: Duration, cx: &mut Context<Self>) {
+        let position = position.min(self.cast.duration());
+        if position < self.position() {
+            // Output can only be played forward, so earlier positions are reached by replaying
+            // the recording from its start.
+            self.terminal
+                .update(cx, |terminal, cx| terminal.write_recorded_output(RESET, cx));
+            self.next_event = 0;
+        }
+        self.position = position;
+        if self.is_playing() {
+            self.resumed_at = Some(Instant::now());
+        }
+        self.write_events_until(position, cx);
+        cx.notify();
+    }
+
+    fn write_events_until(&mut self, position: Duration, cx: &mut Context<Self>) {
+        let due_events = self.cast.events[self.next_event..]
+            .iter()
+            .take_while(|(time, _)| *time <= position)
+            .count();
+        if due_events == 0 {
+            return;
+        }
+
+        let events = self.next_event..self.next_event + due_events;
+        let cast = self.cast.clone();
+        self.terminal.update(cx, |terminal, cx| {
+            for (_, output) in &cast.events[events] {
+                terminal.write_recorded_output(output.as_bytes(), cx);
+            }
+        });
+        self.next_event += due_events;
+    }
+
+    fn toggle_playback(&mut self, _: &TogglePlayback, _: &mut Window, cx: &mut Context<Self>) {
+        if self.is_playing() {
+            self.pause(cx);
+        } else {
+            self.play(cx);
+        }
+    }
+
+    fn seek_backward(&mut self, _: &SeekBackward, _: &mut Window, cx: &mut Context<Self>) {
+        self.seek(self.position().saturating_sub(SEEK_INTERVAL), cx);
+    }
+
+    fn seek_forward(&mut self, _: &SeekForward, _: &mut Window, cx: &mut Context<Self>) {
+        self.seek(self.position() + SEEK_INTERVAL, cx);
+    }
+}
+
+fn format_time(time: Duration) -> String {
+    let seconds = time.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+impl EventEmitter<()> for AsciicastPlayer {}
+
+impl Focusable for AsciicastPlayer {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.terminal_view.focus_handle(cx)
+    }
+}
+
+impl Render for AsciicastPlayer {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle(cx);
+        let (play_icon, play_tooltip) = if self.is_playing() {
+            (IconName::DebugPause, "Pause")
+        } else {
+            (IconName::PlayFilled, "Play")
+        };
+
+        v_flex()
+            .key_context("AsciicastPlayer")
+            .on_action(cx.listener(Self::toggle_playback))
+            .on_action(cx.listener(Self::seek_backward))
+            .on_action(cx.listener(Self::seek_forward))
+            .size_full()
+            .child(
+                h_flex()
+                    .gap_1()
+                    .p_1()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        IconButton::new("seek-backward", IconName::ArrowLeft)
+                            .icon_size(IconSize::Small)
+                            .tooltip({
+                                let focus_handle = focus_handle.clone();
+                                move |_window, cx| {
+                                    Tooltip::for_action_in(
+                                        "Back 5 Seconds",
+                                        &SeekBackward,
+                                        &focus_handle,
+                                        cx,
+                                    )
+                                }
+                            })
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.seek_backward(&SeekBackward, window, cx)
+                            })),
+                    )
+                    .child(
+                        IconButton::new("toggle-playback", play_icon)
+                            .icon_size(IconSize::Small)
+                            .tooltip({
+                                let focus_handle = focus_handle.clone();
+                                move |_window, cx| {
+                                    Tooltip::for_action_in(
+                                        play_tooltip,
+                                        &TogglePlayback,
+                                        &focus_handle,
+                                        cx,
+                                    )
+                                }
+                            })
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.toggle_playback(&TogglePlayback, window, cx)
+                            })),
+                    )
+                    .child(
+                        IconButton::new("seek-forward", IconName::ArrowRight)
+                            .icon_size(IconSize::Small)
+                            .tooltip(move |_window, cx| {
+                                Tooltip::for_action_in(
+                                    "Forward 5 Seconds",
+                                    &SeekForward,
+                                    &focus_handle,
+                                    cx,
+                                )
+                            })
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.seek_forward(&SeekForward, window, cx)
+                            })),
+                    )
+                    .child(
+                        Label::new(format!(
+                            "{} / {}",
+                            format_time(self.position()),
+                            format_time(self.cast.duration())
+                        ))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                    ),
+            )
+            .child(div().flex_1().min_h_0().child(self.terminal_view.clone()))
+    }
+}
+
+impl Item for AsciicastPlayer {
+    type Event = ();
+
+    fn tab_content_text(&self, _detail: usize, cx: &App) -> SharedString {
+        let item = self.item.read(cx);
+        item.cast
+            .title
+            .clone()
+            .or_else(|| {
+                item.project_path
+                    .path
+                    .file_name()
+                    .map(|name| name.to_string())
+            })
+            .unwrap_or_default()
+            .into()
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or(0), cx))
+            .single_line()
+            .color(params.text_color())
+            .when(params.preview, |this| this.italic())
+            .into_any_element()
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        Some(
+            self.item
+                .read(cx)
+                .abs_path
+                .to_string_lossy()
+                .into_owned()
+                .into(),
+        )
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(IconName::Terminal.into())
+    }
+
+    fn buffer_kind(&self, _: &App) -> workspace::item::ItemBufferKind {
+        workspace::item::ItemBufferKind::Singleton
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        f(self.item.entity_id(), self.item.read(cx))
+    }
+
+    fn can_split(&self) -> bool {
+        true
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<workspace::WorkspaceId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<Entity<Self>>> {
+        let (project, item) = (self.project.clone(), self.item.clone());
+        Task::ready(Some(cx.new(|cx| Self::new(project, item, window, cx))))
+    }
+}
+
+impl ProjectItem for AsciicastPlayer {
+    type Item = AsciicastItem;
+
+    fn for_project_item(
+        project: Entity<Project>,
+        _pane: Option<&Pane>,
+        item: Entity<Self::Item>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self::new(project, item, window, cx)
+    }
+}
@@ -422,6 +422,9 @@ impl Domain for TerminalDb {
         sql! (
             ALTER TABLE terminals ADD COLUMN custom_title TEXT;
         ),
+        sql! (
+            ALTER TABLE terminals ADD COLUMN session_id TEXT;
+        ),
     ];
 }
 
@@ -513,4 +516,35 @@ impl TerminalDb {
             WHERE item_id = ? AND workspace_id = ?
         }
     }
+
+    pub async fn save_session_id(
+        &self,
+        item_id: ItemId,
+        workspace_id: WorkspaceId,
+        session_id: String,
+    ) -> Result<()> {
+        log::debug!(
+            "Saving persistent session {session_id} for item {item_id} in workspace {workspace_id:?}"
+        );
+        self.write(move |conn| {
+            let query = "INSERT INTO terminals (item_id, workspace_id, session_id)
+                VALUES (?1, ?2, ?3)
+                ON CONFLICT (workspace_id, item_id) DO UPDATE SET
+                    session_id = excluded.session_id";
+            let mut statement = Statement::prepare(conn, query)?;
+            let mut next_index = statement.bind(&item_id, 1)?;
+            next_index = statement.bind(&workspace_id, next_index)?;
+            statement.bind(&session_id, next_index)?;
+            statement.exec()
+        })
+        .await
+    }
+
+    query! {
+        pub fn get_session_id(item_id: ItemId, workspace_id: WorkspaceId) -> Result<Option<String>> {
+            SELECT session_id
+            FROM terminals
+            WHERE item_id = ? AND workspace_id = ?
+        }
+    }
 }
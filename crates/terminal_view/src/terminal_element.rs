@@ -1,19 +1,19 @@
 use editor::{CursorLayout, EditorSettings, HighlightedRange, HighlightedRangeLine};
 use gpui::{
-    AbsoluteLength, AnyElement, App, AvailableSpace, Bounds, ContentMask, Context, DispatchPhase,
-    Element, ElementId, Entity, FocusHandle, Font, FontFeatures, FontStyle, FontWeight,
-    GlobalElementId, HighlightStyle, Hitbox, Hsla, InputHandler, InteractiveElement, Interactivity,
-    IntoElement, LayoutId, Length, ModifiersChangedEvent, MouseButton, MouseMoveEvent, Pixels,
-    Point, StatefulInteractiveElement, StrikethroughStyle, Styled, TextRun, TextStyle,
-    UTF16Selection, UnderlineStyle, WeakEntity, WhiteSpace, Window, div, fill, point, px, relative,
-    size,
+    AbsoluteLength, AnyElement, App, AvailableSpace, Bounds, ContentMask, Context, Corners,
+    DispatchPhase, Element, ElementId, Entity, FocusHandle, Font, FontFeatures, FontStyle,
+    FontWeight, GlobalElementId, HighlightStyle, Hitbox, Hsla, InputHandler, InteractiveElement,
+    Interactivity, IntoElement, LayoutId, Length, ModifiersChangedEvent, MouseButton,
+    MouseMoveEvent, Pixels, Point, StatefulInteractiveElement, StrikethroughStyle, Styled, TextRun,
+    TextStyle, UTF16Selection, UnderlineStyle, WeakEntity, WhiteSpace, Window, div, fill, point,
+    px, relative, size,
 };
 use itertools::Itertools;
 use language::CursorShape;
 use settings::Settings;
 use std::time::Instant;
 use terminal::{
-    IndexedCell, Terminal, TerminalBounds, TerminalContent,
+    CommandStatus, ImagePlacement, IndexedCell, Terminal, TerminalBounds, TerminalContent,
     alacritty_terminal::{
         grid::Dimensions,
         index::Point as AlacPoint,
@@ -50,6 +50,10 @@ pub struct LayoutState {
     display_offset: usize,
     hyperlink_tooltip: Option<AnyElement>,
     gutter: Pixels,
+    /// Display lines of the visible command prompts, with the color of their exit status.
+    command_markers: Vec<(i32, Hsla)>,
+    /// The visible images, with the display position of their top left cell.
+    images: Vec<(DisplayCursor, ImagePlacement)>,
     block_below_cursor_element: Option<AnyElement>,
     base_text_style: TextStyle,
     content_mode: ContentMode,
@@ -1030,10 +1034,34 @@ impl Element for TerminalElement {
                     cursor_char,
                     selection,
                     cursor,
+                    command_markers,
+                    images,
                     ..
                 } = &self.terminal.read(cx).last_content;
                 let mode = *mode;
                 let display_offset = *display_offset;
+                let status_colors = theme.status();
+                let command_markers = command_markers
+                    .iter()
+                    .map(|marker| {
+                        let color = match marker.status {
+                            CommandStatus::Running => status_colors.info,
+                            CommandStatus::Finished { exit_code: Some(0) } => status_colors.success,
+                            CommandStatus::Finished { exit_code: None } => status_colors.hidden,
+                            CommandStatus::Finished { .. } => status_colors.error,
+                        };
+                        (marker.line.0 + display_offset as i32, color)
+                    })
+                    .collect();
+                let images = images
+                    .iter()
+                    .map(|image| {
+                        (
+                            DisplayCursor::from(image.point, display_offset),
+                            image.clone(),
+                        )
+                    })
+                    .collect();
 
                 // searches, highlights to a single range representations
                 let mut relative_highlighted_ranges = Vec::new();
@@ -1219,6 +1247,8 @@ impl Element for TerminalElement {
                     display_offset,
                     hyperlink_tooltip,
                     gutter,
+                    command_markers,
+                    images,
                     block_below_cursor_element,
                     base_text_style: text_style,
                     content_mode,
@@ -1302,6 +1332,17 @@ impl Element for TerminalElement {
                         rect.paint(origin, &layout.dimensions, window);
                     }
 
+                    for (line, color) in &layout.command_markers {
+                        let marker_bounds = Bounds::new(
+                            point(
+                                bounds.origin.x,
+                                origin.y + layout.dimensions.line_height * *line as f32,
+                            ),
+                            size(layout.gutter / 3., layout.dimensions.line_height),
+                        );
+                        window.paint_quad(fill(marker_bounds, *color));
+                    }
+
                     for (relative_highlighted_range, color) in &layout.relative_highlighted_ranges {
                         if let Some((start_y, highlighted_range_lines)) =
                             to_highlighted_range_lines(relative_highlighted_range, layout, origin)
@@ -1330,6 +1371,36 @@ impl Element for TerminalElement {
                     }
                     let text_paint_time = text_paint_start.elapsed();
 
+                    for (position, placement) in &layout.images {
+                        // Images keep their aspect ratio, filling as much of their cells as they can.
+                        let cell_width = layout.dimensions.cell_width;
+                        let line_height = layout.dimensions.line_height;
+                        let image_size = placement.image.size(0);
+                        let image_size = size(
+                            px(image_size.width.0 as f32),
+                            px(image_size.height.0 as f32),
+                        );
+                        let scale = (cell_width * placement.columns as f32 / image_size.width)
+                            .min(line_height * placement.lines as f32 / image_size.height);
+                        let image_bounds = Bounds::new(
+                            origin
+                                + point(
+                                    cell_width * position.col() as f32,
+                                    line_height * position.line() as f32,
+                                ),
+                            size(image_size.width * scale, image_size.height * scale),
+                        );
+                        window
+                            .paint_image(
+                                image_bounds,
+                                Corners::default(),
+                                placement.image.clone(),
+                                0,
+                                false,
+                            )
+                            .log_err();
+                    }
+
                     if let Some(text_to_mark) = &marked_text_cloned
                         && !text_to_mark.is_empty()
                         && let Some(ime_bounds) = layout.ime_cursor_bounds
@@ -31,7 +31,8 @@ use workspace::{
     ActivatePaneUp, ActivatePreviousPane, DraggedSelection, DraggedTab, ItemId, MoveItemToPane,
     MoveItemToPaneInDirection, MovePaneDown, MovePaneLeft, MovePaneRight, MovePaneUp, Pane,
     PaneGroup, SplitDirection, SplitDown, SplitLeft, SplitMode, SplitRight, SplitUp, SwapPaneDown,
-    SwapPaneLeft, SwapPaneRight, SwapPaneUp, ToggleZoom, Workspace,
+    SwapPaneLeft, SwapPaneRight, SwapPaneUp, TaskGraphStatus, TaskGraphStepState, ToggleZoom,
+    Workspace,
     dock::{DockPosition, Panel, PanelEvent, PanelHandle},
     item::SerializableItem,
     move_active_item, move_item, pane,
@@ -84,6 +85,8 @@ pub struct TerminalPanel {
     pending_serialization: Task<Option<()>>,
     pending_terminals_to_add: usize,
     deferred_tasks: HashMap<TaskId, Task<()>>,
+    /// Tasks with dependencies, spawned recently; cleared by the user once finished.
+    task_graphs: Vec<TaskGraphStatus>,
     assistant_enabled: bool,
     assistant_tab_bar_button: Option<AnyView>,
     active: bool,
@@ -104,6 +107,7 @@ impl TerminalPanel {
             height: None,
             pending_terminals_to_add: 0,
             deferred_tasks: HashMap::default(),
+            task_graphs: Vec::new(),
             assistant_enabled: false,
             assistant_tab_bar_button: None,
             active: false,
@@ -1157,6 +1161,84 @@ impl TerminalPanel {
             cx.notify();
         }
     }
+
+    fn update_task_graph_status(&mut self, status: TaskGraphStatus, cx: &mut Context<Self>) {
+        match self
+            .task_graphs
+            .iter_mut()
+            .find(|task_graph| task_graph.id == status.id)
+        {
+            Some(task_graph) => *task_graph = status,
+            None => self.task_graphs.push(status),
+        }
+        cx.notify();
+    }
+
+    fn render_task_graphs(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        if self.task_graphs.is_empty() {
+            return None;
+        }
+
+        let step_icon = |state: TaskGraphStepState| match state {
+            TaskGraphStepState::Pending => (IconName::Circle, Color::Muted),
+            TaskGraphStepState::Running => (IconName::PlayFilled, Color::Accent),
+            TaskGraphStepState::Succeeded => (IconName::Check, Color::Success),
+            TaskGraphStepState::Failed => (IconName::XCircle, Color::Error),
+            TaskGraphStepState::Skipped => (IconName::Dash, Color::Disabled),
+        };
+
+        Some(
+            v_flex()
+                .w_full()
+                .border_b_1()
+                .border_color(cx.theme().colors().border)
+                .children(self.task_graphs.iter().enumerate().map(|(ix, task_graph)| {
+                    let (icon, color) = if !task_graph.is_finished() {
+                        step_icon(TaskGraphStepState::Running)
+                    } else if task_graph.has_failed() {
+                        step_icon(TaskGraphStepState::Failed)
+                    } else {
+                        step_icon(TaskGraphStepState::Succeeded)
+                    };
+                    let id = task_graph.id.clone();
+
+                    h_flex()
+                        .id(("task-graph", ix))
+                        .w_full()
+                        .px_2()
+                        .py_0p5()
+                        .gap_2()
+                        .child(Icon::new(icon).size(IconSize::Small).color(color))
+                        .child(Label::new(task_graph.label.clone()).size(LabelSize::Small))
+                        .child(h_flex().flex_1().gap_1p5().overflow_x_hidden().children(
+                            task_graph.steps.iter().map(|step| {
+                                let (icon, color) = step_icon(step.state);
+                                h_flex()
+                                    .gap_0p5()
+                                    .child(Icon::new(icon).size(IconSize::XSmall).color(color))
+                                    .child(
+                                        Label::new(step.label.clone())
+                                            .size(LabelSize::XSmall)
+                                            .color(Color::Muted),
+                                    )
+                            }),
+                        ))
+                        .when(task_graph.is_finished(), |this| {
+                            this.child(
+                                IconButton::new(("dismiss-task-graph", ix), IconName::Close)
+                                    .icon_size(IconSize::XSmall)
+                                    .tooltip(Tooltip::text("Dismiss"))
+                                    .on_click(cx.listener(move |terminal_panel, _, _, cx| {
+                                        terminal_panel
+                                            .task_graphs
+                                            .retain(|task_graph| task_graph.id != id);
+                                        cx.notify();
+                                    })),
+                            )
+                        })
+                })),
+        )
+    }
 }
 
 /// Prepares a `SpawnInTerminal` by computing the command, args, and command_label
@@ -1484,6 +1566,7 @@ impl EventEmitter<PanelEvent> for TerminalPanel {}
 
 impl Render for TerminalPanel {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let task_graphs = self.render_task_graphs(cx);
         let registrar = cx
             .try_global::<workspace::PaneSearchBarCallbacks>()
             .map(|callbacks| {
@@ -1492,19 +1575,24 @@ impl Render for TerminalPanel {
             .unwrap_or_else(div);
         self.workspace
             .update(cx, |workspace, cx| {
-                registrar.size_full().child(self.center.render(
-                    workspace.zoomed_item(),
-                    &workspace::PaneRenderContext {
-                        follower_states: &HashMap::default(),
-                        active_call: workspace.active_call(),
-                        active_pane: &self.active_pane,
-                        app_state: workspace.app_state(),
-                        project: workspace.project(),
-                        workspace: &workspace.weak_handle(),
-                    },
-                    window,
-                    cx,
-                ))
+                registrar
+                    .size_full()
+                    .flex()
+                    .flex_col()
+                    .children(task_graphs)
+                    .child(self.center.render(
+                        workspace.zoomed_item(),
+                        &workspace::PaneRenderContext {
+                            follower_states: &HashMap::default(),
+                            active_call: workspace.active_call(),
+                            active_pane: &self.active_pane,
+                            app_state: workspace.app_state(),
+                            project: workspace.project(),
+                            workspace: &workspace.weak_handle(),
+                        },
+                        window,
+                        cx,
+                    ))
             })
             .ok()
             .map(|div| {
@@ -1818,6 +1906,17 @@ impl workspace::TerminalProvider for TerminalProvider {
             }
         })
     }
+
+    fn task_graph_status_changed(
+        &self,
+        status: TaskGraphStatus,
+        _window: &mut Window,
+        cx: &mut App,
+    ) {
+        self.0.update(cx, |terminal_panel, cx| {
+            terminal_panel.update_task_graph_status(status, cx);
+        });
+    }
 }
 
 struct InlineAssistTabBarButton {
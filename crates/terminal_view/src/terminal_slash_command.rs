@@ -78,20 +78,26 @@ impl SlashCommand for TerminalSlashCommand {
             return Task::ready(Err(anyhow::anyhow!("no active terminal")));
         };
 
-        let line_count = arguments
-            .get(0)
-            .and_then(|s| s.parse::<usize>().ok())
-            .unwrap_or(DEFAULT_CONTEXT_LINES);
-
-        let lines = active_terminal
-            .read(cx)
-            .entity()
-            .read(cx)
-            .last_n_non_empty_lines(line_count);
+        let line_count = arguments.get(0).and_then(|s| s.parse::<usize>().ok());
+        let terminal = active_terminal.read(cx).entity().read(cx);
 
         let mut text = String::new();
-        text.push_str("Terminal output:\n");
-        text.push_str(&lines.join("\n"));
+        // When the shell reports its commands, the last one's output is more relevant than the last lines on screen.
+        if let Some((block, output)) = terminal
+            .last_command_output()
+            .filter(|_| line_count.is_none())
+        {
+            match block.command() {
+                Some(command) => text.push_str(&format!("Output of `{command}`:\n")),
+                None => text.push_str("Output of the last command:\n"),
+            }
+            text.push_str(&output);
+        } else {
+            let lines =
+                terminal.last_n_non_empty_lines(line_count.unwrap_or(DEFAULT_CONTEXT_LINES));
+            text.push_str("Terminal output:\n");
+            text.push_str(&lines.join("\n"));
+        }
         let range = 0..text.len();
 
         Task::ready(Ok(SlashCommandOutput {
@@ -1,3 +1,4 @@
+mod asciicast_player;
 mod persistence;
 pub mod terminal_element;
 pub mod terminal_panel;
@@ -8,10 +9,10 @@ mod terminal_slash_command;
 use assistant_slash_command::SlashCommandRegistry;
 use editor::{Editor, EditorSettings, actions::SelectAll, blink_manager::BlinkManager};
 use gpui::{
-    Action, AnyElement, App, ClipboardEntry, DismissEvent, Entity, EventEmitter, FocusHandle,
-    Focusable, KeyContext, KeyDownEvent, Keystroke, MouseButton, MouseDownEvent, Pixels, Point,
-    Render, ScrollWheelEvent, Styled, Subscription, Task, WeakEntity, actions, anchored, deferred,
-    div,
+    Action, AnyElement, App, ClipboardEntry, ClipboardItem, DismissEvent, Entity, EventEmitter,
+    FocusHandle, Focusable, KeyContext, KeyDownEvent, Keystroke, MouseButton, MouseDownEvent,
+    Pixels, Point, Render, ScrollWheelEvent, Styled, Subscription, Task, WeakEntity, actions,
+    anchored, deferred, div,
 };
 use menu;
 use persistence::TERMINAL_DB;
@@ -29,9 +30,10 @@ use std::{
 };
 use task::TaskId;
 use terminal::{
-    Clear, Copy, Event, HoveredWord, MaybeNavigationTarget, Paste, ScrollLineDown, ScrollLineUp,
-    ScrollPageDown, ScrollPageUp, ScrollToBottom, ScrollToTop, ShowCharacterPalette, TaskState,
-    TaskStatus, Terminal, TerminalBounds, ToggleViMode,
+    Clear, Copy, CopyLastCommandOutput, Event, HoveredWord, MaybeNavigationTarget, Paste,
+    ScrollLineDown, ScrollLineUp, ScrollPageDown, ScrollPageUp, ScrollToBottom, ScrollToNextPrompt,
+    ScrollToPreviousPrompt, ScrollToTop, ShowCharacterPalette, TaskState, TaskStatus, Terminal,
+    TerminalBounds, ToggleRecording, ToggleViMode,
     alacritty_terminal::{
         index::Point as AlacPoint,
         term::{TermMode, point_to_viewport, search::RegexSearch},
@@ -44,17 +46,18 @@ use terminal_path_like_target::{hover_path_like_target, open_path_like_target};
 use terminal_scrollbar::TerminalScrollHandle;
 use terminal_slash_command::TerminalSlashCommand;
 use ui::{
-    ContextMenu, Divider, ScrollAxes, Scrollbars, Tooltip, WithScrollbar,
+    ContextMenu, Divider, Indicator, ScrollAxes, Scrollbars, Tooltip, WithScrollbar,
     prelude::*,
     scrollbars::{self, GlobalSetting, ScrollbarVisibility},
 };
-use util::ResultExt;
+use util::{ResultExt, paths};
 use workspace::{
     CloseActiveItem, NewCenterTerminal, NewTerminal, ToolbarItemLocation, Workspace, WorkspaceId,
     delete_unloaded_items,
     item::{
         BreadcrumbText, Item, ItemEvent, SerializableItem, TabContentParams, TabTooltipContent,
     },
+    notifications::DetachAndPromptErr,
     register_serializable_item,
     searchable::{
         Direction, SearchEvent, SearchOptions, SearchToken, SearchableItem, SearchableItemHandle,
@@ -87,6 +90,12 @@ actions!(
     [
         /// Reruns the last executed task in the terminal.
         RerunTask,
+        /// Plays or pauses the playback of a terminal recording.
+        TogglePlayback,
+        /// Moves the playback of a terminal recording backward by 5 seconds.
+        SeekBackward,
+        /// Moves the playback of a terminal recording forward by 5 seconds.
+        SeekForward,
     ]
 );
 
@@ -98,6 +107,7 @@ pub struct RenameTerminal;
 pub fn init(cx: &mut App) {
     assistant_slash_command::init(cx);
     terminal_panel::init(cx);
+    asciicast_player::init(cx);
 
     register_serializable_item::<TerminalView>(cx);
 
@@ -260,6 +270,9 @@ impl TerminalView {
             )
         });
 
+        // A persistent session's id is saved right away, so that it is reattached to even if
+        // nothing else about the terminal changes before Zed quits.
+        let needs_serialize = terminal.read(cx).persistent_session().is_some();
         let subscriptions = vec![
             focus_in,
             focus_out,
@@ -285,7 +298,7 @@ impl TerminalView {
             block_below_cursor: None,
             scroll_top: Pixels::ZERO,
             scroll_handle,
-            needs_serialize: false,
+            needs_serialize,
             custom_title: None,
             ime_state: None,
             self_handle: cx.entity().downgrade(),
@@ -746,6 +759,77 @@ impl TerminalView {
         cx.notify();
     }
 
+    fn scroll_to_previous_prompt(
+        &mut self,
+        _: &ScrollToPreviousPrompt,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.terminal
+            .update(cx, |term, _| term.scroll_to_previous_prompt());
+        cx.notify();
+    }
+
+    fn scroll_to_next_prompt(
+        &mut self,
+        _: &ScrollToNextPrompt,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.terminal
+            .update(cx, |term, _| term.scroll_to_next_prompt());
+        cx.notify();
+    }
+
+    fn copy_last_command_output(
+        &mut self,
+        _: &CopyLastCommandOutput,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some((_, output)) = self.terminal.read(cx).last_command_output() {
+            cx.write_to_clipboard(ClipboardItem::new_string(output));
+        }
+    }
+
+    fn toggle_recording(
+        &mut self,
+        _: &ToggleRecording,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let recording = self.terminal.update(cx, |terminal, _| {
+            if terminal.is_recording() {
+                terminal.stop_recording()
+            } else {
+                terminal.start_recording();
+                None
+            }
+        });
+        cx.notify();
+        let (Some(recording), Some(project)) = (recording, self.project.upgrade()) else {
+            return;
+        };
+
+        let project = project.read(cx);
+        let fs = project.fs().clone();
+        // Recordings are saved on this machine, even for terminals of remote projects.
+        let directory = self
+            .terminal
+            .read(cx)
+            .working_directory()
+            .filter(|_| project.is_local())
+            .unwrap_or_else(|| paths::home_dir().clone());
+        let path = cx.prompt_for_new_path(&directory, Some("recording.cast"));
+        cx.spawn_in(window, async move |_, _| {
+            let Some(path) = path.await?? else {
+                return Ok(());
+            };
+            fs.atomic_write(path, recording).await
+        })
+        .detach_and_prompt_err("Failed to save the recording", window, cx, |_, _, _| None);
+    }
+
     fn toggle_vi_mode(&mut self, _: &ToggleViMode, _: &mut Window, cx: &mut Context<Self>) {
         self.terminal.update(cx, |term, _| term.toggle_vi_mode());
         cx.notify();
@@ -1220,6 +1304,10 @@ impl Render for TerminalView {
             .on_action(cx.listener(TerminalView::scroll_page_down))
             .on_action(cx.listener(TerminalView::scroll_to_top))
             .on_action(cx.listener(TerminalView::scroll_to_bottom))
+            .on_action(cx.listener(TerminalView::scroll_to_previous_prompt))
+            .on_action(cx.listener(TerminalView::scroll_to_next_prompt))
+            .on_action(cx.listener(TerminalView::copy_last_command_output))
+            .on_action(cx.listener(TerminalView::toggle_recording))
             .on_action(cx.listener(TerminalView::toggle_vi_mode))
             .on_action(cx.listener(TerminalView::show_character_palette))
             .on_action(cx.listener(TerminalView::select_all))
@@ -1397,6 +1485,9 @@ impl Item for TerminalView {
                         )
                     }),
             )
+            .when(terminal.is_recording(), |this| {
+                this.child(Indicator::dot().color(Color::Error))
+            })
             .into_any()
     }
 
@@ -1418,11 +1509,16 @@ impl Item for TerminalView {
         cx: &mut Context<Self>,
     ) -> Vec<(SharedString, Box<dyn gpui::Action>)> {
         let terminal = self.terminal.read(cx);
+        let mut actions: Vec<(SharedString, Box<dyn gpui::Action>)> = Vec::new();
         if terminal.task().is_none() {
-            vec![("Rename".into(), Box::new(RenameTerminal))]
-        } else {
-            Vec::new()
+            actions.push(("Rename".into(), Box::new(RenameTerminal)));
+        }
+        if terminal.is_recording() {
+            actions.push(("Stop Recording".into(), Box::new(ToggleRecording)));
+        } else if terminal.can_record() {
+            actions.push(("Start Recording".into(), Box::new(ToggleRecording)));
         }
+        actions
     }
 
     fn buffer_kind(&self, _: &App) -> workspace::item::ItemBufferKind {
@@ -1565,6 +1661,7 @@ impl SerializableItem for TerminalView {
         let workspace_id = self.workspace_id?;
         let cwd = terminal.working_directory();
         let custom_title = self.custom_title.clone();
+        let session_id = terminal.persistent_session().map(ToOwned::to_owned);
         self.needs_serialize = false;
 
         Some(cx.background_spawn(async move {
@@ -1573,6 +1670,11 @@ impl SerializableItem for TerminalView {
                     .save_working_directory(item_id, workspace_id, cwd)
                     .await?;
             }
+            if let Some(session_id) = session_id {
+                TERMINAL_DB
+                    .save_session_id(item_id, workspace_id, session_id)
+                    .await?;
+            }
             TERMINAL_DB
                 .save_custom_title(item_id, workspace_id, custom_title)
                 .await?;
@@ -1593,7 +1695,7 @@ impl SerializableItem for TerminalView {
         cx: &mut App,
     ) -> Task<anyhow::Result<Entity<Self>>> {
         window.spawn(cx, async move |cx| {
-            let (cwd, custom_title) = cx
+            let (cwd, custom_title, session_id) = cx
                 .update(|_window, cx| {
                     let from_db = TERMINAL_DB
                         .get_working_directory(item_id, workspace_id)
@@ -1614,13 +1716,20 @@ impl SerializableItem for TerminalView {
                         .log_err()
                         .flatten()
                         .filter(|title| !title.trim().is_empty());
-                    (cwd, custom_title)
+                    let session_id = TERMINAL_DB
+                        .get_session_id(item_id, workspace_id)
+                        .log_err()
+                        .flatten();
+                    (cwd, custom_title, session_id)
                 })
                 .ok()
-                .unwrap_or((None, None));
+                .unwrap_or((None, None, None));
 
             let terminal = project
-                .update(cx, |project, cx| project.create_terminal_shell(cwd, cx))
+                .update(cx, |project, cx| match session_id {
+                    Some(session_id) => project.attach_terminal_shell(cwd, session_id, cx),
+                    None => project.create_terminal_shell(cwd, cx),
+                })
                 .await?;
             cx.update(|window, cx| {
                 cx.new(|cx| {
@@ -0,0 +1,119 @@
+use std::{ops::Range, path::PathBuf, sync::Arc};
+
+use collections::HashMap;
+use gpui::{App, AppContext as _, AsyncApp, Entity, Task};
+use language::{Buffer, Language, Point, ToPoint as _};
+use project::{Project, ProjectPath};
+use util::ResultExt as _;
+
+use crate::test_run::TestFramework;
+
+/// Discovery stops looking into further files past this number of candidates,
+/// to keep it bounded in very large projects.
+const MAX_CANDIDATE_FILES: usize = 5_000;
+
+/// A test found in a file, as reported by the runnables of the file's language.
+#[derive(Clone, Debug)]
+pub(crate) struct DiscoveredTest {
+    pub name: String,
+    pub framework: TestFramework,
+    /// Row of the runnable, the one the editor shows the run indicator at.
+    pub row: u32,
+    pub run_range: Range<usize>,
+    pub extra_captures: HashMap<String, String>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct DiscoveredFile {
+    pub project_path: ProjectPath,
+    pub abs_path: PathBuf,
+    pub language: Arc<Language>,
+    pub tests: Vec<DiscoveredTest>,
+}
+
+/// Finds the tests in all visible worktrees of a local project,
+/// running the languages' runnable queries over files that may contain tests.
+pub(crate) fn discover_tests(project: &Entity<Project>, cx: &mut App) -> Task<Vec<DiscoveredFile>> {
+    let project = project.read(cx);
+    let fs = project.fs().clone();
+    let languages = project.languages().clone();
+    let candidates = project
+        .visible_worktrees(cx)
+        .flat_map(|worktree| {
+            let worktree = worktree.read(cx);
+            let worktree_id = worktree.id();
+            worktree
+                .files(false, 0)
+                .filter_map(|entry| {
+                    let framework = TestFramework::for_path(&entry.path)?;
+                    let project_path = ProjectPath {
+                        worktree_id,
+                        path: entry.path.clone(),
+                    };
+                    Some((framework, project_path, worktree.absolutize(&entry.path)))
+                })
+                .collect::<Vec<_>>()
+        })
+        .take(MAX_CANDIDATE_FILES)
+        .collect::<Vec<_>>();
+
+    cx.spawn(async move |cx| {
+        let mut files = Vec::new();
+        for (framework, project_path, abs_path) in candidates {
+            let Some(text) = fs.load(&abs_path).await.log_err() else {
+                continue;
+            };
+            if !framework.may_contain_tests(&text) {
+                continue;
+            }
+            let Some(language) = languages
+                .load_language_for_file_path(project_path.path.as_std_path())
+                .await
+                .ok()
+            else {
+                continue;
+            };
+            let tests = tests_in_text(text, language.clone(), cx).await;
+            if !tests.is_empty() {
+                files.push(DiscoveredFile {
+                    project_path,
+                    abs_path,
+                    language,
+                    tests,
+                });
+            }
+        }
+        files
+    })
+}
+
+/// Parses the text in a detached buffer, so that discovery does not open the files in the project.
+async fn tests_in_text(
+    text: String,
+    language: Arc<Language>,
+    cx: &mut AsyncApp,
+) -> Vec<DiscoveredTest> {
+    let buffer = cx.new(|cx| Buffer::local(text, cx).with_language(language, cx));
+    let parsing_idle = buffer.read_with(cx, |buffer, _| buffer.parsing_idle());
+    parsing_idle.await;
+    buffer.read_with(cx, |buffer, _| {
+        let snapshot = buffer.snapshot();
+        snapshot
+            .runnable_ranges(0..snapshot.len())
+            .filter_map(|runnable| {
+                let framework = TestFramework::from_tags(&runnable.runnable.tags)?;
+                let name = snapshot
+                    .text_for_range(runnable.run_range.clone())
+                    .collect::<String>();
+                let Point { row, .. } = runnable.run_range.start.to_point(&snapshot);
+                Some(DiscoveredTest {
+                    name,
+                    framework,
+                    row,
+                    run_range: runnable.run_range,
+                    extra_captures: runnable.extra_captures,
+                })
+            })
+            .collect()
+    })
+}
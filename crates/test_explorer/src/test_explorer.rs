@@ -0,0 +1,1014 @@
+mod discovery;
+mod test_run;
+
+use std::{ops::Range, path::PathBuf, time::Duration};
+
+use anyhow::Result;
+use collections::{HashMap, HashSet};
+use db::kvp::KEY_VALUE_STORE;
+use editor::Editor;
+use fs::RemoveOptions;
+use gpui::{
+    Action, App, AsyncWindowContext, Context, Entity, EventEmitter, FocusHandle, Focusable, Pixels,
+    Render, Subscription, Task, UniformListScrollHandle, WeakEntity, Window, actions, px,
+    uniform_list,
+};
+use language::Point;
+use project::{Project, lsp_store::TaskProblem};
+use serde::{Deserialize, Serialize};
+use task::{Problem, ProblemSeverity, TaskId, TestOutcome, TestResult};
+use ui::{ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+use crate::{
+    discovery::{DiscoveredFile, DiscoveredTest, discover_tests},
+    test_run::{
+        TestBatch, TestFramework, TestKey, TestToRun, batch_tests, format_duration,
+        resolve_test_task,
+    },
+};
+
+const TEST_EXPLORER_PANEL_KEY: &str = "TestExplorerPanel";
+/// The label failed tests are reported under in the project diagnostics.
+const TEST_DIAGNOSTICS_LABEL: &str = "Test Explorer";
+const DISCOVERY_DEBOUNCE: Duration = Duration::from_secs(1);
+
+actions!(
+    test_explorer,
+    [
+        /// Toggles focus on the test explorer panel.
+        ToggleFocus,
+        /// Looks for tests in the project again.
+        RefreshTests,
+        /// Runs all tests found in the project.
+        RunAllTests,
+        /// Runs the tests that failed in their last run.
+        RunFailedTests,
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<TestExplorerPanel>(window, cx);
+        });
+        workspace.register_action(|workspace, action: &RunAllTests, window, cx| {
+            if let Some(panel) = workspace.panel::<TestExplorerPanel>(cx) {
+                panel.update(cx, |panel, cx| panel.run_all_tests(action, window, cx));
+            }
+        });
+        workspace.register_action(|workspace, action: &RunFailedTests, window, cx| {
+            if let Some(panel) = workspace.panel::<TestExplorerPanel>(cx) {
+                panel.update(cx, |panel, cx| panel.run_failed_tests(action, window, cx));
+            }
+        });
+    })
+    .detach();
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedTestExplorerPanel {
+    width: Option<Pixels>,
+}
+
+#[derive(Clone, Debug, Default)]
+enum TestState {
+    #[default]
+    NotRun,
+    Running,
+    Finished(TestResult),
+}
+
+impl TestState {
+    fn outcome(&self) -> Option<TestOutcome> {
+        match self {
+            Self::Finished(result) => Some(result.outcome),
+            Self::NotRun | Self::Running => None,
+        }
+    }
+}
+
+struct TestFile {
+    discovered: DiscoveredFile,
+    /// States of the file's tests, in the order they were discovered in.
+    states: Vec<TestState>,
+    expanded: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ListEntry {
+    File(usize),
+    Test { file_ix: usize, test_ix: usize },
+}
+
+/// Lists the tests found in the project, runs them and shows their results.
+pub struct TestExplorerPanel {
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    files: Vec<TestFile>,
+    visible_entries: Vec<ListEntry>,
+    selected_entry: Option<usize>,
+    discovering: bool,
+    /// Files whose test outcomes are currently shown in the editors' gutters.
+    files_with_outcomes: HashSet<PathBuf>,
+    width: Option<Pixels>,
+    position: DockPosition,
+    pending_serialization: Task<Option<()>>,
+    _discovery_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl TestExplorerPanel {
+    pub fn new(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let project = workspace.project().clone();
+        let workspace_handle = workspace.weak_handle();
+        cx.new(|cx| {
+            let project_subscription =
+                cx.subscribe_in(&project, window, |this, _, event, _, cx| match event {
+                    project::Event::WorktreeUpdatedEntries(_, changes) => {
+                        if changes
+                            .iter()
+                            .any(|(path, _, _)| TestFramework::for_path(path).is_some())
+                        {
+                            this.schedule_discovery(DISCOVERY_DEBOUNCE, cx);
+                        }
+                    }
+                    project::Event::WorktreeAdded(_) | project::Event::WorktreeRemoved(_) => {
+                        this.schedule_discovery(DISCOVERY_DEBOUNCE, cx);
+                    }
+                    _ => {}
+                });
+            let mut this = Self {
+                workspace: workspace_handle,
+                project,
+                focus_handle: cx.focus_handle(),
+                scroll_handle: UniformListScrollHandle::new(),
+                files: Vec::new(),
+                visible_entries: Vec::new(),
+                selected_entry: None,
+                discovering: false,
+                files_with_outcomes: HashSet::default(),
+                width: None,
+                position: DockPosition::Left,
+                pending_serialization: Task::ready(None),
+                _discovery_task: Task::ready(()),
+                _subscriptions: vec![project_subscription],
+            };
+            this.schedule_discovery(Duration::ZERO, cx);
+            this
+        })
+    }
+
+    pub fn load(
+        workspace: WeakEntity<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<Entity<Self>>> {
+        cx.spawn(async move |cx| {
+            let serialized_panel = if let Some(panel) = cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(TEST_EXPLORER_PANEL_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedTestExplorerPanel>(&panel)?)
+            } else {
+                None
+            };
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let panel = Self::new(workspace, window, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width.map(|width| width.round());
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        TEST_EXPLORER_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedTestExplorerPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn refresh_tests(&mut self, _: &RefreshTests, _: &mut Window, cx: &mut Context<Self>) {
+        self.schedule_discovery(Duration::ZERO, cx);
+    }
+
+    fn schedule_discovery(&mut self, delay: Duration, cx: &mut Context<Self>) {
+        // Tests are found by reading the files directly, which is only possible for local projects.
+        if !self.project.read(cx).is_local() {
+            return;
+        }
+        self.discovering = true;
+        self._discovery_task = cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(delay).await;
+            let Ok(files) = this.update(cx, |this, cx| discover_tests(&this.project, cx)) else {
+                return;
+            };
+            let files = files.await;
+            this.update(cx, |this, cx| this.set_discovered_files(files, cx))
+                .ok();
+        });
+        cx.notify();
+    }
+
+    /// Replaces the tests listed, keeping the results of the ones that were there before.
+    fn set_discovered_files(&mut self, files: Vec<DiscoveredFile>, cx: &mut Context<Self>) {
+        let mut previous_states = HashMap::<TestKey, TestState>::default();
+        let mut collapsed_files = HashSet::default();
+        for file in self.files.drain(..) {
+            if !file.expanded {
+                collapsed_files.insert(file.discovered.abs_path.clone());
+            }
+            for (test, state) in file.discovered.tests.iter().zip(file.states) {
+                previous_states
+                    .insert((file.discovered.abs_path.clone(), test.name.clone()), state);
+            }
+        }
+
+        self.files = files
+            .into_iter()
+            .map(|discovered| {
+                let states = discovered
+                    .tests
+                    .iter()
+                    .map(|test| {
+                        previous_states
+                            .remove(&(discovered.abs_path.clone(), test.name.clone()))
+                            .unwrap_or_default()
+                    })
+                    .collect();
+                TestFile {
+                    expanded: !collapsed_files.contains(&discovered.abs_path),
+                    discovered,
+                    states,
+                }
+            })
+            .collect();
+        self.discovering = false;
+        self.update_visible_entries(cx);
+        // Tests may have moved to other rows since their results were published.
+        self.publish_results(cx);
+    }
+
+    fn update_visible_entries(&mut self, cx: &mut Context<Self>) {
+        self.visible_entries.clear();
+        for (file_ix, file) in self.files.iter().enumerate() {
+            self.visible_entries.push(ListEntry::File(file_ix));
+            if file.expanded {
+                self.visible_entries.extend(
+                    (0..file.discovered.tests.len())
+                        .map(|test_ix| ListEntry::Test { file_ix, test_ix }),
+                );
+            }
+        }
+        if self
+            .selected_entry
+            .is_some_and(|ix| ix >= self.visible_entries.len())
+        {
+            self.selected_entry = None;
+        }
+        cx.notify();
+    }
+
+    fn toggle_file(&mut self, file_ix: usize, cx: &mut Context<Self>) {
+        let selected_entry = self.selected_entry.map(|ix| self.visible_entries[ix]);
+        if let Some(file) = self.files.get_mut(file_ix) {
+            file.expanded = !file.expanded;
+        }
+        self.update_visible_entries(cx);
+        self.selected_entry =
+            selected_entry.and_then(|entry| self.visible_entries.iter().position(|e| *e == entry));
+    }
+
+    fn all_tests(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.files.iter().enumerate().flat_map(|(file_ix, file)| {
+            (0..file.discovered.tests.len()).map(move |test_ix| (file_ix, test_ix))
+        })
+    }
+
+    fn has_failed_tests(&self) -> bool {
+        self.files.iter().any(|file| {
+            file.states
+                .iter()
+                .any(|state| state.outcome() == Some(TestOutcome::Failed))
+        })
+    }
+
+    fn is_running(&self) -> bool {
+        self.files.iter().any(|file| {
+            file.states
+                .iter()
+                .any(|state| matches!(state, TestState::Running))
+        })
+    }
+
+    fn run_all_tests(&mut self, _: &RunAllTests, window: &mut Window, cx: &mut Context<Self>) {
+        let tests = self.all_tests().collect();
+        self.run_tests(tests, window, cx);
+    }
+
+    fn run_failed_tests(
+        &mut self,
+        _: &RunFailedTests,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let tests = self
+            .all_tests()
+            .filter(|&(file_ix, test_ix)| {
+                self.files[file_ix].states[test_ix].outcome() == Some(TestOutcome::Failed)
+            })
+            .collect();
+        self.run_tests(tests, window, cx);
+    }
+
+    fn run_entry(&mut self, entry: ListEntry, window: &mut Window, cx: &mut Context<Self>) {
+        let tests = match entry {
+            ListEntry::File(file_ix) => self.all_tests().filter(|(ix, _)| *ix == file_ix).collect(),
+            ListEntry::Test { file_ix, test_ix } => vec![(file_ix, test_ix)],
+        };
+        self.run_tests(tests, window, cx);
+    }
+
+    /// Runs the tests given, grouping the ones that share a test command into a single task.
+    fn run_tests(
+        &mut self,
+        tests: Vec<(usize, usize)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let mut requests = Vec::<(DiscoveredFile, Vec<DiscoveredTest>)>::new();
+        for (file_ix, test_ix) in tests {
+            let file = &mut self.files[file_ix];
+            if matches!(file.states[test_ix], TestState::Running) {
+                continue;
+            }
+            file.states[test_ix] = TestState::Running;
+            let test = file.discovered.tests[test_ix].clone();
+            match requests.last_mut() {
+                Some((discovered, tests)) if discovered.abs_path == file.discovered.abs_path => {
+                    tests.push(test);
+                }
+                _ => requests.push((file.discovered.clone(), vec![test])),
+            }
+        }
+        if requests.is_empty() {
+            return;
+        }
+        cx.notify();
+
+        let project = self.project.clone();
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let mut tests_to_run = Vec::new();
+            for (file, tests) in requests {
+                // Tests from the same file share the task and differ only in the test they select.
+                match resolve_test_task(&project, &file, &tests[0], cx).await {
+                    Ok(task) => {
+                        tests_to_run.extend(tests.iter().map(|test| TestToRun {
+                            key: (file.abs_path.clone(), test.name.clone()),
+                            framework: test.framework,
+                            task: task.clone(),
+                        }));
+                    }
+                    Err(error) => {
+                        log::error!(
+                            "Failed to resolve the task to run tests in {:?}: {error:#}",
+                            file.abs_path
+                        );
+                        let keys = tests
+                            .iter()
+                            .map(|test| (file.abs_path.clone(), test.name.clone()))
+                            .collect::<Vec<_>>();
+                        this.update(cx, |this, cx| {
+                            for (abs_path, name) in &keys {
+                                this.set_test_state(abs_path, name, TestState::NotRun);
+                            }
+                            cx.notify();
+                        })
+                        .ok();
+                    }
+                }
+            }
+
+            let runs = batch_tests(tests_to_run, paths::temp_dir())
+                .into_iter()
+                .map(|batch| {
+                    Self::run_batch(
+                        this.clone(),
+                        workspace.clone(),
+                        project.clone(),
+                        batch,
+                        cx.clone(),
+                    )
+                });
+            futures::future::join_all(runs).await;
+        })
+        .detach();
+    }
+
+    async fn run_batch(
+        this: WeakEntity<Self>,
+        workspace: WeakEntity<Workspace>,
+        project: Entity<Project>,
+        batch: TestBatch,
+        mut cx: AsyncWindowContext,
+    ) {
+        let fs = project.read_with(&cx, |project, _| project.fs().clone());
+        if let Some(report_path) = &batch.report_path {
+            // Do not mistake the report of a previous run for this one's.
+            fs.remove_file(
+                report_path,
+                RemoveOptions {
+                    ignore_if_not_exists: true,
+                    ..RemoveOptions::default()
+                },
+            )
+            .await
+            .log_err();
+        }
+
+        let exit_status = match workspace.update_in(&mut cx, |workspace, window, cx| {
+            workspace.spawn_in_terminal(batch.task.clone(), window, cx)
+        }) {
+            Ok(run) => run.await,
+            Err(_) => return,
+        };
+        let succeeded = matches!(exit_status, Some(Ok(status)) if status.success());
+
+        let report = match &batch.report_path {
+            Some(report_path) => fs.load(report_path).await.log_err(),
+            None => project.read_with(&cx, |project, cx| task_output(project, &batch.task.id, cx)),
+        };
+        let results = report
+            .and_then(|report| batch.report_format.parse(&report).log_err())
+            .unwrap_or_default();
+        this.update(&mut cx, |this, cx| {
+            this.finish_batch(&batch, &results, succeeded, cx)
+        })
+        .ok();
+    }
+
+    fn finish_batch(
+        &mut self,
+        batch: &TestBatch,
+        results: &[TestResult],
+        succeeded: bool,
+        cx: &mut Context<Self>,
+    ) {
+        for (abs_path, name) in &batch.tests {
+            let state = match batch.framework.test_result(name, abs_path, results) {
+                Some(result) => TestState::Finished(result),
+                None if succeeded => TestState::NotRun,
+                None => TestState::Finished(TestResult {
+                    name: name.clone(),
+                    suite: None,
+                    outcome: TestOutcome::Failed,
+                    duration: None,
+                    message: Some(
+                        "The test runner has not reported a result for this test, see the task output."
+                            .to_string(),
+                    ),
+                }),
+            };
+            self.set_test_state(abs_path, name, state);
+        }
+        self.publish_results(cx);
+        cx.notify();
+    }
+
+    fn set_test_state(&mut self, abs_path: &PathBuf, name: &str, state: TestState) {
+        let Some(file) = self
+            .files
+            .iter_mut()
+            .find(|file| &file.discovered.abs_path == abs_path)
+        else {
+            return;
+        };
+        for (test, test_state) in file.discovered.tests.iter().zip(&mut file.states) {
+            if test.name == name {
+                *test_state = state.clone();
+            }
+        }
+    }
+
+    /// Shows the test outcomes in the editors' gutters, and reports the failed tests as diagnostics.
+    fn publish_results(&mut self, cx: &mut Context<Self>) {
+        let project = self.project.read(cx);
+        let inventory = project.task_store().read(cx).task_inventory().cloned();
+        let lsp_store = project.lsp_store();
+
+        let mut files_with_outcomes = HashSet::default();
+        let mut problems = Vec::new();
+        let mut outcomes_by_file = Vec::new();
+        for file in &self.files {
+            let mut outcomes = HashMap::default();
+            for (test, state) in file.discovered.tests.iter().zip(&file.states) {
+                let TestState::Finished(result) = state else {
+                    continue;
+                };
+                outcomes.insert(test.row, result.outcome);
+                if result.outcome == TestOutcome::Failed {
+                    let message = match result.message.as_deref().map(str::trim) {
+                        Some(message) if !message.is_empty() => {
+                            format!("Test `{}` failed:\n{message}", test.name)
+                        }
+                        _ => format!("Test `{}` failed", test.name),
+                    };
+                    problems.push(TaskProblem {
+                        abs_path: file.discovered.abs_path.clone(),
+                        source: "test".to_string(),
+                        problem: Problem {
+                            file: file.discovered.abs_path.to_string_lossy().into_owned(),
+                            line: test.row + 1,
+                            column: None,
+                            end_line: None,
+                            end_column: None,
+                            severity: ProblemSeverity::Error,
+                            code: None,
+                            message,
+                        },
+                    });
+                }
+            }
+            if !outcomes.is_empty() {
+                files_with_outcomes.insert(file.discovered.abs_path.clone());
+                outcomes_by_file.push((file.discovered.abs_path.clone(), outcomes));
+            }
+        }
+
+        if let Some(inventory) = inventory {
+            inventory.update(cx, |inventory, cx| {
+                for abs_path in self.files_with_outcomes.difference(&files_with_outcomes) {
+                    inventory.set_test_outcomes(abs_path.clone(), HashMap::default(), cx);
+                }
+                for (abs_path, outcomes) in outcomes_by_file {
+                    inventory.set_test_outcomes(abs_path, outcomes, cx);
+                }
+            });
+        }
+        self.files_with_outcomes = files_with_outcomes;
+        lsp_store
+            .update(cx, |lsp_store, cx| {
+                lsp_store.update_task_diagnostics(TEST_DIAGNOSTICS_LABEL.into(), problems, cx)
+            })
+            .log_err();
+    }
+
+    fn open_entry(&self, entry: ListEntry, window: &mut Window, cx: &mut Context<Self>) {
+        let (file_ix, row) = match entry {
+            ListEntry::File(file_ix) => (file_ix, None),
+            ListEntry::Test { file_ix, test_ix } => (
+                file_ix,
+                Some(self.files[file_ix].discovered.tests[test_ix].row),
+            ),
+        };
+        let project_path = self.files[file_ix].discovered.project_path.clone();
+        let Some(open_task) = self
+            .workspace
+            .update(cx, |workspace, cx| {
+                workspace.open_path(project_path, None, true, window, cx)
+            })
+            .log_err()
+        else {
+            return;
+        };
+        cx.spawn_in(window, async move |_, cx| {
+            let item = open_task.await?;
+            if let Some(row) = row
+                && let Some(editor) = item.downcast::<Editor>()
+            {
+                editor.update_in(cx, |editor, window, cx| {
+                    editor.go_to_singleton_buffer_point(Point::new(row, 0), window, cx);
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.visible_entries.is_empty() {
+            return;
+        }
+        let ix = match self.selected_entry {
+            Some(ix) => ix.saturating_sub(1),
+            None => self.visible_entries.len() - 1,
+        };
+        self.select_entry(ix, cx);
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.visible_entries.is_empty() {
+            return;
+        }
+        let ix = match self.selected_entry {
+            Some(ix) => (ix + 1).min(self.visible_entries.len() - 1),
+            None => 0,
+        };
+        self.select_entry(ix, cx);
+    }
+
+    fn select_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_entry = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, gpui::ScrollStrategy::Top);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(entry) = self.selected_entry.map(|ix| self.visible_entries[ix]) {
+            self.open_entry(entry, window, cx);
+        }
+    }
+
+    fn status_icon(outcome: Option<TestOutcome>, running: bool) -> Icon {
+        let (icon, color) = match outcome {
+            _ if running => (IconName::ArrowCircle, Color::Accent),
+            Some(TestOutcome::Passed) => (IconName::Check, Color::Success),
+            Some(TestOutcome::Failed) => (IconName::XCircle, Color::Error),
+            Some(TestOutcome::Skipped) => (IconName::Dash, Color::Muted),
+            None => (IconName::Circle, Color::Muted),
+        };
+        Icon::new(icon).size(IconSize::Small).color(color)
+    }
+
+    fn render_entries(&self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<AnyElement> {
+        range
+            .map(|ix| {
+                let entry = self.visible_entries[ix];
+                let (label, detail, status_icon, toggle) = match entry {
+                    ListEntry::File(file_ix) => {
+                        let file = &self.files[file_ix];
+                        let outcome = if file
+                            .states
+                            .iter()
+                            .any(|state| state.outcome() == Some(TestOutcome::Failed))
+                        {
+                            Some(TestOutcome::Failed)
+                        } else if file.states.iter().all(|state| state.outcome().is_some()) {
+                            Some(TestOutcome::Passed)
+                        } else {
+                            None
+                        };
+                        let running = file
+                            .states
+                            .iter()
+                            .any(|state| matches!(state, TestState::Running));
+                        (
+                            file.discovered
+                                .project_path
+                                .path
+                                .display(self.project.read(cx).path_style(cx))
+                                .to_string(),
+                            Some(format!("{} tests", file.discovered.tests.len())),
+                            Self::status_icon(outcome, running),
+                            Some(file.expanded),
+                        )
+                    }
+                    ListEntry::Test { file_ix, test_ix } => {
+                        let file = &self.files[file_ix];
+                        let state = &file.states[test_ix];
+                        let duration = match state {
+                            TestState::Finished(result) => result.duration.map(format_duration),
+                            TestState::NotRun | TestState::Running => None,
+                        };
+                        (
+                            file.discovered.tests[test_ix].name.clone(),
+                            duration,
+                            Self::status_icon(state.outcome(), matches!(state, TestState::Running)),
+                            None,
+                        )
+                    }
+                };
+                let indent_level = match entry {
+                    ListEntry::File(_) => 0,
+                    ListEntry::Test { .. } => 1,
+                };
+
+                ListItem::new(ix)
+                    .spacing(ListItemSpacing::Dense)
+                    .indent_level(indent_level)
+                    .indent_step_size(px(12.))
+                    .toggle(toggle)
+                    .when_some(
+                        match entry {
+                            ListEntry::File(file_ix) => Some(file_ix),
+                            ListEntry::Test { .. } => None,
+                        },
+                        |this, file_ix| {
+                            this.on_toggle(
+                                cx.listener(move |this, _, _, cx| this.toggle_file(file_ix, cx)),
+                            )
+                        },
+                    )
+                    .toggle_state(self.selected_entry == Some(ix))
+                    .start_slot(status_icon)
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.select_entry(ix, cx);
+                        this.open_entry(entry, window, cx);
+                    }))
+                    .child(Label::new(label).truncate())
+                    .end_slot(
+                        h_flex()
+                            .gap_1()
+                            .when_some(detail, |this, detail| {
+                                this.child(
+                                    Label::new(detail)
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted),
+                                )
+                            })
+                            .child(
+                                IconButton::new(("run-test-entry", ix), IconName::PlayOutlined)
+                                    .icon_size(IconSize::Small)
+                                    .tooltip(Tooltip::text(match entry {
+                                        ListEntry::File(_) => "Run Tests in File",
+                                        ListEntry::Test { .. } => "Run Test",
+                                    }))
+                                    .on_click(cx.listener(move |this, _, window, cx| {
+                                        this.run_entry(entry, window, cx);
+                                    })),
+                            ),
+                    )
+                    .into_any_element()
+            })
+            .collect()
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let mut counts = HashMap::<TestOutcome, usize>::default();
+        for state in self.files.iter().flat_map(|file| &file.states) {
+            if let Some(outcome) = state.outcome() {
+                *counts.entry(outcome).or_default() += 1;
+            }
+        }
+        let summary = if self.discovering && self.files.is_empty() {
+            "Looking for tests…".to_string()
+        } else if self.is_running() {
+            "Running tests…".to_string()
+        } else if counts.is_empty() {
+            let test_count = self.all_tests().count();
+            format!("{test_count} tests")
+        } else {
+            [
+                (TestOutcome::Passed, "passed"),
+                (TestOutcome::Failed, "failed"),
+                (TestOutcome::Skipped, "skipped"),
+            ]
+            .into_iter()
+            .filter_map(|(outcome, label)| {
+                let count = counts.get(&outcome)?;
+                Some(format!("{count} {label}"))
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+        };
+        let focus_handle = self.focus_handle.clone();
+        let action_button =
+            move |id: &'static str, icon, label: &'static str, action: &dyn Action| {
+                let action = action.boxed_clone();
+                let focus_handle = focus_handle.clone();
+                IconButton::new(id, icon)
+                    .icon_size(IconSize::Small)
+                    .tooltip({
+                        let action = action.boxed_clone();
+                        move |_window, cx| {
+                            Tooltip::for_action_in(label, action.as_ref(), &focus_handle, cx)
+                        }
+                    })
+                    .on_click(move |_, window, cx| window.dispatch_action(action.boxed_clone(), cx))
+            };
+
+        h_flex()
+            .w_full()
+            .p_1()
+            .gap_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                Label::new(summary)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .truncate(),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(action_button(
+                        "refresh-tests",
+                        IconName::RotateCw,
+                        "Refresh Tests",
+                        &RefreshTests,
+                    ))
+                    .child(
+                        action_button(
+                            "run-failed-tests",
+                            IconName::Rerun,
+                            "Run Failed Tests",
+                            &RunFailedTests,
+                        )
+                        .disabled(!self.has_failed_tests()),
+                    )
+                    .child(
+                        action_button(
+                            "run-all-tests",
+                            IconName::PlayOutlined,
+                            "Run All Tests",
+                            &RunAllTests,
+                        )
+                        .disabled(self.files.is_empty()),
+                    ),
+            )
+    }
+
+    /// The output of the selected test, when it did not pass.
+    fn render_test_output(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let ListEntry::Test { file_ix, test_ix } = self.visible_entries[self.selected_entry?]
+        else {
+            return None;
+        };
+        let TestState::Finished(result) = &self.files[file_ix].states[test_ix] else {
+            return None;
+        };
+        let message = result.message.as_deref()?.trim();
+        if result.outcome == TestOutcome::Passed || message.is_empty() {
+            return None;
+        }
+        Some(
+            div()
+                .id("test-output")
+                .max_h(px(240.))
+                .p_2()
+                .overflow_y_scroll()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .font_buffer(cx)
+                .text_ui_sm(cx)
+                .child(message.to_string()),
+        )
+    }
+}
+
+/// The output of the most recent terminal that ran the task.
+fn task_output(project: &Project, task_id: &TaskId, cx: &App) -> Option<String> {
+    project
+        .local_terminal_handles()
+        .iter()
+        .filter_map(|terminal| terminal.upgrade())
+        .filter(|terminal| {
+            terminal
+                .read(cx)
+                .task()
+                .is_some_and(|task| &task.spawned_task.id == task_id)
+        })
+        .last()
+        .map(|terminal| terminal.read(cx).get_content())
+}
+
+impl Render for TestExplorerPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_local = self.project.read(cx).is_local();
+        v_flex()
+            .id("test-explorer")
+            .key_context("TestExplorerPanel")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .on_action(cx.listener(Self::refresh_tests))
+            .on_action(cx.listener(Self::run_all_tests))
+            .on_action(cx.listener(Self::run_failed_tests))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::confirm))
+            .child(self.render_header(cx))
+            .map(|this| {
+                if self.files.is_empty() {
+                    let message = if !is_local {
+                        "Tests can only be discovered in local projects"
+                    } else if self.discovering {
+                        "Looking for tests…"
+                    } else {
+                        "No tests found"
+                    };
+                    this.child(
+                        h_flex()
+                            .size_full()
+                            .justify_center()
+                            .child(Label::new(message).color(Color::Muted)),
+                    )
+                } else {
+                    this.child(
+                        uniform_list(
+                            "test-explorer-entries",
+                            self.visible_entries.len(),
+                            cx.processor(|this, range: Range<usize>, _, cx| {
+                                this.render_entries(range, cx)
+                            }),
+                        )
+                        .track_scroll(&self.scroll_handle)
+                        .flex_grow()
+                        .p_1(),
+                    )
+                }
+            })
+            .children(self.render_test_output(cx))
+    }
+}
+
+impl Focusable for TestExplorerPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for TestExplorerPanel {}
+
+impl Panel for TestExplorerPanel {
+    fn persistent_name() -> &'static str {
+        "TestExplorerPanel"
+    }
+
+    fn panel_key() -> &'static str {
+        TEST_EXPLORER_PANEL_KEY
+    }
+
+    fn position(&self, _: &Window, _: &App) -> DockPosition {
+        self.position
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        self.position = position;
+        cx.notify();
+    }
+
+    fn size(&self, _: &Window, _: &App) -> Pixels {
+        self.width.unwrap_or(px(300.))
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, _: &App) -> Option<IconName> {
+        Some(IconName::ListTree)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Test Explorer")
+    }
+
+    fn icon_label(&self, _: &Window, _: &App) -> Option<String> {
+        let failed_count = self
+            .files
+            .iter()
+            .flat_map(|file| &file.states)
+            .filter(|state| state.outcome() == Some(TestOutcome::Failed))
+            .count();
+        (failed_count > 0).then(|| failed_count.to_string())
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        9
+    }
+}
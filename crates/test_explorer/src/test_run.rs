@@ -0,0 +1,628 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{Context as _, Result};
+use gpui::{AsyncApp, Entity};
+use language::RunnableTag;
+use project::{Location, Project};
+use task::{
+    SpawnInTerminal, TaskId, TaskVariables, TestOutcome, TestReportFormat, TestResult, VariableName,
+};
+use util::rel_path::RelPath;
+
+use crate::discovery::{DiscoveredFile, DiscoveredTest};
+
+/// Test runners whose tests are discovered from runnables, and whose structured output can be read back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum TestFramework {
+    Cargo,
+    Go,
+    Pytest,
+}
+
+impl TestFramework {
+    /// The runnable tag languages assign to a single test of this framework.
+    pub(crate) fn runnable_tag(self) -> &'static str {
+        match self {
+            Self::Cargo => "rust-test",
+            Self::Go => "go-test",
+            Self::Pytest => "python-pytest-method",
+        }
+    }
+
+    pub(crate) fn from_tags(tags: &[RunnableTag]) -> Option<Self> {
+        [Self::Cargo, Self::Go, Self::Pytest]
+            .into_iter()
+            .find(|framework| tags.iter().any(|tag| tag.0 == framework.runnable_tag()))
+    }
+
+    /// Rules out the files that cannot contain tests of any framework, before they get opened and parsed.
+    pub(crate) fn for_path(path: &RelPath) -> Option<Self> {
+        let file_stem = path.file_stem()?;
+        match path.extension()? {
+            "rs" => Some(Self::Cargo),
+            "go" if file_stem.ends_with("_test") => Some(Self::Go),
+            "py" if file_stem.starts_with("test_") || file_stem.ends_with("_test") => {
+                Some(Self::Pytest)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the file contents look like they could contain tests; most Rust files do not.
+    pub(crate) fn may_contain_tests(self, text: &str) -> bool {
+        match self {
+            Self::Cargo => text.contains("#[test") || text.contains("::test"),
+            Self::Go | Self::Pytest => true,
+        }
+    }
+
+    /// The format the framework reports results in, unless asked for a more detailed one.
+    fn report_format(self) -> TestReportFormat {
+        match self {
+            Self::Cargo => TestReportFormat::Libtest,
+            Self::Go => TestReportFormat::GoTestJson,
+            Self::Pytest => TestReportFormat::JunitXml,
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Cargo => "cargo",
+            Self::Go => "go",
+            Self::Pytest => "pytest",
+        }
+    }
+
+    /// The argument selecting a single test, built from the one of a test in the same file.
+    /// Tasks of all supported frameworks pass it as their last argument.
+    fn test_filter(self, test_name: &str, file_test_filter: &str) -> String {
+        match self {
+            Self::Cargo | Self::Go => test_name.to_string(),
+            Self::Pytest => match file_test_filter.rsplit_once("::") {
+                Some((file, _)) => format!("{file}::{test_name}"),
+                None => format!("{file_test_filter}::{test_name}"),
+            },
+        }
+    }
+
+    /// Finds the result of the test among all the results reported by a run.
+    pub(crate) fn test_result(
+        self,
+        test_name: &str,
+        abs_path: &Path,
+        results: &[TestResult],
+    ) -> Option<TestResult> {
+        let module = abs_path.file_stem().and_then(|stem| stem.to_str());
+        let rust_module_path = match self {
+            Self::Cargo => rust_module_path(abs_path),
+            Self::Go | Self::Pytest => None,
+        };
+        let mut matching_results = results
+            .iter()
+            .filter(|result| match self {
+                Self::Cargo => {
+                    let Some(prefix) = result.name.strip_suffix(test_name) else {
+                        return false;
+                    };
+                    let Some(prefix) = prefix
+                        .strip_suffix("::")
+                        .or(prefix.is_empty().then_some(""))
+                    else {
+                        return false;
+                    };
+                    // Tests are usually declared in a `tests` module nested into the file's module.
+                    rust_module_path.as_deref().is_none_or(|module_path| {
+                        module_path.is_empty()
+                            || prefix
+                                .strip_prefix(module_path)
+                                .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+                    })
+                }
+                Self::Go => result.name == test_name,
+                Self::Pytest => {
+                    let same_name = result.name == test_name
+                        || result
+                            .name
+                            .strip_prefix(test_name)
+                            .is_some_and(|parameters| parameters.starts_with('['));
+                    same_name
+                        && result
+                            .suite
+                            .as_deref()
+                            .zip(module)
+                            .is_none_or(|(suite, module)| {
+                                suite.split('.').any(|part| part == module)
+                            })
+                }
+            })
+            .collect::<Vec<_>>();
+        if self == Self::Cargo {
+            // Crate roots cannot tell their own tests from the ones of the files declared as their submodules,
+            // so prefer the tests declared closest to the file's module.
+            let depth = |result: &TestResult| result.name.matches("::").count();
+            if let Some(min_depth) = matching_results.iter().map(|result| depth(result)).min() {
+                matching_results.retain(|result| depth(result) == min_depth);
+            }
+        }
+
+        // Parametrized tests are reported together.
+        let mut matching_results = matching_results.into_iter();
+        let mut result = matching_results.next()?.clone();
+        result.name = test_name.to_string();
+        for other in matching_results {
+            result.outcome = match (result.outcome, other.outcome) {
+                (TestOutcome::Failed, _) | (_, TestOutcome::Failed) => TestOutcome::Failed,
+                (TestOutcome::Passed, _) | (_, TestOutcome::Passed) => TestOutcome::Passed,
+                (TestOutcome::Skipped, TestOutcome::Skipped) => TestOutcome::Skipped,
+            };
+            result.duration = match (result.duration, other.duration) {
+                (Some(duration), Some(other_duration)) => Some(duration + other_duration),
+                (duration, other_duration) => duration.or(other_duration),
+            };
+            if let Some(message) = &other.message {
+                let result_message = result.message.get_or_insert_default();
+                if !result_message.is_empty() {
+                    result_message.push('\n');
+                }
+                result_message.push_str(message);
+            }
+        }
+        Some(result)
+    }
+}
+
+/// The path of the module a Rust file defines, relative to its crate root,
+/// as libtest prefixes the names of the tests in it: `parser::lexer` for `src/parser/lexer.rs`.
+///
+/// Returns an empty path for the crate roots, and `None` for the files outside of the usual cargo layout.
+fn rust_module_path(abs_path: &Path) -> Option<String> {
+    let components = abs_path
+        .components()
+        .map(|component| component.as_os_str().to_str())
+        .collect::<Option<Vec<_>>>()?;
+    let root_ix = components
+        .iter()
+        .rposition(|component| matches!(*component, "src" | "tests" | "benches" | "examples"))?;
+    let mut modules = components[root_ix + 1..].to_vec();
+    let file_name = modules.pop()?;
+    modules.push(Path::new(file_name).file_stem()?.to_str()?);
+
+    // Every file directly in these directories is a crate root, as is the `main.rs` of their subdirectories.
+    let mut crate_roots = components[root_ix] != "src";
+    if !crate_roots && modules.len() > 1 && modules[0] == "bin" {
+        modules.remove(0);
+        crate_roots = true;
+    }
+    if crate_roots {
+        if modules.len() == 1 {
+            return Some(String::new());
+        }
+        modules.remove(0);
+    }
+
+    if modules.last() == Some(&"mod") || modules.len() == 1 && matches!(modules[0], "lib" | "main")
+    {
+        modules.pop();
+    }
+    Some(modules.join("::"))
+}
+
+/// Identifies a test across discoveries: its file and its name.
+pub(crate) type TestKey = (PathBuf, String);
+
+/// A test to run, along with the task resolved for it or for another test from the same file.
+pub(crate) struct TestToRun {
+    pub key: TestKey,
+    pub framework: TestFramework,
+    pub task: SpawnInTerminal,
+}
+
+/// Tests run by a single test runner invocation.
+#[derive(Debug)]
+pub(crate) struct TestBatch {
+    pub framework: TestFramework,
+    pub task: SpawnInTerminal,
+    pub report_format: TestReportFormat,
+    /// The file the report is written to, for the formats not printed to the output.
+    pub report_path: Option<PathBuf>,
+    pub tests: Vec<TestKey>,
+}
+
+/// Groups the tests that can be run by the same command together,
+/// and makes their commands produce reports Zed can read.
+pub(crate) fn batch_tests(tests: Vec<TestToRun>, report_dir: &Path) -> Vec<TestBatch> {
+    let mut batches = Vec::<(TestBatch, Vec<String>)>::new();
+    for test in tests {
+        let Some(file_test_filter) = test.task.args.last() else {
+            continue;
+        };
+        let filter = test.framework.test_filter(&test.key.1, file_test_filter);
+        let common_args = &test.task.args[..test.task.args.len() - 1];
+        let batch = batches.iter_mut().find(|(batch, _)| {
+            batch.framework == test.framework
+                && batch.task.command == test.task.command
+                && batch.task.args[..batch.task.args.len() - 1] == *common_args
+                && batch.task.cwd == test.task.cwd
+        });
+        match batch {
+            Some((batch, filters)) => {
+                batch.tests.push(test.key);
+                filters.push(filter);
+            }
+            None => batches.push((
+                TestBatch {
+                    framework: test.framework,
+                    task: test.task,
+                    report_format: test.framework.report_format(),
+                    report_path: None,
+                    tests: vec![test.key],
+                },
+                vec![filter],
+            )),
+        }
+    }
+
+    batches
+        .into_iter()
+        .enumerate()
+        .map(|(ix, (mut batch, filters))| {
+            let task = &mut batch.task;
+            task.args.pop();
+            match batch.framework {
+                TestFramework::Cargo => {
+                    task.args.retain(|arg| arg != "--nocapture");
+                    if !task.args.iter().any(|arg| arg == "--") {
+                        task.args.push("--".to_string());
+                    }
+                    task.args.extend(filters);
+                    // libtest's JSON output is unstable, so it is only requested from the tasks
+                    // that allow unstable features themselves. The default output lacks the durations.
+                    if task.env.contains_key("RUSTC_BOOTSTRAP") {
+                        task.args.extend(
+                            [
+                                "-Z",
+                                "unstable-options",
+                                "--format",
+                                "json",
+                                "--report-time",
+                            ]
+                            .map(ToOwned::to_owned),
+                        );
+                        batch.report_format = TestReportFormat::LibtestJson;
+                    }
+                }
+                TestFramework::Go => {
+                    let json_arg_ix = task
+                        .args
+                        .iter()
+                        .position(|arg| arg == "test")
+                        .map_or(0, |ix| ix + 1);
+                    task.args.insert(json_arg_ix, "-json".to_string());
+                    task.args.push(format!("'^({})$'", filters.join("|")));
+                }
+                TestFramework::Pytest => {
+                    let report_path = report_dir.join(format!("zed-test-report-{ix}.xml"));
+                    task.args.extend(filters);
+                    task.args
+                        .push(format!("--junitxml={}", report_path.to_string_lossy()));
+                    batch.report_path = Some(report_path);
+                }
+            }
+
+            let label = match batch.tests.as_slice() {
+                [(_, test_name)] => format!("Test {test_name}"),
+                tests => format!("{} tests ({})", tests.len(), batch.framework.name()),
+            };
+            task.id = TaskId(format!("test_explorer_{}_{ix}", batch.framework.name()));
+            task.full_label = label.clone();
+            task.label = label;
+            task.command_label = task
+                .command
+                .iter()
+                .chain(&task.args)
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(" ");
+            batch
+        })
+        .collect()
+}
+
+/// Resolves the task the language provides for running a test, the same one the editor's run indicator spawns.
+pub(crate) async fn resolve_test_task(
+    project: &Entity<Project>,
+    file: &DiscoveredFile,
+    test: &DiscoveredTest,
+    cx: &mut AsyncApp,
+) -> Result<SpawnInTerminal> {
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer(file.project_path.clone(), cx)
+        })
+        .await?;
+    let (location, buffer_file) = buffer.read_with(cx, |buffer, _| {
+        let start = test.run_range.start.min(buffer.len());
+        let end = test.run_range.end.min(buffer.len());
+        let location = Location {
+            buffer: buffer.clone(),
+            range: buffer.anchor_before(start)..buffer.anchor_after(end),
+        };
+        (location, buffer.file().cloned())
+    });
+    let captured_variables = test
+        .extra_captures
+        .iter()
+        .map(|(name, value)| (VariableName::Custom(name.clone().into()), value.clone()))
+        .collect::<TaskVariables>();
+
+    let (task_context, inventory) = project.update(cx, |project, cx| {
+        let task_store = project.task_store().clone();
+        let inventory = task_store.read(cx).task_inventory().cloned();
+        let task_context = task_store.update(cx, |task_store, cx| {
+            task_store.task_context_for_location(captured_variables, location, cx)
+        });
+        (task_context, inventory)
+    });
+    let task_context = task_context
+        .await
+        .context("resolving the task context for a test")?;
+    let inventory = inventory.context("no task inventory in the project")?;
+    let templates = inventory
+        .update(cx, |inventory, cx| {
+            inventory.list_tasks(
+                buffer_file,
+                Some(file.language.clone()),
+                Some(file.project_path.worktree_id),
+                cx,
+            )
+        })
+        .await;
+
+    let tag = test.framework.runnable_tag();
+    // Same as for the run indicators: worktree tasks take precedence over the global and language ones.
+    let (source_kind, template) = templates
+        .into_iter()
+        .filter(|(_, template)| template.tags.iter().any(|template_tag| template_tag == tag))
+        .min_by(|(a, _), (b, _)| a.cmp(b))
+        .with_context(|| format!("no task is bound to the `{tag}` runnable tag"))?;
+    let resolved_task = template
+        .resolve_task(&source_kind.to_id_base(), &task_context)
+        .with_context(|| format!("resolving task `{}`", template.label))?;
+    Ok(resolved_task.resolved)
+}
+
+/// Formats the duration for display, e.g. `12ms` or `1.5s`.
+pub(crate) fn format_duration(duration: Duration) -> String {
+    if duration < Duration::from_secs(1) {
+        format!("{}ms", duration.as_millis())
+    } else {
+        format!("{:.1}s", duration.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn test_to_run(file: &str, name: &str, framework: TestFramework, args: &[&str]) -> TestToRun {
+        TestToRun {
+            key: (PathBuf::from(file), name.to_string()),
+            framework,
+            task: SpawnInTerminal {
+                command: Some(framework.name().to_string()),
+                args: args.iter().map(|arg| arg.to_string()).collect(),
+                cwd: Some(PathBuf::from("/project")),
+                ..SpawnInTerminal::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_batching() {
+        let cargo_args = ["test", "-p", "demo", "--", "--nocapture", "first"];
+        let batches = batch_tests(
+            vec![
+                test_to_run(
+                    "/project/src/a.rs",
+                    "first",
+                    TestFramework::Cargo,
+                    &cargo_args,
+                ),
+                test_to_run(
+                    "/project/src/a.rs",
+                    "second",
+                    TestFramework::Cargo,
+                    &cargo_args,
+                ),
+                test_to_run(
+                    "/project/src/b.rs",
+                    "third",
+                    TestFramework::Cargo,
+                    &["test", "-p", "other", "--", "--nocapture", "third"],
+                ),
+                test_to_run(
+                    "/project/demo_test.go",
+                    "TestA",
+                    TestFramework::Go,
+                    &["test", "-run", "\\^TestA\\$"],
+                ),
+                test_to_run(
+                    "/project/demo_test.go",
+                    "TestB",
+                    TestFramework::Go,
+                    &["test", "-run", "\\^TestA\\$"],
+                ),
+                test_to_run(
+                    "/project/tests/test_demo.py",
+                    "test_b",
+                    TestFramework::Pytest,
+                    &["-m", "pytest", "tests/test_demo.py::test_a"],
+                ),
+            ],
+            Path::new("/tmp"),
+        );
+
+        let summary = batches
+            .iter()
+            .map(|batch| {
+                (
+                    batch.task.label.as_str(),
+                    batch.task.args.join(" "),
+                    batch.tests.len(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "2 tests (cargo)",
+                    "test -p demo -- first second".to_string(),
+                    2
+                ),
+                ("Test third", "test -p other -- third".to_string(), 1),
+                (
+                    "2 tests (go)",
+                    "test -json -run '^(TestA|TestB)$'".to_string(),
+                    2
+                ),
+                (
+                    "Test test_b",
+                    "-m pytest tests/test_demo.py::test_b --junitxml=/tmp/zed-test-report-3.xml"
+                        .to_string(),
+                    1
+                ),
+            ]
+        );
+        assert_eq!(batches[0].report_format, TestReportFormat::Libtest);
+        assert_eq!(batches[0].task.env.get("RUSTC_BOOTSTRAP"), None);
+        assert_eq!(
+            batches[3].report_path,
+            Some(PathBuf::from("/tmp/zed-test-report-3.xml"))
+        );
+    }
+
+    #[test]
+    fn test_batching_with_unstable_libtest_output() {
+        let mut test = test_to_run(
+            "/project/src/a.rs",
+            "first",
+            TestFramework::Cargo,
+            &["test", "-p", "demo", "--", "first"],
+        );
+        test.task
+            .env
+            .insert("RUSTC_BOOTSTRAP".to_string(), "1".to_string());
+
+        let batches = batch_tests(vec![test], Path::new("/tmp"));
+        assert_eq!(batches.len(), 1);
+        assert_eq!(
+            batches[0].task.args.join(" "),
+            "test -p demo -- first -Z unstable-options --format json --report-time"
+        );
+        assert_eq!(batches[0].report_format, TestReportFormat::LibtestJson);
+    }
+
+    #[test]
+    fn test_rust_module_path() {
+        let module_path = |path: &str| rust_module_path(Path::new(path));
+        assert_eq!(module_path("/project/src/lib.rs").as_deref(), Some(""));
+        assert_eq!(module_path("/project/src/main.rs").as_deref(), Some(""));
+        assert_eq!(
+            module_path("/project/src/parser.rs").as_deref(),
+            Some("parser")
+        );
+        assert_eq!(
+            module_path("/project/src/parser/mod.rs").as_deref(),
+            Some("parser")
+        );
+        assert_eq!(
+            module_path("/project/src/parser/lexer.rs").as_deref(),
+            Some("parser::lexer")
+        );
+        assert_eq!(module_path("/project/src/bin/tool.rs").as_deref(), Some(""));
+        assert_eq!(
+            module_path("/project/src/bin/tool/main.rs").as_deref(),
+            Some("")
+        );
+        assert_eq!(
+            module_path("/project/src/bin/tool/args.rs").as_deref(),
+            Some("args")
+        );
+        assert_eq!(
+            module_path("/project/tests/integration.rs").as_deref(),
+            Some("")
+        );
+        assert_eq!(
+            module_path("/project/tests/integration/helpers.rs").as_deref(),
+            Some("helpers")
+        );
+        assert_eq!(module_path("/project/build.rs"), None);
+    }
+
+    #[test]
+    fn test_matching_results() {
+        let result = |name: &str, suite: Option<&str>, outcome, millis| TestResult {
+            name: name.to_string(),
+            suite: suite.map(ToOwned::to_owned),
+            outcome,
+            duration: Some(Duration::from_millis(millis)),
+            message: (outcome == TestOutcome::Failed).then(|| format!("{name} failed")),
+        };
+        let results = [
+            result("tests::parses", None, TestOutcome::Passed, 5),
+            result("tests::parses_empty", None, TestOutcome::Failed, 5),
+            result("lexer::tests::parses", None, TestOutcome::Failed, 1),
+            result("parser::tests::parses", None, TestOutcome::Passed, 2),
+            result(
+                "test_sum[1]",
+                Some("tests.test_math"),
+                TestOutcome::Passed,
+                1,
+            ),
+            result(
+                "test_sum[2]",
+                Some("tests.test_math"),
+                TestOutcome::Failed,
+                2,
+            ),
+            result("test_sum", Some("tests.test_other"), TestOutcome::Passed, 3),
+        ];
+
+        let cargo_result = TestFramework::Cargo
+            .test_result("parses", Path::new("/project/src/lib.rs"), &results)
+            .unwrap();
+        assert_eq!(cargo_result.outcome, TestOutcome::Passed);
+        assert_eq!(
+            TestFramework::Cargo.test_result("missing", Path::new("/project/src/lib.rs"), &results),
+            None
+        );
+        let module_result = TestFramework::Cargo
+            .test_result("parses", Path::new("/project/src/parser.rs"), &results)
+            .unwrap();
+        assert_eq!(module_result.outcome, TestOutcome::Passed);
+        assert_eq!(module_result.duration, Some(Duration::from_millis(2)));
+
+        let pytest_result = TestFramework::Pytest
+            .test_result(
+                "test_sum",
+                Path::new("/project/tests/test_math.py"),
+                &results,
+            )
+            .unwrap();
+        assert_eq!(
+            pytest_result,
+            TestResult {
+                name: "test_sum".to_string(),
+                suite: Some("tests.test_math".to_string()),
+                outcome: TestOutcome::Failed,
+                duration: Some(Duration::from_millis(3)),
+                message: Some("test_sum[2] failed".to_string()),
+            }
+        );
+    }
+}
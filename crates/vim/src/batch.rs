@@ -0,0 +1,644 @@
+use anyhow::{Context as _, Result};
+use collections::HashSet;
+use editor::Editor;
+use futures::{
+    FutureExt as _,
+    channel::oneshot,
+    future::{LocalBoxFuture, Shared},
+};
+use gpui::{Action, App, AsyncWindowContext, Context, Entity, Focusable as _, Window};
+use itertools::Itertools as _;
+use language::Point;
+use project::ProjectPath;
+use std::{future::Future, path::Path};
+use util::{paths::PathMatcher, rel_path::RelPath};
+use workspace::{
+    ItemHandle, Pane, Workspace,
+    notifications::{NotifyResultExt, NotifyTaskExt},
+};
+
+use crate::{Vim, VimAddon, command::command_interceptor, state::VimGlobals};
+
+/// Runs ex commands on each of a kind of targets, as in `:bufdo %s/a/b/g | update`.
+#[derive(Clone, Debug, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct BatchCommand {
+    targets: BatchTargets,
+    commands: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum BatchTargets {
+    /// Each open buffer, for `:bufdo`.
+    Buffers,
+    /// The active item of each pane, for `:windo`.
+    Windows,
+    /// Each item of the active pane, for `:tabdo`.
+    Tabs,
+    /// Each excerpt of the project search results or diagnostics, for `:cdo`.
+    QuickfixEntries,
+    /// Each file of the project search results or diagnostics, for `:cfdo`.
+    QuickfixFiles,
+    /// Each file of the argument list, for `:argdo`.
+    Arguments,
+}
+
+/// Sets the argument list to the given files, expanding globs, or shows it when no files are
+/// given.
+#[derive(Clone, Debug, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct VimArgs {
+    pub files: Vec<String>,
+}
+
+pub(crate) type PendingCommand = Shared<LocalBoxFuture<'static, ()>>;
+
+pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
+    Vim::action(editor, cx, |vim, action: &BatchCommand, window, cx| {
+        action.run(vim, window, cx)
+    });
+    Vim::action(editor, cx, |vim, action: &VimArgs, window, cx| {
+        vim.set_arguments(action, window, cx)
+    });
+}
+
+impl Vim {
+    /// Runs the asynchronous part of an ex command, keeping track of it so that batch commands
+    /// only run the next command once it is done.
+    pub(crate) fn track_command(
+        &mut self,
+        command: impl Future<Output = impl Sized> + 'static,
+        cx: &mut Context<Self>,
+    ) {
+        let command = command.map(|_| ()).boxed_local().shared();
+        cx.foreground_executor().spawn(command.clone()).detach();
+        self.pending_command = Some(command);
+    }
+
+    fn set_arguments(&mut self, action: &VimArgs, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(workspace) = self.workspace(window, cx) else {
+            return;
+        };
+
+        if action.files.is_empty() {
+            let path_style = workspace.read(cx).project().read(cx).path_style(cx);
+            let arguments = &cx.global::<VimGlobals>().argument_list;
+            self.status_label = (!arguments.is_empty()).then(|| {
+                arguments
+                    .iter()
+                    .map(|path| path.path.display(path_style))
+                    .join(" ")
+                    .into()
+            });
+            cx.notify();
+            return;
+        }
+
+        let arguments = expand_arguments(&action.files, workspace.read(cx), cx);
+        if let Some(arguments) =
+            workspace.update(cx, |workspace, cx| arguments.notify_err(workspace, cx))
+        {
+            Vim::globals(cx).argument_list = arguments;
+        }
+    }
+}
+
+impl BatchCommand {
+    pub(crate) fn parse(targets: BatchTargets, args: &str) -> Option<Box<dyn Action>> {
+        let commands = split_commands(args);
+        if commands.is_empty() {
+            return None;
+        }
+        Some(Self { targets, commands }.boxed_clone())
+    }
+
+    fn run(&self, vim: &mut Vim, window: &mut Window, cx: &mut Context<Vim>) {
+        let Some(workspace) = vim.workspace(window, cx) else {
+            return;
+        };
+        let targets = self.targets.collect(workspace.read(cx), cx);
+        let Some(targets) = workspace.update(cx, |workspace, cx| targets.notify_err(workspace, cx))
+        else {
+            return;
+        };
+
+        let commands = self.commands.clone();
+        let weak_workspace = workspace.downgrade();
+        cx.spawn_in(window, async move |_, cx| {
+            // Commands stop at the first one that fails, as in Vim.
+            for target in targets {
+                let Some(editor) = target.activate(&workspace, cx).await? else {
+                    continue;
+                };
+                for command in &commands {
+                    run_command(command, &editor, &workspace, cx).await?;
+                }
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_notify_err(weak_workspace, window, cx);
+    }
+}
+
+/// Splits commands separated by `|`. The commands that take other commands as their argument,
+/// like `:normal` and `:g`, take the rest of the line, `|` included.
+fn split_commands(input: &str) -> Vec<String> {
+    let mut commands = Vec::new();
+    let mut command = String::new();
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\' && chars.peek() == Some(&'|') {
+            command.push(c);
+            command.extend(chars.next());
+        } else if c == '|' && !takes_rest_of_line(&command) {
+            commands.push(command.trim().to_string());
+            command.clear();
+        } else {
+            command.push(c);
+        }
+    }
+    commands.push(command.trim().to_string());
+    commands.retain(|command| !command.is_empty());
+    commands
+}
+
+fn takes_rest_of_line(command: &str) -> bool {
+    let name = command
+        .trim_start_matches(|c: char| !c.is_ascii_alphabetic())
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .unwrap_or_default();
+    let abbreviates =
+        |full_name: &str, min_len: usize| name.len() >= min_len && full_name.starts_with(name);
+    abbreviates("global", 1)
+        || abbreviates("vglobal", 1)
+        || abbreviates("normal", 4)
+        || abbreviates("bufdo", 4)
+        || abbreviates("windo", 5)
+        || abbreviates("tabdo", 4)
+        || abbreviates("cdo", 3)
+        || abbreviates("cfdo", 3)
+        || abbreviates("argdo", 5)
+}
+
+enum Target {
+    Item {
+        pane: Entity<Pane>,
+        item: Box<dyn ItemHandle>,
+    },
+    Path {
+        path: ProjectPath,
+        position: Option<Point>,
+    },
+}
+
+impl BatchTargets {
+    fn collect(self, workspace: &Workspace, cx: &App) -> Result<Vec<Target>> {
+        let item_target = |pane: &Entity<Pane>, item: &Box<dyn ItemHandle>| Target::Item {
+            pane: pane.clone(),
+            item: item.boxed_clone(),
+        };
+        let targets = match self {
+            Self::Buffers => {
+                let mut buffers = HashSet::default();
+                workspace
+                    .panes()
+                    .iter()
+                    .flat_map(|pane| pane.read(cx).items().map(move |item| (pane, item)))
+                    .filter(|(_, item)| {
+                        item.act_as::<Editor>(cx)
+                            .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+                            .is_some_and(|buffer| buffers.insert(buffer.entity_id()))
+                    })
+                    .map(|(pane, item)| item_target(pane, item))
+                    .collect()
+            }
+            Self::Windows => workspace
+                .panes()
+                .iter()
+                .filter_map(|pane| Some(item_target(pane, &pane.read(cx).active_item()?)))
+                .collect(),
+            Self::Tabs => {
+                let pane = workspace.active_pane();
+                pane.read(cx)
+                    .items()
+                    .map(|item| item_target(pane, item))
+                    .collect()
+            }
+            Self::QuickfixEntries | Self::QuickfixFiles => {
+                let editor = quickfix_list(workspace, cx)
+                    .context("No search results or diagnostics to run commands on")?;
+                let snapshot = editor.read(cx).buffer().read(cx).snapshot(cx);
+                let mut files = HashSet::default();
+                snapshot
+                    .excerpts()
+                    .filter_map(|(_, buffer, range)| {
+                        let file = buffer.file()?;
+                        let path = ProjectPath {
+                            worktree_id: file.worktree_id(cx),
+                            path: file.path().clone(),
+                        };
+                        if self == Self::QuickfixFiles {
+                            return files.insert(path.clone()).then_some(Target::Path {
+                                path,
+                                position: None,
+                            });
+                        }
+                        let position = buffer.summary_for_anchor::<Point>(&range.primary.start);
+                        Some(Target::Path {
+                            path,
+                            position: Some(position),
+                        })
+                    })
+                    .collect()
+            }
+            Self::Arguments => cx
+                .global::<VimGlobals>()
+                .argument_list
+                .iter()
+                .map(|path| Target::Path {
+                    path: path.clone(),
+                    position: None,
+                })
+                .collect(),
+        };
+        Ok(targets)
+    }
+}
+
+/// The multibuffer listing search results or diagnostics that commands like `:cdo` run on,
+/// preferring the active item.
+fn quickfix_list(workspace: &Workspace, cx: &App) -> Option<Entity<Editor>> {
+    workspace
+        .active_item(cx)
+        .into_iter()
+        .chain(workspace.items(cx).cloned())
+        .filter_map(|item| item.act_as::<Editor>(cx))
+        .find(|editor| !editor.read(cx).buffer().read(cx).is_singleton())
+}
+
+impl Target {
+    /// Activates the target, returning its editor, or `None` for targets commands don't run on.
+    async fn activate(
+        self,
+        workspace: &Entity<Workspace>,
+        cx: &mut AsyncWindowContext,
+    ) -> Result<Option<Entity<Editor>>> {
+        let (item, position) = match self {
+            Target::Item { pane, item } => {
+                let activated = pane.update_in(cx, |pane, window, cx| {
+                    let index = pane.index_for_item(item.as_ref())?;
+                    pane.activate_item(index, true, true, window, cx);
+                    Some(())
+                })?;
+                if activated.is_none() {
+                    // Earlier commands closed the item.
+                    return Ok(None);
+                }
+                (item, None)
+            }
+            Target::Path { path, position } => {
+                let item = workspace
+                    .update_in(cx, |workspace, window, cx| {
+                        workspace.open_path(path, None, true, window, cx)
+                    })?
+                    .await?;
+                (item, position)
+            }
+        };
+
+        cx.update(|window, cx| {
+            let editor = item.act_as::<Editor>(cx)?;
+            window.focus(&editor.focus_handle(cx), cx);
+            if let Some(position) = position {
+                editor.update(cx, |editor, cx| {
+                    editor.change_selections(Default::default(), window, cx, |s| {
+                        s.select_ranges([position..position])
+                    });
+                });
+            }
+            Some(editor)
+        })
+    }
+}
+
+async fn run_command(
+    command: &str,
+    editor: &Entity<Editor>,
+    workspace: &Entity<Workspace>,
+    cx: &mut AsyncWindowContext,
+) -> Result<()> {
+    let results = cx
+        .update(|_, cx| command_interceptor(command, workspace.downgrade(), cx))?
+        .await;
+    let action = results
+        .results
+        .into_iter()
+        .next()
+        .map(|result| result.action)
+        .with_context(|| format!("Not an editor command: {command}"))?;
+
+    wait_until_rendered(editor, action.as_ref(), cx).await?;
+    let pending_command = cx.update(|window, cx| {
+        let vim = editor
+            .read(cx)
+            .addon::<VimAddon>()
+            .map(|addon| addon.entity.clone());
+        if let Some(vim) = &vim {
+            vim.update(cx, |vim, _| vim.pending_command = None);
+        }
+        editor
+            .focus_handle(cx)
+            .dispatch_action(action.as_ref(), window, cx);
+        vim?.update(cx, |vim, _| vim.pending_command.take())
+    })?;
+    if let Some(pending_command) = pending_command {
+        pending_command.await;
+    }
+    Ok(())
+}
+
+/// Waits for the window to draw the editor, if it did not yet, since actions are only dispatched
+/// to the editors it drew.
+async fn wait_until_rendered(
+    editor: &Entity<Editor>,
+    action: &dyn Action,
+    cx: &mut AsyncWindowContext,
+) -> Result<()> {
+    let is_rendered =
+        |window: &Window, cx: &App| window.is_action_available_in(action, &editor.focus_handle(cx));
+    let next_frame = cx.update(|window, cx| {
+        if is_rendered(window, cx) {
+            return None;
+        }
+        let (tx, rx) = oneshot::channel();
+        window.on_next_frame(move |_, _| {
+            tx.send(()).ok();
+        });
+        window.refresh();
+        Some(rx)
+    })?;
+    if let Some(next_frame) = next_frame
+        && !cx.update(|window, cx| is_rendered(window, cx))?
+    {
+        next_frame.await.ok();
+    }
+    Ok(())
+}
+
+/// Resolves the files of an argument list in the first worktree, expanding globs.
+fn expand_arguments(files: &[String], workspace: &Workspace, cx: &App) -> Result<Vec<ProjectPath>> {
+    let worktree = workspace
+        .project()
+        .read(cx)
+        .visible_worktrees(cx)
+        .next()
+        .context("No project to find the arguments in")?;
+    let worktree = worktree.read(cx);
+    let path_style = worktree.path_style();
+
+    let mut paths = Vec::new();
+    for file in files {
+        if file.contains(['*', '?', '[']) {
+            let matcher = PathMatcher::new([file], path_style)?;
+            paths.extend(
+                worktree
+                    .files(false, 0)
+                    .filter(|entry| matcher.is_match(&entry.path))
+                    .map(|entry| ProjectPath {
+                        worktree_id: worktree.id(),
+                        path: entry.path.clone(),
+                    }),
+            );
+        } else {
+            let path = RelPath::new(Path::new(file), path_style)?;
+            paths.push(ProjectPath {
+                worktree_id: worktree.id(),
+                path: path.into_arc(),
+            });
+        }
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::VimTestContext;
+    use gpui::TestAppContext;
+    use pretty_assertions::assert_eq;
+    use search::{ProjectSearchView, project_search};
+    use std::path::PathBuf;
+    use util::path;
+    use workspace::{DeploySearch, OpenOptions};
+
+    async fn insert_files(cx: &mut VimTestContext, files: &[(&str, &str)]) {
+        let fs = cx.workspace(|workspace, _, cx| workspace.project().read(cx).fs().clone());
+        for (path, text) in files {
+            fs.as_fake()
+                .insert_file(path, text.as_bytes().to_vec())
+                .await;
+        }
+        cx.run_until_parked();
+    }
+
+    async fn open(cx: &mut VimTestContext, path: &str) {
+        cx.workspace(|workspace, window, cx| {
+            workspace.open_abs_path(PathBuf::from(path), OpenOptions::default(), window, cx)
+        })
+        .await
+        .unwrap();
+        cx.run_until_parked();
+    }
+
+    fn buffer_text(cx: &mut VimTestContext, path: &str) -> String {
+        cx.workspace(|workspace, _, cx| {
+            workspace
+                .items(cx)
+                .filter_map(|item| item.act_as::<Editor>(cx))
+                .filter_map(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+                .find(|buffer| {
+                    buffer
+                        .read(cx)
+                        .file()
+                        .and_then(|file| file.as_local())
+                        .is_some_and(|file| file.abs_path(cx) == Path::new(path))
+                })
+                .map(|buffer| buffer.read(cx).text())
+                .unwrap_or_else(|| panic!("{path} should be open"))
+        })
+    }
+
+    #[gpui::test]
+    async fn test_bufdo(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        insert_files(
+            &mut cx,
+            &[
+                (path!("/root/dir/a.txt"), "foo\nfoo foo\n"),
+                (path!("/root/dir/b.txt"), "foo\n"),
+                (path!("/root/dir/c.txt"), "foo\n"),
+            ],
+        )
+        .await;
+        open(&mut cx, path!("/root/dir/a.txt")).await;
+        open(&mut cx, path!("/root/dir/b.txt")).await;
+
+        cx.simulate_keystrokes(": bufdo space %s/foo/bar/g enter");
+        cx.run_until_parked();
+
+        assert_eq!(
+            buffer_text(&mut cx, path!("/root/dir/a.txt")),
+            "bar\nbar bar\n"
+        );
+        assert_eq!(buffer_text(&mut cx, path!("/root/dir/b.txt")), "bar\n");
+        let fs = cx.workspace(|workspace, _, cx| workspace.project().read(cx).fs().clone());
+        assert_eq!(
+            fs.load(Path::new(path!("/root/dir/c.txt"))).await.unwrap(),
+            "foo\n"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_windo(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        insert_files(
+            &mut cx,
+            &[
+                (path!("/root/dir/a.txt"), "foo\n"),
+                (path!("/root/dir/b.txt"), "foo\n"),
+                (path!("/root/dir/c.txt"), "foo\n"),
+            ],
+        )
+        .await;
+        open(&mut cx, path!("/root/dir/a.txt")).await;
+        open(&mut cx, path!("/root/dir/b.txt")).await;
+        let c = cx.workspace(|workspace, _, cx| {
+            let worktree = workspace.project().read(cx).worktrees(cx).next().unwrap();
+            ProjectPath {
+                worktree_id: worktree.read(cx).id(),
+                path: RelPath::unix("dir/c.txt").unwrap().into_arc(),
+            }
+        });
+        cx.workspace(|workspace, window, cx| workspace.split_path(c, window, cx))
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        // Only the active item of each pane is a window.
+        cx.simulate_keystrokes(": windo space s/foo/bar/ enter");
+        cx.run_until_parked();
+
+        assert_eq!(buffer_text(&mut cx, path!("/root/dir/a.txt")), "foo\n");
+        assert_eq!(buffer_text(&mut cx, path!("/root/dir/b.txt")), "bar\n");
+        assert_eq!(buffer_text(&mut cx, path!("/root/dir/c.txt")), "bar\n");
+    }
+
+    #[gpui::test]
+    async fn test_cdo_and_cfdo(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        // The matches of `a.txt` are far enough apart to be in different excerpts.
+        let a = format!("foo one\n{}foo two foo\n", "-\n".repeat(10));
+        insert_files(
+            &mut cx,
+            &[
+                (path!("/root/dir/a.txt"), &a),
+                (path!("/root/dir/b.txt"), "foo three\n"),
+            ],
+        )
+        .await;
+
+        let search_view = cx.workspace(|workspace, window, cx| {
+            ProjectSearchView::deploy_search(workspace, &DeploySearch::default(), window, cx);
+            workspace
+                .active_pane()
+                .read(cx)
+                .items()
+                .find_map(|item| item.downcast::<ProjectSearchView>())
+                .expect("Project search view should be active")
+        });
+        project_search::perform_project_search(&search_view, "foo", &mut cx);
+        search_view.update_in(&mut cx, |search_view, window, cx| {
+            search_view
+                .results_editor()
+                .focus_handle(cx)
+                .focus(window, cx)
+        });
+        cx.run_until_parked();
+
+        // Each match runs the commands on its line.
+        cx.simulate_keystrokes(": cdo space s/foo/baz/ enter");
+        cx.run_until_parked();
+
+        assert_eq!(
+            buffer_text(&mut cx, path!("/root/dir/a.txt")),
+            format!("baz one\n{}baz two foo\n", "-\n".repeat(10))
+        );
+        assert_eq!(
+            buffer_text(&mut cx, path!("/root/dir/b.txt")),
+            "baz three\n"
+        );
+
+        // Each file runs the commands once.
+        cx.simulate_keystrokes(": cfdo space %s/baz/xbaz/ enter");
+        cx.run_until_parked();
+
+        assert_eq!(
+            buffer_text(&mut cx, path!("/root/dir/a.txt")),
+            format!("xbaz one\n{}xbaz two foo\n", "-\n".repeat(10))
+        );
+        assert_eq!(
+            buffer_text(&mut cx, path!("/root/dir/b.txt")),
+            "xbaz three\n"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_args_and_argdo(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        insert_files(
+            &mut cx,
+            &[
+                (path!("/root/dir/one.txt"), "foo 1\nfoo\n"),
+                (path!("/root/dir/two.txt"), "foo 2\n"),
+                (path!("/root/dir/three.md"), "foo 3\n"),
+            ],
+        )
+        .await;
+
+        cx.simulate_keystrokes(": args space dir/*.txt enter");
+        cx.simulate_keystrokes(": args enter");
+        let status_label = cx.update_editor(|editor, _, cx| {
+            editor
+                .addon::<VimAddon>()
+                .unwrap()
+                .entity
+                .read(cx)
+                .status_label
+                .clone()
+        });
+        assert_eq!(
+            status_label.as_deref(),
+            Some(path!("dir/one.txt dir/two.txt"))
+        );
+
+        cx.simulate_keystrokes(": argdo space %s/foo/bar/g space | space update enter");
+        cx.run_until_parked();
+
+        let fs = cx.workspace(|workspace, _, cx| workspace.project().read(cx).fs().clone());
+        let load = async |path: &str| fs.load(Path::new(path)).await.unwrap();
+        assert_eq!(load(path!("/root/dir/one.txt")).await, "bar 1\nbar\n");
+        assert_eq!(load(path!("/root/dir/two.txt")).await, "bar 2\n");
+        assert_eq!(load(path!("/root/dir/three.md")).await, "foo 3\n");
+    }
+
+    #[test]
+    fn test_split_commands() {
+        assert_eq!(split_commands("%s/a/b/g | update"), ["%s/a/b/g", "update"]);
+        assert_eq!(split_commands(r"s/a\|b/c/ |w"), [r"s/a\|b/c/", "w"]);
+        assert_eq!(split_commands("norm Ax | y"), ["norm Ax | y"]);
+        assert_eq!(split_commands("g/a/d | s/b/c/"), ["g/a/d | s/b/c/"]);
+        assert_eq!(split_commands("update | | "), ["update"]);
+        assert!(split_commands(" ").is_empty());
+    }
+}
@@ -41,6 +41,7 @@ use zed_actions::{OpenDocs, RevealTarget};
 
 use crate::{
     ToggleMarksView, ToggleRegistersView, Vim, VimSettings,
+    batch::{BatchCommand, BatchTargets, VimArgs},
     motion::{EndOfDocument, Motion, MotionKind, StartOfDocument},
     normal::{
         JoinLines,
@@ -823,7 +824,7 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
         let had_range = action.range.is_some();
         let had_override = action.override_rows.is_some();
 
-        cx.spawn_in(window, async move |vim, cx| {
+        let task = cx.spawn_in(window, async move |vim, cx| {
             task.await;
             vim.update_in(cx, |vim, window, cx| {
                 if matches!(vim.mode, Mode::Insert | Mode::Replace) {
@@ -850,8 +851,8 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
                 }
             })
             .log_err();
-        })
-        .detach();
+        });
+        vim.track_command(task, cx);
     });
 
     Vim::action(editor, cx, |vim, _: &CountCommand, window, cx| {
@@ -1699,6 +1700,26 @@ fn generate_commands(_: &App) -> Vec<VimCommand> {
         .bang(workspace::CloseInactiveTabsAndPanes {
             save_intent: Some(SaveIntent::Skip),
         }),
+        VimCommand::new(("bufd", "o"), ArgumentRequired)
+            .args(|_, args| BatchCommand::parse(BatchTargets::Buffers, &args)),
+        VimCommand::new(("windo", ""), ArgumentRequired)
+            .args(|_, args| BatchCommand::parse(BatchTargets::Windows, &args)),
+        VimCommand::new(("tabd", "o"), ArgumentRequired)
+            .args(|_, args| BatchCommand::parse(BatchTargets::Tabs, &args)),
+        VimCommand::new(("cdo", ""), ArgumentRequired)
+            .args(|_, args| BatchCommand::parse(BatchTargets::QuickfixEntries, &args)),
+        VimCommand::new(("cfd", "o"), ArgumentRequired)
+            .args(|_, args| BatchCommand::parse(BatchTargets::QuickfixFiles, &args)),
+        VimCommand::new(("ar", "gs"), VimArgs { files: Vec::new() }).args(|_, args| {
+            Some(
+                VimArgs {
+                    files: args.split_whitespace().map(ToString::to_string).collect(),
+                }
+                .boxed_clone(),
+            )
+        }),
+        VimCommand::new(("argdo", ""), ArgumentRequired)
+            .args(|_, args| BatchCommand::parse(BatchTargets::Arguments, &args)),
         VimCommand::str(("cl", "ist"), "diagnostics::Deploy"),
         VimCommand::new(("cc", ""), editor::actions::Hover),
         VimCommand::new(("ll", ""), editor::actions::Hover),
@@ -2190,7 +2211,7 @@ impl OnMatchingLines {
             });
         };
 
-        vim.update_editor(cx, |_, editor, cx| {
+        let task = vim.update_editor(cx, |_, editor, cx| {
             let snapshot = editor.snapshot(window, cx);
             let mut row = range.start.0;
 
@@ -2269,8 +2290,10 @@ impl OnMatchingLines {
                     })
                     .log_err();
             })
-            .detach();
         });
+        if let Some(task) = task {
+            vim.track_command(task, cx);
+        }
     }
 }
 
@@ -2479,6 +2502,7 @@ impl ShellExec {
                     show_summary: false,
                     show_command: false,
                     show_rerun: false,
+                    problem_matchers: Vec::new(),
                 };
 
                 let task_status = workspace.spawn_in_terminal(spawn_in_terminal, window, cx);
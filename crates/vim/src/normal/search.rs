@@ -6,7 +6,7 @@ use search::{BufferSearchBar, SearchOptions, buffer_search};
 use serde::Deserialize;
 use settings::Settings;
 use std::{iter::Peekable, str::Chars};
-use util::serde::default_true;
+use util::{TryFutureExt as _, serde::default_true};
 use workspace::{notifications::NotifyResultExt, searchable::Direction};
 
 use crate::{
@@ -632,7 +632,7 @@ impl Vim {
         }
         let Some(search) = search else { return };
         let search_bar = search_bar.downgrade();
-        cx.spawn_in(window, async move |vim, cx| {
+        let task = cx.spawn_in(window, async move |vim, cx| {
             search.await?;
             search_bar.update_in(cx, |search_bar, window, cx| {
                 if replacement.flag_c {
@@ -662,8 +662,8 @@ impl Vim {
                 options.set(SearchOptions::ONE_MATCH_PER_LINE, false);
                 search_bar.set_search_options(options, cx);
             })
-        })
-        .detach_and_log_err(cx);
+        });
+        self.track_command(task.log_err(), cx);
     }
 }
 
@@ -240,6 +240,9 @@ pub struct VimGlobals {
     pub focused_vim: Option<WeakEntity<Vim>>,
 
     pub marks: HashMap<EntityId, Entity<MarksState>>,
+
+    /// The files `:argdo` runs commands on, set with `:args`.
+    pub argument_list: Vec<ProjectPath>,
 }
 
 pub struct MarksState {
@@ -3,6 +3,7 @@
 #[cfg(test)]
 mod test;
 
+mod batch;
 mod change_list;
 mod command;
 mod digraph;
@@ -521,6 +522,8 @@ pub(crate) struct Vim {
 
     last_command: Option<String>,
     running_command: Option<Task<()>>,
+    /// The asynchronous part of the last ex command, which batch commands wait for.
+    pending_command: Option<batch::PendingCommand>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -580,6 +583,7 @@ impl Vim {
 
             last_command: None,
             running_command: None,
+            pending_command: None,
 
             editor: editor.downgrade(),
             _subscriptions: vec![
@@ -952,6 +956,7 @@ impl Vim {
             helix::register(editor, cx);
             motion::register(editor, cx);
             command::register(editor, cx);
+            batch::register(editor, cx);
             replace::register(editor, cx);
             indent::register(editor, cx);
             rewrap::register(editor, cx);
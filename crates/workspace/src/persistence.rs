@@ -21,7 +21,7 @@ use db::{
 };
 use gpui::{Axis, Bounds, Task, WindowBounds, WindowId, point, size};
 use project::{
-    debugger::breakpoint_store::{BreakpointState, SourceBreakpoint},
+    debugger::breakpoint_store::{BreakpointState, FunctionBreakpoint, SourceBreakpoint},
     trusted_worktrees::{DbTrustedPaths, RemoteHostLocation},
 };
 
@@ -970,6 +970,18 @@ impl Domain for WorkspaceDb {
         sql!(
             ALTER TABLE remote_connections ADD COLUMN use_podman BOOLEAN;
         ),
+        sql!(
+            CREATE TABLE function_breakpoints (
+                workspace_id INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                condition TEXT,
+                hit_condition TEXT,
+                state INTEGER DEFAULT(0) NOT NULL,
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            );
+        ),
     ];
 
     // Allow recovering from bad migration that was initially shipped to nightly
@@ -1105,6 +1117,7 @@ impl WorkspaceDb {
             docks,
             session_id: None,
             breakpoints: self.breakpoints(workspace_id),
+            function_breakpoints: self.function_breakpoints(workspace_id),
             window_id,
             user_toolchains: self.user_toolchains(workspace_id, remote_connection_id),
         })
@@ -1195,6 +1208,7 @@ impl WorkspaceDb {
             docks,
             session_id: None,
             breakpoints: self.breakpoints(workspace_id),
+            function_breakpoints: self.function_breakpoints(workspace_id),
             window_id,
             user_toolchains: self.user_toolchains(workspace_id, remote_connection_id),
         })
@@ -1246,6 +1260,41 @@ impl WorkspaceDb {
         }
     }
 
+    fn function_breakpoints(&self, workspace_id: WorkspaceId) -> Vec<FunctionBreakpoint> {
+        let breakpoints: Result<
+            Vec<(
+                String,
+                Option<String>,
+                Option<String>,
+                BreakpointStateWrapper<'static>,
+            )>,
+        > = self
+            .select_bound(sql! {
+                SELECT name, condition, hit_condition, state
+                FROM function_breakpoints
+                WHERE workspace_id = ?
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match breakpoints {
+            Ok(breakpoints) => breakpoints
+                .into_iter()
+                .map(
+                    |(name, condition, hit_condition, state)| FunctionBreakpoint {
+                        name: name.into(),
+                        condition: condition.map(Arc::from),
+                        hit_condition: hit_condition.map(Arc::from),
+                        state: state.0.into_owned(),
+                    },
+                )
+                .collect(),
+            Err(msg) => {
+                log::error!("Function breakpoints query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
     fn user_toolchains(
         &self,
         workspace_id: WorkspaceId,
@@ -1374,6 +1423,35 @@ impl WorkspaceDb {
                     }
                 }
 
+                conn.exec_bound(
+                    sql!(
+                        DELETE FROM function_breakpoints WHERE workspace_id = ?1;
+                    )
+                )?(workspace.id).context("Clearing old function breakpoints")?;
+
+                for bp in workspace.function_breakpoints {
+                    let state = BreakpointStateWrapper::from(bp.state);
+                    match conn.exec_bound(sql!(
+                        INSERT INTO function_breakpoints (workspace_id, name, condition, hit_condition, state)
+                        VALUES (?1, ?2, ?3, ?4, ?5);))?
+
+                    ((
+                        workspace.id,
+                        bp.name.as_ref(),
+                        bp.condition,
+                        bp.hit_condition,
+                        state,
+                    )) {
+                        Ok(_) => {
+                            log::debug!("Stored function breakpoint: {}", bp.name)
+                        }
+                        Err(err) => {
+                            log::error!("{err}");
+                            continue;
+                        }
+                    }
+                }
+
                 conn.exec_bound(
                     sql!(
                         DELETE FROM user_toolchains WHERE workspace_id = ?1;
@@ -2568,6 +2646,7 @@ mod tests {
             },
             session_id: None,
             window_id: None,
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2689,6 +2768,7 @@ mod tests {
             },
             session_id: None,
             window_id: None,
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2723,6 +2803,7 @@ mod tests {
             breakpoints: collections::BTreeMap::default(),
             session_id: None,
             window_id: None,
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2737,6 +2818,54 @@ mod tests {
         assert!(empty_breakpoints.is_none());
     }
 
+    #[gpui::test]
+    async fn test_function_breakpoints() {
+        zlog::init_test();
+
+        let db = WorkspaceDb::open_test_db("test_function_breakpoints").await;
+        let id = db.next_id().await.unwrap();
+
+        let function_breakpoints = vec![
+            FunctionBreakpoint::new("main"),
+            FunctionBreakpoint {
+                name: "parse".into(),
+                condition: Some("input.is_empty()".into()),
+                hit_condition: Some(">= 2".into()),
+                state: BreakpointState::Disabled,
+            },
+        ];
+
+        let mut workspace = SerializedWorkspace {
+            id,
+            paths: PathList::new(&["/tmp"]),
+            location: SerializedWorkspaceLocation::Local,
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            breakpoints: Default::default(),
+            function_breakpoints: function_breakpoints.clone(),
+            session_id: None,
+            window_id: None,
+            user_toolchains: Default::default(),
+        };
+
+        db.save_workspace(workspace.clone()).await;
+
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(loaded.function_breakpoints, function_breakpoints);
+
+        workspace.function_breakpoints.truncate(1);
+        db.save_workspace(workspace).await;
+
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(
+            loaded.function_breakpoints,
+            [FunctionBreakpoint::new("main")]
+        );
+    }
+
     #[gpui::test]
     async fn test_next_id_stability() {
         zlog::init_test();
@@ -2821,6 +2950,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2836,6 +2966,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2943,6 +3074,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: Some(999),
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2977,6 +3109,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: Some(1),
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2992,6 +3125,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: Some(2),
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3034,6 +3168,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: Some(3),
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3072,6 +3207,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(10),
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3087,6 +3223,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(20),
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3102,6 +3239,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(30),
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3117,6 +3255,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3143,6 +3282,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(50),
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3158,6 +3298,7 @@ mod tests {
             centered_layout: false,
             session_id: Some("session-id-3".to_owned()),
             window_id: Some(60),
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3215,6 +3356,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: None,
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         }
     }
@@ -3256,6 +3398,7 @@ mod tests {
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
             window_id: Some(window_id),
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         })
         .collect::<Vec<_>>();
@@ -3368,6 +3511,7 @@ mod tests {
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
             window_id: Some(window_id),
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         })
         .collect::<Vec<_>>();
@@ -3729,6 +3873,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: None,
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3805,6 +3950,7 @@ mod tests {
                 session_id: Some("test-session".to_owned()),
                 breakpoints: Default::default(),
                 window_id: Some(*window_id),
+                function_breakpoints: Default::default(),
                 user_toolchains: Default::default(),
             })
             .await;
@@ -4146,6 +4292,7 @@ mod tests {
             session_id: Some("remove-test-session".to_owned()),
             breakpoints: Default::default(),
             window_id: Some(99),
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         })
         .await;
@@ -4228,6 +4375,7 @@ mod tests {
             session_id: Some(session_id.to_owned()),
             breakpoints: Default::default(),
             window_id: Some(window_id_val),
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         })
         .await;
@@ -4244,6 +4392,7 @@ mod tests {
             session_id: Some(session_id.to_owned()),
             breakpoints: Default::default(),
             window_id: Some(window_id_val),
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         })
         .await;
@@ -4322,6 +4471,7 @@ mod tests {
             session_id: Some("pending-removal-session".to_owned()),
             breakpoints: Default::default(),
             window_id: Some(88),
+            function_breakpoints: Default::default(),
             user_toolchains: Default::default(),
         })
         .await;
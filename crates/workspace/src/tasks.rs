@@ -1,12 +1,18 @@
-use std::process::ExitStatus;
+use std::{cell::RefCell, process::ExitStatus, rc::Rc};
 
 use anyhow::Result;
-use gpui::{AppContext, Context, Entity, Task};
+use collections::HashMap;
+use futures::{
+    FutureExt as _,
+    future::{LocalBoxFuture, Shared, join_all},
+};
+use gpui::{AppContext, AsyncWindowContext, Context, Entity, Task, WeakEntity};
 use language::Buffer;
-use project::{TaskSourceKind, WorktreeId};
+use project::{TaskGraph, TaskSourceKind, WorktreeId};
 use remote::ConnectionState;
 use task::{
-    DebugScenario, ResolvedTask, SharedTaskContext, SpawnInTerminal, TaskContext, TaskTemplate,
+    DebugScenario, DependsOrder, ResolvedTask, SharedTaskContext, SpawnInTerminal, TaskContext,
+    TaskId, TaskTemplate,
 };
 use ui::Window;
 
@@ -67,12 +73,17 @@ impl Workspace {
                     project.task_store().read(cx).task_inventory().cloned()
                 {
                     task_inventory.update(cx, |inventory, _| {
-                        inventory.task_scheduled(task_source_kind, resolved_task);
+                        inventory.task_scheduled(task_source_kind.clone(), resolved_task.clone());
                     })
                 }
             });
         }
 
+        if !resolved_task.original_task().depends_on.is_empty() {
+            self.schedule_task_graph(task_source_kind, resolved_task, window, cx);
+            return;
+        }
+
         if let Some(terminal_provider) = self.terminal_provider.as_ref() {
             let task_status = terminal_provider.spawn(spawn_in_terminal, window, cx);
 
@@ -100,6 +111,59 @@ impl Workspace {
         }
     }
 
+    /// Spawns the task after everything it `depends_on`, stopping at the first failed dependency.
+    fn schedule_task_graph(
+        &mut self,
+        task_source_kind: TaskSourceKind,
+        resolved_task: ResolvedTask,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(task_inventory) = self
+            .project
+            .read(cx)
+            .task_store()
+            .read(cx)
+            .task_inventory()
+            .cloned()
+        else {
+            return;
+        };
+        let id = resolved_task.id.clone();
+        let label = resolved_task.display_label().to_owned();
+        let graph = task_inventory
+            .read(cx)
+            .resolve_task_graph(task_source_kind, resolved_task, cx);
+
+        let task = cx.spawn_in(window, async move |workspace, cx| {
+            let graph = match graph.await {
+                Ok(graph) => graph,
+                Err(e) => {
+                    log::error!("Failed to resolve dependencies of task `{label}`: {e:#}");
+                    _ = workspace.update(cx, |workspace, cx| {
+                        let id = NotificationId::unique::<TaskGraph>();
+                        workspace.show_toast(
+                            Toast::new(id, format!("Cannot run task `{label}`: {e}")),
+                            cx,
+                        );
+                    });
+                    return;
+                }
+            };
+
+            let mut steps = Vec::new();
+            let planned_task = PlannedTask::new(&graph, &mut steps, &mut HashMap::default());
+            let run = Rc::new(TaskGraphRun {
+                workspace,
+                status: RefCell::new(TaskGraphStatus { id, label, steps }),
+            });
+            run.report(cx);
+            run_planned_task(&planned_task, &run, cx, &mut HashMap::default()).await;
+            run.finish(cx);
+        });
+        self.scheduled_tasks.push(task);
+    }
+
     pub fn start_debug_session(
         &mut self,
         scenario: DebugScenario,
@@ -134,3 +198,244 @@ impl Workspace {
         }
     }
 }
+
+/// Progress of a task that was spawned together with the tasks it `depends_on`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaskGraphStatus {
+    /// Id of the task that was scheduled.
+    pub id: TaskId,
+    pub label: String,
+    /// All tasks of the graph that spawn a command, dependencies first.
+    pub steps: Vec<TaskGraphStep>,
+}
+
+impl TaskGraphStatus {
+    pub fn is_finished(&self) -> bool {
+        self.steps.iter().all(|step| {
+            !matches!(
+                step.state,
+                TaskGraphStepState::Pending | TaskGraphStepState::Running
+            )
+        })
+    }
+
+    pub fn has_failed(&self) -> bool {
+        self.steps
+            .iter()
+            .any(|step| step.state == TaskGraphStepState::Failed)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaskGraphStep {
+    pub label: String,
+    pub state: TaskGraphStepState,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaskGraphStepState {
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+    /// Not spawned, because one of its dependencies failed.
+    Skipped,
+}
+
+struct PlannedTask {
+    task: ResolvedTask,
+    /// Index in [`TaskGraphStatus::steps`], `None` for tasks without a command of their own.
+    step: Option<usize>,
+    dependencies: Vec<Rc<PlannedTask>>,
+}
+
+impl PlannedTask {
+    /// Plans the graph, creating a single step for every distinct task in it,
+    /// no matter how many tasks depend on it.
+    fn new(
+        graph: &TaskGraph,
+        steps: &mut Vec<TaskGraphStep>,
+        planned: &mut HashMap<TaskId, Rc<PlannedTask>>,
+    ) -> Rc<Self> {
+        if let Some(planned_task) = planned.get(&graph.task.id) {
+            return planned_task.clone();
+        }
+        let dependencies = graph
+            .dependencies
+            .iter()
+            .map(|dependency| Self::new(dependency, steps, planned))
+            .collect();
+        let step = (!graph.task.original_task().is_composite()).then(|| {
+            steps.push(TaskGraphStep {
+                label: graph.task.display_label().to_owned(),
+                state: TaskGraphStepState::Pending,
+            });
+            steps.len() - 1
+        });
+        let planned_task = Rc::new(Self {
+            task: graph.task.clone(),
+            step,
+            dependencies,
+        });
+        planned.insert(graph.task.id.clone(), planned_task.clone());
+        planned_task
+    }
+}
+
+struct TaskGraphRun {
+    workspace: WeakEntity<Workspace>,
+    status: RefCell<TaskGraphStatus>,
+}
+
+impl TaskGraphRun {
+    fn set_state(&self, step: usize, state: TaskGraphStepState, cx: &mut AsyncWindowContext) {
+        self.status.borrow_mut().steps[step].state = state;
+        self.report(cx);
+    }
+
+    fn finish(&self, cx: &mut AsyncWindowContext) {
+        for step in &mut self.status.borrow_mut().steps {
+            if step.state == TaskGraphStepState::Pending {
+                step.state = TaskGraphStepState::Skipped;
+            }
+        }
+        self.report(cx);
+    }
+
+    fn report(&self, cx: &mut AsyncWindowContext) {
+        let status = self.status.borrow().clone();
+        _ = self.workspace.update_in(cx, |workspace, window, cx| {
+            if let Some(terminal_provider) = workspace.terminal_provider.as_ref() {
+                terminal_provider.task_graph_status_changed(status, window, cx);
+            }
+        });
+    }
+}
+
+/// Runs the dependencies of the task in the requested order, then the task itself.
+/// Resolves to whether the task and all of its dependencies succeeded.
+///
+/// Every task is spawned at most once: tasks that depend on the same task
+/// all wait for the outcome of its single run.
+fn run_planned_task(
+    planned_task: &Rc<PlannedTask>,
+    run: &Rc<TaskGraphRun>,
+    cx: &AsyncWindowContext,
+    outcomes: &mut HashMap<TaskId, Shared<LocalBoxFuture<'static, bool>>>,
+) -> Shared<LocalBoxFuture<'static, bool>> {
+    if let Some(outcome) = outcomes.get(&planned_task.task.id) {
+        return outcome.clone();
+    }
+
+    let dependencies = planned_task
+        .dependencies
+        .iter()
+        .map(|dependency| run_planned_task(dependency, run, cx, outcomes))
+        .collect::<Vec<_>>();
+    let id = planned_task.task.id.clone();
+    let planned_task = planned_task.clone();
+    let run = run.clone();
+    let mut cx = cx.clone();
+    let outcome = async move {
+        let dependencies_succeeded = match planned_task.task.original_task().depends_order {
+            DependsOrder::Sequence => {
+                let mut succeeded = true;
+                for dependency in dependencies {
+                    if !dependency.await {
+                        succeeded = false;
+                        break;
+                    }
+                }
+                succeeded
+            }
+            DependsOrder::Parallel => join_all(dependencies)
+                .await
+                .into_iter()
+                .all(|succeeded| succeeded),
+        };
+        if !dependencies_succeeded {
+            return false;
+        }
+        let Some(step) = planned_task.step else {
+            return true;
+        };
+
+        run.set_state(step, TaskGraphStepState::Running, &mut cx);
+        let task_status = run.workspace.update_in(&mut cx, |workspace, window, cx| {
+            workspace.spawn_in_terminal(planned_task.task.resolved.clone(), window, cx)
+        });
+        let succeeded = match task_status {
+            Ok(task_status) => match task_status.await {
+                Some(Ok(status)) => status.success(),
+                Some(Err(e)) => {
+                    log::error!("Task spawn failed: {e:#}");
+                    false
+                }
+                None => false,
+            },
+            Err(_) => false,
+        };
+        let state = if succeeded {
+            TaskGraphStepState::Succeeded
+        } else {
+            TaskGraphStepState::Failed
+        };
+        run.set_state(step, state, &mut cx);
+        succeeded
+    }
+    .boxed_local()
+    .shared();
+    outcomes.insert(id, outcome.clone());
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    fn graph(label: &str, dependencies: Vec<Arc<TaskGraph>>) -> TaskGraph {
+        let template = TaskTemplate {
+            label: label.to_owned(),
+            command: "echo".to_owned(),
+            ..TaskTemplate::default()
+        };
+        TaskGraph {
+            source_kind: TaskSourceKind::UserInput,
+            task: template
+                .resolve_task("test", &TaskContext::default())
+                .unwrap(),
+            dependencies,
+        }
+    }
+
+    #[test]
+    fn test_planning_shared_dependencies() {
+        let graph = graph(
+            "start server",
+            vec![
+                Arc::new(graph("build", Vec::new())),
+                Arc::new(graph(
+                    "migrate db",
+                    vec![Arc::new(graph("build", Vec::new()))],
+                )),
+            ],
+        );
+
+        let mut steps = Vec::new();
+        let planned_task = PlannedTask::new(&graph, &mut steps, &mut HashMap::default());
+
+        assert_eq!(
+            steps
+                .iter()
+                .map(|step| step.label.as_str())
+                .collect::<Vec<_>>(),
+            ["build", "migrate db", "start server"]
+        );
+        assert!(Rc::ptr_eq(
+            &planned_task.dependencies[0],
+            &planned_task.dependencies[1].dependencies[0]
+        ));
+    }
+}
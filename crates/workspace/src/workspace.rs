@@ -122,6 +122,7 @@ use std::{
     time::Duration,
 };
 use task::{DebugScenario, SharedTaskContext, SpawnInTerminal};
+pub use tasks::{TaskGraphStatus, TaskGraphStep, TaskGraphStepState};
 use theme::{ActiveTheme, GlobalTheme, SystemAppearance, ThemeSettings};
 pub use toolbar::{
     PaneSearchBarCallbacks, Toolbar, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView,
@@ -173,6 +174,9 @@ pub trait TerminalProvider {
         window: &mut Window,
         cx: &mut App,
     ) -> Task<Option<Result<ExitStatus>>>;
+
+    /// Reports the progress of a task spawned together with the tasks it depends on.
+    fn task_graph_status_changed(&self, status: TaskGraphStatus, window: &mut Window, cx: &mut App);
 }
 
 pub trait DebuggerProvider {
@@ -1457,7 +1461,8 @@ impl Workspace {
             window,
             |workspace, _, event, window, cx| match event {
                 BreakpointStoreEvent::BreakpointsUpdated(_, _)
-                | BreakpointStoreEvent::BreakpointsCleared(_) => {
+                | BreakpointStoreEvent::BreakpointsCleared(_)
+                | BreakpointStoreEvent::FunctionBreakpointsUpdated => {
                     workspace.serialize_workspace(window, cx);
                 }
                 BreakpointStoreEvent::SetDebugLine | BreakpointStoreEvent::ClearDebugLines => {}
@@ -6046,11 +6051,12 @@ impl Workspace {
 
         match self.workspace_location(cx) {
             WorkspaceLocation::Location(location, paths) => {
-                let breakpoints = self.project.update(cx, |project, cx| {
-                    project
-                        .breakpoint_store()
-                        .read(cx)
-                        .all_source_breakpoints(cx)
+                let (breakpoints, function_breakpoints) = self.project.update(cx, |project, cx| {
+                    let breakpoint_store = project.breakpoint_store().read(cx);
+                    (
+                        breakpoint_store.all_source_breakpoints(cx),
+                        breakpoint_store.function_breakpoints().to_vec(),
+                    )
                 });
                 let user_toolchains = self
                     .project
@@ -6073,6 +6079,7 @@ impl Workspace {
                     centered_layout: self.centered_layout,
                     session_id: self.session_id.clone(),
                     breakpoints,
+                    function_breakpoints,
                     window_id: Some(window.window_handle().window_id().as_u64()),
                     user_toolchains,
                 };
@@ -6282,6 +6289,10 @@ impl Workspace {
                     project
                         .breakpoint_store()
                         .update(cx, |breakpoint_store, cx| {
+                            breakpoint_store.with_serialized_function_breakpoints(
+                                serialized_workspace.function_breakpoints,
+                                cx,
+                            );
                             breakpoint_store
                                 .with_serialized_breakpoints(serialized_workspace.breakpoints, cx)
                         })
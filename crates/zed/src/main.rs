@@ -196,6 +196,22 @@ fn main() {
         return;
     }
 
+    // `zed --terminal-host` Makes zed operate as the process owning persistent terminal sessions
+    #[cfg(unix)]
+    if let Some(socket) = &args.terminal_host {
+        if let Err(err) = terminal_host::run_host(socket) {
+            eprintln!("Error: {err:#}");
+            process::exit(1);
+        }
+        return;
+    }
+
+    // `zed --attach-terminal` Makes zed attach the terminal it runs in to a persistent session
+    #[cfg(unix)]
+    if let Some(attach_args) = &args.attach_terminal {
+        process::exit(terminal_host::attach(attach_args));
+    }
+
     // `zed --nc` Makes zed operate in nc/netcat mode for use with MCP
     if let Some(socket) = &args.nc {
         match nc::main(socket) {
@@ -655,6 +671,8 @@ fn main() {
         tab_switcher::init(cx);
         outline::init(cx);
         project_symbols::init(cx);
+        hierarchy_view::init(cx);
+        test_explorer::init(cx);
         project_panel::init(cx);
         outline_panel::init(cx);
         tasks_ui::init(cx);
@@ -1570,6 +1588,18 @@ struct Args {
     #[arg(long, hide = true)]
     crash_handler: Option<PathBuf>,
 
+    /// Used for persistent terminal sessions, by having Zed run a separate
+    /// process that owns the terminals' shells, listening on a Unix socket.
+    #[arg(long, hide = true)]
+    #[cfg(unix)]
+    terminal_host: Option<PathBuf>,
+
+    /// Used for persistent terminal sessions, to attach the terminal Zed runs in
+    /// to the session with the given id, starting it with the given command if needed.
+    #[arg(long, hide = true, num_args = 1.., allow_hyphen_values = true)]
+    #[cfg(unix)]
+    attach_terminal: Option<Vec<String>>,
+
     /// Run zed in the foreground, only used on Windows, to match the behavior on macOS.
     #[arg(long)]
     #[cfg(target_os = "windows")]
@@ -632,6 +632,8 @@ fn initialize_panels(
             workspace_handle.clone(),
             cx.clone(),
         );
+        let test_explorer_panel =
+            test_explorer::TestExplorerPanel::load(workspace_handle.clone(), cx.clone());
         let debug_panel = DebugPanel::load(workspace_handle.clone(), cx);
 
         async fn add_panel_when_ready(
@@ -657,6 +659,7 @@ fn initialize_panels(
             add_panel_when_ready(channels_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(notification_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(debug_panel, workspace_handle.clone(), cx.clone()),
+            add_panel_when_ready(test_explorer_panel, workspace_handle.clone(), cx.clone()),
             initialize_agent_panel(workspace_handle, prompt_builder, cx.clone()).map(|r| r.log_err()),
         );
 
@@ -4800,6 +4803,7 @@ mod tests {
                 "bedrock",
                 "branches",
                 "buffer_search",
+                "call_hierarchy",
                 "channel_modal",
                 "cli",
                 "client",
@@ -4824,10 +4828,12 @@ mod tests {
                 "git_panel",
                 "git_picker",
                 "go_to_line",
+                "hierarchy_view",
                 "highlights_tree_view",
                 "icon_theme_selector",
                 "image_viewer",
                 "inline_assistant",
+                "interactive_rebase",
                 "journal",
                 "keymap_editor",
                 "keystroke_input",
@@ -4837,6 +4843,7 @@ mod tests {
                 "lsp_tool",
                 "markdown",
                 "menu",
+                "merge_editor",
                 "multi_workspace",
                 "new_process_modal",
                 "notebook",
@@ -4867,9 +4874,11 @@ mod tests {
                 "task",
                 "terminal",
                 "terminal_panel",
+                "test_explorer",
                 "theme_selector",
                 "toast",
                 "toolchain",
+                "type_hierarchy",
                 "variable_list",
                 "vim",
                 "window",
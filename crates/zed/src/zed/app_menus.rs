@@ -282,6 +282,8 @@ pub fn app_menus(cx: &mut App) -> Vec<Menu> {
                 MenuItem::action("Toggle Breakpoint", editor::actions::ToggleBreakpoint),
                 MenuItem::action("Edit Breakpoint", editor::actions::EditLogBreakpoint),
                 MenuItem::action("Clear All Breakpoints", debugger_ui::ClearAllBreakpoints),
+                MenuItem::action("Export Breakpoints...", debugger_ui::ExportBreakpoints),
+                MenuItem::action("Import Breakpoints...", debugger_ui::ImportBreakpoints),
             ],
         },
         Menu {